@@ -0,0 +1,6 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+pragma solidity 0.8.24;
+
+interface IMessageReceiver {
+    function onMessage(string calldata sender, bytes calldata data) external;
+}
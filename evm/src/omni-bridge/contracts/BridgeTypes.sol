@@ -12,6 +12,20 @@ library BridgeTypes {
         string feeRecipient;
     }
 
+    struct DestinationCall {
+        address target;
+        bytes32 calldataHash;
+        uint64 gasLimit;
+    }
+
+    struct MessagePayload {
+        uint64 nonce;
+        string sender;
+        bytes32 payloadHash;
+        uint64 gasLimit;
+        uint128 nativeFee;
+    }
+
     struct MetadataPayload {
         string token;
         string name;
@@ -39,6 +53,25 @@ library BridgeTypes {
         string feeRecipient
     );
 
+    event DestinationCallExecuted(
+        uint64 indexed destinationNonce,
+        address indexed target,
+        bool success
+    );
+
+    event Message(
+        uint64 indexed originNonce,
+        address sender,
+        string recipient,
+        bytes32 payloadHash
+    );
+
+    event MessageReceived(
+        uint64 indexed nonce,
+        address indexed target,
+        string sender
+    );
+
     event DeployToken(
         address indexed tokenAddress,
         string token,
@@ -66,6 +99,11 @@ library BridgeTypes {
     enum PayloadType {
         TransferMessage,
         Metadata,
-        ClaimNativeFee
+        ClaimNativeFee,
+        TransferCancellation,
+        UnclaimedDepositClaim,
+        IncomingTransferNotice,
+        TransferMessageWithCall,
+        Message
     }
 }
@@ -13,6 +13,7 @@ import {IERC1155} from "@openzeppelin/contracts/token/ERC1155/IERC1155.sol";
 import {IERC1155Receiver} from "@openzeppelin/contracts/token/ERC1155/IERC1155Receiver.sol";
 import {IERC165} from "@openzeppelin/contracts/utils/introspection/IERC165.sol";
 import {ICustomMinter} from "../../common/ICustomMinter.sol";
+import {IMessageReceiver} from "../../common/IMessageReceiver.sol";
 
 import "./BridgeToken.sol";
 import "./SelectivePausableUpgradable.sol";
@@ -46,6 +47,9 @@ contract OmniBridge is
     mapping(address => address) public customMinters;
     mapping(address => MultiTokenInfo) public multiTokens;
 
+    uint64 public currentMessageNonce;
+    mapping(address => mapping(uint64 => bool)) public completedMessages;
+
     bytes32 public constant PAUSABLE_ADMIN_ROLE =
         keccak256("PAUSABLE_ADMIN_ROLE");
     uint256 constant UNPAUSED_ALL = 0;
@@ -53,6 +57,8 @@ contract OmniBridge is
     uint256 constant PAUSED_FIN_TRANSFER = 1 << 1;
 
     error InvalidSignature();
+    error InvalidCalldata();
+    error InsufficientCallGas();
     error NonceAlreadyUsed(uint64 nonce);
     error InvalidFee();
     error InvalidValue();
@@ -274,35 +280,185 @@ contract OmniBridge is
         bytes calldata signatureData,
         BridgeTypes.TransferMessagePayload calldata payload
     ) external payable whenNotPaused(PAUSED_FIN_TRANSFER) {
-        if (completedTransfers[payload.destinationNonce]) {
-            revert NonceAlreadyUsed(payload.destinationNonce);
+        useTransferNonce(payload.destinationNonce);
+        verifySignature(
+            encodeTransferMessage(
+                BridgeTypes.PayloadType.TransferMessage,
+                payload
+            ),
+            signatureData
+        );
+
+        deliverTransfer(payload);
+    }
+
+    // The tokens are sent to the recipient, which is usually the target itself, before the
+    // call. A failed call doesn't revert the transfer, so the tokens can't get stuck.
+    function finTransferWithCall(
+        bytes calldata signatureData,
+        BridgeTypes.TransferMessagePayload calldata payload,
+        BridgeTypes.DestinationCall calldata destinationCall,
+        bytes calldata callData
+    ) external payable whenNotPaused(PAUSED_FIN_TRANSFER) {
+        if (keccak256(callData) != destinationCall.calldataHash) {
+            revert InvalidCalldata();
         }
+        useTransferNonce(payload.destinationNonce);
+        verifySignature(
+            bytes.concat(
+                encodeTransferMessage(
+                    BridgeTypes.PayloadType.TransferMessageWithCall,
+                    payload
+                ),
+                Borsh.encodeAddress(destinationCall.target),
+                destinationCall.calldataHash,
+                Borsh.encodeUint64(destinationCall.gasLimit)
+            ),
+            signatureData
+        );
 
-        completedTransfers[payload.destinationNonce] = true;
+        deliverTransfer(payload);
 
-        bytes memory borshEncoded = bytes.concat(
-            bytes1(uint8(BridgeTypes.PayloadType.TransferMessage)),
-            Borsh.encodeUint64(payload.destinationNonce),
-            bytes1(payload.originChain),
-            Borsh.encodeUint64(payload.originNonce),
-            bytes1(omniBridgeChainId),
-            Borsh.encodeAddress(payload.tokenAddress),
-            Borsh.encodeUint128(payload.amount),
-            bytes1(omniBridgeChainId),
-            Borsh.encodeAddress(payload.recipient),
-            bytes(payload.feeRecipient).length == 0 // None or Some(String) in rust
-                ? bytes("\x00")
-                : bytes.concat(
-                    bytes("\x01"),
-                    Borsh.encodeString(payload.feeRecipient)
-                )
+        // Only 63/64 of the remaining gas is forwarded, so the relayer can't make the call
+        // fail by sending too little gas
+        if ((gasleft() * 63) / 64 < destinationCall.gasLimit) {
+            revert InsufficientCallGas();
+        }
+        // slither-disable-next-line low-level-calls
+        (bool success, ) = destinationCall.target.call{
+            gas: destinationCall.gasLimit
+        }(callData);
+
+        emit BridgeTypes.DestinationCallExecuted(
+            payload.destinationNonce,
+            destinationCall.target,
+            success
+        );
+    }
+
+    // Sends a message to an app on NEAR without moving tokens. Only the hash of the payload
+    // is proven, the relayer delivers the payload itself.
+    function sendMessage(
+        string calldata recipient,
+        bytes32 payloadHash
+    ) external payable whenNotPaused(PAUSED_INIT_TRANSFER) {
+        currentMessageNonce += 1;
+
+        sendMessageExtension(
+            msg.sender,
+            currentMessageNonce,
+            recipient,
+            payloadHash,
+            msg.value
+        );
+
+        emit BridgeTypes.Message(
+            currentMessageNonce,
+            msg.sender,
+            recipient,
+            payloadHash
+        );
+    }
+
+    // Delivers a message signed on NEAR to the target app, which has to check the NEAR sender.
+    // The nonce is used per target, so a delivery to another target doesn't block the message.
+    // A failed call reverts, so the message can be delivered again.
+    function receiveMessage(
+        bytes calldata signatureData,
+        BridgeTypes.MessagePayload calldata payload,
+        address target,
+        bytes calldata data
+    ) external whenNotPaused(PAUSED_FIN_TRANSFER) {
+        if (keccak256(data) != payload.payloadHash) {
+            revert InvalidCalldata();
+        }
+        if (completedMessages[target][payload.nonce]) {
+            revert NonceAlreadyUsed(payload.nonce);
+        }
+        completedMessages[target][payload.nonce] = true;
+
+        verifySignature(
+            bytes.concat(
+                bytes1(uint8(BridgeTypes.PayloadType.Message)),
+                Borsh.encodeUint64(payload.nonce),
+                Borsh.encodeString(payload.sender),
+                bytes1(omniBridgeChainId),
+                payload.payloadHash,
+                Borsh.encodeUint64(payload.gasLimit),
+                Borsh.encodeUint128(payload.nativeFee)
+            ),
+            signatureData
+        );
+
+        if ((gasleft() * 63) / 64 < payload.gasLimit) {
+            revert InsufficientCallGas();
+        }
+        IMessageReceiver(target).onMessage{gas: payload.gasLimit}(
+            payload.sender,
+            data
         );
+
+        emit BridgeTypes.MessageReceived(payload.nonce, target, payload.sender);
+    }
+
+    function sendMessageExtension(
+        address /*sender*/,
+        uint64 /*originNonce*/,
+        string calldata /*recipient*/,
+        bytes32 /*payloadHash*/,
+        uint256 value
+    ) internal virtual {
+        if (value != 0) {
+            revert InvalidValue();
+        }
+    }
+
+    function useTransferNonce(uint64 destinationNonce) internal {
+        if (completedTransfers[destinationNonce]) {
+            revert NonceAlreadyUsed(destinationNonce);
+        }
+
+        completedTransfers[destinationNonce] = true;
+    }
+
+    function encodeTransferMessage(
+        BridgeTypes.PayloadType payloadType,
+        BridgeTypes.TransferMessagePayload calldata payload
+    ) internal view returns (bytes memory) {
+        return
+            bytes.concat(
+                bytes1(uint8(payloadType)),
+                Borsh.encodeUint64(payload.destinationNonce),
+                bytes1(payload.originChain),
+                Borsh.encodeUint64(payload.originNonce),
+                bytes1(omniBridgeChainId),
+                Borsh.encodeAddress(payload.tokenAddress),
+                Borsh.encodeUint128(payload.amount),
+                bytes1(omniBridgeChainId),
+                Borsh.encodeAddress(payload.recipient),
+                bytes(payload.feeRecipient).length == 0 // None or Some(String) in rust
+                    ? bytes("\x00")
+                    : bytes.concat(
+                        bytes("\x01"),
+                        Borsh.encodeString(payload.feeRecipient)
+                    )
+            );
+    }
+
+    function verifySignature(
+        bytes memory borshEncoded,
+        bytes calldata signatureData
+    ) internal view {
         bytes32 hashed = keccak256(borshEncoded);
 
         if (ECDSA.recover(hashed, signatureData) != nearBridgeDerivedAddress) {
             revert InvalidSignature();
         }
+    }
 
+    function deliverTransfer(
+        BridgeTypes.TransferMessagePayload calldata payload
+    ) internal {
         MultiTokenInfo memory multiToken = multiTokens[payload.tokenAddress];
 
         if (payload.tokenAddress == address(0)) {
@@ -576,5 +732,5 @@ contract OmniBridge is
         address newImplementation
     ) internal override onlyRole(DEFAULT_ADMIN_ROLE) {}
 
-    uint256[49] private __gap;
+    uint256[47] private __gap;
 }
@@ -19,7 +19,10 @@ enum MessageType {
     InitTransfer,
     FinTransfer,
     DeployToken,
-    LogMetadata
+    LogMetadata,
+    // Published by NEAR only, kept so the types match the `ProofKind` of the prover
+    GovernanceAction,
+    Message
 }
 
 // slither-disable-start unused-return
@@ -149,6 +152,32 @@ contract OmniBridgeWormhole is OmniBridge {
         wormholeNonce++;
     }
 
+    function sendMessageExtension(
+        address sender,
+        uint64 originNonce,
+        string calldata recipient,
+        bytes32 payloadHash,
+        uint256 value
+    ) internal override {
+        bytes memory payload = bytes.concat(
+            bytes1(uint8(MessageType.Message)),
+            bytes1(omniBridgeChainId),
+            Borsh.encodeUint64(originNonce),
+            bytes1(omniBridgeChainId),
+            Borsh.encodeAddress(sender),
+            Borsh.encodeString(recipient),
+            payloadHash
+        );
+        // slither-disable-next-line reentrancy-eth
+        _wormhole.publishMessage{value: value}(
+            wormholeNonce,
+            payload,
+            _consistencyLevel
+        );
+
+        wormholeNonce++;
+    }
+
     function setWormholeAddress(
         address wormholeAddress,
         uint8 consistencyLevel
@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+pragma solidity ^0.8.24;
+
+import {IMessageReceiver} from "../../../common/IMessageReceiver.sol";
+
+contract TestMessageReceiver is IMessageReceiver {
+    string public lastSender;
+    bytes public lastData;
+
+    function onMessage(
+        string calldata sender,
+        bytes calldata data
+    ) external override {
+        lastSender = sender;
+        lastData = data;
+    }
+}
@@ -3,7 +3,13 @@ import type { BridgeToken, OmniBridge, TestBridgeToken } from "../typechain-type
 import type { HardhatEthersSigner } from "@nomicfoundation/hardhat-ethers/signers"
 import { expect } from "chai"
 import { ethers, upgrades } from "hardhat"
-import { depositSignature, metadataSignature, testWallet } from "./helpers/signatures"
+import {
+  depositSignature,
+  depositWithCallSignature,
+  messageSignature,
+  metadataSignature,
+  testWallet,
+} from "./helpers/signatures"
 
 const PauseMode = {
   UnpausedAll: 0,
@@ -124,6 +130,90 @@ describe("BridgeToken", () => {
     )
   })
 
+  it("can fin transfer with a destination call", async () => {
+    const { token } = await createToken(wrappedNearId)
+    const tokenProxyAddress = await token.getAddress()
+    const callData = token.interface.encodeFunctionData("balanceOf", [user1.address])
+
+    const { signature, payload, destinationCall } = depositWithCallSignature(
+      tokenProxyAddress,
+      user1.address,
+      tokenProxyAddress,
+      callData,
+    )
+
+    await expect(OmniBridge.finTransferWithCall(signature, payload, destinationCall, callData))
+      .to.emit(OmniBridge, "DestinationCallExecuted")
+      .withArgs(payload.destinationNonce, tokenProxyAddress, true)
+
+    expect((await token.balanceOf(payload.recipient)).toString()).to.be.equal(
+      payload.amount.toString(),
+    )
+  })
+
+  it("can't fin transfer with a calldata not matching the signed hash", async () => {
+    const { token } = await createToken(wrappedNearId)
+    const tokenProxyAddress = await token.getAddress()
+    const callData = token.interface.encodeFunctionData("balanceOf", [user1.address])
+
+    const { signature, payload, destinationCall } = depositWithCallSignature(
+      tokenProxyAddress,
+      user1.address,
+      tokenProxyAddress,
+      callData,
+    )
+    const otherCallData = token.interface.encodeFunctionData("balanceOf", [user2.address])
+
+    await expect(
+      OmniBridge.finTransferWithCall(signature, payload, destinationCall, otherCallData),
+    ).to.be.revertedWithCustomError(OmniBridge, "InvalidCalldata")
+  })
+
+  it("can't fin transfer with a call signed as a plain transfer", async () => {
+    const { token } = await createToken(wrappedNearId)
+    const tokenProxyAddress = await token.getAddress()
+    const callData = token.interface.encodeFunctionData("balanceOf", [user1.address])
+
+    const { payload, destinationCall } = depositWithCallSignature(
+      tokenProxyAddress,
+      user1.address,
+      tokenProxyAddress,
+      callData,
+    )
+    const { signature } = depositSignature(tokenProxyAddress, user1.address)
+
+    await expect(
+      OmniBridge.finTransferWithCall(signature, payload, destinationCall, callData),
+    ).to.be.revertedWithCustomError(OmniBridge, "InvalidSignature")
+  })
+
+  it("can send a message", async () => {
+    const payloadHash = ethers.keccak256("0x1234")
+
+    await expect(OmniBridge.connect(user1).sendMessage("app.testnet", payloadHash))
+      .to.emit(OmniBridge, "Message")
+      .withArgs(1, user1.address, "app.testnet", payloadHash)
+    expect(await OmniBridge.currentMessageNonce()).to.be.equal(1)
+  })
+
+  it("can receive a message once per target", async () => {
+    const TestMessageReceiver_factory = await ethers.getContractFactory("TestMessageReceiver")
+    const receiver = await TestMessageReceiver_factory.deploy()
+    const receiverAddress = await receiver.getAddress()
+    const data = "0x1234"
+
+    const { signature, payload } = messageSignature(data)
+    await expect(OmniBridge.receiveMessage(signature, payload, receiverAddress, data))
+      .to.emit(OmniBridge, "MessageReceived")
+      .withArgs(payload.nonce, receiverAddress, payload.sender)
+    expect(await receiver.lastSender()).to.be.equal(payload.sender)
+    expect(await receiver.lastData()).to.be.equal(data)
+
+    await expect(
+      OmniBridge.receiveMessage(signature, payload, receiverAddress, data),
+    ).to.be.revertedWithCustomError(OmniBridge, "NonceAlreadyUsed")
+  })
+
   it("can't fin transfer if the contract is paused", async () => {
     await createToken(wrappedNearId)
     const tokenProxyAddress = await OmniBridge.nearToEthToken(wrappedNearId)
@@ -71,6 +71,61 @@ class TransferMessage {
   }
 }
 
+class TransferWithCallMessage {
+  static schema = {
+    struct: {
+      ...TransferMessage.schema.struct,
+      callTarget: { array: { type: "u8", len: 20 } },
+      calldataHash: { array: { type: "u8", len: 32 } },
+      callGasLimit: "u64",
+    },
+  }
+
+  constructor(
+    public transfer: TransferMessage,
+    public callTarget: Uint8Array,
+    public calldataHash: Uint8Array,
+    public callGasLimit: bigint,
+  ) {}
+
+  static serialize(msg: TransferWithCallMessage): Uint8Array {
+    return borsh.serialize(TransferWithCallMessage.schema, {
+      ...msg.transfer,
+      callTarget: msg.callTarget,
+      calldataHash: msg.calldataHash,
+      callGasLimit: msg.callGasLimit,
+    })
+  }
+}
+
+class MessageMessage {
+  static schema = {
+    struct: {
+      payloadType: "u8",
+      nonce: "u64",
+      sender: "string",
+      destinationChain: "u8",
+      payloadHash: { array: { type: "u8", len: 32 } },
+      gasLimit: "u64",
+      nativeFee: "u128",
+    },
+  }
+
+  constructor(
+    public payloadType: number,
+    public nonce: bigint,
+    public sender: string,
+    public destinationChain: number,
+    public payloadHash: Uint8Array,
+    public gasLimit: bigint,
+    public nativeFee: bigint,
+  ) {}
+
+  static serialize(msg: MessageMessage): Uint8Array {
+    return borsh.serialize(MessageMessage.schema, msg)
+  }
+}
+
 // Utility Functions
 function createMessageHash(borshEncoded: Uint8Array): string {
   return ethers.keccak256(borshEncoded)
@@ -142,3 +197,77 @@ export function depositSignature(
 
   return { payload, signature }
 }
+
+export function depositWithCallSignature(
+  tokenAddress: string,
+  recipient: string,
+  callTarget: string,
+  callData: string,
+): SignatureData<BridgeTypes.TransferMessagePayloadStruct> & {
+  destinationCall: BridgeTypes.DestinationCallStruct
+} {
+  const payload: BridgeTypes.TransferMessagePayloadStruct = {
+    destinationNonce: 1,
+    tokenAddress,
+    amount: 1,
+    recipient,
+    feeRecipient: "",
+    originChain: 1,
+    originNonce: 1,
+  }
+  const destinationCall: BridgeTypes.DestinationCallStruct = {
+    target: callTarget,
+    calldataHash: ethers.keccak256(callData),
+    gasLimit: 100000,
+  }
+
+  const message = new TransferWithCallMessage(
+    new TransferMessage(
+      6,
+      BigInt(payload.destinationNonce),
+      payload.originChain,
+      BigInt(payload.originNonce),
+      0,
+      ethers.getBytes(tokenAddress),
+      BigInt(payload.amount),
+      0,
+      ethers.getBytes(recipient),
+      null,
+    ),
+    ethers.getBytes(callTarget),
+    ethers.getBytes(destinationCall.calldataHash as string),
+    BigInt(destinationCall.gasLimit),
+  )
+
+  const borshEncoded = TransferWithCallMessage.serialize(message)
+  const messageHash = createMessageHash(borshEncoded)
+  const signature = signMessage(messageHash)
+
+  return { payload, destinationCall, signature }
+}
+
+export function messageSignature(data: string): SignatureData<BridgeTypes.MessagePayloadStruct> {
+  const payload: BridgeTypes.MessagePayloadStruct = {
+    nonce: 1,
+    sender: "app.testnet",
+    payloadHash: ethers.keccak256(data),
+    gasLimit: 100000,
+    nativeFee: 0,
+  }
+
+  const message = new MessageMessage(
+    7,
+    BigInt(payload.nonce),
+    payload.sender as string,
+    0,
+    ethers.getBytes(payload.payloadHash as string),
+    BigInt(payload.gasLimit),
+    BigInt(payload.nativeFee),
+  )
+
+  const borshEncoded = MessageMessage.serialize(message)
+  const messageHash = createMessageHash(borshEncoded)
+  const signature = signMessage(messageHash)
+
+  return { payload, signature }
+}
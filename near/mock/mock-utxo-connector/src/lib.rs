@@ -1,6 +1,8 @@
 use near_sdk::json_types::U128;
 use near_sdk::serde_json;
-use near_sdk::{ext_contract, near, AccountId, Gas, NearToken, PanicOnDefault, Promise};
+use near_sdk::{
+    ext_contract, near, AccountId, Gas, NearToken, PanicOnDefault, Promise, PromiseOrValue,
+};
 use omni_types::{BridgeOnTransferMsg, UtxoFinTransferMsg};
 
 const FT_TRANSFER_CALL_GAS: Gas = Gas::from_tgas(210);
@@ -22,6 +24,7 @@ pub trait ExtToken {
 pub struct MockUtxoConnector {
     pub bridge_account: AccountId,
     pub token_account: AccountId,
+    pub reject_withdrawals: bool,
 }
 
 #[allow(clippy::needless_pass_by_value)]
@@ -32,9 +35,15 @@ impl MockUtxoConnector {
         Self {
             bridge_account,
             token_account,
+            reject_withdrawals: false,
         }
     }
 
+    // Makes the connector refund the tokens of the next withdrawals, as on a connector-side failure
+    pub fn set_reject_withdrawals(&mut self, reject_withdrawals: bool) {
+        self.reject_withdrawals = reject_withdrawals;
+    }
+
     #[allow(clippy::missing_panics_doc)]
     pub fn verify_deposit(&mut self, amount: U128, msg: UtxoFinTransferMsg) -> Promise {
         ext_token::ext(self.token_account.clone())
@@ -47,4 +56,19 @@ impl MockUtxoConnector {
                 serde_json::to_string(&BridgeOnTransferMsg::UtxoFinTransfer(msg)).unwrap(),
             )
     }
+
+    // Accepts the tokens of the withdrawals submitted by the bridge, unless they are rejected
+    pub fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        let _ = (sender_id, msg);
+        if self.reject_withdrawals {
+            PromiseOrValue::Value(amount)
+        } else {
+            PromiseOrValue::Value(U128(0))
+        }
+    }
 }
@@ -0,0 +1,81 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::U128;
+use near_sdk::{env, near, AccountId, Gas};
+
+use crate::external::ext_auditor;
+use crate::locked::LockedState;
+use crate::{Contract, ContractExt, Role};
+
+const AUDITOR_NOTIFICATION_GAS: Gas = Gas::from_tgas(5);
+// Gas left for the rest of the action after the notification is scheduled
+const AUDITOR_NOTIFICATION_GAS_RESERVE: Gas = Gas::from_tgas(10);
+
+#[near(serializers=[json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountingEntry {
+    Lock { state: LockedState },
+    Release { state: LockedState },
+    Move { from: LockedState, to: LockedState },
+}
+
+/// Change of the funds held by the locker, sent to the auditor contract.
+/// The notifications are not retried, so the auditor detects the lost records by gaps in `seq`.
+#[near(serializers=[json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountingRecord {
+    pub seq: u64,
+    pub token_id: AccountId,
+    pub amount: U128,
+    pub entry: AccountingEntry,
+}
+
+#[near]
+impl Contract {
+    /// Sets the contract notified about every change of the locked funds. `None` disables it.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_auditor(&mut self, auditor: Option<AccountId>) {
+        self.auditor = auditor;
+    }
+
+    pub fn get_auditor(&self) -> Option<AccountId> {
+        self.auditor.clone()
+    }
+
+    pub fn get_last_accounting_seq(&self) -> u64 {
+        self.last_accounting_seq
+    }
+}
+
+impl Contract {
+    pub(crate) fn notify_auditor(
+        &mut self,
+        token_id: &AccountId,
+        amount: u128,
+        entry: AccountingEntry,
+    ) {
+        let Some(auditor) = self.auditor.clone() else {
+            return;
+        };
+        if amount == 0 {
+            return;
+        }
+
+        self.last_accounting_seq += 1;
+        // The notification is skipped rather than failing the action in the callbacks with little gas
+        let remaining_gas = env::prepaid_gas().saturating_sub(env::used_gas());
+        if remaining_gas < AUDITOR_NOTIFICATION_GAS.saturating_add(AUDITOR_NOTIFICATION_GAS_RESERVE)
+        {
+            return;
+        }
+        ext_auditor::ext(auditor)
+            .with_static_gas(AUDITOR_NOTIFICATION_GAS)
+            .with_unused_gas_weight(0)
+            .on_accounting_record(AccountingRecord {
+                seq: self.last_accounting_seq,
+                token_id: token_id.clone(),
+                amount: U128(amount),
+                entry,
+            })
+            .detach();
+    }
+}
@@ -0,0 +1,141 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::{near, require};
+use omni_types::ChainKind;
+
+use crate::routes::BUILTIN_CHAINS;
+use crate::{Contract, ContractExt, Role};
+
+/// Steps of the configuration of a fresh deployment, in the order they are completed.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BootstrapStage {
+    Deployed,
+    RolesSet,
+    ChainsConfigured,
+    TokensBound,
+    ProversConfigured,
+    Activated,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootstrapProgress {
+    pub stage: BootstrapStage,
+    // Set by the first token binding, since the bound tokens can't be enumerated
+    pub tokens_bound: bool,
+}
+
+impl Default for BootstrapProgress {
+    fn default() -> Self {
+        Self {
+            stage: BootstrapStage::Deployed,
+            tokens_bound: false,
+        }
+    }
+}
+
+#[near]
+impl Contract {
+    pub fn get_bootstrap_stage(&self) -> BootstrapStage {
+        self.bootstrap
+            .as_ref()
+            .map_or(BootstrapStage::Activated, |progress| progress.stage)
+    }
+
+    /// Completes the next step of the checklist if its requirements are met. The steps are:
+    /// a pause manager is granted, a factory or a UTXO connector is added, a token is bound,
+    /// and every chain with a factory has a prover.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn advance_bootstrap_stage(&mut self) -> BootstrapStage {
+        require!(self.bootstrap.is_some(), "ERR_BRIDGE_ALREADY_ACTIVATED");
+        let stage = self.next_bootstrap_stage();
+        require!(stage != BootstrapStage::Activated, "ERR_USE_ACTIVATE");
+        self.set_bootstrap_stage(stage);
+        stage
+    }
+
+    /// Completes the remaining steps of the checklist and opens the transfer methods.
+    /// The requirements of the completed steps are checked again, since the configuration
+    /// could have been changed after they were completed.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn activate(&mut self) {
+        require!(self.bootstrap.is_some(), "ERR_BRIDGE_ALREADY_ACTIVATED");
+        self.set_bootstrap_stage(BootstrapStage::Deployed);
+        loop {
+            let stage = self.next_bootstrap_stage();
+            if stage == BootstrapStage::Activated {
+                break;
+            }
+            self.set_bootstrap_stage(stage);
+        }
+        self.bootstrap = None;
+    }
+}
+
+impl Contract {
+    pub(crate) fn require_bridge_activated(&self) {
+        require!(self.bootstrap.is_none(), "ERR_BRIDGE_NOT_ACTIVATED");
+    }
+
+    pub(crate) fn record_bootstrap_token_bound(&mut self) {
+        if let Some(progress) = self.bootstrap.as_mut() {
+            progress.tokens_bound = true;
+        }
+    }
+
+    fn set_bootstrap_stage(&mut self, stage: BootstrapStage) {
+        if let Some(progress) = self.bootstrap.as_mut() {
+            progress.stage = stage;
+        }
+    }
+
+    // Returns the stage after the current one, panicking if its requirements are not met.
+    fn next_bootstrap_stage(&self) -> BootstrapStage {
+        let Some(progress) = self.bootstrap.as_ref() else {
+            return BootstrapStage::Activated;
+        };
+        match progress.stage {
+            BootstrapStage::Deployed => {
+                require!(
+                    !self
+                        .acl_get_grantees(Role::PauseManager.into(), 0, 1)
+                        .is_empty(),
+                    "ERR_BOOTSTRAP_PAUSE_MANAGER_NOT_SET"
+                );
+                BootstrapStage::RolesSet
+            }
+            BootstrapStage::RolesSet => {
+                require!(
+                    !self.utxo_chain_connectors.is_empty()
+                        || self.configured_factory_chains().next().is_some(),
+                    "ERR_BOOTSTRAP_NO_CHAIN_CONFIGURED"
+                );
+                BootstrapStage::ChainsConfigured
+            }
+            BootstrapStage::ChainsConfigured => {
+                require!(progress.tokens_bound, "ERR_BOOTSTRAP_NO_TOKEN_BOUND");
+                BootstrapStage::TokensBound
+            }
+            BootstrapStage::TokensBound => {
+                require!(
+                    self.configured_factory_chains()
+                        .all(|chain_kind| self.provers.get(&chain_kind).is_some()),
+                    "ERR_BOOTSTRAP_PROVER_NOT_SET"
+                );
+                BootstrapStage::ProversConfigured
+            }
+            BootstrapStage::ProversConfigured | BootstrapStage::Activated => {
+                BootstrapStage::Activated
+            }
+        }
+    }
+
+    // The UTXO chains are finalised by their connectors, so they don't need a prover.
+    fn configured_factory_chains(&self) -> impl Iterator<Item = ChainKind> + '_ {
+        BUILTIN_CHAINS.into_iter().filter(|chain_kind| {
+            *chain_kind != ChainKind::Near
+                && !chain_kind.is_utxo_chain()
+                && self.factories.get(chain_kind).is_some()
+        })
+    }
+}
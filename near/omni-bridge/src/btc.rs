@@ -1,119 +1,46 @@
-use crate::storage::NEP141_DEPOSIT;
-use crate::{
-    ext_token, ext_utxo_connector, Contract, ContractExt, Role, FT_TRANSFER_CALL_GAS, ONE_YOCTO,
-    STORAGE_DEPOSIT_GAS,
-};
-use near_plugins::{access_control_any, pause, AccessControllable, Pausable};
-use near_sdk::json_types::{U128, U64};
-use near_sdk::{
-    env, near, require, serde_json, AccountId, Gas, Promise, PromiseError, PromiseOrValue,
+use crate::connector_version::{
+    FEE_BUMP_MIN_CONNECTOR_INTERFACE_VERSION, LEGACY_SWEEP_MIN_CONNECTOR_INTERFACE_VERSION,
+    SWEEP_DESTINATION_MIN_CONNECTOR_INTERFACE_VERSION,
 };
-use omni_types::btc::{TokenReceiverMessage, TxOut, UTXOChainConfig};
-use omni_types::{ChainKind, Fee, OmniAddress, TransferId, TransferMessage};
+use crate::external::{ext_token, ext_utxo_connector};
+use crate::helpers::{SdkExpect, SdkUnwrap};
+use crate::storage::NEP141_DEPOSIT;
+use crate::utxo_key_epochs::UtxoKeyRotation;
+use crate::{Contract, ContractExt, Role, STORAGE_DEPOSIT_GAS};
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::U64;
+use near_sdk::{env, near, require, serde_json, AccountId, Gas, Promise, PromiseResult};
+use omni_types::btc::{OutPoint, ScriptKind, TxOut, UTXOChainConfig, UTXOChainMsg};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::{ChainKind, OmniAddress, TransferId};
 
-const SUBMIT_TRANSFER_TO_BTC_CONNECTOR_CALLBACK_GAS: Gas = Gas::from_tgas(5);
 const WITHDRAW_RBF_GAS: Gas = Gas::from_tgas(100);
+const SWEEP_LEGACY_UTXOS_GAS: Gas = Gas::from_tgas(100);
+const SWEEP_LEGACY_UTXOS_CALLBACK_GAS: Gas = Gas::from_tgas(5);
+
+/// Limits of the Withdraw messages submitted to the connector of a UTXO chain.
+/// Oversized transactions are rejected by the connector anyway, after the gas was spent.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UtxoWithdrawLimits {
+    pub max_inputs: Option<u32>,
+    pub max_outputs: Option<u32>,
+    pub max_msg_size: Option<u32>,
+}
 
 #[near(serializers=[json])]
-#[derive(Debug, PartialEq)]
-enum UTXOChainMsg {
-    MaxGasFee(U64),
+#[derive(Debug, Clone)]
+pub struct UtxoChainConfigView {
+    pub connector: AccountId,
+    pub token_id: AccountId,
+    pub withdraw_limits: UtxoWithdrawLimits,
+    pub key_rotation: Option<UtxoKeyRotation>,
+    pub extra_msg_required: bool,
+    pub dust_limit: Option<U64>,
 }
 
 #[near]
 impl Contract {
-    #[payable]
-    #[pause(except(roles(Role::DAO, Role::UnrestrictedRelayer)))]
-    pub fn submit_transfer_to_utxo_chain_connector(
-        &mut self,
-        transfer_id: TransferId,
-        msg: String,
-        fee_recipient: Option<AccountId>,
-        fee: &Option<Fee>,
-    ) -> Promise {
-        let transfer = self.get_transfer_message_storage(transfer_id);
-
-        let message = serde_json::from_str::<TokenReceiverMessage>(&msg).expect("INVALID MSG");
-        let amount = U128(transfer.message.amount.0 - transfer.message.fee.fee.0);
-
-        if let Some(btc_address) = transfer.message.recipient.get_utxo_address() {
-            if let TokenReceiverMessage::Withdraw {
-                target_btc_address,
-                input: _,
-                output: _,
-                max_gas_fee,
-            } = message
-            {
-                require!(
-                    btc_address == target_btc_address,
-                    "Incorrect target address"
-                );
-
-                if !transfer.message.msg.is_empty() {
-                    let utxo_chain_extra_info: UTXOChainMsg =
-                        serde_json::from_str(&transfer.message.msg)
-                            .expect("Invalid Transfer MSG for UTXO chain");
-                    let UTXOChainMsg::MaxGasFee(max_gas_fee_from_msg) = utxo_chain_extra_info;
-                    require!(
-                        max_gas_fee.expect("max_gas_fee is missing").0
-                            == max_gas_fee_from_msg.0.into(),
-                        "Invalid max gas fee"
-                    );
-                }
-            } else {
-                env::panic_str("Invalid message type");
-            }
-        } else {
-            env::panic_str("Invalid destination chain");
-        }
-
-        if let Some(fee) = &fee {
-            require!(&transfer.message.fee == fee, "Invalid fee");
-        }
-
-        let chain_kind = transfer.message.get_destination_chain();
-        let btc_account_id = self.get_utxo_chain_token(chain_kind);
-        require!(
-            self.get_token_id(&transfer.message.token) == btc_account_id,
-            "Only the native token of this UTXO chain can be transferred."
-        );
-
-        self.remove_transfer_message(transfer_id);
-
-        let fee_recipient = fee_recipient.unwrap_or(env::predecessor_account_id());
-
-        ext_token::ext(btc_account_id)
-            .with_attached_deposit(ONE_YOCTO)
-            .with_static_gas(FT_TRANSFER_CALL_GAS)
-            .ft_transfer_call(self.get_utxo_chain_connector(chain_kind), amount, None, msg)
-            .then(
-                Self::ext(env::current_account_id())
-                    .with_static_gas(SUBMIT_TRANSFER_TO_BTC_CONNECTOR_CALLBACK_GAS)
-                    .submit_transfer_to_btc_connector_callback(
-                        transfer.message,
-                        transfer.owner,
-                        fee_recipient,
-                    ),
-            )
-    }
-
-    #[private]
-    pub fn submit_transfer_to_btc_connector_callback(
-        &mut self,
-        transfer_msg: TransferMessage,
-        transfer_owner: AccountId,
-        fee_recipient: AccountId,
-        #[callback_result] call_result: &Result<U128, PromiseError>,
-    ) -> PromiseOrValue<()> {
-        if matches!(call_result, Ok(result) if result.0 > 0) {
-            let token_fee = transfer_msg.fee.fee.0;
-            self.send_fee_internal(&transfer_msg, fee_recipient, token_fee)
-        } else {
-            self.insert_raw_transfer(transfer_msg, transfer_owner);
-            PromiseOrValue::Value(())
-        }
-    }
-
     #[payable]
     #[access_control_any(roles(Role::DAO))]
     pub fn add_utxo_chain_connector(
@@ -129,6 +56,9 @@ impl Contract {
 
         self.add_token(&utxo_chain_token_id, &token_address, decimals, decimals);
 
+        // The new connector has to declare its interface version again
+        self.connector_interface_versions.remove(&chain_kind);
+        self.register_connector(chain_kind, utxo_chain_connector_id.clone());
         self.utxo_chain_connectors.insert(
             chain_kind,
             UTXOChainConfig {
@@ -161,9 +91,166 @@ impl Contract {
         original_btc_pending_verify_id: String,
         output: Vec<TxOut>,
     ) -> Promise {
-        ext_utxo_connector::ext(self.get_utxo_chain_connector(chain_kind))
-            .with_static_gas(WITHDRAW_RBF_GAS)
-            .withdraw_rbf(original_btc_pending_verify_id, output)
+        self.withdraw_rbf(chain_kind, original_btc_pending_verify_id, output)
+    }
+
+    /// Asks the connector to replace the transaction of a forwarded withdrawal with one paying
+    /// a higher fee, when the fee accepted at its submission is too low for it to be mined.
+    /// The owner of the transfer can bump its own withdrawal. The replacement spends the inputs
+    /// of the recorded transaction, so only one of them can be confirmed and the withdrawal
+    /// is paid once. The connector checks the fee against the replaced transaction, whose id
+    /// is then reported with the outputs of the bump.
+    pub fn bump_utxo_withdrawal_fee(
+        &mut self,
+        transfer_id: TransferId,
+        output: Vec<TxOut>,
+    ) -> Promise {
+        let mut withdrawal = self
+            .utxo_withdrawals
+            .get(&transfer_id)
+            .sdk_expect("ERR_WITHDRAWAL_NOT_FORWARDED");
+        let predecessor = env::predecessor_account_id();
+        require!(
+            predecessor == withdrawal.owner
+                || self.acl_has_role(Role::DAO.into(), predecessor.clone())
+                || self.acl_has_role(Role::RbfOperator.into(), predecessor),
+            "ERR_FEE_BUMP_NOT_ALLOWED"
+        );
+        // The connector identifies the pending transaction by its id
+        let txid = withdrawal
+            .txid
+            .sdk_expect("ERR_WITHDRAWAL_TXID_NOT_RECORDED");
+
+        let chain_kind = withdrawal.chain_kind;
+        output.iter().try_for_each(TxOut::validate).sdk_unwrap();
+        self.check_utxo_withdraw_limits(chain_kind, "", 0, output.len());
+        self.check_utxo_dust_limit(chain_kind, output.iter().map(|output| output.value).min());
+        self.check_utxo_output_script_kinds(
+            chain_kind,
+            &output
+                .iter()
+                .map(|output| ScriptKind::from_hex(&output.script_pubkey))
+                .collect::<Vec<_>>(),
+        );
+
+        withdrawal.outputs_hash = Self::utxo_outputs_hash(&output);
+        self.utxo_withdrawals.insert(&transfer_id, &withdrawal);
+        self.withdraw_rbf(chain_kind, hex::encode(txid), output)
+    }
+
+    /// Asks the connector to move the deposits received by the address of a retired signer key,
+    /// derived with `legacy_path`, to the current bridge address. The connector builds the
+    /// sweep transaction and signs it through the signer, like the withdrawals.
+    /// `destination_script` sends the funds to a script approved by the DAO instead, e.g. to
+    /// consolidate them on a cold address.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn sweep_legacy_address(
+        &mut self,
+        chain_kind: ChainKind,
+        legacy_path: String,
+        outpoints: Vec<OutPoint>,
+        fee_rate: u64,
+        destination_script: Option<String>,
+    ) -> Promise {
+        self.require_connector_interface_version(
+            chain_kind,
+            LEGACY_SWEEP_MIN_CONNECTOR_INTERFACE_VERSION,
+        );
+        if let Some(destination_script) = &destination_script {
+            self.require_connector_interface_version(
+                chain_kind,
+                SWEEP_DESTINATION_MIN_CONNECTOR_INTERFACE_VERSION,
+            );
+            require!(
+                self.is_approved_sweep_destination(chain_kind, destination_script.clone()),
+                "ERR_SWEEP_DESTINATION_NOT_APPROVED"
+            );
+        }
+        require!(!outpoints.is_empty(), "ERR_NO_SWEEP_INPUTS");
+        require!(
+            outpoints.iter().all(|outpoint| self
+                .reserved_outpoints
+                .get(&(chain_kind, *outpoint))
+                .is_none()),
+            "ERR_OUTPOINT_ALREADY_RESERVED"
+        );
+        // The swept outpoints are reserved until the connector accepts the sweep
+        for outpoint in &outpoints {
+            require!(
+                self.sweeping_outpoints.insert(&(chain_kind, *outpoint)),
+                "ERR_OUTPOINT_ALREADY_RESERVED"
+            );
+        }
+        self.check_utxo_withdraw_limits(chain_kind, "", outpoints.len(), 1);
+
+        env::log_str(
+            &OmniBridgeEvent::LegacyAddressSweepEvent {
+                chain_kind,
+                legacy_path: legacy_path.clone(),
+                outpoints: outpoints.clone(),
+                fee_rate,
+                destination_script: destination_script.clone(),
+            }
+            .to_log_string(),
+        );
+
+        ext_utxo_connector::ext(self.require_chain_configured(chain_kind).connector)
+            .with_static_gas(SWEEP_LEGACY_UTXOS_GAS)
+            .sweep_legacy_utxos(legacy_path, outpoints.clone(), fee_rate, destination_script)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(SWEEP_LEGACY_UTXOS_CALLBACK_GAS)
+                    .sweep_legacy_address_callback(chain_kind, outpoints),
+            )
+    }
+
+    /// Releases the reservation of the swept outpoints. They stay legacy outpoints if the
+    /// connector rejected the sweep, so they can be swept again.
+    #[private]
+    pub fn sweep_legacy_address_callback(
+        &mut self,
+        chain_kind: ChainKind,
+        outpoints: Vec<OutPoint>,
+    ) {
+        let is_swept = matches!(env::promise_result(0), PromiseResult::Successful(_));
+        for outpoint in &outpoints {
+            self.sweeping_outpoints.remove(&(chain_kind, *outpoint));
+            // The swept UTXOs are spent, so they no longer have to be blocked for the withdrawals
+            if is_swept {
+                self.legacy_outpoints.remove(&(chain_kind, *outpoint));
+            }
+        }
+    }
+
+    /// Approves the script pubkey, in hex, as a destination of the sweeps of the UTXO chain.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn add_approved_sweep_destination(&mut self, chain_kind: ChainKind, script_pubkey: String) {
+        require!(self.is_utxo_chain(chain_kind), "ERR_NOT_UTXO_CHAIN");
+        require!(
+            hex::decode(&script_pubkey).is_ok_and(|script| !script.is_empty()),
+            "ERR_INVALID_SCRIPT_PUBKEY"
+        );
+        self.approved_sweep_destinations
+            .insert(&(chain_kind, script_pubkey));
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn remove_approved_sweep_destination(
+        &mut self,
+        chain_kind: ChainKind,
+        script_pubkey: String,
+    ) {
+        self.approved_sweep_destinations
+            .remove(&(chain_kind, script_pubkey));
+    }
+
+    pub fn is_approved_sweep_destination(
+        &self,
+        chain_kind: ChainKind,
+        script_pubkey: String,
+    ) -> bool {
+        self.approved_sweep_destinations
+            .contains(&(chain_kind, script_pubkey))
     }
 
     /// Returns the `AccountId` of the connector for the given UTXO chain.
@@ -172,11 +259,8 @@ impl Contract {
     ///
     /// Panics if a Сonnector for the specified `chain_kind` has not been configured.
     pub fn get_utxo_chain_connector(&self, chain_kind: ChainKind) -> AccountId {
-        self.utxo_chain_connectors
-            .get(&chain_kind)
+        self.get_chain_connector(chain_kind)
             .expect("Connector has not been set up for this chain")
-            .connector
-            .clone()
     }
 
     /// Returns the `AccountId` of the token for the given UTXO chain.
@@ -185,11 +269,203 @@ impl Contract {
     ///
     /// Panics if a UTXO chain Token for the specified `chain_kind` has not been configured.
     pub fn get_utxo_chain_token(&self, chain_kind: ChainKind) -> AccountId {
-        self.utxo_chain_connectors
-            .get(&chain_kind)
+        self.get_chain_token(chain_kind)
             .expect("UTXO Token has not been set up for this chain")
-            .token_id
-            .clone()
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_utxo_withdraw_limits(&mut self, chain_kind: ChainKind, limits: UtxoWithdrawLimits) {
+        if limits == UtxoWithdrawLimits::default() {
+            self.utxo_withdraw_limits.remove(&chain_kind);
+        } else {
+            self.utxo_withdraw_limits.insert(&chain_kind, &limits);
+        }
+    }
+
+    /// Requires the transfers to the UTXO chain to carry the `UTXOChainMsg` with the maximum
+    /// gas fee, so the fee of the withdrawal is always cross-checked on submission.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_extra_msg_required(&mut self, chain_kind: ChainKind, required: bool) {
+        require!(self.is_utxo_chain(chain_kind), "ERR_NOT_UTXO_CHAIN");
+        if required {
+            self.extra_msg_required_chains.insert(&chain_kind);
+        } else {
+            self.extra_msg_required_chains.remove(&chain_kind);
+        }
+    }
+
+    pub fn is_extra_msg_required(&self, chain_kind: ChainKind) -> bool {
+        self.extra_msg_required_chains.contains(&chain_kind)
+    }
+
+    /// Sets the smallest value of the outputs of the withdrawals to the UTXO chain. The nodes
+    /// don't relay the transactions with dust outputs, so the connector would fail to
+    /// broadcast the withdrawal after the gas of the submission was spent.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_utxo_dust_limit(&mut self, chain_kind: ChainKind, dust_limit: Option<U64>) {
+        require!(self.is_utxo_chain(chain_kind), "ERR_NOT_UTXO_CHAIN");
+        if let Some(dust_limit) = dust_limit {
+            self.utxo_dust_limits.insert(&chain_kind, &dust_limit);
+        } else {
+            self.utxo_dust_limits.remove(&chain_kind);
+        }
+    }
+
+    pub fn get_utxo_dust_limit(&self, chain_kind: ChainKind) -> Option<U64> {
+        self.utxo_dust_limits.get(&chain_kind)
+    }
+
+    /// Sets the types of the output scripts accepted in the withdrawals to the chain, so the
+    /// tokens are not sent to unspendable or non-standard scripts by mistake. `None` restores
+    /// the built-in types of the chain.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_utxo_script_kinds(
+        &mut self,
+        chain_kind: ChainKind,
+        script_kinds: Option<Vec<ScriptKind>>,
+    ) {
+        require!(self.is_utxo_chain(chain_kind), "ERR_NOT_UTXO_CHAIN");
+        if let Some(script_kinds) = script_kinds {
+            require!(
+                !script_kinds.contains(&ScriptKind::NonStandard),
+                "ERR_NON_STANDARD_SCRIPT_NOT_ALLOWED"
+            );
+            self.utxo_script_kinds.insert(&chain_kind, &script_kinds);
+        } else {
+            self.utxo_script_kinds.remove(&chain_kind);
+        }
+    }
+
+    pub fn get_utxo_script_kinds(&self, chain_kind: ChainKind) -> Vec<ScriptKind> {
+        self.utxo_script_kinds.get(&chain_kind).unwrap_or_else(|| {
+            self.get_utxo_chain_validator(chain_kind)
+                .default_script_kinds()
+        })
+    }
+
+    pub fn get_utxo_chain_config(&self, chain_kind: ChainKind) -> Option<UtxoChainConfigView> {
+        self.get_chain_config(chain_kind)
+            .map(|config| UtxoChainConfigView {
+                connector: config.connector,
+                token_id: config.token_id,
+                withdraw_limits: self.get_utxo_withdraw_limits(chain_kind),
+                key_rotation: self.utxo_key_rotations.get(&chain_kind),
+                extra_msg_required: self.extra_msg_required_chains.contains(&chain_kind),
+                dust_limit: self.utxo_dust_limits.get(&chain_kind),
+            })
+    }
+
+    /// Returns the `AccountId` of the connector for the given UTXO chain, if it is configured.
+    pub fn get_chain_connector(&self, chain_kind: ChainKind) -> Option<AccountId> {
+        self.get_chain_config(chain_kind)
+            .map(|config| config.connector)
+    }
+
+    /// Returns the `AccountId` of the token for the given UTXO chain, if it is configured.
+    pub fn get_chain_token(&self, chain_kind: ChainKind) -> Option<AccountId> {
+        self.get_chain_config(chain_kind)
+            .map(|config| config.token_id)
+    }
+}
+
+impl Contract {
+    fn withdraw_rbf(
+        &self,
+        chain_kind: ChainKind,
+        original_btc_pending_verify_id: String,
+        output: Vec<TxOut>,
+    ) -> Promise {
+        self.require_connector_interface_version(
+            chain_kind,
+            FEE_BUMP_MIN_CONNECTOR_INTERFACE_VERSION,
+        );
+        ext_utxo_connector::ext(self.require_chain_configured(chain_kind).connector)
+            .with_static_gas(WITHDRAW_RBF_GAS)
+            .withdraw_rbf(original_btc_pending_verify_id, output)
+    }
+
+    pub(crate) fn get_chain_config(&self, chain_kind: ChainKind) -> Option<UTXOChainConfig> {
+        self.utxo_chain_connectors.get(&chain_kind).cloned()
+    }
+
+    pub(crate) fn get_utxo_withdraw_limits(&self, chain_kind: ChainKind) -> UtxoWithdrawLimits {
+        self.utxo_withdraw_limits
+            .get(&chain_kind)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn check_utxo_extra_msg(&self, recipient: &OmniAddress, msg: Option<&str>) {
+        if !self.is_utxo_chain(recipient.get_chain())
+            || !self
+                .extra_msg_required_chains
+                .contains(&recipient.get_chain())
+        {
+            return;
+        }
+        require!(
+            msg.is_some_and(|msg| serde_json::from_str::<UTXOChainMsg>(msg).is_ok()),
+            "ERR_EXTRA_MSG_REQUIRED"
+        );
+    }
+
+    pub(crate) fn check_utxo_withdraw_limits(
+        &self,
+        chain_kind: ChainKind,
+        msg: &str,
+        inputs: usize,
+        outputs: usize,
+    ) {
+        let limits = self.get_utxo_withdraw_limits(chain_kind);
+        let within_limit = |value: usize, limit: Option<u32>| {
+            limit.is_none_or(|limit| u32::try_from(value).is_ok_and(|value| value <= limit))
+        };
+
+        require!(
+            within_limit(inputs, limits.max_inputs),
+            "ERR_TOO_MANY_WITHDRAW_INPUTS"
+        );
+        require!(
+            within_limit(outputs, limits.max_outputs),
+            "ERR_TOO_MANY_WITHDRAW_OUTPUTS"
+        );
+        require!(
+            within_limit(msg.len(), Some(self.max_withdraw_msg_size(chain_kind))),
+            "ERR_WITHDRAW_MSG_TOO_LARGE"
+        );
+    }
+
+    pub(crate) fn check_utxo_dust_limit(
+        &self,
+        chain_kind: ChainKind,
+        min_output_value: Option<u64>,
+    ) {
+        if let (Some(min_output_value), Some(dust_limit)) =
+            (min_output_value, self.utxo_dust_limits.get(&chain_kind))
+        {
+            require!(
+                min_output_value >= dust_limit.0,
+                "ERR_WITHDRAW_OUTPUT_BELOW_DUST_LIMIT"
+            );
+        }
+    }
+
+    pub(crate) fn check_utxo_output_script_kinds(
+        &self,
+        chain_kind: ChainKind,
+        output_script_kinds: &[ScriptKind],
+    ) {
+        let script_kinds = self.get_utxo_script_kinds(chain_kind);
+        require!(
+            output_script_kinds
+                .iter()
+                .all(|kind| script_kinds.contains(kind)),
+            "ERR_WITHDRAW_OUTPUT_SCRIPT_NOT_ALLOWED"
+        );
+    }
+
+    pub(crate) fn require_chain_configured(&self, chain_kind: ChainKind) -> UTXOChainConfig {
+        self.get_chain_config(chain_kind)
+            .sdk_expect("ERR_UTXO_CONFIG_MISSING")
     }
 }
 
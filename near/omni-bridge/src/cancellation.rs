@@ -0,0 +1,273 @@
+use near_plugins::{pause, Pausable};
+use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::{
+    borsh, env, near, require, AccountId, CurveType, Gas, NearToken, Promise, PromiseError,
+};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::{
+    ChainKind, PayloadType, TransferBeneficiary, TransferCancellationPayload, TransferId,
+    TransferMessage,
+};
+
+use crate::fee_ledger::FeeAsset;
+use crate::helpers::{SdkExpect, SdkUnwrap};
+use crate::locked::LockedState;
+use crate::{Contract, ContractExt, Role};
+
+const REFUND_UNSIGNED_TRANSFER_CALLBACK_GAS: Gas = Gas::from_tgas(5);
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferBeneficiaryRecord {
+    pub beneficiary: TransferBeneficiary,
+    // Once the signature was requested the transfer can be finalised on the destination chain
+    pub signing_requested: bool,
+}
+
+#[near]
+impl Contract {
+    pub fn get_transfer_beneficiary(
+        &self,
+        transfer_id: TransferId,
+    ) -> Option<TransferBeneficiaryRecord> {
+        self.transfer_beneficiaries.get(&transfer_id)
+    }
+
+    /// Cancels a transfer that was not signed yet and refunds the tokens to its beneficiary.
+    /// `signature` is the ed25519 signature of the borsh-encoded `TransferCancellationPayload`
+    /// made with the key of the beneficiary, so the call can be submitted by any account.
+    #[pause(except(roles(Role::DAO)))]
+    pub fn cancel_transfer(&mut self, transfer_id: TransferId, signature: Base64VecU8) -> Promise {
+        let record = self
+            .transfer_beneficiaries
+            .get(&transfer_id)
+            .sdk_expect("ERR_TRANSFER_BENEFICIARY_NOT_FOUND");
+        require!(!record.signing_requested, "ERR_TRANSFER_ALREADY_SIGNED");
+        self.require_transfer_not_held(transfer_id);
+        require!(
+            Self::verify_cancellation_signature(&record.beneficiary, transfer_id, &signature.0),
+            "ERR_INVALID_CANCELLATION_SIGNATURE"
+        );
+
+        let (transfer_message, refund) =
+            self.refund_unsigned_transfer(transfer_id, record.beneficiary.account_id.clone());
+        env::log_str(
+            &OmniBridgeEvent::CancelTransferEvent {
+                transfer_message,
+                beneficiary: record.beneficiary.account_id,
+            }
+            .to_log_string(),
+        );
+        refund
+    }
+
+    /// Cancels a transfer initiated on NEAR that is owned by the caller and was not signed yet,
+    /// and refunds the tokens to the caller, minus the token fee of the transfer. The owner is
+    /// the sender unless the ownership of the transfer was handed over. The transfers with
+    /// a beneficiary are cancelled by the beneficiary with `cancel_transfer`.
+    #[pause(except(roles(Role::DAO)))]
+    pub fn cancel_transfer_as_owner(&mut self, transfer_id: TransferId) -> Promise {
+        let owner = env::predecessor_account_id();
+        let transfer = self.get_transfer_message_storage(transfer_id);
+        require!(transfer.owner == owner, "ERR_ONLY_TRANSFER_OWNER");
+        require!(
+            transfer_id.origin_chain == ChainKind::Near,
+            "ERR_ONLY_TRANSFER_SENDER_CAN_CANCEL"
+        );
+        require!(
+            self.transfer_beneficiaries.get(&transfer_id).is_none(),
+            "ERR_TRANSFER_HAS_BENEFICIARY"
+        );
+        require!(
+            !self.signing_requested_transfers.contains_key(&transfer_id),
+            "ERR_TRANSFER_ALREADY_SIGNED"
+        );
+        self.require_transfer_not_held(transfer_id);
+
+        let (transfer_message, refund) = self.refund_unsigned_transfer(transfer_id, owner.clone());
+        env::log_str(
+            &OmniBridgeEvent::CancelTransferEvent {
+                transfer_message,
+                beneficiary: owner,
+            }
+            .to_log_string(),
+        );
+        refund
+    }
+
+    // A refund that failed, e.g. to an account not registered with the token, is credited to
+    // its recipient, who can claim it with `claim_fees`.
+    #[private]
+    pub fn refund_unsigned_transfer_callback(
+        &mut self,
+        token_id: AccountId,
+        recipient: AccountId,
+        amount: U128,
+        #[callback_result] call_result: &Result<(), PromiseError>,
+    ) {
+        if call_result.is_err() {
+            self.lock_amount(&token_id, LockedState::FeePool, amount.0);
+            self.credit_claimable_fee(recipient, FeeAsset::Token(token_id), amount.0);
+        }
+    }
+}
+
+impl Contract {
+    // Returns the storage cost of the record.
+    pub(crate) fn add_transfer_beneficiary(
+        &mut self,
+        transfer_id: &TransferId,
+        beneficiary: TransferBeneficiary,
+    ) -> NearToken {
+        require!(
+            beneficiary.public_key.curve_type() == CurveType::ED25519,
+            "ERR_UNSUPPORTED_BENEFICIARY_KEY"
+        );
+        let storage_usage = env::storage_usage();
+        self.transfer_beneficiaries.insert(
+            transfer_id,
+            &TransferBeneficiaryRecord {
+                beneficiary,
+                signing_requested: false,
+            },
+        );
+        env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into())
+    }
+
+    // Removes the transfer and refunds its tokens to `recipient` and its native fee to its owner.
+    // The escalated part of the native fee goes back to the escalation budget.
+    // The token fee of a transfer without a beneficiary may have been paid by a sponsor of the
+    // recipient, so it is kept as a protocol fee.
+    pub(crate) fn refund_unsigned_transfer(
+        &mut self,
+        transfer_id: TransferId,
+        recipient: AccountId,
+    ) -> (TransferMessage, Promise) {
+        let has_beneficiary = self.transfer_beneficiaries.get(&transfer_id).is_some();
+        let owner = self.get_transfer_message_storage(transfer_id).owner;
+        let escalated_native_fee = self.reclaim_fee_escalation(&transfer_id);
+        let transfer_message = self.remove_transfer_message(transfer_id);
+        self.remove_submission_intent(&transfer_id);
+        self.remove_transfer_timestamps(&transfer_id, &owner);
+
+        // The native fee was paid by the owner and was not claimed by a relayer yet
+        if let Some(mut storage) = self.accounts_balances.get(&owner) {
+            storage.available = storage.available.saturating_add(
+                NearToken::from_yoctonear(transfer_message.fee.native_fee.0)
+                    .saturating_sub(escalated_native_fee),
+            );
+            self.accounts_balances.insert(&owner, &storage);
+        }
+
+        let token = self.get_token_id(&transfer_message.token);
+        self.record_tvl_release(&token, transfer_message.amount.0);
+        let refund = if has_beneficiary {
+            transfer_message.amount
+        } else {
+            self.collect_protocol_fee(&token, transfer_message.fee.fee);
+            transfer_message
+                .fee
+                .net_amount(transfer_message.amount)
+                .sdk_unwrap()
+        };
+
+        let promise = self
+            .send_tokens(token.clone(), recipient.clone(), refund, "")
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(REFUND_UNSIGNED_TRANSFER_CALLBACK_GAS)
+                    .refund_unsigned_transfer_callback(token, recipient, refund),
+            );
+        (transfer_message, promise)
+    }
+
+    // The first signer of the transfer pays the storage of the marker from its storage
+    // balance, and gets it back when the transfer is removed
+    pub(crate) fn mark_transfer_signing_requested(
+        &mut self,
+        transfer_id: &TransferId,
+        signer: &AccountId,
+    ) {
+        if !self.signing_requested_transfers.contains_key(transfer_id) {
+            self.update_storage_balance(
+                signer.clone(),
+                Self::required_balance_for_signing_requested_marker(transfer_id, signer),
+                NearToken::from_yoctonear(0),
+            );
+            self.signing_requested_transfers.insert(transfer_id, signer);
+        }
+        let Some(mut record) = self.transfer_beneficiaries.get(transfer_id) else {
+            return;
+        };
+        if !record.signing_requested {
+            record.signing_requested = true;
+            self.transfer_beneficiaries.insert(transfer_id, &record);
+        }
+    }
+
+    pub(crate) fn unmark_transfer_signing_requested(&mut self, transfer_id: &TransferId) {
+        let Some(signer) = self.signing_requested_transfers.remove(transfer_id) else {
+            return;
+        };
+        let storage_deposit =
+            Self::required_balance_for_signing_requested_marker(transfer_id, &signer);
+        if let Some(mut storage) = self.accounts_balances.get(&signer) {
+            storage.available = storage.available.saturating_add(storage_deposit);
+            self.accounts_balances.insert(&signer, &storage);
+        }
+    }
+
+    pub(crate) fn required_balance_for_signing_requested_marker(
+        transfer_id: &TransferId,
+        signer: &AccountId,
+    ) -> NearToken {
+        let record_len: u64 = borsh::to_vec(&(transfer_id, signer))
+            .sdk_expect("ERR_BORSH")
+            .len()
+            .try_into()
+            .sdk_expect("ERR_CAST");
+
+        env::storage_byte_cost().saturating_mul((Self::get_basic_storage() + record_len).into())
+    }
+
+    pub(crate) fn required_balance_for_transfer_beneficiary(
+        transfer_id: &TransferId,
+        beneficiary: &TransferBeneficiary,
+    ) -> NearToken {
+        let record_len: u64 = borsh::to_vec(&(
+            transfer_id,
+            TransferBeneficiaryRecord {
+                beneficiary: beneficiary.clone(),
+                signing_requested: false,
+            },
+        ))
+        .sdk_expect("ERR_BORSH")
+        .len()
+        .try_into()
+        .sdk_expect("ERR_CAST");
+
+        env::storage_byte_cost().saturating_mul((Self::get_basic_storage() + record_len).into())
+    }
+
+    fn verify_cancellation_signature(
+        beneficiary: &TransferBeneficiary,
+        transfer_id: TransferId,
+        signature: &[u8],
+    ) -> bool {
+        let Ok(signature) = <&[u8; 64]>::try_from(signature) else {
+            return false;
+        };
+        let Ok(public_key) = <&[u8; 32]>::try_from(&beneficiary.public_key.as_bytes()[1..]) else {
+            return false;
+        };
+        let payload = borsh::to_vec(&TransferCancellationPayload {
+            prefix: PayloadType::TransferCancellation,
+            locker: env::current_account_id(),
+            transfer_id,
+        })
+        .sdk_expect("ERR_BORSH");
+
+        env::ed25519_verify(signature, &payload, public_key)
+    }
+}
@@ -0,0 +1,44 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::{near, require};
+use omni_types::{ChainKind, ChainTraits, OmniAddress};
+
+use crate::{Contract, ContractExt, Role};
+
+#[near]
+impl Contract {
+    /// Overrides the traits of the chain. `None` restores the built-in traits of the chain.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_chain_traits(&mut self, chain_kind: ChainKind, traits: Option<ChainTraits>) {
+        require!(
+            chain_kind != ChainKind::Near,
+            "ERR_NEAR_TRAITS_NOT_CONFIGURABLE"
+        );
+        if let Some(traits) = traits {
+            self.chain_traits.insert(&chain_kind, &traits);
+        } else {
+            self.chain_traits.remove(&chain_kind);
+        }
+    }
+
+    pub fn get_chain_traits(&self, chain_kind: ChainKind) -> ChainTraits {
+        self.chain_traits
+            .get(&chain_kind)
+            .unwrap_or_else(|| chain_kind.default_traits())
+    }
+}
+
+impl Contract {
+    pub(crate) fn is_utxo_chain(&self, chain_kind: ChainKind) -> bool {
+        self.get_chain_traits(chain_kind).is_utxo
+    }
+
+    // The `msg` of the transfers to UTXO chains carries the `UTXOChainMsg` of the withdrawal,
+    // not a memo for the recipient.
+    pub(crate) fn check_transfer_memo(&self, recipient: &OmniAddress, msg: Option<&str>) {
+        let traits = self.get_chain_traits(recipient.get_chain());
+        if traits.is_utxo || traits.supports_memo {
+            return;
+        }
+        require!(msg.is_none_or(str::is_empty), "ERR_MEMO_NOT_SUPPORTED");
+    }
+}
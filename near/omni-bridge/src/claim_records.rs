@@ -0,0 +1,101 @@
+use near_sdk::json_types::U64;
+use near_sdk::{env, near};
+use omni_types::{ChainKind, TransferId};
+
+use crate::{Contract, ContractExt};
+
+const NANOSECONDS_PER_DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+const MAX_UNCLAIMED_TRANSFERS_PAGE: u64 = 100;
+
+/// Observation that settled the transfer on the destination chain.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaimEvidence {
+    /// Proof of the finalization on the destination chain, submitted with `claim_fee`.
+    DestinationProof,
+    /// Acknowledgement of the UTXO chain connector that accepted the withdrawal.
+    ConnectorAck,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferClaimRecord {
+    pub destination_chain: ChainKind,
+    pub claimed_at: U64,
+    pub evidence: ClaimEvidence,
+}
+
+#[near(serializers=[json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnclaimedTransfer {
+    pub transfer_id: TransferId,
+    pub initiated_at: U64,
+}
+
+#[near]
+impl Contract {
+    pub fn is_transfer_claimed(&self, transfer_id: TransferId) -> bool {
+        self.transfer_claim_records.contains_key(&transfer_id)
+    }
+
+    pub fn get_transfer_claim_record(
+        &self,
+        transfer_id: TransferId,
+    ) -> Option<TransferClaimRecord> {
+        self.transfer_claim_records.get(&transfer_id)
+    }
+
+    /// Returns the pending transfers to the destination chain that were initiated at least
+    /// `min_age_days` days ago and were not claimed yet. The page is taken from the per-chain
+    /// pending index, so a page can contain less than `limit` transfers while more remain.
+    /// The transfers initiated before their timestamps were recorded are skipped.
+    pub fn get_unclaimed_transfers(
+        &self,
+        chain_kind: ChainKind,
+        min_age_days: u32,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<UnclaimedTransfer> {
+        let Some(transfer_ids) = self.pending_transfers_by_chain.get(&chain_kind) else {
+            return Vec::new();
+        };
+        let from_index = from_index.unwrap_or_default();
+        let limit = limit
+            .unwrap_or(MAX_UNCLAIMED_TRANSFERS_PAGE)
+            .min(MAX_UNCLAIMED_TRANSFERS_PAGE);
+        let max_initiated_at = env::block_timestamp()
+            .saturating_sub(u64::from(min_age_days).saturating_mul(NANOSECONDS_PER_DAY));
+
+        (from_index..transfer_ids.len().min(from_index.saturating_add(limit)))
+            .filter_map(|index| transfer_ids.as_vector().get(index))
+            .filter_map(|transfer_id| {
+                let initiated_at = self.transfer_timestamps.get(&transfer_id)?.initiated_at;
+                (initiated_at.0 <= max_initiated_at).then_some(UnclaimedTransfer {
+                    transfer_id,
+                    initiated_at,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Contract {
+    pub(crate) fn record_transfer_claimed(
+        &mut self,
+        transfer_id: &TransferId,
+        destination_chain: ChainKind,
+        evidence: ClaimEvidence,
+    ) {
+        if self.transfer_claim_records.contains_key(transfer_id) {
+            return;
+        }
+        self.transfer_claim_records.insert(
+            transfer_id,
+            &TransferClaimRecord {
+                destination_chain,
+                claimed_at: U64(env::block_timestamp()),
+                evidence,
+            },
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{env, near, require, AccountId};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::safe_amount::SafeAmount;
+use omni_types::units::BPS_DENOMINATOR;
+use omni_types::{ChainKind, TransferId};
+
+use crate::connector_registry::ConnectorId;
+use crate::helpers::SdkUnwrap;
+use crate::{Contract, ContractExt, Role};
+
+const MAX_FAILURE_BPS: u16 = BPS_DENOMINATOR;
+
+/// Trips the circuit breaker of the connector when at least `max_failure_bps` of
+/// the submissions of the current window failed. With `auto_failover` the submissions
+/// are switched to the standby connector, otherwise the DAO has to switch them.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailoverPolicy {
+    pub window: U64,
+    pub min_submissions: u32,
+    pub max_failure_bps: u16,
+    pub auto_failover: bool,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectorHealth {
+    pub window_start: U64,
+    pub submissions: u32,
+    pub failures: u32,
+    pub is_tripped: bool,
+}
+
+/// Transfers submitted to the connector whose result is not known yet.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectorInFlight {
+    pub transfers: u32,
+    pub amount: U128,
+}
+
+#[near]
+impl Contract {
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_standby_connector(&mut self, chain_kind: ChainKind, connector: Option<AccountId>) {
+        if let Some(connector) = connector {
+            require!(
+                connector != self.require_chain_configured(chain_kind).connector,
+                "ERR_STANDBY_IS_PRIMARY_CONNECTOR"
+            );
+            self.standby_connectors.insert(&chain_kind, &connector);
+        } else {
+            self.standby_connectors.remove(&chain_kind);
+        }
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_failover_policy(&mut self, chain_kind: ChainKind, policy: Option<FailoverPolicy>) {
+        if let Some(policy) = policy {
+            require!(
+                policy.window.0 > 0 && policy.max_failure_bps <= MAX_FAILURE_BPS,
+                "ERR_INVALID_FAILOVER_POLICY"
+            );
+            self.failover_policies.insert(&chain_kind, &policy);
+        } else {
+            self.failover_policies.remove(&chain_kind);
+        }
+    }
+
+    /// Switches the submissions of the chain to the standby connector, which becomes
+    /// the primary one, and moves the given dead-letter transfers back to the pending
+    /// transfers, so they are submitted to the new connector.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn switch_to_standby_connector(
+        &mut self,
+        chain_kind: ChainKind,
+        dead_letter_transfers: Vec<TransferId>,
+    ) {
+        self.switch_connector(chain_kind, false);
+        for transfer_id in dead_letter_transfers {
+            self.requeue_dead_letter_transfer(transfer_id);
+        }
+    }
+
+    pub fn get_standby_connector(&self, chain_kind: ChainKind) -> Option<AccountId> {
+        self.standby_connectors.get(&chain_kind)
+    }
+
+    pub fn get_failover_policy(&self, chain_kind: ChainKind) -> Option<FailoverPolicy> {
+        self.failover_policies.get(&chain_kind)
+    }
+
+    /// Clears the circuit breaker and the counters of the connector, e.g. once it is fixed.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn reset_connector_health(&mut self, connector_id: ConnectorId) {
+        self.connector_health.remove(&connector_id);
+    }
+
+    pub fn get_connector_health(&self, connector_id: ConnectorId) -> ConnectorHealth {
+        self.connector_health.get(&connector_id).unwrap_or_default()
+    }
+
+    pub fn get_connector_in_flight(&self, connector_id: ConnectorId) -> ConnectorInFlight {
+        self.connector_in_flight
+            .get(&connector_id)
+            .unwrap_or_default()
+    }
+}
+
+impl Contract {
+    pub(crate) fn record_connector_submission(&mut self, connector_id: ConnectorId, amount: U128) {
+        let mut in_flight = self.get_connector_in_flight(connector_id);
+        in_flight.transfers += 1;
+        in_flight.amount = SafeAmount::from(in_flight.amount)
+            .checked_add(amount.into())
+            .sdk_unwrap()
+            .into();
+        self.connector_in_flight.insert(&connector_id, &in_flight);
+    }
+
+    pub(crate) fn release_connector_in_flight(&mut self, connector_id: ConnectorId, amount: U128) {
+        let mut in_flight = self.get_connector_in_flight(connector_id);
+        in_flight.transfers = in_flight.transfers.saturating_sub(1);
+        in_flight.amount = U128(in_flight.amount.0.saturating_sub(amount.0));
+        if in_flight == ConnectorInFlight::default() {
+            self.connector_in_flight.remove(&connector_id);
+        } else {
+            self.connector_in_flight.insert(&connector_id, &in_flight);
+        }
+    }
+
+    // Records the result of the submission and trips the circuit breaker of the connector
+    // if too many submissions failed. Only the failures of the connector are recorded, not
+    // the rejections of the invalid withdrawals built by an untrusted relayer, so a relayer
+    // can't trip the breaker. A tripped breaker is cleared a full window after it tripped.
+    pub(crate) fn record_connector_result(
+        &mut self,
+        chain_kind: ChainKind,
+        connector_id: ConnectorId,
+        is_success: bool,
+    ) {
+        let Some(policy) = self.failover_policies.get(&chain_kind) else {
+            return;
+        };
+        let mut health = self.get_connector_health(connector_id);
+        let now = env::block_timestamp();
+        if now.saturating_sub(health.window_start.0) >= policy.window.0 {
+            health = ConnectorHealth {
+                window_start: U64(now),
+                ..ConnectorHealth::default()
+            };
+        }
+        health.submissions += 1;
+        if !is_success {
+            health.failures += 1;
+        }
+
+        let should_trip = !health.is_tripped
+            && health.submissions >= policy.min_submissions
+            && u128::from(health.failures) * u128::from(MAX_FAILURE_BPS)
+                >= u128::from(health.submissions) * u128::from(policy.max_failure_bps);
+        if should_trip {
+            env::log_str(
+                &OmniBridgeEvent::ConnectorCircuitBreakerEvent {
+                    chain_kind,
+                    connector: self.resolve_connector(connector_id),
+                    submissions: health.submissions,
+                    failures: health.failures,
+                }
+                .to_log_string(),
+            );
+            health = ConnectorHealth {
+                window_start: U64(now),
+                is_tripped: true,
+                ..ConnectorHealth::default()
+            };
+        }
+        self.connector_health.insert(&connector_id, &health);
+
+        if should_trip && policy.auto_failover && self.standby_connectors.contains_key(&chain_kind)
+        {
+            self.switch_connector(chain_kind, true);
+        }
+    }
+
+    // The breaker of a connector without submissions is not cleared by a new window, so it
+    // expires with its window.
+    pub(crate) fn is_connector_tripped(
+        &self,
+        chain_kind: ChainKind,
+        connector_id: ConnectorId,
+    ) -> bool {
+        let Some(health) = self.connector_health.get(&connector_id) else {
+            return false;
+        };
+        health.is_tripped
+            && self
+                .failover_policies
+                .get(&chain_kind)
+                .is_some_and(|policy| {
+                    env::block_timestamp().saturating_sub(health.window_start.0) < policy.window.0
+                })
+    }
+
+    fn switch_connector(&mut self, chain_kind: ChainKind, is_automatic: bool) {
+        let new_connector = self
+            .standby_connectors
+            .remove(&chain_kind)
+            .unwrap_or_else(|| env::panic_str("ERR_STANDBY_CONNECTOR_NOT_SET"));
+        let mut config = self.require_chain_configured(chain_kind);
+        let old_connector = config.connector.clone();
+
+        // The standby is a separate deployment, so it gets its own registry id and
+        // the results of the transfers in flight are still attributed to the old connector
+        self.register_connector(chain_kind, new_connector.clone());
+        config.connector = new_connector.clone();
+        self.utxo_chain_connectors.insert(chain_kind, config);
+        self.connector_interface_versions.remove(&chain_kind);
+        self.standby_connectors.insert(&chain_kind, &old_connector);
+
+        env::log_str(
+            &OmniBridgeEvent::ConnectorFailoverEvent {
+                chain_kind,
+                old_connector,
+                new_connector,
+                is_automatic,
+            }
+            .to_log_string(),
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::{near, AccountId};
+use omni_types::{ChainKind, TransferId};
+
+use crate::helpers::SdkExpect;
+use crate::{Contract, ContractExt, Role};
+
+pub type ConnectorId = u32;
+
+/// Connector referenced by its id in the state of the submitted transfers,
+/// so the account can be migrated without losing track of them.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectorRecord {
+    pub chain_kind: ChainKind,
+    pub account_id: AccountId,
+}
+
+#[near]
+impl Contract {
+    /// Moves the connector of the UTXO chain to a new account. Unlike `add_utxo_chain_connector`,
+    /// the registry id is kept, so the transfers submitted to the old account are resolved
+    /// to the new one.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn migrate_utxo_chain_connector(&mut self, chain_kind: ChainKind, account_id: AccountId) {
+        let connector_id = self.chain_connector_id(chain_kind);
+        self.connectors.insert(
+            &connector_id,
+            &ConnectorRecord {
+                chain_kind,
+                account_id: account_id.clone(),
+            },
+        );
+
+        let mut config = self.require_chain_configured(chain_kind);
+        config.connector = account_id;
+        self.utxo_chain_connectors.insert(chain_kind, config);
+        // The new account has to declare its interface version again
+        self.connector_interface_versions.remove(&chain_kind);
+    }
+
+    pub fn get_connector(&self, connector_id: ConnectorId) -> Option<ConnectorRecord> {
+        self.connectors.get(&connector_id)
+    }
+
+    pub fn get_chain_connector_id(&self, chain_kind: ChainKind) -> Option<ConnectorId> {
+        self.chain_connector_ids.get(&chain_kind)
+    }
+}
+
+impl Contract {
+    pub(crate) fn register_connector(
+        &mut self,
+        chain_kind: ChainKind,
+        account_id: AccountId,
+    ) -> ConnectorId {
+        self.last_connector_id += 1;
+        let connector_id = self.last_connector_id;
+        self.connectors.insert(
+            &connector_id,
+            &ConnectorRecord {
+                chain_kind,
+                account_id,
+            },
+        );
+        self.chain_connector_ids.insert(&chain_kind, &connector_id);
+        connector_id
+    }
+
+    // Connectors added before the registry are registered on first use.
+    pub(crate) fn chain_connector_id(&mut self, chain_kind: ChainKind) -> ConnectorId {
+        if let Some(connector_id) = self.chain_connector_ids.get(&chain_kind) {
+            return connector_id;
+        }
+        let connector = self.require_chain_configured(chain_kind).connector;
+        self.register_connector(chain_kind, connector)
+    }
+
+    pub(crate) fn resolve_connector(&self, connector_id: ConnectorId) -> AccountId {
+        self.connectors
+            .get(&connector_id)
+            .sdk_expect("ERR_CONNECTOR_NOT_REGISTERED")
+            .account_id
+    }
+
+    // Returns the connector the transfer was submitted to, or the current connector
+    // of the chain for the transfers submitted before the registry.
+    pub(crate) fn resolve_transfer_connector(
+        &self,
+        transfer_id: &TransferId,
+        chain_kind: ChainKind,
+    ) -> AccountId {
+        self.unreported_fee_connectors.get(transfer_id).map_or_else(
+            || self.require_chain_configured(chain_kind).connector,
+            |connector_id| self.resolve_connector(connector_id),
+        )
+    }
+}
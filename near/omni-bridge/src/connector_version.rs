@@ -0,0 +1,53 @@
+use near_sdk::{env, near, require};
+use omni_types::ChainKind;
+
+use crate::{Contract, ContractExt};
+
+// Version of the connectors that never declared their interface version. The connectors
+// deployed before the versioning already support fee bump (RBF) requests.
+pub const DEFAULT_CONNECTOR_INTERFACE_VERSION: u32 = 2;
+// Connectors below this version don't support fee bump (RBF) requests
+pub const FEE_BUMP_MIN_CONNECTOR_INTERFACE_VERSION: u32 = 2;
+// Connectors below this version can't sweep the addresses of the retired signer keys
+pub const LEGACY_SWEEP_MIN_CONNECTOR_INTERFACE_VERSION: u32 = 3;
+// Connectors below this version always send the swept funds to the bridge address
+pub const SWEEP_DESTINATION_MIN_CONNECTOR_INTERFACE_VERSION: u32 = 4;
+// Connectors below this version only accept the withdrawals of a single transfer
+pub const BATCH_WITHDRAW_MIN_CONNECTOR_INTERFACE_VERSION: u32 = 5;
+// Connectors below this version don't report the UTXOs received and spent by the bridge
+// address with `update_custody_utxos`
+pub const CUSTODY_REPORTS_MIN_CONNECTOR_INTERFACE_VERSION: u32 = 6;
+// Connectors below this version don't accept the `Consolidate` messages
+pub const CONSOLIDATION_MIN_CONNECTOR_INTERFACE_VERSION: u32 = 6;
+
+#[near]
+impl Contract {
+    /// Called by the connector of the UTXO chain to declare the version of the interface it implements.
+    pub fn set_connector_interface_version(&mut self, chain_kind: ChainKind, version: u32) {
+        require!(
+            env::predecessor_account_id() == self.require_chain_configured(chain_kind).connector,
+            "ERR_ONLY_CONNECTOR_CAN_SET_INTERFACE_VERSION"
+        );
+        self.connector_interface_versions
+            .insert(&chain_kind, &version);
+    }
+
+    pub fn get_connector_interface_version(&self, chain_kind: ChainKind) -> u32 {
+        self.connector_interface_versions
+            .get(&chain_kind)
+            .unwrap_or(DEFAULT_CONNECTOR_INTERFACE_VERSION)
+    }
+}
+
+impl Contract {
+    pub(crate) fn require_connector_interface_version(
+        &self,
+        chain_kind: ChainKind,
+        min_version: u32,
+    ) {
+        require!(
+            self.get_connector_interface_version(chain_kind) >= min_version,
+            "ERR_CONNECTOR_INTERFACE_VERSION_NOT_SUPPORTED"
+        );
+    }
+}
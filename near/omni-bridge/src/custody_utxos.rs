@@ -0,0 +1,138 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::{env, near, require};
+use omni_types::btc::{OutPoint, TxOut};
+use omni_types::ChainKind;
+
+use crate::connector_version::CUSTODY_REPORTS_MIN_CONNECTOR_INTERFACE_VERSION;
+use crate::helpers::SdkExpect;
+use crate::{Contract, ContractExt, Role};
+
+/// Unspent output held by the bridge address of a UTXO chain.
+#[near(serializers=[json])]
+#[derive(Debug, Clone)]
+pub struct CustodyUtxo {
+    pub outpoint: OutPoint,
+    pub output: TxOut,
+}
+
+#[near]
+impl Contract {
+    /// Requires the inputs of the withdrawals to the chain to be custody UTXOs known to the
+    /// locker. The registry has to be populated before the tracking is enabled, and is kept
+    /// up to date by the connector, so the connector has to implement the custody reports.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_custody_utxo_tracking(&mut self, chain_kind: ChainKind, enabled: bool) {
+        require!(self.is_utxo_chain(chain_kind), "ERR_NOT_UTXO_CHAIN");
+        if enabled {
+            self.require_connector_interface_version(
+                chain_kind,
+                CUSTODY_REPORTS_MIN_CONNECTOR_INTERFACE_VERSION,
+            );
+            self.custody_tracked_chains.insert(&chain_kind);
+        } else {
+            self.custody_tracked_chains.remove(&chain_kind);
+        }
+    }
+
+    pub fn is_custody_utxo_tracking_enabled(&self, chain_kind: ChainKind) -> bool {
+        self.custody_tracked_chains.contains(&chain_kind)
+    }
+
+    /// Records the UTXOs received and spent by the bridge address, reported by the connector
+    /// of the chain once it has verified the transactions. The connectors declaring the
+    /// interface version 6 or above report every transaction of the bridge address. The spent
+    /// UTXOs are released from the reservations of their withdrawals. The DAO can correct
+    /// the registry.
+    pub fn update_custody_utxos(
+        &mut self,
+        chain_kind: ChainKind,
+        added: Vec<CustodyUtxo>,
+        spent: Vec<OutPoint>,
+    ) {
+        let predecessor = env::predecessor_account_id();
+        require!(
+            self.get_chain_connector(chain_kind).as_ref() == Some(&predecessor)
+                || self.acl_has_role(Role::DAO.into(), predecessor),
+            "ERR_CUSTODY_UPDATE_NOT_ALLOWED"
+        );
+
+        for utxo in added {
+            self.custody_utxos
+                .insert(&(chain_kind, utxo.outpoint), &utxo.output);
+        }
+        self.spend_custody_utxos(chain_kind, &spent);
+        // The spends are confirmed, so the inputs can't be reused by another withdrawal
+        self.release_outpoints(chain_kind, &spent);
+    }
+
+    pub fn get_custody_utxo(&self, chain_kind: ChainKind, outpoint: OutPoint) -> Option<TxOut> {
+        self.custody_utxos.get(&(chain_kind, outpoint))
+    }
+}
+
+impl Contract {
+    pub(crate) fn check_custody_utxos(&self, chain_kind: ChainKind, outpoints: &[OutPoint]) {
+        if !self.custody_tracked_chains.contains(&chain_kind) {
+            return;
+        }
+        require!(
+            outpoints
+                .iter()
+                .all(|outpoint| self.custody_utxos.contains_key(&(chain_kind, *outpoint))),
+            "ERR_INPUT_NOT_IN_CUSTODY"
+        );
+    }
+
+    // The connector takes the network fee from the transferred amount and returns the rest of
+    // the inputs to the bridge. The outputs to the scripts of the inputs are that change, so
+    // the check needs the values and scripts of the inputs from the registry. The payout to
+    // the recipient is checked by the caller, whether or not the chain is tracked.
+    pub(crate) fn check_utxo_withdraw_outputs(
+        &self,
+        chain_kind: ChainKind,
+        outpoints: &[OutPoint],
+        outputs: &[TxOut],
+        amount: u128,
+    ) {
+        if !self.custody_tracked_chains.contains(&chain_kind) {
+            return;
+        }
+        let inputs: Vec<TxOut> = outpoints
+            .iter()
+            .map(|outpoint| {
+                self.custody_utxos
+                    .get(&(chain_kind, *outpoint))
+                    .sdk_expect("ERR_INPUT_NOT_IN_CUSTODY")
+            })
+            .collect();
+        let input_value: u128 = inputs.iter().map(|input| u128::from(input.value)).sum();
+        let output_value: u128 = outputs.iter().map(|output| u128::from(output.value)).sum();
+        let spent_value: u128 = outputs
+            .iter()
+            .filter(|output| {
+                inputs
+                    .iter()
+                    .all(|input| input.script_pubkey != output.script_pubkey)
+            })
+            .map(|output| u128::from(output.value))
+            .sum();
+
+        require!(
+            output_value <= input_value,
+            "ERR_WITHDRAW_OUTPUTS_EXCEED_INPUTS"
+        );
+        let network_fee = input_value - output_value;
+        require!(
+            spent_value.saturating_add(network_fee) <= amount,
+            "ERR_WITHDRAW_SPENDS_ABOVE_AMOUNT"
+        );
+    }
+
+    // The inputs of a withdrawal accepted by the connector are spent, whether or not
+    // the tracking is enabled, so the registry stays accurate when it is enabled later.
+    pub(crate) fn spend_custody_utxos(&mut self, chain_kind: ChainKind, outpoints: &[OutPoint]) {
+        for outpoint in outpoints {
+            self.custody_utxos.remove(&(chain_kind, *outpoint));
+        }
+    }
+}
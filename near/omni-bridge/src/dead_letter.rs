@@ -0,0 +1,233 @@
+use near_plugins::{access_control_any, pause, AccessControllable, Pausable};
+use near_sdk::{env, near, require, AccountId, Gas, NearToken, Promise, PromiseError};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::{ChainKind, TransferId, TransferMessage};
+
+use crate::helpers::SdkExpect;
+use crate::locked::LockedState;
+use crate::storage::{TransferMessageStorage, TransferMessageStorageValue};
+use crate::{Contract, ContractExt, Role};
+
+pub const DEFAULT_MAX_TRANSFER_RESTORES: u32 = 3;
+const REFUND_DEAD_LETTER_TRANSFER_CALLBACK_GAS: Gas = Gas::from_tgas(10);
+
+#[near]
+impl Contract {
+    /// Sets how many times a transfer can be restored after a failed submission
+    /// before it is moved to the dead-letter queue.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_max_transfer_restores(&mut self, max_restores: u32) {
+        self.max_transfer_restores = max_restores;
+    }
+
+    pub fn get_max_transfer_restores(&self) -> u32 {
+        self.max_transfer_restores
+    }
+
+    pub fn get_transfer_restore_count(&self, transfer_id: TransferId) -> u32 {
+        self.transfer_restore_counts
+            .get(&transfer_id)
+            .unwrap_or_default()
+    }
+
+    pub fn get_dead_letter_transfer(
+        &self,
+        transfer_id: TransferId,
+    ) -> Option<TransferMessageStorageValue> {
+        self.dead_letter_transfers
+            .get(&transfer_id)
+            .map(TransferMessageStorage::into_main)
+    }
+
+    /// Moves the transfer from the dead-letter queue back to the pending transfers
+    /// with a reset restore counter, so it can be submitted again.
+    /// Can be called by the DAO or by the owner of the transfer.
+    pub fn retry_dead_letter_transfer(&mut self, transfer_id: TransferId) {
+        self.require_dead_letter_transfer_owner_or_dao(transfer_id);
+        self.requeue_dead_letter_transfer(transfer_id);
+    }
+
+    /// Refunds the tokens and the native fee of a dead-letter transfer initiated on NEAR
+    /// to its owner. The transfer failed on the connector side, so the token fee is refunded
+    /// as well.
+    #[pause(except(roles(Role::DAO)))]
+    pub fn refund_dead_letter_transfer(&mut self, transfer_id: TransferId) -> Promise {
+        let owner = env::predecessor_account_id();
+        let transfer = self
+            .dead_letter_transfers
+            .get(&transfer_id)
+            .map(TransferMessageStorage::into_main)
+            .sdk_expect("ERR_DEAD_LETTER_TRANSFER_NOT_FOUND");
+        require!(transfer.owner == owner, "ERR_ONLY_TRANSFER_OWNER");
+        // The owner of a transfer from another chain is the relayer that finalised it
+        require!(
+            transfer_id.origin_chain == ChainKind::Near,
+            "ERR_ONLY_TRANSFER_SENDER_CAN_CANCEL"
+        );
+        self.dead_letter_transfers.remove(&transfer_id);
+        let token = self.get_token_id(&transfer.message.token);
+        self.unlock_amount(&token, LockedState::DeadLetter, transfer.message.amount.0);
+
+        self.send_tokens(token, owner.clone(), transfer.message.amount, "")
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(REFUND_DEAD_LETTER_TRANSFER_CALLBACK_GAS)
+                    .refund_dead_letter_transfer_callback(transfer.message, owner),
+            )
+    }
+
+    // The transfer is settled once its tokens are refunded. Otherwise it is put back into the
+    // dead-letter queue, e.g. when the owner isn't registered with the token.
+    #[private]
+    pub fn refund_dead_letter_transfer_callback(
+        &mut self,
+        transfer_message: TransferMessage,
+        owner: AccountId,
+        #[callback_result] call_result: &Result<(), PromiseError>,
+    ) {
+        let transfer_id = transfer_message.get_transfer_id();
+        let token = self.get_token_id(&transfer_message.token);
+        if call_result.is_err() {
+            self.lock_amount(&token, LockedState::DeadLetter, transfer_message.amount.0);
+            self.dead_letter_transfers.insert(
+                &transfer_id,
+                &TransferMessageStorage::V3(TransferMessageStorageValue {
+                    message: transfer_message,
+                    owner,
+                }),
+            );
+            return;
+        }
+
+        self.remove_transfer_records(&transfer_id, &owner);
+        self.remove_transfer_timestamps(&transfer_id, &owner);
+        self.record_tvl_release(&token, transfer_message.amount.0);
+        // The escalated part of the native fee goes back to the escalation budget
+        let escalated_native_fee = self.reclaim_fee_escalation(&transfer_id);
+        if let Some(mut storage) = self.accounts_balances.get(&owner) {
+            storage.available = storage.available.saturating_add(
+                NearToken::from_yoctonear(transfer_message.fee.native_fee.0)
+                    .saturating_sub(escalated_native_fee),
+            );
+            self.accounts_balances.insert(&owner, &storage);
+        }
+
+        env::log_str(
+            &OmniBridgeEvent::CancelTransferEvent {
+                transfer_message,
+                beneficiary: owner,
+            }
+            .to_log_string(),
+        );
+    }
+}
+
+impl Contract {
+    pub(crate) fn requeue_dead_letter_transfer(&mut self, transfer_id: TransferId) {
+        let transfer = self
+            .dead_letter_transfers
+            .remove(&transfer_id)
+            .map(TransferMessageStorage::into_main)
+            .sdk_expect("ERR_DEAD_LETTER_TRANSFER_NOT_FOUND");
+
+        let token = self.get_token_id(&transfer.message.token);
+        self.move_locked_amount(
+            &token,
+            LockedState::DeadLetter,
+            LockedState::Pending,
+            transfer.message.amount.0,
+        );
+        self.insert_raw_transfer(transfer.message, transfer.owner);
+    }
+
+    // The DAO, the unrestricted relayers and the owner of a transfer initiated on NEAR. The
+    // owner of a transfer from another chain is the relayer that finalised it.
+    pub(crate) fn is_trusted_submitter(
+        &self,
+        submitter: &AccountId,
+        transfer_id: &TransferId,
+        transfer_owner: &AccountId,
+    ) -> bool {
+        (transfer_id.origin_chain == ChainKind::Near && submitter == transfer_owner)
+            || self.acl_has_role(Role::DAO.into(), submitter.clone())
+            || self.acl_has_role(Role::UnrestrictedRelayer.into(), submitter.clone())
+    }
+
+    fn require_dead_letter_transfer_owner_or_dao(&self, transfer_id: TransferId) {
+        let predecessor = env::predecessor_account_id();
+        let is_owner = self
+            .dead_letter_transfers
+            .get(&transfer_id)
+            .map(TransferMessageStorage::into_main)
+            .is_some_and(|transfer| transfer.owner == predecessor);
+        require!(
+            is_owner || self.acl_has_role(Role::DAO.into(), predecessor),
+            "ERR_ONLY_TRANSFER_OWNER_OR_DAO"
+        );
+    }
+
+    // Restores the transfer after a failed submission to the connector.
+    // Once the transfer has been restored after `max_transfer_restores` connector failures it is
+    // parked in the dead-letter queue instead, to prevent endless storage writes by failing
+    // callbacks. The failures that are not attributed to the connector are not counted.
+    pub(crate) fn restore_transfer(
+        &mut self,
+        transfer_message: TransferMessage,
+        transfer_owner: AccountId,
+        is_connector_failure: bool,
+    ) -> bool {
+        let transfer_id = transfer_message.get_transfer_id();
+        let token = self.get_token_id(&transfer_message.token);
+        let restore_count =
+            self.get_transfer_restore_count(transfer_id) + u32::from(is_connector_failure);
+
+        if restore_count > self.max_transfer_restores {
+            self.transfer_restore_counts.remove(&transfer_id);
+            self.move_locked_amount(
+                &token,
+                LockedState::InFlight,
+                LockedState::DeadLetter,
+                transfer_message.amount.0,
+            );
+            self.dead_letter_transfers.insert(
+                &transfer_id,
+                &TransferMessageStorage::V3(TransferMessageStorageValue {
+                    message: transfer_message.clone(),
+                    owner: transfer_owner,
+                }),
+            );
+
+            env::log_str(
+                &OmniBridgeEvent::DeadLetterTransferEvent {
+                    transfer_message,
+                    restore_count,
+                }
+                .to_log_string(),
+            );
+            false
+        } else {
+            if is_connector_failure {
+                self.transfer_restore_counts
+                    .insert(&transfer_id, &restore_count);
+            }
+            self.move_locked_amount(
+                &token,
+                LockedState::InFlight,
+                LockedState::Pending,
+                transfer_message.amount.0,
+            );
+            self.emit_event(&OmniBridgeEvent::TransferRestoredEvent {
+                transfer_id,
+                chain_kind: transfer_message.get_destination_chain(),
+                amount: transfer_message.amount,
+                restore_count,
+            });
+            self.insert_raw_transfer(transfer_message, transfer_owner);
+            true
+        }
+    }
+
+    pub(crate) fn clear_transfer_restore_count(&mut self, transfer_id: &TransferId) {
+        self.transfer_restore_counts.remove(transfer_id);
+    }
+}
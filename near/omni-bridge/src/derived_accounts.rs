@@ -0,0 +1,59 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::{env, near, AccountId, NearToken, Promise};
+use omni_types::OmniAddress;
+
+use crate::{Contract, ContractExt, Role};
+
+#[near]
+impl Contract {
+    /// Sets the amount of NEAR used to create the implicit account of an origin-chain sender
+    /// the first time it receives bridged tokens. Zero disables the auto-creation.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_derived_account_funding(&mut self, amount: NearToken) {
+        self.derived_account_funding = amount;
+    }
+
+    pub fn get_derived_account_funding(&self) -> NearToken {
+        self.derived_account_funding
+    }
+
+    /// Returns the deterministic NEAR account derived from the origin-chain address.
+    pub fn get_derived_near_account_id(&self, address: OmniAddress) -> Option<AccountId> {
+        address.get_implicit_near_account_id()
+    }
+
+    pub fn is_derived_account_funded(&self, account_id: AccountId) -> bool {
+        self.funded_derived_accounts.contains(&account_id)
+    }
+}
+
+impl Contract {
+    // Creates the implicit account of the sender if the transfer is addressed to it, so users coming
+    // from other chains can receive tokens without owning a NEAR account yet. Token storage for the
+    // account is registered through the regular storage deposit actions of the relayer.
+    // Returns the amount of NEAR that has to be covered by the relayer, who is compensated by the transfer fee.
+    pub(crate) fn fund_derived_account_if_needed(
+        &mut self,
+        recipient: &AccountId,
+        sender: &OmniAddress,
+    ) -> NearToken {
+        if self.derived_account_funding.is_zero()
+            || sender.get_implicit_near_account_id().as_ref() != Some(recipient)
+        {
+            return NearToken::from_yoctonear(0);
+        }
+
+        let storage_usage = env::storage_usage();
+        if !self.funded_derived_accounts.insert(recipient) {
+            return NearToken::from_yoctonear(0);
+        }
+        let storage_cost = env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into());
+
+        Promise::new(recipient.clone())
+            .transfer(self.derived_account_funding)
+            .detach();
+
+        storage_cost.saturating_add(self.derived_account_funding)
+    }
+}
@@ -0,0 +1,145 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::U128;
+use near_sdk::{borsh, env, near, require, NearToken, Promise, PromiseError};
+use omni_types::mpc_types::SignatureResponse;
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::{
+    ChainKind, EvmDestinationCall, Fee, OmniAddress, PayloadType, TransferId,
+    TransferMessagePayload, TransferMessageWithCallPayload,
+};
+
+use crate::helpers::SdkExpect;
+use crate::{Contract, ContractExt, Role, SIGN_TRANSFER_CALLBACK_GAS};
+
+#[near]
+impl Contract {
+    /// Sets the native fee per unit of gas of the destination calls on the EVM chain. The calls
+    /// to a chain are accepted only once its fee is set; `None` disables them.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_destination_call_gas_fee(
+        &mut self,
+        chain_kind: ChainKind,
+        fee_per_gas: Option<U128>,
+    ) {
+        require!(
+            chain_kind.is_evm_chain(),
+            "ERR_DESTINATION_CALL_NOT_SUPPORTED"
+        );
+        if let Some(fee_per_gas) = fee_per_gas {
+            self.destination_call_gas_fees
+                .insert(&chain_kind, &fee_per_gas);
+        } else {
+            self.destination_call_gas_fees.remove(&chain_kind);
+        }
+    }
+
+    pub fn get_destination_call_gas_fee(&self, chain_kind: ChainKind) -> Option<U128> {
+        self.destination_call_gas_fees.get(&chain_kind)
+    }
+
+    pub fn get_destination_call(&self, transfer_id: TransferId) -> Option<EvmDestinationCall> {
+        self.destination_calls.get(&transfer_id)
+    }
+
+    #[private]
+    pub fn sign_transfer_with_call_callback(
+        &mut self,
+        #[callback_result] call_result: Result<SignatureResponse, PromiseError>,
+        #[serializer(borsh)] message_payload: TransferMessageWithCallPayload,
+        #[serializer(borsh)] fee: &Fee,
+    ) {
+        if let Ok(signature) = call_result {
+            self.complete_transfer_signing(message_payload.transfer_id, fee);
+
+            env::log_str(
+                &OmniBridgeEvent::SignTransferWithCallEvent {
+                    signature,
+                    message_payload,
+                }
+                .to_log_string(),
+            );
+        }
+    }
+}
+
+impl Contract {
+    // The native fee of the transfer has to cover the extra gas of the call on top of the fee
+    // of the relayer, which is checked by the fee floors.
+    pub(crate) fn check_destination_call(
+        &self,
+        recipient: &OmniAddress,
+        destination_call: &EvmDestinationCall,
+        native_fee: U128,
+    ) {
+        let fee_per_gas = self
+            .destination_call_gas_fees
+            .get(&recipient.get_chain())
+            .sdk_expect("ERR_DESTINATION_CALL_NOT_SUPPORTED");
+        require!(
+            destination_call.gas_limit.0 > 0,
+            "ERR_INVALID_DESTINATION_CALL_GAS_LIMIT"
+        );
+        let gas_fee = fee_per_gas
+            .0
+            .checked_mul(destination_call.gas_limit.0.into())
+            .sdk_expect("ERR_DESTINATION_CALL_GAS_FEE_OVERFLOW");
+        require!(
+            native_fee.0 >= gas_fee,
+            "ERR_NATIVE_FEE_BELOW_DESTINATION_CALL_GAS_FEE"
+        );
+    }
+
+    // Returns the storage cost of the record.
+    pub(crate) fn add_destination_call(
+        &mut self,
+        transfer_id: &TransferId,
+        destination_call: &EvmDestinationCall,
+    ) -> NearToken {
+        let storage_usage = env::storage_usage();
+        self.destination_calls.insert(transfer_id, destination_call);
+        env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into())
+    }
+
+    pub(crate) fn required_balance_for_destination_call(
+        transfer_id: &TransferId,
+        destination_call: &EvmDestinationCall,
+    ) -> NearToken {
+        let record_len: u64 = borsh::to_vec(&(transfer_id, destination_call))
+            .sdk_expect("ERR_BORSH")
+            .len()
+            .try_into()
+            .sdk_expect("ERR_CAST");
+
+        env::storage_byte_cost().saturating_mul((Self::get_basic_storage() + record_len).into())
+    }
+
+    pub(crate) fn sign_transfer_with_call(
+        &self,
+        transfer_payload: TransferMessagePayload,
+        destination_call: EvmDestinationCall,
+        fee: &Fee,
+    ) -> Promise {
+        let transfer_payload = TransferMessageWithCallPayload {
+            prefix: PayloadType::TransferMessageWithCall,
+            destination_nonce: transfer_payload.destination_nonce,
+            transfer_id: transfer_payload.transfer_id,
+            token_address: transfer_payload.token_address,
+            amount: transfer_payload.amount,
+            recipient: transfer_payload.recipient,
+            fee_recipient: transfer_payload.fee_recipient,
+            destination_call,
+        };
+
+        let request = self.build_signature_request(
+            transfer_payload.recipient.get_chain(),
+            &borsh::to_vec(&transfer_payload).sdk_expect("ERR_BORSH"),
+        );
+
+        self.request_signature(request).then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(SIGN_TRANSFER_CALLBACK_GAS)
+                .sign_transfer_with_call_callback(transfer_payload, fee),
+        )
+    }
+}
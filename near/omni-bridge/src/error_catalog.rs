@@ -0,0 +1,1696 @@
+use near_sdk::json_types::Base58CryptoHash;
+use near_sdk::{env, near};
+
+use crate::{Contract, ContractExt};
+
+struct ErrorDefinition {
+    code: u32,
+    name: &'static str,
+    retryable: bool,
+    description: &'static str,
+}
+
+// The codes are stable: new errors are appended with the next code, and the codes of the
+// removed errors are not reused. The legacy panic messages, kept for the integrators matching
+// on them, are listed with their text as the name.
+const ERROR_CATALOG: &[ErrorDefinition] = &[
+    ErrorDefinition {
+        code: 1,
+        name: "ERR_AMOUNT_OVERFLOW",
+        retryable: false,
+        description: "The amount overflows after the fee or decimals are applied.",
+    },
+    ErrorDefinition {
+        code: 2,
+        name: "ERR_BLOCK_NOT_FINISHED",
+        retryable: true,
+        description: "The aggregated events of the current block can only be flushed in a later block.",
+    },
+    ErrorDefinition {
+        code: 3,
+        name: "ERR_BORSH",
+        retryable: false,
+        description: "A value could not be serialized with borsh.",
+    },
+    ErrorDefinition {
+        code: 4,
+        name: "ERR_CAST",
+        retryable: false,
+        description: "A number does not fit in the target integer type.",
+    },
+    ErrorDefinition {
+        code: 5,
+        name: "ERR_CONNECTOR_INTERFACE_VERSION_NOT_SUPPORTED",
+        retryable: true,
+        description: "The connector of the chain does not declare the interface version required by the call.",
+    },
+    ErrorDefinition {
+        code: 6,
+        name: "ERR_CONNECTOR_NOT_REGISTERED",
+        retryable: false,
+        description: "The connector id is not in the connector registry.",
+    },
+    ErrorDefinition {
+        code: 7,
+        name: "ERR_CONTRACT_AVAILABLE_BALANCE_TOO_LOW",
+        retryable: true,
+        description: "The call would bring the available balance of the bridge below the safety margin.",
+    },
+    ErrorDefinition {
+        code: 8,
+        name: "ERR_CUTOVER_HEIGHT_NOT_REACHED",
+        retryable: true,
+        description: "The key rotation can't be finished before its cutover height.",
+    },
+    ErrorDefinition {
+        code: 9,
+        name: "ERR_DEAD_LETTER_TRANSFER_NOT_FOUND",
+        retryable: false,
+        description: "The transfer is not in the dead letter queue.",
+    },
+    ErrorDefinition {
+        code: 10,
+        name: "ERR_DEPLOYER_NOT_SET",
+        retryable: false,
+        description: "No token deployer is configured for the chain.",
+    },
+    ErrorDefinition {
+        code: 11,
+        name: "ERR_EVENT_NOT_AGGREGATED",
+        retryable: false,
+        description: "The event type is not emitted with the per-block aggregation policy.",
+    },
+    ErrorDefinition {
+        code: 12,
+        name: "ERR_EVENT_NOT_CONFIGURABLE",
+        retryable: false,
+        description: "The emission policy of the event type can't be changed.",
+    },
+    ErrorDefinition {
+        code: 13,
+        name: "ERR_EXPECTED_TO_OVERWRITE_TOKEN_ADDRESS",
+        retryable: false,
+        description: "The token address was expected to be bound already.",
+    },
+    ErrorDefinition {
+        code: 14,
+        name: "ERR_EXTRA_MSG_REQUIRED",
+        retryable: false,
+        description: "Transfers to the UTXO chain must carry the maximum gas fee message.",
+    },
+    ErrorDefinition {
+        code: 15,
+        name: "ERR_FAILED_TO_GET_TOKEN_ADDRESS",
+        retryable: false,
+        description: "The token is not bound on the destination chain.",
+    },
+    ErrorDefinition {
+        code: 16,
+        name: "ERR_FAILED_TO_GET_ZERO_ADDRESS",
+        retryable: false,
+        description: "The chain has no zero address.",
+    },
+    ErrorDefinition {
+        code: 17,
+        name: "ERR_FAST_TRANSFER_ALREADY_FINALISED",
+        retryable: false,
+        description: "The fast transfer is already finalised.",
+    },
+    ErrorDefinition {
+        code: 18,
+        name: "ERR_FAST_TRANSFER_NOT_FINALISED",
+        retryable: true,
+        description: "The fast transfer is not finalised yet.",
+    },
+    ErrorDefinition {
+        code: 19,
+        name: "ERR_FAST_TRANSFER_NOT_FOUND",
+        retryable: false,
+        description: "The fast transfer does not exist.",
+    },
+    ErrorDefinition {
+        code: 20,
+        name: "ERR_FEE_BELOW_RELAYER_FEE_FLOOR",
+        retryable: false,
+        description: "The fee is below the relayer fee floors of the destination chain.",
+    },
+    ErrorDefinition {
+        code: 21,
+        name: "ERR_FEE_EXCEEDS_AMOUNT",
+        retryable: false,
+        description: "The fee is greater than the transferred amount.",
+    },
+    ErrorDefinition {
+        code: 22,
+        name: "ERR_FEE_RECIPIENT_NOT_SET_OR_EMPTY",
+        retryable: false,
+        description: "The fast transfer has no fee recipient to claim the fee.",
+    },
+    ErrorDefinition {
+        code: 23,
+        name: "ERR_FEE_SPONSOR_ALREADY_REGISTERED",
+        retryable: false,
+        description: "The fee sponsor is already registered.",
+    },
+    ErrorDefinition {
+        code: 24,
+        name: "ERR_FEE_SPONSOR_BALANCE_NOT_EMPTY",
+        retryable: false,
+        description: "The fee sponsor must withdraw its balance before unregistering.",
+    },
+    ErrorDefinition {
+        code: 25,
+        name: "ERR_FEE_SPONSOR_NOT_REGISTERED",
+        retryable: false,
+        description: "The fee sponsor is not registered.",
+    },
+    ErrorDefinition {
+        code: 26,
+        name: "ERR_FORCE_FINALIZE_NOT_APPROVED",
+        retryable: true,
+        description: "The force finalize proposal is not approved by a guardian yet.",
+    },
+    ErrorDefinition {
+        code: 27,
+        name: "ERR_FORCE_FINALIZE_PROPOSAL_NOT_FOUND",
+        retryable: false,
+        description: "The transfer has no force finalize proposal.",
+    },
+    ErrorDefinition {
+        code: 28,
+        name: "ERR_FORCE_FINALIZE_TIMELOCK_NOT_EXPIRED",
+        retryable: true,
+        description: "The timelock of the force finalize proposal has not expired yet.",
+    },
+    ErrorDefinition {
+        code: 29,
+        name: "ERR_GOVERNANCE_ACTION_ALREADY_EXECUTED",
+        retryable: false,
+        description: "The nonce of the governance action was already executed.",
+    },
+    ErrorDefinition {
+        code: 30,
+        name: "ERR_GOVERNANCE_EMITTER_NOT_SET",
+        retryable: false,
+        description: "No governance emitter is configured for the chain.",
+    },
+    ErrorDefinition {
+        code: 31,
+        name: "ERR_INSUFFICIENT_FEE_SPONSOR_BALANCE",
+        retryable: true,
+        description: "The fee sponsor balance does not cover the fee.",
+    },
+    ErrorDefinition {
+        code: 32,
+        name: "ERR_INSUFFICIENT_PROTOCOL_FEES",
+        retryable: false,
+        description: "The withdrawn amount exceeds the accrued protocol fees.",
+    },
+    ErrorDefinition {
+        code: 33,
+        name: "ERR_INVALID_ADDRESS_FORMAT",
+        retryable: false,
+        description: "The address format has an empty prefix or an invalid length range.",
+    },
+    ErrorDefinition {
+        code: 34,
+        name: "ERR_INVALID_ALLOWANCE_EXPIRY",
+        retryable: false,
+        description: "The oversize transfer allowance expires in the past.",
+    },
+    ErrorDefinition {
+        code: 35,
+        name: "ERR_INVALID_AMOUNT_PER_POINT",
+        retryable: false,
+        description: "The reward amount per point is zero.",
+    },
+    ErrorDefinition {
+        code: 36,
+        name: "ERR_INVALID_ATTACHED_DEPOSIT",
+        retryable: false,
+        description: "The attached deposit does not match the increase of the native fee.",
+    },
+    ErrorDefinition {
+        code: 37,
+        name: "ERR_INVALID_CANCELLATION_SIGNATURE",
+        retryable: false,
+        description: "The cancellation is not signed by the beneficiary of the transfer.",
+    },
+    ErrorDefinition {
+        code: 38,
+        name: "ERR_INVALID_CLAIM_SIGNATURE",
+        retryable: false,
+        description: "The claim of the unclaimed deposit is not signed by its recipient.",
+    },
+    ErrorDefinition {
+        code: 39,
+        name: "ERR_INVALID_CUTOVER_HEIGHT",
+        retryable: false,
+        description: "The cutover height of the key epoch is in the past.",
+    },
+    ErrorDefinition {
+        code: 40,
+        name: "ERR_INVALID_EPOCH_DURATION",
+        retryable: false,
+        description: "The reward epoch duration is zero.",
+    },
+    ErrorDefinition {
+        code: 41,
+        name: "ERR_INVALID_FAILOVER_POLICY",
+        retryable: false,
+        description: "The failover policy has an empty window or a failure rate above 100%.",
+    },
+    ErrorDefinition {
+        code: 42,
+        name: "ERR_INVALID_FAST_TRANSFER_AMOUNT",
+        retryable: false,
+        description: "The fast transfer amount does not match the transfer.",
+    },
+    ErrorDefinition {
+        code: 43,
+        name: "ERR_INVALID_FEE",
+        retryable: false,
+        description: "The fee does not match the fee of the transfer.",
+    },
+    ErrorDefinition {
+        code: 44,
+        name: "ERR_INVALID_FEE_BPS",
+        retryable: false,
+        description: "The inbound fee is above the maximum.",
+    },
+    ErrorDefinition {
+        code: 45,
+        name: "ERR_INVALID_FIN_TRANSFER_AMOUNT",
+        retryable: false,
+        description: "The finalised amount does not match the transfer.",
+    },
+    ErrorDefinition {
+        code: 46,
+        name: "ERR_INVALID_GOVERNANCE_ACTION",
+        retryable: false,
+        description: "The governance action can't be decoded or executed.",
+    },
+    ErrorDefinition {
+        code: 47,
+        name: "ERR_INVALID_GOVERNANCE_CHAIN",
+        retryable: false,
+        description: "The governance message comes from an unexpected chain.",
+    },
+    ErrorDefinition {
+        code: 48,
+        name: "ERR_INVALID_JUSTIFICATION_HASH",
+        retryable: false,
+        description: "The justification hash does not match the proposal.",
+    },
+    ErrorDefinition {
+        code: 49,
+        name: "ERR_INVALID_MAX_SLIPPAGE",
+        retryable: false,
+        description: "The maximum slippage of the fee conversion is above the limit.",
+    },
+    ErrorDefinition {
+        code: 50,
+        name: "ERR_INVALID_METADATA",
+        retryable: false,
+        description: "The token metadata is invalid.",
+    },
+    ErrorDefinition {
+        code: 51,
+        name: "ERR_INVALID_ORIGIN_SENDER",
+        retryable: false,
+        description: "The origin sender address is not valid for its chain.",
+    },
+    ErrorDefinition {
+        code: 52,
+        name: "ERR_INVALID_PROOF",
+        retryable: false,
+        description: "The prover rejected the proof.",
+    },
+    ErrorDefinition {
+        code: 53,
+        name: "ERR_INVALID_RECIPIENT",
+        retryable: false,
+        description: "Unclaimed deposits are not enabled for the chain of the recipient.",
+    },
+    ErrorDefinition {
+        code: 54,
+        name: "ERR_INVALID_RECIPIENT_CHAIN",
+        retryable: false,
+        description: "The recipient is on an unsupported chain.",
+    },
+    ErrorDefinition {
+        code: 55,
+        name: "ERR_INVALID_REWARD_TOKEN",
+        retryable: false,
+        description: "The token is not the reward token of the program.",
+    },
+    ErrorDefinition {
+        code: 56,
+        name: "ERR_INVALID_SAMPLING_RATE",
+        retryable: false,
+        description: "The event sampling rate is zero.",
+    },
+    ErrorDefinition {
+        code: 57,
+        name: "ERR_INVALID_SIGNATURE_PAYLOAD",
+        retryable: false,
+        description: "The payload can't be signed with the signature scheme of the chain.",
+    },
+    ErrorDefinition {
+        code: 58,
+        name: "ERR_INVALID_STATE",
+        retryable: false,
+        description: "The stored transfer has an unexpected recipient.",
+    },
+    ErrorDefinition {
+        code: 59,
+        name: "ERR_INVALID_TRANSFER_LABEL",
+        retryable: false,
+        description: "The transfer label is empty or too long.",
+    },
+    ErrorDefinition {
+        code: 60,
+        name: "ERR_JSON",
+        retryable: false,
+        description: "A value could not be serialized or parsed as JSON.",
+    },
+    ErrorDefinition {
+        code: 61,
+        name: "ERR_KEY_EXIST",
+        retryable: false,
+        description: "The transfer or init transfer promise is already stored.",
+    },
+    ErrorDefinition {
+        code: 62,
+        name: "ERR_KEY_ROTATION_IN_PROGRESS",
+        retryable: true,
+        description: "A key rotation of the UTXO chain is already in progress.",
+    },
+    ErrorDefinition {
+        code: 63,
+        name: "ERR_KEY_ROTATION_NOT_FOUND",
+        retryable: false,
+        description: "The UTXO chain has no registered key epochs.",
+    },
+    ErrorDefinition {
+        code: 64,
+        name: "ERR_KEY_ROTATION_NOT_IN_PROGRESS",
+        retryable: false,
+        description: "No key rotation of the UTXO chain is in progress.",
+    },
+    ErrorDefinition {
+        code: 65,
+        name: "ERR_LEGACY_EPOCH_OUTPOINT",
+        retryable: false,
+        description: "The withdrawal spends an outpoint of a retired key epoch.",
+    },
+    ErrorDefinition {
+        code: 66,
+        name: "ERR_LOWER_FEE",
+        retryable: false,
+        description: "The new fee is lower than the current fee of the transfer.",
+    },
+    ErrorDefinition {
+        code: 67,
+        name: "ERR_MESSAGE_ACCOUNT_NOT_REGISTERED",
+        retryable: true,
+        description: "The account paying the storage of the message has no storage deposit.",
+    },
+    ErrorDefinition {
+        code: 68,
+        name: "ERR_NEAR_WITHDRAW_FAILED",
+        retryable: true,
+        description: "Unwrapping wNEAR failed.",
+    },
+    ErrorDefinition {
+        code: 69,
+        name: "ERR_NEW_OWNER_NOT_REGISTERED",
+        retryable: true,
+        description: "The new owner of the transfer has no storage deposit.",
+    },
+    ErrorDefinition {
+        code: 70,
+        name: "ERR_NOTICE_NOT_SUPPORTED_FOR_CHAIN",
+        retryable: false,
+        description: "Incoming transfer notices are only signed for EVM and UTXO chains.",
+    },
+    ErrorDefinition {
+        code: 71,
+        name: "ERR_NOT_A_SCRIPT_HASH_ADDRESS",
+        retryable: false,
+        description: "The address is not a script hash address.",
+    },
+    ErrorDefinition {
+        code: 72,
+        name: "ERR_NOT_ENOUGH_ATTACHED_DEPOSIT",
+        retryable: false,
+        description: "The attached deposit does not cover the storage deposits of the tokens.",
+    },
+    ErrorDefinition {
+        code: 73,
+        name: "ERR_NOT_ENOUGH_BALANCE_FOR_FEE",
+        retryable: true,
+        description: "The storage balance does not cover the native fee.",
+    },
+    ErrorDefinition {
+        code: 74,
+        name: "ERR_NOT_UTXO_CHAIN",
+        retryable: false,
+        description: "The chain is not a UTXO chain.",
+    },
+    ErrorDefinition {
+        code: 75,
+        name: "ERR_NO_AGGREGATED_EVENTS",
+        retryable: false,
+        description: "No events of the type are aggregated.",
+    },
+    ErrorDefinition {
+        code: 76,
+        name: "ERR_NO_REWARDS_TO_CLAIM",
+        retryable: false,
+        description: "The account has no rewards to claim.",
+    },
+    ErrorDefinition {
+        code: 77,
+        name: "ERR_NO_SWEEP_INPUTS",
+        retryable: false,
+        description: "The sweep has no inputs.",
+    },
+    ErrorDefinition {
+        code: 78,
+        name: "ERR_OLD_TOKEN_NOT_DEPLOYED",
+        retryable: false,
+        description: "The migrated token was not deployed by the bridge.",
+    },
+    ErrorDefinition {
+        code: 79,
+        name: "ERR_ONLY_CONNECTOR_CAN_REPORT_FEE_RECIPIENT",
+        retryable: false,
+        description: "Only the connector of the transfer can report its fee recipient.",
+    },
+    ErrorDefinition {
+        code: 80,
+        name: "ERR_ONLY_CONNECTOR_CAN_SET_INTERFACE_VERSION",
+        retryable: false,
+        description: "Only the connector of the chain can declare its interface version.",
+    },
+    ErrorDefinition {
+        code: 81,
+        name: "ERR_ONLY_FEE_RECIPIENT_CAN_CLAIM",
+        retryable: false,
+        description: "Only the fee recipient can claim the fee.",
+    },
+    ErrorDefinition {
+        code: 82,
+        name: "ERR_ONLY_FEE_SPONSOR_OWNER",
+        retryable: false,
+        description: "Only the owner of the fee sponsor can manage it.",
+    },
+    ErrorDefinition {
+        code: 83,
+        name: "ERR_ONLY_TRANSFER_INITIATOR_CAN_SET_LABELS",
+        retryable: false,
+        description: "Only the sender of the transfer can label it.",
+    },
+    ErrorDefinition {
+        code: 84,
+        name: "ERR_ONLY_TRANSFER_OWNER",
+        retryable: false,
+        description: "Only the owner of the transfer can transfer its ownership.",
+    },
+    ErrorDefinition {
+        code: 85,
+        name: "ERR_OUTPOINT_ALREADY_RESERVED",
+        retryable: true,
+        description: "The outpoint is spent by another in-flight withdrawal.",
+    },
+    ErrorDefinition {
+        code: 86,
+        name: "ERR_PARSE_ACCOUNT",
+        retryable: false,
+        description: "The account id can't be parsed.",
+    },
+    ErrorDefinition {
+        code: 87,
+        name: "ERR_PARSE_ACCOUNT_ID",
+        retryable: false,
+        description: "The account id can't be parsed.",
+    },
+    ErrorDefinition {
+        code: 88,
+        name: "ERR_PARSE_MSG",
+        retryable: false,
+        description: "The msg can't be parsed.",
+    },
+    ErrorDefinition {
+        code: 89,
+        name: "ERR_PROPOSER_CANNOT_APPROVE",
+        retryable: false,
+        description: "The proposer of the force finalize can't approve it.",
+    },
+    ErrorDefinition {
+        code: 90,
+        name: "ERR_PROVER_FOR_CHAIN_KIND_NOT_REGISTERED",
+        retryable: false,
+        description: "No prover is registered for the chain.",
+    },
+    ErrorDefinition {
+        code: 91,
+        name: "ERR_READ_PROMISE_REGISTER",
+        retryable: false,
+        description: "The promise yield register can't be read.",
+    },
+    ErrorDefinition {
+        code: 92,
+        name: "ERR_READ_PROMISE_YIELD_ID",
+        retryable: false,
+        description: "The promise yield id can't be read.",
+    },
+    ErrorDefinition {
+        code: 93,
+        name: "ERR_REWARDS_NOT_ACTIVE",
+        retryable: false,
+        description: "The rewards program is not active.",
+    },
+    ErrorDefinition {
+        code: 94,
+        name: "ERR_REWARDS_NOT_CONFIGURED",
+        retryable: false,
+        description: "The rewards program is not configured.",
+    },
+    ErrorDefinition {
+        code: 95,
+        name: "ERR_REWARD_EPOCH_NOT_FINISHED",
+        retryable: true,
+        description: "The reward epoch has not finished yet.",
+    },
+    ErrorDefinition {
+        code: 96,
+        name: "ERR_SAME_TRANSFER_OWNER",
+        retryable: false,
+        description: "The new owner is the current owner of the transfer.",
+    },
+    ErrorDefinition {
+        code: 97,
+        name: "ERR_SCRIPT_HASH_RECIPIENT_NOT_ALLOWED",
+        retryable: false,
+        description: "The script hash recipient is not allowlisted.",
+    },
+    ErrorDefinition {
+        code: 98,
+        name: "ERR_SENDER_IS_NOT_CONNECTOR",
+        retryable: false,
+        description: "The sender is not the connector of the UTXO chain.",
+    },
+    ErrorDefinition {
+        code: 99,
+        name: "ERR_SERIALIZE_INIT_TRANSFER_RESUME_ARGS",
+        retryable: false,
+        description: "The arguments of the resumed init transfer can't be serialized.",
+    },
+    ErrorDefinition {
+        code: 100,
+        name: "ERR_SIGNER_NOT_ENOUGH_BALANCE",
+        retryable: true,
+        description: "The storage balance of the signer does not cover the call.",
+    },
+    ErrorDefinition {
+        code: 101,
+        name: "ERR_SIGNER_NOT_REGISTERED",
+        retryable: true,
+        description: "The signer has no storage deposit.",
+    },
+    ErrorDefinition {
+        code: 102,
+        name: "ERR_STANDBY_CONNECTOR_NOT_SET",
+        retryable: false,
+        description: "No standby connector is configured for the chain.",
+    },
+    ErrorDefinition {
+        code: 103,
+        name: "ERR_STANDBY_IS_PRIMARY_CONNECTOR",
+        retryable: false,
+        description: "The standby connector is the primary connector of the chain.",
+    },
+    ErrorDefinition {
+        code: 104,
+        name: "ERR_TOKEN_ALREADY_MIGRATED",
+        retryable: false,
+        description: "The token is already migrated.",
+    },
+    ErrorDefinition {
+        code: 105,
+        name: "ERR_TOKEN_DECIMALS_NOT_FOUND",
+        retryable: false,
+        description: "The decimals of the token are not registered.",
+    },
+    ErrorDefinition {
+        code: 106,
+        name: "ERR_TOKEN_EXIST",
+        retryable: false,
+        description: "The new token of the migration is already deployed.",
+    },
+    ErrorDefinition {
+        code: 107,
+        name: "ERR_TOKEN_NOT_FOUND",
+        retryable: false,
+        description: "The token is not known to the bridge.",
+    },
+    ErrorDefinition {
+        code: 108,
+        name: "ERR_TOKEN_NOT_MIGRATED",
+        retryable: false,
+        description: "The token is not migrated.",
+    },
+    ErrorDefinition {
+        code: 109,
+        name: "ERR_TOKEN_NOT_REGISTERED",
+        retryable: false,
+        description: "The token is not registered.",
+    },
+    ErrorDefinition {
+        code: 110,
+        name: "ERR_TOO_MANY_ORIGIN_SENDERS",
+        retryable: false,
+        description: "Retired: the origin senders beyond the first eight are no longer recorded.",
+    },
+    ErrorDefinition {
+        code: 111,
+        name: "ERR_TOO_MANY_TRANSFER_LABELS",
+        retryable: false,
+        description: "The transfer has too many labels.",
+    },
+    ErrorDefinition {
+        code: 112,
+        name: "ERR_TOO_MANY_WITHDRAW_INPUTS",
+        retryable: false,
+        description: "The withdrawal has more inputs than the limit of the chain.",
+    },
+    ErrorDefinition {
+        code: 113,
+        name: "ERR_TOO_MANY_WITHDRAW_OUTPUTS",
+        retryable: false,
+        description: "The withdrawal has more outputs than the limit of the chain.",
+    },
+    ErrorDefinition {
+        code: 114,
+        name: "ERR_TRANSFER_ALREADY_FINALISED",
+        retryable: false,
+        description: "The transfer is already finalised.",
+    },
+    ErrorDefinition {
+        code: 115,
+        name: "ERR_TRANSFER_ALREADY_SIGNED",
+        retryable: false,
+        description: "The transfer was signed and can no longer be cancelled.",
+    },
+    ErrorDefinition {
+        code: 116,
+        name: "ERR_TRANSFER_AMOUNT_ABOVE_CAP",
+        retryable: false,
+        description: "The amount is above the transfer cap of the token.",
+    },
+    ErrorDefinition {
+        code: 117,
+        name: "ERR_TRANSFER_BENEFICIARY_NOT_FOUND",
+        retryable: false,
+        description: "The transfer has no beneficiary.",
+    },
+    ErrorDefinition {
+        code: 118,
+        name: "ERR_TRANSFER_NOT_EXIST",
+        retryable: false,
+        description: "The transfer does not exist.",
+    },
+    ErrorDefinition {
+        code: 119,
+        name: "ERR_TRANSFER_QUOTA_EXCEEDED",
+        retryable: true,
+        description: "The transfer count quota of the period is exhausted.",
+    },
+    ErrorDefinition {
+        code: 120,
+        name: "ERR_TRANSFER_VOLUME_QUOTA_EXCEEDED",
+        retryable: true,
+        description: "The transfer volume quota of the period is exhausted.",
+    },
+    ErrorDefinition {
+        code: 121,
+        name: "ERR_UNCLAIMED_DEPOSIT_NOT_FOUND",
+        retryable: false,
+        description: "The unclaimed deposit does not exist.",
+    },
+    ErrorDefinition {
+        code: 122,
+        name: "ERR_UNKNOWN_FACTORY",
+        retryable: false,
+        description: "The emitter of the proof is not a known factory.",
+    },
+    ErrorDefinition {
+        code: 123,
+        name: "ERR_UNKNOWN_GOVERNANCE_EMITTER",
+        retryable: false,
+        description: "The emitter of the governance message is not the configured one.",
+    },
+    ErrorDefinition {
+        code: 124,
+        name: "ERR_UNREPORTED_FEE_TRANSFER_NOT_FOUND",
+        retryable: false,
+        description: "The transfer has no fee waiting for its recipient.",
+    },
+    ErrorDefinition {
+        code: 125,
+        name: "ERR_UNSUPPORTED_ADDRESS_FORMAT",
+        retryable: false,
+        description: "The recipient does not match any address format of the UTXO chain.",
+    },
+    ErrorDefinition {
+        code: 126,
+        name: "ERR_UNSUPPORTED_BENEFICIARY_KEY",
+        retryable: false,
+        description: "The key type of the beneficiary is not supported.",
+    },
+    ErrorDefinition {
+        code: 127,
+        name: "ERR_UNSUPPORTED_SIGNATURE_SCHEME",
+        retryable: false,
+        description: "The signature scheme is not supported for the chain.",
+    },
+    ErrorDefinition {
+        code: 128,
+        name: "ERR_UTXO_CONFIG_MISSING",
+        retryable: false,
+        description: "The UTXO chain has no connector configured.",
+    },
+    ErrorDefinition {
+        code: 129,
+        name: "ERR_WITHDRAW_MSG_TOO_LARGE",
+        retryable: false,
+        description: "The withdraw msg is longer than the limit of the chain.",
+    },
+    ErrorDefinition {
+        code: 130,
+        name: "ERR_ZERO_MAINTENANCE_FUNDING",
+        retryable: false,
+        description: "No deposit is attached to fund the maintenance budget.",
+    },
+    ErrorDefinition {
+        code: 131,
+        name: "ERR_NEAR_TRAITS_NOT_CONFIGURABLE",
+        retryable: false,
+        description: "The traits of NEAR can't be configured.",
+    },
+    ErrorDefinition {
+        code: 132,
+        name: "ERR_MEMO_NOT_SUPPORTED",
+        retryable: false,
+        description: "The destination chain doesn't deliver the msg of the transfer to the recipient.",
+    },
+    ErrorDefinition {
+        code: 133,
+        name: "ERR_SHIELDED_ADDRESS_NOT_SUPPORTED",
+        retryable: false,
+        description: "The recipient of the transfer is a shielded or unified Zcash address.",
+    },
+    ErrorDefinition {
+        code: 134,
+        name: "ERR_SHIELDED_OUTPUT_NOT_SUPPORTED",
+        retryable: false,
+        description: "An output of the Zcash withdrawal is not a transparent P2PKH or P2SH script.",
+    },
+    ErrorDefinition {
+        code: 135,
+        name: "ERR_UTXO_CHAIN_ALREADY_REGISTERED",
+        retryable: false,
+        description: "The UTXO chain already has a connector.",
+    },
+    ErrorDefinition {
+        code: 136,
+        name: "ERR_FEE_MODEL_MISMATCH",
+        retryable: false,
+        description: "The fee model differs from the one of the built-in validator of the chain.",
+    },
+    ErrorDefinition {
+        code: 137,
+        name: "ERR_UTXO_CHAIN_NOT_REGISTERED",
+        retryable: false,
+        description: "The UTXO chain has no connector.",
+    },
+    ErrorDefinition {
+        code: 138,
+        name: "ERR_INVALID_REVIEW_THRESHOLD",
+        retryable: false,
+        description: "The review threshold of the watchtowers is zero.",
+    },
+    ErrorDefinition {
+        code: 139,
+        name: "ERR_PENALTY_EXCEEDS_STAKE",
+        retryable: false,
+        description: "The penalty of the watchtowers is above their minimum stake.",
+    },
+    ErrorDefinition {
+        code: 140,
+        name: "ERR_WATCHTOWER_ALREADY_REGISTERED",
+        retryable: false,
+        description: "The account is already a watchtower.",
+    },
+    ErrorDefinition {
+        code: 141,
+        name: "ERR_INSUFFICIENT_WATCHTOWER_STAKE",
+        retryable: false,
+        description: "The stake of the watchtower is below the minimum stake or the penalty.",
+    },
+    ErrorDefinition {
+        code: 142,
+        name: "ERR_WATCHTOWER_HAS_ACTIVE_FLAGS",
+        retryable: false,
+        description: "The flags of the watchtower have not been reviewed yet.",
+    },
+    ErrorDefinition {
+        code: 143,
+        name: "ERR_TRANSFER_ALREADY_FLAGGED",
+        retryable: false,
+        description: "The watchtower already flagged the transfer.",
+    },
+    ErrorDefinition {
+        code: 144,
+        name: "ERR_TRANSFER_NOT_FLAGGED",
+        retryable: false,
+        description: "The transfer has no flags.",
+    },
+    ErrorDefinition {
+        code: 145,
+        name: "ERR_TRANSFER_HELD_FOR_REVIEW",
+        retryable: true,
+        description: "The transfer is flagged by the watchtowers and waits for the clearance of a guardian.",
+    },
+    ErrorDefinition {
+        code: 146,
+        name: "ERR_WATCHTOWERS_DISABLED",
+        retryable: false,
+        description: "The watchtowers are not configured.",
+    },
+    ErrorDefinition {
+        code: 147,
+        name: "ERR_WATCHTOWER_NOT_REGISTERED",
+        retryable: false,
+        description: "The account is not a watchtower.",
+    },
+    ErrorDefinition {
+        code: 148,
+        name: "ERR_NO_CLAIMABLE_FEES",
+        retryable: false,
+        description: "The caller has no fees of the asset to claim.",
+    },
+    ErrorDefinition {
+        code: 149,
+        name: "ERR_ONLY_TRANSFER_SENDER_CAN_CANCEL",
+        retryable: false,
+        description: "Only the transfers initiated on NEAR can be cancelled by their owner.",
+    },
+    ErrorDefinition {
+        code: 150,
+        name: "ERR_TRANSFER_HAS_BENEFICIARY",
+        retryable: false,
+        description: "The transfer has a beneficiary, which has to cancel it with its signature.",
+    },
+    ErrorDefinition {
+        code: 151,
+        name: "ERR_INVALID_TRANSFER_EXPIRY",
+        retryable: false,
+        description: "The expiry of the transfer must be in the future.",
+    },
+    ErrorDefinition {
+        code: 152,
+        name: "ERR_ONLY_NEAR_TRANSFERS_EXPIRE",
+        retryable: false,
+        description: "Only the transfers initiated on NEAR can be expired.",
+    },
+    ErrorDefinition {
+        code: 153,
+        name: "ERR_TRANSFER_EXPIRY_NOT_SET",
+        retryable: false,
+        description: "The sender did not set an expiry for the transfer.",
+    },
+    ErrorDefinition {
+        code: 154,
+        name: "ERR_TRANSFER_NOT_EXPIRED",
+        retryable: true,
+        description: "The transfer can be expired after its expiry timestamp.",
+    },
+    ErrorDefinition {
+        code: 155,
+        name: "ERR_BRIDGE_NOT_ACTIVATED",
+        retryable: true,
+        description: "The bridge accepts transfers once the DAO activates it.",
+    },
+    ErrorDefinition {
+        code: 156,
+        name: "ERR_BRIDGE_ALREADY_ACTIVATED",
+        retryable: false,
+        description: "The bootstrap checklist was already completed.",
+    },
+    ErrorDefinition {
+        code: 157,
+        name: "ERR_USE_ACTIVATE",
+        retryable: false,
+        description: "The last step of the bootstrap checklist is completed by activate.",
+    },
+    ErrorDefinition {
+        code: 158,
+        name: "ERR_BOOTSTRAP_PAUSE_MANAGER_NOT_SET",
+        retryable: false,
+        description: "A pause manager has to be granted before the activation.",
+    },
+    ErrorDefinition {
+        code: 159,
+        name: "ERR_BOOTSTRAP_NO_CHAIN_CONFIGURED",
+        retryable: false,
+        description: "A factory or a UTXO connector has to be added before the activation.",
+    },
+    ErrorDefinition {
+        code: 160,
+        name: "ERR_BOOTSTRAP_NO_TOKEN_BOUND",
+        retryable: false,
+        description: "A token has to be bound before the activation.",
+    },
+    ErrorDefinition {
+        code: 161,
+        name: "ERR_BOOTSTRAP_PROVER_NOT_SET",
+        retryable: false,
+        description: "Every chain with a factory needs a prover before the activation.",
+    },
+    ErrorDefinition {
+        code: 162,
+        name: "ERR_SUBMISSION_WINDOW_CLAIMED",
+        retryable: true,
+        description: "Another relayer registered to submit the transfer and its submission window has not ended.",
+    },
+    ErrorDefinition {
+        code: 163,
+        name: "ERR_DESTINATION_CALL_NOT_SUPPORTED",
+        retryable: false,
+        description: "The destination calls are not enabled for the destination chain.",
+    },
+    ErrorDefinition {
+        code: 164,
+        name: "ERR_INVALID_DESTINATION_CALL_GAS_LIMIT",
+        retryable: false,
+        description: "The gas limit of the destination call is zero.",
+    },
+    ErrorDefinition {
+        code: 165,
+        name: "ERR_NATIVE_FEE_BELOW_DESTINATION_CALL_GAS_FEE",
+        retryable: false,
+        description: "The native fee doesn't cover the gas of the destination call.",
+    },
+    ErrorDefinition {
+        code: 166,
+        name: "ERR_TRANSFER_MSG_TOO_LARGE",
+        retryable: false,
+        description: "The msg of the transfer exceeds the limit of the route, see get_route_limits.",
+    },
+    ErrorDefinition {
+        code: 167,
+        name: "ERR_PROOF_TOO_LARGE",
+        retryable: false,
+        description: "The proof exceeds the limit of the route, see get_route_limits.",
+    },
+    ErrorDefinition {
+        code: 168,
+        name: "ERR_FEE_RATE_NOT_SUPPORTED",
+        retryable: false,
+        description: "The fee of the withdrawals of the UTXO chain is bounded by its total amount, not by a rate.",
+    },
+    ErrorDefinition {
+        code: 169,
+        name: "ERR_INVALID_FEE_RATE",
+        retryable: false,
+        description: "The fee rate of the UTXO chain must be positive.",
+    },
+    ErrorDefinition {
+        code: 170,
+        name: "ERR_UTXO_FEE_RATE_ABOVE_MAX_FEE",
+        retryable: true,
+        description: "The fee rate of the oracle is above the fee bound of the transfer, the withdrawal can be submitted with an explicit bound or once the rate drops.",
+    },
+    ErrorDefinition {
+        code: 171,
+        name: "ERR_WITHDRAW_FEE_ABOVE_MAX_ALLOWED",
+        retryable: false,
+        description: "The fee bound of the Withdraw message is above the highest bound allowed by the DAO for the UTXO chain.",
+    },
+    ErrorDefinition {
+        code: 172,
+        name: "ERR_WITHDRAW_OUTPUT_BELOW_DUST_LIMIT",
+        retryable: false,
+        description: "An output of the Withdraw message is below the dust limit of the UTXO chain.",
+    },
+    ErrorDefinition {
+        code: 173,
+        name: "ERR_SWEEP_DESTINATION_NOT_APPROVED",
+        retryable: false,
+        description: "The destination script of the sweep is not approved by the DAO for the UTXO chain.",
+    },
+    ErrorDefinition {
+        code: 174,
+        name: "ERR_INVALID_SCRIPT_PUBKEY",
+        retryable: false,
+        description: "The script pubkey must be a non-empty hex string.",
+    },
+    ErrorDefinition {
+        code: 175,
+        name: "ERR_TVL_CAP_EXCEEDED",
+        retryable: true,
+        description: "The transfer would take the value of the token held through the bridge above the cap set by the DAO, it can succeed once other transfers reduce it.",
+    },
+    ErrorDefinition {
+        code: 176,
+        name: "ERR_MESSAGE_HANDLER_NOT_REGISTERED",
+        retryable: false,
+        description: "The recipient of the message is not a registered message handler.",
+    },
+    ErrorDefinition {
+        code: 177,
+        name: "ERR_MESSAGE_ALREADY_RECEIVED",
+        retryable: false,
+        description: "The message has already been delivered to its handler.",
+    },
+    ErrorDefinition {
+        code: 178,
+        name: "ERR_MESSAGE_NOT_SUPPORTED_FOR_CHAIN",
+        retryable: false,
+        description: "Messages can't be sent to the chain.",
+    },
+    ErrorDefinition {
+        code: 179,
+        name: "ERR_WITHDRAW_OUTPUT_SCRIPT_NOT_ALLOWED",
+        retryable: false,
+        description: "An output script of the withdrawal is of a type not accepted for the chain.",
+    },
+    ErrorDefinition {
+        code: 180,
+        name: "ERR_NON_STANDARD_SCRIPT_NOT_ALLOWED",
+        retryable: false,
+        description: "Non-standard output scripts can't be accepted for a chain.",
+    },
+    ErrorDefinition {
+        code: 181,
+        name: "ERR_CUSTODY_UPDATE_NOT_ALLOWED",
+        retryable: false,
+        description: "Only the connector of the chain or the DAO can update the custody UTXOs.",
+    },
+    ErrorDefinition {
+        code: 182,
+        name: "ERR_INPUT_NOT_IN_CUSTODY",
+        retryable: false,
+        description: "An input of the withdrawal is not an unspent UTXO held by the bridge.",
+    },
+    ErrorDefinition {
+        code: 183,
+        name: "ERR_INVALID_FEE_ESCALATION_POLICY",
+        retryable: false,
+        description: "The steps of the fee escalation policy are empty, have a zero increment or are not ordered by age.",
+    },
+    ErrorDefinition {
+        code: 184,
+        name: "ERR_INSUFFICIENT_FEE_ESCALATION_BUDGET",
+        retryable: true,
+        description: "The fee escalation budget doesn't cover the due escalation steps.",
+    },
+    ErrorDefinition {
+        code: 185,
+        name: "ERR_ONLY_NEAR_TRANSFERS_ESCALATE",
+        retryable: false,
+        description: "Only the fees of transfers initiated on NEAR can be escalated.",
+    },
+    ErrorDefinition {
+        code: 186,
+        name: "ERR_FEE_ESCALATION_POLICY_NOT_SET",
+        retryable: false,
+        description: "No fee escalation policy is set for the destination chain.",
+    },
+    ErrorDefinition {
+        code: 187,
+        name: "ERR_NO_FEE_ESCALATION_DUE",
+        retryable: true,
+        description: "No escalation step of the transfer is due yet.",
+    },
+    ErrorDefinition {
+        code: 188,
+        name: "ERR_TRANSFER_TIMESTAMPS_NOT_FOUND",
+        retryable: false,
+        description: "The transfer was initiated before its timestamps were recorded.",
+    },
+    ErrorDefinition {
+        code: 189,
+        name: "ERR_ZERO_OUTPUT_VALUE",
+        retryable: false,
+        description: "An output of the withdrawal has a zero value.",
+    },
+    ErrorDefinition {
+        code: 190,
+        name: "ERR_SCRIPT_PUBKEY_TOO_LARGE",
+        retryable: false,
+        description: "An output script of the withdrawal is larger than the standard size.",
+    },
+    ErrorDefinition {
+        code: 191,
+        name: "ERR_NOT_PREFERRED_RELAYER",
+        retryable: true,
+        description: "Only the preferred relayer of the transfer can submit it until its exclusivity window ends.",
+    },
+    ErrorDefinition {
+        code: 192,
+        name: "ERR_CUSTODY_UTXO_TRACKING_DISABLED",
+        retryable: false,
+        description: "The consolidation of UTXOs requires the custody UTXO tracking of the chain.",
+    },
+    ErrorDefinition {
+        code: 193,
+        name: "ERR_TOO_FEW_CONSOLIDATION_INPUTS",
+        retryable: false,
+        description: "A consolidation has to spend at least two custody UTXOs.",
+    },
+    ErrorDefinition {
+        code: 194,
+        name: "ERR_CONSOLIDATION_OUTPUT_NOT_IN_CUSTODY",
+        retryable: false,
+        description: "The output of a consolidation has to be locked by the script of one of its inputs.",
+    },
+    ErrorDefinition {
+        code: 195,
+        name: "ERR_CONSOLIDATION_INPUTS_OVERFLOW",
+        retryable: false,
+        description: "The total value of the consolidated UTXOs overflows.",
+    },
+    ErrorDefinition {
+        code: 196,
+        name: "ERR_INVALID_CONSOLIDATION_FEE",
+        retryable: false,
+        description: "The output of a consolidation has to be smaller than its inputs.",
+    },
+    ErrorDefinition {
+        code: 197,
+        name: "ERR_SERIALIZE_CONSOLIDATION_MSG",
+        retryable: false,
+        description: "The message of the consolidation couldn't be serialized.",
+    },
+    ErrorDefinition {
+        code: 198,
+        name: "ERR_TXID_REPORT_NOT_ALLOWED",
+        retryable: false,
+        description: "Only the connector of the chain can report the transaction of a withdrawal.",
+    },
+    ErrorDefinition {
+        code: 199,
+        name: "ERR_WITHDRAWAL_NOT_FORWARDED",
+        retryable: false,
+        description: "The transfer was not forwarded to the connector of the chain.",
+    },
+    ErrorDefinition {
+        code: 200,
+        name: "ERR_INVALID_TXID",
+        retryable: false,
+        description: "The transaction id is not a 32-byte hex string.",
+    },
+    ErrorDefinition {
+        code: 201,
+        name: "ERR_ONLY_TRANSFER_OWNER_OR_DAO",
+        retryable: false,
+        description: "Only the owner of the transfer or the DAO can make this call.",
+    },
+    ErrorDefinition {
+        code: 202,
+        name: "ERR_FEE_SPONSOR_NOT_ALLOWED",
+        retryable: false,
+        description: "Only the recipient or the DAO can register a fee sponsor for the recipient.",
+    },
+    ErrorDefinition {
+        code: 203,
+        name: "ERR_AMOUNT_UNDERFLOW",
+        retryable: false,
+        description: "The amount is smaller than the value subtracted from it.",
+    },
+    ErrorDefinition {
+        code: 204,
+        name: "ERR_DECIMALS_OVERFLOW",
+        retryable: false,
+        description: "The difference between the decimals of the token on the two chains is too large.",
+    },
+    ErrorDefinition {
+        code: 205,
+        name: "ERR_DESTINATION_CALL_GAS_FEE_OVERFLOW",
+        retryable: false,
+        description: "The gas fee of the destination call overflows.",
+    },
+    ErrorDefinition {
+        code: 206,
+        name: "ERR_INVALIDE_HEX",
+        retryable: false,
+        description: "The EVM address is not a valid hex string.",
+    },
+    ErrorDefinition {
+        code: 207,
+        name: "ERR_INVALID_AMOUNT",
+        retryable: false,
+        description: "The amount is not a decimal number of whole tokens.",
+    },
+    ErrorDefinition {
+        code: 208,
+        name: "ERR_INVALID_OUTPOINT",
+        retryable: false,
+        description: "The outpoint is not in the txid:vout format.",
+    },
+    ErrorDefinition {
+        code: 209,
+        name: "ERR_INVALID_OUTPOINT_TXID",
+        retryable: false,
+        description: "The txid of the outpoint is not a 32-byte hex string.",
+    },
+    ErrorDefinition {
+        code: 210,
+        name: "ERR_INVALID_OUTPOINT_VOUT",
+        retryable: false,
+        description: "The vout of the outpoint is not a 32-bit integer.",
+    },
+    ErrorDefinition {
+        code: 211,
+        name: "ERR_TOO_MANY_DECIMALS",
+        retryable: false,
+        description: "The amount has more fractional digits than the decimals of the token.",
+    },
+    ErrorDefinition {
+        code: 212,
+        name: "Can't have native fee for transfers from UTXO chains",
+        retryable: false,
+        description: "The transfers from UTXO chains can't pay a native fee.",
+    },
+    ErrorDefinition {
+        code: 213,
+        name: "Cannot convert UTXO transfer ID to general transfer ID",
+        retryable: false,
+        description: "The transfer is identified by its UTXO and has no nonce.",
+    },
+    ErrorDefinition {
+        code: 214,
+        name: "Connector has not been set up for this chain",
+        retryable: false,
+        description: "The UTXO chain has no connector.",
+    },
+    ErrorDefinition {
+        code: 215,
+        name: "ERROR: Invalid proof message",
+        retryable: false,
+        description: "The proof is not of the kind expected by the call.",
+    },
+    ErrorDefinition {
+        code: 216,
+        name: "ERROR: The deposit is not sufficient to cover the storage.",
+        retryable: true,
+        description: "The attached deposit doesn't cover the storage of the call.",
+    },
+    ErrorDefinition {
+        code: 217,
+        name: "Error on recipient parsing",
+        retryable: false,
+        description: "The recipient of the transfer is not a valid account.",
+    },
+    ErrorDefinition {
+        code: 218,
+        name: "Fast transfer is already performed",
+        retryable: false,
+        description: "The fast transfer was already performed by a relayer.",
+    },
+    ErrorDefinition {
+        code: 219,
+        name: "Incorrect Signature",
+        retryable: false,
+        description: "The signature returned by the MPC signer is not valid.",
+    },
+    ErrorDefinition {
+        code: 220,
+        name: "Invalid amount to transfer",
+        retryable: false,
+        description: "The amount left after the fee is zero.",
+    },
+    ErrorDefinition {
+        code: 221,
+        name: "ERR_INVALID_DESTINATION_CHAIN",
+        retryable: false,
+        description: "The destination chain is not a configured UTXO chain.",
+    },
+    ErrorDefinition {
+        code: 222,
+        name: "Invalid fee",
+        retryable: true,
+        description: "The fee doesn't match the current fee of the transfer.",
+    },
+    ErrorDefinition {
+        code: 223,
+        name: "Invalid len of accounts for storage deposit",
+        retryable: false,
+        description: "The storage deposit actions don't match the accounts of the transfer.",
+    },
+    ErrorDefinition {
+        code: 224,
+        name: "Invalid proof",
+        retryable: false,
+        description: "The proof could not be decoded.",
+    },
+    ErrorDefinition {
+        code: 225,
+        name: "Invalid proof message",
+        retryable: false,
+        description: "The proof is not of the kind expected by the call.",
+    },
+    ErrorDefinition {
+        code: 226,
+        name: "Old state not found. Migration is not needed.",
+        retryable: false,
+        description: "The contract state is already migrated.",
+    },
+    ErrorDefinition {
+        code: 227,
+        name: "Only BTC can be transferred to the Bitcoin network.",
+        retryable: false,
+        description: "Only the UTXO token of the chain can be transferred to it.",
+    },
+    ErrorDefinition {
+        code: 228,
+        name: "Only sender can update token fee",
+        retryable: false,
+        description: "Only the sender of the transfer can update its token fee.",
+    },
+    ErrorDefinition {
+        code: 229,
+        name: "ERR_NOT_UTXO_CHAIN_TOKEN",
+        retryable: false,
+        description: "Only the UTXO token of the chain can be transferred to it.",
+    },
+    ErrorDefinition {
+        code: 230,
+        name: "STORAGE_ERR: The fee recipient is omitted",
+        retryable: false,
+        description: "The storage deposit actions don't include the fee recipient.",
+    },
+    ErrorDefinition {
+        code: 231,
+        name: "STORAGE_ERR: The native fee recipient is omitted",
+        retryable: false,
+        description: "The storage deposit actions don't include the native fee recipient.",
+    },
+    ErrorDefinition {
+        code: 232,
+        name: "STORAGE_ERR: The transfer recipient is omitted",
+        retryable: false,
+        description: "The storage deposit actions don't include the recipient.",
+    },
+    ErrorDefinition {
+        code: 233,
+        name: "The UTXO transfer is already finalised",
+        retryable: false,
+        description: "The UTXO transfer was already finalised.",
+    },
+    ErrorDefinition {
+        code: 234,
+        name: "The account is not registered",
+        retryable: true,
+        description: "The account has no storage deposit.",
+    },
+    ErrorDefinition {
+        code: 235,
+        name: "The amount is greater than the available storage balance",
+        retryable: false,
+        description: "The withdrawal exceeds the available storage balance.",
+    },
+    ErrorDefinition {
+        code: 236,
+        name: "The amount is greater than the total storage balance",
+        retryable: false,
+        description: "The withdrawal exceeds the total storage balance.",
+    },
+    ErrorDefinition {
+        code: 237,
+        name: "The attached deposit is less than required",
+        retryable: false,
+        description: "The attached deposit is below the required amount.",
+    },
+    ErrorDefinition {
+        code: 238,
+        name: "The attached deposit is less than the minimum storage balance",
+        retryable: false,
+        description: "The attached deposit is below the minimum storage balance.",
+    },
+    ErrorDefinition {
+        code: 239,
+        name: "The transfer does not exist",
+        retryable: false,
+        description: "The transfer is not pending.",
+    },
+    ErrorDefinition {
+        code: 240,
+        name: "The transfer is already finalised",
+        retryable: false,
+        description: "The transfer was already finalised.",
+    },
+    ErrorDefinition {
+        code: 241,
+        name: "This account owns some pending transfers, use `force=true` to ignore them.",
+        retryable: true,
+        description: "The account can't be unregistered while it owns pending transfers.",
+    },
+    ErrorDefinition {
+        code: 242,
+        name: "UTXO Token has not been set up for this chain",
+        retryable: false,
+        description: "The UTXO chain has no token.",
+    },
+    ErrorDefinition {
+        code: 243,
+        name: "Unknown factory",
+        retryable: false,
+        description: "The emitter of the proof is not the factory of the chain.",
+    },
+    ErrorDefinition {
+        code: 244,
+        name: "ERR_UTXO_CHAIN_HAS_PENDING_TRANSFERS",
+        retryable: true,
+        description: "The UTXO chain can't be unregistered while transfers to it are pending or in flight.",
+    },
+    ErrorDefinition {
+        code: 245,
+        name: "ERR_INVALID_WATCHTOWER_PENALTY",
+        retryable: false,
+        description: "The watchtower config has no penalty for frivolous flags.",
+    },
+    ErrorDefinition {
+        code: 246,
+        name: "ERR_FEE_BUMP_NOT_ALLOWED",
+        retryable: false,
+        description: "Only the owner of the transfer or an RBF operator can bump the fee of its withdrawal.",
+    },
+    ErrorDefinition {
+        code: 247,
+        name: "ERR_WITHDRAWAL_TXID_NOT_RECORDED",
+        retryable: true,
+        description: "The connector hasn't reported the transaction of the withdrawal yet.",
+    },
+    ErrorDefinition {
+        code: 248,
+        name: "ERR_WITHDRAW_OUTPUTS_EXCEED_INPUTS",
+        retryable: false,
+        description: "The outputs of the withdrawal are worth more than its custody inputs.",
+    },
+    ErrorDefinition {
+        code: 249,
+        name: "ERR_WITHDRAW_SPENDS_ABOVE_AMOUNT",
+        retryable: false,
+        description: "The outputs and the network fee of the withdrawal spend more than the amount of the transfer.",
+    },
+    ErrorDefinition {
+        code: 250,
+        name: "ERR_WITHDRAW_PAYOUT_BELOW_AMOUNT",
+        retryable: false,
+        description: "The outputs of the withdrawal pay the recipient less than the amount of the transfer minus the largest network fee.",
+    },
+    ErrorDefinition {
+        code: 251,
+        name: "ERR_INVALID_BATCH_SIZE",
+        retryable: false,
+        description: "The batch withdrawal has no transfers or more than the connector can pay in one callback.",
+    },
+    ErrorDefinition {
+        code: 252,
+        name: "ERR_BATCH_OUTPUTS_MISMATCH",
+        retryable: false,
+        description: "The batch withdrawal doesn't have a recipient and an output for every transfer.",
+    },
+    ErrorDefinition {
+        code: 253,
+        name: "ERR_BATCH_OUTPUT_ABOVE_AMOUNT",
+        retryable: false,
+        description: "An output of the batch withdrawal pays more than the amount of its transfer.",
+    },
+    ErrorDefinition {
+        code: 254,
+        name: "ERR_BATCH_FEE_ABOVE_MAX_FEE",
+        retryable: false,
+        description: "The shares of the transfers in the network fee exceed the fee bound of the batch withdrawal.",
+    },
+    ErrorDefinition {
+        code: 255,
+        name: "ERR_SUBMISSION_INTENT_RENEWAL",
+        retryable: false,
+        description: "The relayer already held the submission window of the transfer and can't renew it.",
+    },
+    ErrorDefinition {
+        code: 256,
+        name: "ERR_INVALID_WITHDRAW_MSG",
+        retryable: false,
+        description: "The withdrawal message of the UTXO chain can't be parsed.",
+    },
+    ErrorDefinition {
+        code: 257,
+        name: "ERR_INVALID_WITHDRAW_MSG_TYPE",
+        retryable: false,
+        description: "The message is not a withdrawal supported by the UTXO chain.",
+    },
+    ErrorDefinition {
+        code: 258,
+        name: "ERR_INCORRECT_TARGET_ADDRESS",
+        retryable: false,
+        description: "The target address of the withdrawal is not the recipient of the transfer.",
+    },
+    ErrorDefinition {
+        code: 259,
+        name: "ERR_INVALID_UTXO_CHAIN_MSG",
+        retryable: false,
+        description: "The message of the transfer to the UTXO chain can't be parsed.",
+    },
+    ErrorDefinition {
+        code: 260,
+        name: "ERR_MISSING_MAX_GAS_FEE",
+        retryable: false,
+        description: "The withdrawal has no max_gas_fee while the transfer bounds it.",
+    },
+    ErrorDefinition {
+        code: 261,
+        name: "ERR_INVALID_MAX_GAS_FEE",
+        retryable: false,
+        description: "The max_gas_fee of the withdrawal doesn't match the transfer.",
+    },
+    ErrorDefinition {
+        code: 262,
+        name: "ERR_MISSING_MAX_FEE_RATE",
+        retryable: false,
+        description: "The withdrawal has no max_fee_rate while the transfer bounds it.",
+    },
+    ErrorDefinition {
+        code: 263,
+        name: "ERR_INVALID_MAX_FEE_RATE",
+        retryable: false,
+        description: "The max_fee_rate of the withdrawal doesn't match the transfer.",
+    },
+    ErrorDefinition {
+        code: 264,
+        name: "ERR_MISSING_MAX_FEE_PER_KB",
+        retryable: false,
+        description: "The withdrawal has no max_fee_per_kb while the transfer bounds it.",
+    },
+    ErrorDefinition {
+        code: 265,
+        name: "ERR_INVALID_MAX_FEE_PER_KB",
+        retryable: false,
+        description: "The max_fee_per_kb of the withdrawal doesn't match the transfer.",
+    },
+    ErrorDefinition {
+        code: 266,
+        name: "ERR_WITHDRAW_FEE_BOUND_REQUIRED",
+        retryable: false,
+        description: "The Withdraw message has no fee bound while the DAO caps the fee bound of the chain.",
+    },
+    ErrorDefinition {
+        code: 267,
+        name: "ERR_FEE_ESCALATION_CAP_REACHED",
+        retryable: false,
+        description: "The transfer was already escalated by the highest increase allowed for its native fee.",
+    },
+    ErrorDefinition {
+        code: 268,
+        name: "ERR_OUTPOINT_RESERVATION_NOT_STALE",
+        retryable: false,
+        description: "The outpoint is not reserved, or was reserved too recently to be released.",
+    },
+    ErrorDefinition {
+        code: 269,
+        name: "ERR_INVALID_TRANSACTION",
+        retryable: false,
+        description: "The reported transaction is not a valid transaction of the chain.",
+    },
+    ErrorDefinition {
+        code: 270,
+        name: "ERR_TXID_NOT_SUPPORTED",
+        retryable: false,
+        description: "The transaction ids of the chain can't be computed by the bridge.",
+    },
+    ErrorDefinition {
+        code: 271,
+        name: "ERR_TRANSACTION_NOT_WITHDRAWAL",
+        retryable: false,
+        description: "The reported transaction doesn't spend the inputs of the withdrawal into its outputs.",
+    },
+    ErrorDefinition {
+        code: 272,
+        name: "ERR_WITHDRAWAL_REMOVAL_NOT_ALLOWED",
+        retryable: false,
+        description: "Only the owner, the connector of the chain or the DAO can remove the withdrawal.",
+    },
+    ErrorDefinition {
+        code: 273,
+        name: "ERR_INVALID_ADDRESS",
+        retryable: false,
+        description: "The address does not decode to an output script, e.g. because of an invalid checksum.",
+    },
+    ErrorDefinition {
+        code: 274,
+        name: "ERR_NO_OUTPUT_TO_RECIPIENT",
+        retryable: false,
+        description: "No output of the withdrawal pays to the script of the recipient of the transfer.",
+    },
+    ErrorDefinition {
+        code: 275,
+        name: "ERR_BATCH_OUTPUT_NOT_TO_RECIPIENT",
+        retryable: false,
+        description: "An output of the batch withdrawal does not pay to the script of the recipient of its transfer.",
+    },
+];
+
+#[near(serializers=[json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorCatalogEntry {
+    pub code: u32,
+    pub name: String,
+    /// The call can succeed later without changing its arguments, e.g. once a quota resets,
+    /// a timelock expires or a storage deposit is made.
+    pub retryable: bool,
+    /// SHA-256 of the description, so relayers notice when the meaning of a code changes.
+    pub description_hash: Base58CryptoHash,
+}
+
+#[near]
+impl Contract {
+    /// Returns the errors the contract panics with, so relayers can map the failures of their
+    /// transactions to handling policies without parsing the panic messages.
+    pub fn get_error_catalog(&self) -> Vec<ErrorCatalogEntry> {
+        ERROR_CATALOG
+            .iter()
+            .map(|error| ErrorCatalogEntry {
+                code: error.code,
+                name: error.name.to_string(),
+                retryable: error.retryable,
+                description_hash: env::sha256_array(error.description.as_bytes()).into(),
+            })
+            .collect()
+    }
+}
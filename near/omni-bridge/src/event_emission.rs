@@ -0,0 +1,139 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::{env, near, require};
+use omni_types::near_events::OmniBridgeEvent;
+
+use crate::{Contract, ContractExt, Role};
+
+// Events emitted once per transfer that neither the relayers nor the indexers depend on. The
+// events carrying signatures, fees or the data needed to finalise a transfer are always
+// emitted.
+pub(crate) const CONFIGURABLE_EVENTS: [&str; 2] = ["ClaimFeeEvent", "TransferLabelsEvent"];
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventEmissionPolicy {
+    #[default]
+    Always,
+    /// Emits one event out of every `one_in`.
+    Sampled { one_in: u32 },
+    /// Emits an `EventSummaryEvent` with the number of events of each block instead of the
+    /// events. The summary of a block is emitted with the first event of a later block, or
+    /// by `flush_event_summary`.
+    AggregatePerBlock,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EventCounter {
+    pub block_height: u64,
+    pub count: u64,
+}
+
+/// Policy and counter of a configurable event. They are kept in the contract state, which is
+/// written by every call anyway, so the events don't cost an extra storage write.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EventEmissionState {
+    pub policy: EventEmissionPolicy,
+    pub counter: Option<EventCounter>,
+}
+
+#[near]
+impl Contract {
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_event_emission_policy(&mut self, event_name: String, policy: EventEmissionPolicy) {
+        let index = Self::configurable_event_index(&event_name)
+            .unwrap_or_else(|| env::panic_str("ERR_EVENT_NOT_CONFIGURABLE"));
+        if let EventEmissionPolicy::Sampled { one_in } = policy {
+            require!(one_in > 0, "ERR_INVALID_SAMPLING_RATE");
+        }
+
+        // The events counted under the previous policy are reported before it is replaced
+        let state = &mut self.event_emissions[index];
+        if let Some(counter) = state.counter.take() {
+            if state.policy == EventEmissionPolicy::AggregatePerBlock {
+                Self::log_event_summary(event_name, counter);
+            }
+        }
+        state.policy = policy;
+    }
+
+    pub fn get_event_emission_policy(&self, event_name: String) -> EventEmissionPolicy {
+        Self::configurable_event_index(&event_name)
+            .map(|index| self.event_emissions[index].policy)
+            .unwrap_or_default()
+    }
+
+    /// Emits the summary of the aggregated events of a past block, so the count of the last
+    /// busy block isn't delayed until the next event of the type.
+    pub fn flush_event_summary(&mut self, event_name: String) {
+        let state = Self::configurable_event_index(&event_name)
+            .map(|index| &mut self.event_emissions[index])
+            .filter(|state| state.policy == EventEmissionPolicy::AggregatePerBlock)
+            .unwrap_or_else(|| env::panic_str("ERR_EVENT_NOT_AGGREGATED"));
+        let counter = state
+            .counter
+            .unwrap_or_else(|| env::panic_str("ERR_NO_AGGREGATED_EVENTS"));
+        require!(
+            counter.block_height < env::block_height(),
+            "ERR_BLOCK_NOT_FINISHED"
+        );
+
+        state.counter = None;
+        Self::log_event_summary(event_name, counter);
+    }
+}
+
+impl Contract {
+    pub(crate) fn configurable_event_index(event_name: &str) -> Option<usize> {
+        CONFIGURABLE_EVENTS
+            .iter()
+            .position(|configurable| *configurable == event_name)
+    }
+
+    pub(crate) fn emit_event(&mut self, event: &OmniBridgeEvent) {
+        let Some(index) = Self::configurable_event_index(event.name()) else {
+            env::log_str(&event.to_log_string());
+            return;
+        };
+        let state = &mut self.event_emissions[index];
+        match state.policy {
+            EventEmissionPolicy::Always => env::log_str(&event.to_log_string()),
+            EventEmissionPolicy::Sampled { one_in } => {
+                let counter = state.counter.get_or_insert_with(EventCounter::default);
+                if counter.count % u64::from(one_in) == 0 {
+                    env::log_str(&event.to_log_string());
+                }
+                counter.count += 1;
+            }
+            EventEmissionPolicy::AggregatePerBlock => {
+                let block_height = env::block_height();
+                if let Some(counter) = state
+                    .counter
+                    .filter(|counter| counter.block_height != block_height)
+                {
+                    Self::log_event_summary(event.name().to_string(), counter);
+                    state.counter = None;
+                }
+                state
+                    .counter
+                    .get_or_insert(EventCounter {
+                        block_height,
+                        count: 0,
+                    })
+                    .count += 1;
+            }
+        }
+    }
+
+    fn log_event_summary(event_name: String, counter: EventCounter) {
+        env::log_str(
+            &OmniBridgeEvent::EventSummaryEvent {
+                event_name,
+                block_height: counter.block_height,
+                count: counter.count,
+            }
+            .to_log_string(),
+        );
+    }
+}
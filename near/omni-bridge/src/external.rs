@@ -0,0 +1,132 @@
+//! Typed interfaces of the contracts called by the locker.
+//! Every cross-contract call goes through these definitions, so the argument shapes
+//! are checked at compile time instead of being assembled as JSON at the call site.
+use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
+use near_contract_standards::storage_management::StorageBalance;
+use near_sdk::json_types::{Base58CryptoHash, Base64VecU8, U128};
+use near_sdk::{ext_contract, near, AccountId, Promise, PromiseOrValue};
+use omni_types::btc::{OutPoint, TxOut};
+use omni_types::{
+    BasicMetadata, ChainKind, EvmDestinationCall, OmniAddress, SignRequest, TransferBeneficiary,
+    TransferMessage,
+};
+
+use crate::auditor::AccountingRecord;
+
+#[ext_contract(ext_token)]
+pub trait ExtToken {
+    fn ft_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+    ) -> PromiseOrValue<U128>;
+
+    fn ft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<U128>;
+
+    fn ft_metadata(&self) -> FungibleTokenMetadata;
+
+    fn storage_deposit(
+        &mut self,
+        account_id: &AccountId,
+        registration_only: Option<bool>,
+    ) -> Option<StorageBalance>;
+
+    fn storage_balance_of(&mut self, account_id: &AccountId) -> Option<StorageBalance>;
+
+    fn mint(&mut self, account_id: AccountId, amount: U128, msg: Option<String>);
+
+    fn burn(&mut self, amount: U128);
+
+    fn set_metadata(
+        &mut self,
+        name: Option<String>,
+        symbol: Option<String>,
+        reference: Option<String>,
+        reference_hash: Option<Base64VecU8>,
+        decimals: Option<u8>,
+        icon: Option<String>,
+    );
+}
+
+#[ext_contract(ext_bridge_token_facory)]
+pub trait ExtBridgeTokenFactory {
+    fn set_controller_for_tokens(&self, tokens_account_id: Vec<AccountId>);
+}
+
+#[ext_contract(ext_signer)]
+pub trait ExtSigner {
+    fn sign(&mut self, request: SignRequest);
+}
+
+#[ext_contract(ext_omni_prover_proxy)]
+pub trait Prover {
+    fn verify_proof(&self, #[serializer(borsh)] proof: Vec<u8>);
+}
+
+#[ext_contract(ext_wnear_token)]
+pub trait ExtWNearToken {
+    fn near_withdraw(&self, amount: U128);
+}
+
+#[ext_contract(ext_swap)]
+pub trait ExtSwap {
+    fn get_return(
+        &self,
+        pool_id: u64,
+        token_in: AccountId,
+        amount_in: U128,
+        token_out: AccountId,
+    ) -> U128;
+}
+
+#[ext_contract(ext_deployer)]
+pub trait TokenDeployer {
+    fn deploy_token(&self, account_id: AccountId, metadata: BasicMetadata) -> Promise;
+}
+
+#[ext_contract(ext_utxo_connector)]
+pub trait ExtUTXOConnector {
+    fn withdraw_rbf(&mut self, original_btc_pending_verify_id: String, output: Vec<TxOut>);
+
+    fn sweep_legacy_utxos(
+        &mut self,
+        legacy_path: String,
+        input: Vec<OutPoint>,
+        fee_rate: u64,
+        destination_script: Option<String>,
+    );
+}
+
+#[ext_contract(ext_message_handler)]
+pub trait ExtMessageHandler {
+    fn on_message(
+        &mut self,
+        origin_chain: ChainKind,
+        sender: OmniAddress,
+        payload_hash: Base58CryptoHash,
+    );
+}
+
+#[ext_contract(ext_auditor)]
+pub trait ExtAuditor {
+    fn on_accounting_record(&mut self, record: AccountingRecord);
+}
+
+/// Arguments of `init_transfer_resume`, passed through the yielded promise.
+#[near(serializers=[json])]
+#[derive(Debug, Clone)]
+pub struct InitTransferResumeArgs {
+    pub transfer_message: TransferMessage,
+    pub message_storage_account_id: AccountId,
+    pub storage_owner: AccountId,
+    pub beneficiary: Option<TransferBeneficiary>,
+    pub destination_call: Option<EvmDestinationCall>,
+    pub preferred_relayer: Option<AccountId>,
+}
@@ -0,0 +1,262 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::U128;
+use near_sdk::serde::Serialize;
+use near_sdk::{
+    env, near, require, serde_json, AccountId, Gas, Promise, PromiseError, PromiseOrValue,
+};
+use omni_types::safe_amount::SafeAmount;
+use omni_types::units::BPS_DENOMINATOR;
+
+use crate::external::{ext_swap, ext_token};
+use crate::fee_ledger::{FeeAsset, CLAIM_FEES_CALLBACK_GAS};
+use crate::helpers::{SdkExpect, SdkUnwrap};
+use crate::locked::LockedState;
+use crate::{Contract, ContractExt, Role, FT_TRANSFER_GAS, MINT_TOKEN_GAS, ONE_YOCTO};
+
+const MAX_SLIPPAGE_BPS: u16 = BPS_DENOMINATOR;
+const GET_RETURN_GAS: Gas = Gas::from_tgas(5);
+const FEE_SWAP_GAS: Gas = Gas::from_tgas(60);
+const FEE_QUOTE_CALLBACK_GAS: Gas = Gas::from_tgas(90);
+const FEE_SWAP_CALLBACK_GAS: Gas = Gas::from_tgas(15);
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct SwapAction {
+    pool_id: u64,
+    token_in: AccountId,
+    token_out: AccountId,
+    amount_in: U128,
+    min_amount_out: U128,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct SwapMsg {
+    actions: Vec<SwapAction>,
+    swap_out_recipient: AccountId,
+    skip_unwrap_near: bool,
+}
+
+#[near]
+impl Contract {
+    /// Sets the swap contract used to convert the token fees of the relayers to wNEAR.
+    /// `None` disables the conversion.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_fee_swap_contract(&mut self, swap_contract: Option<AccountId>) {
+        self.fee_swap_contract = swap_contract;
+    }
+
+    /// Whitelists the pool of the swap contract that converts `token_id` to wNEAR.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_fee_swap_pool(&mut self, token_id: AccountId, pool_id: Option<u64>) {
+        if let Some(pool_id) = pool_id {
+            self.fee_swap_pools.insert(&token_id, &pool_id);
+        } else {
+            self.fee_swap_pools.remove(&token_id);
+        }
+    }
+
+    pub fn get_fee_swap_contract(&self) -> Option<AccountId> {
+        self.fee_swap_contract.clone()
+    }
+
+    pub fn get_fee_swap_pool(&self, token_id: AccountId) -> Option<u64> {
+        self.fee_swap_pools.get(&token_id)
+    }
+
+    /// Opts the caller in to the conversion of its token fees to wNEAR when they are claimed
+    /// with `claim_fees` and a minimum output. The swap also fails if the returned amount is
+    /// lower than the quote of the pool by more than `max_slippage_bps`. A failed swap credits
+    /// the fee back to the caller. `None` opts out.
+    /// The conversion needs more gas than the direct payment, so the claims should attach
+    /// enough gas for it.
+    #[payable]
+    pub fn set_fee_conversion(&mut self, max_slippage_bps: Option<u16>) {
+        let account_id = env::predecessor_account_id();
+        let storage_usage = env::storage_usage();
+        if let Some(max_slippage_bps) = max_slippage_bps {
+            require!(
+                max_slippage_bps <= MAX_SLIPPAGE_BPS,
+                "ERR_INVALID_MAX_SLIPPAGE"
+            );
+            self.fee_conversions.insert(&account_id, &max_slippage_bps);
+        } else {
+            self.fee_conversions.remove(&account_id);
+        }
+        let current_storage_usage = env::storage_usage();
+
+        let required_balance = env::storage_byte_cost()
+            .saturating_mul((current_storage_usage.saturating_sub(storage_usage)).into());
+        let refund = env::storage_byte_cost()
+            .saturating_mul((storage_usage.saturating_sub(current_storage_usage)).into());
+        if let Some(mut storage) = self.accounts_balances.get(&account_id) {
+            storage.available = storage.available.saturating_add(refund);
+            self.accounts_balances.insert(&account_id, &storage);
+        }
+        self.update_storage_balance(account_id, required_balance, env::attached_deposit());
+    }
+
+    pub fn get_fee_conversion(&self, account_id: AccountId) -> Option<u16> {
+        self.fee_conversions.get(&account_id)
+    }
+
+    #[private]
+    pub fn fee_swap_quote_callback(
+        &mut self,
+        token_id: AccountId,
+        fee_recipient: AccountId,
+        amount: U128,
+        pool_id: u64,
+        max_slippage_bps: u16,
+        min_amount_out: U128,
+        #[callback_result] quote: Result<U128, PromiseError>,
+    ) -> Promise {
+        let (Ok(quote), Some(swap_contract)) = (quote, self.fee_swap_contract.clone()) else {
+            return self
+                .pay_token_fee(token_id.clone(), fee_recipient.clone(), amount)
+                .then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(CLAIM_FEES_CALLBACK_GAS)
+                        .claim_fees_callback(fee_recipient, FeeAsset::Token(token_id), amount),
+                );
+        };
+        // The quote of the pool can be moved in the same block, so the bound of the caller
+        // is the one that protects the swap
+        let min_amount_out =
+            U128(Self::apply_slippage(quote.0, max_slippage_bps).max(min_amount_out.0));
+        let msg = serde_json::to_string(&SwapMsg {
+            actions: vec![SwapAction {
+                pool_id,
+                token_in: token_id.clone(),
+                token_out: self.wnear_account_id.clone(),
+                amount_in: amount,
+                min_amount_out,
+            }],
+            swap_out_recipient: fee_recipient.clone(),
+            skip_unwrap_near: true,
+        })
+        .sdk_expect("ERR_JSON");
+
+        // Bridged tokens are minted to the locker and forwarded to the swap contract in one call
+        let swap = if self.deployed_tokens.contains(&token_id) {
+            ext_token::ext(token_id.clone())
+                .with_static_gas(FEE_SWAP_GAS)
+                .with_attached_deposit(ONE_YOCTO)
+                .mint(swap_contract, amount, Some(msg))
+        } else {
+            ext_token::ext(token_id.clone())
+                .with_static_gas(FEE_SWAP_GAS)
+                .with_attached_deposit(ONE_YOCTO)
+                .ft_transfer_call(swap_contract, amount, None, msg)
+        };
+        swap.then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(FEE_SWAP_CALLBACK_GAS)
+                .fee_swap_callback(token_id, fee_recipient, amount),
+        )
+    }
+
+    /// Pays the part of the fee refunded by the swap contract in the original token. The fee
+    /// is credited back to the recipient if the swap couldn't be started.
+    #[private]
+    pub fn fee_swap_callback(
+        &mut self,
+        token_id: AccountId,
+        fee_recipient: AccountId,
+        amount: U128,
+        #[callback_result] used_amount: Result<U128, PromiseError>,
+    ) -> PromiseOrValue<()> {
+        let Ok(used_amount) = used_amount else {
+            self.credit_claimable_fee(fee_recipient, FeeAsset::Token(token_id), amount.0);
+            return PromiseOrValue::Value(());
+        };
+        let used_amount = used_amount.0.min(amount.0);
+        self.unlock_amount(&token_id, LockedState::FeePool, used_amount);
+
+        let refunded_amount = U128(amount.0.saturating_sub(used_amount));
+        if refunded_amount.0 == 0 {
+            return PromiseOrValue::Value(());
+        }
+        ext_token::ext(token_id.clone())
+            .with_static_gas(FT_TRANSFER_GAS)
+            .with_attached_deposit(ONE_YOCTO)
+            .ft_transfer(fee_recipient.clone(), refunded_amount, None)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(CLAIM_FEES_CALLBACK_GAS)
+                    .claim_fees_callback(fee_recipient, FeeAsset::Token(token_id), refunded_amount),
+            )
+            .into()
+    }
+}
+
+impl Contract {
+    // Returns `None` if the fee has to be paid in the original token. The fee stays in the fee
+    // pool until the conversion pays it, or is credited back to the recipient.
+    pub(crate) fn convert_fee_to_wnear(
+        &self,
+        token_id: &AccountId,
+        fee_recipient: &AccountId,
+        amount: u128,
+        min_amount_out: U128,
+    ) -> Option<Promise> {
+        let swap_contract = self.fee_swap_contract.clone()?;
+        let max_slippage_bps = self.fee_conversions.get(fee_recipient)?;
+        let pool_id = self.fee_swap_pools.get(token_id)?;
+        if token_id == &self.wnear_account_id {
+            return None;
+        }
+
+        Some(
+            ext_swap::ext(swap_contract)
+                .with_static_gas(GET_RETURN_GAS)
+                .get_return(
+                    pool_id,
+                    token_id.clone(),
+                    U128(amount),
+                    self.wnear_account_id.clone(),
+                )
+                .then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(FEE_QUOTE_CALLBACK_GAS)
+                        .fee_swap_quote_callback(
+                            token_id.clone(),
+                            fee_recipient.clone(),
+                            U128(amount),
+                            pool_id,
+                            max_slippage_bps,
+                            min_amount_out,
+                        ),
+                ),
+        )
+    }
+
+    pub(crate) fn pay_token_fee(
+        &self,
+        token_id: AccountId,
+        fee_recipient: AccountId,
+        amount: U128,
+    ) -> Promise {
+        if self.deployed_tokens.contains(&token_id) {
+            ext_token::ext(token_id)
+                .with_static_gas(MINT_TOKEN_GAS)
+                .mint(fee_recipient, amount, None)
+        } else {
+            ext_token::ext(token_id)
+                .with_static_gas(FT_TRANSFER_GAS)
+                .with_attached_deposit(ONE_YOCTO)
+                .ft_transfer(fee_recipient, amount, None)
+        }
+    }
+
+    // Computes `amount * (1 - max_slippage_bps / 10000)`.
+    fn apply_slippage(amount: u128, max_slippage_bps: u16) -> u128 {
+        SafeAmount::new(amount)
+            .checked_mul_div(
+                u128::from(MAX_SLIPPAGE_BPS - max_slippage_bps),
+                u128::from(MAX_SLIPPAGE_BPS),
+            )
+            .sdk_unwrap()
+            .get()
+    }
+}
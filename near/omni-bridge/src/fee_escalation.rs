@@ -0,0 +1,190 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{env, near, require, NearToken, Promise};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::units::BPS_DENOMINATOR;
+use omni_types::{ChainKind, TransferId};
+
+use crate::helpers::SdkExpect;
+use crate::{Contract, ContractExt, Role};
+
+/// Native fee added to a transfer once it has been pending for `min_age` nanoseconds.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeEscalationStep {
+    pub min_age: U64,
+    pub native_fee_increment: U128,
+}
+
+/// Steps of the escalation of the native fee of the transfers to a chain, ordered by age.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeEscalationPolicy {
+    pub steps: Vec<FeeEscalationStep>,
+    /// Highest total escalation of a transfer, in basis points of the native fee paid by its
+    /// sender, so the budget can't be drained by the transfers that pay no fee.
+    pub max_increase_bps: u32,
+}
+
+/// Escalation applied to a pending transfer, paid from the escalation budget.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeeEscalation {
+    pub steps: u32,
+    pub native_fee_added: U128,
+}
+
+#[near]
+impl Contract {
+    /// Sets the escalation of the native fee of the transfers to the chain. `None` stops the
+    /// escalation of the transfers to the chain, the steps already applied are kept.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_fee_escalation_policy(
+        &mut self,
+        chain_kind: ChainKind,
+        policy: Option<FeeEscalationPolicy>,
+    ) {
+        if let Some(policy) = policy {
+            require!(
+                !policy.steps.is_empty()
+                    && policy
+                        .steps
+                        .iter()
+                        .all(|step| step.native_fee_increment.0 > 0)
+                    && policy
+                        .steps
+                        .windows(2)
+                        .all(|steps| steps[0].min_age.0 < steps[1].min_age.0)
+                    && policy.max_increase_bps > 0,
+                "ERR_INVALID_FEE_ESCALATION_POLICY"
+            );
+            self.fee_escalation_policies.insert(&chain_kind, &policy);
+        } else {
+            self.fee_escalation_policies.remove(&chain_kind);
+        }
+    }
+
+    pub fn get_fee_escalation_policy(&self, chain_kind: ChainKind) -> Option<FeeEscalationPolicy> {
+        self.fee_escalation_policies.get(&chain_kind)
+    }
+
+    /// Adds the attached deposit to the budget the escalated native fees are paid from.
+    #[payable]
+    #[access_control_any(roles(Role::DAO))]
+    pub fn fund_fee_escalation_budget(&mut self) {
+        self.fee_escalation_budget = self
+            .fee_escalation_budget
+            .saturating_add(env::attached_deposit());
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn withdraw_fee_escalation_budget(&mut self, amount: NearToken) -> Promise {
+        self.fee_escalation_budget = self
+            .fee_escalation_budget
+            .checked_sub(amount)
+            .sdk_expect("ERR_INSUFFICIENT_FEE_ESCALATION_BUDGET");
+        Promise::new(env::predecessor_account_id()).transfer(amount)
+    }
+
+    pub fn get_fee_escalation_budget(&self) -> NearToken {
+        self.fee_escalation_budget
+    }
+
+    pub fn get_fee_escalation(&self, transfer_id: TransferId) -> Option<FeeEscalation> {
+        self.fee_escalations.get(&transfer_id)
+    }
+
+    /// Applies the steps of the policy of the destination chain that are due for the pending
+    /// transfer initiated on NEAR. Anyone can call it, so the stuck transfers get a higher fee
+    /// for the relayers without an update of the sender. The total escalation is capped by the
+    /// `max_increase_bps` of the policy.
+    pub fn escalate_transfer_fee(&mut self, transfer_id: TransferId) {
+        require!(
+            transfer_id.origin_chain == ChainKind::Near,
+            "ERR_ONLY_NEAR_TRANSFERS_ESCALATE"
+        );
+        let mut transfer = self.get_transfer_message_storage(transfer_id);
+        let policy = self
+            .fee_escalation_policies
+            .get(&transfer.message.get_destination_chain())
+            .sdk_expect("ERR_FEE_ESCALATION_POLICY_NOT_SET");
+        let initiated_at = self
+            .transfer_timestamps
+            .get(&transfer_id)
+            .sdk_expect("ERR_TRANSFER_TIMESTAMPS_NOT_FOUND")
+            .initiated_at;
+        let age = env::block_timestamp().saturating_sub(initiated_at.0);
+
+        let mut escalation = self.fee_escalations.get(&transfer_id).unwrap_or_default();
+        let due_steps: Vec<&FeeEscalationStep> = policy
+            .steps
+            .iter()
+            .skip(escalation.steps as usize)
+            .take_while(|step| age >= step.min_age.0)
+            .collect();
+        require!(!due_steps.is_empty(), "ERR_NO_FEE_ESCALATION_DUE");
+
+        let sender_native_fee = transfer
+            .message
+            .fee
+            .native_fee
+            .0
+            .saturating_sub(escalation.native_fee_added.0);
+        let max_native_fee_added = sender_native_fee.saturating_mul(policy.max_increase_bps.into())
+            / u128::from(BPS_DENOMINATOR);
+        let native_fee_added = due_steps
+            .iter()
+            .map(|step| step.native_fee_increment.0)
+            .fold(0u128, u128::saturating_add)
+            .min(max_native_fee_added.saturating_sub(escalation.native_fee_added.0));
+        require!(native_fee_added > 0, "ERR_FEE_ESCALATION_CAP_REACHED");
+        self.fee_escalation_budget = self
+            .fee_escalation_budget
+            .checked_sub(NearToken::from_yoctonear(native_fee_added))
+            .sdk_expect("ERR_INSUFFICIENT_FEE_ESCALATION_BUDGET");
+
+        escalation.steps +=
+            u32::try_from(due_steps.len()).sdk_expect("ERR_INVALID_FEE_ESCALATION_POLICY");
+        escalation.native_fee_added = U128(
+            escalation
+                .native_fee_added
+                .0
+                .saturating_add(native_fee_added),
+        );
+        self.fee_escalations.insert(&transfer_id, &escalation);
+
+        transfer.message.fee.native_fee = U128(
+            transfer
+                .message
+                .fee
+                .native_fee
+                .0
+                .saturating_add(native_fee_added),
+        );
+        self.insert_raw_transfer(transfer.message.clone(), transfer.owner);
+
+        self.emit_event(&OmniBridgeEvent::FeeEscalatedEvent {
+            transfer_message: transfer.message,
+            step: escalation.steps,
+            native_fee_added: U128(native_fee_added),
+        });
+    }
+}
+
+impl Contract {
+    // Returns the escalated native fee of a transfer that is removed without being relayed
+    // to the budget, so it isn't refunded to the owner of the transfer.
+    pub(crate) fn reclaim_fee_escalation(&mut self, transfer_id: &TransferId) -> NearToken {
+        let Some(escalation) = self.fee_escalations.remove(transfer_id) else {
+            return NearToken::from_yoctonear(0);
+        };
+        let native_fee_added = NearToken::from_yoctonear(escalation.native_fee_added.0);
+        self.fee_escalation_budget = self.fee_escalation_budget.saturating_add(native_fee_added);
+        native_fee_added
+    }
+
+    // The escalated native fee was paid to the relayer of the finalised transfer.
+    pub(crate) fn settle_fee_escalation(&mut self, transfer_id: &TransferId) {
+        self.fee_escalations.remove(transfer_id);
+    }
+}
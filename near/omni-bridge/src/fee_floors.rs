@@ -0,0 +1,74 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::U128;
+use near_sdk::{near, require, AccountId};
+use omni_types::ChainKind;
+
+use crate::{Contract, ContractExt, Role};
+
+#[near]
+impl Contract {
+    /// Sets the minimum native fee of the transfers to the destination chain. `None` removes it.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_min_native_relayer_fee(&mut self, chain_kind: ChainKind, min_fee: Option<U128>) {
+        if let Some(min_fee) = min_fee {
+            self.min_native_relayer_fees.insert(&chain_kind, &min_fee);
+        } else {
+            self.min_native_relayer_fees.remove(&chain_kind);
+        }
+    }
+
+    /// Sets the minimum fee in the transferred token of the transfers to the destination chain.
+    /// `None` removes it.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_min_token_relayer_fee(
+        &mut self,
+        chain_kind: ChainKind,
+        token_id: AccountId,
+        min_fee: Option<U128>,
+    ) {
+        let key = (chain_kind, token_id);
+        if let Some(min_fee) = min_fee {
+            self.min_token_relayer_fees.insert(&key, &min_fee);
+        } else {
+            self.min_token_relayer_fees.remove(&key);
+        }
+    }
+
+    pub fn get_min_native_relayer_fee(&self, chain_kind: ChainKind) -> Option<U128> {
+        self.min_native_relayer_fees.get(&chain_kind)
+    }
+
+    pub fn get_min_token_relayer_fee(
+        &self,
+        chain_kind: ChainKind,
+        token_id: AccountId,
+    ) -> Option<U128> {
+        self.min_token_relayer_fees.get(&(chain_kind, token_id))
+    }
+}
+
+impl Contract {
+    // The fee is enough for a relayer when it reaches either of the floors set for the chain,
+    // since relayers accept both the native fee and the fee in the transferred token.
+    pub(crate) fn check_relayer_fee_floor(
+        &self,
+        chain_kind: ChainKind,
+        token_id: &AccountId,
+        fee: U128,
+        native_fee: U128,
+    ) {
+        let min_native_fee = self.min_native_relayer_fees.get(&chain_kind);
+        let min_token_fee = self
+            .min_token_relayer_fees
+            .get(&(chain_kind, token_id.clone()));
+        if min_native_fee.is_none() && min_token_fee.is_none() {
+            return;
+        }
+
+        require!(
+            min_native_fee.is_some_and(|min_fee| native_fee.0 >= min_fee.0)
+                || min_token_fee.is_some_and(|min_fee| fee.0 >= min_fee.0),
+            "ERR_FEE_BELOW_RELAYER_FEE_FLOOR"
+        );
+    }
+}
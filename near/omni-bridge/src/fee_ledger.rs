@@ -0,0 +1,170 @@
+use near_plugins::{pause, AccessControllable, Pausable};
+use near_sdk::json_types::U128;
+use near_sdk::{borsh, env, near, require, AccountId, Gas, NearToken, Promise, PromiseError};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::safe_amount::SafeAmount;
+use omni_types::{ChainKind, TransferMessage};
+
+use crate::helpers::{SdkExpect, SdkUnwrap};
+use crate::locked::LockedState;
+use crate::{Contract, ContractExt, Role};
+
+pub(crate) const CLAIM_FEES_CALLBACK_GAS: Gas = Gas::from_tgas(5);
+/// Pausable feature of the payouts of the fees: `claim_fee`, `claim_fees`,
+/// `report_fee_recipient`, `claim_rewards`, `withdraw_fee_sponsor_balance` and
+/// `withdraw_protocol_fees`. It can be paused without pausing the transfers.
+pub const FEE_SETTLEMENT_FEATURE: &str = "fee_settlement";
+// The payouts were paused under the names of their methods before they shared the feature
+const LEGACY_FEE_SETTLEMENT_FEATURES: [&str; 4] = [
+    "claim_fee",
+    "claim_fees",
+    "report_fee_recipient",
+    "claim_rewards",
+];
+
+/// Asset in which a fee is owed to its recipient.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeeAsset {
+    /// Native fee of the transfers initiated on NEAR.
+    Near,
+    /// Native fee of the transfers forwarded from another chain, minted in the native token
+    /// of the chain when claimed.
+    NativeToken(ChainKind),
+    /// Fee in the transferred token, held by the bridge until claimed.
+    Token(AccountId),
+}
+
+#[near]
+impl Contract {
+    pub fn get_claimable_fee(&self, account_id: AccountId, asset: FeeAsset) -> U128 {
+        self.claimable_fees
+            .get(&(account_id, asset))
+            .unwrap_or_default()
+    }
+
+    pub fn is_fee_settlement_paused(&self) -> bool {
+        self.pa_is_paused(FEE_SETTLEMENT_FEATURE.to_string())
+    }
+
+    /// Pays the fees of the asset owed to the caller. The fees are credited back if the payment
+    /// fails. The token fees of a caller that opted in to the conversion are swapped to wNEAR
+    /// when `min_amount_out` is set.
+    #[pause(
+        name = "fee_settlement",
+        except(roles(Role::DAO, Role::UnrestrictedRelayer))
+    )]
+    pub fn claim_fees(&mut self, asset: FeeAsset, min_amount_out: Option<U128>) -> Promise {
+        let account_id = env::predecessor_account_id();
+        let amount = self
+            .claimable_fees
+            .remove(&(account_id.clone(), asset.clone()))
+            .unwrap_or_default();
+        require!(amount.0 > 0, "ERR_NO_CLAIMABLE_FEES");
+
+        let payment = match &asset {
+            FeeAsset::Near => {
+                Promise::new(account_id.clone()).transfer(NearToken::from_yoctonear(amount.0))
+            }
+            FeeAsset::NativeToken(chain_kind) => self.pay_token_fee(
+                self.get_native_token_id(*chain_kind),
+                account_id.clone(),
+                amount,
+            ),
+            FeeAsset::Token(token_id) => {
+                if let Some(conversion) = min_amount_out.and_then(|min_amount_out| {
+                    self.convert_fee_to_wnear(token_id, &account_id, amount.0, min_amount_out)
+                }) {
+                    return conversion;
+                }
+                self.pay_token_fee(token_id.clone(), account_id.clone(), amount)
+            }
+        };
+
+        payment.then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(CLAIM_FEES_CALLBACK_GAS)
+                .claim_fees_callback(account_id, asset, amount),
+        )
+    }
+
+    #[private]
+    pub fn claim_fees_callback(
+        &mut self,
+        account_id: AccountId,
+        asset: FeeAsset,
+        amount: U128,
+        #[callback_result] call_result: &Result<(), PromiseError>,
+    ) {
+        if call_result.is_err() {
+            self.credit_claimable_fee(account_id, asset, amount.0);
+        } else if let FeeAsset::Token(token_id) = &asset {
+            self.unlock_amount(token_id, LockedState::FeePool, amount.0);
+        }
+    }
+}
+
+impl Contract {
+    // Keeps a payout paused under the name of its method paused after the upgrade, by pausing
+    // the whole fee settlement instead.
+    pub(crate) fn migrate_fee_settlement_pause(&self) {
+        let Some(mut paused) = self.pa_all_paused() else {
+            return;
+        };
+        let paused_len = paused.len();
+        paused.retain(|feature| !LEGACY_FEE_SETTLEMENT_FEATURES.contains(&feature.as_str()));
+        if paused.len() < paused_len {
+            paused.insert(FEE_SETTLEMENT_FEATURE.to_owned());
+            env::storage_write(
+                self.pa_storage_key(),
+                &borsh::to_vec(&paused).sdk_expect("ERR_BORSH"),
+            );
+        }
+    }
+
+    // Records the fees of the transfer as owed to the fee recipient instead of paying them,
+    // so the settlement of the transfer doesn't depend on the payment of its fees.
+    pub(crate) fn credit_transfer_fees(
+        &mut self,
+        message: &TransferMessage,
+        fee_recipient: AccountId,
+    ) {
+        // The transfers from the chains without a native fee are rejected when initiated
+        if message.fee.native_fee.0 != 0 {
+            let origin_chain = message.origin_transfer_id.as_ref().map_or_else(
+                || message.get_origin_chain(),
+                |origin_transfer_id| origin_transfer_id.origin_chain,
+            );
+            let asset = if origin_chain == ChainKind::Near {
+                FeeAsset::Near
+            } else {
+                FeeAsset::NativeToken(origin_chain)
+            };
+            self.credit_claimable_fee(fee_recipient.clone(), asset, message.fee.native_fee.0);
+        }
+
+        let token_fee = message.fee.fee.0;
+        if token_fee > 0 {
+            let token = self.get_token_id(&message.token);
+            self.lock_amount(&token, LockedState::FeePool, token_fee);
+            self.credit_claimable_fee(fee_recipient, FeeAsset::Token(token), token_fee);
+        }
+
+        self.emit_event(&OmniBridgeEvent::ClaimFeeEvent {
+            transfer_message: message.clone(),
+        });
+    }
+
+    pub(crate) fn credit_claimable_fee(
+        &mut self,
+        account_id: AccountId,
+        asset: FeeAsset,
+        amount: u128,
+    ) {
+        let key = (account_id, asset);
+        let balance = SafeAmount::from(self.claimable_fees.get(&key).unwrap_or_default())
+            .checked_add(amount.into())
+            .sdk_unwrap();
+        self.claimable_fees.insert(&key, &balance.into());
+    }
+}
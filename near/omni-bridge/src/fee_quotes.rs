@@ -0,0 +1,99 @@
+use near_sdk::json_types::U128;
+use near_sdk::{env, near, AccountId};
+use omni_types::btc::TxOut;
+use omni_types::ChainKind;
+
+use crate::helpers::SdkExpect;
+use crate::utxo::{UtxoChainValidator, UtxoFeeModel};
+use crate::{Contract, ContractExt};
+
+const QUOTE_INPUTS: usize = 1;
+const QUOTE_OUTPUT_SCRIPT_SIZE: usize = 34;
+
+/// Expected fees of a transfer from NEAR, for the frontends to show what the recipient gets
+/// before the transfer is initiated.
+#[near(serializers=[json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferFeeQuote {
+    /// Fee in the transferred token that meets the fee floor of the relayers of the chain.
+    pub fee: U128,
+    /// Native fee that meets the fee floor when the fee in the token is not enough.
+    pub native_fee: U128,
+    /// Amount received on the destination chain, in the decimals of its token.
+    pub amount_to_receive: U128,
+    /// How the network fee of the withdrawal is bounded by the msg of the transfers to
+    /// the destination UTXO chain. The fee itself is set by the connector at submission.
+    pub utxo_fee_model: Option<UtxoFeeModel>,
+    /// Network fee of the withdrawal to the UTXO chain at the fresh rate of the oracle, in
+    /// the smallest unit of the chain. It is already taken from `amount_to_receive`.
+    pub utxo_network_fee: Option<U128>,
+}
+
+#[near]
+impl Contract {
+    /// Returns the fees of a transfer of `amount` of the token to the chain. The fee in the
+    /// token is preferred when the DAO has set floors in both the token and the native token.
+    pub fn get_transfer_fee_quote(
+        &self,
+        chain_kind: ChainKind,
+        token_id: AccountId,
+        amount: U128,
+    ) -> TransferFeeQuote {
+        let token_address = self
+            .get_token_address(chain_kind, token_id.clone())
+            .unwrap_or_else(|| env::panic_str("ERR_FAILED_TO_GET_TOKEN_ADDRESS"));
+        let decimals = self
+            .token_decimals
+            .get(&token_address)
+            .sdk_expect("ERR_TOKEN_DECIMALS_NOT_FOUND");
+
+        let min_token_fee = self.min_token_relayer_fees.get(&(chain_kind, token_id));
+        let (fee, native_fee) = match min_token_fee {
+            Some(min_fee) => (min_fee, U128(0)),
+            None => (
+                U128(0),
+                self.min_native_relayer_fees
+                    .get(&chain_kind)
+                    .unwrap_or_default(),
+            ),
+        };
+
+        let utxo_fee_model = self
+            .is_utxo_chain(chain_kind)
+            .then(|| self.get_utxo_chain_validator(chain_kind).fee_model());
+        let utxo_network_fee = utxo_fee_model
+            .and_then(|fee_model| self.estimate_utxo_network_fee(chain_kind, fee_model));
+
+        TransferFeeQuote {
+            fee,
+            native_fee,
+            amount_to_receive: U128(
+                Self::normalize_amount(amount.0.saturating_sub(fee.0), decimals)
+                    .saturating_sub(utxo_network_fee.unwrap_or_default()),
+            ),
+            utxo_fee_model,
+            utxo_network_fee: utxo_network_fee.map(U128),
+        }
+    }
+}
+
+impl Contract {
+    // The withdrawal of a single transfer spends one input and pays the recipient and the
+    // change, with scripts as large as the P2WSH and P2TR ones. The chains bounded by the total
+    // fee have no oracle rate to estimate it.
+    fn estimate_utxo_network_fee(
+        &self,
+        chain_kind: ChainKind,
+        fee_model: UtxoFeeModel,
+    ) -> Option<u128> {
+        if fee_model == UtxoFeeModel::MaxFee {
+            return None;
+        }
+        let fee_rate = self.fresh_utxo_fee_rate(chain_kind)?;
+        let output = TxOut {
+            value: 0,
+            script_pubkey: "00".repeat(QUOTE_OUTPUT_SCRIPT_SIZE),
+        };
+        Some(fee_model.max_network_fee(fee_rate.into(), QUOTE_INPUTS, &[output.clone(), output]))
+    }
+}
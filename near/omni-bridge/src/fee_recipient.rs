@@ -0,0 +1,112 @@
+use near_plugins::{access_control_any, pause, AccessControllable, Pausable};
+use near_sdk::{env, near, require, AccountId, PromiseOrValue};
+use omni_types::{ChainKind, TransferId, TransferMessage};
+
+use crate::connector_registry::ConnectorId;
+use crate::helpers::SdkExpect;
+use crate::locked::LockedState;
+use crate::{Contract, ContractExt, Role};
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeeRecipientStrategy {
+    // The fee is paid to the account that submitted the transfer, or to the account it specified
+    #[default]
+    Predecessor,
+    // The fee is held until the connector reports the account that broadcasted the transfer
+    ConnectorReported,
+}
+
+#[near]
+impl Contract {
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_fee_recipient_strategy(
+        &mut self,
+        chain_kind: ChainKind,
+        strategy: FeeRecipientStrategy,
+    ) {
+        if strategy == FeeRecipientStrategy::default() {
+            self.fee_recipient_strategies.remove(&chain_kind);
+        } else {
+            self.fee_recipient_strategies.insert(&chain_kind, &strategy);
+        }
+    }
+
+    pub fn get_fee_recipient_strategy(&self, chain_kind: ChainKind) -> FeeRecipientStrategy {
+        self.fee_recipient_strategies
+            .get(&chain_kind)
+            .unwrap_or_default()
+    }
+
+    /// Returns the transfer whose fee is waiting for the connector to report the fee recipient.
+    pub fn get_unreported_fee_transfer(&self, transfer_id: TransferId) -> Option<TransferMessage> {
+        self.unreported_fee_transfers.get(&transfer_id)
+    }
+
+    /// Called by the connector of the destination chain to pay the fee of a submitted transfer
+    /// to the account identified as the broadcaster of the transaction.
+    #[pause(name = "fee_settlement", except(roles(Role::DAO)))]
+    pub fn report_fee_recipient(
+        &mut self,
+        transfer_id: TransferId,
+        fee_recipient: AccountId,
+    ) -> PromiseOrValue<()> {
+        let transfer_message = self
+            .unreported_fee_transfers
+            .remove(&transfer_id)
+            .sdk_expect("ERR_UNREPORTED_FEE_TRANSFER_NOT_FOUND");
+
+        require!(
+            env::predecessor_account_id()
+                == self.resolve_transfer_connector(
+                    &transfer_id,
+                    transfer_message.get_destination_chain()
+                ),
+            "ERR_ONLY_CONNECTOR_CAN_REPORT_FEE_RECIPIENT"
+        );
+        self.unreported_fee_connectors.remove(&transfer_id);
+
+        let token = self.get_token_id(&transfer_message.token);
+        let token_fee = transfer_message.fee.fee.0;
+        self.unlock_amount(&token, LockedState::FeePool, token_fee);
+        self.send_fee_internal(&transfer_message, fee_recipient, token_fee)
+    }
+}
+
+impl Contract {
+    // Returns `None` if the fee recipient has to be reported later by the connector.
+    // The relayer that registered the intent to submit the transfer takes precedence while its
+    // window is open, whichever account submits the transfer.
+    pub(crate) fn resolve_fee_recipient(
+        &mut self,
+        chain_kind: ChainKind,
+        transfer_id: &TransferId,
+        fee_recipient: Option<AccountId>,
+    ) -> Option<AccountId> {
+        if let Some(relayer) = self.take_entitled_relayer(transfer_id) {
+            return Some(relayer);
+        }
+        match self.get_fee_recipient_strategy(chain_kind) {
+            FeeRecipientStrategy::Predecessor => {
+                Some(fee_recipient.unwrap_or_else(env::predecessor_account_id))
+            }
+            FeeRecipientStrategy::ConnectorReported => None,
+        }
+    }
+
+    pub(crate) fn hold_fee_until_reported(
+        &mut self,
+        transfer_message: &TransferMessage,
+        connector_id: Option<ConnectorId>,
+    ) {
+        let token = self.get_token_id(&transfer_message.token);
+        self.lock_amount(&token, LockedState::FeePool, transfer_message.fee.fee.0);
+        let transfer_id = transfer_message.get_transfer_id();
+        self.unreported_fee_transfers
+            .insert(&transfer_id, transfer_message);
+        if let Some(connector_id) = connector_id {
+            self.unreported_fee_connectors
+                .insert(&transfer_id, &connector_id);
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use near_plugins::{pause, AccessControllable, Pausable};
+use near_sdk::json_types::U128;
+use near_sdk::{env, near, require, AccountId, Gas, Promise, PromiseError};
+use omni_types::safe_amount::SafeAmount;
+use omni_types::{OmniAddress, TransferMessage};
+
+use crate::external::ext_token;
+use crate::helpers::{SdkExpect, SdkUnwrap};
+use crate::{Contract, ContractExt, Role, FT_TRANSFER_GAS, ONE_YOCTO};
+
+const FEE_SPONSOR_PERIOD_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+const WITHDRAW_FEE_SPONSOR_CALLBACK_GAS: Gas = Gas::from_tgas(5);
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeSponsorLimits {
+    // Fee drawn from the sponsor balance for every sponsored transfer
+    pub fee_per_transfer: U128,
+    // Transfers below this amount are not sponsored
+    pub min_transfer_amount: U128,
+    // Maximum amount of fees drawn from the sponsor balance per day
+    pub max_daily_fees: Option<U128>,
+}
+
+/// Balance pre-funded by a recipient to pay the token fee of the transfers sent to it.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeSponsor {
+    pub owner: AccountId,
+    pub balance: U128,
+    pub limits: FeeSponsorLimits,
+    pub period: u64,
+    pub spent_in_period: U128,
+}
+
+#[near]
+impl Contract {
+    /// Registers the caller as the sponsor of the fees of the `token_id` transfers sent to `recipient`.
+    /// Only the recipient itself or the DAO can register the sponsor, so nobody can take the slot
+    /// of a recipient. The storage of the record is paid from the attached deposit or the storage
+    /// balance of the caller.
+    #[payable]
+    pub fn register_fee_sponsor(
+        &mut self,
+        recipient: OmniAddress,
+        token_id: AccountId,
+        limits: FeeSponsorLimits,
+    ) {
+        let owner = env::predecessor_account_id();
+        require!(
+            recipient == OmniAddress::Near(owner.clone())
+                || self.acl_has_role(Role::DAO.into(), owner.clone()),
+            "ERR_FEE_SPONSOR_NOT_ALLOWED"
+        );
+        let key = (recipient, token_id);
+        require!(
+            self.fee_sponsors.get(&key).is_none(),
+            "ERR_FEE_SPONSOR_ALREADY_REGISTERED"
+        );
+
+        let storage_usage = env::storage_usage();
+        self.fee_sponsors.insert(
+            &key,
+            &FeeSponsor {
+                owner: owner.clone(),
+                balance: U128(0),
+                limits,
+                period: Self::current_fee_sponsor_period(),
+                spent_in_period: U128(0),
+            },
+        );
+        let required_balance = env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into());
+
+        self.update_storage_balance(owner, required_balance, env::attached_deposit());
+    }
+
+    /// Removes the sponsor record with an empty balance and returns its storage to the owner.
+    pub fn unregister_fee_sponsor(&mut self, recipient: OmniAddress, token_id: AccountId) {
+        let key = (recipient, token_id);
+        let sponsor = self.require_fee_sponsor_owner(&key);
+        require!(sponsor.balance.0 == 0, "ERR_FEE_SPONSOR_BALANCE_NOT_EMPTY");
+
+        let storage_usage = env::storage_usage();
+        self.fee_sponsors.remove(&key);
+        let refund = env::storage_byte_cost()
+            .saturating_mul((storage_usage.saturating_sub(env::storage_usage())).into());
+
+        if let Some(mut storage) = self.accounts_balances.get(&sponsor.owner) {
+            storage.available = storage.available.saturating_add(refund);
+            self.accounts_balances.insert(&sponsor.owner, &storage);
+        }
+    }
+
+    pub fn set_fee_sponsor_limits(
+        &mut self,
+        recipient: OmniAddress,
+        token_id: AccountId,
+        limits: FeeSponsorLimits,
+    ) {
+        let key = (recipient, token_id);
+        let mut sponsor = self.require_fee_sponsor_owner(&key);
+        sponsor.limits = limits;
+        self.fee_sponsors.insert(&key, &sponsor);
+    }
+
+    pub fn get_fee_sponsor(
+        &self,
+        recipient: OmniAddress,
+        token_id: AccountId,
+    ) -> Option<FeeSponsor> {
+        self.fee_sponsors.get(&(recipient, token_id))
+    }
+
+    #[pause(name = "fee_settlement", except(roles(Role::DAO)))]
+    pub fn withdraw_fee_sponsor_balance(
+        &mut self,
+        recipient: OmniAddress,
+        token_id: AccountId,
+        amount: U128,
+    ) -> Promise {
+        let key = (recipient, token_id);
+        let mut sponsor = self.require_fee_sponsor_owner(&key);
+        sponsor.balance = U128(
+            sponsor
+                .balance
+                .0
+                .checked_sub(amount.0)
+                .sdk_expect("ERR_INSUFFICIENT_FEE_SPONSOR_BALANCE"),
+        );
+        self.fee_sponsors.insert(&key, &sponsor);
+
+        ext_token::ext(key.1.clone())
+            .with_static_gas(FT_TRANSFER_GAS)
+            .with_attached_deposit(ONE_YOCTO)
+            .ft_transfer(sponsor.owner, amount, None)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(WITHDRAW_FEE_SPONSOR_CALLBACK_GAS)
+                    .withdraw_fee_sponsor_balance_callback(key.0, key.1, amount),
+            )
+    }
+
+    #[private]
+    pub fn withdraw_fee_sponsor_balance_callback(
+        &mut self,
+        recipient: OmniAddress,
+        token_id: AccountId,
+        amount: U128,
+        #[callback_result] call_result: &Result<(), PromiseError>,
+    ) {
+        if call_result.is_err() {
+            self.credit_fee_sponsor(&(recipient, token_id), amount);
+        }
+    }
+}
+
+impl Contract {
+    // Adds the deposited tokens to the balance of the sponsor.
+    // Returns the amount that has to be refunded to the sender.
+    pub(crate) fn fund_fee_sponsor(
+        &mut self,
+        recipient: OmniAddress,
+        token_id: AccountId,
+        amount: U128,
+    ) -> U128 {
+        let key = (recipient, token_id);
+        require!(
+            self.fee_sponsors.get(&key).is_some(),
+            "ERR_FEE_SPONSOR_NOT_REGISTERED"
+        );
+        self.credit_fee_sponsor(&key, amount);
+        U128(0)
+    }
+
+    // Pays the token fee of a transfer sent without a fee from the balance of the recipient's sponsor.
+    // The sponsored fee is added both to the amount and to the fee of the transfer, so the recipient
+    // receives the full amount deposited by the sender.
+    pub(crate) fn apply_fee_sponsorship(&mut self, transfer_message: &mut TransferMessage) {
+        if !transfer_message.fee.is_zero() {
+            return;
+        }
+        let key = (
+            transfer_message.recipient.clone(),
+            self.get_token_id(&transfer_message.token),
+        );
+        let Some(mut sponsor) = self.fee_sponsors.get(&key) else {
+            return;
+        };
+
+        let fee = sponsor.limits.fee_per_transfer.0;
+        let period = Self::current_fee_sponsor_period();
+        let spent_in_period = if sponsor.period == period {
+            sponsor.spent_in_period.0
+        } else {
+            0
+        };
+        if fee == 0
+            || transfer_message.amount.0 < sponsor.limits.min_transfer_amount.0
+            || sponsor.balance.0 < fee
+            || sponsor.limits.max_daily_fees.is_some_and(|max_daily_fees| {
+                spent_in_period.saturating_add(fee) > max_daily_fees.0
+            })
+        {
+            return;
+        }
+
+        sponsor.balance = SafeAmount::from(sponsor.balance)
+            .checked_sub(fee.into())
+            .sdk_unwrap()
+            .into();
+        sponsor.period = period;
+        sponsor.spent_in_period = SafeAmount::from(spent_in_period)
+            .checked_add(fee.into())
+            .sdk_unwrap()
+            .into();
+        self.fee_sponsors.insert(&key, &sponsor);
+
+        transfer_message.amount = U128(transfer_message.amount.0.saturating_add(fee));
+        transfer_message.fee.fee = U128(fee);
+    }
+
+    fn credit_fee_sponsor(&mut self, key: &(OmniAddress, AccountId), amount: U128) {
+        let mut sponsor = self
+            .fee_sponsors
+            .get(key)
+            .sdk_expect("ERR_FEE_SPONSOR_NOT_REGISTERED");
+        sponsor.balance = SafeAmount::from(sponsor.balance)
+            .checked_add(amount.into())
+            .sdk_unwrap()
+            .into();
+        self.fee_sponsors.insert(key, &sponsor);
+    }
+
+    fn require_fee_sponsor_owner(&self, key: &(OmniAddress, AccountId)) -> FeeSponsor {
+        let sponsor = self
+            .fee_sponsors
+            .get(key)
+            .sdk_expect("ERR_FEE_SPONSOR_NOT_REGISTERED");
+        require!(
+            sponsor.owner == env::predecessor_account_id(),
+            "ERR_ONLY_FEE_SPONSOR_OWNER"
+        );
+        sponsor
+    }
+
+    fn current_fee_sponsor_period() -> u64 {
+        env::block_timestamp() / FEE_SPONSOR_PERIOD_NS
+    }
+}
@@ -0,0 +1,131 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::{Base58CryptoHash, U64};
+use near_sdk::{env, near, require, AccountId};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::TransferId;
+
+use crate::helpers::SdkExpect;
+use crate::{Contract, ContractExt, Role};
+
+const FORCE_FINALIZE_DELAY_NS: u64 = 3 * 24 * 60 * 60 * 1_000_000_000;
+
+/// Proposal of the DAO to finalise a transfer without a proof, for example when the transfer
+/// arrived on the destination chain but a bug of the chain prevents proving it.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForceFinalizeProposal {
+    // Hash of the published evidence that the funds arrived on the destination chain
+    pub justification_hash: Base58CryptoHash,
+    pub proposer: AccountId,
+    pub proposed_at: U64,
+    pub guardian: Option<AccountId>,
+}
+
+#[near]
+impl Contract {
+    pub fn get_force_finalize_proposal(
+        &self,
+        transfer_id: TransferId,
+    ) -> Option<ForceFinalizeProposal> {
+        self.force_finalize_proposals.get(&transfer_id)
+    }
+
+    /// Starts the timelock of the forced finalisation of a pending transfer.
+    /// Proposing again replaces the proposal and drops the approval of the guardian.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn propose_force_finalize(
+        &mut self,
+        transfer_id: TransferId,
+        justification_hash: Base58CryptoHash,
+    ) {
+        self.get_transfer_message_storage(transfer_id);
+        self.force_finalize_proposals.insert(
+            &transfer_id,
+            &ForceFinalizeProposal {
+                justification_hash,
+                proposer: env::predecessor_account_id(),
+                proposed_at: U64(env::block_timestamp()),
+                guardian: None,
+            },
+        );
+    }
+
+    /// The guardian approves the proposal with the same justification hash, so a replaced
+    /// proposal can't reuse an earlier approval.
+    #[access_control_any(roles(Role::Guardian))]
+    pub fn approve_force_finalize(
+        &mut self,
+        transfer_id: TransferId,
+        justification_hash: Base58CryptoHash,
+    ) {
+        let mut proposal = self
+            .force_finalize_proposals
+            .get(&transfer_id)
+            .sdk_expect("ERR_FORCE_FINALIZE_PROPOSAL_NOT_FOUND");
+        require!(
+            proposal.justification_hash == justification_hash,
+            "ERR_INVALID_JUSTIFICATION_HASH"
+        );
+        let guardian = env::predecessor_account_id();
+        require!(guardian != proposal.proposer, "ERR_PROPOSER_CANNOT_APPROVE");
+        proposal.guardian = Some(guardian);
+        self.force_finalize_proposals
+            .insert(&transfer_id, &proposal);
+    }
+
+    #[access_control_any(roles(Role::DAO, Role::Guardian))]
+    pub fn cancel_force_finalize(&mut self, transfer_id: TransferId) {
+        require!(
+            self.force_finalize_proposals.remove(&transfer_id).is_some(),
+            "ERR_FORCE_FINALIZE_PROPOSAL_NOT_FOUND"
+        );
+    }
+
+    /// Finalises the pending transfer once the proposal was approved by a guardian and the
+    /// timelock expired. The fees of the transfer are kept by the locker.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn force_finalize(
+        &mut self,
+        transfer_id: TransferId,
+        justification_hash: Base58CryptoHash,
+    ) {
+        let proposal = self
+            .force_finalize_proposals
+            .remove(&transfer_id)
+            .sdk_expect("ERR_FORCE_FINALIZE_PROPOSAL_NOT_FOUND");
+        require!(
+            proposal.justification_hash == justification_hash,
+            "ERR_INVALID_JUSTIFICATION_HASH"
+        );
+        let guardian = proposal
+            .guardian
+            .sdk_expect("ERR_FORCE_FINALIZE_NOT_APPROVED");
+        require!(
+            env::block_timestamp()
+                >= proposal
+                    .proposed_at
+                    .0
+                    .saturating_add(FORCE_FINALIZE_DELAY_NS),
+            "ERR_FORCE_FINALIZE_TIMELOCK_NOT_EXPIRED"
+        );
+
+        let owner = self.get_transfer_message_storage(transfer_id).owner;
+        let transfer_message = self.remove_transfer_message(transfer_id);
+        self.settle_fee_escalation(&transfer_id);
+        self.record_transfer_finalized(
+            &transfer_id,
+            transfer_message.get_destination_chain(),
+            &owner,
+        );
+
+        env::log_str(
+            &OmniBridgeEvent::ForceFinalizeTransferEvent {
+                transfer_message,
+                justification_hash,
+                proposer: proposal.proposer,
+                guardian,
+            }
+            .to_log_string(),
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::{borsh, env, near, require, AccountId, Gas, NearToken, Promise, PromiseError};
+use omni_types::locker_args::GovernanceActionArgs;
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::prover_result::ProverResult;
+use omni_types::{ChainKind, Nonce, OmniAddress};
+
+use crate::fee_recipient::FeeRecipientStrategy;
+use crate::helpers::SdkExpect;
+use crate::quota::TransferQuota;
+use crate::{Contract, ContractExt, Role};
+
+const EXECUTE_GOVERNANCE_ACTION_CALLBACK_GAS: Gas = Gas::from_tgas(10);
+
+/// Locker parameters that can be controlled by the governance contract on the governance chain.
+/// The action is borsh-encoded in the payload of the governance message.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GovernanceAction {
+    SetTransferQuota {
+        token_id: AccountId,
+        quota: Option<TransferQuota>,
+    },
+    SetMaxTransferRestores(u32),
+    SetStorageSafetyMargin(NearToken),
+    SetDerivedAccountFunding(NearToken),
+    SetFeeRecipientStrategy {
+        chain_kind: ChainKind,
+        strategy: FeeRecipientStrategy,
+    },
+}
+
+#[near]
+impl Contract {
+    /// Sets the contract on the governance chain whose proven decisions are executed by the locker.
+    /// `None` disables the cross-chain governance.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_governance_emitter(&mut self, emitter: Option<OmniAddress>) {
+        self.governance_emitter = emitter;
+    }
+
+    pub fn get_governance_emitter(&self) -> Option<OmniAddress> {
+        self.governance_emitter.clone()
+    }
+
+    pub fn get_last_governance_nonce(&self) -> Nonce {
+        self.last_governance_nonce
+    }
+
+    pub fn execute_governance_action(
+        &mut self,
+        #[serializer(borsh)] args: GovernanceActionArgs,
+    ) -> Promise {
+        let emitter = self
+            .governance_emitter
+            .as_ref()
+            .sdk_expect("ERR_GOVERNANCE_EMITTER_NOT_SET");
+        require!(
+            emitter.get_chain() == args.chain_kind,
+            "ERR_INVALID_GOVERNANCE_CHAIN"
+        );
+
+        self.verify_proof(args.chain_kind, args.prover_args).then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(EXECUTE_GOVERNANCE_ACTION_CALLBACK_GAS)
+                .execute_governance_action_callback(),
+        )
+    }
+
+    #[private]
+    pub fn execute_governance_action_callback(
+        &mut self,
+        #[callback_result]
+        #[serializer(borsh)]
+        call_result: Result<ProverResult, PromiseError>,
+    ) {
+        let Ok(ProverResult::GovernanceAction(message)) = call_result else {
+            env::panic_str("Invalid proof message")
+        };
+
+        require!(
+            self.governance_emitter.as_ref() == Some(&message.emitter_address),
+            "ERR_UNKNOWN_GOVERNANCE_EMITTER"
+        );
+        // Nonces are strictly increasing, so every decision is executed at most once and in order
+        require!(
+            message.nonce > self.last_governance_nonce,
+            "ERR_GOVERNANCE_ACTION_ALREADY_EXECUTED"
+        );
+        self.last_governance_nonce = message.nonce;
+
+        let action: GovernanceAction =
+            borsh::from_slice(&message.action).sdk_expect("ERR_INVALID_GOVERNANCE_ACTION");
+        self.apply_governance_action(action);
+
+        env::log_str(
+            &OmniBridgeEvent::GovernanceActionEvent {
+                nonce: message.nonce,
+                emitter_address: message.emitter_address,
+            }
+            .to_log_string(),
+        );
+    }
+}
+
+impl Contract {
+    pub(crate) fn apply_governance_action(&mut self, action: GovernanceAction) {
+        match action {
+            GovernanceAction::SetTransferQuota { token_id, quota } => {
+                if let Some(quota) = quota {
+                    self.transfer_quotas.insert(&token_id, &quota);
+                } else {
+                    self.transfer_quotas.remove(&token_id);
+                }
+            }
+            GovernanceAction::SetMaxTransferRestores(max_restores) => {
+                self.max_transfer_restores = max_restores;
+            }
+            GovernanceAction::SetStorageSafetyMargin(margin) => {
+                self.storage_safety_margin = margin;
+            }
+            GovernanceAction::SetDerivedAccountFunding(amount) => {
+                self.derived_account_funding = amount;
+            }
+            GovernanceAction::SetFeeRecipientStrategy {
+                chain_kind,
+                strategy,
+            } => {
+                if strategy == FeeRecipientStrategy::default() {
+                    self.fee_recipient_strategies.remove(&chain_kind);
+                } else {
+                    self.fee_recipient_strategies.insert(&chain_kind, &strategy);
+                }
+            }
+        }
+    }
+}
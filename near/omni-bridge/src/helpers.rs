@@ -2,6 +2,10 @@ use near_sdk::{
     env::{self, panic_str},
     serde_json, Promise, PromiseIndex,
 };
+use omni_types::errors::BridgeError;
+use omni_types::safe_amount::AmountError;
+use omni_types::units::UnitsError;
+use omni_types::FeeExceedsAmount;
 use serde::Serialize;
 
 pub trait SdkExpect<T> {
@@ -20,6 +24,46 @@ impl<T, E> SdkExpect<T> for Result<T, E> {
     }
 }
 
+/// Errors of the shared types that carry their own `ERR_` code.
+pub trait ErrorCode {
+    fn error_code(&self) -> &'static str;
+}
+
+impl ErrorCode for AmountError {
+    fn error_code(&self) -> &'static str {
+        self.as_str()
+    }
+}
+
+impl ErrorCode for BridgeError {
+    fn error_code(&self) -> &'static str {
+        self.as_str()
+    }
+}
+
+impl ErrorCode for FeeExceedsAmount {
+    fn error_code(&self) -> &'static str {
+        self.as_str()
+    }
+}
+
+impl ErrorCode for UnitsError {
+    fn error_code(&self) -> &'static str {
+        self.as_str()
+    }
+}
+
+/// Like `SdkExpect`, but panics with the code of the error.
+pub trait SdkUnwrap<T> {
+    fn sdk_unwrap(self) -> T;
+}
+
+impl<T, E: ErrorCode> SdkUnwrap<T> for Result<T, E> {
+    fn sdk_unwrap(self) -> T {
+        self.unwrap_or_else(|err| panic_str(err.error_code()))
+    }
+}
+
 pub enum PromiseOrPromiseIndexOrValue<T> {
     Promise(Promise),
     PromiseIndex(PromiseIndex),
@@ -0,0 +1,137 @@
+use near_plugins::{access_control_any, pause, AccessControllable, Pausable};
+use near_sdk::json_types::U128;
+use near_sdk::{env, near, require, AccountId, Gas, Promise, PromiseError};
+use omni_types::safe_amount::SafeAmount;
+use omni_types::units::BPS_DENOMINATOR;
+use omni_types::ChainKind;
+
+use crate::external::ext_token;
+use crate::helpers::SdkUnwrap;
+use crate::locked::LockedState;
+use crate::{Contract, ContractExt, Role, FT_TRANSFER_GAS, ONE_YOCTO};
+
+const MAX_INBOUND_FEE_BPS: u16 = BPS_DENOMINATOR;
+const WITHDRAW_PROTOCOL_FEES_CALLBACK_GAS: Gas = Gas::from_tgas(5);
+
+/// Fee deducted from the deposits of a UTXO chain credited to NEAR accounts.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InboundFee {
+    pub fee_bps: u16,
+    pub flat_fee: U128,
+}
+
+#[near]
+impl Contract {
+    /// Sets the fee of the deposits from the UTXO chain to NEAR. `None` removes the fee.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_inbound_fee(&mut self, chain_kind: ChainKind, fee: Option<InboundFee>) {
+        if let Some(fee) = fee {
+            require!(fee.fee_bps <= MAX_INBOUND_FEE_BPS, "ERR_INVALID_FEE_BPS");
+            self.inbound_fees.insert(&chain_kind, &fee);
+        } else {
+            self.inbound_fees.remove(&chain_kind);
+        }
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn add_inbound_fee_exempt_account(&mut self, account_id: AccountId) {
+        self.inbound_fee_exempt_accounts.insert(&account_id);
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn remove_inbound_fee_exempt_account(&mut self, account_id: AccountId) {
+        self.inbound_fee_exempt_accounts.remove(&account_id);
+    }
+
+    pub fn get_inbound_fee(&self, chain_kind: ChainKind) -> Option<InboundFee> {
+        self.inbound_fees.get(&chain_kind)
+    }
+
+    pub fn is_inbound_fee_exempt(&self, account_id: AccountId) -> bool {
+        self.inbound_fee_exempt_accounts.contains(&account_id)
+    }
+
+    pub fn get_protocol_fees(&self, token_id: AccountId) -> U128 {
+        self.protocol_fees.get(&token_id).unwrap_or_default()
+    }
+
+    /// Paused with the fee settlement even for the DAO, which has to lift the pause first.
+    #[access_control_any(roles(Role::DAO))]
+    #[pause(name = "fee_settlement")]
+    pub fn withdraw_protocol_fees(
+        &mut self,
+        token_id: AccountId,
+        recipient: AccountId,
+        amount: U128,
+    ) -> Promise {
+        let balance = SafeAmount::from(self.get_protocol_fees(token_id.clone()))
+            .checked_sub(amount.into())
+            .unwrap_or_else(|_| env::panic_str("ERR_INSUFFICIENT_PROTOCOL_FEES"));
+        self.protocol_fees.insert(&token_id, &balance.into());
+        self.unlock_amount(&token_id, LockedState::FeePool, amount.0);
+
+        ext_token::ext(token_id.clone())
+            .with_static_gas(FT_TRANSFER_GAS)
+            .with_attached_deposit(ONE_YOCTO)
+            .ft_transfer(recipient, amount, None)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(WITHDRAW_PROTOCOL_FEES_CALLBACK_GAS)
+                    .withdraw_protocol_fees_callback(token_id, amount),
+            )
+    }
+
+    #[private]
+    pub fn withdraw_protocol_fees_callback(
+        &mut self,
+        token_id: AccountId,
+        amount: U128,
+        #[callback_result] call_result: &Result<(), PromiseError>,
+    ) {
+        if call_result.is_err() {
+            self.accrue_protocol_fee(&token_id, amount);
+        }
+    }
+}
+
+impl Contract {
+    // Returns the fee of the deposit credited to `recipient`, capped to the deposited amount.
+    pub(crate) fn get_inbound_fee_amount(
+        &self,
+        chain_kind: ChainKind,
+        recipient: &AccountId,
+        amount: U128,
+    ) -> U128 {
+        let Some(fee) = self.inbound_fees.get(&chain_kind) else {
+            return U128(0);
+        };
+        if self.inbound_fee_exempt_accounts.contains(recipient) {
+            return U128(0);
+        }
+
+        let amount = SafeAmount::from(amount);
+        let fee_amount = amount
+            .checked_mul_div(fee.fee_bps.into(), MAX_INBOUND_FEE_BPS.into())
+            .and_then(|bps_fee| bps_fee.checked_add(fee.flat_fee.into()))
+            .map_or(amount, |fee_amount| fee_amount.min(amount));
+        fee_amount.into()
+    }
+
+    // Collects a fee charged by the protocol, of which a share may fund the rewards.
+    pub(crate) fn collect_protocol_fee(&mut self, token_id: &AccountId, fee: U128) {
+        let fee = self.fund_rewards_from_protocol_fee(token_id, fee);
+        self.accrue_protocol_fee(token_id, fee);
+    }
+
+    pub(crate) fn accrue_protocol_fee(&mut self, token_id: &AccountId, fee: U128) {
+        if fee.0 == 0 {
+            return;
+        }
+        let balance = SafeAmount::from(self.get_protocol_fees(token_id.clone()))
+            .checked_add(fee.into())
+            .sdk_unwrap();
+        self.protocol_fees.insert(token_id, &balance.into());
+        self.lock_amount(token_id, LockedState::FeePool, fee.0);
+    }
+}
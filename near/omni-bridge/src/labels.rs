@@ -0,0 +1,70 @@
+use near_sdk::{env, near, require};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::{OmniAddress, TransferId};
+
+use crate::{Contract, ContractExt};
+
+pub const MAX_TRANSFER_LABELS: usize = 4;
+pub const MAX_TRANSFER_LABEL_LEN: usize = 64;
+
+#[near]
+impl Contract {
+    /// Attaches labels to a pending transfer, replacing the previous ones, so the initiator
+    /// can reconcile its transfers off-chain. The labels are kept after the transfer is finalised.
+    /// The storage is paid from the attached deposit or the storage balance of the caller.
+    #[payable]
+    pub fn set_transfer_labels(&mut self, transfer_id: TransferId, labels: Vec<String>) {
+        require!(
+            labels.len() <= MAX_TRANSFER_LABELS,
+            "ERR_TOO_MANY_TRANSFER_LABELS"
+        );
+        require!(
+            labels
+                .iter()
+                .all(|label| !label.is_empty() && label.len() <= MAX_TRANSFER_LABEL_LEN),
+            "ERR_INVALID_TRANSFER_LABEL"
+        );
+
+        let transfer = self.get_transfer_message_storage(transfer_id);
+        let predecessor_account_id = env::predecessor_account_id();
+        require!(
+            transfer.owner == predecessor_account_id
+                || transfer.message.sender == OmniAddress::Near(predecessor_account_id.clone()),
+            "ERR_ONLY_TRANSFER_INITIATOR_CAN_SET_LABELS"
+        );
+
+        let storage_usage = env::storage_usage();
+        if labels.is_empty() {
+            self.transfer_labels.remove(&transfer_id);
+        } else {
+            self.transfer_labels.insert(&transfer_id, &labels);
+        }
+        let current_storage_usage = env::storage_usage();
+
+        let required_balance = env::storage_byte_cost()
+            .saturating_mul((current_storage_usage.saturating_sub(storage_usage)).into());
+        self.update_storage_balance(
+            predecessor_account_id.clone(),
+            required_balance,
+            env::attached_deposit(),
+        );
+
+        // Replacing the labels with shorter ones returns the freed storage to the caller
+        let refund = env::storage_byte_cost()
+            .saturating_mul((storage_usage.saturating_sub(current_storage_usage)).into());
+        if let Some(mut storage) = self.accounts_balances.get(&predecessor_account_id) {
+            storage.available = storage.available.saturating_add(refund);
+            self.accounts_balances
+                .insert(&predecessor_account_id, &storage);
+        }
+
+        self.emit_event(&OmniBridgeEvent::TransferLabelsEvent {
+            transfer_id,
+            labels,
+        });
+    }
+
+    pub fn get_transfer_labels(&self, transfer_id: TransferId) -> Vec<String> {
+        self.transfer_labels.get(&transfer_id).unwrap_or_default()
+    }
+}
@@ -1,5 +1,5 @@
 #![allow(clippy::too_many_arguments)]
-use helpers::{PromiseOrPromiseIndexOrValue, SdkExpect};
+use helpers::{PromiseOrPromiseIndexOrValue, SdkExpect, SdkUnwrap};
 use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
 use near_contract_standards::storage_management::StorageBalance;
 use near_plugins::{
@@ -7,40 +7,138 @@ use near_plugins::{
     Upgradable,
 };
 
+use bootstrap::BootstrapProgress;
+use btc::UtxoWithdrawLimits;
+use cancellation::TransferBeneficiaryRecord;
+use claim_records::{ClaimEvidence, TransferClaimRecord};
+use connector_failover::{ConnectorHealth, ConnectorInFlight, FailoverPolicy};
+use connector_registry::{ConnectorId, ConnectorRecord};
+use dead_letter::DEFAULT_MAX_TRANSFER_RESTORES;
+use event_emission::{EventEmissionState, CONFIGURABLE_EVENTS};
+use external::{
+    ext_bridge_token_facory, ext_deployer, ext_omni_prover_proxy, ext_token, ext_wnear_token,
+    InitTransferResumeArgs,
+};
+use fee_escalation::{FeeEscalation, FeeEscalationPolicy};
+use fee_ledger::FeeAsset;
+use fee_recipient::FeeRecipientStrategy;
+use fee_sponsors::FeeSponsor;
+use force_finalize::ForceFinalizeProposal;
+use inbound_fees::InboundFee;
+use locked::{LockedBreakdown, LockedState};
+use maintenance::MaintenanceConfig;
+use metrics::MetricId;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LookupMap, LookupSet, UnorderedMap};
-use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::collections::{LookupMap, LookupSet, UnorderedMap, UnorderedSet};
+use near_sdk::json_types::{Base64VecU8, U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
-use near_sdk::serde_json::json;
 use near_sdk::{
-    env, ext_contract, near, require, serde_json, AccountId, BorshStorageKey, CryptoHash, Gas,
-    GasWeight, NearToken, PanicOnDefault, Promise, PromiseError, PromiseOrValue, PromiseResult,
+    env, near, require, serde_json, AccountId, BorshStorageKey, CryptoHash, Gas, GasWeight,
+    NearToken, PanicOnDefault, Promise, PromiseError, PromiseOrValue, PromiseResult,
 };
-use omni_types::btc::{TxOut, UTXOChainConfig};
+use omni_types::btc::{OutPoint, ScriptKind, TxOut, UTXOChainConfig};
 use omni_types::locker_args::{
     AddDeployedTokenArgs, BindTokenArgs, ClaimFeeArgs, DeployTokenArgs, FinTransferArgs,
     StorageDepositAction,
 };
-use omni_types::mpc_types::SignatureResponse;
-use omni_types::near_events::OmniBridgeEvent;
+use omni_types::mpc_types::{SignatureRequest, SignatureResponse, SignatureScheme};
+use omni_types::near_events::{EventSchema, OmniBridgeEvent};
 use omni_types::prover_result::ProverResult;
+use omni_types::units;
 use omni_types::{
-    BasicMetadata, BridgeOnTransferMsg, ChainKind, FastFinTransferMsg, FastTransfer,
-    FastTransferId, FastTransferStatus, Fee, InitTransferMsg, MetadataPayload, Nonce, OmniAddress,
-    PayloadType, SignRequest, TransferId, TransferIdKind, TransferMessage, TransferMessagePayload,
-    UnifiedTransferId, UpdateFee, UtxoFinTransferMsg, H160,
+    BasicMetadata, BridgeOnTransferMsg, ChainKind, ChainTraits, EvmDestinationCall,
+    FastFinTransferMsg, FastTransfer, FastTransferId, FastTransferStatus, Fee, FeeModel,
+    InitTransferMsg, MetadataPayload, Nonce, OmniAddress, PayloadType, TransferBeneficiary,
+    TransferId, TransferIdKind, TransferMessage, TransferMessagePayload, UnifiedTransferId,
+    UpdateFee, UtxoFinTransferMsg, H160,
 };
+use outpoints::OutpointReservation;
+use preferred_relayers::PreferredRelayer;
+use quota::{TransferQuota, TransferQuotaUsage};
+use rewards::{RewardEpoch, RewardedToken, RewardsConfig};
+use sla::{LatencyBucket, RouteLatency, TransferTimestamps};
 use std::collections::HashMap;
 use std::str::FromStr;
 use storage::{
     Decimals, FastTransferStatusStorage, TransferMessageStorage, TransferMessageStorageValue,
     NEP141_DEPOSIT,
 };
-
+use submission_intents::SubmissionIntent;
+use transfer_caps::OversizeTransferAllowance;
+use unclaimed_deposits::UnclaimedDeposit;
+use utxo_address_formats::UtxoAddressFormatTable;
+use utxo_fee_rates::UtxoFeeRate;
+use utxo_key_epochs::UtxoKeyRotation;
+use utxo_refunds::UtxoRefundPolicy;
+use utxo_withdrawals::UtxoWithdrawal;
+use watchtowers::{TransferFlag, Watchtower, WatchtowerConfig};
+
+mod auditor;
+mod bootstrap;
 mod btc;
+mod cancellation;
+mod chain_traits;
+mod claim_records;
+mod connector_failover;
+mod connector_registry;
+mod connector_version;
+mod custody_utxos;
+mod dead_letter;
+mod derived_accounts;
+mod destination_calls;
+mod error_catalog;
+mod event_emission;
+mod external;
+mod fee_conversion;
+mod fee_escalation;
+mod fee_floors;
+mod fee_ledger;
+mod fee_quotes;
+mod fee_recipient;
+mod fee_sponsors;
+mod force_finalize;
+mod governance;
 mod helpers;
+mod inbound_fees;
+mod labels;
+mod locked;
+mod maintenance;
+mod messaging;
+mod metrics;
 mod migrate;
+mod origin_senders;
+mod orphans;
+mod outpoints;
+mod pending_index;
+mod preferred_relayers;
+mod proof_validation;
+mod quota;
+mod rewards;
+mod role_members;
+mod route_limits;
+mod routes;
+mod script_hash_recipients;
+mod signature_schemes;
+mod sla;
 mod storage;
+mod storage_guard;
+mod submission_intents;
+mod transfer_caps;
+mod transfer_expiry;
+mod transfer_notices;
+mod transfer_ownership;
+mod tvl_caps;
+mod unclaimed_deposits;
+mod utxo;
+mod utxo_address_formats;
+mod utxo_batches;
+mod utxo_consolidation;
+mod utxo_fee_rates;
+mod utxo_key_epochs;
+mod utxo_refunds;
+mod utxo_registry;
+mod utxo_withdrawals;
+mod watchtowers;
 
 #[cfg(test)]
 mod tests;
@@ -96,6 +194,86 @@ enum StorageKey {
     InitTransferPromises,
     MigratedTokens,
     FinalisedUtxoTransfers,
+    TransferQuotas,
+    TransferQuotaUsage,
+    QuotaExemptAccounts,
+    OriginSenders,
+    FundedDerivedAccounts,
+    LockedBreakdown,
+    TransferRestoreCounts,
+    DeadLetterTransfers,
+    FeeRecipientStrategies,
+    UnreportedFeeTransfers,
+    ConnectorInterfaceVersions,
+    RewardedTokens,
+    RewardEpochs,
+    RewardPoints,
+    ReservedOutpoints,
+    UtxoWithdrawLimits,
+    TransferTimestamps,
+    LatencyBuckets,
+    FeeSponsors,
+    TransferLabels,
+    PendingTransfersIndex,
+    PendingTransfersByChain(ChainKind),
+    TransferBeneficiaries,
+    ScriptHashAllowlistChains,
+    AllowedScriptHashRecipients,
+    UnclaimedDepositChains,
+    UnclaimedDeposits,
+    FeeSwapPools,
+    FeeConversions,
+    ForceFinalizeProposals,
+    UtxoKeyRotations,
+    LegacyOutpoints,
+    SignatureSchemes,
+    Connectors,
+    ChainConnectorIds,
+    UnreportedFeeConnectors,
+    InboundFees,
+    InboundFeeExemptAccounts,
+    ProtocolFees,
+    MaxTransferAmounts,
+    OversizeTransferAllowances,
+    StandbyConnectors,
+    FailoverPolicies,
+    ConnectorHealth,
+    ConnectorInFlight,
+    TransferClaimRecords,
+    UtxoAddressFormats,
+    MinNativeRelayerFees,
+    MinTokenRelayerFees,
+    RouteLatencies,
+    ExtraMsgRequiredChains,
+    UtxoRefundPolicies,
+    ChainTraits,
+    Watchtowers,
+    TransferFlags,
+    ClaimableFees,
+    SigningRequestedTransfers,
+    MetricCounters,
+    LastVerifiedProofs,
+    SubmissionIntents,
+    DestinationCallGasFees,
+    DestinationCalls,
+    PendingTransfersOwnerIndex,
+    PendingTransfersByOwner(AccountId),
+    UtxoFeeRates,
+    MaxAllowedWithdrawFees,
+    UtxoDustLimits,
+    ApprovedSweepDestinations,
+    TvlCaps,
+    TokenTvl,
+    MessageHandlers,
+    ReceivedMessages,
+    UtxoScriptKinds,
+    CustodyUtxos,
+    CustodyTrackedChains,
+    FeeEscalationPolicies,
+    FeeEscalations,
+    PreferredRelayers,
+    UtxoWithdrawals,
+    SweepingOutpoints,
 }
 
 #[derive(AccessControlRole, Deserialize, Serialize, Copy, Clone)]
@@ -112,48 +290,9 @@ pub enum Role {
     NativeFeeRestricted,
     RbfOperator,
     TokenUpgrader,
-}
-
-#[ext_contract(ext_token)]
-pub trait ExtToken {
-    fn ft_transfer(
-        &mut self,
-        receiver_id: AccountId,
-        amount: U128,
-        memo: Option<String>,
-    ) -> PromiseOrValue<U128>;
-
-    fn ft_transfer_call(
-        &mut self,
-        receiver_id: AccountId,
-        amount: U128,
-        memo: Option<String>,
-        msg: String,
-    ) -> PromiseOrValue<U128>;
-
-    fn ft_metadata(&self) -> FungibleTokenMetadata;
-
-    fn storage_deposit(
-        &mut self,
-        account_id: &AccountId,
-        registration_only: Option<bool>,
-    ) -> Option<StorageBalance>;
-
-    fn storage_balance_of(&mut self, account_id: &AccountId) -> Option<StorageBalance>;
-
-    fn mint(&mut self, account_id: AccountId, amount: U128, msg: Option<String>);
-
-    fn burn(&mut self, amount: U128);
-
-    fn set_metadata(
-        &mut self,
-        name: Option<String>,
-        symbol: Option<String>,
-        reference: Option<String>,
-        reference_hash: Option<Base64VecU8>,
-        decimals: Option<u8>,
-        icon: Option<String>,
-    );
+    Guardian,
+    FeeRateOracle,
+    UtxoConsolidator,
 }
 
 #[near(serializers = [json])]
@@ -162,36 +301,6 @@ pub struct BtcConfig {
     pub chain_signatures_root_public_key: Option<near_sdk::PublicKey>,
 }
 
-#[ext_contract(ext_bridge_token_facory)]
-pub trait ExtBridgeTokenFactory {
-    fn set_controller_for_tokens(&self, tokens_account_id: Vec<AccountId>);
-}
-
-#[ext_contract(ext_signer)]
-pub trait ExtSigner {
-    fn sign(&mut self, request: SignRequest);
-}
-
-#[ext_contract(ext_omni_prover_proxy)]
-pub trait Prover {
-    fn verify_proof(&self, #[serializer(borsh)] proof: Vec<u8>);
-}
-
-#[ext_contract(ext_wnear_token)]
-pub trait ExtWNearToken {
-    fn near_withdraw(&self, amount: U128);
-}
-
-#[ext_contract(ext_deployer)]
-pub trait TokenDeployer {
-    fn deploy_token(&self, account_id: AccountId, metadata: BasicMetadata) -> Promise;
-}
-
-#[ext_contract(ext_utxo_connector)]
-pub trait ExtUTXOConnector {
-    fn withdraw_rbf(&mut self, original_btc_pending_verify_id: String, output: Vec<TxOut>);
-}
-
 #[near(contract_state)]
 #[derive(Pausable, Upgradable, PanicOnDefault)]
 #[access_control(role_type(Role))]
@@ -224,12 +333,108 @@ pub struct Contract {
     pub init_transfer_promises: LookupMap<AccountId, CryptoHash>,
     pub utxo_chain_connectors: HashMap<ChainKind, UTXOChainConfig>,
     pub migrated_tokens: LookupMap<AccountId, AccountId>,
+    pub transfer_quotas: LookupMap<AccountId, TransferQuota>,
+    pub transfer_quota_usage: LookupMap<(AccountId, AccountId), TransferQuotaUsage>,
+    pub quota_exempt_accounts: LookupSet<AccountId>,
+    pub origin_senders: LookupMap<UnifiedTransferId, Vec<OmniAddress>>,
+    pub derived_account_funding: NearToken,
+    pub funded_derived_accounts: LookupSet<AccountId>,
+    pub locked_breakdown: LookupMap<AccountId, LockedBreakdown>,
+    pub max_transfer_restores: u32,
+    pub transfer_restore_counts: LookupMap<TransferId, u32>,
+    pub dead_letter_transfers: LookupMap<TransferId, TransferMessageStorage>,
+    pub fee_recipient_strategies: LookupMap<ChainKind, FeeRecipientStrategy>,
+    pub unreported_fee_transfers: LookupMap<TransferId, TransferMessage>,
+    pub connector_interface_versions: LookupMap<ChainKind, u32>,
+    pub storage_safety_margin: NearToken,
+    pub governance_emitter: Option<OmniAddress>,
+    pub last_governance_nonce: Nonce,
+    pub rewards_config: Option<RewardsConfig>,
+    pub rewarded_tokens: LookupMap<AccountId, RewardedToken>,
+    pub reward_epochs: LookupMap<u64, RewardEpoch>,
+    pub reward_points: LookupMap<(AccountId, u64), U128>,
+    pub reserved_outpoints: LookupMap<(ChainKind, OutPoint), OutpointReservation>,
+    pub utxo_withdraw_limits: LookupMap<ChainKind, UtxoWithdrawLimits>,
+    pub transfer_timestamps: LookupMap<TransferId, TransferTimestamps>,
+    pub latency_buckets: LookupMap<(ChainKind, u64), LatencyBucket>,
+    pub fee_sponsors: LookupMap<(OmniAddress, AccountId), FeeSponsor>,
+    pub transfer_labels: LookupMap<TransferId, Vec<String>>,
+    pub pending_transfers_by_chain: LookupMap<ChainKind, UnorderedSet<TransferId>>,
+    pub transfer_beneficiaries: LookupMap<TransferId, TransferBeneficiaryRecord>,
+    pub maintenance_config: Option<MaintenanceConfig>,
+    pub maintenance_budget: NearToken,
+    pub script_hash_allowlist_chains: LookupSet<ChainKind>,
+    pub allowed_script_hash_recipients: LookupSet<OmniAddress>,
+    pub unclaimed_deposit_chains: LookupSet<ChainKind>,
+    pub unclaimed_deposits: LookupMap<TransferId, UnclaimedDeposit>,
+    pub fee_swap_contract: Option<AccountId>,
+    pub fee_swap_pools: LookupMap<AccountId, u64>,
+    pub fee_conversions: LookupMap<AccountId, u16>,
+    pub force_finalize_proposals: LookupMap<TransferId, ForceFinalizeProposal>,
+    pub utxo_key_rotations: LookupMap<ChainKind, UtxoKeyRotation>,
+    pub legacy_outpoints: LookupSet<(ChainKind, OutPoint)>,
+    pub signature_schemes: LookupMap<ChainKind, SignatureScheme>,
+    pub connectors: LookupMap<ConnectorId, ConnectorRecord>,
+    pub chain_connector_ids: LookupMap<ChainKind, ConnectorId>,
+    pub last_connector_id: ConnectorId,
+    pub unreported_fee_connectors: LookupMap<TransferId, ConnectorId>,
+    pub inbound_fees: LookupMap<ChainKind, InboundFee>,
+    pub inbound_fee_exempt_accounts: LookupSet<AccountId>,
+    pub protocol_fees: LookupMap<AccountId, U128>,
+    pub max_transfer_amounts: LookupMap<AccountId, U128>,
+    pub oversize_transfer_allowances: LookupMap<(AccountId, AccountId), OversizeTransferAllowance>,
+    pub auditor: Option<AccountId>,
+    pub last_accounting_seq: u64,
+    pub standby_connectors: LookupMap<ChainKind, AccountId>,
+    pub failover_policies: LookupMap<ChainKind, FailoverPolicy>,
+    pub connector_health: LookupMap<ConnectorId, ConnectorHealth>,
+    pub connector_in_flight: LookupMap<ConnectorId, ConnectorInFlight>,
+    pub transfer_claim_records: LookupMap<TransferId, TransferClaimRecord>,
+    pub utxo_address_formats: LookupMap<ChainKind, UtxoAddressFormatTable>,
+    pub min_native_relayer_fees: LookupMap<ChainKind, U128>,
+    pub min_token_relayer_fees: LookupMap<(ChainKind, AccountId), U128>,
+    pub route_latencies: LookupMap<(ChainKind, ChainKind), RouteLatency>,
+    pub extra_msg_required_chains: LookupSet<ChainKind>,
+    pub event_emissions: [EventEmissionState; CONFIGURABLE_EVENTS.len()],
+    pub utxo_refund_policies: LookupMap<ChainKind, UtxoRefundPolicy>,
+    pub chain_traits: LookupMap<ChainKind, ChainTraits>,
+    pub watchtower_config: Option<WatchtowerConfig>,
+    pub watchtowers: LookupMap<AccountId, Watchtower>,
+    pub transfer_flags: LookupMap<TransferId, Vec<TransferFlag>>,
+    pub claimable_fees: LookupMap<(AccountId, FeeAsset), U128>,
+    pub signing_requested_transfers: LookupMap<TransferId, AccountId>,
+    pub bootstrap: Option<BootstrapProgress>,
+    pub metric_counters: UnorderedMap<MetricId, U128>,
+    pub last_verified_proofs: LookupMap<ChainKind, U64>,
+    pub submission_intents: LookupMap<TransferId, SubmissionIntent>,
+    pub destination_call_gas_fees: LookupMap<ChainKind, U128>,
+    pub destination_calls: LookupMap<TransferId, EvmDestinationCall>,
+    pub pending_transfers_by_owner: LookupMap<AccountId, UnorderedSet<TransferId>>,
+    pub utxo_fee_rates: LookupMap<ChainKind, UtxoFeeRate>,
+    pub max_allowed_withdraw_fees: LookupMap<ChainKind, U128>,
+    pub utxo_dust_limits: LookupMap<ChainKind, U64>,
+    pub approved_sweep_destinations: LookupSet<(ChainKind, String)>,
+    pub tvl_caps: LookupMap<AccountId, U128>,
+    pub token_tvl: LookupMap<AccountId, U128>,
+    pub current_message_nonce: Nonce,
+    pub message_handlers: LookupSet<AccountId>,
+    pub received_messages: LookupSet<(ChainKind, Nonce)>,
+    pub utxo_script_kinds: LookupMap<ChainKind, Vec<ScriptKind>>,
+    pub custody_utxos: LookupMap<(ChainKind, OutPoint), TxOut>,
+    pub custody_tracked_chains: LookupSet<ChainKind>,
+    pub fee_escalation_policies: LookupMap<ChainKind, FeeEscalationPolicy>,
+    pub fee_escalations: LookupMap<TransferId, FeeEscalation>,
+    pub fee_escalation_budget: NearToken,
+    pub preferred_relayers: LookupMap<TransferId, PreferredRelayer>,
+    pub utxo_withdrawals: LookupMap<TransferId, UtxoWithdrawal>,
+    pub sweeping_outpoints: LookupSet<(ChainKind, OutPoint)>,
 }
 
 #[near]
 impl Contract {
     #[pause(except(roles(Role::DAO, Role::UnrestrictedDeposit)))]
     pub fn ft_on_transfer(&mut self, sender_id: AccountId, amount: U128, msg: String) {
+        self.require_bridge_activated();
         let token_id = env::predecessor_account_id();
         let parsed_msg: BridgeOnTransferMsg = serde_json::from_str(&msg)
             .or_else(|_| serde_json::from_str(&msg).map(BridgeOnTransferMsg::InitTransfer))
@@ -251,6 +456,11 @@ impl Contract {
                 &sender_id,
                 utxo_fin_transfer_msg,
             ),
+            BridgeOnTransferMsg::FundFeeSponsor { recipient } => {
+                PromiseOrPromiseIndexOrValue::Value(
+                    self.fund_fee_sponsor(recipient, token_id, amount),
+                )
+            }
             BridgeOnTransferMsg::SwapMigratedToken => {
                 self.swap_migrated_token(sender_id, token_id, amount)
                     .detach();
@@ -283,6 +493,101 @@ impl Contract {
             init_transfer_promises: LookupMap::new(StorageKey::InitTransferPromises),
             utxo_chain_connectors: HashMap::new(),
             migrated_tokens: LookupMap::new(StorageKey::MigratedTokens),
+            transfer_quotas: LookupMap::new(StorageKey::TransferQuotas),
+            transfer_quota_usage: LookupMap::new(StorageKey::TransferQuotaUsage),
+            quota_exempt_accounts: LookupSet::new(StorageKey::QuotaExemptAccounts),
+            origin_senders: LookupMap::new(StorageKey::OriginSenders),
+            derived_account_funding: NearToken::from_yoctonear(0),
+            funded_derived_accounts: LookupSet::new(StorageKey::FundedDerivedAccounts),
+            locked_breakdown: LookupMap::new(StorageKey::LockedBreakdown),
+            max_transfer_restores: DEFAULT_MAX_TRANSFER_RESTORES,
+            transfer_restore_counts: LookupMap::new(StorageKey::TransferRestoreCounts),
+            dead_letter_transfers: LookupMap::new(StorageKey::DeadLetterTransfers),
+            fee_recipient_strategies: LookupMap::new(StorageKey::FeeRecipientStrategies),
+            unreported_fee_transfers: LookupMap::new(StorageKey::UnreportedFeeTransfers),
+            connector_interface_versions: LookupMap::new(StorageKey::ConnectorInterfaceVersions),
+            storage_safety_margin: NearToken::from_yoctonear(0),
+            governance_emitter: None,
+            last_governance_nonce: 0,
+            rewards_config: None,
+            rewarded_tokens: LookupMap::new(StorageKey::RewardedTokens),
+            reward_epochs: LookupMap::new(StorageKey::RewardEpochs),
+            reward_points: LookupMap::new(StorageKey::RewardPoints),
+            reserved_outpoints: LookupMap::new(StorageKey::ReservedOutpoints),
+            utxo_withdraw_limits: LookupMap::new(StorageKey::UtxoWithdrawLimits),
+            transfer_timestamps: LookupMap::new(StorageKey::TransferTimestamps),
+            latency_buckets: LookupMap::new(StorageKey::LatencyBuckets),
+            fee_sponsors: LookupMap::new(StorageKey::FeeSponsors),
+            transfer_labels: LookupMap::new(StorageKey::TransferLabels),
+            pending_transfers_by_chain: LookupMap::new(StorageKey::PendingTransfersIndex),
+            transfer_beneficiaries: LookupMap::new(StorageKey::TransferBeneficiaries),
+            maintenance_config: None,
+            maintenance_budget: NearToken::from_yoctonear(0),
+            script_hash_allowlist_chains: LookupSet::new(StorageKey::ScriptHashAllowlistChains),
+            allowed_script_hash_recipients: LookupSet::new(StorageKey::AllowedScriptHashRecipients),
+            unclaimed_deposit_chains: LookupSet::new(StorageKey::UnclaimedDepositChains),
+            unclaimed_deposits: LookupMap::new(StorageKey::UnclaimedDeposits),
+            fee_swap_contract: None,
+            fee_swap_pools: LookupMap::new(StorageKey::FeeSwapPools),
+            fee_conversions: LookupMap::new(StorageKey::FeeConversions),
+            force_finalize_proposals: LookupMap::new(StorageKey::ForceFinalizeProposals),
+            utxo_key_rotations: LookupMap::new(StorageKey::UtxoKeyRotations),
+            legacy_outpoints: LookupSet::new(StorageKey::LegacyOutpoints),
+            signature_schemes: LookupMap::new(StorageKey::SignatureSchemes),
+            connectors: LookupMap::new(StorageKey::Connectors),
+            chain_connector_ids: LookupMap::new(StorageKey::ChainConnectorIds),
+            last_connector_id: 0,
+            unreported_fee_connectors: LookupMap::new(StorageKey::UnreportedFeeConnectors),
+            inbound_fees: LookupMap::new(StorageKey::InboundFees),
+            inbound_fee_exempt_accounts: LookupSet::new(StorageKey::InboundFeeExemptAccounts),
+            protocol_fees: LookupMap::new(StorageKey::ProtocolFees),
+            max_transfer_amounts: LookupMap::new(StorageKey::MaxTransferAmounts),
+            oversize_transfer_allowances: LookupMap::new(StorageKey::OversizeTransferAllowances),
+            auditor: None,
+            last_accounting_seq: 0,
+            standby_connectors: LookupMap::new(StorageKey::StandbyConnectors),
+            failover_policies: LookupMap::new(StorageKey::FailoverPolicies),
+            connector_health: LookupMap::new(StorageKey::ConnectorHealth),
+            connector_in_flight: LookupMap::new(StorageKey::ConnectorInFlight),
+            transfer_claim_records: LookupMap::new(StorageKey::TransferClaimRecords),
+            utxo_address_formats: LookupMap::new(StorageKey::UtxoAddressFormats),
+            min_native_relayer_fees: LookupMap::new(StorageKey::MinNativeRelayerFees),
+            min_token_relayer_fees: LookupMap::new(StorageKey::MinTokenRelayerFees),
+            route_latencies: LookupMap::new(StorageKey::RouteLatencies),
+            extra_msg_required_chains: LookupSet::new(StorageKey::ExtraMsgRequiredChains),
+            event_emissions: [EventEmissionState::default(); CONFIGURABLE_EVENTS.len()],
+            utxo_refund_policies: LookupMap::new(StorageKey::UtxoRefundPolicies),
+            chain_traits: LookupMap::new(StorageKey::ChainTraits),
+            watchtower_config: None,
+            watchtowers: LookupMap::new(StorageKey::Watchtowers),
+            transfer_flags: LookupMap::new(StorageKey::TransferFlags),
+            claimable_fees: LookupMap::new(StorageKey::ClaimableFees),
+            signing_requested_transfers: LookupMap::new(StorageKey::SigningRequestedTransfers),
+            bootstrap: Some(BootstrapProgress::default()),
+            metric_counters: UnorderedMap::new(StorageKey::MetricCounters),
+            last_verified_proofs: LookupMap::new(StorageKey::LastVerifiedProofs),
+            submission_intents: LookupMap::new(StorageKey::SubmissionIntents),
+            destination_call_gas_fees: LookupMap::new(StorageKey::DestinationCallGasFees),
+            destination_calls: LookupMap::new(StorageKey::DestinationCalls),
+            pending_transfers_by_owner: LookupMap::new(StorageKey::PendingTransfersOwnerIndex),
+            utxo_fee_rates: LookupMap::new(StorageKey::UtxoFeeRates),
+            max_allowed_withdraw_fees: LookupMap::new(StorageKey::MaxAllowedWithdrawFees),
+            utxo_dust_limits: LookupMap::new(StorageKey::UtxoDustLimits),
+            approved_sweep_destinations: LookupSet::new(StorageKey::ApprovedSweepDestinations),
+            tvl_caps: LookupMap::new(StorageKey::TvlCaps),
+            token_tvl: LookupMap::new(StorageKey::TokenTvl),
+            current_message_nonce: 0,
+            message_handlers: LookupSet::new(StorageKey::MessageHandlers),
+            received_messages: LookupSet::new(StorageKey::ReceivedMessages),
+            utxo_script_kinds: LookupMap::new(StorageKey::UtxoScriptKinds),
+            custody_utxos: LookupMap::new(StorageKey::CustodyUtxos),
+            custody_tracked_chains: LookupSet::new(StorageKey::CustodyTrackedChains),
+            fee_escalation_policies: LookupMap::new(StorageKey::FeeEscalationPolicies),
+            fee_escalations: LookupMap::new(StorageKey::FeeEscalations),
+            fee_escalation_budget: NearToken::from_yoctonear(0),
+            preferred_relayers: LookupMap::new(StorageKey::PreferredRelayers),
+            utxo_withdrawals: LookupMap::new(StorageKey::UtxoWithdrawals),
+            sweeping_outpoints: LookupSet::new(StorageKey::SweepingOutpoints),
         };
 
         contract.acl_init_super_admin(near_sdk::env::predecessor_account_id());
@@ -323,23 +628,18 @@ impl Contract {
             decimals: metadata.decimals,
         };
 
-        let payload = near_sdk::env::keccak256_array(
-            borsh::to_vec(&metadata_payload).sdk_expect("ERR_BORSH"),
+        // The metadata is logged for every chain, so it is signed with the default scheme
+        let request = SignatureRequest::new(
+            SignatureScheme::default(),
+            &borsh::to_vec(&metadata_payload).sdk_expect("ERR_BORSH"),
+            SIGN_PATH.to_owned(),
         );
 
-        ext_signer::ext(self.mpc_signer.clone())
-            .with_static_gas(MPC_SIGNING_GAS)
-            .with_attached_deposit(env::attached_deposit())
-            .sign(SignRequest {
-                payload,
-                path: SIGN_PATH.to_owned(),
-                key_version: 0,
-            })
-            .then(
-                Self::ext(env::current_account_id())
-                    .with_static_gas(SIGN_LOG_METADATA_CALLBACK_GAS)
-                    .sign_log_metadata_callback(metadata_payload),
-            )
+        self.request_signature(request).then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(SIGN_LOG_METADATA_CALLBACK_GAS)
+                .sign_log_metadata_callback(metadata_payload),
+        )
     }
 
     #[private]
@@ -394,12 +694,9 @@ impl Contract {
                 transfer.message.fee = fee;
                 self.insert_raw_transfer(transfer.message.clone(), transfer.owner);
 
-                env::log_str(
-                    &OmniBridgeEvent::UpdateFeeEvent {
-                        transfer_message: transfer.message,
-                    }
-                    .to_log_string(),
-                );
+                self.emit_event(&OmniBridgeEvent::UpdateFeeEvent {
+                    transfer_message: transfer.message,
+                });
             }
             UpdateFee::Proof(_) => env::panic_str("TODO"),
         }
@@ -411,6 +708,7 @@ impl Contract {
     ///
     /// - If the `borsh::to_vec` serialization of the `TransferMessagePayload` fails.
     /// - If a `fee` is provided and it doesn't match the fee in the stored transfer message.
+    /// - If the first signer has not enough storage balance for the signing request marker.
     #[payable]
     #[pause(except(roles(Role::DAO, Role::UnrestrictedRelayer)))]
     pub fn sign_transfer(
@@ -420,28 +718,15 @@ impl Contract {
         fee: &Option<Fee>,
     ) -> Promise {
         let transfer_message = self.get_transfer_message(transfer_id);
+        self.require_transfer_not_held(transfer_id);
+        self.require_preferred_relayer(&transfer_id);
 
         if let Some(fee) = &fee {
             require!(&transfer_message.fee == fee, "Invalid fee");
         }
 
-        let token_address = self
-            .get_token_address(
-                transfer_message.get_destination_chain(),
-                self.get_token_id(&transfer_message.token),
-            )
-            .unwrap_or_else(|| env::panic_str("ERR_FAILED_TO_GET_TOKEN_ADDRESS"));
-
-        let decimals = self
-            .token_decimals
-            .get(&token_address)
-            .sdk_expect("ERR_TOKEN_DECIMALS_NOT_FOUND");
-        let amount_to_transfer = Self::normalize_amount(
-            transfer_message.amount.0 - transfer_message.fee.fee.0,
-            decimals,
-        );
-
-        require!(amount_to_transfer > 0, "Invalid amount to transfer");
+        let (token_address, amount_to_transfer) =
+            self.get_destination_token_amount(&transfer_message);
 
         let transfer_payload = TransferMessagePayload {
             prefix: PayloadType::TransferMessage,
@@ -452,24 +737,26 @@ impl Contract {
             recipient: transfer_message.recipient,
             fee_recipient,
         };
+        self.mark_transfer_signing_requested(&transfer_id, &env::predecessor_account_id());
+
+        if let Some(destination_call) = self.destination_calls.get(&transfer_id) {
+            return self.sign_transfer_with_call(
+                transfer_payload,
+                destination_call,
+                &transfer_message.fee,
+            );
+        }
 
-        let payload = near_sdk::env::keccak256_array(
-            borsh::to_vec(&transfer_payload).sdk_expect("ERR_BORSH"),
+        let request = self.build_signature_request(
+            transfer_payload.recipient.get_chain(),
+            &borsh::to_vec(&transfer_payload).sdk_expect("ERR_BORSH"),
         );
 
-        ext_signer::ext(self.mpc_signer.clone())
-            .with_static_gas(MPC_SIGNING_GAS)
-            .with_attached_deposit(env::attached_deposit())
-            .sign(SignRequest {
-                payload,
-                path: SIGN_PATH.to_owned(),
-                key_version: 0,
-            })
-            .then(
-                Self::ext(env::current_account_id())
-                    .with_static_gas(SIGN_TRANSFER_CALLBACK_GAS)
-                    .sign_transfer_callback(transfer_payload, &transfer_message.fee),
-            )
+        self.request_signature(request).then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(SIGN_TRANSFER_CALLBACK_GAS)
+                .sign_transfer_callback(transfer_payload, &transfer_message.fee),
+        )
     }
 
     fn init_transfer(
@@ -484,6 +771,35 @@ impl Contract {
             init_transfer_msg.recipient.get_chain() != ChainKind::Near,
             "ERR_INVALID_RECIPIENT_CHAIN"
         );
+        self.check_utxo_address_format(&init_transfer_msg.recipient);
+        self.check_script_hash_recipient(&init_transfer_msg.recipient);
+        self.check_utxo_extra_msg(
+            &init_transfer_msg.recipient,
+            init_transfer_msg.msg.as_deref(),
+        );
+        self.check_transfer_memo(
+            &init_transfer_msg.recipient,
+            init_transfer_msg.msg.as_deref(),
+        );
+        self.check_transfer_msg_size(
+            &init_transfer_msg.recipient,
+            init_transfer_msg.msg.as_deref(),
+        );
+        self.check_relayer_fee_floor(
+            init_transfer_msg.recipient.get_chain(),
+            &token_id,
+            init_transfer_msg.fee,
+            init_transfer_msg.native_token_fee,
+        );
+        if let Some(destination_call) = &init_transfer_msg.destination_call {
+            self.check_destination_call(
+                &init_transfer_msg.recipient,
+                destination_call,
+                init_transfer_msg.native_token_fee,
+            );
+        }
+        self.require_storage_safety_margin();
+        self.check_transfer_quota(&sender_id, &token_id, amount);
 
         self.current_origin_nonce += 1;
         let destination_nonce =
@@ -502,16 +818,30 @@ impl Contract {
             msg: init_transfer_msg.msg.unwrap_or_default(),
             destination_nonce,
             origin_transfer_id: None,
+            expires_at: init_transfer_msg.expires_at,
         };
         require!(
             transfer_message.fee.fee < transfer_message.amount,
             "ERR_INVALID_FEE"
         );
+        require!(
+            transfer_message
+                .expires_at
+                .is_none_or(|expires_at| expires_at.0 > env::block_timestamp()),
+            "ERR_INVALID_TRANSFER_EXPIRY"
+        );
 
-        let required_storage_balance =
-            self.required_balance_for_init_transfer_message(transfer_message.clone());
+        let beneficiary = init_transfer_msg.beneficiary;
+        let destination_call = init_transfer_msg.destination_call;
+        let preferred_relayer = init_transfer_msg.preferred_relayer;
+        let required_storage_balance = self.required_balance_for_new_transfer(
+            &transfer_message,
+            beneficiary.as_ref(),
+            destination_call.as_ref(),
+            preferred_relayer.as_ref(),
+        );
 
-        let message_storage_account_id = transfer_message.calculate_storage_account_id();
+        let message_storage_account_id = self.resolve_message_storage_account_id(&transfer_message);
 
         // Choose storage payer or whether to yield execution until storage is available
         if self
@@ -530,19 +860,25 @@ impl Contract {
             ) && (init_transfer_msg.native_token_fee.0 == 0
                 || !self.acl_has_role(Role::NativeFeeRestricted.into(), signer_id.clone())))
         {
-            PromiseOrPromiseIndexOrValue::Value(
-                self.init_transfer_internal(transfer_message, signer_id),
-            )
+            PromiseOrPromiseIndexOrValue::Value(self.init_transfer_internal(
+                transfer_message,
+                signer_id,
+                beneficiary,
+                destination_call,
+                preferred_relayer,
+            ))
         } else {
             let promise_index = env::promise_yield_create(
                 "init_transfer_resume",
-                json!({
-                    "transfer_message": transfer_message,
-                    "message_storage_account_id": message_storage_account_id,
-                    "storage_owner": signer_id,
+                &serde_json::to_vec(&InitTransferResumeArgs {
+                    transfer_message,
+                    message_storage_account_id: message_storage_account_id.clone(),
+                    storage_owner: signer_id,
+                    beneficiary,
+                    destination_call,
+                    preferred_relayer,
                 })
-                .to_string()
-                .as_bytes(),
+                .sdk_expect("ERR_SERIALIZE_INIT_TRANSFER_RESUME_ARGS"),
                 INIT_TRANSFER_RESUME_GAS,
                 GasWeight(0),
                 PROMISE_REGISTER_ID,
@@ -576,6 +912,9 @@ impl Contract {
         transfer_message: TransferMessage,
         message_storage_account_id: AccountId,
         storage_owner: AccountId,
+        beneficiary: Option<TransferBeneficiary>,
+        destination_call: Option<EvmDestinationCall>,
+        preferred_relayer: Option<AccountId>,
         #[callback_result] response: Result<(), PromiseError>,
     ) -> U128 {
         self.remove_promise(&message_storage_account_id);
@@ -587,13 +926,24 @@ impl Contract {
             &message_storage_account_id,
             NearToken::from_yoctonear(transfer_message.fee.native_fee.0),
             &storage_owner,
-            self.required_balance_for_init_transfer(Some(transfer_message.msg.clone())),
+            self.required_balance_for_new_transfer(
+                &transfer_message,
+                beneficiary.as_ref(),
+                destination_call.as_ref(),
+                preferred_relayer.as_ref(),
+            ),
         ) {
             env::log_str(&format!("Error paying native fee and storage: {err}"));
             return transfer_message.amount;
         }
 
-        self.init_transfer_internal(transfer_message, storage_owner)
+        self.init_transfer_internal(
+            transfer_message,
+            storage_owner,
+            beneficiary,
+            destination_call,
+            preferred_relayer,
+        )
     }
 
     #[private]
@@ -604,9 +954,7 @@ impl Contract {
         #[serializer(borsh)] fee: &Fee,
     ) {
         if let Ok(signature) = call_result {
-            if fee.is_zero() {
-                self.remove_transfer_message(message_payload.transfer_id);
-            }
+            self.complete_transfer_signing(message_payload.transfer_id, fee);
 
             env::log_str(
                 &OmniBridgeEvent::SignTransferEvent {
@@ -621,6 +969,7 @@ impl Contract {
     #[payable]
     #[pause(except(roles(Role::DAO, Role::UnrestrictedRelayer)))]
     pub fn fin_transfer(&mut self, #[serializer(borsh)] args: FinTransferArgs) -> Promise {
+        self.require_bridge_activated();
         require!(
             args.storage_deposit_actions.len() <= 3,
             "Invalid len of accounts for storage deposit"
@@ -652,8 +1001,13 @@ impl Contract {
         #[serializer(borsh)] storage_deposit_actions: &Vec<StorageDepositAction>,
         #[serializer(borsh)] predecessor_account_id: AccountId,
     ) -> PromiseOrValue<Nonce> {
-        let Ok(ProverResult::InitTransfer(init_transfer)) = Self::decode_prover_result(0) else {
-            env::panic_str("Invalid proof message")
+        let init_transfer = match Self::decode_prover_result(0) {
+            Ok(ProverResult::InitTransfer(init_transfer)) => init_transfer,
+            Ok(ProverResult::UnresolvedRecipientTransfer(transfer)) => {
+                self.add_unclaimed_deposit(transfer, predecessor_account_id);
+                return PromiseOrValue::Value(0);
+            }
+            _ => env::panic_str("Invalid proof message"),
         };
         require!(
             self.factories
@@ -661,6 +1015,7 @@ impl Contract {
                 == Some(init_transfer.emitter_address),
             "Unknown factory"
         );
+        self.record_proof_verified(init_transfer.emitter_address.get_chain());
 
         let decimals = self
             .token_decimals
@@ -679,6 +1034,7 @@ impl Contract {
             msg: init_transfer.msg,
             destination_nonce,
             origin_transfer_id: None,
+            expires_at: None,
         };
 
         if let OmniAddress::Near(recipient) = transfer_message.recipient.clone() {
@@ -816,7 +1172,10 @@ impl Contract {
             .to_log_string(),
         );
 
-        let amount = U128(fast_transfer.amount.0 - fast_transfer.fee.fee.0);
+        let amount = fast_transfer
+            .fee
+            .net_amount(fast_transfer.amount)
+            .sdk_unwrap();
 
         self.send_tokens(
             fast_transfer.token_id.clone(),
@@ -829,7 +1188,7 @@ impl Contract {
                 .with_static_gas(RESOLVE_FAST_TRANSFER_GAS)
                 .resolve_fast_transfer(
                     fast_transfer.token_id.clone(),
-                    &fast_transfer.id(),
+                    &self.resolve_fast_transfer_id(fast_transfer),
                     amount,
                     !fast_transfer.msg.is_empty(),
                 ),
@@ -861,8 +1220,10 @@ impl Contract {
         storage_payer: AccountId,
         relayer_id: AccountId,
     ) {
-        if fast_transfer.recipient.is_utxo_chain() {
-            let btc_account_id = self.get_utxo_chain_token(fast_transfer.recipient.get_chain());
+        if self.is_utxo_chain(fast_transfer.recipient.get_chain()) {
+            let btc_account_id = self
+                .require_chain_configured(fast_transfer.recipient.get_chain())
+                .token_id;
             require!(
                 fast_transfer.token_id == btc_account_id,
                 "Only BTC can be transferred to the Bitcoin network."
@@ -886,6 +1247,7 @@ impl Contract {
             msg: fast_transfer.msg.clone(),
             destination_nonce,
             origin_transfer_id: Some(fast_transfer.transfer_id.clone()),
+            expires_at: None,
         };
         let new_transfer_id = transfer_message.get_transfer_id();
 
@@ -914,6 +1276,8 @@ impl Contract {
         origin_chain: ChainKind,
         storage_owner: &AccountId,
     ) -> PromiseOrValue<U128> {
+        // The missing storage can still be deposited, so the deposit is returned to the
+        // connector and can be finalised again
         if !Self::check_storage_balance_result(0) {
             env::log_str("STORAGE_ERR: The transfer recipient is omitted");
             self.remove_fin_utxo_transfer(
@@ -923,10 +1287,11 @@ impl Contract {
             return PromiseOrValue::Value(amount);
         }
 
+        let inbound_fee = self.get_inbound_fee_amount(origin_chain, &recipient, amount);
         self.send_tokens(
             token_id.clone(),
             recipient,
-            amount,
+            U128(amount.0 - inbound_fee.0),
             &utxo_fin_transfer_msg.msg,
         )
         .then(
@@ -938,6 +1303,7 @@ impl Contract {
                     utxo_fin_transfer_msg,
                     origin_chain,
                     storage_owner,
+                    Some(inbound_fee),
                 ),
         )
         .into()
@@ -951,15 +1317,29 @@ impl Contract {
         utxo_fin_transfer_msg: UtxoFinTransferMsg,
         origin_chain: ChainKind,
         storage_owner: &AccountId,
+        // Not set for the transfers finalized before the inbound fee
+        inbound_fee: Option<U128>,
     ) -> U128 {
         let is_ft_transfer_call = !utxo_fin_transfer_msg.msg.is_empty();
+        // The recipient rejected the deposit, so the whole deposit, including the fee, is
+        // returned to its origin address or to the connector
         if Self::is_refund_required(is_ft_transfer_call) {
+            if self.try_refund_utxo_deposit_to_origin(
+                &token_id,
+                amount,
+                &utxo_fin_transfer_msg,
+                origin_chain,
+                storage_owner,
+            ) {
+                return U128(0);
+            }
             self.remove_fin_utxo_transfer(
                 &utxo_fin_transfer_msg.get_transfer_id(origin_chain),
                 storage_owner,
             );
             amount
         } else {
+            self.collect_protocol_fee(&token_id, inbound_fee.unwrap_or(U128(0)));
             env::log_str(
                 &OmniBridgeEvent::UtxoTransferEvent {
                     token_id,
@@ -983,7 +1363,10 @@ impl Contract {
     }
 
     #[payable]
-    #[pause(except(roles(Role::DAO, Role::UnrestrictedRelayer)))]
+    #[pause(
+        name = "fee_settlement",
+        except(roles(Role::DAO, Role::UnrestrictedRelayer))
+    )]
     pub fn claim_fee(&mut self, #[serializer(borsh)] args: ClaimFeeArgs) -> Promise {
         self.verify_proof(args.chain_kind, args.prover_args).then(
             Self::ext(env::current_account_id())
@@ -1022,19 +1405,35 @@ impl Contract {
             "ERR_UNKNOWN_FACTORY"
         );
 
+        let owner = self
+            .get_transfer_message_storage(fin_transfer.transfer_id)
+            .owner;
         let message = self.remove_transfer_message(fin_transfer.transfer_id);
+        self.settle_fee_escalation(&fin_transfer.transfer_id);
+        self.record_transfer_finalized(
+            &fin_transfer.transfer_id,
+            message.get_destination_chain(),
+            &owner,
+        );
+        self.record_reward_points(&message);
+        self.record_transfer_claimed(
+            &fin_transfer.transfer_id,
+            message.get_destination_chain(),
+            ClaimEvidence::DestinationProof,
+        );
 
         if let Some(origin_transfer_id) = message.origin_transfer_id.clone() {
             let mut fast_transfer =
                 FastTransfer::from_transfer(message.clone(), self.get_token_id(&message.token));
             fast_transfer.transfer_id = origin_transfer_id;
+            let fast_transfer_id = self.resolve_fast_transfer_id(&fast_transfer);
 
-            if let Some(fast_transfer_status) = self.get_fast_transfer_status(&fast_transfer.id()) {
+            if let Some(fast_transfer_status) = self.get_fast_transfer_status(&fast_transfer_id) {
                 // For fast transfers we need to wait for finalization of the first leg (Origin chain -> Near) before allowing fee claim.
                 // This confirms that fast transfer was executed with correct parameters.
                 // Othewise malicious relayer can create a fast transfer with arbitrary high fee and claim it here.
                 if fast_transfer_status.finalised {
-                    self.remove_fast_transfer(&fast_transfer.id());
+                    self.remove_fast_transfer(&fast_transfer_id);
                 } else {
                     env::panic_str("ERR_FAST_TRANSFER_NOT_FINALISED");
                 }
@@ -1052,7 +1451,11 @@ impl Contract {
                 .get(&token_address)
                 .sdk_expect("ERR_TOKEN_DECIMALS_NOT_FOUND"),
         );
-        let fee = message.amount.0 - denormalized_amount;
+        let fee = message
+            .amount
+            .0
+            .checked_sub(denormalized_amount)
+            .sdk_expect("ERR_INVALID_FIN_TRANSFER_AMOUNT");
 
         self.send_fee_internal(&message, fee_recipient, fee)
     }
@@ -1226,6 +1629,7 @@ impl Contract {
             msg: String::new(),
             destination_nonce,
             origin_transfer_id: None,
+            expires_at: None,
         };
 
         let required_storage_balance =
@@ -1294,6 +1698,11 @@ impl Contract {
         self.get_token_id(&native_token_address)
     }
 
+    /// Returns the names and the payload versions of the events emitted by the locker.
+    pub fn get_event_schemas(&self) -> Vec<EventSchema> {
+        OmniBridgeEvent::schemas()
+    }
+
     pub fn get_transfer_message(&self, transfer_id: TransferId) -> TransferMessage {
         self.pending_transfers
             .get(&transfer_id)
@@ -1503,13 +1912,14 @@ impl Contract {
         if Self::is_refund_required(is_ft_transfer_call) {
             self.burn_tokens_if_needed(
                 token,
-                U128(transfer_message.amount.0 - transfer_message.fee.fee.0),
+                transfer_message
+                    .fee
+                    .net_amount(transfer_message.amount)
+                    .sdk_unwrap(),
             );
             self.remove_fin_transfer(&transfer_message.get_transfer_id(), storage_owner);
 
-            env::log_str(
-                &OmniBridgeEvent::FailedFinTransferEvent { transfer_message }.to_log_string(),
-            );
+            self.emit_event(&OmniBridgeEvent::FailedFinTransferEvent { transfer_message });
         } else {
             // Send fee to the fee recipient
             if transfer_message.fee.fee.0 > 0 {
@@ -1536,7 +1946,7 @@ impl Contract {
                     .detach();
             }
 
-            env::log_str(&OmniBridgeEvent::FinTransferEvent { transfer_message }.to_log_string());
+            self.emit_event(&OmniBridgeEvent::FinTransferEvent { transfer_message });
         }
     }
 
@@ -1571,6 +1981,15 @@ impl Contract {
 }
 
 impl Contract {
+    // The message is kept until the fee is claimed, unless there is no fee to claim.
+    pub(crate) fn complete_transfer_signing(&mut self, transfer_id: TransferId, fee: &Fee) {
+        self.record_transfer_submitted(&transfer_id);
+        if fee.is_zero() {
+            self.remove_transfer_message(transfer_id);
+            self.settle_fee_escalation(&transfer_id);
+        }
+    }
+
     fn is_refund_required(is_ft_transfer_call: bool) -> bool {
         if is_ft_transfer_call {
             match env::promise_result(0) {
@@ -1618,34 +2037,149 @@ impl Contract {
 
     fn init_transfer_internal(
         &mut self,
-        transfer_message: TransferMessage,
+        mut transfer_message: TransferMessage,
         storage_owner: AccountId,
+        beneficiary: Option<TransferBeneficiary>,
+        destination_call: Option<EvmDestinationCall>,
+        preferred_relayer: Option<AccountId>,
     ) -> U128 {
-        let required_storage_balance = self
-            .add_transfer_message(transfer_message.clone(), storage_owner.clone())
+        let transferred_amount = transfer_message.amount;
+        // The storage is checked before anything is written, so a transfer refunded for the
+        // lack of storage leaves no state behind
+        let required_balance = self
+            .required_balance_for_new_transfer(
+                &transfer_message,
+                beneficiary.as_ref(),
+                destination_call.as_ref(),
+                preferred_relayer.as_ref(),
+            )
             .saturating_add(NearToken::from_yoctonear(transfer_message.fee.native_fee.0));
+        if !self.has_storage_balance(&storage_owner, required_balance) {
+            return transferred_amount;
+        }
 
-        if self
-            .try_update_storage_balance(
-                storage_owner,
-                required_storage_balance,
+        if let (OmniAddress::Near(sender_id), OmniAddress::Near(token_id)) =
+            (&transfer_message.sender, &transfer_message.token)
+        {
+            self.check_transfer_cap(sender_id, token_id, transferred_amount);
+        }
+        if let OmniAddress::Near(token_id) = &transfer_message.token {
+            if !self.deployed_tokens.contains(token_id) {
+                self.check_tvl_cap(token_id, transferred_amount.0)
+                    .sdk_unwrap();
+            }
+        }
+
+        // A cancelled transfer refunds its whole amount, so it can't use the fee of a sponsor
+        if beneficiary.is_none() {
+            self.apply_fee_sponsorship(&mut transfer_message);
+        }
+
+        let mut required_storage_balance = beneficiary
+            .map_or(NearToken::from_yoctonear(0), |beneficiary| {
+                self.add_transfer_beneficiary(&transfer_message.get_transfer_id(), beneficiary)
+            })
+            .saturating_add(destination_call.map_or(
+                NearToken::from_yoctonear(0),
+                |destination_call| {
+                    self.add_destination_call(
+                        &transfer_message.get_transfer_id(),
+                        &destination_call,
+                    )
+                },
+            ))
+            .saturating_add(preferred_relayer.map_or(
                 NearToken::from_yoctonear(0),
+                |preferred_relayer| {
+                    self.add_preferred_relayer(
+                        &transfer_message.get_transfer_id(),
+                        preferred_relayer,
+                    )
+                },
+            ))
+            .saturating_add(
+                self.add_transfer_message(transfer_message.clone(), storage_owner.clone()),
             )
-            .is_err()
+            .saturating_add(self.record_transfer_initiated(&transfer_message.get_transfer_id()))
+            .saturating_add(NearToken::from_yoctonear(transfer_message.fee.native_fee.0));
+
+        if let (OmniAddress::Near(sender_id), OmniAddress::Near(token_id)) =
+            (&transfer_message.sender, &transfer_message.token)
         {
-            return transfer_message.amount;
+            required_storage_balance = required_storage_balance.saturating_add(
+                self.record_transfer_quota_usage(sender_id, token_id, transferred_amount),
+            );
         }
 
+        // The counters are stored at the expense of the contract
+        self.increment_metric(
+            &MetricId::InitiatedTransfers(transfer_message.get_destination_chain()),
+            1,
+        );
+        if let OmniAddress::Near(token_id) = &transfer_message.token {
+            self.increment_metric(
+                &MetricId::InitiatedVolume(token_id.clone()),
+                transferred_amount.0,
+            );
+        }
+
+        // The required balance is an upper bound of the storage used, a panic reverts the transfer
+        self.update_storage_balance(
+            storage_owner,
+            required_storage_balance,
+            NearToken::from_yoctonear(0),
+        );
+
         if let OmniAddress::Near(token_id) = transfer_message.token.clone() {
+            self.record_tvl_outbound(&token_id, transfer_message.amount.0);
             self.burn_tokens_if_needed(token_id, transfer_message.amount);
         } else {
-            return transfer_message.amount;
+            return transferred_amount;
         }
 
         env::log_str(&OmniBridgeEvent::InitTransferEvent { transfer_message }.to_log_string());
+        self.alert_if_available_balance_low();
         U128(0)
     }
 
+    // Storage of a new transfer and of the records attached to it, paid by its storage owner
+    // on both the direct and the resumed initiation.
+    fn required_balance_for_new_transfer(
+        &self,
+        transfer_message: &TransferMessage,
+        beneficiary: Option<&TransferBeneficiary>,
+        destination_call: Option<&EvmDestinationCall>,
+        preferred_relayer: Option<&AccountId>,
+    ) -> NearToken {
+        let transfer_id = transfer_message.get_transfer_id();
+        let quota_usage_balance = match (&transfer_message.sender, &transfer_message.token) {
+            (OmniAddress::Near(sender_id), OmniAddress::Near(token_id)) => {
+                self.required_balance_for_transfer_quota_usage(sender_id, token_id)
+            }
+            _ => NearToken::from_yoctonear(0),
+        };
+
+        self.required_balance_for_init_transfer_message(transfer_message.clone())
+            .saturating_add(quota_usage_balance)
+            .saturating_add(
+                beneficiary.map_or(NearToken::from_yoctonear(0), |beneficiary| {
+                    Self::required_balance_for_transfer_beneficiary(&transfer_id, beneficiary)
+                }),
+            )
+            .saturating_add(destination_call.map_or(
+                NearToken::from_yoctonear(0),
+                |destination_call| {
+                    Self::required_balance_for_destination_call(&transfer_id, destination_call)
+                },
+            ))
+            .saturating_add(preferred_relayer.map_or(
+                NearToken::from_yoctonear(0),
+                |preferred_relayer| {
+                    Self::required_balance_for_preferred_relayer(&transfer_id, preferred_relayer)
+                },
+            ))
+    }
+
     #[allow(clippy::too_many_lines, clippy::ptr_arg)]
     fn process_fin_transfer_to_near(
         &mut self,
@@ -1654,25 +2188,32 @@ impl Contract {
         transfer_message: TransferMessage,
         storage_deposit_actions: &Vec<StorageDepositAction>,
     ) -> Promise {
-        let mut required_balance = self.add_fin_transfer(&transfer_message.get_transfer_id());
+        let mut required_balance = self
+            .add_fin_transfer(&transfer_message.get_transfer_id())
+            .saturating_add(self.add_origin_senders(
+                &transfer_message.get_transfer_id().into(),
+                &[transfer_message.sender.clone()],
+            ));
 
         let token = self.get_token_id(&transfer_message.token);
+        self.record_tvl_inbound(&token, transfer_message.amount.0);
 
         // If fast transfer happened, change recipient and fee recipient to the relayer that executed fast transfer
         let fast_transfer = FastTransfer::from_transfer(transfer_message.clone(), token.clone());
-        let (recipient, msg, fee_recipient) =
-            match self.get_fast_transfer_status(&fast_transfer.id()) {
-                Some(status) => {
-                    require!(!status.finalised, "ERR_FAST_TRANSFER_ALREADY_FINALISED");
-                    self.remove_fast_transfer(&fast_transfer.id());
-                    (status.relayer.clone(), String::new(), status.relayer)
-                }
-                None => (
-                    recipient,
-                    transfer_message.msg.clone(),
-                    predecessor_account_id.clone(),
-                ),
-            };
+        let fast_transfer_id = self.resolve_fast_transfer_id(&fast_transfer);
+        let (recipient, msg, fee_recipient) = match self.get_fast_transfer_status(&fast_transfer_id)
+        {
+            Some(status) => {
+                require!(!status.finalised, "ERR_FAST_TRANSFER_ALREADY_FINALISED");
+                self.remove_fast_transfer(&fast_transfer_id);
+                (status.relayer.clone(), String::new(), status.relayer)
+            }
+            None => (
+                recipient,
+                transfer_message.msg.clone(),
+                predecessor_account_id.clone(),
+            ),
+        };
 
         let mut storage_deposit_action_index: usize = 0;
         require!(
@@ -1721,13 +2262,20 @@ impl Contract {
             );
         }
 
+        required_balance = required_balance.saturating_add(
+            self.fund_derived_account_if_needed(&recipient, &transfer_message.sender),
+        );
+
         self.update_storage_balance(
             predecessor_account_id.clone(),
             required_balance,
             env::attached_deposit(),
         );
 
-        let amount_to_transfer = U128(transfer_message.amount.0 - transfer_message.fee.fee.0);
+        let amount_to_transfer = transfer_message
+            .fee
+            .net_amount(transfer_message.amount)
+            .sdk_unwrap();
         self.send_tokens(token.clone(), recipient, amount_to_transfer, &msg)
             .then(
                 Self::ext(env::current_account_id())
@@ -1749,8 +2297,10 @@ impl Contract {
         let mut required_balance = self.add_fin_transfer(&transfer_message.get_transfer_id());
         let token = self.get_token_id(&transfer_message.token);
 
-        if transfer_message.recipient.is_utxo_chain() {
-            let btc_account_id = self.get_utxo_chain_token(transfer_message.recipient.get_chain());
+        if self.is_utxo_chain(transfer_message.recipient.get_chain()) {
+            let btc_account_id = self
+                .require_chain_configured(transfer_message.recipient.get_chain())
+                .token_id;
             require!(
                 token == btc_account_id,
                 "Only BTC can be transferred to the Bitcoin network."
@@ -1758,7 +2308,8 @@ impl Contract {
         }
 
         let fast_transfer = FastTransfer::from_transfer(transfer_message.clone(), token.clone());
-        let recipient = match self.get_fast_transfer_status(&fast_transfer.id()) {
+        let fast_transfer_id = self.resolve_fast_transfer_id(&fast_transfer);
+        let recipient = match self.get_fast_transfer_status(&fast_transfer_id) {
             Some(status) => {
                 require!(!status.finalised, "ERR_FAST_TRANSFER_ALREADY_FINALISED");
                 Some(status.relayer)
@@ -1771,11 +2322,14 @@ impl Contract {
             self.send_tokens(
                 token,
                 relayer,
-                U128(transfer_message.amount.0 - transfer_message.fee.fee.0),
+                transfer_message
+                    .fee
+                    .net_amount(transfer_message.amount)
+                    .sdk_unwrap(),
                 "",
             )
             .detach();
-            self.mark_fast_transfer_as_finalised(&fast_transfer.id());
+            self.mark_fast_transfer_as_finalised(&fast_transfer_id);
         } else {
             required_balance = self
                 .add_transfer_message(transfer_message.clone(), predecessor_account_id.clone())
@@ -1788,7 +2342,7 @@ impl Contract {
             env::attached_deposit(),
         );
 
-        env::log_str(&OmniBridgeEvent::FinTransferEvent { transfer_message }.to_log_string());
+        self.emit_event(&OmniBridgeEvent::FinTransferEvent { transfer_message });
     }
 
     fn send_tokens(
@@ -1893,8 +2447,11 @@ impl Contract {
         transfer_message: TransferMessage,
         message_owner: AccountId,
     ) -> Option<Vec<u8>> {
+        let transfer_id = transfer_message.get_transfer_id();
+        self.index_pending_transfer(transfer_message.get_destination_chain(), transfer_id);
+        self.index_pending_transfer_owner(&message_owner, transfer_id);
         self.pending_transfers.insert_raw(
-            &borsh::to_vec(&transfer_message.get_transfer_id()).sdk_expect("ERR_BORSH"),
+            &borsh::to_vec(&transfer_id).sdk_expect("ERR_BORSH"),
             &TransferMessageStorage::encode_borsh(transfer_message, message_owner)
                 .sdk_expect("ERR_BORSH"),
         )
@@ -1905,22 +2462,70 @@ impl Contract {
         transfer_message: TransferMessage,
         message_owner: AccountId,
     ) -> NearToken {
+        let token = self.get_token_id(&transfer_message.token);
+        let amount = transfer_message.amount.0;
+
         let storage_usage = env::storage_usage();
         require!(
             self.insert_raw_transfer(transfer_message, message_owner,)
                 .is_none(),
             "ERR_KEY_EXIST"
         );
-        env::storage_byte_cost().saturating_mul((env::storage_usage() - storage_usage).into())
+        let required_balance =
+            env::storage_byte_cost().saturating_mul((env::storage_usage() - storage_usage).into());
+
+        self.lock_amount(&token, LockedState::Pending, amount);
+        required_balance
+    }
+
+    // Returns the token on the destination chain and the amount received there, in its decimals.
+    fn get_destination_token_amount(
+        &self,
+        transfer_message: &TransferMessage,
+    ) -> (OmniAddress, u128) {
+        let token_address = self
+            .get_token_address(
+                transfer_message.get_destination_chain(),
+                self.get_token_id(&transfer_message.token),
+            )
+            .unwrap_or_else(|| env::panic_str("ERR_FAILED_TO_GET_TOKEN_ADDRESS"));
+
+        let decimals = self
+            .token_decimals
+            .get(&token_address)
+            .sdk_expect("ERR_TOKEN_DECIMALS_NOT_FOUND");
+        let amount_to_transfer = Self::normalize_amount(
+            transfer_message
+                .fee
+                .net_amount(transfer_message.amount)
+                .sdk_unwrap()
+                .0,
+            decimals,
+        );
+
+        require!(amount_to_transfer > 0, "Invalid amount to transfer");
+        (token_address, amount_to_transfer)
     }
 
     fn remove_transfer_message(&mut self, transfer_id: TransferId) -> TransferMessage {
+        let transfer = self.take_transfer_message(transfer_id);
+        self.remove_transfer_records(&transfer_id, &transfer.owner);
+        transfer.message
+    }
+
+    // Removes the message of a transfer submitted to a UTXO connector. The records of the
+    // transfer are kept until the connector accepts the withdrawal, so a restored transfer
+    // still has them.
+    fn take_transfer_message(&mut self, transfer_id: TransferId) -> TransferMessageStorageValue {
+        self.unmark_transfer_signing_requested(&transfer_id);
         let storage_usage = env::storage_usage();
         let transfer = self
             .pending_transfers
             .remove(&transfer_id)
             .map(storage::TransferMessageStorage::into_main)
             .sdk_expect("ERR_TRANSFER_NOT_EXIST");
+        self.unindex_pending_transfer(transfer.message.get_destination_chain(), &transfer_id);
+        self.unindex_pending_transfer_owner(&transfer.owner, &transfer_id);
 
         let refund =
             env::storage_byte_cost().saturating_mul((storage_usage - env::storage_usage()).into());
@@ -1930,7 +2535,26 @@ impl Contract {
             self.accounts_balances.insert(&transfer.owner, &storage);
         }
 
-        transfer.message
+        let token = self.get_token_id(&transfer.message.token);
+        self.unlock_amount(&token, LockedState::Pending, transfer.message.amount.0);
+
+        transfer
+    }
+
+    // Removes the records attached to the transfer at its initiation and refunds their storage
+    // to the owner of the transfer.
+    fn remove_transfer_records(&mut self, transfer_id: &TransferId, transfer_owner: &AccountId) {
+        let storage_usage = env::storage_usage();
+        self.transfer_beneficiaries.remove(transfer_id);
+        self.destination_calls.remove(transfer_id);
+        self.preferred_relayers.remove(transfer_id);
+
+        let refund = env::storage_byte_cost()
+            .saturating_mul((storage_usage.saturating_sub(env::storage_usage())).into());
+        if let Some(mut storage) = self.accounts_balances.get(transfer_owner) {
+            storage.available = storage.available.saturating_add(refund);
+            self.accounts_balances.insert(transfer_owner, &storage);
+        }
     }
 
     fn add_fin_transfer(&mut self, transfer_id: &TransferId) -> NearToken {
@@ -1939,8 +2563,10 @@ impl Contract {
             self.finalised_transfers.insert(transfer_id),
             "The transfer is already finalised"
         );
-        env::storage_byte_cost()
-            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into())
+        let required_balance = env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into());
+        self.increment_metric(&MetricId::FinalisedTransfers(transfer_id.origin_chain), 1);
+        required_balance
     }
 
     fn add_fin_utxo_transfer(&mut self, transfer_id: &UnifiedTransferId) -> NearToken {
@@ -1949,8 +2575,10 @@ impl Contract {
             self.finalised_utxo_transfers.insert(transfer_id),
             "The UTXO transfer is already finalised"
         );
-        env::storage_byte_cost()
-            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into())
+        let required_balance = env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into());
+        self.increment_metric(&MetricId::FinalisedTransfers(transfer_id.origin_chain), 1);
+        required_balance
     }
 
     fn add_fast_transfer(
@@ -1963,7 +2591,7 @@ impl Contract {
         require!(
             self.fast_transfers
                 .insert(
-                    &fast_transfer.id(),
+                    &self.resolve_fast_transfer_id(fast_transfer),
                     &FastTransferStatusStorage::V0(FastTransferStatus {
                         relayer,
                         storage_owner,
@@ -1977,6 +2605,30 @@ impl Contract {
             .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into())
     }
 
+    // The storage of the message is deposited to the account of its canonical form, like the
+    // fast transfers. The accounts funded before by the raw message are still used.
+    fn resolve_message_storage_account_id(&self, transfer_message: &TransferMessage) -> AccountId {
+        let message_storage_account_id = transfer_message.calculate_storage_account_id();
+        if self
+            .accounts_balances
+            .contains_key(&message_storage_account_id)
+        {
+            return message_storage_account_id;
+        }
+        transfer_message.calculate_canonical_storage_account_id()
+    }
+
+    // Fast transfers are stored by the id of their canonical form, so the relayer and the
+    // finalization may format the JSON message differently. The transfers stored before
+    // by the id of the raw message are still found by it.
+    fn resolve_fast_transfer_id(&self, fast_transfer: &FastTransfer) -> FastTransferId {
+        let fast_transfer_id = fast_transfer.id();
+        if self.fast_transfers.contains_key(&fast_transfer_id) {
+            return fast_transfer_id;
+        }
+        fast_transfer.canonical_id()
+    }
+
     fn mark_fast_transfer_as_finalised(&mut self, fast_transfer_id: &FastTransferId) {
         let mut status = self
             .get_fast_transfer_status(fast_transfer_id)
@@ -2032,6 +2684,7 @@ impl Contract {
     fn remove_fin_transfer(&mut self, transfer_id: &TransferId, storage_owner: &AccountId) {
         let storage_usage = env::storage_usage();
         self.finalised_transfers.remove(transfer_id);
+        self.origin_senders.remove(&(*transfer_id).into());
 
         let refund =
             env::storage_byte_cost().saturating_mul((storage_usage - env::storage_usage()).into());
@@ -2050,6 +2703,7 @@ impl Contract {
         let storage_usage = env::storage_usage();
 
         self.finalised_utxo_transfers.remove(transfer_id);
+        self.origin_senders.remove(transfer_id);
 
         let refund =
             env::storage_byte_cost().saturating_mul((storage_usage - env::storage_usage()).into());
@@ -2170,10 +2824,7 @@ impl Contract {
         let origin_chain = self
             .get_utxo_chain_by_token(&token_id)
             .sdk_expect("ERR_UTXO_CONFIG_MISSING");
-        let config = self
-            .utxo_chain_connectors
-            .get(&origin_chain)
-            .sdk_expect("ERR_UTXO_CONFIG_MISSING");
+        let config = self.require_chain_configured(origin_chain);
         require!(
             sender_id == &config.connector,
             "ERR_SENDER_IS_NOT_CONNECTOR"
@@ -2186,12 +2837,21 @@ impl Contract {
             origin_chain,
         );
 
-        if let Some(status) = self.get_fast_transfer_status(&fast_transfer.id()) {
+        if let Some(status) =
+            self.get_fast_transfer_status(&self.resolve_fast_transfer_id(&fast_transfer))
+        {
             return self.utxo_fin_transfer_fast(fast_transfer, status, utxo_fin_transfer_msg);
         }
 
-        let required_storage_balance =
-            self.add_fin_utxo_transfer(&utxo_fin_transfer_msg.get_transfer_id(origin_chain));
+        let transfer_id = utxo_fin_transfer_msg.get_transfer_id(origin_chain);
+        let mut required_storage_balance = self.add_fin_utxo_transfer(&transfer_id);
+        if utxo_fin_transfer_msg.recipient.get_chain() == ChainKind::Near {
+            required_storage_balance =
+                required_storage_balance.saturating_add(self.add_origin_senders(
+                    &transfer_id,
+                    &Self::utxo_origin_senders(origin_chain, &utxo_fin_transfer_msg),
+                ));
+        }
 
         self.update_storage_balance(
             signer_id.clone(),
@@ -2231,13 +2891,24 @@ impl Contract {
             "ERR_FAST_TRANSFER_ALREADY_FINALISED"
         );
 
-        let amount = if fast_transfer.recipient.get_chain() == ChainKind::Near {
-            self.remove_fast_transfer(&fast_transfer.id());
-            fast_transfer.amount
+        let fast_transfer_id = self.resolve_fast_transfer_id(&fast_transfer);
+        let amount = if let OmniAddress::Near(recipient) = &fast_transfer.recipient {
+            self.remove_fast_transfer(&fast_transfer_id);
+            // The relayer that credited the recipient is reimbursed without the inbound fee
+            let inbound_fee = self.get_inbound_fee_amount(
+                fast_transfer.transfer_id.origin_chain,
+                recipient,
+                fast_transfer.amount,
+            );
+            self.collect_protocol_fee(&fast_transfer.token_id, inbound_fee);
+            U128(fast_transfer.amount.0 - inbound_fee.0)
         } else {
-            self.mark_fast_transfer_as_finalised(&fast_transfer.id());
+            self.mark_fast_transfer_as_finalised(&fast_transfer_id);
             // With transfers to other chain the fee will be claimed after finalization on the destination chain
-            U128(fast_transfer.amount.0 - fast_transfer.fee.fee.0)
+            fast_transfer
+                .fee
+                .net_amount(fast_transfer.amount)
+                .sdk_unwrap()
         };
 
         self.send_tokens(
@@ -2300,6 +2971,8 @@ impl Contract {
         storage_owner: &AccountId,
     ) -> PromiseOrPromiseIndexOrValue<U128> {
         let origin_transfer_id = utxo_fin_transfer_msg.get_transfer_id(origin_chain);
+        // The deposit stays locked on NEAR until it is forwarded or signed
+        self.record_tvl_outbound(&token_id, amount.0);
 
         self.current_origin_nonce += 1;
         let transfer_message = TransferMessage {
@@ -2316,6 +2989,7 @@ impl Contract {
             destination_nonce: self
                 .get_next_destination_nonce(utxo_fin_transfer_msg.recipient.get_chain()),
             origin_transfer_id: Some(origin_transfer_id),
+            expires_at: None,
         };
 
         let required_storage_balance =
@@ -2352,7 +3026,7 @@ impl Contract {
                 |origin_transfer_id| origin_transfer_id.origin_chain,
             );
 
-            if origin_chain.is_utxo_chain() {
+            if self.get_chain_traits(origin_chain).fee_model == FeeModel::TokenOnly {
                 env::panic_str("Can't have native fee for transfers from UTXO chains")
             } else if origin_chain == ChainKind::Near {
                 Promise::new(fee_recipient.clone())
@@ -2367,28 +3041,14 @@ impl Contract {
         }
 
         let token = self.get_token_id(&message.token);
-        env::log_str(
-            &OmniBridgeEvent::ClaimFeeEvent {
-                transfer_message: message.clone(),
-            }
-            .to_log_string(),
-        );
+        self.emit_event(&OmniBridgeEvent::ClaimFeeEvent {
+            transfer_message: message.clone(),
+        });
 
+        // The fees are converted to wNEAR only when they are claimed from the ledger, with
+        // the minimum output of the recipient
         if token_fee > 0 {
-            if self.deployed_tokens.contains(&token) {
-                PromiseOrValue::Promise(ext_token::ext(token).with_static_gas(MINT_TOKEN_GAS).mint(
-                    fee_recipient,
-                    U128(token_fee),
-                    None,
-                ))
-            } else {
-                PromiseOrValue::Promise(
-                    ext_token::ext(token)
-                        .with_static_gas(FT_TRANSFER_GAS)
-                        .with_attached_deposit(ONE_YOCTO)
-                        .ft_transfer(fee_recipient, U128(token_fee), None),
-                )
-            }
+            PromiseOrValue::Promise(self.pay_token_fee(token, fee_recipient, U128(token_fee)))
         } else {
             PromiseOrValue::Value(())
         }
@@ -2426,6 +3086,7 @@ impl Contract {
                 .is_none(),
             "ERR_TOKEN_EXIST"
         );
+        self.record_bootstrap_token_bound();
     }
 
     pub fn swap_migrated_token(
@@ -2446,6 +3107,7 @@ impl Contract {
     }
 
     fn verify_proof(&self, chain_kind: ChainKind, prover_args: Vec<u8>) -> Promise {
+        Self::check_proof_size(&prover_args);
         let prover_account_id = self
             .provers
             .get(&chain_kind)
@@ -2464,13 +3126,11 @@ impl Contract {
     }
 
     fn denormalize_amount(amount: u128, decimals: Decimals) -> u128 {
-        let diff_decimals: u32 = (decimals.origin_decimals - decimals.decimals).into();
-        amount * (10_u128.pow(diff_decimals))
+        units::scale_amount(amount, decimals.decimals, decimals.origin_decimals).sdk_unwrap()
     }
 
     fn normalize_amount(amount: u128, decimals: Decimals) -> u128 {
-        let diff_decimals: u32 = (decimals.origin_decimals - decimals.decimals).into();
-        amount / (10_u128.pow(diff_decimals))
+        units::scale_amount(amount, decimals.origin_decimals, decimals.decimals).sdk_unwrap()
     }
 
     // Native tokens always have the same decimals on Near as on origin chain
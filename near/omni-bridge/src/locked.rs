@@ -0,0 +1,104 @@
+use near_sdk::json_types::U128;
+use near_sdk::{near, AccountId};
+use omni_types::safe_amount::SafeAmount;
+
+use crate::auditor::AccountingEntry;
+use crate::helpers::SdkUnwrap;
+use crate::{Contract, ContractExt};
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockedState {
+    // Stored in `pending_transfers` and waiting to be signed or submitted
+    Pending,
+    // Forwarded to a connector and waiting for the result of the call
+    InFlight,
+    // Parked after repeated failures and waiting for a manual resolution
+    DeadLetter,
+    // Collected fees that were not paid out yet
+    FeePool,
+    // Held for a recipient that has to claim the tokens
+    Escrow,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LockedBreakdown {
+    pub pending: U128,
+    pub in_flight: U128,
+    pub dead_letter: U128,
+    pub fee_pool: U128,
+    pub escrow: U128,
+}
+
+impl LockedBreakdown {
+    fn get_mut(&mut self, state: LockedState) -> &mut U128 {
+        match state {
+            LockedState::Pending => &mut self.pending,
+            LockedState::InFlight => &mut self.in_flight,
+            LockedState::DeadLetter => &mut self.dead_letter,
+            LockedState::FeePool => &mut self.fee_pool,
+            LockedState::Escrow => &mut self.escrow,
+        }
+    }
+}
+
+#[near]
+impl Contract {
+    /// Returns the amount of the token held by the bridge, split by the state of the funds.
+    pub fn get_locked_breakdown(&self, token: AccountId) -> LockedBreakdown {
+        self.locked_breakdown.get(&token).unwrap_or_default()
+    }
+}
+
+impl Contract {
+    pub(crate) fn lock_amount(&mut self, token: &AccountId, state: LockedState, amount: u128) {
+        self.notify_auditor(token, amount, AccountingEntry::Lock { state });
+        self.update_locked_breakdown(token, |breakdown| {
+            let value = breakdown.get_mut(state);
+            *value = SafeAmount::from(*value)
+                .checked_add(amount.into())
+                .sdk_unwrap()
+                .into();
+        });
+    }
+
+    // Transfers created before the breakdown was tracked are not accounted,
+    // so the subtraction saturates instead of failing.
+    pub(crate) fn unlock_amount(&mut self, token: &AccountId, state: LockedState, amount: u128) {
+        self.notify_auditor(token, amount, AccountingEntry::Release { state });
+        self.update_locked_breakdown(token, |breakdown| {
+            let value = breakdown.get_mut(state);
+            *value = U128(value.0.saturating_sub(amount));
+        });
+    }
+
+    pub(crate) fn move_locked_amount(
+        &mut self,
+        token: &AccountId,
+        from: LockedState,
+        to: LockedState,
+        amount: u128,
+    ) {
+        self.notify_auditor(token, amount, AccountingEntry::Move { from, to });
+        self.update_locked_breakdown(token, |breakdown| {
+            let value = breakdown.get_mut(from);
+            *value = U128(value.0.saturating_sub(amount));
+            let value = breakdown.get_mut(to);
+            *value = SafeAmount::from(*value)
+                .checked_add(amount.into())
+                .sdk_unwrap()
+                .into();
+        });
+    }
+
+    fn update_locked_breakdown(
+        &mut self,
+        token: &AccountId,
+        update: impl FnOnce(&mut LockedBreakdown),
+    ) {
+        let mut breakdown = self.locked_breakdown.get(token).unwrap_or_default();
+        update(&mut breakdown);
+        self.locked_breakdown.insert(token, &breakdown);
+    }
+}
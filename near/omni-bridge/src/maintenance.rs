@@ -0,0 +1,80 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::{env, near, require, Gas, NearToken};
+
+use crate::{Contract, ContractExt, Role};
+
+/// Rebate paid to the callers of the permissionless maintenance methods.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaintenanceConfig {
+    pub rebate_per_tgas: NearToken,
+    // Bounds the rebate by the work done, so a call can't burn gas on items it doesn't process
+    pub max_rebate_per_item: NearToken,
+    pub max_rebate_per_call: NearToken,
+}
+
+#[near]
+impl Contract {
+    /// `None` stops the rebates, the remaining budget is kept for later.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_maintenance_config(&mut self, config: Option<MaintenanceConfig>) {
+        self.maintenance_config = config;
+    }
+
+    pub fn get_maintenance_config(&self) -> Option<MaintenanceConfig> {
+        self.maintenance_config.clone()
+    }
+
+    pub fn get_maintenance_budget(&self) -> NearToken {
+        self.maintenance_budget
+    }
+
+    #[payable]
+    pub fn fund_maintenance_budget(&mut self) {
+        require!(
+            !env::attached_deposit().is_zero(),
+            "ERR_ZERO_MAINTENANCE_FUNDING"
+        );
+        self.maintenance_budget = self
+            .maintenance_budget
+            .saturating_add(env::attached_deposit());
+    }
+}
+
+impl Contract {
+    // Reimburses the gas burnt by a maintenance call from the budget. The rebate is credited
+    // to the storage balance of the caller, so keepers have to be registered to receive it.
+    // Calls that didn't change anything are not reimbursed, so the budget can't be drained.
+    pub(crate) fn pay_maintenance_rebate(&mut self, processed_items: u64) -> NearToken {
+        let Some(config) = &self.maintenance_config else {
+            return NearToken::from_yoctonear(0);
+        };
+        if processed_items == 0 {
+            return NearToken::from_yoctonear(0);
+        }
+        let caller = env::predecessor_account_id();
+        let Some(mut storage) = self.accounts_balances.get(&caller) else {
+            return NearToken::from_yoctonear(0);
+        };
+
+        let used_tgas = env::used_gas()
+            .as_gas()
+            .div_ceil(Gas::from_tgas(1).as_gas());
+        let rebate = config
+            .rebate_per_tgas
+            .saturating_mul(used_tgas.into())
+            .min(
+                config
+                    .max_rebate_per_item
+                    .saturating_mul(processed_items.into()),
+            )
+            .min(config.max_rebate_per_call)
+            .min(self.maintenance_budget);
+
+        self.maintenance_budget = self.maintenance_budget.saturating_sub(rebate);
+        storage.total = storage.total.saturating_add(rebate);
+        storage.available = storage.available.saturating_add(rebate);
+        self.accounts_balances.insert(&caller, &storage);
+        rebate
+    }
+}
@@ -0,0 +1,195 @@
+use near_plugins::{access_control_any, pause, AccessControllable, Pausable};
+use near_sdk::json_types::Base58CryptoHash;
+use near_sdk::{borsh, env, near, require, AccountId, Gas, NearToken, Promise, PromiseError};
+use omni_types::locker_args::ReceiveMessageArgs;
+use omni_types::mpc_types::SignatureResponse;
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::prover_result::{IncomingMessage, ProverResult};
+use omni_types::{AddressKind, ChainKind, MessageGasParams, MessagePayload, Nonce, PayloadType};
+
+use crate::external::ext_message_handler;
+use crate::helpers::SdkExpect;
+use crate::{Contract, ContractExt, Role};
+
+const SEND_MESSAGE_CALLBACK_GAS: Gas = Gas::from_tgas(5);
+const RECEIVE_MESSAGE_CALLBACK_GAS: Gas = Gas::from_tgas(60);
+const ON_MESSAGE_GAS: Gas = Gas::from_tgas(30);
+const ON_MESSAGE_CALLBACK_GAS: Gas = Gas::from_tgas(10);
+
+#[near]
+impl Contract {
+    /// Signs a message of the caller for the app on the destination chain, without moving
+    /// tokens. The gas params are signed with the payload hash, so the relayer of the
+    /// destination chain is held to them.
+    #[payable]
+    #[pause(except(roles(Role::DAO, Role::UnrestrictedRelayer)))]
+    pub fn send_message(
+        &mut self,
+        destination_chain: ChainKind,
+        payload_hash: Base58CryptoHash,
+        gas_params: MessageGasParams,
+    ) -> Promise {
+        require!(
+            matches!(
+                self.get_chain_traits(destination_chain).address_kind,
+                AddressKind::Evm | AddressKind::Solana
+            ),
+            "ERR_MESSAGE_NOT_SUPPORTED_FOR_CHAIN"
+        );
+
+        self.current_message_nonce += 1;
+        let message_payload = MessagePayload {
+            prefix: PayloadType::Message,
+            nonce: self.current_message_nonce,
+            sender: env::predecessor_account_id(),
+            destination_chain,
+            payload_hash,
+            gas_params,
+        };
+
+        let request = self.build_signature_request(
+            destination_chain,
+            &borsh::to_vec(&message_payload).sdk_expect("ERR_BORSH"),
+        );
+        self.request_signature(request).then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(SEND_MESSAGE_CALLBACK_GAS)
+                .send_message_callback(message_payload),
+        )
+    }
+
+    #[private]
+    pub fn send_message_callback(
+        &mut self,
+        #[callback_result] call_result: Result<SignatureResponse, PromiseError>,
+        #[serializer(borsh)] message_payload: MessagePayload,
+    ) {
+        if let Ok(signature) = call_result {
+            env::log_str(
+                &OmniBridgeEvent::SendMessageEvent {
+                    signature,
+                    message_payload,
+                }
+                .to_log_string(),
+            );
+        }
+    }
+
+    /// Delivers the proven message from the factory of the origin chain to its handler on
+    /// NEAR. The caller pays for the storage of the replay protection of the message.
+    #[payable]
+    #[pause(except(roles(Role::DAO, Role::UnrestrictedRelayer)))]
+    pub fn receive_message(&mut self, #[serializer(borsh)] args: ReceiveMessageArgs) -> Promise {
+        self.verify_proof(args.chain_kind, args.prover_args).then(
+            Self::ext(env::current_account_id())
+                .with_attached_deposit(env::attached_deposit())
+                .with_static_gas(RECEIVE_MESSAGE_CALLBACK_GAS)
+                .receive_message_callback(&env::predecessor_account_id()),
+        )
+    }
+
+    #[private]
+    #[payable]
+    pub fn receive_message_callback(
+        &mut self,
+        #[callback_result]
+        #[serializer(borsh)]
+        call_result: Result<ProverResult, PromiseError>,
+        predecessor_account_id: &AccountId,
+    ) -> Promise {
+        let Ok(ProverResult::Message(message)) = call_result else {
+            env::panic_str("Invalid proof message")
+        };
+
+        let origin_chain = message.emitter_address.get_chain();
+        require!(
+            self.factories.get(&origin_chain).as_ref() == Some(&message.emitter_address),
+            "ERR_UNKNOWN_FACTORY"
+        );
+        require!(
+            self.message_handlers.contains(&message.recipient),
+            "ERR_MESSAGE_HANDLER_NOT_REGISTERED"
+        );
+
+        let storage_usage = env::storage_usage();
+        require!(
+            self.received_messages
+                .insert(&(origin_chain, message.origin_nonce)),
+            "ERR_MESSAGE_ALREADY_RECEIVED"
+        );
+        let required_balance = env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into());
+        self.update_storage_balance(
+            predecessor_account_id.clone(),
+            required_balance,
+            env::attached_deposit(),
+        );
+
+        ext_message_handler::ext(message.recipient.clone())
+            .with_static_gas(ON_MESSAGE_GAS)
+            .on_message(origin_chain, message.sender.clone(), message.payload_hash)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(ON_MESSAGE_CALLBACK_GAS)
+                    .on_message_callback(message, predecessor_account_id, required_balance),
+            )
+    }
+
+    /// Keeps the message received only if its handler accepted it. Otherwise the replay
+    /// protection is removed and its storage credited back, so the message can be delivered
+    /// again with the same proof.
+    #[private]
+    pub fn on_message_callback(
+        &mut self,
+        #[serializer(borsh)] message: IncomingMessage,
+        #[serializer(borsh)] relayer: &AccountId,
+        #[serializer(borsh)] storage_deposit: NearToken,
+        #[callback_result] call_result: &Result<(), PromiseError>,
+    ) {
+        let origin_chain = message.emitter_address.get_chain();
+        if call_result.is_err() {
+            self.received_messages
+                .remove(&(origin_chain, message.origin_nonce));
+            if let Some(mut storage) = self.accounts_balances.get(relayer) {
+                storage.available = storage.available.saturating_add(storage_deposit);
+                self.accounts_balances.insert(relayer, &storage);
+            }
+            return;
+        }
+
+        env::log_str(
+            &OmniBridgeEvent::ReceiveMessageEvent {
+                origin_chain,
+                origin_nonce: message.origin_nonce,
+                sender: message.sender,
+                recipient: message.recipient,
+                payload_hash: message.payload_hash,
+            }
+            .to_log_string(),
+        );
+    }
+
+    /// Allows the contract to receive messages from the other chains. Only registered
+    /// handlers are called, so the locker never calls arbitrary contracts.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn register_message_handler(&mut self, handler: AccountId) {
+        self.message_handlers.insert(&handler);
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn unregister_message_handler(&mut self, handler: AccountId) {
+        self.message_handlers.remove(&handler);
+    }
+
+    pub fn is_message_handler(&self, handler: AccountId) -> bool {
+        self.message_handlers.contains(&handler)
+    }
+
+    pub fn is_message_received(&self, chain_kind: ChainKind, nonce: Nonce) -> bool {
+        self.received_messages.contains(&(chain_kind, nonce))
+    }
+
+    pub fn get_current_message_nonce(&self) -> Nonce {
+        self.current_message_nonce
+    }
+}
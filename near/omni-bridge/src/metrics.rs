@@ -0,0 +1,78 @@
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{env, near, AccountId};
+use omni_types::safe_amount::SafeAmount;
+use omni_types::ChainKind;
+
+use crate::helpers::SdkUnwrap;
+use crate::{Contract, ContractExt};
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+/// Identifier of a metric of `get_metrics_packed`. The identifiers are stable: the new metrics
+/// are added at the end and the existing ones are never renamed or reused.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MetricId {
+    /// Pending transfers to the chain.
+    PendingTransfers(ChainKind),
+    /// Transfers initiated on NEAR to the chain.
+    InitiatedTransfers(ChainKind),
+    /// Amount of the token sent by the transfers initiated on NEAR.
+    InitiatedVolume(AccountId),
+    /// Transfers from the chain finalised on NEAR.
+    FinalisedTransfers(ChainKind),
+    /// 1 if the circuit breaker of the connector of the UTXO chain is tripped.
+    ConnectorTripped(ChainKind),
+    /// Seconds since the prover of the chain last verified a transfer proof, approximating
+    /// the age of its light client.
+    LightClientAge(ChainKind),
+}
+
+#[near]
+impl Contract {
+    /// Returns the numeric metrics of the bridge, sorted by id, for the exporters polling them.
+    /// The values are read from the counters and indexes updated by the transfers,
+    /// so the cost of the view doesn't depend on the number of transfers.
+    pub fn get_metrics_packed(&self) -> Vec<(MetricId, U128)> {
+        let mut metrics: Vec<(MetricId, U128)> = self.metric_counters.iter().collect();
+
+        for chain_kind in self.known_chains() {
+            let pending_transfers = self.get_pending_transfers_count(chain_kind);
+            if pending_transfers > 0 {
+                metrics.push((
+                    MetricId::PendingTransfers(chain_kind),
+                    U128(pending_transfers.into()),
+                ));
+            }
+        }
+        for chain_kind in self.utxo_chain_connectors.keys() {
+            metrics.push((
+                MetricId::ConnectorTripped(*chain_kind),
+                U128(self.is_chain_connector_tripped(*chain_kind).into()),
+            ));
+        }
+        for (chain_kind, _) in self.provers.iter() {
+            if let Some(verified_at) = self.last_verified_proofs.get(&chain_kind) {
+                let age = env::block_timestamp().saturating_sub(verified_at.0) / NANOS_PER_SECOND;
+                metrics.push((MetricId::LightClientAge(chain_kind), U128(age.into())));
+            }
+        }
+
+        metrics.sort_by(|(a, _), (b, _)| a.cmp(b));
+        metrics
+    }
+}
+
+impl Contract {
+    pub(crate) fn increment_metric(&mut self, metric_id: &MetricId, amount: u128) {
+        let value = SafeAmount::from(self.metric_counters.get(metric_id).unwrap_or_default())
+            .checked_add(amount.into())
+            .sdk_unwrap();
+        self.metric_counters.insert(metric_id, &value.into());
+    }
+
+    pub(crate) fn record_proof_verified(&mut self, chain_kind: ChainKind) {
+        self.last_verified_proofs
+            .insert(&chain_kind, &U64(env::block_timestamp()));
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
 use crate::{
+    dead_letter::DEFAULT_MAX_TRANSFER_RESTORES,
+    event_emission::{EventEmissionState, CONFIGURABLE_EVENTS},
     storage::{Decimals, FastTransferStatusStorage, TransferMessageStorage},
     Contract, ContractExt, StorageKey,
 };
@@ -8,15 +10,19 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use near_contract_standards::storage_management::StorageBalance;
 use near_sdk::{
     collections::{LookupMap, LookupSet, UnorderedMap},
-    env, near, AccountId, CryptoHash, PanicOnDefault,
+    env, near, AccountId, CryptoHash, NearToken, PanicOnDefault,
+};
+use omni_types::{
+    btc::UTXOChainConfig, ChainKind, FastTransferId, Nonce, OmniAddress, TransferId,
+    UnifiedTransferId,
 };
-use omni_types::{btc::UTXOChainConfig, ChainKind, FastTransferId, Nonce, OmniAddress, TransferId};
 
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct OldState {
     pub factories: LookupMap<ChainKind, OmniAddress>,
     pub pending_transfers: LookupMap<TransferId, TransferMessageStorage>,
     pub finalised_transfers: LookupSet<TransferId>,
+    pub finalised_utxo_transfers: LookupSet<UnifiedTransferId>,
     pub fast_transfers: LookupMap<FastTransferId, FastTransferStatusStorage>,
     pub token_id_to_address: LookupMap<(ChainKind, AccountId), OmniAddress>,
     pub token_address_to_id: LookupMap<OmniAddress, AccountId>,
@@ -31,6 +37,7 @@ pub struct OldState {
     pub provers: UnorderedMap<ChainKind, AccountId>,
     pub init_transfer_promises: LookupMap<AccountId, CryptoHash>,
     pub utxo_chain_connectors: HashMap<ChainKind, UTXOChainConfig>,
+    pub migrated_tokens: LookupMap<AccountId, AccountId>,
 }
 
 #[near]
@@ -39,11 +46,11 @@ impl Contract {
     #[init(ignore_state)]
     pub fn migrate() -> Self {
         if let Some(old_state) = env::state_read::<OldState>() {
-            Self {
+            let contract = Self {
                 factories: old_state.factories,
                 pending_transfers: old_state.pending_transfers,
                 finalised_transfers: old_state.finalised_transfers,
-                finalised_utxo_transfers: LookupSet::new(StorageKey::FinalisedUtxoTransfers),
+                finalised_utxo_transfers: old_state.finalised_utxo_transfers,
                 fast_transfers: old_state.fast_transfers,
                 token_id_to_address: old_state.token_id_to_address,
                 token_address_to_id: old_state.token_address_to_id,
@@ -58,8 +65,112 @@ impl Contract {
                 provers: old_state.provers,
                 init_transfer_promises: old_state.init_transfer_promises,
                 utxo_chain_connectors: old_state.utxo_chain_connectors,
-                migrated_tokens: LookupMap::new(StorageKey::MigratedTokens),
-            }
+                migrated_tokens: old_state.migrated_tokens,
+                transfer_quotas: LookupMap::new(StorageKey::TransferQuotas),
+                transfer_quota_usage: LookupMap::new(StorageKey::TransferQuotaUsage),
+                quota_exempt_accounts: LookupSet::new(StorageKey::QuotaExemptAccounts),
+                origin_senders: LookupMap::new(StorageKey::OriginSenders),
+                derived_account_funding: NearToken::from_yoctonear(0),
+                funded_derived_accounts: LookupSet::new(StorageKey::FundedDerivedAccounts),
+                locked_breakdown: LookupMap::new(StorageKey::LockedBreakdown),
+                max_transfer_restores: DEFAULT_MAX_TRANSFER_RESTORES,
+                transfer_restore_counts: LookupMap::new(StorageKey::TransferRestoreCounts),
+                dead_letter_transfers: LookupMap::new(StorageKey::DeadLetterTransfers),
+                fee_recipient_strategies: LookupMap::new(StorageKey::FeeRecipientStrategies),
+                unreported_fee_transfers: LookupMap::new(StorageKey::UnreportedFeeTransfers),
+                connector_interface_versions: LookupMap::new(
+                    StorageKey::ConnectorInterfaceVersions,
+                ),
+                storage_safety_margin: NearToken::from_yoctonear(0),
+                governance_emitter: None,
+                last_governance_nonce: 0,
+                rewards_config: None,
+                rewarded_tokens: LookupMap::new(StorageKey::RewardedTokens),
+                reward_epochs: LookupMap::new(StorageKey::RewardEpochs),
+                reward_points: LookupMap::new(StorageKey::RewardPoints),
+                reserved_outpoints: LookupMap::new(StorageKey::ReservedOutpoints),
+                utxo_withdraw_limits: LookupMap::new(StorageKey::UtxoWithdrawLimits),
+                transfer_timestamps: LookupMap::new(StorageKey::TransferTimestamps),
+                latency_buckets: LookupMap::new(StorageKey::LatencyBuckets),
+                fee_sponsors: LookupMap::new(StorageKey::FeeSponsors),
+                transfer_labels: LookupMap::new(StorageKey::TransferLabels),
+                pending_transfers_by_chain: LookupMap::new(StorageKey::PendingTransfersIndex),
+                transfer_beneficiaries: LookupMap::new(StorageKey::TransferBeneficiaries),
+                maintenance_config: None,
+                maintenance_budget: NearToken::from_yoctonear(0),
+                script_hash_allowlist_chains: LookupSet::new(StorageKey::ScriptHashAllowlistChains),
+                allowed_script_hash_recipients: LookupSet::new(
+                    StorageKey::AllowedScriptHashRecipients,
+                ),
+                unclaimed_deposit_chains: LookupSet::new(StorageKey::UnclaimedDepositChains),
+                unclaimed_deposits: LookupMap::new(StorageKey::UnclaimedDeposits),
+                fee_swap_contract: None,
+                fee_swap_pools: LookupMap::new(StorageKey::FeeSwapPools),
+                fee_conversions: LookupMap::new(StorageKey::FeeConversions),
+                force_finalize_proposals: LookupMap::new(StorageKey::ForceFinalizeProposals),
+                utxo_key_rotations: LookupMap::new(StorageKey::UtxoKeyRotations),
+                legacy_outpoints: LookupSet::new(StorageKey::LegacyOutpoints),
+                signature_schemes: LookupMap::new(StorageKey::SignatureSchemes),
+                connectors: LookupMap::new(StorageKey::Connectors),
+                chain_connector_ids: LookupMap::new(StorageKey::ChainConnectorIds),
+                last_connector_id: 0,
+                unreported_fee_connectors: LookupMap::new(StorageKey::UnreportedFeeConnectors),
+                inbound_fees: LookupMap::new(StorageKey::InboundFees),
+                inbound_fee_exempt_accounts: LookupSet::new(StorageKey::InboundFeeExemptAccounts),
+                protocol_fees: LookupMap::new(StorageKey::ProtocolFees),
+                max_transfer_amounts: LookupMap::new(StorageKey::MaxTransferAmounts),
+                oversize_transfer_allowances: LookupMap::new(
+                    StorageKey::OversizeTransferAllowances,
+                ),
+                auditor: None,
+                last_accounting_seq: 0,
+                standby_connectors: LookupMap::new(StorageKey::StandbyConnectors),
+                failover_policies: LookupMap::new(StorageKey::FailoverPolicies),
+                connector_health: LookupMap::new(StorageKey::ConnectorHealth),
+                connector_in_flight: LookupMap::new(StorageKey::ConnectorInFlight),
+                transfer_claim_records: LookupMap::new(StorageKey::TransferClaimRecords),
+                utxo_address_formats: LookupMap::new(StorageKey::UtxoAddressFormats),
+                min_native_relayer_fees: LookupMap::new(StorageKey::MinNativeRelayerFees),
+                min_token_relayer_fees: LookupMap::new(StorageKey::MinTokenRelayerFees),
+                route_latencies: LookupMap::new(StorageKey::RouteLatencies),
+                extra_msg_required_chains: LookupSet::new(StorageKey::ExtraMsgRequiredChains),
+                event_emissions: [EventEmissionState::default(); CONFIGURABLE_EVENTS.len()],
+                utxo_refund_policies: LookupMap::new(StorageKey::UtxoRefundPolicies),
+                chain_traits: LookupMap::new(StorageKey::ChainTraits),
+                watchtower_config: None,
+                watchtowers: LookupMap::new(StorageKey::Watchtowers),
+                transfer_flags: LookupMap::new(StorageKey::TransferFlags),
+                claimable_fees: LookupMap::new(StorageKey::ClaimableFees),
+                signing_requested_transfers: LookupMap::new(StorageKey::SigningRequestedTransfers),
+                // The migrated deployments are already configured
+                bootstrap: None,
+                metric_counters: UnorderedMap::new(StorageKey::MetricCounters),
+                last_verified_proofs: LookupMap::new(StorageKey::LastVerifiedProofs),
+                submission_intents: LookupMap::new(StorageKey::SubmissionIntents),
+                destination_call_gas_fees: LookupMap::new(StorageKey::DestinationCallGasFees),
+                destination_calls: LookupMap::new(StorageKey::DestinationCalls),
+                pending_transfers_by_owner: LookupMap::new(StorageKey::PendingTransfersOwnerIndex),
+                utxo_fee_rates: LookupMap::new(StorageKey::UtxoFeeRates),
+                max_allowed_withdraw_fees: LookupMap::new(StorageKey::MaxAllowedWithdrawFees),
+                utxo_dust_limits: LookupMap::new(StorageKey::UtxoDustLimits),
+                approved_sweep_destinations: LookupSet::new(StorageKey::ApprovedSweepDestinations),
+                tvl_caps: LookupMap::new(StorageKey::TvlCaps),
+                token_tvl: LookupMap::new(StorageKey::TokenTvl),
+                current_message_nonce: 0,
+                message_handlers: LookupSet::new(StorageKey::MessageHandlers),
+                received_messages: LookupSet::new(StorageKey::ReceivedMessages),
+                utxo_script_kinds: LookupMap::new(StorageKey::UtxoScriptKinds),
+                custody_utxos: LookupMap::new(StorageKey::CustodyUtxos),
+                custody_tracked_chains: LookupSet::new(StorageKey::CustodyTrackedChains),
+                fee_escalation_policies: LookupMap::new(StorageKey::FeeEscalationPolicies),
+                fee_escalations: LookupMap::new(StorageKey::FeeEscalations),
+                fee_escalation_budget: NearToken::from_yoctonear(0),
+                preferred_relayers: LookupMap::new(StorageKey::PreferredRelayers),
+                utxo_withdrawals: LookupMap::new(StorageKey::UtxoWithdrawals),
+                sweeping_outpoints: LookupSet::new(StorageKey::SweepingOutpoints),
+            };
+            contract.migrate_fee_settlement_pause();
+            contract
         } else {
             env::panic_str("Old state not found. Migration is not needed.")
         }
@@ -0,0 +1,58 @@
+use near_sdk::{env, near, NearToken};
+use omni_types::{ChainKind, OmniAddress, UnifiedTransferId, UtxoFinTransferMsg};
+
+use crate::helpers::SdkExpect;
+use crate::{Contract, ContractExt};
+
+pub(crate) const MAX_ORIGIN_SENDERS: usize = 8;
+
+#[near]
+impl Contract {
+    /// Returns the origin-chain sender addresses of an inbound transfer that was finalised on NEAR.
+    ///
+    /// For EVM and Solana transfers this is the single sender of the origin transaction.
+    /// For UTXO chains these are the addresses of the deposit inputs reported by the connector,
+    /// up to the first eight.
+    pub fn get_transfer_origin_senders(
+        &self,
+        transfer_id: UnifiedTransferId,
+    ) -> Option<Vec<OmniAddress>> {
+        self.origin_senders.get(&transfer_id)
+    }
+}
+
+impl Contract {
+    pub(crate) fn add_origin_senders(
+        &mut self,
+        transfer_id: &UnifiedTransferId,
+        senders: &[OmniAddress],
+    ) -> NearToken {
+        if senders.is_empty() {
+            return NearToken::from_yoctonear(0);
+        }
+
+        // A deposit can have any number of inputs, so only the first senders are recorded
+        // rather than failing the finalization of the deposit.
+        let storage_usage = env::storage_usage();
+        self.origin_senders.insert(
+            transfer_id,
+            &senders[..senders.len().min(MAX_ORIGIN_SENDERS)].to_vec(),
+        );
+        env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into())
+    }
+
+    pub(crate) fn utxo_origin_senders(
+        origin_chain: ChainKind,
+        utxo_fin_transfer_msg: &UtxoFinTransferMsg,
+    ) -> Vec<OmniAddress> {
+        utxo_fin_transfer_msg
+            .sender_addresses
+            .iter()
+            .map(|address| {
+                OmniAddress::new_from_slice(origin_chain, address.as_bytes())
+                    .sdk_expect("ERR_INVALID_ORIGIN_SENDER")
+            })
+            .collect()
+    }
+}
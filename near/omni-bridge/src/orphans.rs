@@ -0,0 +1,158 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::{near, AccountId};
+use omni_types::{ChainKind, Nonce, TransferId};
+
+use crate::storage::TransferMessageStorage;
+use crate::{Contract, ContractExt, Role};
+
+const MAX_ORPHANS_SCAN_PAGE: u64 = 500;
+
+/// Index of the pending transfers checked for entries without a matching transfer.
+#[near(serializers=[json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrphanCollection {
+    PendingTransfersByChain(ChainKind),
+    PendingTransfersByOwner(AccountId),
+}
+
+#[near(serializers=[json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrphanScan {
+    pub orphans: Vec<TransferId>,
+    /// Position to continue the scan from, `None` once the end of the index is reached.
+    pub next_index: Option<u64>,
+}
+
+#[near(serializers=[json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnindexedTransferScan {
+    pub transfer_ids: Vec<TransferId>,
+    /// Nonce to continue the scan from, `None` once the last transfer initiated on NEAR
+    /// is reached.
+    pub next_nonce: Option<Nonce>,
+}
+
+#[near]
+impl Contract {
+    /// Returns the entries of the index that point to no pending transfer, or to a transfer
+    /// to another chain or of another owner, left by interrupted updates of the index. The
+    /// pending transfers missing from the indexes are found with `scan_unindexed_transfers`.
+    pub fn scan_orphans(&self, collection: OrphanCollection, from: u64, limit: u64) -> OrphanScan {
+        let Some(transfer_ids) = (match &collection {
+            OrphanCollection::PendingTransfersByChain(chain_kind) => {
+                self.pending_transfers_by_chain.get(chain_kind)
+            }
+            OrphanCollection::PendingTransfersByOwner(owner) => {
+                self.pending_transfers_by_owner.get(owner)
+            }
+        }) else {
+            return OrphanScan {
+                orphans: Vec::new(),
+                next_index: None,
+            };
+        };
+
+        let end = transfer_ids
+            .len()
+            .min(from.saturating_add(limit.min(MAX_ORPHANS_SCAN_PAGE)));
+        OrphanScan {
+            orphans: (from..end)
+                .filter_map(|index| transfer_ids.as_vector().get(index))
+                .filter(|transfer_id| self.is_orphan(&collection, transfer_id))
+                .collect(),
+            next_index: (end < transfer_ids.len()).then_some(end),
+        }
+    }
+
+    /// Removes the orphaned entries found by `scan_orphans` from the index. The entries are
+    /// checked again, so the transfers indexed since the scan are kept. The removal moves the
+    /// last entries of the index, so the scan has to be restarted after a repair.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn repair_orphans(
+        &mut self,
+        collection: OrphanCollection,
+        transfer_ids: Vec<TransferId>,
+    ) -> u32 {
+        let mut repaired = 0;
+        for transfer_id in transfer_ids {
+            if !self.is_orphan(&collection, &transfer_id) {
+                continue;
+            }
+            match &collection {
+                OrphanCollection::PendingTransfersByChain(chain_kind) => {
+                    self.unindex_pending_transfer(*chain_kind, &transfer_id);
+                }
+                OrphanCollection::PendingTransfersByOwner(owner) => {
+                    self.unindex_pending_transfer_owner(owner, &transfer_id);
+                }
+            }
+            repaired += 1;
+        }
+        repaired
+    }
+
+    /// Returns the pending transfers from the origin chain, in the range of origin nonces,
+    /// that are missing from the index of their destination chain or of their owner. The
+    /// pending transfers can't be iterated, so the scan probes the nonces: up to the last
+    /// transfer initiated on NEAR, or the range of the transfers of the other chains known
+    /// to the caller. The transfers found are added back with `index_pending_transfers`.
+    pub fn scan_unindexed_transfers(
+        &self,
+        origin_chain: ChainKind,
+        from_nonce: Nonce,
+        limit: u64,
+    ) -> UnindexedTransferScan {
+        let mut end = from_nonce.saturating_add(limit.min(MAX_ORPHANS_SCAN_PAGE));
+        if origin_chain == ChainKind::Near {
+            end = end.min(self.current_origin_nonce.saturating_add(1));
+        }
+        UnindexedTransferScan {
+            transfer_ids: (from_nonce..end)
+                .map(|origin_nonce| TransferId {
+                    origin_chain,
+                    origin_nonce,
+                })
+                .filter(|transfer_id| self.is_unindexed(transfer_id))
+                .collect(),
+            next_nonce: (origin_chain != ChainKind::Near || end <= self.current_origin_nonce)
+                .then_some(end),
+        }
+    }
+}
+
+impl Contract {
+    fn is_orphan(&self, collection: &OrphanCollection, transfer_id: &TransferId) -> bool {
+        let Some(transfer) = self
+            .pending_transfers
+            .get(transfer_id)
+            .map(TransferMessageStorage::into_main)
+        else {
+            return true;
+        };
+        match collection {
+            OrphanCollection::PendingTransfersByChain(chain_kind) => {
+                transfer.message.get_destination_chain() != *chain_kind
+            }
+            OrphanCollection::PendingTransfersByOwner(owner) => transfer.owner != *owner,
+        }
+    }
+
+    fn is_unindexed(&self, transfer_id: &TransferId) -> bool {
+        let Some(transfer) = self
+            .pending_transfers
+            .get(transfer_id)
+            .map(TransferMessageStorage::into_main)
+        else {
+            return false;
+        };
+        let is_indexed_by_chain = self
+            .pending_transfers_by_chain
+            .get(&transfer.message.get_destination_chain())
+            .is_some_and(|transfer_ids| transfer_ids.contains(transfer_id));
+        let is_indexed_by_owner = self
+            .pending_transfers_by_owner
+            .get(&transfer.owner)
+            .is_some_and(|transfer_ids| transfer_ids.contains(transfer_id));
+        !is_indexed_by_chain || !is_indexed_by_owner
+    }
+}
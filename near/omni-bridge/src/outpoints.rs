@@ -0,0 +1,119 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::U64;
+use near_sdk::{env, near, require};
+use omni_types::btc::OutPoint;
+use omni_types::{ChainKind, TransferId};
+
+use crate::{Contract, ContractExt, Role};
+
+// Long enough for the connector to report the spend of an accepted withdrawal
+const OUTPOINT_RESERVATION_TTL_NS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
+/// Reservation of an input by an in-flight withdrawal, of a single transfer or of all the
+/// transfers of a batch.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutpointReservation {
+    pub transfer_ids: Vec<TransferId>,
+    pub reserved_at: U64,
+}
+
+#[near]
+impl Contract {
+    /// Returns the transfers whose in-flight withdrawal spends the given outpoint, none if
+    /// the outpoint is not reserved.
+    pub fn get_outpoint_reservation(
+        &self,
+        chain_kind: ChainKind,
+        outpoint: OutPoint,
+    ) -> Vec<TransferId> {
+        self.reserved_outpoints
+            .get(&(chain_kind, outpoint))
+            .map(|reservation| reservation.transfer_ids)
+            .unwrap_or_default()
+    }
+
+    /// Releases the outpoints of withdrawals whose spend was confirmed, for the connectors
+    /// that don't report the spent UTXOs with `update_custody_utxos`.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn release_outpoint_reservations(
+        &mut self,
+        chain_kind: ChainKind,
+        outpoints: Vec<OutPoint>,
+    ) {
+        self.release_outpoints(chain_kind, &outpoints);
+    }
+
+    /// Releases the outpoints reserved for longer than the connector has to report their
+    /// spend, so the reservations of the accepted withdrawals don't pile up when the
+    /// connector doesn't report. Anyone can call it.
+    pub fn release_stale_outpoint_reservations(
+        &mut self,
+        chain_kind: ChainKind,
+        outpoints: Vec<OutPoint>,
+    ) {
+        let now = env::block_timestamp();
+        for outpoint in &outpoints {
+            let reservation = self.reserved_outpoints.get(&(chain_kind, *outpoint));
+            require!(
+                reservation
+                    .is_some_and(|reservation| now.saturating_sub(reservation.reserved_at.0)
+                        >= OUTPOINT_RESERVATION_TTL_NS),
+                "ERR_OUTPOINT_RESERVATION_NOT_STALE"
+            );
+        }
+        self.release_outpoints(chain_kind, &outpoints);
+    }
+}
+
+impl Contract {
+    // Reserves the inputs of a withdrawal until the submission to the connector fails or the
+    // spend is confirmed, so the connector never receives conflicting instructions to spend
+    // the same UTXO.
+    pub(crate) fn reserve_outpoints(
+        &mut self,
+        chain_kind: ChainKind,
+        transfer_ids: &[TransferId],
+        outpoints: &[OutPoint],
+    ) {
+        let reservation = OutpointReservation {
+            transfer_ids: transfer_ids.to_vec(),
+            reserved_at: U64(env::block_timestamp()),
+        };
+        for outpoint in outpoints {
+            require!(
+                !self.sweeping_outpoints.contains(&(chain_kind, *outpoint))
+                    && self
+                        .reserved_outpoints
+                        .insert(&(chain_kind, *outpoint), &reservation)
+                        .is_none(),
+                "ERR_OUTPOINT_ALREADY_RESERVED"
+            );
+        }
+    }
+
+    // Reserves the inputs of a withdrawal submitted to the connector. The submitter pays for
+    // the storage of the reservations with the attached deposit or its storage balance.
+    pub(crate) fn reserve_submitted_outpoints(
+        &mut self,
+        chain_kind: ChainKind,
+        transfer_ids: &[TransferId],
+        outpoints: &[OutPoint],
+    ) {
+        let storage_usage = env::storage_usage();
+        self.reserve_outpoints(chain_kind, transfer_ids, outpoints);
+        let required_balance = env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into());
+        self.update_storage_balance(
+            env::predecessor_account_id(),
+            required_balance,
+            env::attached_deposit(),
+        );
+    }
+
+    pub(crate) fn release_outpoints(&mut self, chain_kind: ChainKind, outpoints: &[OutPoint]) {
+        for outpoint in outpoints {
+            self.reserved_outpoints.remove(&(chain_kind, *outpoint));
+        }
+    }
+}
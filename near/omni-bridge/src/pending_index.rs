@@ -0,0 +1,286 @@
+use near_sdk::collections::UnorderedSet;
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{borsh, env, near, AccountId, NearToken};
+use omni_types::{ChainKind, Fee, TransferId};
+
+use crate::helpers::SdkExpect;
+use crate::storage::{TransferMessageStorage, TransferMessageStorageValue};
+use crate::{Contract, ContractExt, StorageKey};
+
+const MAX_PENDING_TRANSFERS_PAGE: u64 = 100;
+
+/// Pending transfer as listed to the relayers looking for work.
+#[near(serializers=[json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingTransferSummary {
+    pub transfer_id: TransferId,
+    pub owner: AccountId,
+    pub destination_chain: ChainKind,
+    pub amount: U128,
+    pub fee: Fee,
+}
+
+#[near]
+impl Contract {
+    /// Returns the pending transfers to the destination chain without reading the transfers
+    /// to the other chains.
+    pub fn get_pending_transfers(
+        &self,
+        chain_kind: ChainKind,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<(TransferId, TransferMessageStorageValue)> {
+        let Some(transfer_ids) = self.pending_transfers_by_chain.get(&chain_kind) else {
+            return Vec::new();
+        };
+        let from_index = from_index.unwrap_or_default();
+        let limit = limit
+            .unwrap_or(MAX_PENDING_TRANSFERS_PAGE)
+            .min(MAX_PENDING_TRANSFERS_PAGE);
+
+        (from_index..transfer_ids.len().min(from_index.saturating_add(limit)))
+            .filter_map(|index| transfer_ids.as_vector().get(index))
+            .filter_map(|transfer_id| {
+                self.pending_transfers
+                    .get(&transfer_id)
+                    .map(|transfer| (transfer_id, TransferMessageStorage::into_main(transfer)))
+            })
+            .collect()
+    }
+
+    /// Returns the pending transfers to all the chains, ordered by the destination chain.
+    /// `from_index` is the position in that order, so the pages don't repeat the transfers
+    /// while none are added or removed.
+    pub fn get_all_pending_transfers(
+        &self,
+        from_index: U64,
+        limit: u64,
+    ) -> Vec<PendingTransferSummary> {
+        let mut skip = from_index.0;
+        let mut limit = limit.min(MAX_PENDING_TRANSFERS_PAGE);
+        let mut transfers = Vec::new();
+        for chain_kind in self.known_chains() {
+            if limit == 0 {
+                break;
+            }
+            let Some(transfer_ids) = self.pending_transfers_by_chain.get(&chain_kind) else {
+                continue;
+            };
+            let len = transfer_ids.len();
+            if skip >= len {
+                skip -= len;
+                continue;
+            }
+
+            let end = len.min(skip.saturating_add(limit));
+            transfers.extend(
+                (skip..end)
+                    .filter_map(|index| transfer_ids.as_vector().get(index))
+                    .filter_map(|transfer_id| {
+                        self.pending_transfers.get(&transfer_id).map(|transfer| {
+                            let transfer = TransferMessageStorage::into_main(transfer);
+                            PendingTransferSummary {
+                                transfer_id,
+                                owner: transfer.owner,
+                                destination_chain: chain_kind,
+                                amount: transfer.message.amount,
+                                fee: transfer.message.fee,
+                            }
+                        })
+                    }),
+            );
+            limit -= end - skip;
+            skip = 0;
+        }
+        transfers
+    }
+
+    /// Returns the pending transfers owned by the account, e.g. for a wallet to show the
+    /// in-flight transfers of its user.
+    pub fn get_transfers_by_owner(
+        &self,
+        account_id: AccountId,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<(TransferId, TransferMessageStorageValue)> {
+        let Some(transfer_ids) = self.pending_transfers_by_owner.get(&account_id) else {
+            return Vec::new();
+        };
+        let from_index = from_index.unwrap_or_default();
+        let limit = limit
+            .unwrap_or(MAX_PENDING_TRANSFERS_PAGE)
+            .min(MAX_PENDING_TRANSFERS_PAGE);
+
+        (from_index..transfer_ids.len().min(from_index.saturating_add(limit)))
+            .filter_map(|index| transfer_ids.as_vector().get(index))
+            .filter_map(|transfer_id| {
+                self.pending_transfers
+                    .get(&transfer_id)
+                    .map(|transfer| (transfer_id, TransferMessageStorage::into_main(transfer)))
+            })
+            .collect()
+    }
+
+    pub fn get_pending_transfers_count(&self, chain_kind: ChainKind) -> u64 {
+        self.pending_transfers_by_chain
+            .get(&chain_kind)
+            .map_or(0, |transfer_ids| transfer_ids.len())
+    }
+
+    /// Adds the transfers created before the per-chain and per-owner indexes to them.
+    /// Called in batches by keepers after the upgrade, since the pending transfers can't be iterated.
+    /// Returns the maintenance rebate credited to the caller.
+    pub fn index_pending_transfers(&mut self, transfer_ids: Vec<TransferId>) -> NearToken {
+        let mut indexed_transfers = 0;
+        for transfer_id in transfer_ids {
+            let transfer = self.get_transfer_message_storage(transfer_id);
+            let is_indexed_by_chain =
+                self.index_pending_transfer(transfer.message.get_destination_chain(), transfer_id);
+            if self.index_pending_transfer_owner(&transfer.owner, transfer_id)
+                || is_indexed_by_chain
+            {
+                indexed_transfers += 1;
+            }
+        }
+        self.pay_maintenance_rebate(indexed_transfers)
+    }
+}
+
+impl Contract {
+    // Returns `false` if the transfer was already indexed.
+    pub(crate) fn index_pending_transfer(
+        &mut self,
+        chain_kind: ChainKind,
+        transfer_id: TransferId,
+    ) -> bool {
+        let mut transfer_ids = self
+            .pending_transfers_by_chain
+            .get(&chain_kind)
+            .unwrap_or_else(|| UnorderedSet::new(StorageKey::PendingTransfersByChain(chain_kind)));
+        if !transfer_ids.insert(&transfer_id) {
+            return false;
+        }
+        self.pending_transfers_by_chain
+            .insert(&chain_kind, &transfer_ids);
+        true
+    }
+
+    pub(crate) fn unindex_pending_transfer(
+        &mut self,
+        chain_kind: ChainKind,
+        transfer_id: &TransferId,
+    ) {
+        let Some(mut transfer_ids) = self.pending_transfers_by_chain.get(&chain_kind) else {
+            return;
+        };
+        if transfer_ids.remove(transfer_id) {
+            self.pending_transfers_by_chain
+                .insert(&chain_kind, &transfer_ids);
+        }
+    }
+
+    // Returns `false` if the transfer was already indexed.
+    pub(crate) fn index_pending_transfer_owner(
+        &mut self,
+        owner: &AccountId,
+        transfer_id: TransferId,
+    ) -> bool {
+        let mut transfer_ids = self
+            .pending_transfers_by_owner
+            .get(owner)
+            .unwrap_or_else(|| {
+                UnorderedSet::new(StorageKey::PendingTransfersByOwner(owner.clone()))
+            });
+        if !transfer_ids.insert(&transfer_id) {
+            return false;
+        }
+        self.pending_transfers_by_owner.insert(owner, &transfer_ids);
+        true
+    }
+
+    // The collection of an owner without pending transfers is removed, unlike the collections
+    // of the chains there can be many of them.
+    pub(crate) fn unindex_pending_transfer_owner(
+        &mut self,
+        owner: &AccountId,
+        transfer_id: &TransferId,
+    ) {
+        let Some(mut transfer_ids) = self.pending_transfers_by_owner.get(owner) else {
+            return;
+        };
+        if !transfer_ids.remove(transfer_id) {
+            return;
+        }
+        if transfer_ids.is_empty() {
+            self.pending_transfers_by_owner.remove(owner);
+        } else {
+            self.pending_transfers_by_owner.insert(owner, &transfer_ids);
+        }
+    }
+
+    // The index stores the transfer id twice: as the key of its position and in the elements vector.
+    // The first transfer to the chain also pays for the header of the chain collection.
+    pub(crate) fn required_balance_for_pending_transfer_index(
+        &self,
+        chain_kind: ChainKind,
+        transfer_id: &TransferId,
+    ) -> NearToken {
+        let prefix_len: u64 = borsh::to_vec(&StorageKey::PendingTransfersByChain(chain_kind))
+            .sdk_expect("ERR_BORSH")
+            .len()
+            .try_into()
+            .sdk_expect("ERR_CAST");
+        let key_len: u64 = borsh::to_vec(transfer_id)
+            .sdk_expect("ERR_BORSH")
+            .len()
+            .try_into()
+            .sdk_expect("ERR_CAST");
+        let mut required_storage = 2 * (Self::get_basic_storage() + prefix_len + 1 + key_len + 8);
+
+        if self.pending_transfers_by_chain.get(&chain_kind).is_none() {
+            let header_len: u64 = borsh::to_vec(&(
+                chain_kind,
+                UnorderedSet::<TransferId>::new(StorageKey::PendingTransfersByChain(chain_kind)),
+            ))
+            .sdk_expect("ERR_BORSH")
+            .len()
+            .try_into()
+            .sdk_expect("ERR_CAST");
+            required_storage += Self::get_basic_storage() + header_len;
+        }
+
+        env::storage_byte_cost().saturating_mul(required_storage.into())
+    }
+
+    // The owner is not known when the balance is checked, so the longest account id is assumed
+    // and the owner is assumed to have no other pending transfers.
+    pub(crate) fn required_balance_for_pending_transfer_owner_index(
+        transfer_id: &TransferId,
+    ) -> NearToken {
+        let max_account_id: AccountId = "a".repeat(64).parse().sdk_expect("ERR_PARSE_ACCOUNT_ID");
+        let prefix_len: u64 =
+            borsh::to_vec(&StorageKey::PendingTransfersByOwner(max_account_id.clone()))
+                .sdk_expect("ERR_BORSH")
+                .len()
+                .try_into()
+                .sdk_expect("ERR_CAST");
+        let key_len: u64 = borsh::to_vec(transfer_id)
+            .sdk_expect("ERR_BORSH")
+            .len()
+            .try_into()
+            .sdk_expect("ERR_CAST");
+        let header_len: u64 = borsh::to_vec(&(
+            max_account_id.clone(),
+            UnorderedSet::<TransferId>::new(StorageKey::PendingTransfersByOwner(max_account_id)),
+        ))
+        .sdk_expect("ERR_BORSH")
+        .len()
+        .try_into()
+        .sdk_expect("ERR_CAST");
+        let required_storage = 2 * (Self::get_basic_storage() + prefix_len + 1 + key_len + 8)
+            + Self::get_basic_storage()
+            + header_len;
+
+        env::storage_byte_cost().saturating_mul(required_storage.into())
+    }
+}
@@ -0,0 +1,82 @@
+use near_plugins::AccessControllable;
+use near_sdk::json_types::U64;
+use near_sdk::{borsh, env, near, require, AccountId, NearToken};
+use omni_types::TransferId;
+
+use crate::helpers::SdkExpect;
+use crate::{Contract, ContractExt, Role};
+
+const PREFERRED_RELAYER_WINDOW_NS: u64 = 60 * 60 * 1_000_000_000;
+
+/// Relayer chosen by the owner of a transfer at its initiation. Only the relayer can submit
+/// the transfer until the exclusivity window ends, then any relayer can take it over.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreferredRelayer {
+    pub account_id: AccountId,
+    pub exclusive_until: U64,
+}
+
+#[near]
+impl Contract {
+    pub fn get_preferred_relayer(&self, transfer_id: TransferId) -> Option<PreferredRelayer> {
+        self.preferred_relayers.get(&transfer_id)
+    }
+}
+
+impl Contract {
+    // The DAO and the unrestricted relayers can still submit the transfer, e.g. when the
+    // preferred relayer is down.
+    pub(crate) fn require_preferred_relayer(&self, transfer_id: &TransferId) {
+        let Some(preferred_relayer) = self.preferred_relayers.get(transfer_id) else {
+            return;
+        };
+        let predecessor = env::predecessor_account_id();
+        require!(
+            predecessor == preferred_relayer.account_id
+                || env::block_timestamp() >= preferred_relayer.exclusive_until.0
+                || self.acl_has_role(Role::DAO.into(), predecessor.clone())
+                || self.acl_has_role(Role::UnrestrictedRelayer.into(), predecessor),
+            "ERR_NOT_PREFERRED_RELAYER"
+        );
+    }
+
+    // Returns the storage cost of the record.
+    pub(crate) fn add_preferred_relayer(
+        &mut self,
+        transfer_id: &TransferId,
+        account_id: AccountId,
+    ) -> NearToken {
+        let storage_usage = env::storage_usage();
+        self.preferred_relayers.insert(
+            transfer_id,
+            &PreferredRelayer {
+                account_id,
+                exclusive_until: U64(
+                    env::block_timestamp().saturating_add(PREFERRED_RELAYER_WINDOW_NS)
+                ),
+            },
+        );
+        env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into())
+    }
+
+    pub(crate) fn required_balance_for_preferred_relayer(
+        transfer_id: &TransferId,
+        account_id: &AccountId,
+    ) -> NearToken {
+        let record_len: u64 = borsh::to_vec(&(
+            transfer_id,
+            PreferredRelayer {
+                account_id: account_id.clone(),
+                exclusive_until: U64(0),
+            },
+        ))
+        .sdk_expect("ERR_BORSH")
+        .len()
+        .try_into()
+        .sdk_expect("ERR_CAST");
+
+        env::storage_byte_cost().saturating_mul((Self::get_basic_storage() + record_len).into())
+    }
+}
@@ -0,0 +1,70 @@
+use near_sdk::{env, near, Gas, Promise, PromiseError};
+use omni_types::prover_result::ProverResult;
+use omni_types::{ChainKind, TransferId};
+
+use crate::{Contract, ContractExt};
+
+const VALIDATE_PROOF_CALLBACK_GAS: Gas = Gas::from_tgas(5);
+
+#[near(serializers=[json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofVerdict {
+    Valid { transfer_id: TransferId },
+    InvalidProof,
+    UnknownFactory,
+    TransferAlreadyFinalised { transfer_id: TransferId },
+    TokenNotRegistered,
+}
+
+#[near]
+impl Contract {
+    /// Runs the checks of `fin_transfer` against the proof without finalising the transfer,
+    /// so relayers can reject bad proofs before paying for the storage deposits and the transfer.
+    /// The verification needs a call to the prover, so it can't be a view method,
+    /// but it doesn't change the state of the locker.
+    pub fn validate_proof(
+        &self,
+        chain_kind: ChainKind,
+        #[serializer(borsh)] prover_args: Vec<u8>,
+    ) -> Promise {
+        self.verify_proof(chain_kind, prover_args).then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(VALIDATE_PROOF_CALLBACK_GAS)
+                .validate_proof_callback(),
+        )
+    }
+
+    #[private]
+    pub fn validate_proof_callback(
+        &self,
+        #[callback_result]
+        #[serializer(borsh)]
+        call_result: Result<ProverResult, PromiseError>,
+    ) -> ProofVerdict {
+        let Ok(ProverResult::InitTransfer(init_transfer)) = call_result else {
+            return ProofVerdict::InvalidProof;
+        };
+
+        if self
+            .factories
+            .get(&init_transfer.emitter_address.get_chain())
+            != Some(init_transfer.emitter_address)
+        {
+            return ProofVerdict::UnknownFactory;
+        }
+
+        let transfer_id = TransferId {
+            origin_chain: init_transfer.sender.get_chain(),
+            origin_nonce: init_transfer.origin_nonce,
+        };
+        if self.is_transfer_finalised(transfer_id) {
+            return ProofVerdict::TransferAlreadyFinalised { transfer_id };
+        }
+
+        if self.token_decimals.get(&init_transfer.token).is_none() {
+            return ProofVerdict::TokenNotRegistered;
+        }
+
+        ProofVerdict::Valid { transfer_id }
+    }
+}
@@ -0,0 +1,166 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::U128;
+use near_sdk::{borsh, env, near, require, AccountId, NearToken};
+use omni_types::safe_amount::SafeAmount;
+
+use crate::helpers::{SdkExpect, SdkUnwrap};
+use crate::{Contract, ContractExt, Role};
+
+const TRANSFER_QUOTA_PERIOD_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TransferQuota {
+    pub max_transfers: Option<u32>,
+    pub max_volume: Option<U128>,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TransferQuotaUsage {
+    pub period: u64,
+    pub transfers: u32,
+    pub volume: U128,
+}
+
+#[near]
+impl Contract {
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_transfer_quota(&mut self, token_id: AccountId, quota: Option<TransferQuota>) {
+        if let Some(quota) = quota {
+            self.transfer_quotas.insert(&token_id, &quota);
+        } else {
+            self.transfer_quotas.remove(&token_id);
+        }
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn add_quota_exempt_account(&mut self, account_id: AccountId) {
+        self.quota_exempt_accounts.insert(&account_id);
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn remove_quota_exempt_account(&mut self, account_id: AccountId) {
+        self.quota_exempt_accounts.remove(&account_id);
+    }
+
+    pub fn get_transfer_quota(&self, token_id: AccountId) -> Option<TransferQuota> {
+        self.transfer_quotas.get(&token_id)
+    }
+
+    pub fn is_quota_exempt(&self, account_id: AccountId) -> bool {
+        self.quota_exempt_accounts.contains(&account_id)
+    }
+
+    /// Returns the usage of the quota for the current period.
+    /// Usage recorded in one of the previous periods is reported as zero.
+    pub fn get_transfer_quota_usage(
+        &self,
+        account_id: AccountId,
+        token_id: AccountId,
+    ) -> TransferQuotaUsage {
+        let period = Self::current_quota_period();
+        self.transfer_quota_usage
+            .get(&(account_id, token_id))
+            .filter(|usage| usage.period == period)
+            .unwrap_or(TransferQuotaUsage {
+                period,
+                ..TransferQuotaUsage::default()
+            })
+    }
+}
+
+impl Contract {
+    pub(crate) fn check_transfer_quota(
+        &self,
+        account_id: &AccountId,
+        token_id: &AccountId,
+        amount: U128,
+    ) {
+        self.next_transfer_quota_usage(account_id, token_id, amount);
+    }
+
+    // Records the usage of the quota and returns the storage cost of the record.
+    pub(crate) fn record_transfer_quota_usage(
+        &mut self,
+        account_id: &AccountId,
+        token_id: &AccountId,
+        amount: U128,
+    ) -> NearToken {
+        let Some(usage) = self.next_transfer_quota_usage(account_id, token_id, amount) else {
+            return NearToken::from_yoctonear(0);
+        };
+
+        let storage_usage = env::storage_usage();
+        self.transfer_quota_usage
+            .insert(&(account_id.clone(), token_id.clone()), &usage);
+        env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into())
+    }
+
+    pub(crate) fn required_balance_for_transfer_quota_usage(
+        &self,
+        account_id: &AccountId,
+        token_id: &AccountId,
+    ) -> NearToken {
+        let key = (account_id.clone(), token_id.clone());
+        if self.quota_exempt_accounts.contains(account_id)
+            || self.transfer_quotas.get(token_id).is_none()
+            || self.transfer_quota_usage.get(&key).is_some()
+        {
+            return NearToken::from_yoctonear(0);
+        }
+
+        let key_len: u64 = borsh::to_vec(&key)
+            .sdk_expect("ERR_BORSH")
+            .len()
+            .try_into()
+            .sdk_expect("ERR_CAST");
+        let value_len: u64 = borsh::to_vec(&TransferQuotaUsage::default())
+            .sdk_expect("ERR_BORSH")
+            .len()
+            .try_into()
+            .sdk_expect("ERR_CAST");
+
+        env::storage_byte_cost()
+            .saturating_mul((Self::get_basic_storage() + key_len + value_len).into())
+    }
+
+    fn next_transfer_quota_usage(
+        &self,
+        account_id: &AccountId,
+        token_id: &AccountId,
+        amount: U128,
+    ) -> Option<TransferQuotaUsage> {
+        if self.quota_exempt_accounts.contains(account_id) {
+            return None;
+        }
+        let quota = self.transfer_quotas.get(token_id)?;
+
+        let mut usage = self.get_transfer_quota_usage(account_id.clone(), token_id.clone());
+        usage.transfers = usage.transfers.saturating_add(1);
+        usage.volume = SafeAmount::from(usage.volume)
+            .checked_add(amount.into())
+            .sdk_unwrap()
+            .into();
+
+        require!(
+            quota
+                .max_transfers
+                .is_none_or(|max_transfers| usage.transfers <= max_transfers),
+            "ERR_TRANSFER_QUOTA_EXCEEDED"
+        );
+        require!(
+            quota
+                .max_volume
+                .is_none_or(|max_volume| usage.volume.0 <= max_volume.0),
+            "ERR_TRANSFER_VOLUME_QUOTA_EXCEEDED"
+        );
+
+        Some(usage)
+    }
+
+    fn current_quota_period() -> u64 {
+        env::block_timestamp() / TRANSFER_QUOTA_PERIOD_NS
+    }
+}
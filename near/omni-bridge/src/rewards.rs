@@ -0,0 +1,259 @@
+use near_plugins::{access_control_any, pause, AccessControllable, Pausable};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{env, near, require, AccountId, Gas, Promise, PromiseError};
+use omni_types::safe_amount::SafeAmount;
+use omni_types::units::BPS_DENOMINATOR;
+use omni_types::{OmniAddress, TransferMessage};
+
+use crate::external::ext_token;
+use crate::helpers::{SdkExpect, SdkUnwrap};
+use crate::{Contract, ContractExt, Role, FT_TRANSFER_GAS, ONE_YOCTO};
+
+const CLAIM_REWARDS_CALLBACK_GAS: Gas = Gas::from_tgas(5);
+const MAX_PROTOCOL_FEE_SHARE_BPS: u16 = BPS_DENOMINATOR;
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RewardsConfig {
+    pub reward_token: AccountId,
+    pub epoch_duration: U64,
+    // Share of the protocol fees collected in the reward token that funds the current epoch
+    pub protocol_fee_share_bps: u16,
+}
+
+/// Token whose transfers accrue reward points.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RewardedToken {
+    // Transfers below this amount accrue no points
+    pub min_transfer_amount: U128,
+    // Amount of the token that has to be transferred to accrue one point
+    pub amount_per_point: U128,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RewardEpoch {
+    pub total_points: U128,
+    pub reward_amount: U128,
+}
+
+#[near]
+impl Contract {
+    /// Enables the rewards for bridge users. Changing the epoch duration renumbers the epochs,
+    /// so it should only be done after the rewards of the finished epochs were claimed.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_rewards_config(&mut self, config: Option<RewardsConfig>) {
+        if let Some(config) = &config {
+            require!(config.epoch_duration.0 > 0, "ERR_INVALID_EPOCH_DURATION");
+            require!(
+                config.protocol_fee_share_bps <= MAX_PROTOCOL_FEE_SHARE_BPS,
+                "ERR_INVALID_FEE_BPS"
+            );
+        }
+        self.rewards_config = config;
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_rewarded_token(
+        &mut self,
+        token_id: AccountId,
+        rewarded_token: Option<RewardedToken>,
+    ) {
+        if let Some(rewarded_token) = rewarded_token {
+            require!(
+                rewarded_token.amount_per_point.0 > 0,
+                "ERR_INVALID_AMOUNT_PER_POINT"
+            );
+            self.rewarded_tokens.insert(&token_id, &rewarded_token);
+        } else {
+            self.rewarded_tokens.remove(&token_id);
+        }
+    }
+
+    pub fn get_rewards_config(&self) -> Option<RewardsConfig> {
+        self.rewards_config.clone()
+    }
+
+    pub fn get_rewarded_token(&self, token_id: AccountId) -> Option<RewardedToken> {
+        self.rewarded_tokens.get(&token_id)
+    }
+
+    pub fn get_current_reward_epoch(&self) -> Option<u64> {
+        self.current_reward_epoch()
+    }
+
+    pub fn get_reward_epoch(&self, epoch: u64) -> RewardEpoch {
+        self.reward_epochs.get(&epoch).unwrap_or_default()
+    }
+
+    pub fn get_reward_points(&self, account_id: AccountId, epoch: u64) -> U128 {
+        self.reward_points
+            .get(&(account_id, epoch))
+            .unwrap_or_default()
+    }
+
+    /// Returns the amount of the reward token the account can claim for the given finished epochs.
+    pub fn get_claimable_rewards(&self, account_id: AccountId, epochs: Vec<u64>) -> U128 {
+        U128(
+            epochs
+                .into_iter()
+                .filter(|epoch| self.is_reward_epoch_finished(*epoch))
+                .map(|epoch| {
+                    let points = self.get_reward_points(account_id.clone(), epoch);
+                    Self::epoch_reward_share(&self.get_reward_epoch(epoch), points.0)
+                })
+                .fold(0, u128::saturating_add),
+        )
+    }
+
+    #[pause(name = "fee_settlement", except(roles(Role::DAO)))]
+    pub fn claim_rewards(&mut self, epochs: Vec<u64>) -> Promise {
+        let reward_token = self
+            .rewards_config
+            .as_ref()
+            .sdk_expect("ERR_REWARDS_NOT_CONFIGURED")
+            .reward_token
+            .clone();
+        let account_id = env::predecessor_account_id();
+
+        let mut claimed_points = Vec::new();
+        let mut amount = SafeAmount::ZERO;
+        for epoch in epochs {
+            require!(
+                self.is_reward_epoch_finished(epoch),
+                "ERR_REWARD_EPOCH_NOT_FINISHED"
+            );
+            let Some(points) = self.reward_points.remove(&(account_id.clone(), epoch)) else {
+                continue;
+            };
+            amount = amount
+                .checked_add(
+                    Self::epoch_reward_share(&self.get_reward_epoch(epoch), points.0).into(),
+                )
+                .sdk_unwrap();
+            claimed_points.push((epoch, points));
+        }
+        require!(amount > SafeAmount::ZERO, "ERR_NO_REWARDS_TO_CLAIM");
+
+        ext_token::ext(reward_token)
+            .with_static_gas(FT_TRANSFER_GAS)
+            .with_attached_deposit(ONE_YOCTO)
+            .ft_transfer(account_id.clone(), amount.into(), None)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(CLAIM_REWARDS_CALLBACK_GAS)
+                    .claim_rewards_callback(account_id, claimed_points),
+            )
+    }
+
+    #[private]
+    pub fn claim_rewards_callback(
+        &mut self,
+        account_id: AccountId,
+        claimed_points: Vec<(u64, U128)>,
+        #[callback_result] call_result: &Result<(), PromiseError>,
+    ) {
+        if call_result.is_err() {
+            for (epoch, points) in claimed_points {
+                self.reward_points
+                    .insert(&(account_id.clone(), epoch), &points);
+            }
+        }
+    }
+}
+
+impl Contract {
+    // Moves the configured share of a protocol fee collected in the reward token to the reward
+    // pool of the current epoch. Returns the part of the fee that is kept as a protocol fee.
+    pub(crate) fn fund_rewards_from_protocol_fee(
+        &mut self,
+        token_id: &AccountId,
+        fee: U128,
+    ) -> U128 {
+        let Some(config) = self.rewards_config.as_ref() else {
+            return fee;
+        };
+        if &config.reward_token != token_id {
+            return fee;
+        }
+        let Some(epoch) = self.current_reward_epoch() else {
+            return fee;
+        };
+        let reward_share = SafeAmount::from(fee)
+            .checked_mul_div(config.protocol_fee_share_bps.into(), BPS_DENOMINATOR.into())
+            .sdk_unwrap();
+        if reward_share == SafeAmount::ZERO {
+            return fee;
+        }
+
+        let mut reward_epoch = self.get_reward_epoch(epoch);
+        reward_epoch.reward_amount = SafeAmount::from(reward_epoch.reward_amount)
+            .checked_add(reward_share)
+            .sdk_unwrap()
+            .into();
+        self.reward_epochs.insert(&epoch, &reward_epoch);
+
+        U128(fee.0.saturating_sub(reward_share.get()))
+    }
+
+    // Accrues the points of a transfer initiated on NEAR once it is finalised on the destination
+    // chain, so the cancelled and refunded transfers earn nothing. A record needs a finalised
+    // transfer above the minimum amount, so the points are stored at the expense of the contract.
+    pub(crate) fn record_reward_points(&mut self, transfer_message: &TransferMessage) {
+        let (OmniAddress::Near(account_id), OmniAddress::Near(token_id)) =
+            (&transfer_message.sender, &transfer_message.token)
+        else {
+            return;
+        };
+        let Some(epoch) = self.current_reward_epoch() else {
+            return;
+        };
+        let Some(rewarded_token) = self.rewarded_tokens.get(token_id) else {
+            return;
+        };
+        let amount = transfer_message.amount;
+        if amount.0 < rewarded_token.min_transfer_amount.0 {
+            return;
+        }
+        let points = amount.0 / rewarded_token.amount_per_point.0;
+        if points == 0 {
+            return;
+        }
+
+        let key = (account_id.clone(), epoch);
+        let account_points = self.reward_points.get(&key).unwrap_or_default();
+        let account_points = SafeAmount::from(account_points)
+            .checked_add(points.into())
+            .sdk_unwrap();
+        self.reward_points.insert(&key, &account_points.into());
+
+        let mut reward_epoch = self.get_reward_epoch(epoch);
+        reward_epoch.total_points = SafeAmount::from(reward_epoch.total_points)
+            .checked_add(points.into())
+            .sdk_unwrap()
+            .into();
+        self.reward_epochs.insert(&epoch, &reward_epoch);
+    }
+
+    fn current_reward_epoch(&self) -> Option<u64> {
+        let config = self.rewards_config.as_ref()?;
+        Some(env::block_timestamp() / config.epoch_duration.0)
+    }
+
+    fn is_reward_epoch_finished(&self, epoch: u64) -> bool {
+        self.current_reward_epoch()
+            .is_none_or(|current_epoch| epoch < current_epoch)
+    }
+
+    fn epoch_reward_share(reward_epoch: &RewardEpoch, points: u128) -> u128 {
+        let total_points = reward_epoch.total_points.0;
+        if total_points == 0 {
+            return 0;
+        }
+        SafeAmount::from(reward_epoch.reward_amount)
+            .checked_mul_div(points, total_points)
+            .sdk_unwrap()
+            .get()
+    }
+}
@@ -0,0 +1,53 @@
+use near_plugins::AccessControllable;
+use near_sdk::{near, AccountId};
+
+use crate::helpers::SdkExpect;
+use crate::{Contract, ContractExt};
+
+const MAX_ROLE_MEMBERS_PAGE: u64 = 100;
+
+#[near]
+impl Contract {
+    /// Returns the accounts holding the role, e.g. `DAO` or `Guardian`. The grantees are read from the enumerable
+    /// storage of the access control plugin, so no separate index has to be kept in sync.
+    pub fn get_role_members(
+        &self,
+        role: String,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<AccountId> {
+        let limit = limit
+            .unwrap_or(MAX_ROLE_MEMBERS_PAGE)
+            .min(MAX_ROLE_MEMBERS_PAGE);
+        self.acl_get_grantees(role, from_index.unwrap_or_default(), limit)
+    }
+
+    pub fn get_role_members_count(&self, role: String) -> u64 {
+        self.count_role_members(role)
+    }
+
+    /// Returns the number of accounts holding each role.
+    pub fn get_role_member_counts(&self) -> Vec<(String, u64)> {
+        self.acl_role_variants()
+            .into_iter()
+            .map(|role| (role.to_string(), self.count_role_members(role.to_string())))
+            .collect()
+    }
+}
+
+impl Contract {
+    fn count_role_members(&self, role: String) -> u64 {
+        let mut count = 0;
+        loop {
+            let page: u64 = self
+                .acl_get_grantees(role.clone(), count, MAX_ROLE_MEMBERS_PAGE)
+                .len()
+                .try_into()
+                .sdk_expect("ERR_CAST");
+            count += page;
+            if page < MAX_ROLE_MEMBERS_PAGE {
+                return count;
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use near_sdk::{near, require};
+use omni_types::{ChainKind, OmniAddress};
+
+use crate::{Contract, ContractExt};
+
+// Limits of the NEAR runtime config
+const MAX_ARGUMENTS_LENGTH: u32 = 4 * 1024 * 1024;
+const MAX_TOTAL_LOG_LENGTH: u32 = 16 * 1024;
+// Room for the other arguments of the call and the other fields of the event
+const ARGUMENTS_OVERHEAD: u32 = 64 * 1024;
+const EVENT_OVERHEAD: u32 = 4 * 1024;
+
+/// Largest messages and proofs of a route that fit the pipeline of the transfer. Past them the
+/// transfers fail in a later receipt with the errors of the runtime.
+#[near(serializers=[json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteLimits {
+    /// Size of the `msg` of the transfers initiated on NEAR. The msg is logged with the events
+    /// of the transfer, and is empty on the chains without memos.
+    pub max_transfer_msg_size: u32,
+    /// Size of the proofs of the transfers, `None` for the transfers from NEAR.
+    pub max_proof_size: Option<u32>,
+    /// Withdraw messages submitted to the connector of the destination UTXO chain.
+    pub max_withdraw_msg_size: Option<u32>,
+    pub max_withdraw_inputs: Option<u32>,
+    pub max_withdraw_outputs: Option<u32>,
+}
+
+#[near]
+impl Contract {
+    pub fn get_route_limits(
+        &self,
+        origin_chain: ChainKind,
+        destination_chain: ChainKind,
+    ) -> RouteLimits {
+        let traits = self.get_chain_traits(destination_chain);
+        let (max_withdraw_msg_size, max_withdraw_inputs, max_withdraw_outputs) = if traits.is_utxo {
+            let limits = self.get_utxo_withdraw_limits(destination_chain);
+            (
+                Some(self.max_withdraw_msg_size(destination_chain)),
+                limits.max_inputs,
+                limits.max_outputs,
+            )
+        } else {
+            (None, None, None)
+        };
+
+        RouteLimits {
+            max_transfer_msg_size: self.max_transfer_msg_size(destination_chain),
+            max_proof_size: (origin_chain != ChainKind::Near)
+                .then_some(MAX_ARGUMENTS_LENGTH - ARGUMENTS_OVERHEAD),
+            max_withdraw_msg_size,
+            max_withdraw_inputs,
+            max_withdraw_outputs,
+        }
+    }
+}
+
+impl Contract {
+    pub(crate) fn max_transfer_msg_size(&self, destination_chain: ChainKind) -> u32 {
+        let traits = self.get_chain_traits(destination_chain);
+        if traits.is_utxo || traits.supports_memo {
+            MAX_TOTAL_LOG_LENGTH - EVENT_OVERHEAD
+        } else {
+            0
+        }
+    }
+
+    // The Withdraw message is passed to the connector in the arguments of `ft_transfer_call`.
+    pub(crate) fn max_withdraw_msg_size(&self, chain_kind: ChainKind) -> u32 {
+        let max_msg_size = MAX_ARGUMENTS_LENGTH - ARGUMENTS_OVERHEAD;
+        self.get_utxo_withdraw_limits(chain_kind)
+            .max_msg_size
+            .map_or(max_msg_size, |limit| limit.min(max_msg_size))
+    }
+
+    pub(crate) fn check_transfer_msg_size(&self, recipient: &OmniAddress, msg: Option<&str>) {
+        let max_msg_size = self.max_transfer_msg_size(recipient.get_chain());
+        require!(
+            msg.is_none_or(|msg| u32::try_from(msg.len()).is_ok_and(|len| len <= max_msg_size)),
+            "ERR_TRANSFER_MSG_TOO_LARGE"
+        );
+    }
+
+    pub(crate) fn check_proof_size(prover_args: &[u8]) {
+        require!(
+            u32::try_from(prover_args.len())
+                .is_ok_and(|len| len <= MAX_ARGUMENTS_LENGTH - ARGUMENTS_OVERHEAD),
+            "ERR_PROOF_TOO_LARGE"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use near_sdk::json_types::U128;
+use near_sdk::{near, AccountId};
+use omni_types::{ChainKind, OmniAddress};
+
+use crate::{Contract, ContractExt};
+
+// Chains whose tokens are registered without a connector. The UTXO chains registered by the DAO
+// are taken from their connectors.
+pub(crate) const BUILTIN_CHAINS: [ChainKind; 11] = [
+    ChainKind::Near,
+    ChainKind::Eth,
+    ChainKind::Sol,
+    ChainKind::Arb,
+    ChainKind::Base,
+    ChainKind::Bnb,
+    ChainKind::Pol,
+    ChainKind::Btc,
+    ChainKind::Zcash,
+    ChainKind::Ltc,
+    ChainKind::Doge,
+];
+
+/// Corridor of a token from one chain to another. The transfers between two foreign chains
+/// are finalized on NEAR and forwarded to the destination chain.
+#[near(serializers=[json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenRoute {
+    pub origin_chain: ChainKind,
+    pub destination_chain: ChainKind,
+    pub origin_token: OmniAddress,
+    pub destination_token: OmniAddress,
+    pub via_near: bool,
+    pub max_transfer_amount: Option<U128>,
+    pub min_native_relayer_fee: Option<U128>,
+    pub min_token_relayer_fee: Option<U128>,
+    /// Fee deducted from the deposits of the origin UTXO chain credited on NEAR.
+    pub has_inbound_fee: bool,
+    pub extra_msg_required: bool,
+    /// The circuit breaker of the connector of the destination UTXO chain is tripped,
+    /// so the submissions fail until the DAO switches the connector.
+    pub is_connector_tripped: bool,
+    /// Estimated completion time in seconds, see `estimate_completion_time`.
+    pub estimated_completion_time: Option<u64>,
+}
+
+#[near]
+impl Contract {
+    /// Returns the corridors of the token between every pair of chains it is registered on.
+    pub fn get_routes(&self, token: AccountId) -> Vec<TokenRoute> {
+        let deployments = self.token_deployments(&token);
+
+        let mut routes = Vec::new();
+        for (origin_chain, origin_token) in &deployments {
+            for (destination_chain, destination_token) in &deployments {
+                if origin_chain == destination_chain {
+                    continue;
+                }
+                routes.push(TokenRoute {
+                    origin_chain: *origin_chain,
+                    destination_chain: *destination_chain,
+                    origin_token: origin_token.clone(),
+                    destination_token: destination_token.clone(),
+                    via_near: *origin_chain != ChainKind::Near
+                        && *destination_chain != ChainKind::Near,
+                    max_transfer_amount: self.max_transfer_amounts.get(&token),
+                    min_native_relayer_fee: self.min_native_relayer_fees.get(destination_chain),
+                    min_token_relayer_fee: self
+                        .min_token_relayer_fees
+                        .get(&(*destination_chain, token.clone())),
+                    has_inbound_fee: self.inbound_fees.get(origin_chain).is_some(),
+                    extra_msg_required: self.is_extra_msg_required(*destination_chain),
+                    is_connector_tripped: self.is_chain_connector_tripped(*destination_chain),
+                    estimated_completion_time: self
+                        .estimate_completion_time(*origin_chain, *destination_chain),
+                });
+            }
+        }
+        routes
+    }
+}
+
+impl Contract {
+    // Returns the builtin chains followed by the UTXO chains registered by the DAO.
+    pub(crate) fn known_chains(&self) -> Vec<ChainKind> {
+        let mut registered_chains: Vec<ChainKind> = self
+            .utxo_chain_connectors
+            .keys()
+            .filter(|chain_kind| !BUILTIN_CHAINS.contains(chain_kind))
+            .copied()
+            .collect();
+        registered_chains.sort();
+        let mut chains = BUILTIN_CHAINS.to_vec();
+        chains.extend(registered_chains);
+        chains
+    }
+
+    fn token_deployments(&self, token: &AccountId) -> Vec<(ChainKind, OmniAddress)> {
+        self.known_chains()
+            .into_iter()
+            .filter_map(|chain_kind| {
+                if chain_kind == ChainKind::Near {
+                    return Some((chain_kind, OmniAddress::Near(token.clone())));
+                }
+                self.token_id_to_address
+                    .get(&(chain_kind, token.clone()))
+                    .map(|address| (chain_kind, address))
+            })
+            .collect()
+    }
+
+    pub(crate) fn is_chain_connector_tripped(&self, chain_kind: ChainKind) -> bool {
+        self.chain_connector_ids
+            .get(&chain_kind)
+            .is_some_and(|connector_id| self.is_connector_tripped(chain_kind, connector_id))
+    }
+}
@@ -0,0 +1,75 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::{near, require};
+use omni_types::{ChainKind, OmniAddress, UTXOChainAddress};
+
+use crate::utxo::builtin_utxo_chain_validator;
+use crate::{Contract, ContractExt, Role};
+
+#[near]
+impl Contract {
+    /// In the allowlist mode, transfers to the script-hash (multisig custody) addresses of the chain
+    /// are only accepted for the allowed recipients. Key-hash addresses are not affected.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_script_hash_allowlist_mode(&mut self, chain_kind: ChainKind, enabled: bool) {
+        if enabled {
+            self.script_hash_allowlist_chains.insert(&chain_kind);
+        } else {
+            self.script_hash_allowlist_chains.remove(&chain_kind);
+        }
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn add_allowed_script_hash_recipient(&mut self, recipient: OmniAddress) {
+        let address = recipient
+            .get_utxo_address()
+            .filter(|address| self.is_utxo_script_hash_address(recipient.get_chain(), address));
+        require!(address.is_some(), "ERR_NOT_A_SCRIPT_HASH_ADDRESS");
+        self.allowed_script_hash_recipients.insert(&recipient);
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn remove_allowed_script_hash_recipient(&mut self, recipient: OmniAddress) {
+        self.allowed_script_hash_recipients.remove(&recipient);
+    }
+
+    pub fn is_script_hash_allowlist_mode(&self, chain_kind: ChainKind) -> bool {
+        self.script_hash_allowlist_chains.contains(&chain_kind)
+    }
+
+    pub fn is_allowed_script_hash_recipient(&self, recipient: OmniAddress) -> bool {
+        self.allowed_script_hash_recipients.contains(&recipient)
+    }
+}
+
+impl Contract {
+    pub(crate) fn check_script_hash_recipient(&self, recipient: &OmniAddress) {
+        let Some(address) = recipient.get_utxo_address() else {
+            return;
+        };
+        let chain_kind = recipient.get_chain();
+        if !self.script_hash_allowlist_chains.contains(&chain_kind)
+            || !self.is_utxo_script_hash_address(chain_kind, &address)
+        {
+            return;
+        }
+        require!(
+            self.allowed_script_hash_recipients.contains(recipient),
+            "ERR_SCRIPT_HASH_RECIPIENT_NOT_ALLOWED"
+        );
+    }
+
+    // Recognizes P2SH and P2WSH addresses by the script kinds of the built-in address formats,
+    // the scripts themselves are validated by the connector of the chain. Used for the chains
+    // without configured address formats.
+    pub(crate) fn is_script_hash_address(
+        chain_kind: ChainKind,
+        address: &UTXOChainAddress,
+    ) -> bool {
+        builtin_utxo_chain_validator(chain_kind).is_some_and(|validator| {
+            validator
+                .default_address_formats()
+                .iter()
+                .any(|format| format.is_script_hash() && format.matches(address))
+        })
+    }
+}
@@ -0,0 +1,67 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::{env, near, require, Promise};
+use omni_types::mpc_types::{SignatureRequest, SignatureScheme};
+use omni_types::{ChainKind, SignRequest};
+
+use crate::external::ext_signer;
+use crate::{Contract, ContractExt, Role, MPC_SIGNING_GAS, SIGN_PATH};
+
+#[near]
+impl Contract {
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_signature_scheme(&mut self, chain_kind: ChainKind, scheme: SignatureScheme) {
+        require!(
+            Self::is_signature_scheme_supported(scheme),
+            "ERR_UNSUPPORTED_SIGNATURE_SCHEME"
+        );
+        if scheme == SignatureScheme::default() {
+            self.signature_schemes.remove(&chain_kind);
+        } else {
+            self.signature_schemes.insert(&chain_kind, &scheme);
+        }
+    }
+
+    pub fn get_signature_scheme(&self, chain_kind: ChainKind) -> SignatureScheme {
+        self.signature_schemes.get(&chain_kind).unwrap_or_default()
+    }
+}
+
+impl Contract {
+    // Builds the request for a message verified on the destination chain.
+    pub(crate) fn build_signature_request(
+        &self,
+        chain_kind: ChainKind,
+        message: &[u8],
+    ) -> SignatureRequest {
+        SignatureRequest::new(
+            self.get_signature_scheme(chain_kind),
+            message,
+            SIGN_PATH.to_owned(),
+        )
+    }
+
+    // Sends the request to the signer in the format of its interface for the scheme.
+    pub(crate) fn request_signature(&self, request: SignatureRequest) -> Promise {
+        let payload = match request.scheme {
+            SignatureScheme::Secp256k1Ecdsa => <[u8; 32]>::try_from(request.payload)
+                .unwrap_or_else(|_| env::panic_str("ERR_INVALID_SIGNATURE_PAYLOAD")),
+            SignatureScheme::Ed25519 | SignatureScheme::Secp256k1Schnorr => {
+                env::panic_str("ERR_UNSUPPORTED_SIGNATURE_SCHEME")
+            }
+        };
+
+        ext_signer::ext(self.mpc_signer.clone())
+            .with_static_gas(MPC_SIGNING_GAS)
+            .with_attached_deposit(env::attached_deposit())
+            .sign(SignRequest {
+                payload,
+                path: request.path,
+                key_version: 0,
+            })
+    }
+
+    // The signer only produces secp256k1 ECDSA signatures so far
+    const fn is_signature_scheme_supported(scheme: SignatureScheme) -> bool {
+        matches!(scheme, SignatureScheme::Secp256k1Ecdsa)
+    }
+}
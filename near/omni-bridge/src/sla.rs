@@ -0,0 +1,252 @@
+use near_sdk::json_types::U64;
+use near_sdk::{borsh, env, near, AccountId, NearToken};
+use omni_types::{ChainKind, TransferId};
+
+use crate::helpers::SdkExpect;
+use crate::{Contract, ContractExt};
+
+const LATENCY_BUCKET_DURATION_NS: u64 = 60 * 60 * 1_000_000_000;
+const MAX_LATENCY_WINDOW_BUCKETS: u64 = 30 * 24;
+// Weight of the latest transfer in the rolling latency estimate of the route, in 1/256
+const ROUTE_LATENCY_SMOOTHING: u128 = 32;
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferTimestamps {
+    pub initiated_at: U64,
+    pub submitted_at: Option<U64>,
+    pub finalized_at: Option<U64>,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LatencyBucket {
+    pub transfers: u64,
+    pub total_latency: u128,
+    pub max_latency: u64,
+}
+
+/// Exponential moving average of the latency of the transfers between two chains.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RouteLatency {
+    pub estimate: U64,
+    pub transfers: u64,
+}
+
+#[near(serializers=[json])]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LatencyStats {
+    pub transfers: u64,
+    pub average_latency: U64,
+    pub max_latency: U64,
+}
+
+#[near]
+impl Contract {
+    pub fn get_transfer_timestamps(&self, transfer_id: TransferId) -> Option<TransferTimestamps> {
+        self.transfer_timestamps.get(&transfer_id)
+    }
+
+    /// Returns the latency between the initiation and the finalization of the transfers to the
+    /// destination chain that were finalized during the last `window` nanoseconds.
+    /// The latency is aggregated in hourly buckets, and the window is capped to 30 days.
+    pub fn get_latency_stats(&self, chain_kind: ChainKind, window: U64) -> LatencyStats {
+        let current_bucket = Self::current_latency_bucket();
+        let buckets = window
+            .0
+            .div_ceil(LATENCY_BUCKET_DURATION_NS)
+            .clamp(1, MAX_LATENCY_WINDOW_BUCKETS);
+
+        let total = (current_bucket.saturating_sub(buckets - 1)..=current_bucket)
+            .filter_map(|bucket| self.latency_buckets.get(&(chain_kind, bucket)))
+            .fold(LatencyBucket::default(), |total, bucket| LatencyBucket {
+                transfers: total.transfers + bucket.transfers,
+                total_latency: total.total_latency.saturating_add(bucket.total_latency),
+                max_latency: total.max_latency.max(bucket.max_latency),
+            });
+
+        if total.transfers == 0 {
+            return LatencyStats::default();
+        }
+        LatencyStats {
+            transfers: total.transfers,
+            average_latency: U64(
+                u64::try_from(total.total_latency / u128::from(total.transfers))
+                    .unwrap_or(u64::MAX),
+            ),
+            max_latency: U64(total.max_latency),
+        }
+    }
+
+    /// Returns the estimated time in seconds between the initiation of a transfer on the origin
+    /// chain and its finalization on the destination chain, based on the recent transfers
+    /// of the route. `None` if no transfer of the route was finalized yet.
+    pub fn estimate_completion_time(
+        &self,
+        origin_chain: ChainKind,
+        destination_chain: ChainKind,
+    ) -> Option<u64> {
+        self.route_latencies
+            .get(&(origin_chain, destination_chain))
+            .map(|latency| latency.estimate.0.div_ceil(1_000_000_000))
+    }
+
+    pub fn get_route_latency(
+        &self,
+        origin_chain: ChainKind,
+        destination_chain: ChainKind,
+    ) -> Option<RouteLatency> {
+        self.route_latencies.get(&(origin_chain, destination_chain))
+    }
+
+    /// Removes the timestamps of the transfers finalized before the longest latency window.
+    /// The records are paid by the contract since the finalisation, so the freed storage stays
+    /// with the contract. Returns the maintenance rebate credited to the caller.
+    pub fn prune_transfer_timestamps(&mut self, transfer_ids: Vec<TransferId>) -> NearToken {
+        let min_finalized_at = env::block_timestamp()
+            .saturating_sub(MAX_LATENCY_WINDOW_BUCKETS * LATENCY_BUCKET_DURATION_NS);
+        let mut pruned_records = 0;
+        for transfer_id in transfer_ids {
+            let is_expired = self
+                .transfer_timestamps
+                .get(&transfer_id)
+                .and_then(|timestamps| timestamps.finalized_at)
+                .is_some_and(|finalized_at| finalized_at.0 < min_finalized_at);
+            if is_expired {
+                self.transfer_timestamps.remove(&transfer_id);
+                pruned_records += 1;
+            }
+        }
+        self.pay_maintenance_rebate(pruned_records)
+    }
+}
+
+impl Contract {
+    // Returns the storage cost of the record.
+    pub(crate) fn record_transfer_initiated(&mut self, transfer_id: &TransferId) -> NearToken {
+        let storage_usage = env::storage_usage();
+        self.transfer_timestamps.insert(
+            transfer_id,
+            &TransferTimestamps {
+                initiated_at: U64(env::block_timestamp()),
+                submitted_at: None,
+                finalized_at: None,
+            },
+        );
+        env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into())
+    }
+
+    pub(crate) fn record_transfer_submitted(&mut self, transfer_id: &TransferId) {
+        let Some(mut timestamps) = self.transfer_timestamps.get(transfer_id) else {
+            return;
+        };
+        if timestamps.submitted_at.is_none() {
+            timestamps.submitted_at = Some(U64(env::block_timestamp()));
+            self.transfer_timestamps.insert(transfer_id, &timestamps);
+        }
+    }
+
+    // The owner paid for the record at the initiation. Once the transfer is finalised the record
+    // is only kept until it leaves the latency window, at the expense of the contract, so its
+    // storage is refunded to the owner.
+    pub(crate) fn record_transfer_finalized(
+        &mut self,
+        transfer_id: &TransferId,
+        destination_chain: ChainKind,
+        owner: &AccountId,
+    ) {
+        let Some(mut timestamps) = self.transfer_timestamps.get(transfer_id) else {
+            return;
+        };
+        if timestamps.finalized_at.is_some() {
+            return;
+        }
+        let finalized_at = env::block_timestamp();
+        timestamps.finalized_at = Some(U64(finalized_at));
+        self.transfer_timestamps.insert(transfer_id, &timestamps);
+        if let Some(mut storage) = self.accounts_balances.get(owner) {
+            storage.available = storage
+                .available
+                .saturating_add(Self::required_balance_for_transfer_timestamps(transfer_id));
+            self.accounts_balances.insert(owner, &storage);
+        }
+
+        let latency = finalized_at.saturating_sub(timestamps.initiated_at.0);
+        let key = (destination_chain, Self::current_latency_bucket());
+        let mut bucket = self.latency_buckets.get(&key).unwrap_or_default();
+        bucket.transfers += 1;
+        bucket.total_latency = bucket.total_latency.saturating_add(latency.into());
+        bucket.max_latency = bucket.max_latency.max(latency);
+        self.latency_buckets.insert(&key, &bucket);
+
+        self.update_route_latency(transfer_id.origin_chain, destination_chain, latency);
+    }
+
+    // Removes the record of a transfer that ends without being finalised, e.g. when it is
+    // cancelled, and refunds its storage to the owner.
+    pub(crate) fn remove_transfer_timestamps(
+        &mut self,
+        transfer_id: &TransferId,
+        owner: &AccountId,
+    ) {
+        let storage_usage = env::storage_usage();
+        if self.transfer_timestamps.remove(transfer_id).is_none() {
+            return;
+        }
+        let refund = env::storage_byte_cost()
+            .saturating_mul((storage_usage.saturating_sub(env::storage_usage())).into());
+        if let Some(mut storage) = self.accounts_balances.get(owner) {
+            storage.available = storage.available.saturating_add(refund);
+            self.accounts_balances.insert(owner, &storage);
+        }
+    }
+
+    fn update_route_latency(
+        &mut self,
+        origin_chain: ChainKind,
+        destination_chain: ChainKind,
+        latency: u64,
+    ) {
+        let key = (origin_chain, destination_chain);
+        let route_latency = self.route_latencies.get(&key).map_or(
+            RouteLatency {
+                estimate: U64(latency),
+                transfers: 1,
+            },
+            |route_latency| {
+                let estimate = (u128::from(route_latency.estimate.0)
+                    * (256 - ROUTE_LATENCY_SMOOTHING)
+                    + u128::from(latency) * ROUTE_LATENCY_SMOOTHING)
+                    / 256;
+                RouteLatency {
+                    estimate: U64(u64::try_from(estimate).unwrap_or(u64::MAX)),
+                    transfers: route_latency.transfers + 1,
+                }
+            },
+        );
+        self.route_latencies.insert(&key, &route_latency);
+    }
+
+    pub(crate) fn required_balance_for_transfer_timestamps(transfer_id: &TransferId) -> NearToken {
+        let record_len: u64 = borsh::to_vec(&(
+            transfer_id,
+            TransferTimestamps {
+                initiated_at: U64(0),
+                submitted_at: None,
+                finalized_at: None,
+            },
+        ))
+        .sdk_expect("ERR_BORSH")
+        .len()
+        .try_into()
+        .sdk_expect("ERR_CAST");
+
+        env::storage_byte_cost().saturating_mul((Self::get_basic_storage() + record_len).into())
+    }
+
+    fn current_latency_bucket() -> u64 {
+        env::block_timestamp() / LATENCY_BUCKET_DURATION_NS
+    }
+}
@@ -1,8 +1,10 @@
 use near_contract_standards::storage_management::{StorageBalance, StorageBalanceBounds};
+use near_sdk::json_types::U64;
 use near_sdk::{assert_one_yocto, borsh, near, PromiseOrValue};
 use near_sdk::{env, near_bindgen, AccountId, NearToken};
 use omni_types::{FastTransferStatus, Nonce, TransferId, TransferIdKind, UnifiedTransferId};
 
+use crate::origin_senders::MAX_ORIGIN_SENDERS;
 use crate::{
     require, ChainKind, Contract, ContractExt, Fee, OmniAddress, Promise, SdkExpect,
     TransferMessage, U128,
@@ -37,6 +39,20 @@ pub struct TransferMessageV1 {
     pub origin_transfer_id: Option<TransferId>,
 }
 
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone)]
+pub struct TransferMessageV2 {
+    pub origin_nonce: Nonce,
+    pub token: OmniAddress,
+    pub amount: U128,
+    pub recipient: OmniAddress,
+    pub fee: Fee,
+    pub sender: OmniAddress,
+    pub msg: String,
+    pub destination_nonce: Nonce,
+    pub origin_transfer_id: Option<UnifiedTransferId>,
+}
+
 #[near(serializers=[borsh, json])]
 #[derive(Debug, Clone)]
 pub struct TransferMessageStorageValueV0 {
@@ -51,6 +67,13 @@ pub struct TransferMessageStorageValueV1 {
     pub owner: AccountId,
 }
 
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone)]
+pub struct TransferMessageStorageValueV2 {
+    pub message: TransferMessageV2,
+    pub owner: AccountId,
+}
+
 #[near(serializers=[borsh, json])]
 #[derive(Debug, Clone)]
 pub struct TransferMessageStorageValue {
@@ -64,7 +87,8 @@ pub struct TransferMessageStorageValue {
 pub enum TransferMessageStorage {
     V0(TransferMessageStorageValueV0),
     V1(TransferMessageStorageValueV1),
-    V2(TransferMessageStorageValue),
+    V2(TransferMessageStorageValueV2),
+    V3(TransferMessageStorageValue),
 }
 
 impl TransferMessageStorage {
@@ -81,6 +105,7 @@ impl TransferMessageStorage {
                     msg: m.message.msg,
                     destination_nonce: m.message.destination_nonce,
                     origin_transfer_id: None,
+                    expires_at: None,
                 },
                 owner: m.owner,
             },
@@ -98,10 +123,26 @@ impl TransferMessageStorage {
                         origin_chain: m.origin_chain,
                         kind: TransferIdKind::Nonce(m.origin_nonce),
                     }),
+                    expires_at: None,
                 },
                 owner: m.owner,
             },
-            Self::V2(m) => m,
+            Self::V2(m) => TransferMessageStorageValue {
+                message: TransferMessage {
+                    origin_nonce: m.message.origin_nonce,
+                    token: m.message.token,
+                    amount: m.message.amount,
+                    recipient: m.message.recipient,
+                    fee: m.message.fee,
+                    sender: m.message.sender,
+                    msg: m.message.msg,
+                    destination_nonce: m.message.destination_nonce,
+                    origin_transfer_id: m.message.origin_transfer_id,
+                    expires_at: None,
+                },
+                owner: m.owner,
+            },
+            Self::V3(m) => m,
         }
     }
 
@@ -109,7 +150,7 @@ impl TransferMessageStorage {
         message: TransferMessage,
         owner: AccountId,
     ) -> Result<Vec<u8>, std::io::Error> {
-        borsh::to_vec(&Self::V2(TransferMessageStorageValue { message, owner }))
+        borsh::to_vec(&Self::V3(TransferMessageStorageValue { message, owner }))
     }
 }
 
@@ -317,6 +358,7 @@ impl Contract {
                     }
                 }),
             }),
+            expires_at: Some(U64(0)),
         })
     }
 
@@ -326,14 +368,16 @@ impl Contract {
     ) -> NearToken {
         let max_account_id: AccountId = "a".repeat(64).parse().sdk_expect("ERR_PARSE_ACCOUNT_ID");
 
-        let key_len: u64 = borsh::to_vec(&transfer_message.get_transfer_id())
+        let transfer_id = transfer_message.get_transfer_id();
+        let destination_chain = transfer_message.get_destination_chain();
+        let key_len: u64 = borsh::to_vec(&transfer_id)
             .sdk_expect("ERR_BORSH")
             .len()
             .try_into()
             .sdk_expect("ERR_CAST");
 
         let value_len: u64 =
-            borsh::to_vec(&TransferMessageStorage::V2(TransferMessageStorageValue {
+            borsh::to_vec(&TransferMessageStorage::V3(TransferMessageStorageValue {
                 message: transfer_message,
                 owner: max_account_id,
             }))
@@ -344,6 +388,13 @@ impl Contract {
 
         env::storage_byte_cost()
             .saturating_mul((Self::get_basic_storage() + key_len + value_len).into())
+            .saturating_add(Self::required_balance_for_transfer_timestamps(&transfer_id))
+            .saturating_add(
+                self.required_balance_for_pending_transfer_index(destination_chain, &transfer_id),
+            )
+            .saturating_add(Self::required_balance_for_pending_transfer_owner_index(
+                &transfer_id,
+            ))
     }
 
     pub fn required_balance_for_fin_transfer(&self) -> NearToken {
@@ -359,8 +410,24 @@ impl Contract {
         .try_into()
         .sdk_expect("ERR_CAST");
 
-        let storage_cost =
-            env::storage_byte_cost().saturating_mul((Self::get_basic_storage() + key_len).into());
+        let max_account_id: AccountId = "a".repeat(64).parse().sdk_expect("ERR_PARSE_ACCOUNT_ID");
+        let origin_senders_len: u64 = borsh::to_vec(&(
+            UnifiedTransferId {
+                origin_chain: ChainKind::Eth,
+                kind: TransferIdKind::Utxo(omni_types::UtxoId {
+                    tx_hash: "a".repeat(64),
+                    vout: 0,
+                }),
+            },
+            vec![OmniAddress::Near(max_account_id); MAX_ORIGIN_SENDERS],
+        ))
+        .sdk_expect("ERR_BORSH")
+        .len()
+        .try_into()
+        .sdk_expect("ERR_CAST");
+
+        let storage_cost = env::storage_byte_cost()
+            .saturating_mul((2 * Self::get_basic_storage() + key_len + origin_senders_len).into());
         let ft_transfers_cost = NearToken::from_yoctonear(2);
 
         storage_cost.saturating_add(ft_transfers_cost)
@@ -421,7 +488,7 @@ impl Contract {
             .saturating_add(NEP141_DEPOSIT)
     }
 
-    const fn get_basic_storage() -> u64 {
+    pub(crate) const fn get_basic_storage() -> u64 {
         const EXTRA_BYTES_RECORD: u64 = 40;
         const EXTRA_KEY_PREFIX_LEN: u64 = 1;
         EXTRA_BYTES_RECORD + EXTRA_KEY_PREFIX_LEN
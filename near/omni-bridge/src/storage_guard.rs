@@ -0,0 +1,59 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::U128;
+use near_sdk::{env, near, require, NearToken};
+use omni_types::near_events::OmniBridgeEvent;
+
+use crate::{Contract, ContractExt, Role};
+
+// The alert is emitted once the available balance drops below this multiple of the safety margin
+const STORAGE_ALERT_MARGIN_MULTIPLIER: u128 = 2;
+
+#[near]
+impl Contract {
+    /// Sets the minimum balance the contract has to keep on top of its storage staking.
+    /// New transfers are rejected once the available balance falls below it. Zero disables the guard.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_storage_safety_margin(&mut self, margin: NearToken) {
+        self.storage_safety_margin = margin;
+    }
+
+    pub fn get_storage_safety_margin(&self) -> NearToken {
+        self.storage_safety_margin
+    }
+
+    /// Returns the balance of the contract that is not locked for the storage staking.
+    pub fn get_contract_available_balance(&self) -> NearToken {
+        let storage_staking = env::storage_byte_cost().saturating_mul(env::storage_usage().into());
+        env::account_balance().saturating_sub(storage_staking)
+    }
+}
+
+impl Contract {
+    pub(crate) fn require_storage_safety_margin(&self) {
+        require!(
+            self.get_contract_available_balance() >= self.storage_safety_margin,
+            "ERR_CONTRACT_AVAILABLE_BALANCE_TOO_LOW"
+        );
+    }
+
+    pub(crate) fn alert_if_available_balance_low(&self) {
+        if self.storage_safety_margin.is_zero() {
+            return;
+        }
+
+        let available_balance = self.get_contract_available_balance();
+        if available_balance
+            < self
+                .storage_safety_margin
+                .saturating_mul(STORAGE_ALERT_MARGIN_MULTIPLIER)
+        {
+            env::log_str(
+                &OmniBridgeEvent::LowAvailableBalanceEvent {
+                    available_balance: U128(available_balance.as_yoctonear()),
+                    safety_margin: U128(self.storage_safety_margin.as_yoctonear()),
+                }
+                .to_log_string(),
+            );
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use near_plugins::{pause, Pausable};
+use near_sdk::json_types::U64;
+use near_sdk::{env, near, require, AccountId};
+use omni_types::TransferId;
+
+use crate::{Contract, ContractExt, Role};
+
+const SUBMISSION_WINDOW_NS: u64 = 10 * 60 * 1_000_000_000;
+
+/// Entitlement of a relayer to the fees of a transfer it registered to submit.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmissionIntent {
+    pub relayer: AccountId,
+    /// Until then no other relayer can take over the transfer.
+    pub window_ends_at: U64,
+}
+
+#[near]
+impl Contract {
+    pub fn get_submission_intent(&self, transfer_id: TransferId) -> Option<SubmissionIntent> {
+        self.submission_intents.get(&transfer_id)
+    }
+
+    /// Claims the fees of the pending transfer for the caller if the transfer is submitted
+    /// before its submission window ends, whichever account submits it, so the retries of the
+    /// submission from other accounts don't redirect the fees. Another relayer can take over
+    /// the transfer once the window ends, but the caller can't renew its own window. The storage of the record is
+    /// paid by the caller and refunded when the transfer is forwarded or the window expires.
+    #[payable]
+    #[pause(except(roles(Role::DAO, Role::UnrestrictedRelayer)))]
+    pub fn register_submission_intent(&mut self, transfer_id: TransferId) -> SubmissionIntent {
+        require!(
+            self.pending_transfers.get(&transfer_id).is_some(),
+            "ERR_TRANSFER_NOT_EXIST"
+        );
+        self.require_preferred_relayer(&transfer_id);
+        let relayer = env::predecessor_account_id();
+        let now = env::block_timestamp();
+        if let Some(intent) = self.submission_intents.get(&transfer_id) {
+            require!(intent.relayer != relayer, "ERR_SUBMISSION_INTENT_RENEWAL");
+            require!(
+                now >= intent.window_ends_at.0,
+                "ERR_SUBMISSION_WINDOW_CLAIMED"
+            );
+        }
+        self.remove_submission_intent(&transfer_id);
+
+        let intent = SubmissionIntent {
+            relayer: relayer.clone(),
+            window_ends_at: U64(now.saturating_add(SUBMISSION_WINDOW_NS)),
+        };
+        let storage_usage = env::storage_usage();
+        self.submission_intents.insert(&transfer_id, &intent);
+        let required_balance = env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into());
+        self.update_storage_balance(relayer, required_balance, env::attached_deposit());
+
+        intent
+    }
+}
+
+impl Contract {
+    // Returns the holder of the intent when the transfer is submitted within its window. The
+    // expired intent is dropped at the submission.
+    pub(crate) fn take_entitled_relayer(&mut self, transfer_id: &TransferId) -> Option<AccountId> {
+        let intent = self.submission_intents.get(transfer_id)?;
+        if env::block_timestamp() >= intent.window_ends_at.0 {
+            self.remove_submission_intent(transfer_id);
+            return None;
+        }
+        Some(intent.relayer)
+    }
+
+    // Removes the intent and returns its storage to the relayer.
+    pub(crate) fn remove_submission_intent(&mut self, transfer_id: &TransferId) {
+        let storage_usage = env::storage_usage();
+        let Some(intent) = self.submission_intents.remove(transfer_id) else {
+            return;
+        };
+        let refund = env::storage_byte_cost()
+            .saturating_mul((storage_usage.saturating_sub(env::storage_usage())).into());
+
+        if let Some(mut storage) = self.accounts_balances.get(&intent.relayer) {
+            storage.available = storage.available.saturating_add(refund);
+            self.accounts_balances.insert(&intent.relayer, &storage);
+        }
+    }
+}
@@ -2,20 +2,68 @@ use std::collections::HashMap;
 use std::str::FromStr;
 
 use near_contract_standards::storage_management::StorageBalance;
+use near_plugins::{AccessControllable, Pausable};
 use near_sdk::{
-    borsh, json_types::U128, serde_json, test_utils::VMContextBuilder, test_vm_config, testing_env,
-    AccountId, NearToken, PromiseOrValue, PromiseResult, RuntimeFeesConfig,
+    borsh,
+    json_types::{Base58CryptoHash, U128, U64},
+    serde_json,
+    test_utils::{get_logs, VMContextBuilder},
+    test_vm_config, testing_env, AccountId, NearToken, PromiseError, PromiseOrValue, PromiseResult,
+    RuntimeFeesConfig,
 };
 use omni_types::{
+    btc::{AddressEncoding, OutPoint, ScriptKind, TxOut, UTXOChainConfig, UtxoAddressFormat},
+    errors::BridgeError,
     locker_args::StorageDepositAction,
-    prover_result::{InitTransferMessage, ProverResult},
+    near_events::OmniBridgeEvent,
+    prover_result::{
+        GovernanceActionMessage, IncomingMessage, InitTransferMessage, ProverResult,
+        UnresolvedRecipientTransferMessage,
+    },
     sol_address::SolAddress,
-    BridgeOnTransferMsg, ChainKind, EvmAddress, Fee, InitTransferMsg, Nonce, OmniAddress,
-    TransferId, TransferMessage, UpdateFee,
+    test_vectors::WITHDRAW_MSG_VECTORS,
+    BridgeOnTransferMsg, ChainKind, ChainTraits, EvmAddress, EvmDestinationCall, Fee,
+    InitTransferMsg, Nonce, OmniAddress, SubmitOutcome, SubmitStatus, TransferBeneficiary,
+    TransferId, TransferMessage, UpdateFee, UtxoFinTransferMsg, UtxoId,
 };
 
+use crate::bootstrap::BootstrapStage;
+use crate::btc::UtxoWithdrawLimits;
+use crate::cancellation::TransferBeneficiaryRecord;
+use crate::claim_records::{ClaimEvidence, TransferClaimRecord, UnclaimedTransfer};
+use crate::connector_failover::{ConnectorHealth, ConnectorInFlight, FailoverPolicy};
+use crate::connector_version::{
+    CONSOLIDATION_MIN_CONNECTOR_INTERFACE_VERSION, CUSTODY_REPORTS_MIN_CONNECTOR_INTERFACE_VERSION,
+};
+use crate::custody_utxos::CustodyUtxo;
+use crate::event_emission::{EventCounter, EventEmissionPolicy};
+use crate::fee_escalation::{FeeEscalation, FeeEscalationPolicy, FeeEscalationStep};
+use crate::fee_ledger::{FeeAsset, FEE_SETTLEMENT_FEATURE};
+use crate::fee_recipient::FeeRecipientStrategy;
+use crate::fee_sponsors::{FeeSponsor, FeeSponsorLimits};
+use crate::force_finalize::ForceFinalizeProposal;
+use crate::governance::GovernanceAction;
+use crate::inbound_fees::InboundFee;
+use crate::locked::LockedState;
+use crate::maintenance::MaintenanceConfig;
+use crate::metrics::MetricId;
+use crate::origin_senders::MAX_ORIGIN_SENDERS;
+use crate::orphans::OrphanCollection;
+use crate::proof_validation::ProofVerdict;
+use crate::quota::TransferQuota;
+use crate::rewards::{RewardedToken, RewardsConfig};
+use crate::sla::{RouteLatency, TransferTimestamps};
 use crate::storage::Decimals;
-use crate::Contract;
+use crate::transfer_caps::OversizeTransferAllowance;
+use crate::unclaimed_deposits::UnclaimedDeposit;
+use crate::utxo::UtxoFeeModel;
+use crate::utxo_address_formats::UtxoAddressFormatTable;
+use crate::utxo_batches::UtxoBatchTransfer;
+use crate::utxo_fee_rates::{UtxoFeeRate, MAX_UTXO_FEE_RATE_AGE};
+use crate::utxo_key_epochs::UtxoKeyEpoch;
+use crate::utxo_refunds::UtxoRefundPolicy;
+use crate::watchtowers::{Watchtower, WatchtowerConfig};
+use crate::{Contract, Role};
 
 const DEFAULT_NONCE: Nonce = 0;
 const DEFAULT_TRANSFER_ID: TransferId = TransferId {
@@ -56,12 +104,26 @@ fn setup_test_env(
 }
 
 fn setup_contract(mpc_signer_id: String, wnear_id: String) -> Contract {
+    let mut contract = setup_bootstrapping_contract(mpc_signer_id, wnear_id);
+    contract.bootstrap = None;
+    contract
+}
+
+fn setup_bootstrapping_contract(mpc_signer_id: String, wnear_id: String) -> Contract {
     Contract::new(
         AccountId::try_from(mpc_signer_id).expect("Invalid default mpc signer ID"),
         AccountId::try_from(wnear_id).expect("Invalid default wnear ID"),
     )
 }
 
+fn setup_dao_env() {
+    setup_test_env(
+        "dao.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+}
+
 fn get_default_contract() -> Contract {
     setup_contract(
         DEFAULT_MPC_SIGNER_ACCOUNT.to_string(),
@@ -84,6 +146,17 @@ fn get_init_transfer_msg(recipient: &str, fee: u128, native_token_fee: u128) ->
         fee: U128(fee),
         native_token_fee: U128(native_token_fee),
         msg: None,
+        beneficiary: None,
+        destination_call: None,
+        preferred_relayer: None,
+        expires_at: None,
+    }
+}
+
+fn get_outpoint(vout: u32) -> OutPoint {
+    OutPoint {
+        txid: [1; 32],
+        vout,
     }
 }
 
@@ -109,105 +182,5376 @@ fn run_ft_on_transfer(
     run_storage_deposit(contract, sender_id.clone(), attached_deposit);
     setup_test_env(token_id.clone(), NearToken::from_yoctonear(0), None);
 
-    let msg = serde_json::to_string(msg).expect("Failed to serialize transfer message");
+    let msg = serde_json::to_string(msg).expect("Failed to serialize transfer message");
+
+    contract.ft_on_transfer(sender_id, amount, msg);
+}
+
+fn run_ft_on_transfer_legacy(
+    contract: &mut Contract,
+    sender_id: String,
+    token_id: String,
+    amount: U128,
+    attached_deposit: Option<NearToken>,
+    msg: &InitTransferMsg,
+) {
+    let sender_id = AccountId::try_from(sender_id).expect("Invalid sender ID");
+    let token_id = AccountId::try_from(token_id).expect("Invalid token ID");
+
+    let attached_deposit = if let Some(deposit) = attached_deposit {
+        deposit
+    } else {
+        let min_storage_balance = contract.required_balance_for_account();
+        let init_transfer_balance = contract.required_balance_for_init_transfer(None);
+        min_storage_balance.saturating_add(init_transfer_balance)
+    };
+
+    run_storage_deposit(contract, sender_id.clone(), attached_deposit);
+    setup_test_env(token_id.clone(), NearToken::from_yoctonear(0), None);
+
+    let msg = serde_json::to_string(msg).expect("Failed to serialize transfer message");
+
+    contract.ft_on_transfer(sender_id, amount, msg);
+}
+
+#[test]
+fn test_initialize_contract() {
+    let contract = get_default_contract();
+
+    assert_eq!(contract.mpc_signer, DEFAULT_MPC_SIGNER_ACCOUNT);
+    assert_eq!(contract.current_origin_nonce, DEFAULT_NONCE);
+    assert_eq!(contract.wnear_account_id, DEFAULT_WNEAR_ACCOUNT);
+}
+
+#[test]
+fn test_init_transfer_nonce_increment() {
+    let mut contract = get_default_contract();
+
+    run_ft_on_transfer(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(100),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0)),
+    );
+
+    assert_eq!(contract.current_origin_nonce, DEFAULT_NONCE + 1);
+}
+
+#[test]
+fn test_init_transfer_stored_transfer_message() {
+    let mut contract = get_default_contract();
+
+    let msg = get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0);
+    run_ft_on_transfer(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(msg.clone()),
+    );
+
+    let stored_transfer = contract.get_transfer_message(TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    });
+    assert_eq!(
+        stored_transfer.recipient, msg.recipient,
+        "Incorrect stored recipient"
+    );
+    assert_eq!(stored_transfer.fee.fee, msg.fee, "Incorrect stored fee");
+    assert_eq!(
+        stored_transfer.fee.native_fee, msg.native_token_fee,
+        "Incorrect stored native fee"
+    );
+    assert_eq!(
+        stored_transfer.sender,
+        OmniAddress::Near(DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap()),
+        "Incorrect stored sender"
+    );
+    assert_eq!(
+        stored_transfer.token,
+        OmniAddress::Near(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap()),
+        "Incorrect stored token"
+    );
+    assert_eq!(
+        stored_transfer.amount,
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        "Incorrect stored amount"
+    );
+}
+
+#[test]
+fn test_init_transfer_locked_breakdown() {
+    let mut contract = get_default_contract();
+
+    init_default_transfer(&mut contract);
+
+    let breakdown = contract.get_locked_breakdown(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap());
+    assert_eq!(breakdown.pending, U128(DEFAULT_TRANSFER_AMOUNT));
+    assert_eq!(breakdown.in_flight, U128(0));
+
+    contract.remove_transfer_message(TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    });
+    let breakdown = contract.get_locked_breakdown(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap());
+    assert_eq!(breakdown.pending, U128(0));
+}
+
+#[test]
+fn test_restore_transfer_moves_to_dead_letter_after_bound() {
+    let mut contract = get_default_contract();
+
+    let transfer_id = init_default_transfer(&mut contract);
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+
+    for restore_count in 1..=contract.max_transfer_restores {
+        let transfer_message = contract.remove_transfer_message(transfer_id);
+        contract.restore_transfer(transfer_message, owner.clone(), true);
+        assert_eq!(
+            contract.get_transfer_restore_count(transfer_id),
+            restore_count
+        );
+        assert!(contract.pending_transfers.get(&transfer_id).is_some());
+    }
+
+    let transfer_message = contract.remove_transfer_message(transfer_id);
+    contract.restore_transfer(transfer_message, owner, true);
+
+    assert!(contract.pending_transfers.get(&transfer_id).is_none());
+    assert!(contract.get_dead_letter_transfer(transfer_id).is_some());
+    assert_eq!(contract.get_transfer_restore_count(transfer_id), 0);
+}
+
+#[test]
+fn test_resolve_fee_recipient_per_chain() {
+    let mut contract = get_default_contract();
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+    contract
+        .fee_recipient_strategies
+        .insert(&ChainKind::Btc, &FeeRecipientStrategy::ConnectorReported);
+    setup_test_env(relayer.clone(), NearToken::from_yoctonear(0), None);
+
+    let fee_recipient: AccountId = "fee_recipient.testnet".parse().unwrap();
+    assert_eq!(
+        contract.resolve_fee_recipient(
+            ChainKind::Eth,
+            &DEFAULT_TRANSFER_ID,
+            Some(fee_recipient.clone())
+        ),
+        Some(fee_recipient)
+    );
+    assert_eq!(
+        contract.resolve_fee_recipient(ChainKind::Eth, &DEFAULT_TRANSFER_ID, None),
+        Some(relayer.clone())
+    );
+    assert_eq!(
+        contract.resolve_fee_recipient(ChainKind::Btc, &DEFAULT_TRANSFER_ID, Some(relayer)),
+        None
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_CONTRACT_AVAILABLE_BALANCE_TOO_LOW")]
+fn test_init_transfer_below_storage_safety_margin() {
+    let mut contract = get_default_contract();
+    contract.storage_safety_margin = NearToken::from_near(1_000_000_000);
+
+    init_default_transfer(&mut contract);
+}
+
+fn setup_rewards(contract: &mut Contract) {
+    contract.rewards_config = Some(RewardsConfig {
+        reward_token: DEFAULT_WNEAR_ACCOUNT.parse().unwrap(),
+        epoch_duration: U64(1_000_000_000),
+        protocol_fee_share_bps: 2_500,
+    });
+    contract.rewarded_tokens.insert(
+        &DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        &RewardedToken {
+            min_transfer_amount: U128(DEFAULT_TRANSFER_AMOUNT),
+            amount_per_point: U128(10),
+        },
+    );
+}
+
+#[test]
+fn test_finalised_transfer_accrues_reward_points() {
+    let mut contract = get_default_contract();
+    setup_rewards(&mut contract);
+    let epoch = contract.get_current_reward_epoch().unwrap();
+
+    let transfer_id = init_default_transfer(&mut contract);
+    // Below the minimum transfer amount
+    run_ft_on_transfer(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT - 1),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0)),
+    );
+    let small_transfer_id = TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    };
+    // The initiated transfers accrue no points before they are finalised
+    assert_eq!(contract.get_reward_epoch(epoch).total_points, U128(0));
+
+    for transfer_id in [transfer_id, small_transfer_id] {
+        let transfer_message = contract.remove_transfer_message(transfer_id);
+        contract.submit_transfer_to_btc_connector_callback(
+            transfer_message.clone(),
+            DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+            Some("relayer.testnet".parse().unwrap()),
+            Vec::new(),
+            None,
+            None,
+            None,
+            &Ok(transfer_message.amount),
+        );
+    }
+
+    assert_eq!(
+        contract.get_reward_points(DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(), epoch),
+        U128(DEFAULT_TRANSFER_AMOUNT / 10)
+    );
+    assert_eq!(
+        contract.get_reward_epoch(epoch).total_points,
+        U128(DEFAULT_TRANSFER_AMOUNT / 10)
+    );
+}
+
+#[test]
+fn test_protocol_fee_share_funds_rewards() {
+    let mut contract = get_default_contract();
+    setup_rewards(&mut contract);
+    let epoch = contract.get_current_reward_epoch().unwrap();
+    let reward_token: AccountId = DEFAULT_WNEAR_ACCOUNT.parse().unwrap();
+
+    contract.collect_protocol_fee(&reward_token, U128(1_000));
+    // The fees in other tokens are not shared
+    contract.collect_protocol_fee(&DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(), U128(1_000));
+
+    assert_eq!(contract.get_reward_epoch(epoch).reward_amount, U128(250));
+    assert_eq!(contract.get_protocol_fees(reward_token), U128(750));
+    assert_eq!(
+        contract.get_protocol_fees(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap()),
+        U128(1_000)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Pausable: Method is paused")]
+fn test_claim_rewards_paused_with_fee_settlement() {
+    let mut contract = get_default_contract();
+    setup_rewards(&mut contract);
+    pause_fee_settlement(&mut contract);
+
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.claim_rewards(vec![0]).detach();
+}
+
+#[test]
+fn test_init_transfer_fee_paid_by_sponsor() {
+    let mut contract = get_default_contract();
+    let sponsor_key = (
+        OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+        DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+    );
+    contract.fee_sponsors.insert(
+        &sponsor_key,
+        &FeeSponsor {
+            owner: DEFAULT_WNEAR_ACCOUNT.parse().unwrap(),
+            balance: U128(150),
+            limits: FeeSponsorLimits {
+                fee_per_transfer: U128(100),
+                min_transfer_amount: U128(0),
+                max_daily_fees: None,
+            },
+            period: 0,
+            spent_in_period: U128(0),
+        },
+    );
+
+    let sponsored_transfer = contract.get_transfer_message(init_default_transfer(&mut contract));
+    assert_eq!(sponsored_transfer.fee.fee, U128(100));
+    assert_eq!(
+        sponsored_transfer.amount,
+        U128(DEFAULT_TRANSFER_AMOUNT + 100)
+    );
+
+    // The remaining balance doesn't cover the fee of the next transfer
+    let unsponsored_transfer = contract.get_transfer_message(init_default_transfer(&mut contract));
+    assert_eq!(unsponsored_transfer.fee.fee, U128(0));
+    assert_eq!(unsponsored_transfer.amount, U128(DEFAULT_TRANSFER_AMOUNT));
+
+    let sponsor = contract.fee_sponsors.get(&sponsor_key).unwrap();
+    assert_eq!(sponsor.balance, U128(50));
+    assert_eq!(sponsor.spent_in_period, U128(100));
+}
+
+#[test]
+fn test_set_transfer_labels() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_millinear(10),
+        None,
+    );
+    let labels = vec!["batch-42".to_string(), "desk-a".to_string()];
+    contract.set_transfer_labels(transfer_id, labels.clone());
+    assert_eq!(contract.get_transfer_labels(transfer_id), labels);
+
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.set_transfer_labels(transfer_id, Vec::new());
+    assert!(contract.get_transfer_labels(transfer_id).is_empty());
+}
+
+#[test]
+fn test_pending_transfers_indexed_by_destination_chain() {
+    let mut contract = get_default_contract();
+    for _ in 0..2 {
+        run_ft_on_transfer(
+            &mut contract,
+            DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+            DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+            U128(DEFAULT_TRANSFER_AMOUNT),
+            None,
+            &BridgeOnTransferMsg::InitTransfer(get_init_transfer_msg(
+                DEFAULT_ETH_USER_ADDRESS,
+                0,
+                0,
+            )),
+        );
+    }
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    };
+
+    assert_eq!(contract.get_pending_transfers_count(ChainKind::Eth), 2);
+    assert!(contract
+        .get_pending_transfers(ChainKind::Sol, None, None)
+        .is_empty());
+    let pending_transfers = contract.get_pending_transfers(ChainKind::Eth, Some(1), None);
+    assert_eq!(pending_transfers.len(), 1);
+    assert_eq!(pending_transfers[0].0, transfer_id);
+
+    contract.remove_transfer_message(transfer_id);
+    assert_eq!(contract.get_pending_transfers_count(ChainKind::Eth), 1);
+}
+
+#[test]
+fn test_get_all_pending_transfers() {
+    let mut contract = get_default_contract();
+    let mut arb_transfer_msg = get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 10, 0);
+    arb_transfer_msg.recipient =
+        OmniAddress::Arb(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+    for msg in [
+        arb_transfer_msg,
+        get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0),
+        get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0),
+    ] {
+        run_ft_on_transfer(
+            &mut contract,
+            DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+            DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+            U128(DEFAULT_TRANSFER_AMOUNT),
+            None,
+            &BridgeOnTransferMsg::InitTransfer(msg),
+        );
+    }
+
+    let first_page = contract.get_all_pending_transfers(U64(0), 2);
+    assert_eq!(first_page.len(), 2);
+    assert!(first_page
+        .iter()
+        .all(|transfer| transfer.destination_chain == ChainKind::Eth));
+
+    let second_page = contract.get_all_pending_transfers(U64(2), 2);
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(
+        second_page[0].transfer_id,
+        TransferId {
+            origin_chain: ChainKind::Near,
+            origin_nonce: 1,
+        }
+    );
+    assert_eq!(second_page[0].destination_chain, ChainKind::Arb);
+    assert_eq!(
+        second_page[0].owner,
+        DEFAULT_NEAR_USER_ACCOUNT.parse::<AccountId>().unwrap()
+    );
+    assert_eq!(second_page[0].amount, U128(DEFAULT_TRANSFER_AMOUNT));
+    assert_eq!(second_page[0].fee.fee, U128(10));
+
+    assert!(contract.get_all_pending_transfers(U64(3), 2).is_empty());
+}
+
+#[test]
+fn test_pending_transfers_indexed_by_owner() {
+    let mut contract = get_default_contract();
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    for _ in 0..2 {
+        init_default_transfer(&mut contract);
+    }
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    };
+    assert_eq!(
+        contract
+            .get_transfers_by_owner(owner.clone(), None, None)
+            .len(),
+        2
+    );
+
+    let new_owner: AccountId = "custody.testnet".parse().unwrap();
+    run_storage_deposit(&mut contract, new_owner.clone(), NearToken::from_near(1));
+    setup_test_env(owner.clone(), NearToken::from_millinear(10), None);
+    contract.transfer_ownership(transfer_id, new_owner.clone());
+
+    let pending_transfers = contract.get_transfers_by_owner(new_owner.clone(), None, None);
+    assert_eq!(pending_transfers.len(), 1);
+    assert_eq!(pending_transfers[0].0, transfer_id);
+    assert_eq!(
+        contract
+            .get_transfers_by_owner(owner.clone(), Some(1), None)
+            .len(),
+        0
+    );
+
+    contract.remove_transfer_message(transfer_id);
+    assert!(contract
+        .get_transfers_by_owner(new_owner.clone(), None, None)
+        .is_empty());
+    assert!(contract
+        .pending_transfers_by_owner
+        .get(&new_owner)
+        .is_none());
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_CANCELLATION_SIGNATURE")]
+fn test_cancel_transfer_invalid_signature() {
+    let mut contract = get_default_contract();
+    let mut msg = get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0);
+    msg.beneficiary = Some(TransferBeneficiary {
+        account_id: DEFAULT_WNEAR_ACCOUNT.parse().unwrap(),
+        public_key: "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+            .parse()
+            .unwrap(),
+    });
+    run_ft_on_transfer(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        Some(NearToken::from_near(1)),
+        &BridgeOnTransferMsg::InitTransfer(msg),
+    );
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    };
+    assert!(contract.get_transfer_beneficiary(transfer_id).is_some());
+
+    contract.cancel_transfer(transfer_id, vec![0; 64].into());
+}
+
+#[test]
+fn test_utxo_submission_keeps_transfer_beneficiary_until_forwarded() {
+    let mut contract = get_default_contract();
+    let mut msg = get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0);
+    msg.beneficiary = Some(TransferBeneficiary {
+        account_id: DEFAULT_WNEAR_ACCOUNT.parse().unwrap(),
+        public_key: "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+            .parse()
+            .unwrap(),
+    });
+    run_ft_on_transfer(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        Some(NearToken::from_near(1)),
+        &BridgeOnTransferMsg::InitTransfer(msg),
+    );
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    };
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+
+    let transfer = contract.take_transfer_message(transfer_id);
+    let outcome = contract.submit_transfer_to_btc_connector_callback(
+        transfer.message,
+        owner.clone(),
+        None,
+        Vec::new(),
+        None,
+        Some(owner.clone()),
+        None,
+        &Ok(U128(0)),
+    );
+    assert!(outcome.restored);
+    assert!(contract.get_transfer_beneficiary(transfer_id).is_some());
+
+    let transfer = contract.take_transfer_message(transfer_id);
+    contract.submit_transfer_to_btc_connector_callback(
+        transfer.message.clone(),
+        owner.clone(),
+        Some("relayer.testnet".parse().unwrap()),
+        Vec::new(),
+        None,
+        Some(owner),
+        None,
+        &Ok(transfer.message.amount),
+    );
+    assert!(contract.get_transfer_beneficiary(transfer_id).is_none());
+}
+
+#[test]
+fn test_prune_transfer_timestamps_pays_maintenance_rebate() {
+    let mut contract = get_default_contract();
+    let keeper: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    contract.maintenance_config = Some(MaintenanceConfig {
+        rebate_per_tgas: NearToken::from_millinear(1),
+        max_rebate_per_item: NearToken::from_millinear(5),
+        max_rebate_per_call: NearToken::from_millinear(5),
+    });
+    contract.maintenance_budget = NearToken::from_millinear(3);
+    contract.accounts_balances.insert(
+        &keeper,
+        &StorageBalance {
+            total: NearToken::from_yoctonear(0),
+            available: NearToken::from_yoctonear(0),
+        },
+    );
+    contract.transfer_timestamps.insert(
+        &DEFAULT_TRANSFER_ID,
+        &TransferTimestamps {
+            initiated_at: U64(0),
+            submitted_at: None,
+            finalized_at: Some(U64(0)),
+        },
+    );
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(keeper.clone())
+        .block_timestamp(31 * 24 * 60 * 60 * 1_000_000_000)
+        .build());
+
+    // Nothing to prune, so nothing is paid
+    assert!(contract
+        .prune_transfer_timestamps(vec![TransferId {
+            origin_chain: ChainKind::Near,
+            origin_nonce: DEFAULT_NONCE + 1,
+        }])
+        .is_zero());
+
+    let rebate = contract.prune_transfer_timestamps(vec![DEFAULT_TRANSFER_ID]);
+    assert!(contract
+        .get_transfer_timestamps(DEFAULT_TRANSFER_ID)
+        .is_none());
+    assert!(rebate <= NearToken::from_millinear(3));
+    assert_eq!(
+        contract.get_maintenance_budget(),
+        NearToken::from_millinear(3).saturating_sub(rebate)
+    );
+    assert_eq!(
+        contract.accounts_balances.get(&keeper).unwrap().available,
+        rebate
+    );
+}
+
+#[test]
+fn test_validate_proof_verdicts() {
+    let contract = get_default_contract();
+    assert_eq!(
+        contract.validate_proof_callback(Err(PromiseError::Failed)),
+        ProofVerdict::InvalidProof
+    );
+
+    let emitter_address = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+    let init_transfer = InitTransferMessage {
+        origin_nonce: DEFAULT_NONCE,
+        token: emitter_address.clone(),
+        amount: U128(DEFAULT_TRANSFER_AMOUNT),
+        recipient: OmniAddress::Near(DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap()),
+        fee: Fee::default(),
+        sender: emitter_address.clone(),
+        msg: String::new(),
+        emitter_address,
+    };
+    assert_eq!(
+        contract.validate_proof_callback(Ok(ProverResult::InitTransfer(init_transfer))),
+        ProofVerdict::UnknownFactory
+    );
+}
+
+#[test]
+fn test_is_script_hash_address() {
+    assert!(Contract::is_script_hash_address(
+        ChainKind::Btc,
+        &"3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy".to_string()
+    ));
+    assert!(Contract::is_script_hash_address(
+        ChainKind::Btc,
+        &"bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3".to_string()
+    ));
+    assert!(!Contract::is_script_hash_address(
+        ChainKind::Btc,
+        &"bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string()
+    ));
+    assert!(!Contract::is_script_hash_address(
+        ChainKind::Btc,
+        &"1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2".to_string()
+    ));
+    assert!(Contract::is_script_hash_address(
+        ChainKind::Zcash,
+        &"t3Vz22vK5z2LcKEdg16Yv4FFneEL1zg9ojd".to_string()
+    ));
+}
+
+#[test]
+#[should_panic(expected = "ERR_SCRIPT_HASH_RECIPIENT_NOT_ALLOWED")]
+fn test_init_transfer_to_not_allowed_script_hash_recipient() {
+    let mut contract = get_default_contract();
+    contract
+        .script_hash_allowlist_chains
+        .insert(&ChainKind::Btc);
+
+    let mut msg = get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0);
+    msg.recipient = OmniAddress::Btc("3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy".to_string());
+    run_ft_on_transfer(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(msg),
+    );
+}
+
+fn get_btc_address_formats() -> Vec<UtxoAddressFormat> {
+    vec![
+        UtxoAddressFormat {
+            encoding: AddressEncoding::Base58,
+            prefix: "3".to_string(),
+            min_len: 34,
+            max_len: 34,
+            script_kind: ScriptKind::P2sh,
+        },
+        UtxoAddressFormat {
+            encoding: AddressEncoding::Bech32,
+            prefix: "bc1q".to_string(),
+            min_len: 42,
+            max_len: 42,
+            script_kind: ScriptKind::P2wpkh,
+        },
+    ]
+}
+
+#[test]
+fn test_set_utxo_address_formats() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    contract.set_utxo_address_formats(ChainKind::Btc, get_btc_address_formats());
+    contract.set_utxo_address_formats(ChainKind::Btc, get_btc_address_formats());
+
+    let table = contract.get_utxo_address_formats(ChainKind::Btc).unwrap();
+    assert_eq!(table.version, 2);
+    assert_eq!(table.formats, get_btc_address_formats());
+    // The configured formats replace the built-in script-hash prefixes
+    assert!(contract.is_utxo_script_hash_address(
+        ChainKind::Btc,
+        &"3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy".to_string()
+    ));
+    assert!(!contract.is_utxo_script_hash_address(
+        ChainKind::Btc,
+        &"2N2JD6wb56AfK4tfmM6PwdVmoYk2dCKf4Br".to_string()
+    ));
+
+    contract.set_utxo_address_formats(ChainKind::Btc, Vec::new());
+    assert!(contract.get_utxo_address_formats(ChainKind::Btc).is_none());
+}
+
+#[test]
+#[should_panic(expected = "ERR_UNSUPPORTED_ADDRESS_FORMAT")]
+fn test_init_transfer_to_unsupported_address_format() {
+    let mut contract = get_default_contract();
+    contract.utxo_address_formats.insert(
+        &ChainKind::Btc,
+        &UtxoAddressFormatTable {
+            version: 1,
+            formats: get_btc_address_formats(),
+        },
+    );
+
+    let mut msg = get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0);
+    msg.recipient = OmniAddress::Btc("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2".to_string());
+    run_ft_on_transfer(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(msg),
+    );
+}
+
+fn run_fin_unresolved_recipient_transfer(contract: &mut Contract) {
+    run_fin_unresolved_recipient_transfer_from(
+        contract,
+        OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+    );
+}
+
+fn run_fin_unresolved_recipient_transfer_from(contract: &mut Contract, sender: OmniAddress) {
+    let chain_kind = sender.get_chain();
+    let token = OmniAddress::Near(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap());
+    contract.factories.insert(&chain_kind, &sender);
+    contract.token_decimals.insert(
+        &token,
+        &Decimals {
+            decimals: 24,
+            origin_decimals: 24,
+        },
+    );
+    contract.unclaimed_deposit_chains.insert(&chain_kind);
+
+    let prover_result =
+        ProverResult::UnresolvedRecipientTransfer(UnresolvedRecipientTransferMessage {
+            origin_nonce: DEFAULT_NONCE,
+            token,
+            amount: U128(DEFAULT_TRANSFER_AMOUNT),
+            raw_recipient: "near:invalid..account".to_string(),
+            fee: Fee::default(),
+            sender: sender.clone(),
+            msg: String::new(),
+            emitter_address: sender,
+        });
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_near(1),
+        Some(vec![PromiseResult::Successful(
+            borsh::to_vec(&prover_result).unwrap(),
+        )]),
+    );
+    contract.fin_transfer_callback(&Vec::new(), DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap());
+}
+
+#[test]
+fn test_fin_transfer_unresolved_recipient_is_escrowed() {
+    let mut contract = get_default_contract();
+    run_fin_unresolved_recipient_transfer(&mut contract);
+
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Eth,
+        origin_nonce: DEFAULT_NONCE,
+    };
+    assert_eq!(
+        contract.get_unclaimed_deposit(transfer_id),
+        Some(UnclaimedDeposit {
+            token_id: DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+            amount: U128(DEFAULT_TRANSFER_AMOUNT),
+            sender: OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+            raw_recipient: "near:invalid..account".to_string(),
+            storage_owner: DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        })
+    );
+    assert!(contract.is_transfer_finalised(transfer_id));
+    assert_eq!(
+        contract
+            .get_locked_breakdown(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap())
+            .escrow,
+        U128(DEFAULT_TRANSFER_AMOUNT)
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_CLAIM_SIGNATURE")]
+fn test_claim_unclaimed_deposit_invalid_signature() {
+    let mut contract = get_default_contract();
+    run_fin_unresolved_recipient_transfer(&mut contract);
+
+    contract.claim_unclaimed_deposit(
+        TransferId {
+            origin_chain: ChainKind::Eth,
+            origin_nonce: DEFAULT_NONCE,
+        },
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        vec![0; 65].into(),
+    );
+}
+
+#[test]
+fn test_transfer_ownership() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+
+    let new_owner: AccountId = "custody.testnet".parse().unwrap();
+    run_storage_deposit(&mut contract, new_owner.clone(), NearToken::from_near(1));
+
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_millinear(10),
+        None,
+    );
+    contract.transfer_ownership(transfer_id, new_owner.clone());
+    assert_eq!(
+        contract.get_transfer_message_storage(transfer_id).owner,
+        new_owner
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_ONLY_TRANSFER_OWNER")]
+fn test_transfer_ownership_not_owner() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+
+    let new_owner: AccountId = "custody.testnet".parse().unwrap();
+    run_storage_deposit(&mut contract, new_owner.clone(), NearToken::from_near(1));
+
+    setup_test_env(new_owner.clone(), NearToken::from_yoctonear(0), None);
+    contract.transfer_ownership(transfer_id, new_owner);
+}
+
+#[test]
+fn test_set_fee_conversion() {
+    let mut contract = get_default_contract();
+    let relayer: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+
+    setup_test_env(relayer.clone(), NearToken::from_millinear(10), None);
+    contract.set_fee_conversion(Some(50));
+    assert_eq!(contract.get_fee_conversion(relayer.clone()), Some(50));
+
+    setup_test_env(relayer.clone(), NearToken::from_yoctonear(0), None);
+    contract.set_fee_conversion(None);
+    assert_eq!(contract.get_fee_conversion(relayer), None);
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_MAX_SLIPPAGE")]
+fn test_set_fee_conversion_invalid_slippage() {
+    let mut contract = get_default_contract();
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_millinear(10),
+        None,
+    );
+    contract.set_fee_conversion(Some(10_001));
+}
+
+const FORCE_FINALIZE_DELAY_NS: u64 = 3 * 24 * 60 * 60 * 1_000_000_000;
+
+fn propose_and_approve_force_finalize(contract: &mut Contract) -> (TransferId, Base58CryptoHash) {
+    let transfer_id = init_default_transfer(contract);
+    let justification_hash = Base58CryptoHash::from([7; 32]);
+
+    setup_dao_env();
+    contract.acl_grant_role(Role::Guardian.into(), "guardian.testnet".parse().unwrap());
+    contract.propose_force_finalize(transfer_id, justification_hash);
+
+    setup_test_env(
+        "guardian.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.approve_force_finalize(transfer_id, justification_hash);
+    (transfer_id, justification_hash)
+}
+
+#[test]
+fn test_force_finalize() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    let (transfer_id, justification_hash) = propose_and_approve_force_finalize(&mut contract);
+    assert_eq!(
+        contract.get_force_finalize_proposal(transfer_id),
+        Some(ForceFinalizeProposal {
+            justification_hash,
+            proposer: "dao.testnet".parse().unwrap(),
+            proposed_at: U64(0),
+            guardian: Some("guardian.testnet".parse().unwrap()),
+        })
+    );
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id("dao.testnet".parse().unwrap())
+        .block_timestamp(FORCE_FINALIZE_DELAY_NS)
+        .build());
+    contract.force_finalize(transfer_id, justification_hash);
+    assert!(contract.pending_transfers.get(&transfer_id).is_none());
+    assert!(contract.get_force_finalize_proposal(transfer_id).is_none());
+}
+
+#[test]
+#[should_panic(expected = "ERR_FORCE_FINALIZE_TIMELOCK_NOT_EXPIRED")]
+fn test_force_finalize_before_timelock() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    let (transfer_id, justification_hash) = propose_and_approve_force_finalize(&mut contract);
+
+    setup_dao_env();
+    contract.force_finalize(transfer_id, justification_hash);
+}
+
+#[test]
+fn test_register_utxo_key_epoch() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    contract.register_utxo_key_epoch(ChainKind::Btc, "bridge-1".to_string(), U64(0));
+    contract.register_utxo_key_epoch(ChainKind::Btc, "bridge-2".to_string(), U64(100));
+
+    let rotation = contract.get_utxo_key_rotation(ChainKind::Btc).unwrap();
+    assert_eq!(
+        rotation.current,
+        UtxoKeyEpoch {
+            epoch: 1,
+            derivation_path: "bridge-2".to_string(),
+        }
+    );
+    assert_eq!(
+        rotation.previous,
+        Some(UtxoKeyEpoch {
+            epoch: 0,
+            derivation_path: "bridge-1".to_string(),
+        })
+    );
+
+    // Both epochs can be spent before the cutover
+    contract.add_legacy_outpoints(ChainKind::Btc, vec![get_outpoint(0)]);
+    contract.check_withdraw_key_epoch(ChainKind::Btc, &[get_outpoint(0)]);
+}
+
+#[test]
+#[should_panic(expected = "ERR_LEGACY_EPOCH_OUTPOINT")]
+fn test_withdraw_legacy_outpoint_after_cutover() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    contract.register_utxo_key_epoch(ChainKind::Btc, "bridge-1".to_string(), U64(0));
+    contract.register_utxo_key_epoch(ChainKind::Btc, "bridge-2".to_string(), U64(100));
+    contract.add_legacy_outpoints(ChainKind::Btc, vec![get_outpoint(0)]);
+
+    testing_env!(VMContextBuilder::new().block_height(100).build());
+    contract.check_withdraw_key_epoch(ChainKind::Btc, &[get_outpoint(1)]);
+    contract.check_withdraw_key_epoch(ChainKind::Btc, &[get_outpoint(0)]);
+}
+
+#[test]
+#[should_panic(expected = "ERR_FEE_EXCEEDS_AMOUNT")]
+fn test_submit_transfer_fee_exceeds_amount() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+
+    // Malformed stored state with a fee above the amount
+    let mut transfer = contract.get_transfer_message_storage(transfer_id);
+    transfer.message.fee.fee = U128(DEFAULT_TRANSFER_AMOUNT + 1);
+    contract.insert_raw_transfer(transfer.message, transfer.owner);
+
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.submit_transfer_to_utxo_chain_connector(
+        transfer_id,
+        r#"{"Withdraw":{"target_btc_address":"","input":[],"output":[],"max_gas_fee":null}}"#
+            .to_string(),
+        None,
+        &None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_AMOUNT_OVERFLOW")]
+fn test_lock_amount_overflow() {
+    let mut contract = get_default_contract();
+    let token: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+
+    contract.lock_amount(&token, LockedState::Pending, u128::MAX);
+    assert_eq!(
+        contract.get_locked_breakdown(token.clone()).pending,
+        U128(u128::MAX)
+    );
+
+    contract.move_locked_amount(&token, LockedState::Pending, LockedState::InFlight, 1);
+    contract.lock_amount(&token, LockedState::Pending, 2);
+}
+
+#[test]
+fn test_migrate_utxo_chain_connector() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    let old_connector: AccountId = "old-connector.testnet".parse().unwrap();
+    let new_connector: AccountId = "new-connector.testnet".parse().unwrap();
+    contract.utxo_chain_connectors.insert(
+        ChainKind::Btc,
+        UTXOChainConfig {
+            connector: old_connector.clone(),
+            token_id: "btc.testnet".parse().unwrap(),
+        },
+    );
+
+    // The connector added before the registry is registered on first use
+    let connector_id = contract.chain_connector_id(ChainKind::Btc);
+    assert_eq!(contract.resolve_connector(connector_id), old_connector);
+
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: DEFAULT_NONCE,
+    };
+    contract
+        .unreported_fee_connectors
+        .insert(&transfer_id, &connector_id);
+
+    contract.migrate_utxo_chain_connector(ChainKind::Btc, new_connector.clone());
+
+    assert_eq!(
+        contract.get_chain_connector_id(ChainKind::Btc),
+        Some(connector_id)
+    );
+    assert_eq!(
+        contract.get_utxo_chain_connector(ChainKind::Btc),
+        new_connector
+    );
+    // The transfer submitted to the old account resolves to the migrated connector
+    assert_eq!(
+        contract.resolve_transfer_connector(&transfer_id, ChainKind::Btc),
+        new_connector
+    );
+}
+
+#[test]
+fn test_inbound_fee_amount() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    let recipient: AccountId = "recipient.testnet".parse().unwrap();
+    let market_maker: AccountId = "market-maker.testnet".parse().unwrap();
+    contract.set_inbound_fee(
+        ChainKind::Btc,
+        Some(InboundFee {
+            fee_bps: 10,
+            flat_fee: U128(1_000),
+        }),
+    );
+    contract.add_inbound_fee_exempt_account(market_maker.clone());
+
+    assert_eq!(
+        contract.get_inbound_fee_amount(ChainKind::Btc, &recipient, U128(1_000_000)),
+        U128(2_000)
+    );
+    // The fee never exceeds the deposit
+    assert_eq!(
+        contract.get_inbound_fee_amount(ChainKind::Btc, &recipient, U128(500)),
+        U128(500)
+    );
+    assert_eq!(
+        contract.get_inbound_fee_amount(ChainKind::Btc, &market_maker, U128(1_000_000)),
+        U128(0)
+    );
+    assert_eq!(
+        contract.get_inbound_fee_amount(ChainKind::Zcash, &recipient, U128(1_000_000)),
+        U128(0)
+    );
+
+    let token_id: AccountId = "nbtc.testnet".parse().unwrap();
+    contract.accrue_protocol_fee(&token_id, U128(2_000));
+    assert_eq!(contract.get_protocol_fees(token_id.clone()), U128(2_000));
+    assert_eq!(
+        contract.get_locked_breakdown(token_id).fee_pool,
+        U128(2_000)
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_FEE_BPS")]
+fn test_set_inbound_fee_invalid_bps() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    contract.set_inbound_fee(
+        ChainKind::Btc,
+        Some(InboundFee {
+            fee_bps: 10_001,
+            flat_fee: U128(0),
+        }),
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_TRANSFER_AMOUNT_ABOVE_CAP")]
+fn test_init_transfer_above_cap() {
+    let mut contract = get_default_contract();
+    contract.max_transfer_amounts.insert(
+        &DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        &U128(DEFAULT_TRANSFER_AMOUNT - 1),
+    );
+
+    init_default_transfer(&mut contract);
+}
+
+#[test]
+fn test_init_transfer_above_cap_with_allowance() {
+    let mut contract = get_default_contract();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+    let account_id: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    contract
+        .max_transfer_amounts
+        .insert(&token_id, &U128(DEFAULT_TRANSFER_AMOUNT - 1));
+    contract.oversize_transfer_allowances.insert(
+        &(account_id.clone(), token_id.clone()),
+        &OversizeTransferAllowance {
+            amount: U128(DEFAULT_TRANSFER_AMOUNT),
+            expires_at: U64(u64::MAX),
+        },
+    );
+
+    init_default_transfer(&mut contract);
+
+    // The allowance is consumed by the transfer
+    assert!(contract
+        .get_oversize_transfer_allowance(account_id, token_id)
+        .is_none());
+    assert_eq!(contract.current_origin_nonce, DEFAULT_NONCE + 1);
+}
+
+#[test]
+fn test_auditor_notified_on_locked_changes() {
+    let mut contract = get_default_contract();
+    let token: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+
+    // Nothing is recorded without an auditor
+    contract.lock_amount(&token, LockedState::Pending, 100);
+    assert_eq!(contract.get_last_accounting_seq(), 0);
+
+    contract.auditor = Some("auditor.testnet".parse().unwrap());
+    contract.lock_amount(&token, LockedState::Pending, 100);
+    contract.move_locked_amount(&token, LockedState::Pending, LockedState::InFlight, 50);
+    contract.unlock_amount(&token, LockedState::InFlight, 50);
+    // Zero amounts don't change the funds
+    contract.lock_amount(&token, LockedState::FeePool, 0);
+    assert_eq!(contract.get_last_accounting_seq(), 3);
+}
+
+#[test]
+fn test_connector_failover_on_tripped_circuit_breaker() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    let primary: AccountId = "primary-connector.testnet".parse().unwrap();
+    let standby: AccountId = "standby-connector.testnet".parse().unwrap();
+    contract.utxo_chain_connectors.insert(
+        ChainKind::Btc,
+        UTXOChainConfig {
+            connector: primary.clone(),
+            token_id: "btc.testnet".parse().unwrap(),
+        },
+    );
+    contract.set_standby_connector(ChainKind::Btc, Some(standby.clone()));
+    contract.set_failover_policy(
+        ChainKind::Btc,
+        Some(FailoverPolicy {
+            window: U64(3_600_000_000_000),
+            min_submissions: 2,
+            max_failure_bps: 5_000,
+            auto_failover: true,
+        }),
+    );
+
+    let connector_id = contract.chain_connector_id(ChainKind::Btc);
+    contract.record_connector_submission(connector_id, U128(100));
+    contract.record_connector_submission(connector_id, U128(200));
+    assert_eq!(
+        contract.get_connector_in_flight(connector_id),
+        ConnectorInFlight {
+            transfers: 2,
+            amount: U128(300),
+        }
+    );
+
+    contract.release_connector_in_flight(connector_id, U128(100));
+    contract.record_connector_result(ChainKind::Btc, connector_id, true);
+    assert_eq!(contract.get_utxo_chain_connector(ChainKind::Btc), primary);
+
+    contract.release_connector_in_flight(connector_id, U128(200));
+    contract.record_connector_result(ChainKind::Btc, connector_id, false);
+    assert!(contract.get_connector_health(connector_id).is_tripped);
+    assert_eq!(
+        contract.get_connector_in_flight(connector_id),
+        ConnectorInFlight::default()
+    );
+    assert_eq!(contract.get_utxo_chain_connector(ChainKind::Btc), standby);
+    assert_eq!(
+        contract.get_standby_connector(ChainKind::Btc),
+        Some(primary.clone())
+    );
+    // The transfers submitted before the switchover are still attributed to the old connector
+    assert_eq!(contract.resolve_connector(connector_id), primary);
+    assert_ne!(
+        contract.get_chain_connector_id(ChainKind::Btc),
+        Some(connector_id)
+    );
+}
+
+#[test]
+fn test_unclaimed_transfers_report() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+
+    testing_env!(VMContextBuilder::new()
+        .block_timestamp(2 * 24 * 60 * 60 * 1_000_000_000)
+        .build());
+    assert!(contract
+        .get_unclaimed_transfers(ChainKind::Eth, 3, None, None)
+        .is_empty());
+    assert_eq!(
+        contract.get_unclaimed_transfers(ChainKind::Eth, 2, None, None),
+        vec![UnclaimedTransfer {
+            transfer_id,
+            initiated_at: U64(0),
+        }]
+    );
+
+    assert!(!contract.is_transfer_claimed(transfer_id));
+    contract.remove_transfer_message(transfer_id);
+    contract.record_transfer_claimed(
+        &transfer_id,
+        ChainKind::Eth,
+        ClaimEvidence::DestinationProof,
+    );
+    assert!(contract.is_transfer_claimed(transfer_id));
+    assert_eq!(
+        contract.get_transfer_claim_record(transfer_id),
+        Some(TransferClaimRecord {
+            destination_chain: ChainKind::Eth,
+            claimed_at: U64(2 * 24 * 60 * 60 * 1_000_000_000),
+            evidence: ClaimEvidence::DestinationProof,
+        })
+    );
+    assert!(contract
+        .get_unclaimed_transfers(ChainKind::Eth, 2, None, None)
+        .is_empty());
+}
+
+#[test]
+fn test_relayer_fee_floor() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+    contract.set_min_native_relayer_fee(ChainKind::Eth, Some(U128(1_000)));
+    contract.set_min_token_relayer_fee(ChainKind::Eth, token_id.clone(), Some(U128(10)));
+    assert_eq!(
+        contract.get_min_token_relayer_fee(ChainKind::Eth, token_id.clone()),
+        Some(U128(10))
+    );
+
+    // Either floor is enough
+    contract.check_relayer_fee_floor(ChainKind::Eth, &token_id, U128(0), U128(1_000));
+    contract.check_relayer_fee_floor(ChainKind::Eth, &token_id, U128(10), U128(0));
+    // No floor for the chain
+    contract.check_relayer_fee_floor(ChainKind::Sol, &token_id, U128(0), U128(0));
+}
+
+#[test]
+#[should_panic(expected = "ERR_FEE_BELOW_RELAYER_FEE_FLOOR")]
+fn test_init_transfer_below_relayer_fee_floor() {
+    let mut contract = get_default_contract();
+    contract
+        .min_native_relayer_fees
+        .insert(&ChainKind::Eth, &U128(1_000));
+
+    run_ft_on_transfer(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 1, 999)),
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_EXTRA_MSG_REQUIRED")]
+fn test_init_transfer_without_required_extra_msg() {
+    let mut contract = get_default_contract();
+    contract.extra_msg_required_chains.insert(&ChainKind::Btc);
+
+    let mut msg = get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0);
+    msg.recipient = OmniAddress::Btc("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2".to_string());
+    run_ft_on_transfer(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(msg),
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_EXTRA_MSG_REQUIRED")]
+fn test_submit_transfer_without_required_extra_msg() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+
+    // Transfer initiated before the extra msg was required
+    let btc_address = "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2";
+    let mut transfer = contract.get_transfer_message_storage(transfer_id);
+    transfer.message.recipient = OmniAddress::Btc(btc_address.to_string());
+    contract.insert_raw_transfer(transfer.message, transfer.owner);
+    contract.extra_msg_required_chains.insert(&ChainKind::Btc);
+
+    contract.submit_transfer_to_utxo_chain_connector(
+        transfer_id,
+        format!(
+            r#"{{"Withdraw":{{"target_btc_address":"{btc_address}","input":[],"output":[],"max_gas_fee":null}}}}"#
+        ),
+        None,
+        &None,
+    );
+}
+
+#[test]
+fn test_get_role_members() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    contract.acl_grant_role(Role::Guardian.into(), "guardian1.testnet".parse().unwrap());
+    contract.acl_grant_role(Role::Guardian.into(), "guardian2.testnet".parse().unwrap());
+
+    assert_eq!(
+        contract.get_role_members("Guardian".to_string(), Some(1), None),
+        vec!["guardian2.testnet".parse::<AccountId>().unwrap()]
+    );
+    assert_eq!(contract.get_role_members_count("Guardian".to_string()), 2);
+    assert_eq!(
+        contract.get_role_members_count("RbfOperator".to_string()),
+        0
+    );
+    assert!(contract
+        .get_role_member_counts()
+        .contains(&("Guardian".to_string(), 2)));
+}
+
+#[test]
+#[should_panic(expected = "ERR_NOTICE_NOT_SUPPORTED_FOR_CHAIN")]
+fn test_sign_incoming_transfer_notice_unsupported_chain() {
+    let mut contract = get_default_contract();
+    let transfer_msg = TransferMessage {
+        origin_nonce: DEFAULT_NONCE,
+        token: OmniAddress::Near(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap()),
+        amount: U128(DEFAULT_TRANSFER_AMOUNT),
+        recipient: OmniAddress::Near(DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap()),
+        fee: Fee::default(),
+        sender: OmniAddress::Near(DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap()),
+        msg: String::new(),
+        destination_nonce: 1,
+        origin_transfer_id: None,
+        expires_at: None,
+    };
+    contract.insert_raw_transfer(
+        transfer_msg.clone(),
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+    );
+
+    contract.sign_incoming_transfer_notice(transfer_msg.get_transfer_id());
+}
+
+#[test]
+fn test_event_emission_policy() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    let event = OmniBridgeEvent::TransferLabelsEvent {
+        transfer_id: DEFAULT_TRANSFER_ID,
+        labels: vec![],
+    };
+
+    contract.set_event_emission_policy(
+        "TransferLabelsEvent".to_string(),
+        EventEmissionPolicy::Sampled { one_in: 3 },
+    );
+    for _ in 0..4 {
+        contract.emit_event(&event);
+    }
+    assert_eq!(get_logs().len(), 2);
+
+    contract.set_event_emission_policy(
+        "TransferLabelsEvent".to_string(),
+        EventEmissionPolicy::AggregatePerBlock,
+    );
+    testing_env!(VMContextBuilder::new().block_height(10).build());
+    contract.emit_event(&event);
+    contract.emit_event(&event);
+    assert!(get_logs().is_empty());
+
+    testing_env!(VMContextBuilder::new().block_height(11).build());
+    contract.emit_event(&event);
+    let logs = get_logs();
+    assert_eq!(logs.len(), 1);
+    assert!(logs[0].contains("EventSummaryEvent"));
+    assert!(logs[0].contains("\"block_height\":10,\"count\":2"));
+    let index = Contract::configurable_event_index("TransferLabelsEvent").unwrap();
+    assert_eq!(
+        contract.event_emissions[index].counter,
+        Some(EventCounter {
+            block_height: 11,
+            count: 1,
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_EVENT_NOT_CONFIGURABLE")]
+fn test_event_emission_policy_not_configurable() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    contract.set_event_emission_policy(
+        "SignTransferEvent".to_string(),
+        EventEmissionPolicy::AggregatePerBlock,
+    );
+}
+
+#[test]
+fn test_utxo_chain_validator_target_address() {
+    let validator = get_default_contract().get_utxo_chain_validator(ChainKind::Zcash);
+    assert_eq!(
+        validator.validate_target_address(
+            &"t1Hsc1LR8yKnbbe3twRp88p6vFfC5t7DLbs".to_string(),
+            "t1Hsc1LR8yKnbbe3twRp88p6vFfC5t7DLbs",
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        validator.validate_target_address(
+            &"t1Hsc1LR8yKnbbe3twRp88p6vFfC5t7DLbs".to_string(),
+            "t3Vz22vK5z2LcKEdg16Yv4FFneEL1zg9ojd",
+        ),
+        Err(BridgeError::IncorrectTargetAddress)
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_DESTINATION_CHAIN")]
+fn test_utxo_chain_validator_unsupported_chain() {
+    get_default_contract().get_utxo_chain_validator(ChainKind::Eth);
+}
+
+#[test]
+fn test_error_catalog() {
+    let contract = get_default_contract();
+    let catalog = contract.get_error_catalog();
+
+    let mut names = std::collections::HashSet::new();
+    for (index, entry) in catalog.iter().enumerate() {
+        assert_eq!(entry.code, u32::try_from(index).unwrap() + 1);
+        assert!(
+            names.insert(entry.name.clone()),
+            "{} is duplicated",
+            entry.name
+        );
+    }
+
+    let quota_exceeded = catalog
+        .iter()
+        .find(|entry| entry.name == "ERR_TRANSFER_QUOTA_EXCEEDED")
+        .unwrap();
+    assert!(quota_exceeded.retryable);
+    let invalid_proof = catalog
+        .iter()
+        .find(|entry| entry.name == "ERR_INVALID_PROOF")
+        .unwrap();
+    assert!(!invalid_proof.retryable);
+    assert_ne!(
+        quota_exceeded.description_hash,
+        invalid_proof.description_hash
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_UNSUPPORTED_ADDRESS_FORMAT")]
+fn test_init_transfer_to_address_of_other_utxo_chain() {
+    let mut contract = get_default_contract();
+    contract.check_utxo_address_format(&OmniAddress::Zcash(
+        "t1Hsc1LR8yKnbbe3twRp88p6vFfC5t7DLbs".to_string(),
+    ));
+    contract.check_utxo_address_format(&OmniAddress::Btc(
+        "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string(),
+    ));
+
+    let mut msg = get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0);
+    msg.recipient = OmniAddress::Zcash("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2".to_string());
+    run_ft_on_transfer(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(msg),
+    );
+}
+
+#[test]
+fn test_litecoin_withdraw_fee_rate() {
+    let validator = get_default_contract().get_utxo_chain_validator(ChainKind::Ltc);
+    let msg = serde_json::json!({
+        "Withdraw": {
+            "target_ltc_address": "ltc1qg82tjmz2fd6x7mhhvtwd3hrmhzqgrnxsz0h4mq",
+            "input": [format!("{}:1", "ab".repeat(32))],
+            "output": [{"value": 1000, "script_pubkey": "0014"}],
+            "max_fee_rate": "20",
+        }
+    })
+    .to_string();
+
+    let withdraw = validator.parse_withdraw(&msg).unwrap();
+    assert_eq!(
+        withdraw.target_address,
+        "ltc1qg82tjmz2fd6x7mhhvtwd3hrmhzqgrnxsz0h4mq"
+    );
+    assert_eq!(
+        withdraw.outpoints,
+        vec![OutPoint {
+            txid: [0xab; 32],
+            vout: 1
+        }]
+    );
+    assert_eq!(withdraw.outputs.len(), 1);
+    assert_eq!(withdraw.max_fee, Some(20));
+    validator
+        .validate_max_fee(r#"{"MaxFeeRate":"20"}"#, withdraw.max_fee)
+        .unwrap();
+
+    assert!(validator
+        .default_address_formats()
+        .iter()
+        .any(|format| format.matches("ltc1qg82tjmz2fd6x7mhhvtwd3hrmhzqgrnxsz0h4mq")));
+    assert!(!get_default_contract()
+        .get_utxo_chain_validator(ChainKind::Btc)
+        .default_address_formats()
+        .iter()
+        .any(|format| format.matches("ltc1qg82tjmz2fd6x7mhhvtwd3hrmhzqgrnxsz0h4mq")));
+}
+
+#[test]
+fn test_litecoin_withdraw_with_max_gas_fee_msg() {
+    assert_eq!(
+        get_default_contract()
+            .get_utxo_chain_validator(ChainKind::Ltc)
+            .validate_max_fee(r#"{"MaxGasFee":"20"}"#, Some(20)),
+        Err(BridgeError::InvalidUtxoChainMsg)
+    );
+}
+
+#[test]
+fn test_dogecoin_withdraw_fee_per_kb() {
+    let validator = get_default_contract().get_utxo_chain_validator(ChainKind::Doge);
+    let msg = serde_json::json!({
+        "Withdraw": {
+            "target_doge_address": "DH5yaieqoZN36fDVciNyRueRGvGLR3mr7L",
+            "input": [format!("{}:0", "ab".repeat(32))],
+            "output": [{"value": 1000, "script_pubkey": "76a914"}],
+            "max_fee_per_kb": "1000000",
+        }
+    })
+    .to_string();
+
+    let withdraw = validator.parse_withdraw(&msg).unwrap();
+    assert_eq!(
+        withdraw.target_address,
+        "DH5yaieqoZN36fDVciNyRueRGvGLR3mr7L"
+    );
+    assert_eq!(withdraw.max_fee, Some(1_000_000));
+    validator
+        .validate_max_fee(r#"{"MaxFeePerKb":"1000000"}"#, withdraw.max_fee)
+        .unwrap();
+
+    assert!(validator
+        .default_address_formats()
+        .iter()
+        .any(|format| format.matches("DH5yaieqoZN36fDVciNyRueRGvGLR3mr7L")));
+}
+
+#[test]
+fn test_dogecoin_withdraw_with_other_fee_per_kb() {
+    let validator = get_default_contract().get_utxo_chain_validator(ChainKind::Doge);
+    assert_eq!(
+        validator.validate_max_fee(r#"{"MaxFeePerKb":"1000000"}"#, Some(2_000_000)),
+        Err(BridgeError::InvalidMaxFeePerKb)
+    );
+    assert_eq!(
+        validator.validate_max_fee(r#"{"MaxFeePerKb":"1000000"}"#, None),
+        Err(BridgeError::MissingMaxFeePerKb)
+    );
+}
+
+fn get_utxo_fin_transfer_msg(sender_addresses: Vec<String>) -> UtxoFinTransferMsg {
+    UtxoFinTransferMsg {
+        utxo_id: UtxoId {
+            tx_hash: "ab".repeat(32),
+            vout: 0,
+        },
+        recipient: OmniAddress::Near(DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap()),
+        relayer_fee: U128(0),
+        msg: String::new(),
+        sender_addresses,
+    }
+}
+
+fn run_utxo_fin_transfer_to_near_without_storage(
+    contract: &mut Contract,
+    sender_addresses: Vec<String>,
+) -> PromiseOrValue<U128> {
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+    run_storage_deposit(contract, relayer.clone(), NearToken::from_near(1));
+    setup_test_env(
+        relayer.clone(),
+        NearToken::from_yoctonear(0),
+        Some(vec![PromiseResult::Failed]),
+    );
+    contract.utxo_fin_transfer_to_near_callback(
+        DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        U128(10_000),
+        get_utxo_fin_transfer_msg(sender_addresses),
+        ChainKind::Btc,
+        &relayer,
+    )
+}
+
+// The recipient contract rejects the `ft_transfer_call` of the deposit
+fn run_rejected_utxo_fin_transfer_to_near(
+    contract: &mut Contract,
+    sender_addresses: Vec<String>,
+) -> U128 {
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+    run_storage_deposit(contract, relayer.clone(), NearToken::from_near(1));
+    setup_test_env(
+        relayer.clone(),
+        NearToken::from_yoctonear(0),
+        Some(vec![PromiseResult::Successful(
+            serde_json::to_vec(&U128(0)).unwrap(),
+        )]),
+    );
+    contract.resolve_utxo_fin_transfer(
+        DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        U128(10_000),
+        UtxoFinTransferMsg {
+            msg: "deposit".to_string(),
+            ..get_utxo_fin_transfer_msg(sender_addresses)
+        },
+        ChainKind::Btc,
+        &relayer,
+        Some(U128(0)),
+    )
+}
+
+#[test]
+fn test_utxo_deposit_refund_to_origin() {
+    let mut contract = get_default_contract();
+    contract
+        .utxo_refund_policies
+        .insert(&ChainKind::Btc, &UtxoRefundPolicy { fee: U128(500) });
+    let origin_address = "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string();
+
+    let result = run_rejected_utxo_fin_transfer_to_near(
+        &mut contract,
+        vec![origin_address.clone(), origin_address.clone()],
+    );
+
+    assert_eq!(result, U128(0));
+    let refund = contract.get_transfer_message(TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    });
+    assert_eq!(refund.recipient, OmniAddress::Btc(origin_address));
+    assert_eq!(refund.amount, U128(10_000));
+    assert_eq!(refund.fee.fee, U128(500));
+}
+
+#[test]
+fn test_utxo_deposit_refund_to_connector_with_several_origins() {
+    let mut contract = get_default_contract();
+    contract
+        .utxo_refund_policies
+        .insert(&ChainKind::Btc, &UtxoRefundPolicy { fee: U128(500) });
+
+    let result = run_rejected_utxo_fin_transfer_to_near(
+        &mut contract,
+        vec![
+            "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string(),
+            "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2".to_string(),
+        ],
+    );
+
+    assert_eq!(result, U128(10_000));
+    assert_eq!(contract.current_origin_nonce, 0);
+}
+
+// The storage of the recipient can still be deposited, so the deposit is not refunded
+#[test]
+fn test_utxo_deposit_without_storage_is_returned_to_connector() {
+    let mut contract = get_default_contract();
+    contract
+        .utxo_refund_policies
+        .insert(&ChainKind::Btc, &UtxoRefundPolicy { fee: U128(500) });
+    let origin_address = "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string();
+
+    let result = run_utxo_fin_transfer_to_near_without_storage(&mut contract, vec![origin_address]);
+
+    assert!(matches!(result, PromiseOrValue::Value(U128(10_000))));
+    assert_eq!(contract.current_origin_nonce, 0);
+}
+
+#[test]
+#[should_panic(expected = "ERR_MEMO_NOT_SUPPORTED")]
+fn test_init_transfer_with_memo_to_chain_without_memos() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    contract.set_chain_traits(
+        ChainKind::Eth,
+        Some(ChainTraits {
+            supports_memo: false,
+            ..ChainKind::Eth.default_traits()
+        }),
+    );
+    assert!(!contract.get_chain_traits(ChainKind::Eth).supports_memo);
+
+    let mut init_transfer_msg = get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0);
+    init_transfer_msg.msg = Some("memo".to_string());
+    run_ft_on_transfer(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(init_transfer_msg),
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_NOT_UTXO_CHAIN")]
+fn test_utxo_config_of_chain_configured_as_account_based() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    contract.set_chain_traits(
+        ChainKind::Doge,
+        Some(ChainTraits {
+            is_utxo: false,
+            ..ChainKind::Doge.default_traits()
+        }),
+    );
+
+    contract.set_extra_msg_required(ChainKind::Doge, true);
+}
+
+#[test]
+fn test_zcash_withdraw_to_shielded_address() {
+    let address =
+        "zs1z7rejlpsa98s2rrrfkwmaxu53e4ue0ulcrw0h4x5g8jl04tak0d3mm47vdtahatqrlkngh9sly".to_string();
+    assert_eq!(
+        get_default_contract()
+            .get_utxo_chain_validator(ChainKind::Zcash)
+            .validate_target_address(&address, &address),
+        Err(BridgeError::ShieldedAddressNotSupported)
+    );
+}
+
+#[test]
+fn test_zcash_withdraw_with_shielded_output() {
+    let msg = serde_json::json!({
+        "Withdraw": {
+            "target_btc_address": "t1Hsc1LR8yKnbbe3twRp88p6vFfC5t7DLbs",
+            "input": [format!("{}:0", "ab".repeat(32))],
+            "output": [
+                {"value": 1000, "script_pubkey": format!("76a914{}88ac", "11".repeat(20))},
+                {"value": 1000, "script_pubkey": "0014"},
+            ],
+            "max_gas_fee": "100",
+        }
+    })
+    .to_string();
+
+    assert_eq!(
+        get_default_contract()
+            .get_utxo_chain_validator(ChainKind::Zcash)
+            .parse_withdraw(&msg)
+            .err(),
+        Some(BridgeError::ShieldedOutputNotSupported)
+    );
+}
+
+#[test]
+fn test_register_utxo_chain() {
+    setup_test_env(
+        "dao.testnet".parse().unwrap(),
+        NearToken::from_near(1),
+        None,
+    );
+    let mut contract = get_default_contract();
+    let connector_id: AccountId = "ltc_connector.testnet".parse().unwrap();
+    let token_id: AccountId = "ltc.testnet".parse().unwrap();
+
+    contract.register_utxo_chain(
+        ChainKind::Ltc,
+        token_id.clone(),
+        connector_id.clone(),
+        UtxoFeeModel::MaxFeeRate,
+        8,
+    );
+    let registration = contract
+        .get_utxo_chain_registration(ChainKind::Ltc)
+        .unwrap();
+    assert_eq!(registration.token_id, token_id);
+    assert_eq!(registration.connector_id, connector_id);
+    assert_eq!(registration.fee_model, UtxoFeeModel::MaxFeeRate);
+    assert_eq!(
+        contract.get_utxo_chain_connector(ChainKind::Ltc),
+        connector_id
+    );
+
+    contract.unregister_utxo_chain(ChainKind::Ltc);
+    assert!(contract
+        .get_utxo_chain_registration(ChainKind::Ltc)
+        .is_none());
+    assert!(contract.get_chain_connector(ChainKind::Ltc).is_none());
+}
+
+#[test]
+#[should_panic(expected = "ERR_FEE_MODEL_MISMATCH")]
+fn test_register_utxo_chain_with_other_fee_model() {
+    setup_test_env(
+        "dao.testnet".parse().unwrap(),
+        NearToken::from_near(1),
+        None,
+    );
+    let mut contract = get_default_contract();
+    contract.register_utxo_chain(
+        ChainKind::Btc,
+        "btc.testnet".parse().unwrap(),
+        "btc_connector.testnet".parse().unwrap(),
+        UtxoFeeModel::MaxFeePerKb,
+        8,
+    );
+}
+
+fn flag_default_transfer(contract: &mut Contract) -> TransferId {
+    let transfer_id = init_default_transfer(contract);
+
+    setup_dao_env();
+    contract.acl_grant_role(Role::Guardian.into(), "guardian.testnet".parse().unwrap());
+    contract.set_watchtower_config(Some(WatchtowerConfig {
+        min_stake: NearToken::from_near(1),
+        penalty: NearToken::from_millinear(400),
+        review_threshold: 1,
+    }));
+
+    setup_test_env(
+        "watchtower.testnet".parse().unwrap(),
+        NearToken::from_near(1),
+        None,
+    );
+    contract.register_watchtower();
+    setup_test_env(
+        "watchtower.testnet".parse().unwrap(),
+        NearToken::from_millinear(100),
+        None,
+    );
+    contract.flag_transfer(transfer_id, Base58CryptoHash::from([3; 32]));
+    transfer_id
+}
+
+#[test]
+#[should_panic(expected = "ERR_TRANSFER_HELD_FOR_REVIEW")]
+fn test_sign_flagged_transfer() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    let transfer_id = flag_default_transfer(&mut contract);
+    assert!(contract.is_transfer_held(transfer_id));
+
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.sign_transfer(transfer_id, None, &None).detach();
+}
+
+#[test]
+fn test_clear_frivolous_transfer_flags() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    let transfer_id = flag_default_transfer(&mut contract);
+    let watchtower: AccountId = "watchtower.testnet".parse().unwrap();
+    assert_eq!(contract.get_transfer_flags(transfer_id).len(), 1);
+    assert_eq!(
+        contract
+            .get_watchtower(watchtower.clone())
+            .unwrap()
+            .active_flags,
+        1
+    );
+
+    setup_test_env(
+        "guardian.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.clear_transfer_flags(transfer_id, true);
+
+    assert!(!contract.is_transfer_held(transfer_id));
+    assert_eq!(
+        contract.get_watchtower(watchtower),
+        Some(Watchtower {
+            stake: NearToken::from_millinear(600),
+            active_flags: 0,
+        })
+    );
+    let event = OmniBridgeEvent::TransferFlagsClearedEvent {
+        transfer_id,
+        guardian: "guardian.testnet".parse().unwrap(),
+        is_frivolous: true,
+        slashed: NearToken::from_millinear(400),
+    };
+    assert!(get_logs().contains(&event.to_log_string()));
+}
+
+#[test]
+#[should_panic(expected = "ERR_INSUFFICIENT_WATCHTOWER_STAKE")]
+fn test_flag_transfers_beyond_watchtower_stake() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    // The stake of 1 NEAR covers the penalties of two flags of 0.4 NEAR
+    flag_default_transfer(&mut contract);
+    for _ in 0..2 {
+        let transfer_id = init_default_transfer(&mut contract);
+        setup_test_env(
+            "watchtower.testnet".parse().unwrap(),
+            NearToken::from_millinear(100),
+            None,
+        );
+        contract.flag_transfer(transfer_id, Base58CryptoHash::from([3; 32]));
+    }
+}
+
+#[test]
+fn test_clear_transfer_flags_returns_storage_deposit() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    let watchtower: AccountId = "watchtower.testnet".parse().unwrap();
+    flag_default_transfer(&mut contract);
+    run_storage_deposit(&mut contract, watchtower.clone(), NearToken::from_near(1));
+    let available = contract.storage_balance_of(&watchtower).unwrap().available;
+
+    let transfer_id = init_default_transfer(&mut contract);
+    setup_test_env(watchtower.clone(), NearToken::from_yoctonear(0), None);
+    contract.flag_transfer(transfer_id, Base58CryptoHash::from([3; 32]));
+    let storage_deposit = contract.get_transfer_flags(transfer_id)[0].storage_deposit;
+    assert!(!storage_deposit.is_zero());
+    assert_eq!(
+        contract.storage_balance_of(&watchtower).unwrap().available,
+        available.saturating_sub(storage_deposit)
+    );
+
+    setup_test_env(
+        "guardian.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.clear_transfer_flags(transfer_id, false);
+    assert_eq!(
+        contract.storage_balance_of(&watchtower).unwrap().available,
+        available
+    );
+}
+
+#[test]
+fn test_get_routes() {
+    let mut contract = get_default_contract();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+    let eth_token = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+    let sol_token = OmniAddress::new_zero(ChainKind::Sol).unwrap();
+    contract
+        .token_id_to_address
+        .insert(&(ChainKind::Eth, token_id.clone()), &eth_token);
+    contract
+        .token_id_to_address
+        .insert(&(ChainKind::Sol, token_id.clone()), &sol_token);
+    contract
+        .min_native_relayer_fees
+        .insert(&ChainKind::Sol, &U128(5));
+
+    let routes = contract.get_routes(token_id.clone());
+    assert_eq!(routes.len(), 6);
+
+    let from_near = routes
+        .iter()
+        .find(|route| {
+            route.origin_chain == ChainKind::Near && route.destination_chain == ChainKind::Eth
+        })
+        .unwrap();
+    assert!(!from_near.via_near);
+    assert_eq!(from_near.origin_token, OmniAddress::Near(token_id));
+    assert_eq!(from_near.destination_token, eth_token);
+    assert_eq!(from_near.min_native_relayer_fee, None);
+
+    let eth_to_sol = routes
+        .iter()
+        .find(|route| {
+            route.origin_chain == ChainKind::Eth && route.destination_chain == ChainKind::Sol
+        })
+        .unwrap();
+    assert!(eth_to_sol.via_near);
+    assert_eq!(eth_to_sol.destination_token, sol_token);
+    assert_eq!(eth_to_sol.min_native_relayer_fee, Some(U128(5)));
+    assert!(!eth_to_sol.is_connector_tripped);
+
+    assert!(contract
+        .get_routes("unknown.testnet".parse().unwrap())
+        .is_empty());
+}
+
+#[test]
+fn test_utxo_submission_fees_credited_to_ledger() {
+    let mut contract = get_default_contract();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+    let transfer_msg = TransferMessage {
+        origin_nonce: DEFAULT_NONCE,
+        token: OmniAddress::Near(token_id.clone()),
+        amount: U128(DEFAULT_TRANSFER_AMOUNT),
+        recipient: OmniAddress::Btc("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string()),
+        fee: Fee {
+            fee: U128(5),
+            native_fee: U128(10),
+        },
+        sender: OmniAddress::Near(DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap()),
+        msg: String::new(),
+        destination_nonce: 1,
+        origin_transfer_id: None,
+        expires_at: None,
+    };
+
+    let result = contract.submit_transfer_to_btc_connector_callback(
+        transfer_msg.clone(),
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        Some(relayer.clone()),
+        Vec::new(),
+        None,
+        None,
+        None,
+        &Ok(U128(DEFAULT_TRANSFER_AMOUNT - 5)),
+    );
+
+    assert_eq!(
+        result,
+        SubmitOutcome {
+            status: SubmitStatus::Forwarded,
+            forwarded_amount: U128(DEFAULT_TRANSFER_AMOUNT - 5),
+            fee_paid: transfer_msg.fee.clone(),
+            restored: false,
+        }
+    );
+    let event = OmniBridgeEvent::UtxoTransferForwardedEvent {
+        transfer_id: transfer_msg.get_transfer_id(),
+        chain_kind: ChainKind::Btc,
+        amount: U128(DEFAULT_TRANSFER_AMOUNT - 5),
+        fee: transfer_msg.fee,
+    };
+    assert!(get_logs().contains(&event.to_log_string()));
+    assert_eq!(
+        contract.get_claimable_fee(relayer.clone(), FeeAsset::Token(token_id.clone())),
+        U128(5)
+    );
+    assert_eq!(
+        contract.get_claimable_fee(relayer.clone(), FeeAsset::Near),
+        U128(10)
+    );
+    assert_eq!(
+        contract.get_locked_breakdown(token_id.clone()).fee_pool,
+        U128(5)
+    );
+
+    setup_test_env(relayer.clone(), NearToken::from_yoctonear(0), None);
+    contract
+        .claim_fees(FeeAsset::Token(token_id.clone()), None)
+        .detach();
+    assert_eq!(
+        contract.get_claimable_fee(relayer.clone(), FeeAsset::Token(token_id.clone())),
+        U128(0)
+    );
+
+    contract.claim_fees_callback(
+        relayer.clone(),
+        FeeAsset::Token(token_id.clone()),
+        U128(5),
+        &Err(PromiseError::Failed),
+    );
+    assert_eq!(
+        contract.get_claimable_fee(relayer.clone(), FeeAsset::Token(token_id.clone())),
+        U128(5)
+    );
+
+    contract.claim_fees_callback(relayer, FeeAsset::Token(token_id.clone()), U128(5), &Ok(()));
+    assert_eq!(contract.get_locked_breakdown(token_id).fee_pool, U128(0));
+}
+
+#[test]
+#[should_panic(expected = "ERR_NO_CLAIMABLE_FEES")]
+fn test_claim_fees_without_balance() {
+    let mut contract = get_default_contract();
+    setup_test_env(
+        "relayer.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.claim_fees(FeeAsset::Near, None).detach();
+}
+
+fn init_default_transfer(contract: &mut Contract) -> TransferId {
+    run_ft_on_transfer(
+        contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0)),
+    );
+    TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    }
+}
+
+#[test]
+fn test_cancel_transfer_as_owner() {
+    let mut contract = get_default_contract();
+    run_ft_on_transfer(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 10, 0)),
+    );
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    };
+    let transfer_message = contract.get_transfer_message(transfer_id);
+
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.cancel_transfer_as_owner(transfer_id).detach();
+
+    assert!(contract.pending_transfers.get(&transfer_id).is_none());
+    let event = OmniBridgeEvent::CancelTransferEvent {
+        transfer_message,
+        beneficiary: DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+    };
+    assert!(get_logs().contains(&event.to_log_string()));
+    assert_eq!(
+        contract.get_protocol_fees(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap()),
+        U128(10)
+    );
+}
+
+#[test]
+fn test_cancel_transfer_as_owner_failed_refund_is_claimable() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+
+    setup_test_env(owner.clone(), NearToken::from_yoctonear(0), None);
+    contract.cancel_transfer_as_owner(transfer_id).detach();
+    contract.refund_unsigned_transfer_callback(
+        token_id.clone(),
+        owner.clone(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        &Err(PromiseError::Failed),
+    );
+
+    assert_eq!(
+        contract.get_claimable_fee(owner, FeeAsset::Token(token_id.clone())),
+        U128(DEFAULT_TRANSFER_AMOUNT)
+    );
+    assert_eq!(
+        contract.get_locked_breakdown(token_id).fee_pool,
+        U128(DEFAULT_TRANSFER_AMOUNT)
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_ONLY_TRANSFER_OWNER")]
+fn test_cancel_transfer_as_owner_by_other_account() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+
+    setup_test_env(
+        "other.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.cancel_transfer_as_owner(transfer_id).detach();
+}
+
+#[test]
+#[should_panic(expected = "ERR_TRANSFER_ALREADY_SIGNED")]
+fn test_cancel_signed_transfer_as_owner() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+    contract.add_token(
+        &DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        &OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+        18,
+        18,
+    );
+
+    run_storage_deposit(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_near(1),
+    );
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.sign_transfer(transfer_id, None, &None).detach();
+    contract.cancel_transfer_as_owner(transfer_id).detach();
+}
+
+#[test]
+fn test_sign_transfer_charges_marker_to_signer() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+    contract.add_token(
+        &DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        &OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+        18,
+        18,
+    );
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+    run_storage_deposit(&mut contract, relayer.clone(), NearToken::from_near(1));
+    let available = contract.storage_balance_of(&relayer).unwrap().available;
+    let marker_balance =
+        Contract::required_balance_for_signing_requested_marker(&transfer_id, &relayer);
+
+    setup_test_env(relayer.clone(), NearToken::from_yoctonear(0), None);
+    contract.sign_transfer(transfer_id, None, &None).detach();
+    assert_eq!(
+        contract.storage_balance_of(&relayer).unwrap().available,
+        available.saturating_sub(marker_balance)
+    );
+
+    contract.remove_transfer_message(transfer_id);
+    assert_eq!(
+        contract.storage_balance_of(&relayer).unwrap().available,
+        available
+    );
+}
+
+const TRANSFER_EXPIRY_NS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
+fn init_expiring_transfer(contract: &mut Contract, expires_at: u64) -> TransferId {
+    let mut init_transfer_msg = get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0);
+    init_transfer_msg.expires_at = Some(U64(expires_at));
+    run_ft_on_transfer(
+        contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(init_transfer_msg),
+    );
+    TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    }
+}
+
+#[test]
+fn test_expire_transfer() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_expiring_transfer(&mut contract, TRANSFER_EXPIRY_NS);
+    let transfer_message = contract.get_transfer_message(transfer_id);
+    assert_eq!(
+        contract.get_transfer_expiry(transfer_id),
+        Some(U64(TRANSFER_EXPIRY_NS))
+    );
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id("anyone.testnet".parse().unwrap())
+        .block_timestamp(TRANSFER_EXPIRY_NS)
+        .build());
+    contract.expire_transfer(transfer_id).detach();
+
+    assert!(contract.pending_transfers.get(&transfer_id).is_none());
+    let event = OmniBridgeEvent::ExpireTransferEvent {
+        transfer_message,
+        refund_recipient: DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+    };
+    assert!(get_logs().contains(&event.to_log_string()));
+}
+
+#[test]
+fn test_expire_transfer_failed_refund_is_claimable() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_expiring_transfer(&mut contract, TRANSFER_EXPIRY_NS);
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id("anyone.testnet".parse().unwrap())
+        .block_timestamp(TRANSFER_EXPIRY_NS)
+        .build());
+    contract.expire_transfer(transfer_id).detach();
+    // The owner isn't registered with the token
+    contract.refund_unsigned_transfer_callback(
+        token_id.clone(),
+        owner.clone(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        &Err(PromiseError::Failed),
+    );
+
+    assert!(contract.pending_transfers.get(&transfer_id).is_none());
+    assert_eq!(
+        contract.get_claimable_fee(owner, FeeAsset::Token(token_id)),
+        U128(DEFAULT_TRANSFER_AMOUNT)
+    );
+}
+
+fn setup_fee_escalation(contract: &mut Contract) {
+    setup_test_env(
+        "dao.testnet".parse().unwrap(),
+        NearToken::from_near(1),
+        None,
+    );
+    contract.set_fee_escalation_policy(
+        ChainKind::Eth,
+        Some(FeeEscalationPolicy {
+            steps: vec![
+                FeeEscalationStep {
+                    min_age: U64(100),
+                    native_fee_increment: U128(10),
+                },
+                FeeEscalationStep {
+                    min_age: U64(200),
+                    native_fee_increment: U128(20),
+                },
+            ],
+            max_increase_bps: 5_000,
+        }),
+    );
+    contract.fund_fee_escalation_budget();
+}
+
+// Initiates a transfer paying a native fee of 100, which caps its escalation to 50.
+fn init_transfer_with_native_fee(contract: &mut Contract, expires_at: Option<u64>) -> TransferId {
+    let mut init_transfer_msg = get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 100);
+    init_transfer_msg.expires_at = expires_at.map(U64);
+    run_ft_on_transfer(
+        contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        Some(NearToken::from_near(1)),
+        &BridgeOnTransferMsg::InitTransfer(init_transfer_msg),
+    );
+    TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    }
+}
+
+fn escalate_transfer_fee_at(contract: &mut Contract, transfer_id: TransferId, timestamp: u64) {
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id("anyone.testnet".parse().unwrap())
+        .block_timestamp(timestamp)
+        .build());
+    contract.escalate_transfer_fee(transfer_id);
+}
+
+#[test]
+fn test_escalate_transfer_fee() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_transfer_with_native_fee(&mut contract, None);
+    setup_fee_escalation(&mut contract);
+
+    escalate_transfer_fee_at(&mut contract, transfer_id, 150);
+    assert_eq!(
+        contract.get_transfer_message(transfer_id).fee.native_fee,
+        U128(110)
+    );
+    escalate_transfer_fee_at(&mut contract, transfer_id, 250);
+    assert_eq!(
+        contract.get_transfer_message(transfer_id).fee.native_fee,
+        U128(130)
+    );
+    assert_eq!(
+        contract.get_fee_escalation(transfer_id),
+        Some(FeeEscalation {
+            steps: 2,
+            native_fee_added: U128(30),
+        })
+    );
+    assert_eq!(
+        contract.get_fee_escalation_budget(),
+        NearToken::from_near(1).saturating_sub(NearToken::from_yoctonear(30))
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_NO_FEE_ESCALATION_DUE")]
+fn test_escalate_transfer_fee_not_due() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_transfer_with_native_fee(&mut contract, None);
+    setup_fee_escalation(&mut contract);
+
+    escalate_transfer_fee_at(&mut contract, transfer_id, 250);
+    escalate_transfer_fee_at(&mut contract, transfer_id, 300);
+}
+
+#[test]
+fn test_escalate_transfer_fee_capped() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_transfer_with_native_fee(&mut contract, None);
+    setup_fee_escalation(&mut contract);
+    setup_dao_env();
+    let mut policy = contract.get_fee_escalation_policy(ChainKind::Eth).unwrap();
+    policy.max_increase_bps = 2_000;
+    contract.set_fee_escalation_policy(ChainKind::Eth, Some(policy));
+
+    escalate_transfer_fee_at(&mut contract, transfer_id, 150);
+    escalate_transfer_fee_at(&mut contract, transfer_id, 250);
+    assert_eq!(
+        contract.get_fee_escalation(transfer_id),
+        Some(FeeEscalation {
+            steps: 2,
+            native_fee_added: U128(20),
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_FEE_ESCALATION_CAP_REACHED")]
+fn test_escalate_transfer_fee_without_native_fee() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+    setup_fee_escalation(&mut contract);
+
+    escalate_transfer_fee_at(&mut contract, transfer_id, 150);
+}
+
+#[test]
+fn test_expire_transfer_reclaims_fee_escalation() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_transfer_with_native_fee(&mut contract, Some(TRANSFER_EXPIRY_NS));
+    setup_fee_escalation(&mut contract);
+    escalate_transfer_fee_at(&mut contract, transfer_id, 150);
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id("anyone.testnet".parse().unwrap())
+        .block_timestamp(TRANSFER_EXPIRY_NS)
+        .build());
+    contract.expire_transfer(transfer_id).detach();
+
+    assert_eq!(contract.get_fee_escalation(transfer_id), None);
+    assert_eq!(
+        contract.get_fee_escalation_budget(),
+        NearToken::from_near(1)
+    );
+}
+
+#[test]
+fn test_utxo_submission_keeps_fee_escalation() {
+    let mut contract = get_default_contract();
+    let escalation = FeeEscalation {
+        steps: 1,
+        native_fee_added: U128(10),
+    };
+    let transfer = submit_btc_withdrawal(&mut contract, BTC_WITHDRAWAL_AMOUNT, |contract| {
+        let transfer_id = TransferId {
+            origin_chain: ChainKind::Near,
+            origin_nonce: contract.current_origin_nonce,
+        };
+        contract.fee_escalations.insert(&transfer_id, &escalation);
+    });
+
+    // The escalation is reclaimed if the connector rejects the withdrawal
+    assert_eq!(
+        contract.get_fee_escalation(transfer.get_transfer_id()),
+        Some(escalation)
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_TRANSFER_NOT_EXPIRED")]
+fn test_expire_transfer_before_expiry() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_expiring_transfer(&mut contract, TRANSFER_EXPIRY_NS);
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id("anyone.testnet".parse().unwrap())
+        .block_timestamp(TRANSFER_EXPIRY_NS - 1)
+        .build());
+    contract.expire_transfer(transfer_id).detach();
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_TRANSFER_EXPIRY")]
+fn test_init_transfer_with_past_expiry() {
+    let mut contract = get_default_contract();
+    // The transfer is initiated at the timestamp 0
+    init_expiring_transfer(&mut contract, 0);
+}
+
+#[test]
+#[should_panic(expected = "ERR_TRANSFER_EXPIRY_NOT_SET")]
+fn test_expire_transfer_without_expiry() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+    contract.expire_transfer(transfer_id).detach();
+}
+
+fn configure_bootstrapping_contract(with_prover: bool) -> Contract {
+    setup_dao_env();
+    let mut contract = setup_bootstrapping_contract(
+        DEFAULT_MPC_SIGNER_ACCOUNT.to_string(),
+        DEFAULT_WNEAR_ACCOUNT.to_string(),
+    );
+    contract.acl_grant_role(
+        Role::PauseManager.into(),
+        "pause_manager.testnet".parse().unwrap(),
+    );
+    contract.add_factory(OmniAddress::Eth(
+        EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap(),
+    ));
+    contract.add_token(
+        &DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        &OmniAddress::new_zero(ChainKind::Eth).unwrap(),
+        18,
+        18,
+    );
+    if with_prover {
+        contract.add_prover(ChainKind::Eth, "prover.testnet".parse().unwrap());
+    }
+    contract
+}
+
+#[test]
+#[should_panic(expected = "ERR_BRIDGE_NOT_ACTIVATED")]
+fn test_init_transfer_before_activation() {
+    let mut contract = configure_bootstrapping_contract(true);
+    init_default_transfer(&mut contract);
+}
+
+#[test]
+fn test_activate() {
+    let mut contract = configure_bootstrapping_contract(true);
+    assert_eq!(contract.get_bootstrap_stage(), BootstrapStage::Deployed);
+    assert_eq!(contract.advance_bootstrap_stage(), BootstrapStage::RolesSet);
+
+    contract.activate();
+    assert_eq!(contract.get_bootstrap_stage(), BootstrapStage::Activated);
+    contract.require_bridge_activated();
+}
+
+#[test]
+#[should_panic(expected = "ERR_BOOTSTRAP_PROVER_NOT_SET")]
+fn test_activate_without_prover() {
+    let mut contract = configure_bootstrapping_contract(false);
+    contract.activate();
+}
+
+#[test]
+fn test_get_metrics_packed() {
+    let mut contract = get_default_contract();
+    init_default_transfer(&mut contract);
+    contract
+        .provers
+        .insert(&ChainKind::Eth, &"prover.testnet".parse().unwrap());
+    contract.record_proof_verified(ChainKind::Eth);
+
+    testing_env!(VMContextBuilder::new()
+        .block_timestamp(60 * 1_000_000_000)
+        .build());
+    assert_eq!(
+        contract.get_metrics_packed(),
+        vec![
+            (MetricId::PendingTransfers(ChainKind::Eth), U128(1)),
+            (MetricId::InitiatedTransfers(ChainKind::Eth), U128(1)),
+            (
+                MetricId::InitiatedVolume(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap()),
+                U128(DEFAULT_TRANSFER_AMOUNT)
+            ),
+            (MetricId::LightClientAge(ChainKind::Eth), U128(60)),
+        ]
+    );
+}
+
+const SUBMISSION_WINDOW_NS: u64 = 10 * 60 * 1_000_000_000;
+
+#[test]
+fn test_submission_intent_binds_fee_recipient() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+    let proxy: AccountId = "proxy.testnet".parse().unwrap();
+
+    setup_test_env(relayer.clone(), NearToken::from_near(1), None);
+    contract.register_submission_intent(transfer_id);
+    assert_eq!(
+        contract.resolve_fee_recipient(ChainKind::Eth, &transfer_id, Some(proxy.clone())),
+        Some(relayer)
+    );
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(proxy.clone())
+        .attached_deposit(NearToken::from_near(1))
+        .block_timestamp(SUBMISSION_WINDOW_NS)
+        .build());
+    contract.register_submission_intent(transfer_id);
+    assert_eq!(
+        contract.resolve_fee_recipient(ChainKind::Eth, &transfer_id, None),
+        Some(proxy)
+    );
+}
+
+#[test]
+fn test_submission_intent_honoured_for_retry_from_other_relayer() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+    let retry_relayer: AccountId = "retry_relayer.testnet".parse().unwrap();
+
+    setup_test_env(relayer.clone(), NearToken::from_near(1), None);
+    contract.register_submission_intent(transfer_id);
+
+    setup_test_env(retry_relayer.clone(), NearToken::from_yoctonear(0), None);
+    assert_eq!(
+        contract.resolve_fee_recipient(ChainKind::Eth, &transfer_id, Some(retry_relayer)),
+        Some(relayer)
+    );
+    assert!(contract.get_submission_intent(transfer_id).is_some());
+}
+
+#[test]
+fn test_expired_submission_intent_dropped_at_submit() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+    let fee_recipient: AccountId = "fee_recipient.testnet".parse().unwrap();
+    run_storage_deposit(&mut contract, relayer.clone(), NearToken::from_near(1));
+    let available = contract.storage_balance_of(&relayer).unwrap().available;
+
+    setup_test_env(relayer.clone(), NearToken::from_yoctonear(0), None);
+    contract.register_submission_intent(transfer_id);
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(relayer.clone())
+        .block_timestamp(SUBMISSION_WINDOW_NS)
+        .build());
+    assert_eq!(
+        contract.resolve_fee_recipient(ChainKind::Eth, &transfer_id, Some(fee_recipient.clone())),
+        Some(fee_recipient)
+    );
+    assert!(contract.get_submission_intent(transfer_id).is_none());
+    assert_eq!(
+        contract.storage_balance_of(&relayer).unwrap().available,
+        available
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_SUBMISSION_INTENT_RENEWAL")]
+fn test_submission_intent_renewal() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+
+    setup_test_env(relayer.clone(), NearToken::from_near(1), None);
+    contract.register_submission_intent(transfer_id);
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(relayer)
+        .attached_deposit(NearToken::from_near(1))
+        .block_timestamp(SUBMISSION_WINDOW_NS)
+        .build());
+    contract.register_submission_intent(transfer_id);
+}
+
+#[test]
+#[should_panic(expected = "ERR_SUBMISSION_WINDOW_CLAIMED")]
+fn test_submission_intent_during_window_of_other_relayer() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+
+    setup_test_env(
+        "relayer.testnet".parse().unwrap(),
+        NearToken::from_near(1),
+        None,
+    );
+    contract.register_submission_intent(transfer_id);
+
+    setup_test_env(
+        "proxy.testnet".parse().unwrap(),
+        NearToken::from_near(1),
+        None,
+    );
+    contract.register_submission_intent(transfer_id);
+}
+
+fn get_evm_destination_call() -> EvmDestinationCall {
+    EvmDestinationCall {
+        target: EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap(),
+        calldata_hash: Base58CryptoHash::from([4; 32]),
+        gas_limit: U64(200_000),
+    }
+}
+
+fn init_transfer_with_destination_call(contract: &mut Contract, native_token_fee: u128) {
+    setup_dao_env();
+    contract.set_destination_call_gas_fee(ChainKind::Eth, Some(U128(1_000_000)));
+
+    let mut init_transfer_msg =
+        get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, native_token_fee);
+    init_transfer_msg.destination_call = Some(get_evm_destination_call());
+    run_ft_on_transfer(
+        contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        Some(NearToken::from_near(1)),
+        &BridgeOnTransferMsg::InitTransfer(init_transfer_msg),
+    );
+}
+
+#[test]
+fn test_init_transfer_with_destination_call() {
+    let mut contract = get_default_contract();
+    init_transfer_with_destination_call(&mut contract, 200_000_000_000);
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    };
+    assert_eq!(
+        contract.get_destination_call(transfer_id),
+        Some(get_evm_destination_call())
+    );
+
+    contract.add_token(
+        &DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        &OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+        18,
+        18,
+    );
+    run_storage_deposit(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_near(1),
+    );
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.sign_transfer(transfer_id, None, &None).detach();
+    assert!(contract
+        .signing_requested_transfers
+        .contains_key(&transfer_id));
+}
+
+#[test]
+fn test_restored_transfer_keeps_destination_call() {
+    let mut contract = get_default_contract();
+    init_transfer_with_destination_call(&mut contract, 200_000_000_000);
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    };
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+
+    let transfer = contract.take_transfer_message(transfer_id);
+    let outcome = contract.submit_transfer_to_btc_connector_callback(
+        transfer.message,
+        owner.clone(),
+        None,
+        Vec::new(),
+        None,
+        Some(owner),
+        None,
+        &Ok(U128(0)),
+    );
+    assert!(outcome.restored);
+    assert_eq!(
+        contract.get_destination_call(transfer_id),
+        Some(get_evm_destination_call())
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_NATIVE_FEE_BELOW_DESTINATION_CALL_GAS_FEE")]
+fn test_init_transfer_with_destination_call_below_gas_fee() {
+    let mut contract = get_default_contract();
+    init_transfer_with_destination_call(&mut contract, 199_999_999_999);
+}
+
+#[test]
+#[should_panic(expected = "ERR_DESTINATION_CALL_NOT_SUPPORTED")]
+fn test_init_transfer_with_destination_call_to_unsupported_chain() {
+    let mut contract = get_default_contract();
+    let mut init_transfer_msg = get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 200_000_000_000);
+    init_transfer_msg.destination_call = Some(get_evm_destination_call());
+    run_ft_on_transfer(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        Some(NearToken::from_near(1)),
+        &BridgeOnTransferMsg::InitTransfer(init_transfer_msg),
+    );
+}
+
+#[test]
+fn test_get_route_limits() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    contract.set_utxo_withdraw_limits(
+        ChainKind::Btc,
+        UtxoWithdrawLimits {
+            max_inputs: Some(10),
+            max_outputs: None,
+            max_msg_size: Some(2048),
+        },
+    );
+
+    let limits = contract.get_route_limits(ChainKind::Near, ChainKind::Btc);
+    assert_eq!(limits.max_proof_size, None);
+    assert_eq!(limits.max_withdraw_msg_size, Some(2048));
+    assert_eq!(limits.max_withdraw_inputs, Some(10));
+
+    let limits = contract.get_route_limits(ChainKind::Eth, ChainKind::Sol);
+    assert!(limits.max_proof_size.is_some());
+    assert_eq!(limits.max_withdraw_msg_size, None);
+
+    contract.set_chain_traits(
+        ChainKind::Sol,
+        Some(ChainTraits {
+            supports_memo: false,
+            ..ChainKind::Sol.default_traits()
+        }),
+    );
+    assert_eq!(
+        contract
+            .get_route_limits(ChainKind::Eth, ChainKind::Sol)
+            .max_transfer_msg_size,
+        0
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_TRANSFER_MSG_TOO_LARGE")]
+fn test_init_transfer_with_too_large_msg() {
+    let mut contract = get_default_contract();
+    let max_msg_size = contract
+        .get_route_limits(ChainKind::Near, ChainKind::Eth)
+        .max_transfer_msg_size;
+    let mut msg = get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0);
+    msg.msg = Some("a".repeat(max_msg_size as usize + 1));
+    run_ft_on_transfer(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(msg),
+    );
+}
+
+#[test]
+fn test_get_transfer_fee_quote() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+    contract.add_token(
+        &token_id,
+        &OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+        6,
+        18,
+    );
+    contract.add_token(
+        &token_id,
+        &OmniAddress::new_zero(ChainKind::Btc).unwrap(),
+        18,
+        18,
+    );
+    contract.set_min_native_relayer_fee(ChainKind::Eth, Some(U128(5)));
+
+    let quote =
+        contract.get_transfer_fee_quote(ChainKind::Eth, token_id.clone(), U128(3_000_000_000_000));
+    assert_eq!(quote.fee, U128(0));
+    assert_eq!(quote.native_fee, U128(5));
+    assert_eq!(quote.amount_to_receive, U128(3));
+    assert_eq!(quote.utxo_fee_model, None);
+
+    contract.set_min_token_relayer_fee(ChainKind::Btc, token_id.clone(), Some(U128(100)));
+    let quote = contract.get_transfer_fee_quote(ChainKind::Btc, token_id, U128(1_000));
+    assert_eq!(quote.fee, U128(100));
+    assert_eq!(quote.native_fee, U128(0));
+    assert_eq!(quote.amount_to_receive, U128(900));
+    assert_eq!(quote.utxo_fee_model, Some(UtxoFeeModel::MaxFee));
+    assert_eq!(quote.utxo_network_fee, None);
+}
+
+#[test]
+fn test_get_transfer_fee_quote_with_utxo_fee_rate() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+    contract.add_token(
+        &token_id,
+        &OmniAddress::new_zero(ChainKind::Doge).unwrap(),
+        18,
+        18,
+    );
+    contract.set_min_token_relayer_fee(ChainKind::Doge, token_id.clone(), Some(U128(100)));
+
+    let quote = contract.get_transfer_fee_quote(ChainKind::Doge, token_id.clone(), U128(2_000_000));
+    assert_eq!(quote.utxo_network_fee, None);
+    assert_eq!(quote.amount_to_receive, U128(1_999_900));
+
+    // A transaction of one input and two outputs is under a kilobyte
+    contract.set_utxo_fee_rate(ChainKind::Doge, U64(1_000_000));
+    let quote = contract.get_transfer_fee_quote(ChainKind::Doge, token_id, U128(2_000_000));
+    assert_eq!(quote.utxo_fee_model, Some(UtxoFeeModel::MaxFeePerKb));
+    assert_eq!(quote.utxo_network_fee, Some(U128(1_000_000)));
+    assert_eq!(quote.amount_to_receive, U128(999_900));
+}
+
+#[test]
+fn test_withdraw_msg_vectors_accepted_by_validators() {
+    let contract = get_default_contract();
+    for vector in WITHDRAW_MSG_VECTORS {
+        let validator = contract.get_utxo_chain_validator(vector.chain_kind);
+        let withdraw = validator.parse_withdraw(vector.msg).unwrap();
+
+        assert_eq!(withdraw.target_address, vector.target_address);
+        validator
+            .validate_target_address(&vector.target_address.to_string(), &withdraw.target_address)
+            .unwrap();
+        assert_eq!(
+            withdraw.outpoints,
+            vector
+                .input
+                .iter()
+                .map(|outpoint| outpoint.parse().unwrap())
+                .collect::<Vec<OutPoint>>()
+        );
+        assert_eq!(
+            withdraw
+                .outputs
+                .iter()
+                .map(|output| (output.value, output.script_pubkey.as_str()))
+                .collect::<Vec<_>>(),
+            vector.output
+        );
+        assert_eq!(withdraw.max_fee, Some(vector.max_fee));
+    }
+}
+
+#[test]
+fn test_set_utxo_fee_rate() {
+    let mut contract = get_default_contract();
+    setup_dao_env();
+    contract.acl_grant_role(
+        Role::FeeRateOracle.into(),
+        "oracle.testnet".parse().unwrap(),
+    );
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id("oracle.testnet".parse().unwrap())
+        .block_height(100)
+        .build());
+    contract.set_utxo_fee_rate(ChainKind::Doge, U64(1_000_000));
+    assert_eq!(
+        contract.get_utxo_fee_rate(ChainKind::Doge),
+        Some(UtxoFeeRate {
+            fee_rate: U64(1_000_000),
+            updated_at_height: U64(100),
+        })
+    );
+    assert_eq!(
+        contract.fresh_utxo_fee_rate(ChainKind::Doge),
+        Some(1_000_000)
+    );
+
+    testing_env!(VMContextBuilder::new()
+        .block_height(101 + MAX_UTXO_FEE_RATE_AGE)
+        .build());
+    assert_eq!(contract.fresh_utxo_fee_rate(ChainKind::Doge), None);
+    assert_eq!(contract.fresh_utxo_fee_rate(ChainKind::Ltc), None);
+}
+
+#[test]
+#[should_panic(expected = "ERR_FEE_RATE_NOT_SUPPORTED")]
+fn test_set_utxo_fee_rate_of_total_fee_chain() {
+    let mut contract = get_default_contract();
+    setup_dao_env();
+    contract.set_utxo_fee_rate(ChainKind::Btc, U64(10));
+}
+
+#[test]
+fn test_withdraw_with_default_utxo_fee_rate() {
+    let validator = get_default_contract().get_utxo_chain_validator(ChainKind::Doge);
+    let msg = serde_json::json!({
+        "Withdraw": {
+            "target_doge_address": "DH5yaieqoZN36fDVciNyRueRGvGLR3mr7L",
+            "input": [format!("{}:0", "ab".repeat(32))],
+            "output": [{"value": 1000, "script_pubkey": "76a914"}],
+        }
+    })
+    .to_string();
+    assert_eq!(validator.parse_withdraw(&msg).unwrap().max_fee, None);
+
+    let msg = Contract::with_default_utxo_fee_rate(validator, &msg, 1_000_000);
+    assert_eq!(
+        validator.parse_withdraw(&msg).unwrap().max_fee,
+        Some(1_000_000)
+    );
+
+    validator
+        .validate_default_fee_rate(r#"{"MaxFeePerKb":"1000000"}"#, 1_000_000)
+        .unwrap();
+    validator
+        .validate_default_fee_rate(r#"{"MaxFeePerKb":"2000000"}"#, 1_000_000)
+        .unwrap();
+    assert_eq!(
+        validator.validate_default_fee_rate(r#"{"MaxFeePerKb":"999999"}"#, 1_000_000),
+        Err(BridgeError::FeeRateAboveMaxFee)
+    );
+}
+
+fn pause_fee_settlement(contract: &mut Contract) {
+    setup_dao_env();
+    contract.acl_grant_role(
+        Role::PauseManager.into(),
+        "pause_manager.testnet".parse().unwrap(),
+    );
+    setup_test_env(
+        "pause_manager.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    assert!(contract.pa_pause_feature(FEE_SETTLEMENT_FEATURE.to_string()));
+}
+
+#[test]
+fn test_pause_fee_settlement_keeps_transfers() {
+    let mut contract = get_default_contract();
+    pause_fee_settlement(&mut contract);
+    assert!(contract.is_fee_settlement_paused());
+
+    let transfer_id = init_default_transfer(&mut contract);
+    assert_eq!(
+        contract.get_transfer_message(transfer_id).amount,
+        U128(DEFAULT_TRANSFER_AMOUNT)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Pausable: Method is paused")]
+fn test_claim_fees_when_fee_settlement_paused() {
+    let mut contract = get_default_contract();
+    pause_fee_settlement(&mut contract);
+
+    setup_test_env(
+        "relayer.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.claim_fees(FeeAsset::Near, None).detach();
+}
+
+#[test]
+fn test_migrate_fee_settlement_pause() {
+    let mut contract = get_default_contract();
+    pause_fee_settlement(&mut contract);
+    assert!(contract.pa_unpause_feature(FEE_SETTLEMENT_FEATURE.to_string()));
+    assert!(contract.pa_pause_feature("claim_fee".to_string()));
+
+    contract.migrate_fee_settlement_pause();
+    assert!(contract.is_fee_settlement_paused());
+    assert!(!contract.pa_is_paused("claim_fee".to_string()));
+}
+
+#[test]
+#[should_panic(expected = "Pausable: Method is paused")]
+fn test_withdraw_protocol_fees_when_fee_settlement_paused() {
+    let mut contract = get_default_contract();
+    pause_fee_settlement(&mut contract);
+
+    setup_dao_env();
+    contract
+        .withdraw_protocol_fees(
+            DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+            DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+            U128(1),
+        )
+        .detach();
+}
+
+#[test]
+fn test_max_allowed_withdraw_fee() {
+    let mut contract = get_default_contract();
+    // The first Bitcoin vector pays a network fee of at most 1000
+    submit_btc_withdrawal(&mut contract, BTC_WITHDRAWAL_AMOUNT, |contract| {
+        contract.set_max_allowed_withdraw_fee(ChainKind::Btc, Some(U128(1000)));
+    });
+    assert_eq!(
+        contract.get_max_allowed_withdraw_fee(ChainKind::Btc),
+        Some(U128(1000))
+    );
+    assert_eq!(contract.get_max_allowed_withdraw_fee(ChainKind::Doge), None);
+
+    setup_dao_env();
+    contract.set_max_allowed_withdraw_fee(ChainKind::Btc, None);
+    assert_eq!(contract.get_max_allowed_withdraw_fee(ChainKind::Btc), None);
+}
+
+#[test]
+#[should_panic(expected = "ERR_WITHDRAW_FEE_BOUND_REQUIRED")]
+fn test_withdraw_fee_bound_required_by_max_allowed() {
+    let mut contract = get_default_contract();
+    setup_dao_env();
+    contract.check_max_allowed_withdraw_fee(ChainKind::Ltc, None);
+
+    contract.set_max_allowed_withdraw_fee(ChainKind::Ltc, Some(U128(50)));
+    contract.check_max_allowed_withdraw_fee(ChainKind::Ltc, None);
+}
+
+#[test]
+#[should_panic(expected = "ERR_WITHDRAW_FEE_ABOVE_MAX_ALLOWED")]
+fn test_withdraw_fee_above_max_allowed() {
+    let mut contract = get_default_contract();
+    submit_btc_withdrawal(&mut contract, BTC_WITHDRAWAL_AMOUNT, |contract| {
+        contract.set_max_allowed_withdraw_fee(ChainKind::Btc, Some(U128(999)));
+    });
+}
+
+#[test]
+fn test_utxo_dust_limit() {
+    let mut contract = get_default_contract();
+    let withdraw = contract
+        .get_utxo_chain_validator(ChainKind::Btc)
+        .parse_withdraw(WITHDRAW_MSG_VECTORS[0].msg)
+        .unwrap();
+    assert_eq!(withdraw.min_output_value, Some(9000));
+    submit_btc_withdrawal(&mut contract, BTC_WITHDRAWAL_AMOUNT, |contract| {
+        contract.set_utxo_dust_limit(ChainKind::Btc, Some(U64(9000)));
+    });
+    assert_eq!(
+        contract.get_utxo_dust_limit(ChainKind::Btc),
+        Some(U64(9000))
+    );
+    assert_eq!(contract.get_utxo_dust_limit(ChainKind::Ltc), None);
+
+    setup_dao_env();
+    contract.set_utxo_dust_limit(ChainKind::Btc, None);
+    assert_eq!(contract.get_utxo_dust_limit(ChainKind::Btc), None);
+}
+
+#[test]
+#[should_panic(expected = "ERR_WITHDRAW_OUTPUT_BELOW_DUST_LIMIT")]
+fn test_withdraw_output_below_dust_limit() {
+    let mut contract = get_default_contract();
+    submit_btc_withdrawal(&mut contract, BTC_WITHDRAWAL_AMOUNT, |contract| {
+        contract.set_utxo_dust_limit(ChainKind::Btc, Some(U64(9001)));
+    });
+}
+
+#[test]
+fn test_utxo_script_kinds() {
+    let mut contract = get_default_contract();
+    let withdraw = contract
+        .get_utxo_chain_validator(ChainKind::Btc)
+        .parse_withdraw(WITHDRAW_MSG_VECTORS[0].msg)
+        .unwrap();
+    assert_eq!(
+        withdraw.output_script_kinds,
+        vec![ScriptKind::P2wpkh, ScriptKind::P2wpkh]
+    );
+    // The default script kinds of Bitcoin allow the outputs of the vector
+    submit_btc_withdrawal(&mut contract, BTC_WITHDRAWAL_AMOUNT, |_| {});
+    assert_eq!(
+        contract.get_utxo_script_kinds(ChainKind::Doge),
+        vec![ScriptKind::P2pkh, ScriptKind::P2sh]
+    );
+
+    setup_dao_env();
+    contract.set_utxo_script_kinds(ChainKind::Btc, Some(vec![ScriptKind::P2tr]));
+    assert_eq!(
+        contract.get_utxo_script_kinds(ChainKind::Btc),
+        vec![ScriptKind::P2tr]
+    );
+    contract.set_utxo_script_kinds(ChainKind::Btc, None);
+    assert!(contract
+        .get_utxo_script_kinds(ChainKind::Btc)
+        .contains(&ScriptKind::P2wpkh));
+}
+
+#[test]
+#[should_panic(expected = "ERR_WITHDRAW_OUTPUT_SCRIPT_NOT_ALLOWED")]
+fn test_withdraw_output_script_not_allowed() {
+    let mut contract = get_default_contract();
+    submit_btc_withdrawal(&mut contract, BTC_WITHDRAWAL_AMOUNT, |contract| {
+        contract.set_utxo_script_kinds(ChainKind::Btc, Some(vec![ScriptKind::P2tr]));
+    });
+}
+
+#[test]
+fn test_custody_utxos() {
+    let mut contract = get_default_contract();
+    let connector: AccountId = "connector.testnet".parse().unwrap();
+    contract.utxo_chain_connectors.insert(
+        ChainKind::Btc,
+        UTXOChainConfig {
+            connector: connector.clone(),
+            token_id: "btc.testnet".parse().unwrap(),
+        },
+    );
+    let output = TxOut {
+        value: 10_000,
+        script_pubkey: "0014e8df018c7e326cc253faac7e46cdc51e68542c42".to_string(),
+    };
+
+    setup_test_env(connector.clone(), NearToken::from_yoctonear(0), None);
+    contract.update_custody_utxos(
+        ChainKind::Btc,
+        vec![
+            CustodyUtxo {
+                outpoint: get_outpoint(0),
+                output: output.clone(),
+            },
+            CustodyUtxo {
+                outpoint: get_outpoint(1),
+                output,
+            },
+        ],
+        Vec::new(),
+    );
+    assert_eq!(
+        contract
+            .get_custody_utxo(ChainKind::Btc, get_outpoint(0))
+            .map(|output| output.value),
+        Some(10_000)
+    );
+
+    // The input of the withdrawal is spent once the connector accepts it
+    let input: OutPoint = WITHDRAW_MSG_VECTORS[0].input[0].parse().unwrap();
+    let transfer_message = submit_btc_withdrawal_from_custody(&mut contract, 91_000);
+    assert!(contract.get_custody_utxo(ChainKind::Btc, input).is_some());
+    contract.submit_transfer_to_btc_connector_callback(
+        transfer_message.clone(),
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        None,
+        vec![input],
+        None,
+        None,
+        None,
+        &Ok(transfer_message.amount),
+    );
+    assert!(contract.get_custody_utxo(ChainKind::Btc, input).is_none());
+    assert!(contract
+        .get_custody_utxo(ChainKind::Btc, get_outpoint(1))
+        .is_some());
+}
+
+// Enables the tracking as the DAO for a connector that reports the custody UTXOs.
+fn enable_custody_utxo_tracking(contract: &mut Contract) {
+    contract.connector_interface_versions.insert(
+        &ChainKind::Btc,
+        &CUSTODY_REPORTS_MIN_CONNECTOR_INTERFACE_VERSION,
+    );
+    contract.set_custody_utxo_tracking(ChainKind::Btc, true);
+}
+
+#[test]
+#[should_panic(expected = "ERR_CONNECTOR_INTERFACE_VERSION_NOT_SUPPORTED")]
+fn test_custody_utxo_tracking_with_old_connector_version() {
+    let mut contract = get_default_contract();
+    setup_dao_env();
+    contract.set_custody_utxo_tracking(ChainKind::Btc, true);
+}
+
+#[test]
+#[should_panic(expected = "ERR_INPUT_NOT_IN_CUSTODY")]
+fn test_withdraw_input_not_in_custody() {
+    let mut contract = get_default_contract();
+    submit_btc_withdrawal(&mut contract, 91_000, |contract| {
+        enable_custody_utxo_tracking(contract);
+    });
+}
+
+#[test]
+#[should_panic(expected = "ERR_CUSTODY_UPDATE_NOT_ALLOWED")]
+fn test_update_custody_utxos_not_connector() {
+    let mut contract = get_default_contract();
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.update_custody_utxos(ChainKind::Btc, Vec::new(), vec![get_outpoint(0)]);
+}
+
+#[test]
+fn test_approved_sweep_destinations() {
+    let mut contract = get_default_contract();
+    setup_dao_env();
+    let script_pubkey = "0014e8df018c7e326cc253faac7e46cdc51e68542c42".to_string();
+    contract.add_approved_sweep_destination(ChainKind::Btc, script_pubkey.clone());
+    assert!(contract.is_approved_sweep_destination(ChainKind::Btc, script_pubkey.clone()));
+    assert!(!contract.is_approved_sweep_destination(ChainKind::Ltc, script_pubkey.clone()));
+
+    contract.remove_approved_sweep_destination(ChainKind::Btc, script_pubkey.clone());
+    assert!(!contract.is_approved_sweep_destination(ChainKind::Btc, script_pubkey));
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_SCRIPT_PUBKEY")]
+fn test_add_invalid_sweep_destination() {
+    let mut contract = get_default_contract();
+    setup_dao_env();
+    contract.add_approved_sweep_destination(ChainKind::Btc, "bc1q".to_string());
+}
+
+#[test]
+fn test_tvl_cap() {
+    let mut contract = get_default_contract();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+    assert_eq!(contract.get_tvl_headroom(token_id.clone()), None);
+
+    setup_dao_env();
+    contract.set_tvl_cap(
+        token_id.clone(),
+        Some(U128(DEFAULT_TRANSFER_AMOUNT * 3 / 2)),
+    );
+    init_default_transfer(&mut contract);
+
+    assert_eq!(
+        contract.get_tvl(token_id.clone()),
+        U128(DEFAULT_TRANSFER_AMOUNT)
+    );
+    assert_eq!(
+        contract.get_tvl_headroom(token_id.clone()),
+        Some(U128(DEFAULT_TRANSFER_AMOUNT / 2))
+    );
+    assert_eq!(
+        contract.check_tvl_cap(&token_id, DEFAULT_TRANSFER_AMOUNT),
+        Err(BridgeError::TvlCapExceeded)
+    );
+
+    contract.record_tvl_inbound(&token_id, DEFAULT_TRANSFER_AMOUNT * 2);
+    assert_eq!(contract.get_tvl(token_id.clone()), U128(0));
+
+    setup_dao_env();
+    contract.set_tvl_cap(token_id.clone(), None);
+    assert_eq!(contract.get_tvl_headroom(token_id), None);
+}
+
+#[test]
+fn test_tvl_released_on_cancel() {
+    let mut contract = get_default_contract();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+    setup_dao_env();
+    contract.set_tvl_cap(token_id.clone(), Some(U128(DEFAULT_TRANSFER_AMOUNT)));
+    let transfer_id = init_default_transfer(&mut contract);
+    assert_eq!(
+        contract.get_tvl(token_id.clone()),
+        U128(DEFAULT_TRANSFER_AMOUNT)
+    );
+
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.cancel_transfer_as_owner(transfer_id).detach();
+    assert_eq!(contract.get_tvl(token_id), U128(0));
+}
+
+#[test]
+#[should_panic(expected = "ERR_TVL_CAP_EXCEEDED")]
+fn test_init_transfer_above_tvl_cap() {
+    let mut contract = get_default_contract();
+    setup_dao_env();
+    contract.set_tvl_cap(
+        DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        Some(U128(DEFAULT_TRANSFER_AMOUNT - 1)),
+    );
+    init_default_transfer(&mut contract);
+}
+
+fn get_incoming_message(origin_nonce: Nonce) -> ProverResult {
+    ProverResult::Message(IncomingMessage {
+        origin_nonce,
+        sender: OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+        recipient: DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        payload_hash: [1; 32].into(),
+        emitter_address: OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+    })
+}
+
+#[test]
+fn test_receive_message_callback() {
+    let mut contract = get_default_contract();
+    contract.factories.insert(
+        &ChainKind::Eth,
+        &OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+    );
+    setup_dao_env();
+    contract.register_message_handler(DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap());
+
+    setup_test_env(env::current_account_id(), NearToken::from_near(1), None);
+    contract.receive_message_callback(
+        Ok(get_incoming_message(1)),
+        &DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+    );
+
+    assert!(contract.is_message_received(ChainKind::Eth, 1));
+}
+
+#[test]
+fn test_rejected_message_can_be_received_again() {
+    let mut contract = get_default_contract();
+    contract.factories.insert(
+        &ChainKind::Eth,
+        &OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+    );
+    contract
+        .message_handlers
+        .insert(&DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap());
+    let relayer: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    run_storage_deposit(&mut contract, relayer.clone(), NearToken::from_near(1));
+    let available = contract.storage_balance_of(&relayer).unwrap().available;
+
+    setup_test_env(
+        env::current_account_id(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.receive_message_callback(Ok(get_incoming_message(1)), &relayer);
+    let ProverResult::Message(message) = get_incoming_message(1) else {
+        unreachable!()
+    };
+    let storage_deposit =
+        available.saturating_sub(contract.storage_balance_of(&relayer).unwrap().available);
+    contract.on_message_callback(
+        message,
+        &relayer,
+        storage_deposit,
+        &Err(PromiseError::Failed),
+    );
+
+    assert!(!contract.is_message_received(ChainKind::Eth, 1));
+    assert_eq!(
+        contract.storage_balance_of(&relayer).unwrap().available,
+        available
+    );
+    contract.receive_message_callback(Ok(get_incoming_message(1)), &relayer);
+    assert!(contract.is_message_received(ChainKind::Eth, 1));
+}
+
+#[test]
+#[should_panic(expected = "ERR_MESSAGE_ALREADY_RECEIVED")]
+fn test_receive_message_callback_replay() {
+    let mut contract = get_default_contract();
+    contract.factories.insert(
+        &ChainKind::Eth,
+        &OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+    );
+    contract
+        .message_handlers
+        .insert(&DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap());
+    setup_test_env(env::current_account_id(), NearToken::from_near(1), None);
+    let relayer: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+
+    contract.receive_message_callback(Ok(get_incoming_message(1)), &relayer);
+    contract.receive_message_callback(Ok(get_incoming_message(1)), &relayer);
+}
+
+#[test]
+#[should_panic(expected = "ERR_MESSAGE_HANDLER_NOT_REGISTERED")]
+fn test_receive_message_callback_unregistered_handler() {
+    let mut contract = get_default_contract();
+    contract.factories.insert(
+        &ChainKind::Eth,
+        &OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+    );
+
+    contract.receive_message_callback(
+        Ok(get_incoming_message(1)),
+        &DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+    );
+}
+
+#[test]
+fn test_outpoints_reserved_until_spend_confirmed() {
+    let mut contract = get_default_contract();
+    let connector: AccountId = "connector.testnet".parse().unwrap();
+    contract.utxo_chain_connectors.insert(
+        ChainKind::Btc,
+        UTXOChainConfig {
+            connector: connector.clone(),
+            token_id: "btc.testnet".parse().unwrap(),
+        },
+    );
+    let outpoint = get_outpoint(0);
+    contract.reserve_outpoints(ChainKind::Btc, &[DEFAULT_TRANSFER_ID], &[outpoint]);
+
+    setup_test_env(connector, NearToken::from_yoctonear(0), None);
+    contract.update_custody_utxos(ChainKind::Btc, Vec::new(), Vec::new());
+    assert_eq!(
+        contract.get_outpoint_reservation(ChainKind::Btc, outpoint),
+        vec![DEFAULT_TRANSFER_ID]
+    );
+
+    contract.update_custody_utxos(ChainKind::Btc, Vec::new(), vec![outpoint]);
+    assert!(contract
+        .get_outpoint_reservation(ChainKind::Btc, outpoint)
+        .is_empty());
+}
+
+#[test]
+fn test_release_stale_outpoint_reservations() {
+    let mut contract = get_default_contract();
+    let outpoint = get_outpoint(0);
+    contract.reserve_outpoints(ChainKind::Btc, &[DEFAULT_TRANSFER_ID], &[outpoint]);
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id("anyone.testnet".parse().unwrap())
+        .block_timestamp(7 * 24 * 60 * 60 * 1_000_000_000)
+        .build());
+    contract.release_stale_outpoint_reservations(ChainKind::Btc, vec![outpoint]);
+    assert!(contract
+        .get_outpoint_reservation(ChainKind::Btc, outpoint)
+        .is_empty());
+}
+
+#[test]
+#[should_panic(expected = "ERR_OUTPOINT_RESERVATION_NOT_STALE")]
+fn test_release_recent_outpoint_reservations() {
+    let mut contract = get_default_contract();
+    let outpoint = get_outpoint(0);
+    contract.reserve_outpoints(ChainKind::Btc, &[DEFAULT_TRANSFER_ID], &[outpoint]);
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id("anyone.testnet".parse().unwrap())
+        .block_timestamp(24 * 60 * 60 * 1_000_000_000)
+        .build());
+    contract.release_stale_outpoint_reservations(ChainKind::Btc, vec![outpoint]);
+}
+
+#[test]
+fn test_withdraw_with_zero_value_output() {
+    let validator = get_default_contract().get_utxo_chain_validator(ChainKind::Btc);
+    let msg = serde_json::json!({
+        "Withdraw": {
+            "target_btc_address": "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+            "input": [format!("{}:0", "ab".repeat(32))],
+            "output": [{"value": 0, "script_pubkey": format!("0014{}", "11".repeat(20))}],
+            "max_gas_fee": "1000",
+        }
+    })
+    .to_string();
+
+    assert_eq!(
+        validator.parse_withdraw(&msg).err(),
+        Some(BridgeError::ZeroOutputValue)
+    );
+}
+
+fn init_transfer_with_preferred_relayer(contract: &mut Contract) -> TransferId {
+    let mut init_transfer_msg = get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0);
+    init_transfer_msg.preferred_relayer = Some("relayer.testnet".parse().unwrap());
+    run_ft_on_transfer(
+        contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        Some(NearToken::from_near(1)),
+        &BridgeOnTransferMsg::InitTransfer(init_transfer_msg),
+    );
+    contract.add_token(
+        &DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        &OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+        18,
+        18,
+    );
+    TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    }
+}
+
+#[test]
+#[should_panic(expected = "ERR_NOT_PREFERRED_RELAYER")]
+fn test_sign_transfer_not_preferred_relayer() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_transfer_with_preferred_relayer(&mut contract);
+
+    setup_test_env(
+        "other_relayer.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.sign_transfer(transfer_id, None, &None).detach();
+}
+
+#[test]
+fn test_requeued_transfer_keeps_preferred_relayer() {
+    let mut contract = get_default_contract();
+    contract.max_transfer_restores = 0;
+    let transfer_id = init_transfer_with_preferred_relayer(&mut contract);
+    let preferred_relayer = contract.get_preferred_relayer(transfer_id);
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+
+    let transfer = contract.take_transfer_message(transfer_id);
+    let outcome = contract.submit_transfer_to_btc_connector_callback(
+        transfer.message,
+        owner.clone(),
+        None,
+        Vec::new(),
+        None,
+        None,
+        None,
+        &Ok(U128(0)),
+    );
+    assert!(!outcome.restored);
+
+    setup_test_env(owner, NearToken::from_yoctonear(0), None);
+    contract.retry_dead_letter_transfer(transfer_id);
+    assert!(preferred_relayer.is_some());
+    assert_eq!(
+        contract.get_preferred_relayer(transfer_id),
+        preferred_relayer
+    );
+}
+
+#[test]
+fn test_sign_transfer_preferred_relayer() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_transfer_with_preferred_relayer(&mut contract);
+    let preferred_relayer = contract.get_preferred_relayer(transfer_id).unwrap();
+    assert_eq!(preferred_relayer.account_id.as_str(), "relayer.testnet");
+
+    run_storage_deposit(
+        &mut contract,
+        "relayer.testnet".parse().unwrap(),
+        NearToken::from_near(1),
+    );
+    setup_test_env(
+        "relayer.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.sign_transfer(transfer_id, None, &None).detach();
+    assert!(contract
+        .signing_requested_transfers
+        .contains_key(&transfer_id));
+
+    // Any relayer can take the transfer over once the exclusivity window ends
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id("other_relayer.testnet".parse().unwrap())
+        .block_timestamp(preferred_relayer.exclusive_until.0)
+        .build());
+    contract.sign_transfer(transfer_id, None, &None).detach();
+}
+
+fn setup_custody_utxos_for_consolidation(contract: &mut Contract) -> TxOut {
+    let connector: AccountId = "connector.testnet".parse().unwrap();
+    contract.utxo_chain_connectors.insert(
+        ChainKind::Btc,
+        UTXOChainConfig {
+            connector: connector.clone(),
+            token_id: "btc.testnet".parse().unwrap(),
+        },
+    );
+    let output = TxOut {
+        value: 10_000,
+        script_pubkey: "0014e8df018c7e326cc253faac7e46cdc51e68542c42".to_string(),
+    };
+    setup_test_env(connector, NearToken::from_yoctonear(0), None);
+    contract.update_custody_utxos(
+        ChainKind::Btc,
+        (0..3)
+            .map(|vout| CustodyUtxo {
+                outpoint: get_outpoint(vout),
+                output: output.clone(),
+            })
+            .collect(),
+        Vec::new(),
+    );
+    contract.accrue_protocol_fee(&"btc.testnet".parse().unwrap(), U128(1_000));
+
+    setup_dao_env();
+    enable_custody_utxo_tracking(contract);
+    output
+}
+
+#[test]
+fn test_submit_utxo_consolidation() {
+    let mut contract = get_default_contract();
+    let output = setup_custody_utxos_for_consolidation(&mut contract);
+    let inputs = vec![get_outpoint(0), get_outpoint(1)];
+    let consolidated_output = TxOut {
+        value: 19_600,
+        script_pubkey: output.script_pubkey,
+    };
+
+    contract
+        .submit_utxo_consolidation(ChainKind::Btc, inputs.clone(), consolidated_output.clone())
+        .detach();
+    let token_id: AccountId = "btc.testnet".parse().unwrap();
+    assert_eq!(contract.get_protocol_fees(token_id.clone()), U128(600));
+    assert!(contract
+        .get_custody_utxo(ChainKind::Btc, get_outpoint(0))
+        .is_none());
+
+    // The inputs are back in custody and the fee in the pool when the connector rejects it
+    contract.submit_utxo_consolidation_callback(
+        ChainKind::Btc,
+        inputs,
+        vec![output.clone(), output],
+        consolidated_output,
+        U128(400),
+        &Err(PromiseError::Failed),
+    );
+    assert_eq!(contract.get_protocol_fees(token_id), U128(1_000));
+    assert!(contract
+        .get_custody_utxo(ChainKind::Btc, get_outpoint(0))
+        .is_some());
+}
+
+#[test]
+#[should_panic(expected = "ERR_CONNECTOR_INTERFACE_VERSION_NOT_SUPPORTED")]
+fn test_submit_utxo_consolidation_with_old_connector_version() {
+    let mut contract = get_default_contract();
+    let output = setup_custody_utxos_for_consolidation(&mut contract);
+    contract.connector_interface_versions.insert(
+        &ChainKind::Btc,
+        &(CONSOLIDATION_MIN_CONNECTOR_INTERFACE_VERSION - 1),
+    );
+    contract
+        .submit_utxo_consolidation(
+            ChainKind::Btc,
+            vec![get_outpoint(0), get_outpoint(1)],
+            TxOut {
+                value: 19_600,
+                script_pubkey: output.script_pubkey,
+            },
+        )
+        .detach();
+}
+
+#[test]
+#[should_panic(expected = "ERR_CONSOLIDATION_OUTPUT_NOT_IN_CUSTODY")]
+fn test_submit_utxo_consolidation_to_foreign_script() {
+    let mut contract = get_default_contract();
+    setup_custody_utxos_for_consolidation(&mut contract);
+    contract
+        .submit_utxo_consolidation(
+            ChainKind::Btc,
+            vec![get_outpoint(0), get_outpoint(1)],
+            TxOut {
+                value: 19_600,
+                script_pubkey: "00143b1a1ad6e6c3a8f7c0a1c4ae2bb3a7f5e8cf2d11".to_string(),
+            },
+        )
+        .detach();
+}
+
+#[test]
+#[should_panic(expected = "ERR_INSUFFICIENT_PROTOCOL_FEES")]
+fn test_submit_utxo_consolidation_fee_above_protocol_fees() {
+    let mut contract = get_default_contract();
+    let output = setup_custody_utxos_for_consolidation(&mut contract);
+    contract
+        .submit_utxo_consolidation(
+            ChainKind::Btc,
+            vec![get_outpoint(0), get_outpoint(1), get_outpoint(2)],
+            TxOut {
+                value: 28_000,
+                script_pubkey: output.script_pubkey,
+            },
+        )
+        .detach();
+}
+
+#[test]
+fn test_scan_and_repair_orphans() {
+    let mut contract = get_default_contract();
+    init_default_transfer(&mut contract);
+    let orphan = TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: 100,
+    };
+    contract.index_pending_transfer(ChainKind::Eth, orphan);
+    let collection = OrphanCollection::PendingTransfersByChain(ChainKind::Eth);
+
+    let scan = contract.scan_orphans(collection.clone(), 0, 1);
+    assert!(scan.orphans.is_empty());
+    assert_eq!(scan.next_index, Some(1));
+    let scan = contract.scan_orphans(collection.clone(), 1, 10);
+    assert_eq!(scan.orphans, vec![orphan]);
+    assert_eq!(scan.next_index, None);
+
+    setup_dao_env();
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    };
+    assert_eq!(
+        contract.repair_orphans(collection.clone(), vec![orphan, transfer_id]),
+        1
+    );
+    assert_eq!(contract.get_pending_transfers_count(ChainKind::Eth), 1);
+    assert!(contract.scan_orphans(collection, 0, 10).orphans.is_empty());
+}
+
+#[test]
+fn test_scan_orphans_of_owner() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+    let other_owner: AccountId = "other_owner.testnet".parse().unwrap();
+    contract.index_pending_transfer_owner(&other_owner, transfer_id);
+
+    let scan = contract.scan_orphans(
+        OrphanCollection::PendingTransfersByOwner(other_owner),
+        0,
+        10,
+    );
+    assert_eq!(scan.orphans, vec![transfer_id]);
+    assert!(contract
+        .scan_orphans(
+            OrphanCollection::PendingTransfersByOwner(DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap()),
+            0,
+            10
+        )
+        .orphans
+        .is_empty());
+}
+
+#[test]
+fn test_scan_unindexed_transfers() {
+    let mut contract = get_default_contract();
+    let indexed_transfer_id = init_default_transfer(&mut contract);
+    let transfer_id = init_default_transfer(&mut contract);
+    contract.unindex_pending_transfer(ChainKind::Eth, &transfer_id);
+
+    let scan = contract.scan_unindexed_transfers(ChainKind::Near, 0, 10);
+    assert_eq!(scan.transfer_ids, vec![transfer_id]);
+    assert_eq!(scan.next_nonce, None);
+    let scan =
+        contract.scan_unindexed_transfers(ChainKind::Near, 0, indexed_transfer_id.origin_nonce);
+    assert!(scan.transfer_ids.is_empty());
+    assert_eq!(scan.next_nonce, Some(indexed_transfer_id.origin_nonce));
+
+    contract.index_pending_transfers(vec![transfer_id]);
+    assert!(contract
+        .scan_unindexed_transfers(ChainKind::Near, 0, 10)
+        .transfer_ids
+        .is_empty());
+}
+
+#[test]
+fn test_record_utxo_withdrawal_txid() {
+    let mut contract = get_default_contract();
+    let connector: AccountId = "connector.testnet".parse().unwrap();
+    contract.utxo_chain_connectors.insert(
+        ChainKind::Btc,
+        UTXOChainConfig {
+            connector: connector.clone(),
+            token_id: "btc.testnet".parse().unwrap(),
+        },
+    );
+    let transfer_id = forward_utxo_withdrawal(&mut contract);
+    assert_eq!(contract.get_expected_utxo_txid(transfer_id), None);
+
+    setup_test_env(connector, NearToken::from_yoctonear(0), None);
+    contract.record_utxo_withdrawal_txid(
+        ChainKind::Btc,
+        transfer_id,
+        UTXO_WITHDRAWAL_TX.to_string(),
+    );
+    assert_eq!(
+        contract.get_expected_utxo_txid(transfer_id),
+        Some(UTXO_WITHDRAWAL_TXID.to_string())
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_TRANSACTION_NOT_WITHDRAWAL")]
+fn test_record_utxo_withdrawal_txid_of_other_transaction() {
+    let mut contract = get_default_contract();
+    setup_rbf_connector(&mut contract, None);
+    let transfer_id = forward_utxo_withdrawal(&mut contract);
+
+    // The output of the transaction pays 1 more than the forwarded one
+    let tx = UTXO_WITHDRAWAL_TX.replacen("905f01", "915f01", 1);
+    setup_test_env(
+        "connector.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.record_utxo_withdrawal_txid(ChainKind::Btc, transfer_id, tx);
+}
+
+#[test]
+fn test_remove_utxo_withdrawal() {
+    let mut contract = get_default_contract();
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    let transfer_id = forward_utxo_withdrawal(&mut contract);
+    let available = contract.storage_balance_of(&owner).unwrap().available;
+
+    setup_test_env(owner.clone(), NearToken::from_yoctonear(0), None);
+    contract.remove_utxo_withdrawal(transfer_id);
+    assert!(contract.utxo_withdrawals.get(&transfer_id).is_none());
+    assert_eq!(
+        contract.storage_balance_of(&owner).unwrap().available,
+        available.saturating_add(Contract::required_balance_for_utxo_withdrawal(
+            &transfer_id,
+            &owner
+        ))
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_WITHDRAWAL_REMOVAL_NOT_ALLOWED")]
+fn test_remove_utxo_withdrawal_by_other_account() {
+    let mut contract = get_default_contract();
+    let transfer_id = forward_utxo_withdrawal(&mut contract);
+
+    setup_test_env(
+        "relayer.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.remove_utxo_withdrawal(transfer_id);
+}
+
+// SegWit transaction spending `get_outpoint(0)` into the output of `forward_utxo_withdrawal`
+const UTXO_WITHDRAWAL_TX: &str = "0200000000010101010101010101010101010101010101010101010101010101010101010101010000000000fdffffff01905f010000000000160014e8df018c7e326cc253faac7e46cdc51e68542c4202483030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030302102020202020202020202020202020202020202020202020202020202020202020200000000";
+const UTXO_WITHDRAWAL_TXID: &str =
+    "23b3f9055f2d4d98dd166f8d581179377140d5ffac8d50b5d0f7cc369254a5bd";
+
+// Forwards a transfer of the default user to Bitcoin, whose record is paid from the storage
+// balance of the user.
+fn forward_utxo_withdrawal(contract: &mut Contract) -> TransferId {
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    let transfer_id = init_default_transfer(contract);
+    let mut transfer_message = contract.remove_transfer_message(transfer_id);
+    transfer_message.recipient =
+        OmniAddress::Btc("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string());
+    let available = contract.storage_balance_of(&owner).unwrap().available;
+
+    contract.submit_transfer_to_btc_connector_callback(
+        transfer_message.clone(),
+        owner.clone(),
+        Some("relayer.testnet".parse().unwrap()),
+        vec![get_outpoint(0)],
+        None,
+        None,
+        Some(vec![TxOut {
+            value: 90_000,
+            script_pubkey: "0014e8df018c7e326cc253faac7e46cdc51e68542c42".to_string(),
+        }]),
+        &Ok(transfer_message.amount),
+    );
+    assert!(contract.utxo_withdrawals.get(&transfer_id).is_some());
+    assert_eq!(
+        contract.storage_balance_of(&owner).unwrap().available,
+        available
+            .saturating_add(Contract::required_balance_for_transfer_timestamps(
+                &transfer_id
+            ))
+            .saturating_sub(Contract::required_balance_for_utxo_withdrawal(
+                &transfer_id,
+                &owner
+            ))
+    );
+    transfer_id
+}
+
+fn record_forwarded_utxo_withdrawal_txid(contract: &mut Contract) -> TransferId {
+    setup_rbf_connector(contract, None);
+    let transfer_id = forward_utxo_withdrawal(contract);
+    setup_test_env(
+        "connector.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.record_utxo_withdrawal_txid(
+        ChainKind::Btc,
+        transfer_id,
+        UTXO_WITHDRAWAL_TX.to_string(),
+    );
+    transfer_id
+}
+
+#[test]
+fn test_bump_utxo_withdrawal_fee_by_owner() {
+    let mut contract = get_default_contract();
+    let transfer_id = record_forwarded_utxo_withdrawal_txid(&mut contract);
+
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract
+        .bump_utxo_withdrawal_fee(
+            transfer_id,
+            vec![TxOut {
+                value: 90,
+                script_pubkey: "0014e8df018c7e326cc253faac7e46cdc51e68542c42".to_string(),
+            }],
+        )
+        .detach();
+}
+
+#[test]
+#[should_panic(expected = "ERR_FEE_BUMP_NOT_ALLOWED")]
+fn test_bump_utxo_withdrawal_fee_by_other_account() {
+    let mut contract = get_default_contract();
+    let transfer_id = record_forwarded_utxo_withdrawal_txid(&mut contract);
+
+    setup_test_env(
+        "relayer.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract
+        .bump_utxo_withdrawal_fee(transfer_id, Vec::new())
+        .detach();
+}
+
+#[test]
+#[should_panic(expected = "ERR_WITHDRAWAL_TXID_NOT_RECORDED")]
+fn test_bump_utxo_withdrawal_fee_before_txid_report() {
+    let mut contract = get_default_contract();
+    setup_rbf_connector(&mut contract, None);
+    let transfer_id = forward_utxo_withdrawal(&mut contract);
+
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract
+        .bump_utxo_withdrawal_fee(transfer_id, Vec::new())
+        .detach();
+}
+
+#[test]
+#[should_panic(expected = "ERR_TXID_REPORT_NOT_ALLOWED")]
+fn test_record_utxo_withdrawal_txid_not_connector() {
+    let mut contract = get_default_contract();
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.record_utxo_withdrawal_txid(
+        ChainKind::Btc,
+        TransferId {
+            origin_chain: ChainKind::Near,
+            origin_nonce: 1,
+        },
+        "abc94fc5b954136a691594c7044bcfa6c6f127cdb0802ac8b97c0117482f2305".to_string(),
+    );
+}
+
+#[test]
+fn test_utxo_fin_transfer_records_first_origin_senders() {
+    let mut contract = get_default_contract();
+    let connector: AccountId = "connector.testnet".parse().unwrap();
+    contract.utxo_chain_connectors.insert(
+        ChainKind::Btc,
+        UTXOChainConfig {
+            connector: connector.clone(),
+            token_id: "btc.testnet".parse().unwrap(),
+        },
+    );
+    run_storage_deposit(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_near(1),
+    );
+    let sender_addresses: Vec<String> = (0..10)
+        .map(|_| "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string())
+        .collect();
+    let utxo_fin_transfer_msg = get_utxo_fin_transfer_msg(sender_addresses);
+
+    setup_test_env(
+        "btc.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.ft_on_transfer(
+        connector,
+        U128(10_000),
+        serde_json::to_string(&BridgeOnTransferMsg::UtxoFinTransfer(
+            utxo_fin_transfer_msg.clone(),
+        ))
+        .unwrap(),
+    );
+
+    let origin_senders = contract
+        .get_transfer_origin_senders(utxo_fin_transfer_msg.get_transfer_id(ChainKind::Btc).into())
+        .unwrap();
+    assert_eq!(origin_senders.len(), MAX_ORIGIN_SENDERS);
+}
+
+fn run_fin_transfer_to_derived_account(
+    contract: &mut Contract,
+    derived_account_funding: NearToken,
+) -> NearToken {
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+    let sender = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+    let derived_account = sender.get_implicit_near_account_id().unwrap();
+    contract.factories.insert(&ChainKind::Eth, &sender);
+    contract.token_address_to_id.insert(
+        &OmniAddress::new_zero(ChainKind::Eth).unwrap(),
+        &DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+    );
+    contract.token_decimals.insert(
+        &OmniAddress::Near(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap()),
+        &Decimals {
+            decimals: 24,
+            origin_decimals: 24,
+        },
+    );
+    contract.derived_account_funding = derived_account_funding;
+    run_storage_deposit(contract, relayer.clone(), NearToken::from_near(1));
+
+    let storage_actions: Vec<StorageDepositAction> =
+        [derived_account.clone(), relayer.clone(), relayer.clone()]
+            .into_iter()
+            .map(|account_id| StorageDepositAction {
+                token_id: DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+                account_id,
+                storage_deposit_amount: Some(NEP141_DEPOSIT.as_yoctonear()),
+            })
+            .collect();
+    let storage_balance_result = PromiseResult::Successful(
+        serde_json::to_vec(&Some(StorageBalance {
+            total: NearToken::from_near(1),
+            available: NearToken::from_near(1),
+        }))
+        .unwrap(),
+    );
+    let prover_result = get_prover_result(Some(OmniAddress::Near(derived_account)));
+    setup_test_env(
+        relayer.clone(),
+        NearToken::from_yoctonear(0),
+        Some(vec![
+            PromiseResult::Successful(borsh::to_vec(&prover_result).unwrap()),
+            storage_balance_result.clone(),
+            storage_balance_result.clone(),
+            storage_balance_result,
+        ]),
+    );
+    contract.fin_transfer_callback(&storage_actions, relayer.clone());
+
+    NearToken::from_near(1).saturating_sub(contract.storage_balance_of(&relayer).unwrap().available)
+}
+
+#[test]
+fn test_fin_transfer_to_derived_account_charges_relayer() {
+    let mut unfunded_contract = get_default_contract();
+    let unfunded_cost =
+        run_fin_transfer_to_derived_account(&mut unfunded_contract, NearToken::from_yoctonear(0));
+
+    let mut contract = get_default_contract();
+    let funding = NearToken::from_millinear(100);
+    let cost = run_fin_transfer_to_derived_account(&mut contract, funding);
+
+    let derived_account = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap())
+        .get_implicit_near_account_id()
+        .unwrap();
+    assert!(contract.is_derived_account_funded(derived_account.clone()));
+    assert!(!unfunded_contract.is_derived_account_funded(derived_account));
+
+    // The relayer pays the funding and the storage of the funded marker on top of the regular cost.
+    let extra_cost = cost.saturating_sub(unfunded_cost);
+    assert!(extra_cost > funding);
+    assert!(extra_cost < funding.saturating_add(NearToken::from_millinear(1)));
+}
+
+#[test]
+fn test_connector_rejection_counted_for_trusted_submitters_only() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+
+    for (submitter, restore_count) in [("relayer.testnet", 0), (DEFAULT_NEAR_USER_ACCOUNT, 1)] {
+        let transfer_message = contract.remove_transfer_message(transfer_id);
+        let outcome = contract.submit_transfer_to_btc_connector_callback(
+            transfer_message,
+            owner.clone(),
+            None,
+            Vec::new(),
+            None,
+            Some(submitter.parse().unwrap()),
+            None,
+            &Ok(U128(0)),
+        );
+        assert!(outcome.restored);
+        assert_eq!(
+            contract.get_transfer_restore_count(transfer_id),
+            restore_count
+        );
+    }
+}
+
+#[test]
+fn test_connector_rejection_not_counted_for_owner_of_transfer_from_other_chain() {
+    let mut contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut contract);
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+
+    // The owner of a transfer from another chain is the relayer that finalised it
+    let mut transfer_message = contract.remove_transfer_message(transfer_id);
+    transfer_message.sender =
+        OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+    let outcome = contract.submit_transfer_to_btc_connector_callback(
+        transfer_message.clone(),
+        owner.clone(),
+        None,
+        Vec::new(),
+        None,
+        Some(owner),
+        None,
+        &Ok(U128(0)),
+    );
+    assert!(outcome.restored);
+    assert_eq!(
+        contract.get_transfer_restore_count(transfer_message.get_transfer_id()),
+        0
+    );
+}
+
+#[test]
+fn test_dead_letter_transfer_retry_and_refund_by_owner() {
+    let mut contract = get_default_contract();
+    contract.max_transfer_restores = 0;
+    let transfer_id = init_default_transfer(&mut contract);
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+
+    let transfer_message = contract.remove_transfer_message(transfer_id);
+    assert!(!contract.restore_transfer(transfer_message, owner.clone(), true));
+
+    setup_test_env(owner.clone(), NearToken::from_yoctonear(0), None);
+    contract.retry_dead_letter_transfer(transfer_id);
+    assert!(contract.pending_transfers.get(&transfer_id).is_some());
+
+    let transfer_message = contract.remove_transfer_message(transfer_id);
+    assert!(!contract.restore_transfer(transfer_message, owner.clone(), true));
+
+    setup_test_env(owner, NearToken::from_yoctonear(0), None);
+    contract.refund_dead_letter_transfer(transfer_id);
+    assert!(contract.get_dead_letter_transfer(transfer_id).is_none());
+    assert_eq!(contract.get_locked_breakdown(token_id).dead_letter, U128(0));
+}
+
+#[test]
+fn test_dead_letter_transfer_failed_refund_restores_transfer() {
+    let mut contract = get_default_contract();
+    contract.max_transfer_restores = 0;
+    let transfer_id = init_default_transfer(&mut contract);
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+
+    let transfer_message = contract.remove_transfer_message(transfer_id);
+    assert!(!contract.restore_transfer(transfer_message.clone(), owner.clone(), true));
+
+    setup_test_env(owner.clone(), NearToken::from_yoctonear(0), None);
+    contract.refund_dead_letter_transfer(transfer_id).detach();
+    assert!(contract.get_dead_letter_transfer(transfer_id).is_none());
+
+    contract.refund_dead_letter_transfer_callback(
+        transfer_message,
+        owner.clone(),
+        &Err(PromiseError::Failed),
+    );
+    assert_eq!(
+        contract
+            .get_dead_letter_transfer(transfer_id)
+            .map(|transfer| transfer.owner),
+        Some(owner)
+    );
+    assert_eq!(
+        contract.get_locked_breakdown(token_id).dead_letter,
+        U128(DEFAULT_TRANSFER_AMOUNT)
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_ONLY_TRANSFER_SENDER_CAN_CANCEL")]
+fn test_dead_letter_transfer_refund_from_other_chain() {
+    let mut contract = get_default_contract();
+    contract.max_transfer_restores = 0;
+    let transfer_id = init_default_transfer(&mut contract);
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+
+    // A transfer from another chain is owned by the relayer that finalised it
+    let mut transfer_message = contract.remove_transfer_message(transfer_id);
+    transfer_message.sender =
+        OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+    let transfer_id = transfer_message.get_transfer_id();
+    assert!(!contract.restore_transfer(transfer_message, owner.clone(), true));
+
+    setup_test_env(owner, NearToken::from_yoctonear(0), None);
+    contract.refund_dead_letter_transfer(transfer_id);
+}
+
+#[test]
+#[should_panic(expected = "ERR_ONLY_TRANSFER_OWNER_OR_DAO")]
+fn test_dead_letter_transfer_retry_by_other_account() {
+    let mut contract = get_default_contract();
+    contract.max_transfer_restores = 0;
+    let transfer_id = init_default_transfer(&mut contract);
+    let transfer_message = contract.remove_transfer_message(transfer_id);
+    contract.restore_transfer(
+        transfer_message,
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        true,
+    );
+
+    setup_test_env(
+        "relayer.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.retry_dead_letter_transfer(transfer_id);
+}
+
+fn setup_rbf_connector(contract: &mut Contract, version: Option<u32>) {
+    let connector: AccountId = "connector.testnet".parse().unwrap();
+    contract.utxo_chain_connectors.insert(
+        ChainKind::Btc,
+        UTXOChainConfig {
+            connector: connector.clone(),
+            token_id: "btc.testnet".parse().unwrap(),
+        },
+    );
+    if let Some(version) = version {
+        setup_test_env(connector, NearToken::from_yoctonear(0), None);
+        contract.set_connector_interface_version(ChainKind::Btc, version);
+    }
+    setup_dao_env();
+}
+
+#[test]
+fn test_rbf_with_undeclared_connector_version() {
+    let mut contract = get_default_contract();
+    // The connectors that never declared their version support the fee bump requests
+    setup_rbf_connector(&mut contract, None);
+    contract.rbf_increase_gas_fee(ChainKind::Btc, "pending_id".to_string(), Vec::new());
+}
+
+#[test]
+#[should_panic(expected = "ERR_CONNECTOR_INTERFACE_VERSION_NOT_SUPPORTED")]
+fn test_rbf_with_old_connector_version() {
+    let mut contract = get_default_contract();
+    setup_rbf_connector(&mut contract, Some(1));
+    contract.rbf_increase_gas_fee(ChainKind::Btc, "pending_id".to_string(), Vec::new());
+}
+
+// The first Bitcoin vector pays 90000 to the recipient with a network fee of at most 1000.
+const BTC_WITHDRAWAL_AMOUNT: u128 = 91_000;
+
+// Submits the first Bitcoin vector for a transfer of `amount`, once `configure` has set up
+// the checks of the withdrawal as the DAO.
+fn submit_btc_withdrawal(
+    contract: &mut Contract,
+    amount: u128,
+    configure: impl FnOnce(&mut Contract),
+) -> TransferMessage {
+    submit_btc_withdrawal_msg(contract, amount, WITHDRAW_MSG_VECTORS[0].msg, configure)
+}
+
+// Submits a `Withdraw` message to the recipient of the first Bitcoin vector.
+fn submit_btc_withdrawal_msg(
+    contract: &mut Contract,
+    amount: u128,
+    msg: &str,
+    configure: impl FnOnce(&mut Contract),
+) -> TransferMessage {
+    let withdraw_msg = &WITHDRAW_MSG_VECTORS[0];
+    run_ft_on_transfer(
+        contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(amount),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0)),
+    );
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    };
+    let mut transfer = contract.get_transfer_message_storage(transfer_id);
+    transfer.message.recipient = OmniAddress::Btc(withdraw_msg.target_address.to_string());
+    contract.insert_raw_transfer(transfer.message.clone(), transfer.owner);
+    contract.utxo_chain_connectors.insert(
+        ChainKind::Btc,
+        UTXOChainConfig {
+            connector: "connector.testnet".parse().unwrap(),
+            token_id: DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        },
+    );
+
+    setup_dao_env();
+    configure(contract);
+
+    // The relayer pays for the storage of the reservation of the input
+    setup_test_env(
+        "relayer.testnet".parse().unwrap(),
+        NearToken::from_millinear(10),
+        None,
+    );
+    contract
+        .submit_transfer_to_utxo_chain_connector(transfer_id, msg.to_string(), None, &None)
+        .detach();
+    assert!(contract.pending_transfers.get(&transfer_id).is_none());
+    transfer.message
+}
+
+fn submit_btc_withdrawal_with_limits(contract: &mut Contract, limits: UtxoWithdrawLimits) {
+    submit_btc_withdrawal(contract, BTC_WITHDRAWAL_AMOUNT, |contract| {
+        contract.set_utxo_withdraw_limits(ChainKind::Btc, limits);
+    });
+}
+
+#[test]
+fn test_submit_withdrawal_within_limits() {
+    let mut contract = get_default_contract();
+    let withdraw_msg = &WITHDRAW_MSG_VECTORS[0];
+    submit_btc_withdrawal_with_limits(
+        &mut contract,
+        UtxoWithdrawLimits {
+            max_inputs: Some(withdraw_msg.input.len().try_into().unwrap()),
+            max_outputs: Some(withdraw_msg.output.len().try_into().unwrap()),
+            max_msg_size: Some(withdraw_msg.msg.len().try_into().unwrap()),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_TOO_MANY_WITHDRAW_INPUTS")]
+fn test_submit_withdrawal_above_input_limit() {
+    let mut contract = get_default_contract();
+    let withdraw_msg = &WITHDRAW_MSG_VECTORS[0];
+    submit_btc_withdrawal_with_limits(
+        &mut contract,
+        UtxoWithdrawLimits {
+            max_inputs: Some((withdraw_msg.input.len() - 1).try_into().unwrap()),
+            ..UtxoWithdrawLimits::default()
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_TOO_MANY_WITHDRAW_OUTPUTS")]
+fn test_submit_withdrawal_above_output_limit() {
+    let mut contract = get_default_contract();
+    let withdraw_msg = &WITHDRAW_MSG_VECTORS[0];
+    submit_btc_withdrawal_with_limits(
+        &mut contract,
+        UtxoWithdrawLimits {
+            max_outputs: Some((withdraw_msg.output.len() - 1).try_into().unwrap()),
+            ..UtxoWithdrawLimits::default()
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_WITHDRAW_MSG_TOO_LARGE")]
+fn test_submit_withdrawal_above_msg_size_limit() {
+    let mut contract = get_default_contract();
+    let withdraw_msg = &WITHDRAW_MSG_VECTORS[0];
+    submit_btc_withdrawal_with_limits(
+        &mut contract,
+        UtxoWithdrawLimits {
+            max_msg_size: Some((withdraw_msg.msg.len() - 1).try_into().unwrap()),
+            ..UtxoWithdrawLimits::default()
+        },
+    );
+}
+
+// Submits the first Bitcoin vector, which pays 90000 to the recipient and 9000 back to the
+// script of its input, for a transfer of `amount` from an input of 100000 in custody.
+fn submit_btc_withdrawal_from_custody(contract: &mut Contract, amount: u128) -> TransferMessage {
+    let withdraw_msg = &WITHDRAW_MSG_VECTORS[0];
+    submit_btc_withdrawal(contract, amount, |contract| {
+        contract.update_custody_utxos(
+            ChainKind::Btc,
+            vec![CustodyUtxo {
+                outpoint: withdraw_msg.input[0].parse().unwrap(),
+                output: TxOut {
+                    value: 100_000,
+                    script_pubkey: withdraw_msg.output[1].1.to_string(),
+                },
+            }],
+            Vec::new(),
+        );
+        enable_custody_utxo_tracking(contract);
+    })
+}
+
+#[test]
+fn test_submit_withdrawal_outputs_matching_amount() {
+    let mut contract = get_default_contract();
+    // The network fee of 1000 is taken from the amount
+    submit_btc_withdrawal_from_custody(&mut contract, 91_000);
+}
+
+#[test]
+#[should_panic(expected = "ERR_WITHDRAW_SPENDS_ABOVE_AMOUNT")]
+fn test_submit_withdrawal_outputs_above_amount() {
+    let mut contract = get_default_contract();
+    submit_btc_withdrawal_from_custody(&mut contract, 90_000);
+}
+
+#[test]
+#[should_panic(expected = "ERR_WITHDRAW_PAYOUT_BELOW_AMOUNT")]
+fn test_submit_withdrawal_outputs_below_amount() {
+    let mut contract = get_default_contract();
+    submit_btc_withdrawal_from_custody(&mut contract, 95_000);
+}
+
+#[test]
+#[should_panic(expected = "ERR_WITHDRAW_PAYOUT_BELOW_AMOUNT")]
+fn test_submit_withdrawal_below_amount_without_custody_tracking() {
+    let mut contract = get_default_contract();
+    submit_btc_withdrawal(&mut contract, 95_000, |_| {});
+}
+
+#[test]
+#[should_panic(expected = "ERR_NO_OUTPUT_TO_RECIPIENT")]
+fn test_submit_withdrawal_paying_other_script() {
+    let mut contract = get_default_contract();
+    let msg = serde_json::json!({
+        "Withdraw": {
+            "target_btc_address": WITHDRAW_MSG_VECTORS[0].target_address,
+            "input": WITHDRAW_MSG_VECTORS[0].input,
+            "output": [{"value": 90_000, "script_pubkey": format!("0014{}", "11".repeat(20))}],
+            "max_gas_fee": "1000",
+        }
+    })
+    .to_string();
+    submit_btc_withdrawal_msg(&mut contract, BTC_WITHDRAWAL_AMOUNT, &msg, |_| {});
+}
+
+// Submits a transfer of the default amount to Bitcoin for each payout, with a network fee
+// of at most 20 for the batch.
+fn submit_btc_batch_withdrawal(
+    contract: &mut Contract,
+    connector_version: u32,
+    payouts: &[u64],
+) -> Vec<TransferMessage> {
+    let outputs: Vec<(u64, &str)> = payouts
+        .iter()
+        .map(|payout| (*payout, WITHDRAW_MSG_VECTORS[0].output[0].1))
+        .collect();
+    submit_btc_batch_withdrawal_outputs(
+        contract,
+        connector_version,
+        &outputs,
+        NearToken::from_millinear(10),
+    )
+}
+
+// Submits a transfer to the recipient of the first Bitcoin vector for each output, with
+// `deposit` attached for the storage of the reservation of the input.
+fn submit_btc_batch_withdrawal_outputs(
+    contract: &mut Contract,
+    connector_version: u32,
+    outputs: &[(u64, &str)],
+    deposit: NearToken,
+) -> Vec<TransferMessage> {
+    let target_address = WITHDRAW_MSG_VECTORS[0].target_address;
+    let transfer_messages: Vec<TransferMessage> = outputs
+        .iter()
+        .map(|_| {
+            let transfer_id = init_default_transfer(contract);
+            let mut transfer = contract.get_transfer_message_storage(transfer_id);
+            transfer.message.recipient = OmniAddress::Btc(target_address.to_string());
+            contract.insert_raw_transfer(transfer.message.clone(), transfer.owner);
+            transfer.message
+        })
+        .collect();
+    let connector: AccountId = "connector.testnet".parse().unwrap();
+    contract.utxo_chain_connectors.insert(
+        ChainKind::Btc,
+        UTXOChainConfig {
+            connector: connector.clone(),
+            token_id: DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        },
+    );
+    setup_test_env(connector, NearToken::from_yoctonear(0), None);
+    contract.set_connector_interface_version(ChainKind::Btc, connector_version);
+
+    let msg = serde_json::json!({
+        "BatchWithdraw": {
+            "target_btc_addresses": vec![target_address; outputs.len()],
+            "input": [format!("{}:0", "ab".repeat(32))],
+            "output": outputs
+                .iter()
+                .map(|(value, script_pubkey)| serde_json::json!({
+                    "value": value,
+                    "script_pubkey": script_pubkey,
+                }))
+                .collect::<Vec<_>>(),
+            "max_gas_fee": "20",
+        }
+    })
+    .to_string();
+    setup_test_env("relayer.testnet".parse().unwrap(), deposit, None);
+    contract
+        .submit_transfers_to_utxo_chain_connector(
+            transfer_messages
+                .iter()
+                .map(TransferMessage::get_transfer_id)
+                .collect(),
+            msg,
+            Some("relayer.testnet".parse().unwrap()),
+        )
+        .detach();
+    transfer_messages
+}
+
+#[test]
+fn test_submit_batch_withdrawal() {
+    let mut contract = get_default_contract();
+    // The transfers pay 10 and 5 of the network fee
+    let transfer_messages = submit_btc_batch_withdrawal(&mut contract, 5, &[90, 95]);
+    for transfer_message in &transfer_messages {
+        assert!(contract
+            .pending_transfers
+            .get(&transfer_message.get_transfer_id())
+            .is_none());
+    }
+    // The input is reserved by the withdrawal of both transfers
+    let input: OutPoint = format!("{}:0", "ab".repeat(32)).parse().unwrap();
+    assert_eq!(
+        contract.get_outpoint_reservation(ChainKind::Btc, input),
+        transfer_messages
+            .iter()
+            .map(TransferMessage::get_transfer_id)
+            .collect::<Vec<_>>()
+    );
+
+    let connector_id = contract.chain_connector_id(ChainKind::Btc);
+    let outcomes = contract.submit_transfers_to_utxo_connector_callback(
+        transfer_messages
+            .iter()
+            .map(|transfer_message| UtxoBatchTransfer {
+                message: transfer_message.clone(),
+                owner: DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+                fee_recipient: Some("relayer.testnet".parse().unwrap()),
+                forwarded_amount: U128(DEFAULT_TRANSFER_AMOUNT),
+            })
+            .collect(),
+        vec![format!("{}:0", "ab".repeat(32)).parse().unwrap()],
+        Vec::new(),
+        connector_id,
+        "relayer.testnet".parse().unwrap(),
+        &Ok(U128(2 * DEFAULT_TRANSFER_AMOUNT)),
+    );
+    assert_eq!(outcomes.len(), 2);
+    assert!(outcomes
+        .iter()
+        .all(|outcome| outcome.status == SubmitStatus::Forwarded
+            && outcome.forwarded_amount == U128(DEFAULT_TRANSFER_AMOUNT)));
+    for transfer_message in &transfer_messages {
+        assert!(contract.is_transfer_claimed(transfer_message.get_transfer_id()));
+    }
+}
+
+#[test]
+#[should_panic(expected = "ERR_CONNECTOR_INTERFACE_VERSION_NOT_SUPPORTED")]
+fn test_submit_batch_withdrawal_with_old_connector_version() {
+    let mut contract = get_default_contract();
+    submit_btc_batch_withdrawal(&mut contract, 4, &[90, 95]);
+}
+
+#[test]
+#[should_panic(expected = "ERR_BATCH_FEE_ABOVE_MAX_FEE")]
+fn test_submit_batch_withdrawal_fee_above_max_fee() {
+    let mut contract = get_default_contract();
+    submit_btc_batch_withdrawal(&mut contract, 5, &[85, 85]);
+}
+
+#[test]
+#[should_panic(expected = "ERR_BATCH_OUTPUT_NOT_TO_RECIPIENT")]
+fn test_submit_batch_withdrawal_output_to_other_script() {
+    let mut contract = get_default_contract();
+    let other_script = format!("0014{}", "11".repeat(20));
+    submit_btc_batch_withdrawal_outputs(
+        &mut contract,
+        5,
+        &[
+            (90, WITHDRAW_MSG_VECTORS[0].output[0].1),
+            (95, other_script.as_str()),
+        ],
+        NearToken::from_millinear(10),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Account relayer.testnet is not registered")]
+fn test_submit_batch_withdrawal_without_storage_deposit() {
+    let mut contract = get_default_contract();
+    submit_btc_batch_withdrawal_outputs(
+        &mut contract,
+        5,
+        &[
+            (90, WITHDRAW_MSG_VECTORS[0].output[0].1),
+            (95, WITHDRAW_MSG_VECTORS[0].output[0].1),
+        ],
+        NearToken::from_yoctonear(0),
+    );
+}
+
+fn get_fee_sponsor_limits() -> FeeSponsorLimits {
+    FeeSponsorLimits {
+        fee_per_transfer: U128(100),
+        min_transfer_amount: U128(0),
+        max_daily_fees: None,
+    }
+}
+
+#[test]
+fn test_register_fee_sponsor_by_recipient() {
+    let mut contract = get_default_contract();
+    let recipient: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    setup_test_env(recipient.clone(), NearToken::from_near(1), None);
+    contract.register_fee_sponsor(
+        OmniAddress::Near(recipient.clone()),
+        DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        get_fee_sponsor_limits(),
+    );
+
+    let sponsor = contract
+        .get_fee_sponsor(
+            OmniAddress::Near(recipient.clone()),
+            DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        )
+        .unwrap();
+    assert_eq!(sponsor.owner, recipient);
+}
+
+#[test]
+#[should_panic(expected = "ERR_FEE_SPONSOR_NOT_ALLOWED")]
+fn test_register_fee_sponsor_for_other_recipient() {
+    let mut contract = get_default_contract();
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_near(1),
+        None,
+    );
+    contract.register_fee_sponsor(
+        OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+        DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        get_fee_sponsor_limits(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_TRANSFER_HELD_FOR_REVIEW")]
+fn test_cancel_flagged_transfer_with_beneficiary() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    let transfer_id = flag_default_transfer(&mut contract);
+    contract.transfer_beneficiaries.insert(
+        &transfer_id,
+        &TransferBeneficiaryRecord {
+            beneficiary: TransferBeneficiary {
+                account_id: DEFAULT_WNEAR_ACCOUNT.parse().unwrap(),
+                public_key: "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+                    .parse()
+                    .unwrap(),
+            },
+            signing_requested: false,
+        },
+    );
+
+    contract.cancel_transfer(transfer_id, vec![0; 64].into());
+}
+
+#[test]
+fn test_maintenance_rebate_scales_with_processed_items() {
+    let mut contract = get_default_contract();
+    let keeper: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    contract.maintenance_config = Some(MaintenanceConfig {
+        rebate_per_tgas: NearToken::from_millinear(1),
+        max_rebate_per_item: NearToken::from_yoctonear(10),
+        max_rebate_per_call: NearToken::from_millinear(5),
+    });
+    contract.maintenance_budget = NearToken::from_millinear(3);
+    contract.accounts_balances.insert(
+        &keeper,
+        &StorageBalance {
+            total: NearToken::from_yoctonear(0),
+            available: NearToken::from_yoctonear(0),
+        },
+    );
+    contract.transfer_timestamps.insert(
+        &DEFAULT_TRANSFER_ID,
+        &TransferTimestamps {
+            initiated_at: U64(0),
+            submitted_at: None,
+            finalized_at: Some(U64(0)),
+        },
+    );
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id(keeper)
+        .block_timestamp(31 * 24 * 60 * 60 * 1_000_000_000)
+        .build());
+
+    // The unknown transfers burn gas without being processed
+    let mut transfer_ids: Vec<TransferId> = (1..=50)
+        .map(|origin_nonce| TransferId {
+            origin_chain: ChainKind::Near,
+            origin_nonce: DEFAULT_NONCE + origin_nonce,
+        })
+        .collect();
+    transfer_ids.push(DEFAULT_TRANSFER_ID);
+
+    let rebate = contract.prune_transfer_timestamps(transfer_ids);
+    assert!(rebate <= NearToken::from_yoctonear(10));
+}
+
+// The ed25519 key of the sender is derived from the [7; 32] seed. The signature is made over the
+// claim payload for the `bridge.testnet` locker and the default recipient.
+const UNCLAIMED_DEPOSIT_SOL_SENDER: [u8; 32] = [
+    234, 74, 108, 99, 226, 156, 82, 10, 190, 245, 80, 123, 19, 46, 197, 249, 149, 71, 118, 174,
+    190, 190, 123, 146, 66, 30, 234, 105, 20, 70, 210, 44,
+];
+const UNCLAIMED_DEPOSIT_CLAIM_SIGNATURE: &str = "c60700d582a936d25eb635b29bcb3c89455e9a63f83c1c15c7329863ecb01a0d54b635f1f7a7b16759de6409e37c196c12a47090ab492c7e2e1b9615d7320208";
+
+fn fin_sol_unresolved_recipient_transfer(contract: &mut Contract) -> TransferId {
+    run_fin_unresolved_recipient_transfer_from(
+        contract,
+        OmniAddress::Sol(SolAddress(UNCLAIMED_DEPOSIT_SOL_SENDER)),
+    );
+    TransferId {
+        origin_chain: ChainKind::Sol,
+        origin_nonce: DEFAULT_NONCE,
+    }
+}
+
+fn claim_sol_unclaimed_deposit(contract: &mut Contract, transfer_id: TransferId) {
+    testing_env!(VMContextBuilder::new()
+        .current_account_id("bridge.testnet".parse().unwrap())
+        .predecessor_account_id("relayer.testnet".parse().unwrap())
+        .build());
+    contract
+        .claim_unclaimed_deposit(
+            transfer_id,
+            DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+            hex::decode(UNCLAIMED_DEPOSIT_CLAIM_SIGNATURE)
+                .unwrap()
+                .into(),
+        )
+        .detach();
+}
+
+#[test]
+fn test_claim_unclaimed_deposit() {
+    let mut contract = get_default_contract();
+    let transfer_id = fin_sol_unresolved_recipient_transfer(&mut contract);
+    let deposit = contract.get_unclaimed_deposit(transfer_id).unwrap();
+    let available = contract
+        .storage_balance_of(&deposit.storage_owner)
+        .unwrap()
+        .available;
+
+    claim_sol_unclaimed_deposit(&mut contract, transfer_id);
+    assert!(contract.get_unclaimed_deposit(transfer_id).is_none());
+    assert_eq!(
+        contract
+            .get_locked_breakdown(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap())
+            .escrow,
+        U128(0)
+    );
+
+    contract.claim_unclaimed_deposit_callback(
+        transfer_id,
+        deposit.clone(),
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_millinear(1),
+        &Ok(()),
+    );
+    assert_eq!(
+        contract
+            .storage_balance_of(&deposit.storage_owner)
+            .unwrap()
+            .available,
+        available.saturating_add(NearToken::from_millinear(1))
+    );
+    let event = OmniBridgeEvent::ClaimUnclaimedDepositEvent {
+        transfer_id,
+        recipient: DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+    };
+    assert!(get_logs().contains(&event.to_log_string()));
+}
+
+#[test]
+fn test_claim_unclaimed_deposit_failed_transfer_restores_deposit() {
+    let mut contract = get_default_contract();
+    let transfer_id = fin_sol_unresolved_recipient_transfer(&mut contract);
+    let deposit = contract.get_unclaimed_deposit(transfer_id).unwrap();
+    let available = contract
+        .storage_balance_of(&deposit.storage_owner)
+        .unwrap()
+        .available;
+
+    claim_sol_unclaimed_deposit(&mut contract, transfer_id);
+    contract.claim_unclaimed_deposit_callback(
+        transfer_id,
+        deposit.clone(),
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_millinear(1),
+        &Err(PromiseError::Failed),
+    );
+
+    assert_eq!(
+        contract.get_unclaimed_deposit(transfer_id),
+        Some(deposit.clone())
+    );
+    assert_eq!(
+        contract
+            .get_locked_breakdown(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap())
+            .escrow,
+        U128(DEFAULT_TRANSFER_AMOUNT)
+    );
+    assert_eq!(
+        contract
+            .storage_balance_of(&deposit.storage_owner)
+            .unwrap()
+            .available,
+        available
+    );
+}
+
+fn hand_over_default_transfer(contract: &mut Contract, new_owner: &AccountId) -> TransferId {
+    let transfer_id = init_default_transfer(contract);
+    hand_over_transfer(contract, transfer_id, new_owner);
+    transfer_id
+}
+
+fn hand_over_transfer(contract: &mut Contract, transfer_id: TransferId, new_owner: &AccountId) {
+    run_storage_deposit(contract, new_owner.clone(), NearToken::from_near(1));
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_millinear(10),
+        None,
+    );
+    contract.transfer_ownership(transfer_id, new_owner.clone());
+}
+
+#[test]
+fn test_cancel_handed_over_transfer_as_owner() {
+    let mut contract = get_default_contract();
+    let new_owner: AccountId = "custody.testnet".parse().unwrap();
+    let transfer_id = hand_over_default_transfer(&mut contract, &new_owner);
+    let transfer_message = contract.get_transfer_message(transfer_id);
+
+    setup_test_env(new_owner.clone(), NearToken::from_yoctonear(0), None);
+    contract.cancel_transfer_as_owner(transfer_id).detach();
+
+    let event = OmniBridgeEvent::CancelTransferEvent {
+        transfer_message,
+        beneficiary: new_owner,
+    };
+    assert!(get_logs().contains(&event.to_log_string()));
+}
+
+#[test]
+#[should_panic(expected = "ERR_ONLY_TRANSFER_OWNER")]
+fn test_cancel_handed_over_transfer_as_sender() {
+    let mut contract = get_default_contract();
+    let transfer_id =
+        hand_over_default_transfer(&mut contract, &"custody.testnet".parse().unwrap());
+
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.cancel_transfer_as_owner(transfer_id).detach();
+}
+
+#[test]
+fn test_expire_handed_over_transfer_refunds_owner() {
+    let mut contract = get_default_contract();
+    let new_owner: AccountId = "custody.testnet".parse().unwrap();
+    let transfer_id = init_expiring_transfer(&mut contract, TRANSFER_EXPIRY_NS);
+    hand_over_transfer(&mut contract, transfer_id, &new_owner);
+    let transfer_message = contract.get_transfer_message(transfer_id);
+
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id("anyone.testnet".parse().unwrap())
+        .block_timestamp(TRANSFER_EXPIRY_NS)
+        .build());
+    contract.expire_transfer(transfer_id).detach();
+
+    let event = OmniBridgeEvent::ExpireTransferEvent {
+        transfer_message,
+        refund_recipient: new_owner,
+    };
+    assert!(get_logs().contains(&event.to_log_string()));
+}
+
+#[test]
+fn test_failed_fee_swap_credits_fee_back() {
+    let mut contract = get_default_contract();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+    contract.lock_amount(&token_id, LockedState::FeePool, 100);
+
+    let result = contract.fee_swap_callback(
+        token_id.clone(),
+        relayer.clone(),
+        U128(100),
+        Err(PromiseError::Failed),
+    );
+
+    assert!(matches!(result, PromiseOrValue::Value(())));
 
-    contract.ft_on_transfer(sender_id, amount, msg);
+    assert_eq!(
+        contract.get_claimable_fee(relayer, FeeAsset::Token(token_id.clone())),
+        U128(100)
+    );
+    assert_eq!(contract.get_locked_breakdown(token_id).fee_pool, U128(100));
 }
 
-fn run_ft_on_transfer_legacy(
-    contract: &mut Contract,
-    sender_id: String,
-    token_id: String,
-    amount: U128,
-    attached_deposit: Option<NearToken>,
-    msg: &InitTransferMsg,
-) {
-    let sender_id = AccountId::try_from(sender_id).expect("Invalid sender ID");
-    let token_id = AccountId::try_from(token_id).expect("Invalid token ID");
-
-    let attached_deposit = if let Some(deposit) = attached_deposit {
-        deposit
-    } else {
-        let min_storage_balance = contract.required_balance_for_account();
-        let init_transfer_balance = contract.required_balance_for_init_transfer(None);
-        min_storage_balance.saturating_add(init_transfer_balance)
-    };
+#[test]
+fn test_partial_fee_swap_releases_used_amount() {
+    let mut contract = get_default_contract();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+    contract.lock_amount(&token_id, LockedState::FeePool, 100);
 
-    run_storage_deposit(contract, sender_id.clone(), attached_deposit);
-    setup_test_env(token_id.clone(), NearToken::from_yoctonear(0), None);
+    let refund =
+        contract.fee_swap_callback(token_id.clone(), relayer.clone(), U128(100), Ok(U128(60)));
 
-    let msg = serde_json::to_string(msg).expect("Failed to serialize transfer message");
+    assert!(matches!(refund, PromiseOrValue::Promise(_)));
+    // The refunded part stays in the fee pool until it is paid in the original token
+    assert_eq!(
+        contract.get_locked_breakdown(token_id.clone()).fee_pool,
+        U128(40)
+    );
+    assert_eq!(
+        contract.get_claimable_fee(relayer, FeeAsset::Token(token_id)),
+        U128(0)
+    );
+}
 
-    contract.ft_on_transfer(sender_id, amount, msg);
+fn sweep_legacy_outpoint(contract: &mut Contract) {
+    setup_rbf_connector(contract, Some(3));
+    contract.add_legacy_outpoints(ChainKind::Btc, vec![get_outpoint(0)]);
+    contract
+        .sweep_legacy_address(
+            ChainKind::Btc,
+            "legacy".to_string(),
+            vec![get_outpoint(0)],
+            10,
+            None,
+        )
+        .detach();
 }
 
 #[test]
-fn test_initialize_contract() {
-    let contract = get_default_contract();
+#[should_panic(expected = "ERR_OUTPOINT_ALREADY_RESERVED")]
+fn test_sweep_legacy_address_reserves_outpoints() {
+    let mut contract = get_default_contract();
+    sweep_legacy_outpoint(&mut contract);
+    assert!(contract
+        .sweeping_outpoints
+        .contains(&(ChainKind::Btc, get_outpoint(0))));
 
-    assert_eq!(contract.mpc_signer, DEFAULT_MPC_SIGNER_ACCOUNT);
-    assert_eq!(contract.current_origin_nonce, DEFAULT_NONCE);
-    assert_eq!(contract.wnear_account_id, DEFAULT_WNEAR_ACCOUNT);
+    contract.reserve_outpoints(ChainKind::Btc, &[DEFAULT_TRANSFER_ID], &[get_outpoint(0)]);
 }
 
 #[test]
-fn test_init_transfer_nonce_increment() {
+fn test_rejected_legacy_sweep_restores_outpoints() {
     let mut contract = get_default_contract();
+    sweep_legacy_outpoint(&mut contract);
 
-    run_ft_on_transfer(
-        &mut contract,
-        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
-        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
-        U128(100),
-        None,
-        &BridgeOnTransferMsg::InitTransfer(get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0)),
+    setup_test_env(
+        "dao.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        Some(vec![PromiseResult::Failed]),
     );
-
-    assert_eq!(contract.current_origin_nonce, DEFAULT_NONCE + 1);
+    contract.sweep_legacy_address_callback(ChainKind::Btc, vec![get_outpoint(0)]);
+    assert!(contract.is_legacy_outpoint(ChainKind::Btc, get_outpoint(0)));
+    assert!(!contract
+        .sweeping_outpoints
+        .contains(&(ChainKind::Btc, get_outpoint(0))));
 }
 
 #[test]
-fn test_init_transfer_stored_transfer_message() {
+fn test_accepted_legacy_sweep_releases_outpoints() {
     let mut contract = get_default_contract();
+    sweep_legacy_outpoint(&mut contract);
 
-    let msg = get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0);
-    run_ft_on_transfer(
-        &mut contract,
-        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
-        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
-        U128(DEFAULT_TRANSFER_AMOUNT),
-        None,
-        &BridgeOnTransferMsg::InitTransfer(msg.clone()),
+    setup_test_env(
+        "dao.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        Some(vec![PromiseResult::Successful(Vec::new())]),
     );
+    contract.sweep_legacy_address_callback(ChainKind::Btc, vec![get_outpoint(0)]);
+    assert!(!contract.is_legacy_outpoint(ChainKind::Btc, get_outpoint(0)));
+    assert!(!contract
+        .sweeping_outpoints
+        .contains(&(ChainKind::Btc, get_outpoint(0))));
+}
 
-    let stored_transfer = contract.get_transfer_message(TransferId {
-        origin_chain: ChainKind::Near,
-        origin_nonce: contract.current_origin_nonce,
-    });
-    assert_eq!(
-        stored_transfer.recipient, msg.recipient,
-        "Incorrect stored recipient"
+#[test]
+fn test_connector_circuit_breaker_expires_and_resets() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    contract.utxo_chain_connectors.insert(
+        ChainKind::Btc,
+        UTXOChainConfig {
+            connector: "primary-connector.testnet".parse().unwrap(),
+            token_id: "btc.testnet".parse().unwrap(),
+        },
     );
-    assert_eq!(stored_transfer.fee.fee, msg.fee, "Incorrect stored fee");
+    let window = 3_600_000_000_000;
+    contract.set_failover_policy(
+        ChainKind::Btc,
+        Some(FailoverPolicy {
+            window: U64(window),
+            min_submissions: 1,
+            max_failure_bps: 5_000,
+            auto_failover: false,
+        }),
+    );
+    let connector_id = contract.chain_connector_id(ChainKind::Btc);
+
+    contract.record_connector_result(ChainKind::Btc, connector_id, false);
+    assert!(contract.is_chain_connector_tripped(ChainKind::Btc));
+
+    // The breaker expires a window after it tripped, even without new submissions
+    testing_env!(VMContextBuilder::new()
+        .predecessor_account_id("dao.testnet".parse().unwrap())
+        .block_timestamp(window)
+        .build());
+    assert!(!contract.is_chain_connector_tripped(ChainKind::Btc));
+    contract.record_connector_result(ChainKind::Btc, connector_id, true);
+    assert!(!contract.get_connector_health(connector_id).is_tripped);
+
+    contract.record_connector_result(ChainKind::Btc, connector_id, false);
+    contract.record_connector_result(ChainKind::Btc, connector_id, false);
+    assert!(contract.is_chain_connector_tripped(ChainKind::Btc));
+    contract.reset_connector_health(connector_id);
+    assert!(!contract.is_chain_connector_tripped(ChainKind::Btc));
     assert_eq!(
-        stored_transfer.fee.native_fee, msg.native_token_fee,
-        "Incorrect stored native fee"
+        contract.get_connector_health(connector_id),
+        ConnectorHealth::default()
     );
+}
+
+// Every method exposed by the contract must have a name constant in the client, and every
+// constant must name an exposed method, so the client can't drift from the contract.
+#[test]
+fn test_client_method_names_match_contract() {
+    fn collect_sources(dir: &std::path::Path, sources: &mut Vec<String>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                if !path.ends_with("tests") {
+                    collect_sources(&path, sources);
+                }
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                sources.push(std::fs::read_to_string(path).unwrap());
+            }
+        }
+    }
+
+    let manifest_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut sources = Vec::new();
+    collect_sources(&manifest_dir.join("src"), &mut sources);
+
+    let mut exposed_methods = std::collections::BTreeSet::new();
+    for source in &sources {
+        let mut lines = source.lines();
+        while let Some(line) = lines.next() {
+            if !line.starts_with("#[near") {
+                continue;
+            }
+            let Some(impl_line) = lines.next() else {
+                break;
+            };
+            let is_trait_impl = impl_line.ends_with(" for Contract {");
+            if impl_line != "impl Contract {" && !is_trait_impl {
+                continue;
+            }
+            let mut is_hidden = false;
+            for line in lines.by_ref().take_while(|line| *line != "}") {
+                if line.starts_with("    #[private]") || line.starts_with("    #[init") {
+                    is_hidden = true;
+                }
+                let name = line.strip_prefix("    pub fn ").or_else(|| {
+                    is_trait_impl
+                        .then(|| line.strip_prefix("    fn "))
+                        .flatten()
+                });
+                if let Some(name) = name {
+                    let name = name.split(['(', '<']).next().unwrap();
+                    if !is_hidden {
+                        exposed_methods.insert(name.to_string());
+                    }
+                    is_hidden = false;
+                } else if line == "    }" {
+                    is_hidden = false;
+                }
+            }
+        }
+    }
+
+    let client = std::fs::read_to_string(manifest_dir.join("../omni-types/src/client.rs")).unwrap();
+    let client_methods = client
+        .split("pub const ")
+        .skip(1)
+        .filter_map(|constant| {
+            let (_, value) = constant.split_once(": &str =")?;
+            let value = value.trim_start().strip_prefix('"')?;
+            Some(value.split('"').next()?.to_string())
+        })
+        .collect::<std::collections::BTreeSet<_>>();
+
+    assert!(!exposed_methods.is_empty());
     assert_eq!(
-        stored_transfer.sender,
-        OmniAddress::Near(DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap()),
-        "Incorrect stored sender"
+        exposed_methods
+            .difference(&client_methods)
+            .collect::<Vec<_>>(),
+        Vec::<&String>::new(),
+        "Methods without a constant in omni_types::client::methods"
     );
     assert_eq!(
-        stored_transfer.token,
-        OmniAddress::Near(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap()),
-        "Incorrect stored token"
+        client_methods
+            .difference(&exposed_methods)
+            .collect::<Vec<_>>(),
+        Vec::<&String>::new(),
+        "Constants of omni_types::client::methods without a method"
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_EVENT_NOT_CONFIGURABLE")]
+fn test_event_emission_policy_not_configurable_for_indexed_events() {
+    setup_dao_env();
+    let mut contract = get_default_contract();
+    contract.set_event_emission_policy(
+        "FinTransferEvent".to_string(),
+        EventEmissionPolicy::Sampled { one_in: 2 },
     );
+}
+
+// Every `ERR_` code the contract or the shared types can panic with must be in the catalog
+#[test]
+fn test_error_catalog_covers_error_codes() {
+    fn collect_error_codes(
+        dir: &std::path::Path,
+        error_codes: &mut std::collections::BTreeSet<String>,
+    ) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                if !path.ends_with("tests") {
+                    collect_error_codes(&path, error_codes);
+                }
+            } else if path.extension().is_some_and(|extension| extension == "rs")
+                && !path.ends_with("error_catalog.rs")
+            {
+                let source = std::fs::read_to_string(path).unwrap();
+                for literal in source.split("\"ERR_").skip(1) {
+                    let name = literal
+                        .split(|c: char| !c.is_ascii_uppercase() && !c.is_ascii_digit() && c != '_')
+                        .next()
+                        .unwrap();
+                    if literal[name.len()..].starts_with('"') {
+                        error_codes.insert(format!("ERR_{name}"));
+                    }
+                }
+            }
+        }
+    }
+
+    let manifest_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut error_codes = std::collections::BTreeSet::new();
+    collect_error_codes(&manifest_dir.join("src"), &mut error_codes);
+    collect_error_codes(&manifest_dir.join("../omni-types/src"), &mut error_codes);
+
+    let catalog = get_default_contract()
+        .get_error_catalog()
+        .into_iter()
+        .map(|error| error.name)
+        .collect::<std::collections::BTreeSet<_>>();
+    assert!(!error_codes.is_empty());
     assert_eq!(
-        stored_transfer.amount,
-        U128(DEFAULT_TRANSFER_AMOUNT),
-        "Incorrect stored amount"
+        error_codes.difference(&catalog).collect::<Vec<_>>(),
+        Vec::<&String>::new(),
+        "Error codes missing from the error catalog"
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_UTXO_CHAIN_HAS_PENDING_TRANSFERS")]
+fn test_unregister_utxo_chain_with_transfers_in_flight() {
+    setup_test_env(
+        "dao.testnet".parse().unwrap(),
+        NearToken::from_near(1),
+        None,
     );
+    let mut contract = get_default_contract();
+    contract.register_utxo_chain(
+        ChainKind::Ltc,
+        "ltc.testnet".parse().unwrap(),
+        "ltc_connector.testnet".parse().unwrap(),
+        UtxoFeeModel::MaxFeeRate,
+        8,
+    );
+    let connector_id = contract.chain_connector_id(ChainKind::Ltc);
+    contract.record_connector_submission(connector_id, U128(100));
+
+    contract.unregister_utxo_chain(ChainKind::Ltc);
 }
 
 #[test]
@@ -255,6 +5599,126 @@ fn test_init_transfer_balance_updated() {
     );
 }
 
+#[test]
+fn test_init_transfer_without_storage_leaves_no_state() {
+    let mut source_contract = get_default_contract();
+    let transfer_id = init_default_transfer(&mut source_contract);
+    let transfer_message = source_contract.get_transfer_message(transfer_id);
+
+    let mut contract = get_default_contract();
+    let refund = contract.init_transfer_internal(
+        transfer_message.clone(),
+        "unregistered.testnet".parse().unwrap(),
+        None,
+        None,
+        None,
+    );
+    assert_eq!(refund, transfer_message.amount);
+    assert!(contract.pending_transfers.get(&transfer_id).is_none());
+    assert!(contract.transfer_timestamps.get(&transfer_id).is_none());
+    assert_eq!(
+        contract
+            .get_locked_breakdown(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap())
+            .pending,
+        U128(0)
+    );
+}
+
+#[test]
+fn test_init_transfer_quota_usage_recorded() {
+    let mut contract = get_default_contract();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+    contract.transfer_quotas.insert(
+        &token_id,
+        &TransferQuota {
+            max_transfers: Some(2),
+            max_volume: Some(U128(DEFAULT_TRANSFER_AMOUNT * 2)),
+        },
+    );
+
+    init_default_transfer(&mut contract);
+
+    let usage =
+        contract.get_transfer_quota_usage(DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(), token_id);
+    assert_eq!(usage.transfers, 1);
+    assert_eq!(usage.volume, U128(DEFAULT_TRANSFER_AMOUNT));
+}
+
+#[test]
+#[should_panic(expected = "ERR_TRANSFER_QUOTA_EXCEEDED")]
+fn test_init_transfer_quota_exceeded() {
+    let mut contract = get_default_contract();
+    contract.transfer_quotas.insert(
+        &DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        &TransferQuota {
+            max_transfers: Some(1),
+            max_volume: None,
+        },
+    );
+
+    for _ in 0..2 {
+        run_ft_on_transfer(
+            &mut contract,
+            DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+            DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+            U128(DEFAULT_TRANSFER_AMOUNT),
+            None,
+            &BridgeOnTransferMsg::InitTransfer(get_init_transfer_msg(
+                DEFAULT_ETH_USER_ADDRESS,
+                0,
+                0,
+            )),
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "ERR_TRANSFER_VOLUME_QUOTA_EXCEEDED")]
+fn test_init_transfer_volume_quota_exceeded() {
+    let mut contract = get_default_contract();
+    contract.transfer_quotas.insert(
+        &DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        &TransferQuota {
+            max_transfers: None,
+            max_volume: Some(U128(DEFAULT_TRANSFER_AMOUNT - 1)),
+        },
+    );
+
+    init_default_transfer(&mut contract);
+}
+
+#[test]
+fn test_init_transfer_quota_exempt_account() {
+    let mut contract = get_default_contract();
+    contract.transfer_quotas.insert(
+        &DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        &TransferQuota {
+            max_transfers: Some(1),
+            max_volume: None,
+        },
+    );
+    contract
+        .quota_exempt_accounts
+        .insert(&DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap());
+
+    for _ in 0..2 {
+        run_ft_on_transfer(
+            &mut contract,
+            DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+            DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+            U128(DEFAULT_TRANSFER_AMOUNT),
+            None,
+            &BridgeOnTransferMsg::InitTransfer(get_init_transfer_msg(
+                DEFAULT_ETH_USER_ADDRESS,
+                0,
+                0,
+            )),
+        );
+    }
+
+    assert_eq!(contract.current_origin_nonce, DEFAULT_NONCE + 2);
+}
+
 fn run_update_transfer_fee(
     contract: &mut Contract,
     sender_id: String,
@@ -277,6 +5741,7 @@ fn run_update_transfer_fee(
         msg: String::new(),
         destination_nonce: 1,
         origin_transfer_id: None,
+        expires_at: None,
     };
 
     contract.insert_raw_transfer(
@@ -583,6 +6048,18 @@ fn test_fin_transfer_callback_near_success() {
     let result = contract.fin_transfer_callback(&storage_actions, predecessor.clone());
 
     assert!(matches!(result, PromiseOrValue::Promise(_)));
+    assert_eq!(
+        contract.get_transfer_origin_senders(
+            TransferId {
+                origin_chain: ChainKind::Eth,
+                origin_nonce: DEFAULT_NONCE,
+            }
+            .into()
+        ),
+        Some(vec![OmniAddress::Eth(
+            EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()
+        )])
+    );
 }
 
 #[test]
@@ -776,6 +6253,18 @@ fn test_denormalize_amount() {
     );
 }
 
+#[test]
+#[should_panic(expected = "ERR_AMOUNT_OVERFLOW")]
+fn test_denormalize_amount_overflow() {
+    Contract::denormalize_amount(
+        u128::MAX,
+        Decimals {
+            decimals: 8,
+            origin_decimals: 18,
+        },
+    );
+}
+
 #[test]
 fn test_get_bridged_token() {
     let mut contract = get_default_contract();
@@ -880,3 +6369,176 @@ fn test_legacy_ft_on_transfer() {
         &get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0),
     );
 }
+
+fn get_governance_message(nonce: Nonce, action: &GovernanceAction) -> ProverResult {
+    ProverResult::GovernanceAction(GovernanceActionMessage {
+        nonce,
+        action: borsh::to_vec(action).unwrap(),
+        emitter_address: OmniAddress::Eth(
+            EvmAddress::from_str("0x0000000000000000000000000000000000000001").unwrap(),
+        ),
+    })
+}
+
+#[test]
+fn test_execute_governance_action_callback() {
+    let mut contract = get_default_contract();
+    contract.governance_emitter = Some(OmniAddress::Eth(
+        EvmAddress::from_str("0x0000000000000000000000000000000000000001").unwrap(),
+    ));
+
+    contract.execute_governance_action_callback(Ok(get_governance_message(
+        1,
+        &GovernanceAction::SetMaxTransferRestores(7),
+    )));
+
+    assert_eq!(contract.get_max_transfer_restores(), 7);
+    assert_eq!(contract.get_last_governance_nonce(), 1);
+}
+
+#[test]
+#[should_panic(expected = "ERR_GOVERNANCE_ACTION_ALREADY_EXECUTED")]
+fn test_execute_governance_action_callback_replay() {
+    let mut contract = get_default_contract();
+    contract.governance_emitter = Some(OmniAddress::Eth(
+        EvmAddress::from_str("0x0000000000000000000000000000000000000001").unwrap(),
+    ));
+    let action = GovernanceAction::SetMaxTransferRestores(7);
+
+    contract.execute_governance_action_callback(Ok(get_governance_message(1, &action)));
+    contract.execute_governance_action_callback(Ok(get_governance_message(1, &action)));
+}
+
+#[test]
+#[should_panic(expected = "ERR_OUTPOINT_ALREADY_RESERVED")]
+fn test_reserve_outpoints_conflict() {
+    let mut contract = get_default_contract();
+    let outpoint = get_outpoint(0);
+
+    contract.reserve_outpoints(ChainKind::Btc, &[DEFAULT_TRANSFER_ID], &[outpoint]);
+    contract.reserve_outpoints(
+        ChainKind::Btc,
+        &[TransferId {
+            origin_chain: ChainKind::Near,
+            origin_nonce: DEFAULT_NONCE + 1,
+        }],
+        &[outpoint],
+    );
+}
+
+#[test]
+fn test_release_outpoints() {
+    let mut contract = get_default_contract();
+    let outpoint = get_outpoint(0);
+
+    contract.reserve_outpoints(ChainKind::Btc, &[DEFAULT_TRANSFER_ID], &[outpoint]);
+    assert_eq!(
+        contract.get_outpoint_reservation(ChainKind::Btc, outpoint),
+        vec![DEFAULT_TRANSFER_ID]
+    );
+
+    contract.release_outpoints(ChainKind::Btc, &[outpoint]);
+    assert!(contract
+        .get_outpoint_reservation(ChainKind::Btc, outpoint)
+        .is_empty());
+}
+
+#[test]
+fn test_estimate_completion_time() {
+    let mut contract = get_default_contract();
+    assert_eq!(
+        contract.estimate_completion_time(ChainKind::Near, ChainKind::Btc),
+        None
+    );
+
+    for origin_nonce in 1..=2 {
+        let transfer_id = TransferId {
+            origin_chain: ChainKind::Near,
+            origin_nonce,
+        };
+        testing_env!(VMContextBuilder::new().block_timestamp(0).build());
+        contract.record_transfer_initiated(&transfer_id);
+        testing_env!(VMContextBuilder::new()
+            .block_timestamp(origin_nonce * 64_000_000_000)
+            .build());
+        contract.record_transfer_finalized(
+            &transfer_id,
+            ChainKind::Btc,
+            &DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        );
+    }
+
+    // The second transfer moves the estimate by 1/8 of the difference
+    assert_eq!(
+        contract.get_route_latency(ChainKind::Near, ChainKind::Btc),
+        Some(RouteLatency {
+            estimate: U64(72_000_000_000),
+            transfers: 2,
+        })
+    );
+    assert_eq!(
+        contract.estimate_completion_time(ChainKind::Near, ChainKind::Btc),
+        Some(72)
+    );
+    assert_eq!(
+        contract.estimate_completion_time(ChainKind::Eth, ChainKind::Btc),
+        None
+    );
+}
+
+#[test]
+fn test_transfer_latency_stats() {
+    let mut contract = get_default_contract();
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    let transfer_id = init_default_transfer(&mut contract);
+    let available = contract.storage_balance_of(&owner).unwrap().available;
+
+    let latency = 5_000_000_000;
+    testing_env!(VMContextBuilder::new().block_timestamp(latency).build());
+    let transfer_message = contract.remove_transfer_message(transfer_id);
+    contract.submit_transfer_to_btc_connector_callback(
+        transfer_message.clone(),
+        owner.clone(),
+        Some("relayer.testnet".parse().unwrap()),
+        Vec::new(),
+        None,
+        None,
+        None,
+        &Ok(transfer_message.amount),
+    );
+
+    let timestamps = contract.get_transfer_timestamps(transfer_id).unwrap();
+    assert_eq!(timestamps.initiated_at, U64(0));
+    assert_eq!(timestamps.finalized_at, Some(U64(latency)));
+    // The record is kept at the expense of the contract once the transfer is finalised
+    assert_eq!(
+        contract.storage_balance_of(&owner).unwrap().available,
+        available.saturating_add(Contract::required_balance_for_transfer_timestamps(
+            &transfer_id
+        ))
+    );
+
+    let stats = contract.get_latency_stats(ChainKind::Eth, U64(latency));
+    assert_eq!(stats.transfers, 1);
+    assert_eq!(stats.average_latency, U64(latency));
+    assert_eq!(stats.max_latency, U64(latency));
+    assert_eq!(
+        contract
+            .get_latency_stats(ChainKind::Btc, U64(latency))
+            .transfers,
+        0
+    );
+}
+
+#[test]
+fn test_cancelled_transfer_removes_timestamps() {
+    let mut contract = get_default_contract();
+    let owner: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    let transfer_id = init_default_transfer(&mut contract);
+    assert!(contract.get_transfer_timestamps(transfer_id).is_some());
+
+    setup_test_env(owner, NearToken::from_yoctonear(0), None);
+    contract.cancel_transfer_as_owner(transfer_id).detach();
+
+    assert!(contract.get_transfer_timestamps(transfer_id).is_none());
+}
@@ -0,0 +1,94 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{env, near, require, AccountId};
+
+use crate::{Contract, ContractExt, Role};
+
+/// Pre-approved transfer of `account_id` above the maximum transfer amount of the token.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OversizeTransferAllowance {
+    pub amount: U128,
+    pub expires_at: U64,
+}
+
+#[near]
+impl Contract {
+    /// Sets the maximum amount of a single transfer of the token. `None` removes the cap.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_max_transfer_amount(&mut self, token_id: AccountId, max_amount: Option<U128>) {
+        if let Some(max_amount) = max_amount {
+            self.max_transfer_amounts.insert(&token_id, &max_amount);
+        } else {
+            self.max_transfer_amounts.remove(&token_id);
+        }
+    }
+
+    /// Allows one transfer of up to `amount` of the token by `account_id` above the cap,
+    /// until the `expiry` timestamp in nanoseconds.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn approve_oversize_transfer(
+        &mut self,
+        account_id: AccountId,
+        token_id: AccountId,
+        amount: U128,
+        expiry: U64,
+    ) {
+        require!(
+            expiry.0 > env::block_timestamp(),
+            "ERR_INVALID_ALLOWANCE_EXPIRY"
+        );
+        self.oversize_transfer_allowances.insert(
+            &(account_id, token_id),
+            &OversizeTransferAllowance {
+                amount,
+                expires_at: expiry,
+            },
+        );
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn revoke_oversize_transfer(&mut self, account_id: AccountId, token_id: AccountId) {
+        self.oversize_transfer_allowances
+            .remove(&(account_id, token_id));
+    }
+
+    pub fn get_max_transfer_amount(&self, token_id: AccountId) -> Option<U128> {
+        self.max_transfer_amounts.get(&token_id)
+    }
+
+    pub fn get_oversize_transfer_allowance(
+        &self,
+        account_id: AccountId,
+        token_id: AccountId,
+    ) -> Option<OversizeTransferAllowance> {
+        self.oversize_transfer_allowances
+            .get(&(account_id, token_id))
+    }
+}
+
+impl Contract {
+    // Transfers above the cap consume the allowance approved by the DAO.
+    pub(crate) fn check_transfer_cap(
+        &mut self,
+        account_id: &AccountId,
+        token_id: &AccountId,
+        amount: U128,
+    ) {
+        let Some(max_amount) = self.max_transfer_amounts.get(token_id) else {
+            return;
+        };
+        if amount.0 <= max_amount.0 {
+            return;
+        }
+
+        let key = (account_id.clone(), token_id.clone());
+        let allowance = self.oversize_transfer_allowances.remove(&key);
+        require!(
+            allowance.is_some_and(|allowance| {
+                amount.0 <= allowance.amount.0 && env::block_timestamp() < allowance.expires_at.0
+            }),
+            "ERR_TRANSFER_AMOUNT_ABOVE_CAP"
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use near_plugins::{pause, Pausable};
+use near_sdk::json_types::U64;
+use near_sdk::{env, near, require, AccountId, Promise};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::{ChainKind, TransferId};
+
+use crate::helpers::SdkExpect;
+use crate::{Contract, ContractExt, Role};
+
+#[near]
+impl Contract {
+    /// Returns the timestamp set by the sender after which the transfer can be expired.
+    pub fn get_transfer_expiry(&self, transfer_id: TransferId) -> Option<U64> {
+        self.get_transfer_message(transfer_id).expires_at
+    }
+
+    /// Removes a transfer initiated on NEAR that was not signed before its expiry and refunds
+    /// the tokens to its beneficiary, or to its owner minus the token fee of the transfer.
+    #[pause(except(roles(Role::DAO)))]
+    pub fn expire_transfer(&mut self, transfer_id: TransferId) -> Promise {
+        require!(
+            transfer_id.origin_chain == ChainKind::Near,
+            "ERR_ONLY_NEAR_TRANSFERS_EXPIRE"
+        );
+        let expiry = self
+            .get_transfer_expiry(transfer_id)
+            .sdk_expect("ERR_TRANSFER_EXPIRY_NOT_SET");
+        require!(
+            env::block_timestamp() >= expiry.0,
+            "ERR_TRANSFER_NOT_EXPIRED"
+        );
+        require!(
+            !self.signing_requested_transfers.contains_key(&transfer_id),
+            "ERR_TRANSFER_ALREADY_SIGNED"
+        );
+        self.require_transfer_not_held(transfer_id);
+
+        let refund_recipient = self.get_expired_transfer_refund_recipient(transfer_id);
+        let (transfer_message, refund) =
+            self.refund_unsigned_transfer(transfer_id, refund_recipient.clone());
+        env::log_str(
+            &OmniBridgeEvent::ExpireTransferEvent {
+                transfer_message,
+                refund_recipient,
+            }
+            .to_log_string(),
+        );
+        refund
+    }
+}
+
+impl Contract {
+    fn get_expired_transfer_refund_recipient(&self, transfer_id: TransferId) -> AccountId {
+        if let Some(record) = self.transfer_beneficiaries.get(&transfer_id) {
+            return record.beneficiary.account_id;
+        }
+        self.get_transfer_message_storage(transfer_id).owner
+    }
+}
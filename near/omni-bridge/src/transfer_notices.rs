@@ -0,0 +1,70 @@
+use near_plugins::{pause, AccessControllable, Pausable};
+use near_sdk::json_types::U128;
+use near_sdk::{borsh, env, near, require, Gas, Promise, PromiseError};
+use omni_types::mpc_types::SignatureResponse;
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::{AddressKind, IncomingTransferNoticePayload, PayloadType, TransferId};
+
+use crate::helpers::SdkExpect;
+use crate::{Contract, ContractExt, Role};
+
+const SIGN_INCOMING_TRANSFER_NOTICE_CALLBACK_GAS: Gas = Gas::from_tgas(5);
+
+#[near]
+impl Contract {
+    /// Signs a notice of the pending transfer for its EVM or UTXO destination chain, which
+    /// integrators can relay to the destination contracts ahead of the claim. The notice is
+    /// not authoritative: the transfer can still be cancelled, and it is only claimable with
+    /// the signed `TransferMessagePayload`.
+    #[payable]
+    #[pause(except(roles(Role::DAO, Role::UnrestrictedRelayer)))]
+    pub fn sign_incoming_transfer_notice(&mut self, transfer_id: TransferId) -> Promise {
+        let transfer_message = self.get_transfer_message(transfer_id);
+        let destination_chain = transfer_message.get_destination_chain();
+        require!(
+            matches!(
+                self.get_chain_traits(destination_chain).address_kind,
+                AddressKind::Evm | AddressKind::Utxo
+            ),
+            "ERR_NOTICE_NOT_SUPPORTED_FOR_CHAIN"
+        );
+
+        let (token_address, amount) = self.get_destination_token_amount(&transfer_message);
+        let notice_payload = IncomingTransferNoticePayload {
+            prefix: PayloadType::IncomingTransferNotice,
+            trace_id: transfer_id.trace_id(),
+            destination_nonce: transfer_message.destination_nonce,
+            transfer_id,
+            token_address,
+            amount: U128(amount),
+            recipient: transfer_message.recipient,
+        };
+
+        let request = self.build_signature_request(
+            destination_chain,
+            &borsh::to_vec(&notice_payload).sdk_expect("ERR_BORSH"),
+        );
+        self.request_signature(request).then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(SIGN_INCOMING_TRANSFER_NOTICE_CALLBACK_GAS)
+                .sign_incoming_transfer_notice_callback(notice_payload),
+        )
+    }
+
+    #[private]
+    pub fn sign_incoming_transfer_notice_callback(
+        &mut self,
+        #[callback_result] call_result: Result<SignatureResponse, PromiseError>,
+        #[serializer(borsh)] notice_payload: IncomingTransferNoticePayload,
+    ) {
+        if let Ok(signature) = call_result {
+            env::log_str(
+                &OmniBridgeEvent::IncomingTransferNoticeEvent {
+                    signature,
+                    notice_payload,
+                }
+                .to_log_string(),
+            );
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use near_sdk::{env, near, require, AccountId};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::TransferId;
+
+use crate::helpers::SdkExpect;
+use crate::storage::{TransferMessageStorage, TransferMessageStorageValue};
+use crate::{Contract, ContractExt};
+
+#[near]
+impl Contract {
+    /// Moves the rights of the owner of a pending or dead-letter transfer to `new_owner`:
+    /// the refund of the storage and of the native fee, and the refund of the tokens
+    /// of a cancelled transfer whose beneficiary is the owner.
+    /// The change of the storage size is paid from the attached deposit or the storage balance
+    /// of the current owner.
+    #[payable]
+    pub fn transfer_ownership(&mut self, transfer_id: TransferId, new_owner: AccountId) {
+        require!(
+            self.accounts_balances.get(&new_owner).is_some(),
+            "ERR_NEW_OWNER_NOT_REGISTERED"
+        );
+
+        let is_pending = self.pending_transfers.contains_key(&transfer_id);
+        let transfers = if is_pending {
+            &mut self.pending_transfers
+        } else {
+            &mut self.dead_letter_transfers
+        };
+        let transfer = transfers
+            .get(&transfer_id)
+            .map(TransferMessageStorage::into_main)
+            .sdk_expect("The transfer does not exist");
+        let old_owner = transfer.owner;
+        require!(
+            old_owner == env::predecessor_account_id(),
+            "ERR_ONLY_TRANSFER_OWNER"
+        );
+        require!(old_owner != new_owner, "ERR_SAME_TRANSFER_OWNER");
+
+        let storage_usage = env::storage_usage();
+        transfers.insert(
+            &transfer_id,
+            &TransferMessageStorage::V3(TransferMessageStorageValue {
+                message: transfer.message,
+                owner: new_owner.clone(),
+            }),
+        );
+
+        if let Some(mut record) = self.transfer_beneficiaries.get(&transfer_id) {
+            if record.beneficiary.account_id == old_owner {
+                record.beneficiary.account_id = new_owner.clone();
+                self.transfer_beneficiaries.insert(&transfer_id, &record);
+            }
+        }
+        if is_pending {
+            self.unindex_pending_transfer_owner(&old_owner, &transfer_id);
+            self.index_pending_transfer_owner(&new_owner, transfer_id);
+        }
+
+        let current_storage_usage = env::storage_usage();
+        let required_balance = env::storage_byte_cost()
+            .saturating_mul((current_storage_usage.saturating_sub(storage_usage)).into());
+        let refund = env::storage_byte_cost()
+            .saturating_mul((storage_usage.saturating_sub(current_storage_usage)).into());
+        if let Some(mut storage) = self.accounts_balances.get(&old_owner) {
+            storage.available = storage.available.saturating_add(refund);
+            self.accounts_balances.insert(&old_owner, &storage);
+        }
+        self.update_storage_balance(old_owner.clone(), required_balance, env::attached_deposit());
+
+        env::log_str(
+            &OmniBridgeEvent::TransferOwnershipEvent {
+                transfer_id,
+                old_owner,
+                new_owner,
+            }
+            .to_log_string(),
+        );
+    }
+}
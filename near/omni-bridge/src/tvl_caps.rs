@@ -0,0 +1,103 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::U128;
+use near_sdk::{near, AccountId};
+use omni_types::errors::BridgeError;
+
+use crate::helpers::SdkUnwrap;
+use crate::{Contract, ContractExt, Role};
+
+#[near]
+impl Contract {
+    /// Caps the value of the token held through the bridge: the amount locked for a token of
+    /// NEAR, or the amount minted for a bridged token. The value is counted from the time the
+    /// cap is set, so the DAO can ramp up the exposure to a new listing.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_tvl_cap(&mut self, token_id: AccountId, cap: Option<U128>) {
+        if let Some(cap) = cap {
+            self.tvl_caps.insert(&token_id, &cap);
+        } else {
+            self.tvl_caps.remove(&token_id);
+            self.token_tvl.remove(&token_id);
+        }
+    }
+
+    pub fn get_tvl_cap(&self, token_id: AccountId) -> Option<U128> {
+        self.tvl_caps.get(&token_id)
+    }
+
+    pub fn get_tvl(&self, token_id: AccountId) -> U128 {
+        self.token_tvl.get(&token_id).unwrap_or_default()
+    }
+
+    /// Returns the amount of the token that can still be bridged before the cap is reached,
+    /// `None` for the tokens without a cap.
+    pub fn get_tvl_headroom(&self, token_id: AccountId) -> Option<U128> {
+        self.tvl_caps
+            .get(&token_id)
+            .map(|cap| U128(cap.0.saturating_sub(self.get_tvl(token_id).0)))
+    }
+}
+
+impl Contract {
+    pub(crate) fn check_tvl_cap(
+        &self,
+        token_id: &AccountId,
+        amount: u128,
+    ) -> Result<(), BridgeError> {
+        let Some(headroom) = self.get_tvl_headroom(token_id.clone()) else {
+            return Ok(());
+        };
+        if amount > headroom.0 {
+            return Err(BridgeError::TvlCapExceeded);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn increase_tvl(&mut self, token_id: &AccountId, amount: u128) {
+        self.check_tvl_cap(token_id, amount).sdk_unwrap();
+        self.add_tvl(token_id, amount);
+    }
+
+    fn add_tvl(&mut self, token_id: &AccountId, amount: u128) {
+        if self.tvl_caps.contains_key(token_id) {
+            let tvl = self.get_tvl(token_id.clone()).0 + amount;
+            self.token_tvl.insert(token_id, &U128(tvl));
+        }
+    }
+
+    // The value bridged before the cap was set is not counted, so the subtraction saturates.
+    pub(crate) fn decrease_tvl(&mut self, token_id: &AccountId, amount: u128) {
+        if let Some(tvl) = self.token_tvl.get(token_id) {
+            self.token_tvl
+                .insert(token_id, &U128(tvl.0.saturating_sub(amount)));
+        }
+    }
+
+    // Tokens of NEAR are locked when they leave NEAR, while the bridged tokens are burned.
+    pub(crate) fn record_tvl_outbound(&mut self, token_id: &AccountId, amount: u128) {
+        if self.deployed_tokens.contains(token_id) {
+            self.decrease_tvl(token_id, amount);
+        } else {
+            self.increase_tvl(token_id, amount);
+        }
+    }
+
+    // Reverses `record_tvl_outbound` when the tokens of a transfer initiated on NEAR leave
+    // the bridge without reaching another chain: a refund, or a withdrawal through a UTXO
+    // connector. It ignores the cap, so a burned bridged token can always be minted back.
+    pub(crate) fn record_tvl_release(&mut self, token_id: &AccountId, amount: u128) {
+        if self.deployed_tokens.contains(token_id) {
+            self.add_tvl(token_id, amount);
+        } else {
+            self.decrease_tvl(token_id, amount);
+        }
+    }
+
+    pub(crate) fn record_tvl_inbound(&mut self, token_id: &AccountId, amount: u128) {
+        if self.deployed_tokens.contains(token_id) {
+            self.increase_tvl(token_id, amount);
+        } else {
+            self.decrease_tvl(token_id, amount);
+        }
+    }
+}
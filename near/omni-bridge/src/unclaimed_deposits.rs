@@ -0,0 +1,226 @@
+use near_plugins::{access_control_any, pause, AccessControllable, Pausable};
+use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::{borsh, env, near, require, AccountId, Gas, NearToken, Promise, PromiseError};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::prover_result::UnresolvedRecipientTransferMessage;
+use omni_types::utils::keccak256;
+use omni_types::{ChainKind, OmniAddress, PayloadType, TransferId, UnclaimedDepositClaimPayload};
+
+use crate::helpers::SdkExpect;
+use crate::locked::LockedState;
+use crate::{Contract, ContractExt, Role};
+
+const CLAIM_UNCLAIMED_DEPOSIT_CALLBACK_GAS: Gas = Gas::from_tgas(5);
+
+/// Inbound deposit whose recipient couldn't be parsed. The tokens are held by the locker
+/// until the sender proves the control of the origin address.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnclaimedDeposit {
+    pub token_id: AccountId,
+    pub amount: U128,
+    pub sender: OmniAddress,
+    pub raw_recipient: String,
+    // Account that paid the storage of the record
+    pub storage_owner: AccountId,
+}
+
+#[near]
+impl Contract {
+    /// Enables the escrow of the deposits from `chain_kind` whose recipient couldn't be parsed.
+    /// While it is disabled, the finalization of such deposits fails.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_unclaimed_deposits_enabled(&mut self, chain_kind: ChainKind, enabled: bool) {
+        if enabled {
+            self.unclaimed_deposit_chains.insert(&chain_kind);
+        } else {
+            self.unclaimed_deposit_chains.remove(&chain_kind);
+        }
+    }
+
+    pub fn is_unclaimed_deposits_enabled(&self, chain_kind: ChainKind) -> bool {
+        self.unclaimed_deposit_chains.contains(&chain_kind)
+    }
+
+    pub fn get_unclaimed_deposit(&self, transfer_id: TransferId) -> Option<UnclaimedDeposit> {
+        self.unclaimed_deposits.get(&transfer_id)
+    }
+
+    /// Sends the escrowed deposit to `recipient`. `signature` is the signature of the
+    /// borsh-encoded `UnclaimedDepositClaimPayload` made with the key of the origin address:
+    /// a 65 bytes secp256k1 signature of the payload signed as an EIP-191 personal message for
+    /// EVM chains, or an ed25519 signature for Solana. The call can be submitted by any account.
+    /// The deposit can be claimed again if the tokens couldn't be sent to `recipient`.
+    #[pause(except(roles(Role::DAO)))]
+    pub fn claim_unclaimed_deposit(
+        &mut self,
+        transfer_id: TransferId,
+        recipient: AccountId,
+        signature: Base64VecU8,
+    ) -> Promise {
+        let deposit = self
+            .unclaimed_deposits
+            .get(&transfer_id)
+            .sdk_expect("ERR_UNCLAIMED_DEPOSIT_NOT_FOUND");
+        let payload = borsh::to_vec(&UnclaimedDepositClaimPayload {
+            prefix: PayloadType::UnclaimedDepositClaim,
+            locker: env::current_account_id(),
+            transfer_id,
+            recipient: recipient.clone(),
+        })
+        .sdk_expect("ERR_BORSH");
+        require!(
+            Self::verify_origin_signature(&deposit.sender, &payload, &signature.0),
+            "ERR_INVALID_CLAIM_SIGNATURE"
+        );
+
+        let storage_usage = env::storage_usage();
+        self.unclaimed_deposits.remove(&transfer_id);
+        let storage_refund = env::storage_byte_cost()
+            .saturating_mul((storage_usage.saturating_sub(env::storage_usage())).into());
+        self.unlock_amount(&deposit.token_id, LockedState::Escrow, deposit.amount.0);
+
+        self.send_tokens(
+            deposit.token_id.clone(),
+            recipient.clone(),
+            deposit.amount,
+            "",
+        )
+        .then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(CLAIM_UNCLAIMED_DEPOSIT_CALLBACK_GAS)
+                .claim_unclaimed_deposit_callback(transfer_id, deposit, recipient, storage_refund),
+        )
+    }
+
+    // The storage of the record is refunded once the tokens are sent. Otherwise the record is
+    // restored, e.g. when the recipient isn't registered with the token.
+    #[private]
+    pub fn claim_unclaimed_deposit_callback(
+        &mut self,
+        transfer_id: TransferId,
+        deposit: UnclaimedDeposit,
+        recipient: AccountId,
+        storage_refund: NearToken,
+        #[callback_result] call_result: &Result<(), PromiseError>,
+    ) {
+        if call_result.is_err() {
+            self.lock_amount(&deposit.token_id, LockedState::Escrow, deposit.amount.0);
+            self.unclaimed_deposits.insert(&transfer_id, &deposit);
+            return;
+        }
+
+        if let Some(mut storage) = self.accounts_balances.get(&deposit.storage_owner) {
+            storage.available = storage.available.saturating_add(storage_refund);
+            self.accounts_balances
+                .insert(&deposit.storage_owner, &storage);
+        }
+        env::log_str(
+            &OmniBridgeEvent::ClaimUnclaimedDepositEvent {
+                transfer_id,
+                recipient,
+            }
+            .to_log_string(),
+        );
+    }
+}
+
+impl Contract {
+    // Finalises the deposit without a recipient and holds its full amount, fee included, in escrow.
+    // The storage of the records is paid by the relayer.
+    pub(crate) fn add_unclaimed_deposit(
+        &mut self,
+        transfer: UnresolvedRecipientTransferMessage,
+        predecessor_account_id: AccountId,
+    ) {
+        let chain_kind = transfer.emitter_address.get_chain();
+        require!(
+            self.factories.get(&chain_kind) == Some(transfer.emitter_address),
+            "Unknown factory"
+        );
+        require!(
+            self.unclaimed_deposit_chains.contains(&chain_kind),
+            "ERR_INVALID_RECIPIENT"
+        );
+
+        let decimals = self
+            .token_decimals
+            .get(&transfer.token)
+            .sdk_expect("ERR_TOKEN_DECIMALS_NOT_FOUND");
+        let token_id = self.get_token_id(&transfer.token);
+        let amount = U128(Self::denormalize_amount(transfer.amount.0, decimals));
+        let transfer_id = TransferId {
+            origin_chain: transfer.sender.get_chain(),
+            origin_nonce: transfer.origin_nonce,
+        };
+
+        let storage_usage = env::storage_usage();
+        self.add_fin_transfer(&transfer_id);
+        self.unclaimed_deposits.insert(
+            &transfer_id,
+            &UnclaimedDeposit {
+                token_id: token_id.clone(),
+                amount,
+                sender: transfer.sender.clone(),
+                raw_recipient: transfer.raw_recipient.clone(),
+                storage_owner: predecessor_account_id.clone(),
+            },
+        );
+        let required_balance = env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into());
+        self.update_storage_balance(
+            predecessor_account_id,
+            required_balance,
+            env::attached_deposit(),
+        );
+        self.lock_amount(&token_id, LockedState::Escrow, amount.0);
+
+        env::log_str(
+            &OmniBridgeEvent::UnclaimedDepositEvent {
+                transfer_id,
+                token_id,
+                amount,
+                sender: transfer.sender,
+                raw_recipient: transfer.raw_recipient,
+            }
+            .to_log_string(),
+        );
+    }
+
+    fn verify_origin_signature(sender: &OmniAddress, payload: &[u8], signature: &[u8]) -> bool {
+        match sender {
+            OmniAddress::Sol(address) => {
+                let Ok(signature) = <&[u8; 64]>::try_from(signature) else {
+                    return false;
+                };
+                env::ed25519_verify(signature, payload, &address.0)
+            }
+            OmniAddress::Eth(evm_address)
+            | OmniAddress::Arb(evm_address)
+            | OmniAddress::Base(evm_address)
+            | OmniAddress::Bnb(evm_address)
+            | OmniAddress::Pol(evm_address) => {
+                let Ok(signature) = <&[u8; 65]>::try_from(signature) else {
+                    return false;
+                };
+                let (v, signature) = (signature[64], &signature[..64]);
+                // Recovery ids are accepted both as 0/1 and as 27/28
+                let v = if v >= 27 { v - 27 } else { v };
+                // Signed as an EIP-191 personal message, like the wallets do
+                let mut message =
+                    format!("\x19Ethereum Signed Message:\n{}", payload.len()).into_bytes();
+                message.extend_from_slice(payload);
+                let Some(public_key) = env::ecrecover(&keccak256(&message), signature, v, true)
+                else {
+                    return false;
+                };
+                keccak256(&public_key)[12..] == evm_address.0
+            }
+            OmniAddress::Near(_)
+            | OmniAddress::Btc(_)
+            | OmniAddress::Zcash(_)
+            | OmniAddress::Ltc(_)
+            | OmniAddress::Doge(_) => false,
+        }
+    }
+}
@@ -0,0 +1,855 @@
+use crate::claim_records::ClaimEvidence;
+use crate::connector_registry::ConnectorId;
+use crate::external::ext_token;
+use crate::helpers::SdkUnwrap;
+use crate::locked::LockedState;
+use crate::{Contract, ContractExt, Role, FT_TRANSFER_CALL_GAS, ONE_YOCTO};
+use near_plugins::{pause, AccessControllable, Pausable};
+use near_sdk::json_types::U128;
+use near_sdk::{env, near, require, serde_json, AccountId, Gas, Promise, PromiseError};
+use omni_types::btc::{
+    is_zcash_transparent_address, AddressEncoding, DogeTokenReceiverMessage,
+    LtcTokenReceiverMessage, OutPoint, ScriptKind, TokenReceiverMessage, TxOut, UTXOChainMsg,
+    UtxoAddressFormat, UtxoTransaction, ZecTokenReceiverMessage, ZecTxOut,
+};
+use omni_types::errors::BridgeError;
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::{
+    ChainKind, Fee, SubmitOutcome, SubmitStatus, TransferId, TransferMessage, UTXOChainAddress,
+};
+
+// The callback records the forwarding of the transfer or restores it, and spends or releases its
+// inputs with one storage write per input
+const SUBMIT_TRANSFER_TO_BTC_CONNECTOR_CALLBACK_GAS: Gas = Gas::from_tgas(15);
+const SUBMIT_TRANSFER_TO_BTC_CONNECTOR_CALLBACK_GAS_PER_INPUT: Gas = Gas::from_ggas(300);
+
+/// How the fee of the withdrawals of a UTXO chain is bounded by the `UTXOChainMsg` of the
+/// transfers to the chain.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UtxoFeeModel {
+    /// Total fee of the withdrawal, `UTXOChainMsg::MaxGasFee`.
+    MaxFee,
+    /// Fee per virtual byte, `UTXOChainMsg::MaxFeeRate`.
+    MaxFeeRate,
+    /// Fee per kilobyte, `UTXOChainMsg::MaxFeePerKb`.
+    MaxFeePerKb,
+}
+
+impl UtxoFeeModel {
+    fn bound_from_msg(self, msg: &UTXOChainMsg) -> Option<u64> {
+        match (self, msg) {
+            (Self::MaxFee, UTXOChainMsg::MaxGasFee(bound))
+            | (Self::MaxFeeRate, UTXOChainMsg::MaxFeeRate(bound))
+            | (Self::MaxFeePerKb, UTXOChainMsg::MaxFeePerKb(bound)) => Some(bound.0),
+            _ => None,
+        }
+    }
+
+    /// Largest network fee of a withdrawal under the `bound` of its `Withdraw` message, in the
+    /// smallest unit of the chain.
+    pub(crate) fn max_network_fee(self, bound: u128, inputs: usize, outputs: &[TxOut]) -> u128 {
+        match self {
+            Self::MaxFee => bound,
+            Self::MaxFeeRate => bound.saturating_mul(max_tx_size(inputs, outputs)),
+            Self::MaxFeePerKb => bound.saturating_mul(max_tx_size(inputs, outputs).div_ceil(1000)),
+        }
+    }
+}
+
+// Upper bound of the size of a transaction in bytes, with the legacy P2PKH inputs of 148 bytes,
+// so it also bounds the virtual size of the SegWit transactions.
+fn max_tx_size(inputs: usize, outputs: &[TxOut]) -> u128 {
+    let outputs_size: usize = outputs
+        .iter()
+        .map(|output| 9 + output.script_pubkey.len() / 2)
+        .sum();
+    u128::try_from(10 + 148 * inputs + outputs_size).unwrap_or(u128::MAX)
+}
+
+/// `Withdraw` message of a UTXO connector in the form shared by the chains.
+pub(crate) struct UtxoWithdraw {
+    pub target_address: String,
+    pub outpoints: Vec<OutPoint>,
+    pub min_output_value: Option<u64>,
+    pub output_script_kinds: Vec<ScriptKind>,
+    /// Bound of the withdrawal fee, in the unit of the `UTXOChainMsg` of the chain.
+    pub max_fee: Option<u128>,
+    pub outputs: Vec<TxOut>,
+}
+
+/// `BatchWithdraw` message of a UTXO connector paying several transfers in one transaction.
+pub(crate) struct UtxoBatchWithdraw {
+    /// Recipients of the first outputs, in the order of the transfers.
+    pub target_addresses: Vec<String>,
+    pub outpoints: Vec<OutPoint>,
+    pub outputs: Vec<TxOut>,
+    /// Bound of the total fee of the transaction.
+    pub max_fee: u128,
+}
+
+/// Validation of the `Withdraw` messages that differs between the UTXO chains. The rest of the
+/// submission to the connector is shared, so a new UTXO chain only needs an implementation
+/// of this trait and an arm in `builtin_utxo_chain_validator`.
+pub(crate) trait UtxoChainValidator {
+    fn fee_model(&self) -> UtxoFeeModel;
+
+    /// Address formats of the chain checked in `init_transfer` when the DAO hasn't configured
+    /// a table for the chain, so the transfers to an address of another chain fail before the
+    /// tokens are locked.
+    fn default_address_formats(&self) -> Vec<UtxoAddressFormat>;
+
+    /// Types of the output scripts of the withdrawals accepted when the DAO hasn't configured
+    /// them for the chain.
+    fn default_script_kinds(&self) -> Vec<ScriptKind> {
+        vec![
+            ScriptKind::P2pkh,
+            ScriptKind::P2sh,
+            ScriptKind::P2wpkh,
+            ScriptKind::P2wsh,
+            ScriptKind::P2tr,
+        ]
+    }
+
+    /// Field of the fee bound in the `Withdraw` message of the connector.
+    fn max_fee_field(&self) -> &'static str {
+        "max_gas_fee"
+    }
+
+    fn parse_withdraw(&self, msg: &str) -> Result<UtxoWithdraw, BridgeError> {
+        let TokenReceiverMessage::Withdraw {
+            target_btc_address,
+            input,
+            output,
+            max_gas_fee,
+        } = serde_json::from_str(msg).map_err(|_| BridgeError::InvalidWithdrawMsg)?
+        else {
+            return Err(BridgeError::InvalidWithdrawMsgType);
+        };
+        output.iter().try_for_each(TxOut::validate)?;
+        Ok(UtxoWithdraw {
+            target_address: target_btc_address,
+            outpoints: input,
+            min_output_value: output.iter().map(|output| output.value).min(),
+            output_script_kinds: output
+                .iter()
+                .map(|output| ScriptKind::from_hex(&output.script_pubkey))
+                .collect(),
+            max_fee: max_gas_fee.map(|max_gas_fee| max_gas_fee.0),
+            outputs: output,
+        })
+    }
+
+    /// Parses the transaction of a forwarded withdrawal reported by the connector.
+    fn parse_transaction(&self, tx: &[u8]) -> Result<UtxoTransaction, BridgeError> {
+        UtxoTransaction::from_bytes(tx)
+    }
+
+    /// Parses the withdrawal of several transfers. Only the connectors of the chains with a
+    /// total fee bound support it, since the fee is split between the transfers.
+    fn parse_batch_withdraw(&self, _msg: &str) -> Result<UtxoBatchWithdraw, BridgeError> {
+        Err(BridgeError::InvalidWithdrawMsgType)
+    }
+
+    fn validate_target_address(
+        &self,
+        recipient: &UTXOChainAddress,
+        target_address: &str,
+    ) -> Result<(), BridgeError> {
+        if recipient != target_address {
+            return Err(BridgeError::IncorrectTargetAddress);
+        }
+        Ok(())
+    }
+
+    /// Checks the fee bound of the withdrawal against the `UTXOChainMsg` of the transfer.
+    fn validate_max_fee(
+        &self,
+        transfer_msg: &str,
+        max_fee: Option<u128>,
+    ) -> Result<(), BridgeError> {
+        let UTXOChainMsg::MaxGasFee(max_gas_fee_from_msg) = parse_utxo_chain_msg(transfer_msg)?
+        else {
+            return Err(BridgeError::InvalidUtxoChainMsg);
+        };
+        if max_fee.ok_or(BridgeError::MissingMaxGasFee)? != max_gas_fee_from_msg.0.into() {
+            return Err(BridgeError::InvalidMaxGasFee);
+        }
+        Ok(())
+    }
+
+    /// Checks the rate of the oracle, set as the fee bound of a `Withdraw` message without one,
+    /// against the bound accepted by the sender in the `UTXOChainMsg` of the transfer.
+    fn validate_default_fee_rate(
+        &self,
+        transfer_msg: &str,
+        fee_rate: u64,
+    ) -> Result<(), BridgeError> {
+        let max_fee_from_msg = self
+            .fee_model()
+            .bound_from_msg(&parse_utxo_chain_msg(transfer_msg)?)
+            .ok_or(BridgeError::InvalidUtxoChainMsg)?;
+        if fee_rate > max_fee_from_msg {
+            return Err(BridgeError::FeeRateAboveMaxFee);
+        }
+        Ok(())
+    }
+}
+
+fn parse_utxo_chain_msg(transfer_msg: &str) -> Result<UTXOChainMsg, BridgeError> {
+    serde_json::from_str(transfer_msg).map_err(|_| BridgeError::InvalidUtxoChainMsg)
+}
+
+fn address_format(
+    encoding: AddressEncoding,
+    prefix: &str,
+    min_len: u8,
+    max_len: u8,
+    script_kind: ScriptKind,
+) -> UtxoAddressFormat {
+    UtxoAddressFormat {
+        encoding,
+        prefix: prefix.to_string(),
+        min_len,
+        max_len,
+        script_kind,
+    }
+}
+
+struct Bitcoin;
+
+impl UtxoChainValidator for Bitcoin {
+    fn fee_model(&self) -> UtxoFeeModel {
+        UtxoFeeModel::MaxFee
+    }
+
+    // Mainnet, testnet and regtest addresses, since the network is set by the connector
+    fn default_address_formats(&self) -> Vec<UtxoAddressFormat> {
+        vec![
+            address_format(AddressEncoding::Base58, "1", 26, 34, ScriptKind::P2pkh),
+            address_format(AddressEncoding::Base58, "3", 34, 34, ScriptKind::P2sh),
+            address_format(AddressEncoding::Base58, "m", 26, 34, ScriptKind::P2pkh),
+            address_format(AddressEncoding::Base58, "n", 26, 34, ScriptKind::P2pkh),
+            address_format(AddressEncoding::Base58, "2", 35, 35, ScriptKind::P2sh),
+            address_format(AddressEncoding::Bech32, "bc1q", 42, 42, ScriptKind::P2wpkh),
+            address_format(AddressEncoding::Bech32, "bc1q", 62, 62, ScriptKind::P2wsh),
+            address_format(AddressEncoding::Bech32, "bc1p", 62, 62, ScriptKind::P2tr),
+            address_format(AddressEncoding::Bech32, "tb1q", 42, 42, ScriptKind::P2wpkh),
+            address_format(AddressEncoding::Bech32, "tb1q", 62, 62, ScriptKind::P2wsh),
+            address_format(AddressEncoding::Bech32, "tb1p", 62, 62, ScriptKind::P2tr),
+            address_format(
+                AddressEncoding::Bech32,
+                "bcrt1q",
+                44,
+                44,
+                ScriptKind::P2wpkh,
+            ),
+            address_format(AddressEncoding::Bech32, "bcrt1q", 64, 64, ScriptKind::P2wsh),
+            address_format(AddressEncoding::Bech32, "bcrt1p", 64, 64, ScriptKind::P2tr),
+        ]
+    }
+
+    fn parse_batch_withdraw(&self, msg: &str) -> Result<UtxoBatchWithdraw, BridgeError> {
+        let TokenReceiverMessage::BatchWithdraw {
+            target_btc_addresses,
+            input,
+            output,
+            max_gas_fee,
+        } = serde_json::from_str(msg).map_err(|_| BridgeError::InvalidWithdrawMsg)?
+        else {
+            return Err(BridgeError::InvalidWithdrawMsgType);
+        };
+        output.iter().try_for_each(TxOut::validate)?;
+        Ok(UtxoBatchWithdraw {
+            target_addresses: target_btc_addresses,
+            outpoints: input,
+            outputs: output,
+            max_fee: max_gas_fee.0,
+        })
+    }
+}
+
+struct Zcash;
+
+impl UtxoChainValidator for Zcash {
+    fn fee_model(&self) -> UtxoFeeModel {
+        UtxoFeeModel::MaxFee
+    }
+
+    // Transparent addresses of mainnet and testnet. The bridge can't verify the funds sent to
+    // the shielded pools, so the shielded and unified addresses are not supported.
+    fn default_address_formats(&self) -> Vec<UtxoAddressFormat> {
+        vec![
+            address_format(AddressEncoding::Base58, "t1", 35, 35, ScriptKind::P2pkh),
+            address_format(AddressEncoding::Base58, "t3", 35, 35, ScriptKind::P2sh),
+            address_format(AddressEncoding::Base58, "tm", 35, 35, ScriptKind::P2pkh),
+            address_format(AddressEncoding::Base58, "t2", 35, 35, ScriptKind::P2sh),
+        ]
+    }
+
+    fn default_script_kinds(&self) -> Vec<ScriptKind> {
+        vec![ScriptKind::P2pkh, ScriptKind::P2sh]
+    }
+
+    // The ids of the v5 transactions are the BLAKE2b digests of ZIP 244
+    fn parse_transaction(&self, _tx: &[u8]) -> Result<UtxoTransaction, BridgeError> {
+        Err(BridgeError::TxidNotSupported)
+    }
+
+    fn parse_withdraw(&self, msg: &str) -> Result<UtxoWithdraw, BridgeError> {
+        let ZecTokenReceiverMessage::Withdraw {
+            target_btc_address,
+            input,
+            output,
+            max_gas_fee,
+        } = serde_json::from_str(msg).map_err(|_| BridgeError::InvalidWithdrawMsg)?
+        else {
+            return Err(BridgeError::InvalidWithdrawMsgType);
+        };
+        output.iter().try_for_each(ZecTxOut::validate)?;
+        if !output.iter().all(ZecTxOut::is_transparent) {
+            return Err(BridgeError::ShieldedOutputNotSupported);
+        }
+        Ok(UtxoWithdraw {
+            target_address: target_btc_address,
+            outpoints: input,
+            min_output_value: output.iter().map(|output| output.value).min(),
+            output_script_kinds: output
+                .iter()
+                .map(|output| ScriptKind::from_hex(&output.script_pubkey))
+                .collect(),
+            max_fee: max_gas_fee.map(|max_gas_fee| max_gas_fee.0),
+            outputs: output
+                .into_iter()
+                .map(|output| TxOut {
+                    value: output.value,
+                    script_pubkey: output.script_pubkey,
+                })
+                .collect(),
+        })
+    }
+
+    // The recipient of the transfers made before the transparent-only formats is checked
+    // again at submission, so their tokens aren't sent to a shielded address.
+    fn validate_target_address(
+        &self,
+        recipient: &UTXOChainAddress,
+        target_address: &str,
+    ) -> Result<(), BridgeError> {
+        if !is_zcash_transparent_address(recipient) {
+            return Err(BridgeError::ShieldedAddressNotSupported);
+        }
+        if recipient != target_address {
+            return Err(BridgeError::IncorrectTargetAddress);
+        }
+        Ok(())
+    }
+}
+
+struct Litecoin;
+
+impl UtxoChainValidator for Litecoin {
+    fn fee_model(&self) -> UtxoFeeModel {
+        UtxoFeeModel::MaxFeeRate
+    }
+
+    // Mainnet and testnet addresses, including the legacy `3` prefix of the P2SH addresses
+    fn default_address_formats(&self) -> Vec<UtxoAddressFormat> {
+        vec![
+            address_format(AddressEncoding::Base58, "L", 34, 34, ScriptKind::P2pkh),
+            address_format(AddressEncoding::Base58, "M", 34, 34, ScriptKind::P2sh),
+            address_format(AddressEncoding::Base58, "3", 34, 34, ScriptKind::P2sh),
+            address_format(AddressEncoding::Base58, "m", 34, 34, ScriptKind::P2pkh),
+            address_format(AddressEncoding::Base58, "n", 34, 34, ScriptKind::P2pkh),
+            address_format(AddressEncoding::Base58, "Q", 34, 34, ScriptKind::P2sh),
+            address_format(AddressEncoding::Base58, "2", 35, 35, ScriptKind::P2sh),
+            address_format(AddressEncoding::Bech32, "ltc1q", 43, 43, ScriptKind::P2wpkh),
+            address_format(AddressEncoding::Bech32, "ltc1q", 63, 63, ScriptKind::P2wsh),
+            address_format(AddressEncoding::Bech32, "ltc1p", 63, 63, ScriptKind::P2tr),
+            address_format(
+                AddressEncoding::Bech32,
+                "tltc1q",
+                44,
+                44,
+                ScriptKind::P2wpkh,
+            ),
+            address_format(AddressEncoding::Bech32, "tltc1q", 64, 64, ScriptKind::P2wsh),
+            address_format(AddressEncoding::Bech32, "tltc1p", 64, 64, ScriptKind::P2tr),
+        ]
+    }
+
+    fn max_fee_field(&self) -> &'static str {
+        "max_fee_rate"
+    }
+
+    fn parse_withdraw(&self, msg: &str) -> Result<UtxoWithdraw, BridgeError> {
+        let LtcTokenReceiverMessage::Withdraw {
+            target_ltc_address,
+            input,
+            output,
+            max_fee_rate,
+        } = serde_json::from_str(msg).map_err(|_| BridgeError::InvalidWithdrawMsg)?
+        else {
+            return Err(BridgeError::InvalidWithdrawMsgType);
+        };
+        output.iter().try_for_each(TxOut::validate)?;
+        Ok(UtxoWithdraw {
+            target_address: target_ltc_address,
+            outpoints: input,
+            min_output_value: output.iter().map(|output| output.value).min(),
+            output_script_kinds: output
+                .iter()
+                .map(|output| ScriptKind::from_hex(&output.script_pubkey))
+                .collect(),
+            max_fee: max_fee_rate.map(|max_fee_rate| max_fee_rate.0.into()),
+            outputs: output,
+        })
+    }
+
+    // The connector pays at most the given rate in litoshi per vB, whatever the size of the
+    // transaction it builds.
+    fn validate_max_fee(
+        &self,
+        transfer_msg: &str,
+        max_fee: Option<u128>,
+    ) -> Result<(), BridgeError> {
+        let UTXOChainMsg::MaxFeeRate(max_fee_rate_from_msg) = parse_utxo_chain_msg(transfer_msg)?
+        else {
+            return Err(BridgeError::InvalidUtxoChainMsg);
+        };
+        if max_fee.ok_or(BridgeError::MissingMaxFeeRate)? != max_fee_rate_from_msg.0.into() {
+            return Err(BridgeError::InvalidMaxFeeRate);
+        }
+        Ok(())
+    }
+}
+
+struct Dogecoin;
+
+impl UtxoChainValidator for Dogecoin {
+    fn fee_model(&self) -> UtxoFeeModel {
+        UtxoFeeModel::MaxFeePerKb
+    }
+
+    // Dogecoin has no SegWit, so all its addresses are base58
+    fn default_address_formats(&self) -> Vec<UtxoAddressFormat> {
+        vec![
+            address_format(AddressEncoding::Base58, "D", 34, 34, ScriptKind::P2pkh),
+            address_format(AddressEncoding::Base58, "A", 34, 34, ScriptKind::P2sh),
+            address_format(AddressEncoding::Base58, "9", 34, 34, ScriptKind::P2sh),
+            address_format(AddressEncoding::Base58, "n", 34, 34, ScriptKind::P2pkh),
+            address_format(AddressEncoding::Base58, "2", 35, 35, ScriptKind::P2sh),
+        ]
+    }
+
+    fn default_script_kinds(&self) -> Vec<ScriptKind> {
+        vec![ScriptKind::P2pkh, ScriptKind::P2sh]
+    }
+
+    fn max_fee_field(&self) -> &'static str {
+        "max_fee_per_kb"
+    }
+
+    fn parse_withdraw(&self, msg: &str) -> Result<UtxoWithdraw, BridgeError> {
+        let DogeTokenReceiverMessage::Withdraw {
+            target_doge_address,
+            input,
+            output,
+            max_fee_per_kb,
+        } = serde_json::from_str(msg).map_err(|_| BridgeError::InvalidWithdrawMsg)?
+        else {
+            return Err(BridgeError::InvalidWithdrawMsgType);
+        };
+        output.iter().try_for_each(TxOut::validate)?;
+        Ok(UtxoWithdraw {
+            target_address: target_doge_address,
+            outpoints: input,
+            min_output_value: output.iter().map(|output| output.value).min(),
+            output_script_kinds: output
+                .iter()
+                .map(|output| ScriptKind::from_hex(&output.script_pubkey))
+                .collect(),
+            max_fee: max_fee_per_kb.map(|max_fee_per_kb| max_fee_per_kb.0.into()),
+            outputs: output,
+        })
+    }
+
+    fn validate_max_fee(
+        &self,
+        transfer_msg: &str,
+        max_fee: Option<u128>,
+    ) -> Result<(), BridgeError> {
+        let UTXOChainMsg::MaxFeePerKb(max_fee_per_kb_from_msg) =
+            parse_utxo_chain_msg(transfer_msg)?
+        else {
+            return Err(BridgeError::InvalidUtxoChainMsg);
+        };
+        if max_fee.ok_or(BridgeError::MissingMaxFeePerKb)? != max_fee_per_kb_from_msg.0.into() {
+            return Err(BridgeError::InvalidMaxFeePerKb);
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn builtin_utxo_chain_validator(
+    chain_kind: ChainKind,
+) -> Option<&'static dyn UtxoChainValidator> {
+    match chain_kind {
+        ChainKind::Btc => Some(&Bitcoin),
+        ChainKind::Zcash => Some(&Zcash),
+        ChainKind::Ltc => Some(&Litecoin),
+        ChainKind::Doge => Some(&Dogecoin),
+        _ => None,
+    }
+}
+
+#[near]
+impl Contract {
+    #[payable]
+    #[pause(except(roles(Role::DAO, Role::UnrestrictedRelayer)))]
+    pub fn submit_transfer_to_utxo_chain_connector(
+        &mut self,
+        transfer_id: TransferId,
+        msg: String,
+        fee_recipient: Option<AccountId>,
+        fee: &Option<Fee>,
+    ) -> Promise {
+        let chain_kind = self
+            .get_transfer_message(transfer_id)
+            .get_destination_chain();
+        self.submit_transfer_to_utxo_connector(chain_kind, transfer_id, msg, fee_recipient, fee)
+    }
+
+    #[private]
+    pub fn submit_transfer_to_btc_connector_callback(
+        &mut self,
+        transfer_msg: TransferMessage,
+        transfer_owner: AccountId,
+        fee_recipient: Option<AccountId>,
+        outpoints: Vec<OutPoint>,
+        // Not set for the transfers submitted before the connector registry
+        connector_id: Option<ConnectorId>,
+        // Not set for the transfers submitted before the dead-letter accounting by submitter
+        submitter: Option<AccountId>,
+        // Not set for the transfers submitted before the withdrawal receipts
+        outputs: Option<Vec<TxOut>>,
+        #[callback_result] call_result: &Result<U128, PromiseError>,
+    ) -> SubmitOutcome {
+        let is_success = matches!(call_result, Ok(result) if result.0 > 0);
+        let is_connector_failure = self.is_utxo_connector_failure(
+            call_result,
+            is_success,
+            submitter.as_ref(),
+            &transfer_msg.get_transfer_id(),
+            &transfer_owner,
+        );
+        if let Some(connector_id) = connector_id {
+            self.release_connector_in_flight(connector_id, transfer_msg.amount);
+            if is_success || is_connector_failure {
+                self.record_connector_result(
+                    transfer_msg.get_destination_chain(),
+                    connector_id,
+                    is_success,
+                );
+            }
+        }
+        if is_success {
+            let forwarded_amount = call_result.as_ref().map_or(U128(0), |amount| *amount);
+            self.spend_custody_utxos(transfer_msg.get_destination_chain(), &outpoints);
+            self.complete_utxo_submission(
+                transfer_msg,
+                &transfer_owner,
+                fee_recipient,
+                connector_id,
+                forwarded_amount,
+                outputs
+                    .as_deref()
+                    .map(|outputs| (outpoints.as_slice(), outputs)),
+            )
+        } else {
+            self.release_outpoints(transfer_msg.get_destination_chain(), &outpoints);
+            self.fail_utxo_submission(transfer_msg, transfer_owner, is_connector_failure)
+        }
+    }
+}
+
+impl Contract {
+    pub(crate) fn get_utxo_chain_validator(
+        &self,
+        chain_kind: ChainKind,
+    ) -> &'static dyn UtxoChainValidator {
+        builtin_utxo_chain_validator(chain_kind)
+            .ok_or(BridgeError::InvalidDestinationChain)
+            .sdk_unwrap()
+    }
+
+    // Checks of the transfer shared by the single and the batch submissions to the UTXO chain
+    pub(crate) fn validate_utxo_transfer(
+        &self,
+        chain_kind: ChainKind,
+        transfer_message: &TransferMessage,
+        utxo_token_id: &AccountId,
+        target_address: &str,
+    ) -> Result<(), BridgeError> {
+        if transfer_message.get_destination_chain() != chain_kind {
+            return Err(BridgeError::InvalidDestinationChain);
+        }
+        let recipient = transfer_message
+            .recipient
+            .get_utxo_address()
+            .ok_or(BridgeError::InvalidDestinationChain)?;
+        self.get_utxo_chain_validator(chain_kind)
+            .validate_target_address(&recipient, target_address)?;
+        if transfer_message.msg.is_empty() && self.is_extra_msg_required(chain_kind) {
+            return Err(BridgeError::ExtraMsgRequired);
+        }
+        if &self.get_token_id(&transfer_message.token) != utxo_token_id {
+            return Err(BridgeError::NotUtxoChainToken);
+        }
+        Ok(())
+    }
+
+    // The outputs to the script of the recipient pay the amount of the transfer less at most
+    // the largest network fee of the withdrawal, so the tokens can't be paid to another script.
+    pub(crate) fn check_utxo_recipient_output(
+        &self,
+        chain_kind: ChainKind,
+        recipient: &UTXOChainAddress,
+        outputs: &[TxOut],
+        amount: u128,
+        max_network_fee: Option<u128>,
+    ) {
+        let script_pubkey = self.utxo_address_script_pubkey(chain_kind, recipient);
+        let payout_value: u128 = outputs
+            .iter()
+            .filter(|output| {
+                hex::decode(&output.script_pubkey).is_ok_and(|script| script == script_pubkey)
+            })
+            .map(|output| u128::from(output.value))
+            .sum();
+
+        require!(payout_value > 0, "ERR_NO_OUTPUT_TO_RECIPIENT");
+        require!(payout_value <= amount, "ERR_WITHDRAW_SPENDS_ABOVE_AMOUNT");
+        if let Some(max_network_fee) = max_network_fee {
+            require!(
+                payout_value.saturating_add(max_network_fee) >= amount,
+                "ERR_WITHDRAW_PAYOUT_BELOW_AMOUNT"
+            );
+        }
+    }
+
+    // The connector rejects the withdrawals built with invalid inputs or outputs, which are
+    // picked by the relayer. Such a rejection only counts against the connector and towards
+    // the dead-letter bound when the submitter is trusted, so any relayer can't park the
+    // transfer or trip the circuit breaker.
+    pub(crate) fn is_utxo_connector_failure(
+        &self,
+        call_result: &Result<U128, PromiseError>,
+        is_success: bool,
+        submitter: Option<&AccountId>,
+        transfer_id: &TransferId,
+        transfer_owner: &AccountId,
+    ) -> bool {
+        !is_success
+            && (call_result.is_err()
+                || submitter.is_none_or(|submitter| {
+                    self.is_trusted_submitter(submitter, transfer_id, transfer_owner)
+                }))
+    }
+
+    // Records the forwarding of a transfer accepted by the connector. The inputs of the
+    // withdrawal are spent by the caller. The receipt of the withdrawal is kept when its
+    // inputs and outputs are known.
+    pub(crate) fn complete_utxo_submission(
+        &mut self,
+        transfer_msg: TransferMessage,
+        transfer_owner: &AccountId,
+        fee_recipient: Option<AccountId>,
+        connector_id: Option<ConnectorId>,
+        forwarded_amount: U128,
+        withdrawal: Option<(&[OutPoint], &[TxOut])>,
+    ) -> SubmitOutcome {
+        let token = self.get_token_id(&transfer_msg.token);
+        self.unlock_amount(&token, LockedState::InFlight, transfer_msg.amount.0);
+        self.record_tvl_release(&token, transfer_msg.amount.0);
+        self.settle_fee_escalation(&transfer_msg.get_transfer_id());
+        self.clear_transfer_restore_count(&transfer_msg.get_transfer_id());
+        self.remove_submission_intent(&transfer_msg.get_transfer_id());
+        self.remove_transfer_records(&transfer_msg.get_transfer_id(), transfer_owner);
+        self.record_transfer_finalized(
+            &transfer_msg.get_transfer_id(),
+            transfer_msg.get_destination_chain(),
+            transfer_owner,
+        );
+        self.record_reward_points(&transfer_msg);
+        self.record_transfer_claimed(
+            &transfer_msg.get_transfer_id(),
+            transfer_msg.get_destination_chain(),
+            ClaimEvidence::ConnectorAck,
+        );
+        if let Some((outpoints, outputs)) = withdrawal {
+            self.add_utxo_withdrawal(
+                &transfer_msg.get_transfer_id(),
+                transfer_msg.get_destination_chain(),
+                transfer_owner,
+                outpoints,
+                outputs,
+            );
+        }
+        self.emit_event(&OmniBridgeEvent::UtxoTransferForwardedEvent {
+            transfer_id: transfer_msg.get_transfer_id(),
+            chain_kind: transfer_msg.get_destination_chain(),
+            amount: forwarded_amount,
+            fee: transfer_msg.fee.clone(),
+        });
+        if let Some(fee_recipient) = fee_recipient {
+            self.credit_transfer_fees(&transfer_msg, fee_recipient);
+        } else {
+            self.hold_fee_until_reported(&transfer_msg, connector_id);
+        }
+        SubmitOutcome {
+            status: SubmitStatus::Forwarded,
+            forwarded_amount,
+            fee_paid: transfer_msg.fee,
+            restored: false,
+        }
+    }
+
+    // Restores a transfer rejected by the connector. The inputs of the withdrawal are released
+    // by the caller.
+    pub(crate) fn fail_utxo_submission(
+        &mut self,
+        transfer_msg: TransferMessage,
+        transfer_owner: AccountId,
+        is_connector_failure: bool,
+    ) -> SubmitOutcome {
+        SubmitOutcome {
+            status: SubmitStatus::Failed,
+            forwarded_amount: U128(0),
+            fee_paid: Fee::default(),
+            restored: self.restore_transfer(transfer_msg, transfer_owner, is_connector_failure),
+        }
+    }
+
+    pub(crate) fn submit_transfer_callback_gas(inputs: usize) -> Gas {
+        SUBMIT_TRANSFER_TO_BTC_CONNECTOR_CALLBACK_GAS.saturating_add(Gas::from_gas(
+            SUBMIT_TRANSFER_TO_BTC_CONNECTOR_CALLBACK_GAS_PER_INPUT
+                .as_gas()
+                .saturating_mul(inputs.try_into().unwrap_or(u64::MAX)),
+        ))
+    }
+
+    pub(crate) fn submit_transfer_to_utxo_connector(
+        &mut self,
+        chain_kind: ChainKind,
+        transfer_id: TransferId,
+        mut msg: String,
+        fee_recipient: Option<AccountId>,
+        fee: &Option<Fee>,
+    ) -> Promise {
+        let transfer = self.get_transfer_message_storage(transfer_id);
+        self.require_transfer_not_held(transfer_id);
+        self.require_preferred_relayer(&transfer_id);
+        let validator = self.get_utxo_chain_validator(chain_kind);
+
+        let amount = transfer
+            .message
+            .fee
+            .net_amount(transfer.message.amount)
+            .sdk_unwrap();
+
+        let UtxoWithdraw {
+            target_address,
+            outpoints,
+            outputs,
+            min_output_value,
+            output_script_kinds,
+            max_fee,
+        } = validator.parse_withdraw(&msg).sdk_unwrap();
+        let default_fee_rate = max_fee
+            .is_none()
+            .then(|| self.fresh_utxo_fee_rate(chain_kind))
+            .flatten();
+        if let Some(fee_rate) = default_fee_rate {
+            msg = Self::with_default_utxo_fee_rate(validator, &msg, fee_rate);
+        }
+        let fee_bound = max_fee.or(default_fee_rate.map(u128::from));
+        self.check_max_allowed_withdraw_fee(chain_kind, fee_bound);
+
+        self.check_utxo_withdraw_limits(chain_kind, &msg, outpoints.len(), outputs.len());
+        self.check_utxo_dust_limit(chain_kind, min_output_value);
+        self.check_utxo_output_script_kinds(chain_kind, &output_script_kinds);
+        let utxo_token_id = self.require_chain_configured(chain_kind).token_id;
+        self.validate_utxo_transfer(
+            chain_kind,
+            &transfer.message,
+            &utxo_token_id,
+            &target_address,
+        )
+        .sdk_unwrap();
+        if !transfer.message.msg.is_empty() {
+            if let Some(fee_rate) = default_fee_rate {
+                validator.validate_default_fee_rate(&transfer.message.msg, fee_rate)
+            } else {
+                validator.validate_max_fee(&transfer.message.msg, max_fee)
+            }
+            .sdk_unwrap();
+        }
+
+        if let Some(fee) = &fee {
+            require!(&transfer.message.fee == fee, "Invalid fee");
+        }
+
+        self.check_withdraw_key_epoch(chain_kind, &outpoints);
+        self.check_custody_utxos(chain_kind, &outpoints);
+        self.check_utxo_recipient_output(
+            chain_kind,
+            &target_address,
+            &outputs,
+            amount.0,
+            fee_bound.map(|fee_bound| {
+                validator
+                    .fee_model()
+                    .max_network_fee(fee_bound, outpoints.len(), &outputs)
+            }),
+        );
+        self.check_utxo_withdraw_outputs(chain_kind, &outpoints, &outputs, amount.0);
+        self.reserve_submitted_outpoints(chain_kind, &[transfer_id], &outpoints);
+        self.record_transfer_submitted(&transfer_id);
+        self.take_transfer_message(transfer_id);
+        self.lock_amount(
+            &utxo_token_id,
+            LockedState::InFlight,
+            transfer.message.amount.0,
+        );
+
+        let fee_recipient = self.resolve_fee_recipient(chain_kind, &transfer_id, fee_recipient);
+        let connector_id = self.chain_connector_id(chain_kind);
+        self.record_connector_submission(connector_id, transfer.message.amount);
+        self.emit_event(&OmniBridgeEvent::UtxoTransferSubmittedEvent {
+            transfer_id,
+            chain_kind,
+            connector: self.resolve_connector(connector_id),
+            amount,
+            fee: transfer.message.fee.clone(),
+        });
+
+        ext_token::ext(utxo_token_id)
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(FT_TRANSFER_CALL_GAS)
+            .ft_transfer_call(self.resolve_connector(connector_id), amount, None, msg)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(Self::submit_transfer_callback_gas(outpoints.len()))
+                    .submit_transfer_to_btc_connector_callback(
+                        transfer.message,
+                        transfer.owner,
+                        fee_recipient,
+                        outpoints,
+                        Some(connector_id),
+                        Some(env::predecessor_account_id()),
+                        Some(outputs),
+                    ),
+            )
+    }
+}
@@ -0,0 +1,112 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::{near, require};
+use omni_types::btc::UtxoAddressFormat;
+use omni_types::{ChainKind, OmniAddress, UTXOChainAddress};
+
+use crate::helpers::{SdkExpect, SdkUnwrap};
+use crate::{Contract, ContractExt, Role};
+
+/// Address formats accepted for the recipients on a UTXO chain. The version is bumped
+/// on every update, so off-chain validators can tell whether their copy is stale.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UtxoAddressFormatTable {
+    pub version: u32,
+    pub formats: Vec<UtxoAddressFormat>,
+}
+
+#[near]
+impl Contract {
+    /// Replaces the address formats of the UTXO chain. Without a table the recipient addresses
+    /// are validated against the built-in formats of the chain. An empty list removes the table.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_utxo_address_formats(
+        &mut self,
+        chain_kind: ChainKind,
+        formats: Vec<UtxoAddressFormat>,
+    ) {
+        require!(self.is_utxo_chain(chain_kind), "ERR_NOT_UTXO_CHAIN");
+        if formats.is_empty() {
+            self.utxo_address_formats.remove(&chain_kind);
+            return;
+        }
+        require!(
+            formats
+                .iter()
+                .all(|format| !format.prefix.is_empty() && format.min_len <= format.max_len),
+            "ERR_INVALID_ADDRESS_FORMAT"
+        );
+
+        let version = self
+            .utxo_address_formats
+            .get(&chain_kind)
+            .map_or(1, |table| table.version + 1);
+        self.utxo_address_formats
+            .insert(&chain_kind, &UtxoAddressFormatTable { version, formats });
+    }
+
+    pub fn get_utxo_address_formats(
+        &self,
+        chain_kind: ChainKind,
+    ) -> Option<UtxoAddressFormatTable> {
+        self.utxo_address_formats.get(&chain_kind)
+    }
+}
+
+impl Contract {
+    // Without a configured table the recipient is checked against the built-in formats
+    // of the chain.
+    pub(crate) fn check_utxo_address_format(&self, recipient: &OmniAddress) {
+        let Some(address) = recipient.get_utxo_address() else {
+            return;
+        };
+        require!(
+            self.utxo_chain_address_formats(recipient.get_chain())
+                .iter()
+                .any(|format| format.matches(&address)),
+            "ERR_UNSUPPORTED_ADDRESS_FORMAT"
+        );
+    }
+
+    // Script of the outputs paying to an address of the chain, derived with the first format
+    // the address matches.
+    pub(crate) fn utxo_address_script_pubkey(
+        &self,
+        chain_kind: ChainKind,
+        address: &UTXOChainAddress,
+    ) -> Vec<u8> {
+        self.utxo_chain_address_formats(chain_kind)
+            .iter()
+            .find(|format| format.matches(address))
+            .sdk_expect("ERR_UNSUPPORTED_ADDRESS_FORMAT")
+            .script_pubkey(address)
+            .sdk_unwrap()
+    }
+
+    fn utxo_chain_address_formats(&self, chain_kind: ChainKind) -> Vec<UtxoAddressFormat> {
+        self.utxo_address_formats.get(&chain_kind).map_or_else(
+            || {
+                self.get_utxo_chain_validator(chain_kind)
+                    .default_address_formats()
+            },
+            |table| table.formats,
+        )
+    }
+
+    // The configured formats take precedence over the built-in prefixes of the chain.
+    pub(crate) fn is_utxo_script_hash_address(
+        &self,
+        chain_kind: ChainKind,
+        address: &UTXOChainAddress,
+    ) -> bool {
+        self.utxo_address_formats.get(&chain_kind).map_or_else(
+            || Self::is_script_hash_address(chain_kind, address),
+            |table| {
+                table
+                    .formats
+                    .iter()
+                    .any(|format| format.is_script_hash() && format.matches(address))
+            },
+        )
+    }
+}
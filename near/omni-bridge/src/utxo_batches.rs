@@ -0,0 +1,254 @@
+use near_plugins::{pause, AccessControllable, Pausable};
+use near_sdk::json_types::U128;
+use near_sdk::{env, near, require, AccountId, Gas, Promise, PromiseError};
+use omni_types::btc::{OutPoint, ScriptKind, TxOut};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::{SubmitOutcome, TransferId, TransferMessage};
+
+use crate::connector_registry::ConnectorId;
+use crate::connector_version::BATCH_WITHDRAW_MIN_CONNECTOR_INTERFACE_VERSION;
+use crate::external::ext_token;
+use crate::helpers::{SdkExpect, SdkUnwrap};
+use crate::locked::LockedState;
+use crate::utxo::UtxoBatchWithdraw;
+use crate::{Contract, ContractExt, Role, FT_TRANSFER_CALL_GAS, ONE_YOCTO};
+
+// The `ft_transfer_call` to the connector leaves the callback with about 80 TGas, which is
+// enough for the bookkeeping of this many transfers
+const MAX_BATCH_TRANSFERS: usize = 8;
+const SUBMIT_TRANSFERS_CALLBACK_GAS_PER_TRANSFER: Gas = Gas::from_tgas(5);
+
+/// Transfer of a batch withdrawal, kept until the connector accepts or rejects the withdrawal.
+#[near(serializers=[json])]
+#[derive(Debug, Clone)]
+pub struct UtxoBatchTransfer {
+    pub message: TransferMessage,
+    pub owner: AccountId,
+    pub fee_recipient: Option<AccountId>,
+    pub forwarded_amount: U128,
+}
+
+#[near]
+impl Contract {
+    /// Submits several transfers to a UTXO chain in a single withdrawal, so they share the
+    /// network fee of one transaction. The first outputs of the `BatchWithdraw` message pay
+    /// the transfers in order. The part of the amount of a transfer not paid to its recipient
+    /// is its share of the network fee, bounded by the `UTXOChainMsg` of the transfer.
+    /// The combined amount is forwarded to the connector in one `ft_transfer_call`. Like for
+    /// a single transfer, the submitter pays for the storage of the reservations of the inputs.
+    #[payable]
+    #[pause(except(roles(Role::DAO, Role::UnrestrictedRelayer)))]
+    pub fn submit_transfers_to_utxo_chain_connector(
+        &mut self,
+        transfer_ids: Vec<TransferId>,
+        msg: String,
+        fee_recipient: Option<AccountId>,
+    ) -> Promise {
+        require!(
+            !transfer_ids.is_empty() && transfer_ids.len() <= MAX_BATCH_TRANSFERS,
+            "ERR_INVALID_BATCH_SIZE"
+        );
+        let chain_kind = self
+            .get_transfer_message(transfer_ids[0])
+            .get_destination_chain();
+        self.require_connector_interface_version(
+            chain_kind,
+            BATCH_WITHDRAW_MIN_CONNECTOR_INTERFACE_VERSION,
+        );
+        let validator = self.get_utxo_chain_validator(chain_kind);
+        let UtxoBatchWithdraw {
+            target_addresses,
+            outpoints,
+            outputs,
+            max_fee,
+        } = validator.parse_batch_withdraw(&msg).sdk_unwrap();
+        require!(
+            target_addresses.len() == transfer_ids.len() && outputs.len() >= transfer_ids.len(),
+            "ERR_BATCH_OUTPUTS_MISMATCH"
+        );
+        self.check_max_allowed_withdraw_fee(chain_kind, Some(max_fee));
+        self.check_utxo_withdraw_limits(chain_kind, &msg, outpoints.len(), outputs.len());
+        self.check_utxo_dust_limit(chain_kind, outputs.iter().map(|output| output.value).min());
+        self.check_utxo_output_script_kinds(
+            chain_kind,
+            &outputs
+                .iter()
+                .map(|output| ScriptKind::from_hex(&output.script_pubkey))
+                .collect::<Vec<_>>(),
+        );
+        let utxo_token_id = self.require_chain_configured(chain_kind).token_id;
+        let connector_id = self.chain_connector_id(chain_kind);
+
+        let mut transfers = Vec::with_capacity(transfer_ids.len());
+        let mut total_amount: u128 = 0;
+        let mut total_fee: u128 = 0;
+        // The transfers are removed one by one, so a duplicated transfer is not found again
+        for ((transfer_id, target_address), output) in
+            transfer_ids.iter().zip(&target_addresses).zip(&outputs)
+        {
+            let transfer = self.get_transfer_message_storage(*transfer_id);
+            self.require_transfer_not_held(*transfer_id);
+            self.require_preferred_relayer(transfer_id);
+            self.validate_utxo_transfer(
+                chain_kind,
+                &transfer.message,
+                &utxo_token_id,
+                target_address,
+            )
+            .sdk_unwrap();
+            require!(
+                hex::decode(&output.script_pubkey).is_ok_and(|script| {
+                    script == self.utxo_address_script_pubkey(chain_kind, target_address)
+                }),
+                "ERR_BATCH_OUTPUT_NOT_TO_RECIPIENT"
+            );
+
+            let amount = transfer
+                .message
+                .fee
+                .net_amount(transfer.message.amount)
+                .sdk_unwrap();
+            let fee_share = amount
+                .0
+                .checked_sub(output.value.into())
+                .sdk_expect("ERR_BATCH_OUTPUT_ABOVE_AMOUNT");
+            if !transfer.message.msg.is_empty() {
+                validator
+                    .validate_default_fee_rate(
+                        &transfer.message.msg,
+                        fee_share.try_into().unwrap_or(u64::MAX),
+                    )
+                    .sdk_unwrap();
+            }
+            total_amount = total_amount.saturating_add(amount.0);
+            total_fee = total_fee.saturating_add(fee_share);
+
+            self.record_transfer_submitted(transfer_id);
+            self.take_transfer_message(*transfer_id);
+            self.lock_amount(
+                &utxo_token_id,
+                LockedState::InFlight,
+                transfer.message.amount.0,
+            );
+            self.record_connector_submission(connector_id, transfer.message.amount);
+            self.emit_event(&OmniBridgeEvent::UtxoTransferSubmittedEvent {
+                transfer_id: *transfer_id,
+                chain_kind,
+                connector: self.resolve_connector(connector_id),
+                amount,
+                fee: transfer.message.fee.clone(),
+            });
+            transfers.push(UtxoBatchTransfer {
+                fee_recipient: self.resolve_fee_recipient(
+                    chain_kind,
+                    transfer_id,
+                    fee_recipient.clone(),
+                ),
+                message: transfer.message,
+                owner: transfer.owner,
+                forwarded_amount: amount,
+            });
+        }
+        // The connector pays the network fee with the shares of the transfers
+        require!(total_fee <= max_fee, "ERR_BATCH_FEE_ABOVE_MAX_FEE");
+
+        self.check_withdraw_key_epoch(chain_kind, &outpoints);
+        self.check_custody_utxos(chain_kind, &outpoints);
+        self.check_utxo_withdraw_outputs(chain_kind, &outpoints, &outputs, total_amount);
+        self.reserve_submitted_outpoints(chain_kind, &transfer_ids, &outpoints);
+
+        let callback_gas =
+            Self::submit_transfer_callback_gas(outpoints.len()).saturating_add(Gas::from_gas(
+                SUBMIT_TRANSFERS_CALLBACK_GAS_PER_TRANSFER
+                    .as_gas()
+                    .saturating_mul(transfers.len().try_into().unwrap_or(u64::MAX)),
+            ));
+        ext_token::ext(utxo_token_id)
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(FT_TRANSFER_CALL_GAS)
+            .ft_transfer_call(
+                self.resolve_connector(connector_id),
+                U128(total_amount),
+                None,
+                msg,
+            )
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(callback_gas)
+                    .submit_transfers_to_utxo_connector_callback(
+                        transfers,
+                        outpoints,
+                        outputs,
+                        connector_id,
+                        env::predecessor_account_id(),
+                    ),
+            )
+    }
+
+    /// Completes or restores all the transfers of the batch, since the connector accepts or
+    /// rejects their withdrawal as a whole.
+    #[private]
+    pub fn submit_transfers_to_utxo_connector_callback(
+        &mut self,
+        transfers: Vec<UtxoBatchTransfer>,
+        outpoints: Vec<OutPoint>,
+        outputs: Vec<TxOut>,
+        connector_id: ConnectorId,
+        submitter: AccountId,
+        #[callback_result] call_result: &Result<U128, PromiseError>,
+    ) -> Vec<SubmitOutcome> {
+        let Some(chain_kind) = transfers
+            .first()
+            .map(|transfer| transfer.message.get_destination_chain())
+        else {
+            return Vec::new();
+        };
+        let is_success = matches!(call_result, Ok(result) if result.0 > 0);
+        let connector_failures: Vec<bool> = transfers
+            .iter()
+            .map(|transfer| {
+                self.is_utxo_connector_failure(
+                    call_result,
+                    is_success,
+                    Some(&submitter),
+                    &transfer.message.get_transfer_id(),
+                    &transfer.owner,
+                )
+            })
+            .collect();
+        for transfer in &transfers {
+            self.release_connector_in_flight(connector_id, transfer.message.amount);
+        }
+        if is_success || connector_failures.contains(&true) {
+            self.record_connector_result(chain_kind, connector_id, is_success);
+        }
+
+        if is_success {
+            self.spend_custody_utxos(chain_kind, &outpoints);
+        } else {
+            self.release_outpoints(chain_kind, &outpoints);
+        }
+        transfers
+            .into_iter()
+            .zip(connector_failures)
+            .map(|(transfer, is_connector_failure)| {
+                if is_success {
+                    self.complete_utxo_submission(
+                        transfer.message,
+                        &transfer.owner,
+                        transfer.fee_recipient,
+                        Some(connector_id),
+                        transfer.forwarded_amount,
+                        Some((outpoints.as_slice(), outputs.as_slice())),
+                    )
+                } else {
+                    self.fail_utxo_submission(
+                        transfer.message,
+                        transfer.owner,
+                        is_connector_failure,
+                    )
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,140 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::U128;
+use near_sdk::serde_json;
+use near_sdk::{env, near, require, Gas, Promise, PromiseError};
+use omni_types::btc::{OutPoint, TxOut, UtxoConsolidationMessage};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::safe_amount::SafeAmount;
+use omni_types::ChainKind;
+
+use crate::connector_version::CONSOLIDATION_MIN_CONNECTOR_INTERFACE_VERSION;
+use crate::external::ext_token;
+use crate::helpers::{SdkExpect, SdkUnwrap};
+use crate::locked::LockedState;
+use crate::{Contract, ContractExt, Role, FT_TRANSFER_CALL_GAS, ONE_YOCTO};
+
+const SUBMIT_UTXO_CONSOLIDATION_CALLBACK_GAS: Gas = Gas::from_tgas(5);
+
+#[near]
+impl Contract {
+    /// Spends the custody UTXOs into a single output locked by the same script, so the
+    /// withdrawals don't need many small inputs. The network fee is the difference between
+    /// the inputs and the output, and is paid from the protocol fees of the chain's token.
+    /// The output is added to the custody UTXOs once the connector reports it.
+    #[access_control_any(roles(Role::DAO, Role::UtxoConsolidator))]
+    pub fn submit_utxo_consolidation(
+        &mut self,
+        chain_kind: ChainKind,
+        inputs: Vec<OutPoint>,
+        output: TxOut,
+    ) -> Promise {
+        self.require_connector_interface_version(
+            chain_kind,
+            CONSOLIDATION_MIN_CONNECTOR_INTERFACE_VERSION,
+        );
+        require!(
+            self.custody_tracked_chains.contains(&chain_kind),
+            "ERR_CUSTODY_UTXO_TRACKING_DISABLED"
+        );
+        require!(inputs.len() > 1, "ERR_TOO_FEW_CONSOLIDATION_INPUTS");
+        output.validate().sdk_unwrap();
+
+        let spent_outputs: Vec<TxOut> = inputs
+            .iter()
+            .map(|outpoint| {
+                require!(
+                    !self
+                        .reserved_outpoints
+                        .contains_key(&(chain_kind, *outpoint))
+                        && !self.sweeping_outpoints.contains(&(chain_kind, *outpoint)),
+                    "ERR_OUTPOINT_ALREADY_RESERVED"
+                );
+                self.custody_utxos
+                    .remove(&(chain_kind, *outpoint))
+                    .sdk_expect("ERR_INPUT_NOT_IN_CUSTODY")
+            })
+            .collect();
+        require!(
+            spent_outputs
+                .iter()
+                .any(|spent_output| spent_output.script_pubkey == output.script_pubkey),
+            "ERR_CONSOLIDATION_OUTPUT_NOT_IN_CUSTODY"
+        );
+
+        let inputs_value = spent_outputs
+            .iter()
+            .try_fold(0u64, |total, spent_output| {
+                total.checked_add(spent_output.value)
+            })
+            .sdk_expect("ERR_CONSOLIDATION_INPUTS_OVERFLOW");
+        let fee = U128(
+            inputs_value
+                .checked_sub(output.value)
+                .filter(|fee| *fee > 0)
+                .sdk_expect("ERR_INVALID_CONSOLIDATION_FEE")
+                .into(),
+        );
+
+        let msg = serde_json::to_string(&UtxoConsolidationMessage::Consolidate {
+            input: inputs.clone(),
+            output: output.clone(),
+        })
+        .sdk_expect("ERR_SERIALIZE_CONSOLIDATION_MSG");
+        self.check_utxo_withdraw_limits(chain_kind, &msg, inputs.len(), 1);
+
+        let token_id = self.require_chain_configured(chain_kind).token_id;
+        let balance = SafeAmount::from(self.get_protocol_fees(token_id.clone()))
+            .checked_sub(fee.into())
+            .unwrap_or_else(|_| env::panic_str("ERR_INSUFFICIENT_PROTOCOL_FEES"));
+        self.protocol_fees.insert(&token_id, &balance.into());
+        self.unlock_amount(&token_id, LockedState::FeePool, fee.0);
+
+        let connector_id = self.chain_connector_id(chain_kind);
+        ext_token::ext(token_id)
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(FT_TRANSFER_CALL_GAS)
+            .ft_transfer_call(self.resolve_connector(connector_id), fee, None, msg)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(SUBMIT_UTXO_CONSOLIDATION_CALLBACK_GAS)
+                    .submit_utxo_consolidation_callback(
+                        chain_kind,
+                        inputs,
+                        spent_outputs,
+                        output,
+                        fee,
+                    ),
+            )
+    }
+
+    #[private]
+    pub fn submit_utxo_consolidation_callback(
+        &mut self,
+        chain_kind: ChainKind,
+        inputs: Vec<OutPoint>,
+        spent_outputs: Vec<TxOut>,
+        output: TxOut,
+        fee: U128,
+        #[callback_result] call_result: &Result<U128, PromiseError>,
+    ) {
+        let used_fee = call_result.as_ref().map_or(0, |used_fee| used_fee.0);
+        let token_id = self.require_chain_configured(chain_kind).token_id;
+        // The part of the fee not used by the connector goes back to the protocol fees
+        self.accrue_protocol_fee(&token_id, U128(fee.0.saturating_sub(used_fee)));
+
+        if used_fee == 0 {
+            for (outpoint, spent_output) in inputs.into_iter().zip(spent_outputs) {
+                self.custody_utxos
+                    .insert(&(chain_kind, outpoint), &spent_output);
+            }
+            return;
+        }
+
+        self.emit_event(&OmniBridgeEvent::UtxoConsolidatedEvent {
+            chain_kind,
+            inputs,
+            output,
+            fee: U128(used_fee),
+        });
+    }
+}
@@ -0,0 +1,103 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{env, near, require, serde_json};
+use omni_types::ChainKind;
+
+use crate::helpers::SdkExpect;
+use crate::utxo::{UtxoChainValidator, UtxoFeeModel};
+use crate::{Contract, ContractExt, Role};
+
+/// Rates older than this are not used for the withdrawals, about 20 minutes of blocks.
+pub const MAX_UTXO_FEE_RATE_AGE: u64 = 1_200;
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UtxoFeeRate {
+    /// Fee rate in the unit of the fee model of the chain, e.g. koinu per kilobyte for Dogecoin.
+    pub fee_rate: U64,
+    pub updated_at_height: U64,
+}
+
+#[near]
+impl Contract {
+    /// Sets the current fee rate of the UTXO chain. It bounds the fee of the withdrawals whose
+    /// `Withdraw` message doesn't set one, as long as the rate is not stale.
+    #[access_control_any(roles(Role::DAO, Role::FeeRateOracle))]
+    pub fn set_utxo_fee_rate(&mut self, chain_kind: ChainKind, fee_rate: U64) {
+        require!(self.is_utxo_chain(chain_kind), "ERR_NOT_UTXO_CHAIN");
+        require!(
+            self.get_utxo_chain_validator(chain_kind).fee_model() != UtxoFeeModel::MaxFee,
+            "ERR_FEE_RATE_NOT_SUPPORTED"
+        );
+        require!(fee_rate.0 > 0, "ERR_INVALID_FEE_RATE");
+        self.utxo_fee_rates.insert(
+            &chain_kind,
+            &UtxoFeeRate {
+                fee_rate,
+                updated_at_height: U64(env::block_height()),
+            },
+        );
+    }
+
+    pub fn get_utxo_fee_rate(&self, chain_kind: ChainKind) -> Option<UtxoFeeRate> {
+        self.utxo_fee_rates.get(&chain_kind)
+    }
+
+    /// Sets the highest fee bound of the `Withdraw` messages of the UTXO chain, in the unit of
+    /// the fee model of the chain. It stops the relayers from burning the transferred amount in
+    /// network fees when the transfer doesn't bound the fee itself. While it is set, the
+    /// withdrawals without a bound nor a fresh oracle rate are rejected.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_max_allowed_withdraw_fee(&mut self, chain_kind: ChainKind, max_fee: Option<U128>) {
+        require!(self.is_utxo_chain(chain_kind), "ERR_NOT_UTXO_CHAIN");
+        if let Some(max_fee) = max_fee {
+            self.max_allowed_withdraw_fees.insert(&chain_kind, &max_fee);
+        } else {
+            self.max_allowed_withdraw_fees.remove(&chain_kind);
+        }
+    }
+
+    pub fn get_max_allowed_withdraw_fee(&self, chain_kind: ChainKind) -> Option<U128> {
+        self.max_allowed_withdraw_fees.get(&chain_kind)
+    }
+}
+
+impl Contract {
+    pub(crate) fn fresh_utxo_fee_rate(&self, chain_kind: ChainKind) -> Option<u64> {
+        self.utxo_fee_rates
+            .get(&chain_kind)
+            .filter(|rate| {
+                env::block_height().saturating_sub(rate.updated_at_height.0)
+                    <= MAX_UTXO_FEE_RATE_AGE
+            })
+            .map(|rate| rate.fee_rate.0)
+    }
+
+    pub(crate) fn check_max_allowed_withdraw_fee(
+        &self,
+        chain_kind: ChainKind,
+        max_fee: Option<u128>,
+    ) {
+        if let Some(max_allowed_fee) = self.max_allowed_withdraw_fees.get(&chain_kind) {
+            // A message without a bound leaves the fee to the relayer, so the cap requires one
+            let max_fee = max_fee.sdk_expect("ERR_WITHDRAW_FEE_BOUND_REQUIRED");
+            require!(
+                max_fee <= max_allowed_fee.0,
+                "ERR_WITHDRAW_FEE_ABOVE_MAX_ALLOWED"
+            );
+        }
+    }
+
+    // Sets the fee bound of a `Withdraw` message that doesn't have one to the rate of the
+    // oracle, so the connector doesn't reject it. The message is already parsed by the validator.
+    pub(crate) fn with_default_utxo_fee_rate(
+        validator: &dyn UtxoChainValidator,
+        msg: &str,
+        fee_rate: u64,
+    ) -> String {
+        let mut msg: serde_json::Value = serde_json::from_str(msg).sdk_expect("ERR_PARSE_MSG");
+        msg["Withdraw"][validator.max_fee_field()] =
+            serde_json::Value::String(fee_rate.to_string());
+        msg.to_string()
+    }
+}
@@ -0,0 +1,134 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::U64;
+use near_sdk::{env, near, require};
+use omni_types::btc::OutPoint;
+use omni_types::ChainKind;
+
+use crate::helpers::SdkExpect;
+use crate::{Contract, ContractExt, Role};
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UtxoKeyEpoch {
+    pub epoch: u32,
+    // Derivation path of the signer key that controls the bridge address of the epoch
+    pub derivation_path: String,
+}
+
+/// Signer keys of the bridge addresses of a UTXO chain. During the transition window
+/// the deposits are accepted on the addresses of both epochs. After the cutover height
+/// (a NEAR block height) the withdrawals can't spend the UTXOs of the previous epoch.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UtxoKeyRotation {
+    pub current: UtxoKeyEpoch,
+    pub previous: Option<UtxoKeyEpoch>,
+    pub cutover_height: U64,
+}
+
+impl UtxoKeyRotation {
+    pub fn is_cutover_passed(&self) -> bool {
+        env::block_height() >= self.cutover_height.0
+    }
+}
+
+#[near]
+impl Contract {
+    /// Starts the transition to the key derived with `derivation_path`.
+    /// The first registered key of a chain has no transition window.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn register_utxo_key_epoch(
+        &mut self,
+        chain_kind: ChainKind,
+        derivation_path: String,
+        cutover_height: U64,
+    ) {
+        require!(self.is_utxo_chain(chain_kind), "ERR_NOT_UTXO_CHAIN");
+        let rotation = if let Some(rotation) = self.utxo_key_rotations.get(&chain_kind) {
+            require!(rotation.previous.is_none(), "ERR_KEY_ROTATION_IN_PROGRESS");
+            require!(
+                cutover_height.0 > env::block_height(),
+                "ERR_INVALID_CUTOVER_HEIGHT"
+            );
+            UtxoKeyRotation {
+                current: UtxoKeyEpoch {
+                    epoch: rotation.current.epoch + 1,
+                    derivation_path,
+                },
+                previous: Some(rotation.current),
+                cutover_height,
+            }
+        } else {
+            UtxoKeyRotation {
+                current: UtxoKeyEpoch {
+                    epoch: 0,
+                    derivation_path,
+                },
+                previous: None,
+                cutover_height,
+            }
+        };
+        self.utxo_key_rotations.insert(&chain_kind, &rotation);
+    }
+
+    /// Ends the transition window, so the deposits to the address of the previous epoch
+    /// are no longer accepted.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn finish_utxo_key_rotation(&mut self, chain_kind: ChainKind) {
+        let mut rotation = self
+            .utxo_key_rotations
+            .get(&chain_kind)
+            .sdk_expect("ERR_KEY_ROTATION_NOT_FOUND");
+        require!(
+            rotation.previous.is_some(),
+            "ERR_KEY_ROTATION_NOT_IN_PROGRESS"
+        );
+        require!(
+            rotation.is_cutover_passed(),
+            "ERR_CUTOVER_HEIGHT_NOT_REACHED"
+        );
+        rotation.previous = None;
+        self.utxo_key_rotations.insert(&chain_kind, &rotation);
+    }
+
+    /// Marks the UTXOs held by the addresses of the previous epochs, which can't be spent
+    /// by the withdrawals after the cutover.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn add_legacy_outpoints(&mut self, chain_kind: ChainKind, outpoints: Vec<OutPoint>) {
+        for outpoint in outpoints {
+            self.legacy_outpoints.insert(&(chain_kind, outpoint));
+        }
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn remove_legacy_outpoints(&mut self, chain_kind: ChainKind, outpoints: Vec<OutPoint>) {
+        for outpoint in outpoints {
+            self.legacy_outpoints.remove(&(chain_kind, outpoint));
+        }
+    }
+
+    pub fn get_utxo_key_rotation(&self, chain_kind: ChainKind) -> Option<UtxoKeyRotation> {
+        self.utxo_key_rotations.get(&chain_kind)
+    }
+
+    pub fn is_legacy_outpoint(&self, chain_kind: ChainKind, outpoint: OutPoint) -> bool {
+        self.legacy_outpoints.contains(&(chain_kind, outpoint))
+    }
+}
+
+impl Contract {
+    pub(crate) fn check_withdraw_key_epoch(&self, chain_kind: ChainKind, outpoints: &[OutPoint]) {
+        let Some(rotation) = self.utxo_key_rotations.get(&chain_kind) else {
+            return;
+        };
+        if !rotation.is_cutover_passed() {
+            return;
+        }
+        require!(
+            outpoints
+                .iter()
+                .all(|outpoint| !self.legacy_outpoints.contains(&(chain_kind, *outpoint))),
+            "ERR_LEGACY_EPOCH_OUTPOINT"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::U128;
+use near_sdk::{env, near, require, AccountId};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::{ChainKind, OmniAddress, TransferId, UtxoFinTransferMsg};
+
+use crate::{Contract, ContractExt, Role};
+
+/// Return of the inbound UTXO deposits rejected by their NEAR recipient. The deposits of the
+/// recipients without storage are not returned, they stay with the connector until the
+/// storage is deposited.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UtxoRefundPolicy {
+    /// Relayer fee of the return transfer, deducted from the deposit.
+    pub fee: U128,
+}
+
+#[near]
+impl Contract {
+    /// Sets the policy of the returns of the deposits from `chain_kind` to their origin address.
+    /// Without a policy, the rejected deposits are returned to the connector.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_utxo_refund_policy(
+        &mut self,
+        chain_kind: ChainKind,
+        policy: Option<UtxoRefundPolicy>,
+    ) {
+        require!(self.is_utxo_chain(chain_kind), "ERR_NOT_UTXO_CHAIN");
+        if let Some(policy) = policy {
+            self.utxo_refund_policies.insert(&chain_kind, &policy);
+        } else {
+            self.utxo_refund_policies.remove(&chain_kind);
+        }
+    }
+
+    pub fn get_utxo_refund_policy(&self, chain_kind: ChainKind) -> Option<UtxoRefundPolicy> {
+        self.utxo_refund_policies.get(&chain_kind)
+    }
+}
+
+impl Contract {
+    // Queues a transfer of the deposit back to the address that funded it. The deposit is
+    // returned to the connector instead when the chain has no policy, when its inputs come
+    // from several addresses, or when it doesn't cover the fee of the return.
+    pub(crate) fn try_refund_utxo_deposit_to_origin(
+        &mut self,
+        token_id: &AccountId,
+        amount: U128,
+        utxo_fin_transfer_msg: &UtxoFinTransferMsg,
+        origin_chain: ChainKind,
+        storage_owner: &AccountId,
+    ) -> bool {
+        let Some(policy) = self.utxo_refund_policies.get(&origin_chain) else {
+            return false;
+        };
+        let Some(refund_address) = Self::utxo_refund_address(origin_chain, utxo_fin_transfer_msg)
+        else {
+            return false;
+        };
+        // A refund that doesn't fit under the cap goes back to the connector instead of failing
+        if amount.0 <= policy.fee.0 || self.check_tvl_cap(token_id, amount.0).is_err() {
+            return false;
+        }
+
+        let transfer_id = utxo_fin_transfer_msg.get_transfer_id(origin_chain);
+        let refund_msg = UtxoFinTransferMsg {
+            recipient: refund_address.clone(),
+            relayer_fee: policy.fee,
+            msg: String::new(),
+            ..utxo_fin_transfer_msg.clone()
+        };
+        self.utxo_fin_transfer_to_other_chain(
+            token_id.clone(),
+            amount,
+            refund_msg,
+            origin_chain,
+            storage_owner,
+        );
+
+        env::log_str(
+            &OmniBridgeEvent::UtxoRefundEvent {
+                transfer_id,
+                refund_address,
+                new_transfer_id: TransferId {
+                    origin_chain: ChainKind::Near,
+                    origin_nonce: self.current_origin_nonce,
+                },
+            }
+            .to_log_string(),
+        );
+
+        true
+    }
+
+    fn utxo_refund_address(
+        origin_chain: ChainKind,
+        utxo_fin_transfer_msg: &UtxoFinTransferMsg,
+    ) -> Option<OmniAddress> {
+        let (first, rest) = utxo_fin_transfer_msg.sender_addresses.split_first()?;
+        if rest.iter().any(|address| address != first) {
+            return None;
+        }
+        OmniAddress::new_from_slice(origin_chain, first.as_bytes()).ok()
+    }
+}
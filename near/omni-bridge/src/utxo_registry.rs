@@ -0,0 +1,78 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::{near, require, AccountId};
+use omni_types::ChainKind;
+
+use crate::helpers::SdkExpect;
+use crate::utxo::{builtin_utxo_chain_validator, UtxoFeeModel};
+use crate::{Contract, ContractExt, Role};
+
+#[near(serializers=[json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UtxoChainRegistration {
+    pub token_id: AccountId,
+    pub connector_id: AccountId,
+    pub fee_model: UtxoFeeModel,
+}
+
+#[near]
+impl Contract {
+    /// Onboards a UTXO chain by configuration. The chain needs its `OmniAddress` variant and a
+    /// built-in validator, whose fee model has to match `fee_model`.
+    #[payable]
+    #[access_control_any(roles(Role::DAO))]
+    pub fn register_utxo_chain(
+        &mut self,
+        chain_kind: ChainKind,
+        token_id: AccountId,
+        connector_id: AccountId,
+        fee_model: UtxoFeeModel,
+        decimals: u8,
+    ) {
+        let validator = builtin_utxo_chain_validator(chain_kind)
+            .filter(|_| self.is_utxo_chain(chain_kind))
+            .sdk_expect("ERR_NOT_UTXO_CHAIN");
+        require!(
+            !self.utxo_chain_connectors.contains_key(&chain_kind),
+            "ERR_UTXO_CHAIN_ALREADY_REGISTERED"
+        );
+        require!(validator.fee_model() == fee_model, "ERR_FEE_MODEL_MISMATCH");
+
+        self.add_utxo_chain_connector(chain_kind, connector_id, token_id, decimals);
+    }
+
+    /// Removes the connector and the token of the chain, so no transfer can be submitted to it.
+    /// The token mapping is kept, so the chain can be registered again with the same token.
+    /// The chain can't be removed while transfers to it are pending or in flight at its
+    /// connector, since they could neither be submitted nor restored.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn unregister_utxo_chain(&mut self, chain_kind: ChainKind) {
+        require!(
+            self.get_pending_transfers_count(chain_kind) == 0
+                && self
+                    .chain_connector_ids
+                    .get(&chain_kind)
+                    .is_none_or(|connector_id| {
+                        self.get_connector_in_flight(connector_id).transfers == 0
+                    }),
+            "ERR_UTXO_CHAIN_HAS_PENDING_TRANSFERS"
+        );
+        require!(
+            self.utxo_chain_connectors.remove(&chain_kind).is_some(),
+            "ERR_UTXO_CHAIN_NOT_REGISTERED"
+        );
+        self.chain_connector_ids.remove(&chain_kind);
+        self.connector_interface_versions.remove(&chain_kind);
+    }
+
+    pub fn get_utxo_chain_registration(
+        &self,
+        chain_kind: ChainKind,
+    ) -> Option<UtxoChainRegistration> {
+        let config = self.utxo_chain_connectors.get(&chain_kind)?;
+        Some(UtxoChainRegistration {
+            token_id: config.token_id.clone(),
+            connector_id: config.connector.clone(),
+            fee_model: builtin_utxo_chain_validator(chain_kind)?.fee_model(),
+        })
+    }
+}
@@ -0,0 +1,174 @@
+use near_plugins::AccessControllable;
+use near_sdk::{borsh, env, near, require, AccountId, NearToken};
+use omni_types::btc::{OutPoint, TxOut};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::{ChainKind, TransferId};
+
+use crate::helpers::{SdkExpect, SdkUnwrap};
+use crate::{Contract, ContractExt, Role};
+
+/// Withdrawal accepted by the connector of a UTXO chain, kept so its owner can watch for its
+/// transaction and bump its fee. The storage is paid from the storage balance of the owner.
+#[near(serializers=[borsh])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UtxoWithdrawal {
+    pub chain_kind: ChainKind,
+    pub owner: AccountId,
+    /// Hash of the inputs forwarded to the connector, spent by the transaction and by its
+    /// replacements.
+    pub inputs_hash: [u8; 32],
+    /// Hash of the outputs of the transaction, forwarded to the connector or requested by the
+    /// last fee bump.
+    pub outputs_hash: [u8; 32],
+    pub txid: Option<[u8; 32]>,
+}
+
+#[near]
+impl Contract {
+    /// Records the id of the transaction that pays out the withdrawal, so the user can watch for
+    /// that exact transaction instead of relying on the relayer. The connector of the chain
+    /// reports the hex of the transaction once it has signed it, and the bridge computes the id
+    /// after checking that it spends the forwarded inputs into the forwarded outputs, in order.
+    /// A replacement of the transaction overwrites the recorded id.
+    pub fn record_utxo_withdrawal_txid(
+        &mut self,
+        chain_kind: ChainKind,
+        transfer_id: TransferId,
+        tx: String,
+    ) {
+        require!(
+            self.get_chain_connector(chain_kind).as_ref() == Some(&env::predecessor_account_id()),
+            "ERR_TXID_REPORT_NOT_ALLOWED"
+        );
+        let mut withdrawal = self
+            .utxo_withdrawals
+            .get(&transfer_id)
+            .filter(|withdrawal| withdrawal.chain_kind == chain_kind)
+            .sdk_expect("ERR_WITHDRAWAL_NOT_FORWARDED");
+        let tx = hex::decode(&tx).sdk_expect("ERR_INVALID_TRANSACTION");
+        let transaction = self
+            .get_utxo_chain_validator(chain_kind)
+            .parse_transaction(&tx)
+            .sdk_unwrap();
+        require!(
+            Self::utxo_inputs_hash(&transaction.inputs) == withdrawal.inputs_hash
+                && Self::utxo_outputs_hash(&transaction.outputs) == withdrawal.outputs_hash,
+            "ERR_TRANSACTION_NOT_WITHDRAWAL"
+        );
+
+        // The storage of the id was paid with the record
+        withdrawal.txid = Some(transaction.txid);
+        self.utxo_withdrawals.insert(&transfer_id, &withdrawal);
+        self.emit_event(&OmniBridgeEvent::UtxoWithdrawalTxidEvent {
+            transfer_id,
+            chain_kind,
+            txid: hex::encode(transaction.txid),
+        });
+    }
+
+    /// Removes the record of the withdrawal once its transaction is confirmed, and refunds its
+    /// storage to the owner. The fee of the withdrawal can't be bumped afterwards.
+    pub fn remove_utxo_withdrawal(&mut self, transfer_id: TransferId) {
+        let withdrawal = self
+            .utxo_withdrawals
+            .get(&transfer_id)
+            .sdk_expect("ERR_WITHDRAWAL_NOT_FORWARDED");
+        let predecessor = env::predecessor_account_id();
+        require!(
+            predecessor == withdrawal.owner
+                || self.get_chain_connector(withdrawal.chain_kind).as_ref() == Some(&predecessor)
+                || self.acl_has_role(Role::DAO.into(), predecessor),
+            "ERR_WITHDRAWAL_REMOVAL_NOT_ALLOWED"
+        );
+
+        self.utxo_withdrawals.remove(&transfer_id);
+        if let Some(mut storage) = self.accounts_balances.get(&withdrawal.owner) {
+            storage.available =
+                storage
+                    .available
+                    .saturating_add(Self::required_balance_for_utxo_withdrawal(
+                        &transfer_id,
+                        &withdrawal.owner,
+                    ));
+            self.accounts_balances.insert(&withdrawal.owner, &storage);
+        }
+    }
+
+    /// Returns the id of the transaction of the withdrawal as shown by the explorers.
+    pub fn get_expected_utxo_txid(&self, transfer_id: TransferId) -> Option<String> {
+        self.utxo_withdrawals
+            .get(&transfer_id)
+            .and_then(|withdrawal| withdrawal.txid)
+            .map(hex::encode)
+    }
+}
+
+impl Contract {
+    // The withdrawals of the owners without enough storage balance are not recorded. Their
+    // fee can still be bumped by the RBF operators through `rbf_increase_gas_fee`.
+    pub(crate) fn add_utxo_withdrawal(
+        &mut self,
+        transfer_id: &TransferId,
+        chain_kind: ChainKind,
+        owner: &AccountId,
+        outpoints: &[OutPoint],
+        outputs: &[TxOut],
+    ) {
+        let required_balance = Self::required_balance_for_utxo_withdrawal(transfer_id, owner);
+        if self
+            .try_update_storage_balance(
+                owner.clone(),
+                required_balance,
+                NearToken::from_yoctonear(0),
+            )
+            .is_ok()
+        {
+            self.utxo_withdrawals.insert(
+                transfer_id,
+                &UtxoWithdrawal {
+                    chain_kind,
+                    owner: owner.clone(),
+                    inputs_hash: Self::utxo_inputs_hash(outpoints),
+                    outputs_hash: Self::utxo_outputs_hash(outputs),
+                    txid: None,
+                },
+            );
+        }
+    }
+
+    // Covers the record with the id of its transaction
+    pub(crate) fn required_balance_for_utxo_withdrawal(
+        transfer_id: &TransferId,
+        owner: &AccountId,
+    ) -> NearToken {
+        let record_len: u64 = borsh::to_vec(&(
+            transfer_id,
+            UtxoWithdrawal {
+                chain_kind: ChainKind::Btc,
+                owner: owner.clone(),
+                inputs_hash: [0; 32],
+                outputs_hash: [0; 32],
+                txid: Some([0; 32]),
+            },
+        ))
+        .sdk_expect("ERR_BORSH")
+        .len()
+        .try_into()
+        .sdk_expect("ERR_CAST");
+
+        env::storage_byte_cost().saturating_mul((Self::get_basic_storage() + record_len).into())
+    }
+
+    pub(crate) fn utxo_inputs_hash(outpoints: &[OutPoint]) -> [u8; 32] {
+        env::sha256_array(&borsh::to_vec(outpoints).sdk_expect("ERR_BORSH"))
+    }
+
+    // The scripts are compared in the lowercase hex of the parsed transactions
+    pub(crate) fn utxo_outputs_hash(outputs: &[TxOut]) -> [u8; 32] {
+        let outputs: Vec<(u64, String)> = outputs
+            .iter()
+            .map(|output| (output.value, output.script_pubkey.to_ascii_lowercase()))
+            .collect();
+        env::sha256_array(&borsh::to_vec(&outputs).sdk_expect("ERR_BORSH"))
+    }
+}
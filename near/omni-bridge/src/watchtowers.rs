@@ -0,0 +1,240 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::Base58CryptoHash;
+use near_sdk::{env, near, require, AccountId, NearToken, Promise};
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::TransferId;
+
+use crate::helpers::SdkExpect;
+use crate::{Contract, ContractExt, Role};
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchtowerConfig {
+    pub min_stake: NearToken,
+    /// Slashed from the stake of every watchtower whose flag is dismissed as frivolous.
+    pub penalty: NearToken,
+    /// Number of flags from distinct watchtowers that holds the transfer.
+    pub review_threshold: u32,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Watchtower {
+    pub stake: NearToken,
+    pub active_flags: u32,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferFlag {
+    pub watchtower: AccountId,
+    // Hash of the published report of the watchtower
+    pub reason_hash: Base58CryptoHash,
+    // Paid by the watchtower and returned to it when the flags are cleared
+    pub storage_deposit: NearToken,
+}
+
+#[near]
+impl Contract {
+    /// Enables the watchtowers. Without a config, no watchtower can register or flag transfers.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_watchtower_config(&mut self, config: Option<WatchtowerConfig>) {
+        if let Some(config) = &config {
+            require!(config.review_threshold > 0, "ERR_INVALID_REVIEW_THRESHOLD");
+            // Without a penalty, registering and flagging would cost nothing to a spammer
+            require!(!config.penalty.is_zero(), "ERR_INVALID_WATCHTOWER_PENALTY");
+            require!(
+                config.penalty <= config.min_stake,
+                "ERR_PENALTY_EXCEEDS_STAKE"
+            );
+        }
+        self.watchtower_config = config;
+    }
+
+    pub fn get_watchtower_config(&self) -> Option<WatchtowerConfig> {
+        self.watchtower_config.clone()
+    }
+
+    /// Registers the caller as a watchtower with the attached deposit as its stake.
+    #[payable]
+    pub fn register_watchtower(&mut self) {
+        let config = self.require_watchtower_config();
+        let account_id = env::predecessor_account_id();
+        require!(
+            self.watchtowers.get(&account_id).is_none(),
+            "ERR_WATCHTOWER_ALREADY_REGISTERED"
+        );
+        require!(
+            env::attached_deposit() >= config.min_stake,
+            "ERR_INSUFFICIENT_WATCHTOWER_STAKE"
+        );
+
+        self.watchtowers.insert(
+            &account_id,
+            &Watchtower {
+                stake: env::attached_deposit(),
+                active_flags: 0,
+            },
+        );
+    }
+
+    /// Returns the remaining stake to the watchtower once its flags have been reviewed.
+    pub fn unregister_watchtower(&mut self) -> Promise {
+        let account_id = env::predecessor_account_id();
+        let watchtower = self.require_watchtower(&account_id);
+        require!(
+            watchtower.active_flags == 0,
+            "ERR_WATCHTOWER_HAS_ACTIVE_FLAGS"
+        );
+
+        self.watchtowers.remove(&account_id);
+        Promise::new(account_id).transfer(watchtower.stake)
+    }
+
+    pub fn get_watchtower(&self, account_id: AccountId) -> Option<Watchtower> {
+        self.watchtowers.get(&account_id)
+    }
+
+    /// Flags a pending transfer for review. Once it is flagged by `review_threshold`
+    /// watchtowers, it can't be signed or submitted until a guardian clears it.
+    /// Every active flag of the watchtower must be covered by a penalty of its stake.
+    /// The storage of the flag is paid from the attached deposit or the storage balance of
+    /// the watchtower, and returned to its storage balance when the flags are cleared.
+    #[payable]
+    pub fn flag_transfer(&mut self, transfer_id: TransferId, reason_hash: Base58CryptoHash) {
+        let config = self.require_watchtower_config();
+        let account_id = env::predecessor_account_id();
+        let mut watchtower = self.require_watchtower(&account_id);
+        require!(
+            watchtower.stake
+                >= config
+                    .penalty
+                    .saturating_mul(u128::from(watchtower.active_flags) + 1),
+            "ERR_INSUFFICIENT_WATCHTOWER_STAKE"
+        );
+        self.get_transfer_message_storage(transfer_id);
+
+        let mut flags = self.transfer_flags.get(&transfer_id).unwrap_or_default();
+        require!(
+            flags.iter().all(|flag| flag.watchtower != account_id),
+            "ERR_TRANSFER_ALREADY_FLAGGED"
+        );
+
+        let storage_usage = env::storage_usage();
+        flags.push(TransferFlag {
+            watchtower: account_id.clone(),
+            reason_hash,
+            storage_deposit: NearToken::from_yoctonear(0),
+        });
+        self.transfer_flags.insert(&transfer_id, &flags);
+        let required_balance = env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into());
+        // The deposit has a fixed size, so recording it doesn't change the storage usage
+        if let Some(flag) = flags.last_mut() {
+            flag.storage_deposit = required_balance;
+        }
+        self.transfer_flags.insert(&transfer_id, &flags);
+        watchtower.active_flags += 1;
+        self.watchtowers.insert(&account_id, &watchtower);
+        self.update_storage_balance(
+            account_id.clone(),
+            required_balance,
+            env::attached_deposit(),
+        );
+
+        env::log_str(
+            &OmniBridgeEvent::TransferFlaggedEvent {
+                transfer_id,
+                watchtower: account_id,
+                reason_hash,
+                flags: u32::try_from(flags.len()).unwrap_or(u32::MAX),
+            }
+            .to_log_string(),
+        );
+    }
+
+    pub fn get_transfer_flags(&self, transfer_id: TransferId) -> Vec<TransferFlag> {
+        self.transfer_flags.get(&transfer_id).unwrap_or_default()
+    }
+
+    pub fn is_transfer_held(&self, transfer_id: TransferId) -> bool {
+        let Some(config) = &self.watchtower_config else {
+            return false;
+        };
+        self.transfer_flags.get(&transfer_id).is_some_and(|flags| {
+            u32::try_from(flags.len()).unwrap_or(u32::MAX) >= config.review_threshold
+        })
+    }
+
+    /// Removes the flags of the transfer after its review and returns their storage deposits.
+    /// The flags dismissed as frivolous cost the penalty to the stake of their watchtowers.
+    #[access_control_any(roles(Role::Guardian))]
+    pub fn clear_transfer_flags(&mut self, transfer_id: TransferId, is_frivolous: bool) {
+        let flags = self
+            .transfer_flags
+            .remove(&transfer_id)
+            .sdk_expect("ERR_TRANSFER_NOT_FLAGGED");
+        let penalty = if is_frivolous {
+            self.watchtower_config
+                .as_ref()
+                .map_or(NearToken::from_yoctonear(0), |config| config.penalty)
+        } else {
+            NearToken::from_yoctonear(0)
+        };
+
+        // The slashed stakes stay on the contract
+        let mut slashed = NearToken::from_yoctonear(0);
+        for flag in &flags {
+            // Watchtowers can't unregister with active flags, so their records still exist
+            let mut watchtower = self.require_watchtower(&flag.watchtower);
+            let watchtower_penalty = penalty.min(watchtower.stake);
+            watchtower.active_flags -= 1;
+            watchtower.stake = watchtower.stake.saturating_sub(watchtower_penalty);
+            self.watchtowers.insert(&flag.watchtower, &watchtower);
+            slashed = slashed.saturating_add(watchtower_penalty);
+            self.refund_flag_storage_deposit(flag);
+        }
+
+        env::log_str(
+            &OmniBridgeEvent::TransferFlagsClearedEvent {
+                transfer_id,
+                guardian: env::predecessor_account_id(),
+                is_frivolous,
+                slashed,
+            }
+            .to_log_string(),
+        );
+    }
+}
+
+impl Contract {
+    pub(crate) fn require_transfer_not_held(&self, transfer_id: TransferId) {
+        require!(
+            !self.is_transfer_held(transfer_id),
+            "ERR_TRANSFER_HELD_FOR_REVIEW"
+        );
+    }
+
+    // Credits the storage balance of the watchtower, or sends the deposit back if it paid
+    // the flag with an attached deposit and has no storage balance.
+    fn refund_flag_storage_deposit(&mut self, flag: &TransferFlag) {
+        if let Some(mut storage) = self.accounts_balances.get(&flag.watchtower) {
+            storage.available = storage.available.saturating_add(flag.storage_deposit);
+            self.accounts_balances.insert(&flag.watchtower, &storage);
+        } else {
+            Self::refund(flag.watchtower.clone(), flag.storage_deposit);
+        }
+    }
+
+    fn require_watchtower_config(&self) -> WatchtowerConfig {
+        self.watchtower_config
+            .clone()
+            .sdk_expect("ERR_WATCHTOWERS_DISABLED")
+    }
+
+    fn require_watchtower(&self, account_id: &AccountId) -> Watchtower {
+        self.watchtowers
+            .get(account_id)
+            .sdk_expect("ERR_WATCHTOWER_NOT_REGISTERED")
+    }
+}
@@ -6,7 +6,7 @@ use omni_types::evm::events::parse_evm_event;
 use omni_types::evm::header::BlockHeader;
 use omni_types::evm::receipt::{LogEntry, Receipt};
 use omni_types::prover_args::EvmVerifyProofArgs;
-use omni_types::prover_result::{ProofKind, ProverResult};
+use omni_types::prover_result::{ProofKind, ProverResult, UnresolvedRecipientTransferMessage};
 use omni_types::utils::keccak256;
 use omni_types::ChainKind;
 use rlp::Rlp;
@@ -114,10 +114,15 @@ impl EvmProver {
         }
 
         match kind {
-            ProofKind::InitTransfer => Ok(ProverResult::InitTransfer(parse_evm_event(
-                self.chain_kind,
-                log_entry_data,
-            )?)),
+            ProofKind::InitTransfer => {
+                let transfer: UnresolvedRecipientTransferMessage =
+                    parse_evm_event(self.chain_kind, log_entry_data)?;
+                // Only a recipient that can't be parsed makes the locker hold the tokens for the sender
+                match transfer.try_resolve() {
+                    Ok(init_transfer) => Ok(ProverResult::InitTransfer(init_transfer)),
+                    Err(transfer) => Ok(ProverResult::UnresolvedRecipientTransfer(transfer)),
+                }
+            }
             ProofKind::FinTransfer => Ok(ProverResult::FinTransfer(parse_evm_event(
                 self.chain_kind,
                 log_entry_data,
@@ -130,6 +135,14 @@ impl EvmProver {
                 self.chain_kind,
                 log_entry_data,
             )?)),
+            ProofKind::GovernanceAction => Ok(ProverResult::GovernanceAction(parse_evm_event(
+                self.chain_kind,
+                log_entry_data,
+            )?)),
+            ProofKind::Message => Ok(ProverResult::Message(parse_evm_event(
+                self.chain_kind,
+                log_entry_data,
+            )?)),
         }
     }
 
@@ -4,7 +4,7 @@ use near_sdk::{
     PromiseError,
 };
 use omni_types::prover_args::WormholeVerifyProofArgs;
-use omni_types::prover_result::{ProofKind, ProverResult};
+use omni_types::prover_result::{ProofKind, ProverResult, UnresolvedRecipientTransferMessage};
 
 mod byte_utils;
 mod parsed_vaa;
@@ -77,10 +77,21 @@ impl WormholeOmniProverProxy {
         );
 
         match proof_kind {
-            ProofKind::InitTransfer => Ok(ProverResult::InitTransfer(parsed_vaa.try_into()?)),
+            ProofKind::InitTransfer => {
+                let transfer: UnresolvedRecipientTransferMessage = parsed_vaa.try_into()?;
+                // Only a recipient that can't be parsed makes the locker hold the tokens for the sender
+                match transfer.try_resolve() {
+                    Ok(init_transfer) => Ok(ProverResult::InitTransfer(init_transfer)),
+                    Err(transfer) => Ok(ProverResult::UnresolvedRecipientTransfer(transfer)),
+                }
+            }
             ProofKind::FinTransfer => Ok(ProverResult::FinTransfer(parsed_vaa.try_into()?)),
             ProofKind::DeployToken => Ok(ProverResult::DeployToken(parsed_vaa.try_into()?)),
             ProofKind::LogMetadata => Ok(ProverResult::LogMetadata(parsed_vaa.try_into()?)),
+            ProofKind::GovernanceAction => {
+                Ok(ProverResult::GovernanceAction(parsed_vaa.try_into()?))
+            }
+            ProofKind::Message => Ok(ProverResult::Message(parsed_vaa.try_into()?)),
         }
     }
 }
@@ -6,10 +6,10 @@ use {
     near_sdk::env,
     omni_types::{
         prover_result::{
-            DeployTokenMessage, FinTransferMessage, InitTransferMessage, LogMetadataMessage,
-            ProofKind,
+            DeployTokenMessage, FinTransferMessage, GovernanceActionMessage, IncomingMessage,
+            InitTransferMessage, LogMetadataMessage, ProofKind, UnresolvedRecipientTransferMessage,
         },
-        stringify, Fee, Nonce, OmniAddress, TransferId,
+        stringify, ChainKind, Fee, Nonce, OmniAddress, TransferId,
     },
 };
 
@@ -131,6 +131,24 @@ struct LogMetadataWh {
     decimals: u8,
 }
 
+#[derive(Debug, BorshDeserialize)]
+struct GovernanceActionWh {
+    payload_type: ProofKind,
+    chain_kind: ChainKind,
+    nonce: Nonce,
+    action: Vec<u8>,
+}
+
+#[derive(Debug, BorshDeserialize)]
+struct MessageWh {
+    payload_type: ProofKind,
+    chain_kind: ChainKind,
+    origin_nonce: Nonce,
+    sender: OmniAddress,
+    recipient: String,
+    payload_hash: [u8; 32],
+}
+
 #[derive(Debug, BorshDeserialize)]
 struct FinTransferWh {
     payload_type: ProofKind,
@@ -182,6 +200,35 @@ impl TryInto<InitTransferMessage> for ParsedVAA {
     }
 }
 
+impl TryInto<UnresolvedRecipientTransferMessage> for ParsedVAA {
+    type Error = String;
+
+    fn try_into(self) -> Result<UnresolvedRecipientTransferMessage, String> {
+        let transfer: InitTransferWh = borsh::from_slice(&self.payload).map_err(stringify)?;
+
+        if transfer.payload_type != ProofKind::InitTransfer {
+            return Err("Invalid proof kind".to_owned());
+        }
+
+        Ok(UnresolvedRecipientTransferMessage {
+            token: transfer.token_address.clone(),
+            amount: transfer.amount.into(),
+            fee: Fee {
+                fee: transfer.fee.into(),
+                native_fee: transfer.native_fee.into(),
+            },
+            raw_recipient: transfer.recipient,
+            origin_nonce: transfer.origin_nonce,
+            sender: transfer.sender,
+            msg: transfer.message,
+            emitter_address: OmniAddress::new_from_slice(
+                transfer.token_address.get_chain(),
+                &self.emitter_address,
+            )?,
+        })
+    }
+}
+
 impl TryInto<FinTransferMessage> for ParsedVAA {
     type Error = String;
 
@@ -247,3 +294,48 @@ impl TryInto<LogMetadataMessage> for ParsedVAA {
         })
     }
 }
+
+impl TryInto<GovernanceActionMessage> for ParsedVAA {
+    type Error = String;
+
+    fn try_into(self) -> Result<GovernanceActionMessage, String> {
+        let parsed_payload: GovernanceActionWh =
+            borsh::from_slice(&self.payload).map_err(stringify)?;
+
+        if parsed_payload.payload_type != ProofKind::GovernanceAction {
+            return Err("Invalid proof kind".to_owned());
+        }
+
+        Ok(GovernanceActionMessage {
+            nonce: parsed_payload.nonce,
+            action: parsed_payload.action,
+            emitter_address: OmniAddress::new_from_slice(
+                parsed_payload.chain_kind,
+                &self.emitter_address,
+            )?,
+        })
+    }
+}
+
+impl TryInto<IncomingMessage> for ParsedVAA {
+    type Error = String;
+
+    fn try_into(self) -> Result<IncomingMessage, String> {
+        let parsed_payload: MessageWh = borsh::from_slice(&self.payload).map_err(stringify)?;
+
+        if parsed_payload.payload_type != ProofKind::Message {
+            return Err("Invalid proof kind".to_owned());
+        }
+
+        Ok(IncomingMessage {
+            origin_nonce: parsed_payload.origin_nonce,
+            sender: parsed_payload.sender,
+            recipient: parsed_payload.recipient.parse().map_err(stringify)?,
+            payload_hash: parsed_payload.payload_hash.into(),
+            emitter_address: OmniAddress::new_from_slice(
+                parsed_payload.chain_kind,
+                &self.emitter_address,
+            )?,
+        })
+    }
+}
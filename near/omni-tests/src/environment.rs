@@ -15,9 +15,9 @@ use omni_types::{
 };
 
 use crate::helpers::tests::{
-    account_n, eth_eoa_address, eth_factory_address, eth_token_address, get_bind_token_args,
-    get_test_deploy_token_args, wasm_code_hash, BuildArtifacts, GLOBAL_STORAGE_COST_PER_BYTE,
-    NEP141_DEPOSIT,
+    account_n, activate_bridge, eth_eoa_address, eth_factory_address, eth_token_address,
+    get_bind_token_args, get_test_deploy_token_args, wasm_code_hash, BuildArtifacts,
+    GLOBAL_STORAGE_COST_PER_BYTE, NEP141_DEPOSIT,
 };
 
 const PREV_LOCKER_WASM_FILEPATH: &str = "src/data/omni_bridge-0_4_1.wasm";
@@ -85,6 +85,8 @@ impl TestEnvBuilder {
 
         storage_deposit(&token_contract, bridge_contract.id()).await?;
 
+        self.activate_bridge(&bridge_contract).await?;
+
         Ok(TestEnvBuilderWithToken {
             worker: self.worker,
             bridge_contract,
@@ -126,6 +128,8 @@ impl TestEnvBuilder {
 
         storage_deposit(&token_contract, bridge_contract.id()).await?;
 
+        self.activate_bridge(&bridge_contract).await?;
+
         Ok(TestEnvBuilderWithToken {
             worker: self.worker,
             bridge_contract,
@@ -187,6 +191,8 @@ impl TestEnvBuilder {
 
         storage_deposit(&token_contract, bridge_contract.id()).await?;
 
+        self.activate_bridge(&bridge_contract).await?;
+
         Ok(TestEnvBuilderWithToken {
             worker: self.worker,
             bridge_contract,
@@ -253,6 +259,8 @@ impl TestEnvBuilder {
 
         storage_deposit(&token_contract, bridge_contract.id()).await?;
 
+        self.activate_bridge(&bridge_contract).await?;
+
         Ok(TestEnvBuilderWithToken {
             worker: self.worker,
             bridge_contract,
@@ -318,6 +326,8 @@ impl TestEnvBuilder {
             .await?
             .into_result()?;
 
+        self.activate_bridge(&bridge_contract).await?;
+
         Ok(TestEnvBuilderWithToken {
             worker: self.worker,
             bridge_contract,
@@ -332,6 +342,15 @@ impl TestEnvBuilder {
         })
     }
 
+    // The bridge of the previous version has no bootstrap checklist and is activated when
+    // migrated.
+    async fn activate_bridge(&self, bridge_contract: &Contract) -> anyhow::Result<()> {
+        if self.deploy_old_version {
+            return Ok(());
+        }
+        activate_bridge(bridge_contract).await
+    }
+
     async fn deploy_token_deployer(
         &self,
         bridge_contract: &Contract,
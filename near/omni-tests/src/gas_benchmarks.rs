@@ -0,0 +1,419 @@
+#[cfg(test)]
+mod tests {
+    use near_sdk::{
+        borsh,
+        json_types::U128,
+        serde_json::{self, json},
+    };
+    use near_workspaces::{
+        result::ExecutionFinalResult,
+        types::{Gas, NearToken},
+    };
+    use omni_types::{
+        btc::{OutPoint, TokenReceiverMessage, TxOut},
+        client::{ContractMethod, SignTransferArgs, SubmitTransferToUtxoChainConnectorArgs},
+        locker_args::{FinTransferArgs, StorageDepositAction},
+        near_events::OmniBridgeEvent,
+        prover_result::{InitTransferMessage, ProverResult},
+        BridgeOnTransferMsg, ChainKind, Fee, InitTransferMsg, OmniAddress, TransferId,
+        TransferMessage,
+    };
+    use rstest::rstest;
+
+    use crate::{
+        environment::{TestEnvBuilder, TestEnvBuilderWithToken},
+        helpers::tests::{
+            account_n, build_artifacts, eth_eoa_address, eth_factory_address, eth_token_address,
+            get_claim_fee_args_near, get_event_data, relayer_account_id, BuildArtifacts,
+            NEP141_DEPOSIT,
+        },
+    };
+
+    // Gas attached to a single transaction by the protocol
+    const MAX_TRANSACTION_GAS: Gas = Gas::from_tgas(300);
+
+    // Each budget is the gas measured on the sandbox plus a margin of about a third, so only
+    // significant regressions fail. Run the tests with `GAS_BENCHMARKS_REPORT` set to print the
+    // measured gas and the margin left when updating the budgets.
+    const INIT_TRANSFER_GAS_BUDGET: Gas = Gas::from_tgas(80);
+    const CLAIM_FEE_GAS_BUDGET: Gas = Gas::from_tgas(120);
+    const FIN_TRANSFER_GAS_BUDGET: Gas = Gas::from_tgas(150);
+    const UTXO_SUBMIT_GAS_BUDGET: Gas = Gas::from_tgas(150);
+    const UTXO_SUBMIT_50_INPUTS_GAS_BUDGET: Gas = Gas::from_tgas(200);
+    // Static gas of the callback of the UTXO submissions in the bridge
+    const SUBMIT_CALLBACK_GAS: Gas = Gas::from_tgas(15);
+    const SUBMIT_CALLBACK_GAS_PER_INPUT: Gas = Gas::from_ggas(300);
+
+    const GAS_REPORT_ENV: &str = "GAS_BENCHMARKS_REPORT";
+
+    const UTXO_RECIPIENT: &str = "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy";
+    const UTXO_INPUT_TXID: [u8; 32] = [0xab; 32];
+    const TRANSFER_AMOUNT: u128 = 100_000_000;
+    const TRANSFER_FEE: u128 = 1_000;
+
+    fn assert_gas_within_budget(name: &str, result: &ExecutionFinalResult, budget: Gas) {
+        assert!(
+            budget <= MAX_TRANSACTION_GAS,
+            "The budget of {name} exceeds the transaction gas limit"
+        );
+        assert!(
+            result.is_success(),
+            "{name} failed: {:?}",
+            result.failures()
+        );
+        let gas_burnt = result.total_gas_burnt;
+        if std::env::var_os(GAS_REPORT_ENV).is_some() {
+            println!(
+                "{name}: {} TGas burnt, {} TGas budget, {}% margin",
+                gas_burnt.as_tgas(),
+                budget.as_tgas(),
+                budget
+                    .as_gas()
+                    .saturating_sub(gas_burnt.as_gas())
+                    .saturating_mul(100)
+                    / gas_burnt.as_gas().max(1)
+            );
+        }
+        assert!(
+            gas_burnt <= budget,
+            "{name} burnt {} TGas, the budget is {} TGas",
+            gas_burnt.as_tgas(),
+            budget.as_tgas()
+        );
+    }
+
+    fn get_transfer_message(result: &ExecutionFinalResult) -> anyhow::Result<TransferMessage> {
+        let logs = result
+            .receipt_outcomes()
+            .iter()
+            .flat_map(|outcome| &outcome.logs)
+            .collect::<Vec<_>>();
+        let event = get_event_data("InitTransferEvent", &logs)?
+            .ok_or_else(|| anyhow::anyhow!("InitTransferEvent not found"))?;
+
+        let OmniBridgeEvent::InitTransferEvent { transfer_message } =
+            serde_json::from_value(event)?
+        else {
+            anyhow::bail!("Unexpected event");
+        };
+        Ok(transfer_message)
+    }
+
+    async fn init_transfer(
+        env: &TestEnvBuilderWithToken,
+        sender: &near_workspaces::Account,
+        recipient: OmniAddress,
+        fee: u128,
+    ) -> anyhow::Result<ExecutionFinalResult> {
+        let required_balance_account: NearToken = env
+            .bridge_contract
+            .view("required_balance_for_account")
+            .await?
+            .json()?;
+        let required_balance_init_transfer: NearToken = env
+            .bridge_contract
+            .view("required_balance_for_init_transfer")
+            .args_json(json!({
+                "recipient": recipient,
+                "sender": OmniAddress::Near(sender.id().clone()),
+            }))
+            .await?
+            .json()?;
+        env.omni_storage_deposit(
+            sender.id(),
+            required_balance_account
+                .saturating_add(required_balance_init_transfer)
+                .as_yoctonear(),
+        )
+        .await?;
+
+        let init_transfer_msg = InitTransferMsg {
+            recipient,
+            fee: U128(fee),
+            native_token_fee: U128(0),
+            msg: None,
+            beneficiary: None,
+            destination_call: None,
+            preferred_relayer: None,
+            expires_at: None,
+        };
+        let result = sender
+            .call(env.token.contract.id(), "ft_transfer_call")
+            .args_json(json!({
+                "receiver_id": env.bridge_contract.id(),
+                "amount": U128(TRANSFER_AMOUNT),
+                "memo": None::<String>,
+                "msg": serde_json::to_string(&BridgeOnTransferMsg::InitTransfer(init_transfer_msg))?,
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?;
+
+        Ok(result)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_init_transfer_and_fee_settlement_gas(
+        build_artifacts: &BuildArtifacts,
+    ) -> anyhow::Result<()> {
+        let env = TestEnvBuilder::new(build_artifacts.clone())
+            .await?
+            .with_native_nep141_token(24)
+            .await?;
+        let relayer_account = env.create_account(relayer_account_id()).await?;
+        let sender_account = env.create_account(account_n(1)).await?;
+        env.storage_deposit(relayer_account.id()).await?;
+        env.storage_deposit(sender_account.id()).await?;
+        env.mint_tokens(sender_account.id(), TRANSFER_AMOUNT)
+            .await?;
+
+        let result = init_transfer(&env, &sender_account, eth_eoa_address(), TRANSFER_FEE).await?;
+        assert_gas_within_budget("init_transfer", &result, INIT_TRANSFER_GAS_BUDGET);
+        let transfer_message = get_transfer_message(&result)?;
+
+        let sign_transfer_args = SignTransferArgs {
+            transfer_id: TransferId {
+                origin_chain: ChainKind::Near,
+                origin_nonce: transfer_message.origin_nonce,
+            },
+            fee_recipient: Some(relayer_account.id().clone()),
+            fee: Some(transfer_message.fee.clone()),
+        };
+        relayer_account
+            .call(env.bridge_contract.id(), SignTransferArgs::NAME)
+            .args(sign_transfer_args.to_args()?)
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        let result = relayer_account
+            .call(env.bridge_contract.id(), "claim_fee")
+            .args_borsh(get_claim_fee_args_near(
+                ChainKind::Near,
+                ChainKind::Eth,
+                transfer_message.origin_nonce,
+                relayer_account.id(),
+                TRANSFER_AMOUNT - TRANSFER_FEE,
+                eth_factory_address(),
+            ))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?;
+        assert_gas_within_budget("claim_fee", &result, CLAIM_FEE_GAS_BUDGET);
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_fin_transfer_gas(build_artifacts: &BuildArtifacts) -> anyhow::Result<()> {
+        let env = TestEnvBuilder::new(build_artifacts.clone())
+            .await?
+            .with_native_nep141_token(24)
+            .await?;
+        let relayer_account = env.create_account(relayer_account_id()).await?;
+        let recipient = account_n(1);
+
+        env.token
+            .contract
+            .call("ft_transfer")
+            .args_json(json!({
+                "receiver_id": env.bridge_contract.id(),
+                "amount": U128(TRANSFER_AMOUNT),
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        let required_balance_for_fin_transfer: NearToken = env
+            .bridge_contract
+            .view("required_balance_for_fin_transfer")
+            .await?
+            .json()?;
+        let storage_deposit_actions = [recipient.clone(), relayer_account.id().clone()]
+            .into_iter()
+            .map(|account_id| StorageDepositAction {
+                token_id: env.token.contract.id().clone(),
+                account_id,
+                storage_deposit_amount: Some(NEP141_DEPOSIT.as_yoctonear()),
+            })
+            .collect::<Vec<_>>();
+
+        let result = relayer_account
+            .call(env.bridge_contract.id(), "fin_transfer")
+            .args_borsh(FinTransferArgs {
+                chain_kind: ChainKind::Eth,
+                storage_deposit_actions,
+                prover_args: borsh::to_vec(&ProverResult::InitTransfer(InitTransferMessage {
+                    origin_nonce: 1,
+                    token: eth_token_address(),
+                    recipient: OmniAddress::Near(recipient),
+                    amount: U128(TRANSFER_AMOUNT),
+                    fee: Fee {
+                        fee: U128(TRANSFER_FEE),
+                        native_fee: U128(0),
+                    },
+                    sender: eth_eoa_address(),
+                    msg: String::new(),
+                    emitter_address: eth_factory_address(),
+                }))?,
+            })
+            .deposit(
+                NEP141_DEPOSIT
+                    .saturating_mul(2)
+                    .saturating_add(required_balance_for_fin_transfer),
+            )
+            .max_gas()
+            .transact()
+            .await?;
+        assert_gas_within_budget("fin_transfer", &result, FIN_TRANSFER_GAS_BUDGET);
+
+        Ok(())
+    }
+
+    async fn submit_utxo_transfer(
+        env: &TestEnvBuilderWithToken,
+        inputs: u32,
+    ) -> anyhow::Result<ExecutionFinalResult> {
+        let relayer_account = env.create_account(relayer_account_id()).await?;
+        let sender_account = env.create_account(account_n(1)).await?;
+        env.storage_deposit(relayer_account.id()).await?;
+        env.storage_deposit(sender_account.id()).await?;
+        env.mint_tokens(sender_account.id(), TRANSFER_AMOUNT)
+            .await?;
+
+        let result = init_transfer(
+            env,
+            &sender_account,
+            OmniAddress::Btc(UTXO_RECIPIENT.to_string()),
+            TRANSFER_FEE,
+        )
+        .await?;
+        let transfer_message = get_transfer_message(&result)?;
+
+        let msg = TokenReceiverMessage::Withdraw {
+            target_btc_address: UTXO_RECIPIENT.to_string(),
+            input: (0..inputs)
+                .map(|vout| OutPoint {
+                    txid: UTXO_INPUT_TXID,
+                    vout,
+                })
+                .collect(),
+            output: vec![
+                TxOut {
+                    value: 99_000_000,
+                    script_pubkey: "a914b472a266d0bd89c13706a4132ccfb16f7c3b9fcb87".to_string(),
+                },
+                TxOut {
+                    value: 990_000,
+                    script_pubkey: "0014841b80d2cc75f5345c482af96294d04fdd66b2b7".to_string(),
+                },
+            ],
+            max_gas_fee: None,
+        };
+
+        let submit_args = SubmitTransferToUtxoChainConnectorArgs {
+            transfer_id: transfer_message.get_transfer_id(),
+            msg: serde_json::to_string(&msg)?,
+            fee_recipient: Some(relayer_account.id().clone()),
+            fee: Some(transfer_message.fee.clone()),
+        };
+        let result = relayer_account
+            .call(
+                env.bridge_contract.id(),
+                SubmitTransferToUtxoChainConnectorArgs::NAME,
+            )
+            .args(submit_args.to_args()?)
+            // Covers the storage of the reservations of the inputs, the rest is refunded
+            .deposit(NearToken::from_millinear(100))
+            .max_gas()
+            .transact()
+            .await?;
+
+        Ok(result)
+    }
+
+    #[rstest]
+    #[case(1, UTXO_SUBMIT_GAS_BUDGET)]
+    #[case(50, UTXO_SUBMIT_50_INPUTS_GAS_BUDGET)]
+    #[tokio::test]
+    async fn test_submit_transfer_to_utxo_chain_connector_gas(
+        build_artifacts: &BuildArtifacts,
+        #[case] inputs: u32,
+        #[case] budget: Gas,
+    ) -> anyhow::Result<()> {
+        let env = TestEnvBuilder::new(build_artifacts.clone())
+            .await?
+            .with_utxo_token()
+            .await?;
+
+        let result = submit_utxo_transfer(&env, inputs).await?;
+        assert_gas_within_budget(
+            &format!("submit_transfer_to_utxo_chain_connector with {inputs} inputs"),
+            &result,
+            budget,
+        );
+
+        Ok(())
+    }
+
+    // A rejection by the connector restores the transfer and releases its inputs in the callback,
+    // which has to fit in its static gas for any number of inputs
+    #[rstest]
+    #[case(1, UTXO_SUBMIT_GAS_BUDGET)]
+    #[case(50, UTXO_SUBMIT_50_INPUTS_GAS_BUDGET)]
+    #[tokio::test]
+    async fn test_submit_transfer_to_utxo_chain_connector_failure_gas(
+        build_artifacts: &BuildArtifacts,
+        #[case] inputs: u32,
+        #[case] budget: Gas,
+    ) -> anyhow::Result<()> {
+        let env = TestEnvBuilder::new(build_artifacts.clone())
+            .await?
+            .with_utxo_token()
+            .await?;
+        env.utxo_connector
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("UTXO connector not deployed"))?
+            .call("set_reject_withdrawals")
+            .args_json(json!({ "reject_withdrawals": true }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        let result = submit_utxo_transfer(&env, inputs).await?;
+        assert_gas_within_budget(
+            &format!("failed submit_transfer_to_utxo_chain_connector with {inputs} inputs"),
+            &result,
+            budget,
+        );
+        let callback = result
+            .receipt_outcomes()
+            .iter()
+            .find(|outcome| {
+                outcome
+                    .logs
+                    .iter()
+                    .any(|log| log.contains("TransferRestoredEvent"))
+            })
+            .ok_or_else(|| anyhow::anyhow!("The transfer was not restored"))?;
+        let callback_gas = SUBMIT_CALLBACK_GAS.saturating_add(Gas::from_gas(
+            SUBMIT_CALLBACK_GAS_PER_INPUT.as_gas() * u64::from(inputs),
+        ));
+        assert!(
+            callback.gas_burnt <= callback_gas,
+            "The submit callback burnt {} TGas, its static gas is {} TGas",
+            callback.gas_burnt.as_tgas(),
+            callback_gas.as_tgas()
+        );
+
+        Ok(())
+    }
+}
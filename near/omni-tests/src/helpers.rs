@@ -310,4 +310,36 @@ pub mod tests {
             .try_into()
             .expect("sha256 output should be 32 bytes")
     }
+
+    // Completes the bootstrap checklist of a freshly deployed bridge, so the transfer methods
+    // can be called. The bridge account is the super admin of the bridge and grants itself
+    // the pause manager role.
+    pub async fn activate_bridge(
+        bridge_contract: &near_workspaces::Contract,
+    ) -> anyhow::Result<()> {
+        let stage: String = bridge_contract.view("get_bootstrap_stage").await?.json()?;
+        if stage == "Activated" {
+            return Ok(());
+        }
+
+        bridge_contract
+            .call("acl_grant_role")
+            .args_json(serde_json::json!({
+                "role": "PauseManager",
+                "account_id": bridge_contract.id(),
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        bridge_contract
+            .call("activate")
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        Ok(())
+    }
 }
@@ -342,6 +342,10 @@ mod tests {
             fee: U128(0),
             recipient: eth_eoa_address(),
             msg: None,
+            beneficiary: None,
+            destination_call: None,
+            preferred_relayer: None,
+            expires_at: None,
         };
 
         let env = TestEnv::new(sender_balance_token, false, build_artifacts).await?;
@@ -389,6 +393,10 @@ mod tests {
             fee: U128(1000),
             recipient: eth_eoa_address(),
             msg: None,
+            beneficiary: None,
+            destination_call: None,
+            preferred_relayer: None,
+            expires_at: None,
         };
 
         let env = TestEnv::new(sender_balance_token, false, build_artifacts).await?;
@@ -434,6 +442,10 @@ mod tests {
             fee: U128(1000),
             recipient: eth_eoa_address(),
             msg: None,
+            beneficiary: None,
+            destination_call: None,
+            preferred_relayer: None,
+            expires_at: None,
         };
 
         let env = TestEnv::new(sender_balance_token, false, build_artifacts).await?;
@@ -488,6 +500,10 @@ mod tests {
             fee: U128(1000),
             recipient: eth_eoa_address(),
             msg: None,
+            beneficiary: None,
+            destination_call: None,
+            preferred_relayer: None,
+            expires_at: None,
         };
         let update_fee_value = Fee {
             native_fee: U128(NearToken::from_near(2).as_yoctonear()),
@@ -547,6 +563,10 @@ mod tests {
             fee: U128(0),
             recipient: eth_eoa_address(),
             msg: None,
+            beneficiary: None,
+            destination_call: None,
+            preferred_relayer: None,
+            expires_at: None,
         };
 
         let env = TestEnv::new(sender_balance_token, false, build_artifacts).await?;
@@ -596,6 +616,10 @@ mod tests {
             fee: U128(1000),
             recipient: eth_eoa_address(),
             msg: None,
+            beneficiary: None,
+            destination_call: None,
+            preferred_relayer: None,
+            expires_at: None,
         };
         let update_fee_value = Fee {
             native_fee: U128(NearToken::from_near(0).as_yoctonear()),
@@ -630,6 +654,10 @@ mod tests {
             fee: U128(1000),
             recipient: eth_eoa_address(),
             msg: None,
+            beneficiary: None,
+            destination_call: None,
+            preferred_relayer: None,
+            expires_at: None,
         };
         let update_fee_value = Fee {
             native_fee: U128(NearToken::from_near(1).as_yoctonear()),
@@ -664,6 +692,10 @@ mod tests {
             fee: U128(1000),
             recipient: eth_eoa_address(),
             msg: None,
+            beneficiary: None,
+            destination_call: None,
+            preferred_relayer: None,
+            expires_at: None,
         };
         let update_fee_value = Fee {
             native_fee: U128(NearToken::from_near(1).as_yoctonear()),
@@ -699,6 +731,10 @@ mod tests {
             fee: U128(1000),
             recipient: eth_eoa_address(),
             msg: None,
+            beneficiary: None,
+            destination_call: None,
+            preferred_relayer: None,
+            expires_at: None,
         };
         let update_fee = UpdateFee::Proof(vec![]);
 
@@ -728,6 +764,10 @@ mod tests {
             fee: U128(0),
             recipient: eth_eoa_address(),
             msg: None,
+            beneficiary: None,
+            destination_call: None,
+            preferred_relayer: None,
+            expires_at: None,
         };
 
         let env = TestEnv::new(sender_balance_token, true, build_artifacts).await?;
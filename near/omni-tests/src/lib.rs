@@ -2,6 +2,7 @@
 mod environment;
 mod fast_transfer;
 mod fin_transfer;
+mod gas_benchmarks;
 mod helpers;
 mod init_transfer;
 mod native_fee_role;
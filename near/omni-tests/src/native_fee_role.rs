@@ -9,8 +9,9 @@ mod tests {
     use rstest::rstest;
 
     use crate::helpers::tests::{
-        account_n, eth_eoa_address, eth_factory_address, get_event_data, locker_wasm,
-        mock_prover_wasm, mock_token_wasm, NEP141_DEPOSIT,
+        account_n, activate_bridge, eth_eoa_address, eth_factory_address, eth_token_address,
+        get_bind_token_args, get_event_data, locker_wasm, mock_prover_wasm, mock_token_wasm,
+        NEP141_DEPOSIT,
     };
 
     struct TestEnv {
@@ -144,6 +145,28 @@ mod tests {
                 .await?
                 .into_result()?;
 
+            // Bind the token, so the bootstrap checklist of the locker can be completed
+            let required_deposit_for_bind_token: NearToken = locker_contract
+                .view("required_balance_for_bind_token")
+                .await?
+                .json()?;
+            locker_contract
+                .call("bind_token")
+                .args_borsh(get_bind_token_args(
+                    token_contract.id(),
+                    &eth_token_address(),
+                    &eth_factory_address,
+                    24,
+                    24,
+                ))
+                .deposit(required_deposit_for_bind_token)
+                .max_gas()
+                .transact()
+                .await?
+                .into_result()?;
+
+            activate_bridge(&locker_contract).await?;
+
             Ok(Self {
                 worker,
                 token_contract,
@@ -207,6 +230,10 @@ mod tests {
                 fee: U128(token_fee),
                 recipient: eth_eoa_address(),
                 msg: None,
+                beneficiary: None,
+                destination_call: None,
+                preferred_relayer: None,
+                expires_at: None,
             };
 
             let required_balance_init_transfer: NearToken = self
@@ -14,7 +14,7 @@ mod tests {
     use rstest::rstest;
 
     use crate::helpers::tests::{
-        account_n, arb_factory_address, arb_token_address, base_factory_address,
+        account_n, activate_bridge, arb_factory_address, arb_token_address, base_factory_address,
         base_token_address, bnb_factory_address, bnb_token_address, eth_eoa_address,
         eth_factory_address, eth_token_address, get_test_deploy_token_args, locker_wasm,
         mock_global_contract_deployer_wasm, mock_prover_wasm, omni_token_wasm, pol_factory_address,
@@ -228,7 +228,14 @@ mod tests {
                 ChainKind::Base => base_factory_address(),
                 ChainKind::Bnb => bnb_factory_address(),
                 ChainKind::Pol => pol_factory_address(),
-                ChainKind::Near | ChainKind::Btc | ChainKind::Zcash => panic!("Unsupported chain"),
+                ChainKind::Near
+                | ChainKind::Btc
+                | ChainKind::Zcash
+                | ChainKind::Ltc
+                | ChainKind::Doge
+                | ChainKind::Unknown(_) => {
+                    panic!("Unsupported chain")
+                }
             };
 
             locker_contract
@@ -358,6 +365,7 @@ mod tests {
                 }))
                 .await?
                 .json()?;
+            activate_bridge(locker).await?;
 
             Ok(token_account_id)
         }
@@ -270,6 +270,7 @@ mod tests {
                 recipient: OmniAddress::Near(account_n(1)),
                 relayer_fee: U128(1000),
                 msg: String::default(),
+                sender_addresses: vec![],
             },
             is_fast_transfer: false,
             error: None,
@@ -284,6 +285,7 @@ mod tests {
                 recipient: base_eoa_address(),
                 relayer_fee: U128(1000),
                 msg: String::default(),
+                sender_addresses: vec![],
             },
             is_fast_transfer: false,
             error: None,
@@ -298,6 +300,7 @@ mod tests {
                 recipient: OmniAddress::Near(account_n(1)),
                 relayer_fee: U128(2000),
                 msg: "Some_message".to_string(),
+                sender_addresses: vec![],
             },
             is_fast_transfer: false,
             error: Some("CodeDoesNotExist"),
@@ -312,6 +315,7 @@ mod tests {
                 recipient: OmniAddress::Near(account_n(1)),
                 relayer_fee: U128(1000),
                 msg: String::default(),
+                sender_addresses: vec![],
             },
             is_fast_transfer: true,
             error: None,
@@ -326,6 +330,7 @@ mod tests {
                 recipient: base_eoa_address(),
                 relayer_fee: U128(1000),
                 msg: String::default(),
+                sender_addresses: vec![],
             },
             is_fast_transfer: true,
             error: None,
@@ -340,6 +345,7 @@ mod tests {
                 recipient: OmniAddress::Near(account_n(3)),
                 relayer_fee: U128(1000),
                 msg: String::default(),
+                sender_addresses: vec![],
             },
             is_fast_transfer: false,
             error: Some("recipient is omitted"),
@@ -380,6 +386,7 @@ mod tests {
             recipient: OmniAddress::Near(account_n(1)),
             relayer_fee: U128(1000),
             msg: String::default(),
+            sender_addresses: vec![],
         };
 
         // Try to send from relayer (not the connector)
@@ -412,6 +419,7 @@ mod tests {
             recipient: base_eoa_address(),
             relayer_fee: U128(1000),
             msg: String::default(),
+            sender_addresses: vec![],
         };
 
         let _ = do_fast_transfer(&env, amount, utxo_msg.clone()).await?;
@@ -1,10 +1,77 @@
-use near_sdk::json_types::U128;
+use core::fmt;
+use core::str::FromStr;
+use near_sdk::json_types::{U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{near, AccountId};
+use schemars::JsonSchema;
 
-type OutPoint = String;
+use crate::errors::BridgeError;
+use crate::utils::sha256;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Reference to a transaction output. It is stored as the 32-byte transaction id followed by
+/// the output index, 36 bytes in borsh, and is sent to the connectors as the `txid:vout` string.
+#[near(serializers = [borsh])]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct OutPoint {
+    pub txid: [u8; 32],
+    pub vout: u32,
+}
+
+impl FromStr for OutPoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (txid, vout) = s.split_once(':').ok_or("ERR_INVALID_OUTPOINT")?;
+        let txid = hex::decode(txid)
+            .map_err(|_| "ERR_INVALID_OUTPOINT_TXID")?
+            .try_into()
+            .map_err(|_| "ERR_INVALID_OUTPOINT_TXID")?;
+        let vout = vout.parse().map_err(|_| "ERR_INVALID_OUTPOINT_VOUT")?;
+        Ok(Self { txid, vout })
+    }
+}
+
+impl fmt::Display for OutPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", hex::encode(self.txid), self.vout)
+    }
+}
+
+impl<'de> Deserialize<'de> for OutPoint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: near_sdk::serde::Deserializer<'de>,
+    {
+        let outpoint = String::deserialize(deserializer)?;
+        outpoint.parse().map_err(near_sdk::serde::de::Error::custom)
+    }
+}
+
+impl Serialize for OutPoint {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: near_sdk::serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl JsonSchema for OutPoint {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        String::schema_name()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        String::json_schema(gen)
+    }
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug)]
 pub enum TokenReceiverMessage {
     DepositProtocolFee,
     Withdraw {
@@ -13,6 +80,75 @@ pub enum TokenReceiverMessage {
         output: Vec<TxOut>,
         max_gas_fee: Option<U128>,
     },
+    /// Withdrawal of several transfers in one transaction. The first outputs pay the
+    /// `target_btc_addresses` in order, the other outputs return the change to the bridge.
+    BatchWithdraw {
+        target_btc_addresses: Vec<String>,
+        input: Vec<OutPoint>,
+        output: Vec<TxOut>,
+        max_gas_fee: U128,
+    },
+}
+
+/// Message of the `ft_transfer_call` to the connector of a UTXO chain that spends custody
+/// UTXOs into a single output of the bridge. The transferred amount pays the network fee.
+#[near(serializers=[borsh, json])]
+#[derive(Debug)]
+pub enum UtxoConsolidationMessage {
+    Consolidate { input: Vec<OutPoint>, output: TxOut },
+}
+
+/// Message of the `ft_transfer_call` to the Zcash connector. It has the shape of the
+/// `TokenReceiverMessage`, with outputs restricted to transparent scripts.
+#[near(serializers=[borsh, json])]
+#[derive(Debug)]
+pub enum ZecTokenReceiverMessage {
+    DepositProtocolFee,
+    Withdraw {
+        target_btc_address: String,
+        input: Vec<OutPoint>,
+        output: Vec<ZecTxOut>,
+        max_gas_fee: Option<U128>,
+    },
+}
+
+/// Message of the `ft_transfer_call` to the Litecoin connector. The fee of a withdrawal is
+/// bounded by its rate in litoshi per virtual byte instead of its total amount.
+#[near(serializers=[borsh, json])]
+#[derive(Debug)]
+pub enum LtcTokenReceiverMessage {
+    DepositProtocolFee,
+    Withdraw {
+        target_ltc_address: String,
+        input: Vec<OutPoint>,
+        output: Vec<TxOut>,
+        max_fee_rate: Option<U64>,
+    },
+}
+
+/// Message of the `ft_transfer_call` to the Dogecoin connector. Dogecoin charges a fee per
+/// started kilobyte of the transaction, so the fee of a withdrawal is bounded by that amount.
+#[near(serializers=[borsh, json])]
+#[derive(Debug)]
+pub enum DogeTokenReceiverMessage {
+    DepositProtocolFee,
+    Withdraw {
+        target_doge_address: String,
+        input: Vec<OutPoint>,
+        output: Vec<TxOut>,
+        max_fee_per_kb: Option<U64>,
+    },
+}
+
+/// Extra information attached to the `msg` of a transfer to a UTXO chain.
+#[near(serializers=[json])]
+#[derive(Debug, PartialEq)]
+pub enum UTXOChainMsg {
+    MaxGasFee(U64),
+    /// Maximum fee rate of the withdrawal in litoshi per virtual byte, for Litecoin.
+    MaxFeeRate(U64),
+    /// Maximum fee of the withdrawal in koinu per kilobyte, for Dogecoin.
+    MaxFeePerKb(U64),
 }
 
 #[near(serializers=[borsh, json])]
@@ -22,9 +158,421 @@ pub struct UTXOChainConfig {
     pub token_id: AccountId,
 }
 
-#[near(serializers=[json])]
-#[derive(Debug)]
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone)]
 pub struct TxOut {
     pub value: u64,
     pub script_pubkey: String,
 }
+
+/// Largest output script relayed by the nodes, an `OP_RETURN` output with 80 bytes of data.
+pub const MAX_STANDARD_SCRIPT_PUBKEY_SIZE: usize = 83;
+
+impl TxOut {
+    pub fn new(value: u64, script_pubkey: String) -> Result<Self, BridgeError> {
+        validate_output(value, &script_pubkey)?;
+        Ok(Self {
+            value,
+            script_pubkey,
+        })
+    }
+
+    pub fn validate(&self) -> Result<(), BridgeError> {
+        validate_output(self.value, &self.script_pubkey)
+    }
+}
+
+/// Transaction in the serialization of Bitcoin, shared by Litecoin and Dogecoin. The id is the
+/// double SHA-256 of the transaction without its witnesses, so it doesn't depend on the
+/// signatures of the SegWit inputs.
+#[derive(Debug, Clone)]
+pub struct UtxoTransaction {
+    pub inputs: Vec<OutPoint>,
+    pub outputs: Vec<TxOut>,
+    /// In the byte order shown by the explorers, like the ids of the outpoints.
+    pub txid: [u8; 32],
+}
+
+impl UtxoTransaction {
+    pub fn from_bytes(tx: &[u8]) -> Result<Self, BridgeError> {
+        let mut reader = TxReader { tx, pos: 0 };
+        reader.read(4)?;
+        let is_segwit = tx.get(4..6) == Some(&[0x00, 0x01][..]);
+        let body_start = if is_segwit { 6 } else { 4 };
+        reader.pos = body_start;
+
+        let inputs_len = reader.read_len(41)?;
+        let mut inputs = Vec::with_capacity(inputs_len);
+        for _ in 0..inputs_len {
+            let mut txid: [u8; 32] = reader.read(32)?.try_into().unwrap_or_default();
+            txid.reverse();
+            let vout = u32::from_le_bytes(reader.read(4)?.try_into().unwrap_or_default());
+            let script_len = reader.read_len(1)?;
+            reader.read(script_len.saturating_add(4))?;
+            inputs.push(OutPoint { txid, vout });
+        }
+        let outputs_len = reader.read_len(9)?;
+        let mut outputs = Vec::with_capacity(outputs_len);
+        for _ in 0..outputs_len {
+            let value = u64::from_le_bytes(reader.read(8)?.try_into().unwrap_or_default());
+            let script_len = reader.read_len(1)?;
+            outputs.push(TxOut {
+                value,
+                script_pubkey: hex::encode(reader.read(script_len)?),
+            });
+        }
+        let body_end = reader.pos;
+        if is_segwit {
+            for _ in 0..inputs_len {
+                for _ in 0..reader.read_len(1)? {
+                    let item_len = reader.read_len(1)?;
+                    reader.read(item_len)?;
+                }
+            }
+        }
+        let lock_time = reader.read(4)?;
+        if inputs.is_empty() || reader.pos != tx.len() {
+            return Err(BridgeError::InvalidTransaction);
+        }
+
+        let stripped_tx = [&tx[..4], &tx[body_start..body_end], lock_time].concat();
+        let mut txid = sha256(&sha256(&stripped_tx));
+        txid.reverse();
+        Ok(Self {
+            inputs,
+            outputs,
+            txid,
+        })
+    }
+}
+
+struct TxReader<'a> {
+    tx: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> TxReader<'a> {
+    fn read(&mut self, len: usize) -> Result<&'a [u8], BridgeError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .ok_or(BridgeError::InvalidTransaction)?;
+        let bytes = self
+            .tx
+            .get(self.pos..end)
+            .ok_or(BridgeError::InvalidTransaction)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    // Reads a compact size bounded by the rest of the transaction, items of `min_item_len`
+    // bytes each, so a malformed length can't allocate more than the transaction.
+    fn read_len(&mut self, min_item_len: usize) -> Result<usize, BridgeError> {
+        let len = match self.read(1)?[0] {
+            0xfd => u64::from(u16::from_le_bytes(
+                self.read(2)?.try_into().unwrap_or_default(),
+            )),
+            0xfe => u64::from(u32::from_le_bytes(
+                self.read(4)?.try_into().unwrap_or_default(),
+            )),
+            0xff => u64::from_le_bytes(self.read(8)?.try_into().unwrap_or_default()),
+            len => u64::from(len),
+        };
+        usize::try_from(len)
+            .ok()
+            .filter(|len| {
+                len.saturating_mul(min_item_len) <= self.tx.len().saturating_sub(self.pos)
+            })
+            .ok_or(BridgeError::InvalidTransaction)
+    }
+}
+
+/// Output of a Zcash withdrawal. Funds sent to a shielded pool can't be tracked by the
+/// bridge, so only the P2PKH and P2SH scripts of the transparent addresses are supported.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone)]
+pub struct ZecTxOut {
+    pub value: u64,
+    pub script_pubkey: String,
+}
+
+impl ZecTxOut {
+    pub fn validate(&self) -> Result<(), BridgeError> {
+        validate_output(self.value, &self.script_pubkey)
+    }
+
+    pub fn is_transparent(&self) -> bool {
+        matches!(
+            ScriptKind::from_hex(&self.script_pubkey),
+            ScriptKind::P2pkh | ScriptKind::P2sh
+        )
+    }
+}
+
+/// Standard type of an output script. The scripts of the other types, including the witness
+/// programs of the versions without a defined spending rule, are `NonStandard`.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptKind {
+    P2pkh,
+    P2sh,
+    P2wpkh,
+    P2wsh,
+    P2tr,
+    /// `OP_RETURN` output, unspendable.
+    NullData,
+    NonStandard,
+}
+
+impl ScriptKind {
+    pub fn from_script(script: &[u8]) -> Self {
+        match script {
+            // OP_DUP OP_HASH160 <20 bytes> OP_EQUALVERIFY OP_CHECKSIG
+            [0x76, 0xa9, 0x14, hash @ .., 0x88, 0xac] if hash.len() == 20 => Self::P2pkh,
+            // OP_HASH160 <20 bytes> OP_EQUAL
+            [0xa9, 0x14, hash @ .., 0x87] if hash.len() == 20 => Self::P2sh,
+            // OP_0 <20 bytes>
+            [0x00, 0x14, program @ ..] if program.len() == 20 => Self::P2wpkh,
+            // OP_0 <32 bytes>
+            [0x00, 0x20, program @ ..] if program.len() == 32 => Self::P2wsh,
+            // OP_1 <32 bytes>
+            [0x51, 0x20, program @ ..] if program.len() == 32 => Self::P2tr,
+            [0x6a, ..] => Self::NullData,
+            _ => Self::NonStandard,
+        }
+    }
+
+    /// The scripts paying to the hash of a custody script, e.g. a multisig.
+    pub const fn is_script_hash(self) -> bool {
+        matches!(self, Self::P2sh | Self::P2wsh)
+    }
+
+    /// Classifies the hex-encoded script of the outputs of the `Withdraw` messages.
+    pub fn from_hex(script_pubkey: &str) -> Self {
+        hex::decode(script_pubkey).map_or(Self::NonStandard, |script| Self::from_script(&script))
+    }
+}
+
+// Rejects the outputs that the connector can't broadcast, with the reason in the error.
+fn validate_output(value: u64, script_pubkey: &str) -> Result<(), BridgeError> {
+    if value == 0 {
+        return Err(BridgeError::ZeroOutputValue);
+    }
+    let script = hex::decode(script_pubkey).map_err(|_| BridgeError::InvalidScriptPubkey)?;
+    if script.is_empty() {
+        return Err(BridgeError::InvalidScriptPubkey);
+    }
+    if script.len() > MAX_STANDARD_SCRIPT_PUBKEY_SIZE {
+        return Err(BridgeError::ScriptPubkeyTooLarge);
+    }
+    Ok(())
+}
+
+/// Returns whether the Zcash address is a transparent address, as opposed to a Sapling, Orchard
+/// or unified address that can hold shielded funds.
+pub fn is_zcash_transparent_address(address: &str) -> bool {
+    ["t1", "t3", "tm", "t2"]
+        .iter()
+        .any(|prefix| address.starts_with(prefix))
+}
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Encoding of the addresses of a UTXO chain. Supporting a new encoding only needs a new variant
+/// and its character validation, the prefixes and lengths are configured by the DAO.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressEncoding {
+    Base58,
+    Bech32,
+}
+
+/// Address format accepted as the recipient of a transfer to a UTXO chain.
+/// `matches` only checks the shape of the address, the checksum is verified when the script
+/// paying to the address is derived.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UtxoAddressFormat {
+    pub encoding: AddressEncoding,
+    /// Leading characters of the address, e.g. `3` for P2SH or `bc1q` for SegWit v0 addresses.
+    /// Compared case-insensitively for bech32 addresses.
+    pub prefix: String,
+    pub min_len: u8,
+    pub max_len: u8,
+    /// Type of the output script paying to the addresses of the format.
+    pub script_kind: ScriptKind,
+}
+
+impl UtxoAddressFormat {
+    pub const fn is_script_hash(&self) -> bool {
+        self.script_kind.is_script_hash()
+    }
+
+    pub fn matches(&self, address: &str) -> bool {
+        if address.len() < usize::from(self.min_len) || address.len() > usize::from(self.max_len) {
+            return false;
+        }
+
+        match self.encoding {
+            AddressEncoding::Base58 => {
+                address.starts_with(&self.prefix)
+                    && address.chars().all(|c| BASE58_ALPHABET.contains(c))
+            }
+            AddressEncoding::Bech32 => {
+                // Mixed case is not allowed by bech32
+                let lowercase = address.to_ascii_lowercase();
+                if address != lowercase && address != address.to_ascii_uppercase() {
+                    return false;
+                }
+                let Some((_, data)) = lowercase.rsplit_once('1') else {
+                    return false;
+                };
+                lowercase.starts_with(&self.prefix.to_ascii_lowercase())
+                    && !data.is_empty()
+                    && data.chars().all(|c| BECH32_CHARSET.contains(c))
+            }
+        }
+    }
+
+    /// Output script paying to an address of the format. The base58 addresses carry the hash
+    /// of the script of `script_kind`, the bech32 addresses carry the witness program.
+    pub fn script_pubkey(&self, address: &str) -> Result<Vec<u8>, BridgeError> {
+        match self.encoding {
+            AddressEncoding::Base58 => {
+                let payload = decode_base58check(address)?;
+                // The version is one byte, or two bytes for Zcash
+                let hash = payload
+                    .len()
+                    .checked_sub(20)
+                    .filter(|version_len| (1..=2).contains(version_len))
+                    .map(|version_len| &payload[version_len..])
+                    .ok_or(BridgeError::InvalidAddress)?;
+                match self.script_kind {
+                    ScriptKind::P2pkh => Ok([&[0x76, 0xa9, 0x14], hash, &[0x88, 0xac]].concat()),
+                    ScriptKind::P2sh => Ok([&[0xa9, 0x14], hash, &[0x87]].concat()),
+                    _ => Err(BridgeError::InvalidAddress),
+                }
+            }
+            AddressEncoding::Bech32 => {
+                let (version, program) = decode_segwit_address(address)?;
+                // OP_0 or OP_1 to OP_16, followed by the push of the program
+                let opcode = if version == 0 { 0x00 } else { 0x50 + version };
+                let program_len: u8 = program.len().try_into().unwrap_or_default();
+                Ok([&[opcode, program_len], program.as_slice()].concat())
+            }
+        }
+    }
+}
+
+fn decode_base58check(address: &str) -> Result<Vec<u8>, BridgeError> {
+    // Digits of the number in base 256, the least significant first
+    let mut bytes: Vec<u8> = Vec::with_capacity(address.len());
+    for c in address.chars() {
+        let mut carry = BASE58_ALPHABET.find(c).ok_or(BridgeError::InvalidAddress)?;
+        for byte in &mut bytes {
+            carry += usize::from(*byte) * 58;
+            *byte = carry.to_le_bytes()[0];
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry.to_le_bytes()[0]);
+            carry >>= 8;
+        }
+    }
+    // Each leading `1` is a zero byte
+    bytes.extend(address.chars().take_while(|c| *c == '1').map(|_| 0));
+    bytes.reverse();
+
+    let checksum_start = bytes
+        .len()
+        .checked_sub(4)
+        .ok_or(BridgeError::InvalidAddress)?;
+    let (payload, checksum) = bytes.split_at(checksum_start);
+    if sha256(&sha256(payload))[..4] != *checksum {
+        return Err(BridgeError::InvalidAddress);
+    }
+    Ok(payload.to_vec())
+}
+
+const BECH32_CHECKSUM: u32 = 1;
+const BECH32M_CHECKSUM: u32 = 0x2bc8_30a3;
+
+fn bech32_polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [
+        0x3b6a_57b2,
+        0x2650_8e6d,
+        0x1ea1_19fa,
+        0x3d42_33dd,
+        0x2a14_62b3,
+    ];
+    values.fold(1, |checksum, value| {
+        let top = checksum >> 25;
+        GENERATOR.iter().enumerate().fold(
+            ((checksum & 0x01ff_ffff) << 5) ^ u32::from(value),
+            |checksum, (i, generator)| {
+                if (top >> i) & 1 == 1 {
+                    checksum ^ generator
+                } else {
+                    checksum
+                }
+            },
+        )
+    })
+}
+
+// Decodes the witness version and program of a SegWit address, checked with the bech32
+// checksum for version 0 and the bech32m checksum for the later versions (BIP 350).
+fn decode_segwit_address(address: &str) -> Result<(u8, Vec<u8>), BridgeError> {
+    let address = address.to_ascii_lowercase();
+    let (hrp, data) = address
+        .rsplit_once('1')
+        .ok_or(BridgeError::InvalidAddress)?;
+    let values = data
+        .chars()
+        .map(|c| {
+            BECH32_CHARSET
+                .find(c)
+                .and_then(|value| u8::try_from(value).ok())
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or(BridgeError::InvalidAddress)?;
+    let [version, program_data @ .., _, _, _, _, _, _] = values.as_slice() else {
+        return Err(BridgeError::InvalidAddress);
+    };
+    let checksum = bech32_polymod(
+        hrp.bytes()
+            .map(|c| c >> 5)
+            .chain([0])
+            .chain(hrp.bytes().map(|c| c & 0x1f))
+            .chain(values.iter().copied()),
+    );
+    let expected_checksum = if *version == 0 {
+        BECH32_CHECKSUM
+    } else {
+        BECH32M_CHECKSUM
+    };
+    if *version > 16 || checksum != expected_checksum {
+        return Err(BridgeError::InvalidAddress);
+    }
+
+    // Regroups the 5-bit values into bytes, the padding has to be zero and under 5 bits
+    let mut program = Vec::with_capacity(program_data.len() * 5 / 8);
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    for value in program_data {
+        acc = ((acc << 5) | u32::from(*value)) & 0x0fff;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            program.push(((acc >> bits) & 0xff).try_into().unwrap_or_default());
+        }
+    }
+    if bits >= 5 || acc & ((1 << bits) - 1) != 0 || !(2..=40).contains(&program.len()) {
+        return Err(BridgeError::InvalidAddress);
+    }
+    if *version == 0 && program.len() != 20 && program.len() != 32 {
+        return Err(BridgeError::InvalidAddress);
+    }
+    Ok((*version, program))
+}
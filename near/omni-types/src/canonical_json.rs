@@ -0,0 +1,199 @@
+//! Canonical form of the JSON strings compared or hashed by the bridge, so semantically equal
+//! messages with a different key order or whitespace are treated as the same message.
+//!
+//! The numbers keep their literal text: parsing them into `serde_json::Value` would round the
+//! amounts that don't fit in 64 bits.
+use near_sdk::serde_json::{self, Value};
+
+/// Returns the JSON object or array with sorted keys and without whitespace,
+/// or `None` if the string is not a JSON object or array.
+pub fn canonicalize_json(json: &str) -> Option<String> {
+    let mut parser = Parser { json, pos: 0 };
+    parser.skip_whitespace();
+    if !matches!(parser.peek(), Some(b'{' | b'[')) {
+        return None;
+    }
+    let mut canonical = String::with_capacity(json.len());
+    parser.write_value(&mut canonical)?;
+    parser.skip_whitespace();
+    (parser.pos == json.len()).then_some(canonical)
+}
+
+/// Returns the canonical form of a JSON message, or the message itself if it is plain text.
+pub fn canonical_msg(msg: &str) -> String {
+    canonicalize_json(msg).unwrap_or_else(|| msg.to_string())
+}
+
+struct Parser<'a> {
+    json: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.json.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        self.skip_whitespace();
+        (self.peek() == Some(byte)).then(|| self.pos += 1)
+    }
+
+    fn write_value(&mut self, out: &mut String) -> Option<()> {
+        self.skip_whitespace();
+        match self.peek()? {
+            b'{' => self.write_object(out),
+            b'[' => self.write_array(out),
+            b'"' => {
+                let string = self.parse_string()?;
+                out.push_str(&Value::String(string).to_string());
+                Some(())
+            }
+            b't' => self.write_literal("true", out),
+            b'f' => self.write_literal("false", out),
+            b'n' => self.write_literal("null", out),
+            _ => self.write_number(out),
+        }
+    }
+
+    fn write_object(&mut self, out: &mut String) -> Option<()> {
+        self.expect(b'{')?;
+        let mut entries: Vec<(String, String)> = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+        } else {
+            loop {
+                self.skip_whitespace();
+                let key = self.parse_string()?;
+                self.expect(b':')?;
+                let mut value = String::new();
+                self.write_value(&mut value)?;
+                // The last occurrence of a key wins, as in `serde_json`
+                entries.retain(|(existing, _)| *existing != key);
+                entries.push((key, value));
+                self.skip_whitespace();
+                match self.peek()? {
+                    b',' => self.pos += 1,
+                    b'}' => {
+                        self.pos += 1;
+                        break;
+                    }
+                    _ => return None,
+                }
+            }
+        }
+
+        entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        out.push('{');
+        for (i, (key, value)) in entries.into_iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str(&Value::String(key).to_string());
+            out.push(':');
+            out.push_str(&value);
+        }
+        out.push('}');
+        Some(())
+    }
+
+    fn write_array(&mut self, out: &mut String) -> Option<()> {
+        self.expect(b'[')?;
+        out.push('[');
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+        } else {
+            let mut first = true;
+            loop {
+                if !first {
+                    out.push(',');
+                }
+                first = false;
+                self.write_value(out)?;
+                self.skip_whitespace();
+                match self.peek()? {
+                    b',' => self.pos += 1,
+                    b']' => {
+                        self.pos += 1;
+                        break;
+                    }
+                    _ => return None,
+                }
+            }
+        }
+        out.push(']');
+        Some(())
+    }
+
+    // Decodes the escapes, so the same string is always written the same way
+    fn parse_string(&mut self) -> Option<String> {
+        let start = self.pos;
+        if self.peek() != Some(b'"') {
+            return None;
+        }
+        self.pos += 1;
+        loop {
+            match self.peek()? {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => self.pos += 1,
+            }
+        }
+        serde_json::from_str(self.json.get(start..self.pos)?).ok()
+    }
+
+    fn write_literal(&mut self, literal: &str, out: &mut String) -> Option<()> {
+        self.json.get(self.pos..)?.starts_with(literal).then(|| {
+            self.pos += literal.len();
+            out.push_str(literal);
+        })
+    }
+
+    fn write_number(&mut self, out: &mut String) -> Option<()> {
+        let start = self.pos;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        match self.peek()? {
+            b'0' => self.pos += 1,
+            b'1'..=b'9' => self.skip_digits(),
+            _ => return None,
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            self.require_digits()?;
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            self.require_digits()?;
+        }
+        out.push_str(self.json.get(start..self.pos)?);
+        Some(())
+    }
+
+    fn skip_digits(&mut self) {
+        while self.peek().is_some_and(|byte| byte.is_ascii_digit()) {
+            self.pos += 1;
+        }
+    }
+
+    fn require_digits(&mut self) -> Option<()> {
+        let start = self.pos;
+        self.skip_digits();
+        (self.pos > start).then_some(())
+    }
+}
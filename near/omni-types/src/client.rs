@@ -0,0 +1,1101 @@
+//! Typed arguments and method names of the bridge contract for Rust clients, so relayers
+//! don't have to duplicate the argument structs of the contract.
+//!
+//! Every public method of the contract has a name constant in [`methods`]. The methods whose
+//! arguments are defined in this crate also have a request struct implementing
+//! [`ContractMethod`], and the views additionally implement [`ViewMethod`] with their response.
+//! The configuration methods of the DAO take types defined by the contract and are only
+//! exposed by name.
+
+use borsh::BorshSerialize;
+use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::serde::de::DeserializeOwned;
+use near_sdk::serde::Serialize;
+use near_sdk::{near, serde_json, AccountId, NearToken};
+
+use crate::btc::{OutPoint, TxOut, UTXOChainConfig};
+use crate::locker_args::{
+    BindTokenArgs, ClaimFeeArgs, DeployTokenArgs, FinTransferArgs, GovernanceActionArgs,
+    ReceiveMessageArgs,
+};
+use crate::mpc_types::SignatureScheme;
+use crate::near_events::EventSchema;
+use crate::{
+    ChainKind, FastTransferId, FastTransferStatus, Fee, Nonce, OmniAddress, TransferId,
+    TransferMessage, UnifiedTransferId, UpdateFee,
+};
+
+pub mod methods {
+    pub const SET_AUDITOR: &str = "set_auditor";
+    pub const GET_AUDITOR: &str = "get_auditor";
+    pub const GET_LAST_ACCOUNTING_SEQ: &str = "get_last_accounting_seq";
+
+    pub const ADD_UTXO_CHAIN_CONNECTOR: &str = "add_utxo_chain_connector";
+    pub const RBF_INCREASE_GAS_FEE: &str = "rbf_increase_gas_fee";
+    pub const BUMP_UTXO_WITHDRAWAL_FEE: &str = "bump_utxo_withdrawal_fee";
+    pub const SWEEP_LEGACY_ADDRESS: &str = "sweep_legacy_address";
+    pub const ADD_APPROVED_SWEEP_DESTINATION: &str = "add_approved_sweep_destination";
+    pub const REMOVE_APPROVED_SWEEP_DESTINATION: &str = "remove_approved_sweep_destination";
+    pub const IS_APPROVED_SWEEP_DESTINATION: &str = "is_approved_sweep_destination";
+    pub const GET_UTXO_CHAIN_CONNECTOR: &str = "get_utxo_chain_connector";
+    pub const GET_UTXO_CHAIN_TOKEN: &str = "get_utxo_chain_token";
+    pub const SET_UTXO_WITHDRAW_LIMITS: &str = "set_utxo_withdraw_limits";
+    pub const SET_EXTRA_MSG_REQUIRED: &str = "set_extra_msg_required";
+    pub const IS_EXTRA_MSG_REQUIRED: &str = "is_extra_msg_required";
+    pub const GET_UTXO_CHAIN_CONFIG: &str = "get_utxo_chain_config";
+    pub const SET_UTXO_DUST_LIMIT: &str = "set_utxo_dust_limit";
+    pub const GET_UTXO_DUST_LIMIT: &str = "get_utxo_dust_limit";
+    pub const SET_UTXO_SCRIPT_KINDS: &str = "set_utxo_script_kinds";
+    pub const GET_UTXO_SCRIPT_KINDS: &str = "get_utxo_script_kinds";
+    pub const SET_CUSTODY_UTXO_TRACKING: &str = "set_custody_utxo_tracking";
+    pub const IS_CUSTODY_UTXO_TRACKING_ENABLED: &str = "is_custody_utxo_tracking_enabled";
+    pub const UPDATE_CUSTODY_UTXOS: &str = "update_custody_utxos";
+    pub const GET_CUSTODY_UTXO: &str = "get_custody_utxo";
+    pub const GET_CHAIN_CONNECTOR: &str = "get_chain_connector";
+    pub const GET_CHAIN_TOKEN: &str = "get_chain_token";
+
+    pub const GET_TRANSFER_BENEFICIARY: &str = "get_transfer_beneficiary";
+    pub const CANCEL_TRANSFER: &str = "cancel_transfer";
+    pub const CANCEL_TRANSFER_AS_OWNER: &str = "cancel_transfer_as_owner";
+
+    pub const GET_TRANSFER_EXPIRY: &str = "get_transfer_expiry";
+    pub const EXPIRE_TRANSFER: &str = "expire_transfer";
+
+    pub const GET_BOOTSTRAP_STAGE: &str = "get_bootstrap_stage";
+    pub const ADVANCE_BOOTSTRAP_STAGE: &str = "advance_bootstrap_stage";
+    pub const ACTIVATE: &str = "activate";
+
+    pub const GET_METRICS_PACKED: &str = "get_metrics_packed";
+
+    pub const REGISTER_SUBMISSION_INTENT: &str = "register_submission_intent";
+    pub const GET_SUBMISSION_INTENT: &str = "get_submission_intent";
+    pub const GET_PREFERRED_RELAYER: &str = "get_preferred_relayer";
+
+    pub const SET_DESTINATION_CALL_GAS_FEE: &str = "set_destination_call_gas_fee";
+    pub const GET_DESTINATION_CALL_GAS_FEE: &str = "get_destination_call_gas_fee";
+    pub const GET_DESTINATION_CALL: &str = "get_destination_call";
+
+    pub const GET_ROUTE_LIMITS: &str = "get_route_limits";
+    pub const GET_TRANSFER_FEE_QUOTE: &str = "get_transfer_fee_quote";
+
+    pub const SET_CHAIN_TRAITS: &str = "set_chain_traits";
+    pub const GET_CHAIN_TRAITS: &str = "get_chain_traits";
+
+    pub const IS_TRANSFER_CLAIMED: &str = "is_transfer_claimed";
+    pub const GET_TRANSFER_CLAIM_RECORD: &str = "get_transfer_claim_record";
+    pub const GET_UNCLAIMED_TRANSFERS: &str = "get_unclaimed_transfers";
+
+    pub const SET_STANDBY_CONNECTOR: &str = "set_standby_connector";
+    pub const SET_FAILOVER_POLICY: &str = "set_failover_policy";
+    pub const SWITCH_TO_STANDBY_CONNECTOR: &str = "switch_to_standby_connector";
+    pub const GET_STANDBY_CONNECTOR: &str = "get_standby_connector";
+    pub const GET_FAILOVER_POLICY: &str = "get_failover_policy";
+    pub const GET_CONNECTOR_HEALTH: &str = "get_connector_health";
+    pub const RESET_CONNECTOR_HEALTH: &str = "reset_connector_health";
+    pub const GET_CONNECTOR_IN_FLIGHT: &str = "get_connector_in_flight";
+
+    pub const MIGRATE_UTXO_CHAIN_CONNECTOR: &str = "migrate_utxo_chain_connector";
+    pub const GET_CONNECTOR: &str = "get_connector";
+    pub const GET_CHAIN_CONNECTOR_ID: &str = "get_chain_connector_id";
+
+    pub const SET_CONNECTOR_INTERFACE_VERSION: &str = "set_connector_interface_version";
+    pub const GET_CONNECTOR_INTERFACE_VERSION: &str = "get_connector_interface_version";
+
+    pub const SET_MAX_TRANSFER_RESTORES: &str = "set_max_transfer_restores";
+    pub const GET_MAX_TRANSFER_RESTORES: &str = "get_max_transfer_restores";
+    pub const GET_TRANSFER_RESTORE_COUNT: &str = "get_transfer_restore_count";
+    pub const GET_DEAD_LETTER_TRANSFER: &str = "get_dead_letter_transfer";
+    pub const RETRY_DEAD_LETTER_TRANSFER: &str = "retry_dead_letter_transfer";
+    pub const REFUND_DEAD_LETTER_TRANSFER: &str = "refund_dead_letter_transfer";
+
+    pub const SET_DERIVED_ACCOUNT_FUNDING: &str = "set_derived_account_funding";
+    pub const GET_DERIVED_ACCOUNT_FUNDING: &str = "get_derived_account_funding";
+    pub const GET_DERIVED_NEAR_ACCOUNT_ID: &str = "get_derived_near_account_id";
+    pub const IS_DERIVED_ACCOUNT_FUNDED: &str = "is_derived_account_funded";
+
+    pub const GET_ERROR_CATALOG: &str = "get_error_catalog";
+
+    pub const SET_EVENT_EMISSION_POLICY: &str = "set_event_emission_policy";
+    pub const GET_EVENT_EMISSION_POLICY: &str = "get_event_emission_policy";
+    pub const FLUSH_EVENT_SUMMARY: &str = "flush_event_summary";
+
+    pub const SET_FEE_SWAP_CONTRACT: &str = "set_fee_swap_contract";
+    pub const SET_FEE_SWAP_POOL: &str = "set_fee_swap_pool";
+    pub const GET_FEE_SWAP_CONTRACT: &str = "get_fee_swap_contract";
+    pub const GET_FEE_SWAP_POOL: &str = "get_fee_swap_pool";
+    pub const SET_FEE_CONVERSION: &str = "set_fee_conversion";
+    pub const GET_FEE_CONVERSION: &str = "get_fee_conversion";
+
+    pub const SET_MIN_NATIVE_RELAYER_FEE: &str = "set_min_native_relayer_fee";
+    pub const SET_MIN_TOKEN_RELAYER_FEE: &str = "set_min_token_relayer_fee";
+    pub const GET_MIN_NATIVE_RELAYER_FEE: &str = "get_min_native_relayer_fee";
+    pub const GET_MIN_TOKEN_RELAYER_FEE: &str = "get_min_token_relayer_fee";
+
+    pub const GET_CLAIMABLE_FEE: &str = "get_claimable_fee";
+    pub const CLAIM_FEES: &str = "claim_fees";
+    pub const IS_FEE_SETTLEMENT_PAUSED: &str = "is_fee_settlement_paused";
+
+    pub const SET_FEE_RECIPIENT_STRATEGY: &str = "set_fee_recipient_strategy";
+    pub const GET_FEE_RECIPIENT_STRATEGY: &str = "get_fee_recipient_strategy";
+    pub const GET_UNREPORTED_FEE_TRANSFER: &str = "get_unreported_fee_transfer";
+    pub const REPORT_FEE_RECIPIENT: &str = "report_fee_recipient";
+
+    pub const REGISTER_FEE_SPONSOR: &str = "register_fee_sponsor";
+    pub const UNREGISTER_FEE_SPONSOR: &str = "unregister_fee_sponsor";
+    pub const SET_FEE_SPONSOR_LIMITS: &str = "set_fee_sponsor_limits";
+    pub const GET_FEE_SPONSOR: &str = "get_fee_sponsor";
+    pub const WITHDRAW_FEE_SPONSOR_BALANCE: &str = "withdraw_fee_sponsor_balance";
+
+    pub const GET_FORCE_FINALIZE_PROPOSAL: &str = "get_force_finalize_proposal";
+    pub const PROPOSE_FORCE_FINALIZE: &str = "propose_force_finalize";
+    pub const APPROVE_FORCE_FINALIZE: &str = "approve_force_finalize";
+    pub const CANCEL_FORCE_FINALIZE: &str = "cancel_force_finalize";
+    pub const FORCE_FINALIZE: &str = "force_finalize";
+
+    pub const SET_GOVERNANCE_EMITTER: &str = "set_governance_emitter";
+    pub const GET_GOVERNANCE_EMITTER: &str = "get_governance_emitter";
+    pub const GET_LAST_GOVERNANCE_NONCE: &str = "get_last_governance_nonce";
+    pub const EXECUTE_GOVERNANCE_ACTION: &str = "execute_governance_action";
+
+    pub const SET_INBOUND_FEE: &str = "set_inbound_fee";
+    pub const ADD_INBOUND_FEE_EXEMPT_ACCOUNT: &str = "add_inbound_fee_exempt_account";
+    pub const REMOVE_INBOUND_FEE_EXEMPT_ACCOUNT: &str = "remove_inbound_fee_exempt_account";
+    pub const GET_INBOUND_FEE: &str = "get_inbound_fee";
+    pub const IS_INBOUND_FEE_EXEMPT: &str = "is_inbound_fee_exempt";
+    pub const GET_PROTOCOL_FEES: &str = "get_protocol_fees";
+    pub const WITHDRAW_PROTOCOL_FEES: &str = "withdraw_protocol_fees";
+
+    pub const SET_TRANSFER_LABELS: &str = "set_transfer_labels";
+    pub const GET_TRANSFER_LABELS: &str = "get_transfer_labels";
+
+    pub const FT_ON_TRANSFER: &str = "ft_on_transfer";
+    pub const LOG_METADATA: &str = "log_metadata";
+    pub const UPDATE_TRANSFER_FEE: &str = "update_transfer_fee";
+    pub const SIGN_TRANSFER: &str = "sign_transfer";
+    pub const FIN_TRANSFER: &str = "fin_transfer";
+    pub const CLAIM_FEE: &str = "claim_fee";
+    pub const DEPLOY_TOKEN: &str = "deploy_token";
+    pub const DEPLOY_NATIVE_TOKEN: &str = "deploy_native_token";
+    pub const BIND_TOKEN: &str = "bind_token";
+    pub const FINISH_WITHDRAW_V2: &str = "finish_withdraw_v2";
+    pub const GET_TOKEN_ADDRESS: &str = "get_token_address";
+    pub const GET_TOKEN_ID: &str = "get_token_id";
+    pub const GET_BRIDGED_TOKEN: &str = "get_bridged_token";
+    pub const GET_NATIVE_TOKEN_ID: &str = "get_native_token_id";
+    pub const GET_EVENT_SCHEMAS: &str = "get_event_schemas";
+    pub const GET_TRANSFER_MESSAGE: &str = "get_transfer_message";
+    pub const GET_TRANSFER_MESSAGE_STORAGE: &str = "get_transfer_message_storage";
+    pub const IS_TRANSFER_FINALISED: &str = "is_transfer_finalised";
+    pub const IS_UNIFIED_TRANSFER_FINALISED: &str = "is_unified_transfer_finalised";
+    pub const GET_FAST_TRANSFER_STATUS: &str = "get_fast_transfer_status";
+    pub const IS_FAST_TRANSFER_FINALISED: &str = "is_fast_transfer_finalised";
+    pub const ADD_FACTORY: &str = "add_factory";
+    pub const ADD_TOKEN_DEPLOYER: &str = "add_token_deployer";
+    pub const TRANSFER_TOKEN_AS_DAO: &str = "transfer_token_as_dao";
+    pub const ADD_DEPLOYED_TOKENS: &str = "add_deployed_tokens";
+    pub const SET_TOKEN_METADATA: &str = "set_token_metadata";
+    pub const MIGRATE_DEPLOYED_TOKEN: &str = "migrate_deployed_token";
+    pub const GET_CURRENT_DESTINATION_NONCE: &str = "get_current_destination_nonce";
+    pub const GET_MPC_ACCOUNT: &str = "get_mpc_account";
+    pub const GET_TOKEN_DECIMALS: &str = "get_token_decimals";
+    pub const UPDATE_TOKENS_CONTROLLER: &str = "update_tokens_controller";
+    pub const ADD_PROVER: &str = "add_prover";
+    pub const REMOVE_PROVER: &str = "remove_prover";
+    pub const GET_PROVERS: &str = "get_provers";
+    pub const GET_UTXO_CHAIN_CONNECTORS: &str = "get_utxo_chain_connectors";
+    pub const GET_UTXO_CHAIN_BY_TOKEN: &str = "get_utxo_chain_by_token";
+
+    pub const GET_LOCKED_BREAKDOWN: &str = "get_locked_breakdown";
+
+    pub const SET_MAINTENANCE_CONFIG: &str = "set_maintenance_config";
+    pub const GET_MAINTENANCE_CONFIG: &str = "get_maintenance_config";
+    pub const GET_MAINTENANCE_BUDGET: &str = "get_maintenance_budget";
+    pub const FUND_MAINTENANCE_BUDGET: &str = "fund_maintenance_budget";
+
+    pub const GET_TRANSFER_ORIGIN_SENDERS: &str = "get_transfer_origin_senders";
+
+    pub const GET_OUTPOINT_RESERVATION: &str = "get_outpoint_reservation";
+    pub const RELEASE_OUTPOINT_RESERVATIONS: &str = "release_outpoint_reservations";
+    pub const RELEASE_STALE_OUTPOINT_RESERVATIONS: &str = "release_stale_outpoint_reservations";
+    pub const SUBMIT_UTXO_CONSOLIDATION: &str = "submit_utxo_consolidation";
+    pub const RECORD_UTXO_WITHDRAWAL_TXID: &str = "record_utxo_withdrawal_txid";
+    pub const GET_EXPECTED_UTXO_TXID: &str = "get_expected_utxo_txid";
+    pub const REMOVE_UTXO_WITHDRAWAL: &str = "remove_utxo_withdrawal";
+
+    pub const GET_PENDING_TRANSFERS: &str = "get_pending_transfers";
+    pub const GET_PENDING_TRANSFERS_COUNT: &str = "get_pending_transfers_count";
+    pub const GET_ALL_PENDING_TRANSFERS: &str = "get_all_pending_transfers";
+    pub const GET_TRANSFERS_BY_OWNER: &str = "get_transfers_by_owner";
+    pub const INDEX_PENDING_TRANSFERS: &str = "index_pending_transfers";
+    pub const SCAN_ORPHANS: &str = "scan_orphans";
+    pub const REPAIR_ORPHANS: &str = "repair_orphans";
+    pub const SCAN_UNINDEXED_TRANSFERS: &str = "scan_unindexed_transfers";
+
+    pub const VALIDATE_PROOF: &str = "validate_proof";
+
+    pub const SET_TRANSFER_QUOTA: &str = "set_transfer_quota";
+    pub const ADD_QUOTA_EXEMPT_ACCOUNT: &str = "add_quota_exempt_account";
+    pub const REMOVE_QUOTA_EXEMPT_ACCOUNT: &str = "remove_quota_exempt_account";
+    pub const GET_TRANSFER_QUOTA: &str = "get_transfer_quota";
+    pub const IS_QUOTA_EXEMPT: &str = "is_quota_exempt";
+    pub const GET_TRANSFER_QUOTA_USAGE: &str = "get_transfer_quota_usage";
+
+    pub const SET_REWARDS_CONFIG: &str = "set_rewards_config";
+    pub const SET_REWARDED_TOKEN: &str = "set_rewarded_token";
+    pub const GET_REWARDS_CONFIG: &str = "get_rewards_config";
+    pub const GET_REWARDED_TOKEN: &str = "get_rewarded_token";
+    pub const GET_CURRENT_REWARD_EPOCH: &str = "get_current_reward_epoch";
+    pub const GET_REWARD_EPOCH: &str = "get_reward_epoch";
+    pub const GET_REWARD_POINTS: &str = "get_reward_points";
+    pub const GET_CLAIMABLE_REWARDS: &str = "get_claimable_rewards";
+    pub const CLAIM_REWARDS: &str = "claim_rewards";
+
+    pub const GET_ROLE_MEMBERS: &str = "get_role_members";
+    pub const GET_ROLE_MEMBERS_COUNT: &str = "get_role_members_count";
+    pub const GET_ROLE_MEMBER_COUNTS: &str = "get_role_member_counts";
+
+    pub const GET_ROUTES: &str = "get_routes";
+
+    pub const SET_SCRIPT_HASH_ALLOWLIST_MODE: &str = "set_script_hash_allowlist_mode";
+    pub const ADD_ALLOWED_SCRIPT_HASH_RECIPIENT: &str = "add_allowed_script_hash_recipient";
+    pub const REMOVE_ALLOWED_SCRIPT_HASH_RECIPIENT: &str = "remove_allowed_script_hash_recipient";
+    pub const IS_SCRIPT_HASH_ALLOWLIST_MODE: &str = "is_script_hash_allowlist_mode";
+    pub const IS_ALLOWED_SCRIPT_HASH_RECIPIENT: &str = "is_allowed_script_hash_recipient";
+
+    pub const SET_SIGNATURE_SCHEME: &str = "set_signature_scheme";
+    pub const GET_SIGNATURE_SCHEME: &str = "get_signature_scheme";
+
+    pub const GET_TRANSFER_TIMESTAMPS: &str = "get_transfer_timestamps";
+    pub const GET_LATENCY_STATS: &str = "get_latency_stats";
+    pub const ESTIMATE_COMPLETION_TIME: &str = "estimate_completion_time";
+    pub const GET_ROUTE_LATENCY: &str = "get_route_latency";
+    pub const PRUNE_TRANSFER_TIMESTAMPS: &str = "prune_transfer_timestamps";
+
+    pub const STORAGE_DEPOSIT: &str = "storage_deposit";
+    pub const STORAGE_WITHDRAW: &str = "storage_withdraw";
+    pub const STORAGE_UNREGISTER: &str = "storage_unregister";
+    pub const STORAGE_BALANCE_BOUNDS: &str = "storage_balance_bounds";
+    pub const STORAGE_BALANCE_OF: &str = "storage_balance_of";
+    pub const REQUIRED_BALANCE_FOR_ACCOUNT: &str = "required_balance_for_account";
+    pub const REQUIRED_BALANCE_FOR_INIT_TRANSFER: &str = "required_balance_for_init_transfer";
+    pub const REQUIRED_BALANCE_FOR_INIT_TRANSFER_MESSAGE: &str =
+        "required_balance_for_init_transfer_message";
+    pub const REQUIRED_BALANCE_FOR_FIN_TRANSFER: &str = "required_balance_for_fin_transfer";
+    pub const REQUIRED_BALANCE_FOR_FAST_TRANSFER: &str = "required_balance_for_fast_transfer";
+    pub const REQUIRED_BALANCE_FOR_BIND_TOKEN: &str = "required_balance_for_bind_token";
+    pub const REQUIRED_BALANCE_FOR_DEPLOY_TOKEN: &str = "required_balance_for_deploy_token";
+
+    pub const SET_STORAGE_SAFETY_MARGIN: &str = "set_storage_safety_margin";
+    pub const GET_STORAGE_SAFETY_MARGIN: &str = "get_storage_safety_margin";
+    pub const GET_CONTRACT_AVAILABLE_BALANCE: &str = "get_contract_available_balance";
+
+    pub const SET_MAX_TRANSFER_AMOUNT: &str = "set_max_transfer_amount";
+    pub const APPROVE_OVERSIZE_TRANSFER: &str = "approve_oversize_transfer";
+    pub const REVOKE_OVERSIZE_TRANSFER: &str = "revoke_oversize_transfer";
+    pub const GET_MAX_TRANSFER_AMOUNT: &str = "get_max_transfer_amount";
+    pub const GET_OVERSIZE_TRANSFER_ALLOWANCE: &str = "get_oversize_transfer_allowance";
+
+    pub const SET_TVL_CAP: &str = "set_tvl_cap";
+    pub const GET_TVL_CAP: &str = "get_tvl_cap";
+    pub const GET_TVL: &str = "get_tvl";
+    pub const GET_TVL_HEADROOM: &str = "get_tvl_headroom";
+
+    pub const SIGN_INCOMING_TRANSFER_NOTICE: &str = "sign_incoming_transfer_notice";
+
+    pub const TRANSFER_OWNERSHIP: &str = "transfer_ownership";
+
+    pub const SET_UNCLAIMED_DEPOSITS_ENABLED: &str = "set_unclaimed_deposits_enabled";
+    pub const IS_UNCLAIMED_DEPOSITS_ENABLED: &str = "is_unclaimed_deposits_enabled";
+    pub const GET_UNCLAIMED_DEPOSIT: &str = "get_unclaimed_deposit";
+    pub const CLAIM_UNCLAIMED_DEPOSIT: &str = "claim_unclaimed_deposit";
+
+    pub const SUBMIT_TRANSFER_TO_UTXO_CHAIN_CONNECTOR: &str =
+        "submit_transfer_to_utxo_chain_connector";
+    pub const SUBMIT_TRANSFERS_TO_UTXO_CHAIN_CONNECTOR: &str =
+        "submit_transfers_to_utxo_chain_connector";
+
+    pub const SET_UTXO_ADDRESS_FORMATS: &str = "set_utxo_address_formats";
+    pub const GET_UTXO_ADDRESS_FORMATS: &str = "get_utxo_address_formats";
+
+    pub const REGISTER_UTXO_KEY_EPOCH: &str = "register_utxo_key_epoch";
+    pub const FINISH_UTXO_KEY_ROTATION: &str = "finish_utxo_key_rotation";
+    pub const ADD_LEGACY_OUTPOINTS: &str = "add_legacy_outpoints";
+    pub const REMOVE_LEGACY_OUTPOINTS: &str = "remove_legacy_outpoints";
+    pub const GET_UTXO_KEY_ROTATION: &str = "get_utxo_key_rotation";
+    pub const IS_LEGACY_OUTPOINT: &str = "is_legacy_outpoint";
+
+    pub const SET_UTXO_REFUND_POLICY: &str = "set_utxo_refund_policy";
+    pub const GET_UTXO_REFUND_POLICY: &str = "get_utxo_refund_policy";
+
+    pub const SET_UTXO_FEE_RATE: &str = "set_utxo_fee_rate";
+    pub const GET_UTXO_FEE_RATE: &str = "get_utxo_fee_rate";
+    pub const SET_MAX_ALLOWED_WITHDRAW_FEE: &str = "set_max_allowed_withdraw_fee";
+    pub const GET_MAX_ALLOWED_WITHDRAW_FEE: &str = "get_max_allowed_withdraw_fee";
+
+    pub const REGISTER_UTXO_CHAIN: &str = "register_utxo_chain";
+    pub const UNREGISTER_UTXO_CHAIN: &str = "unregister_utxo_chain";
+    pub const GET_UTXO_CHAIN_REGISTRATION: &str = "get_utxo_chain_registration";
+
+    pub const SET_WATCHTOWER_CONFIG: &str = "set_watchtower_config";
+    pub const GET_WATCHTOWER_CONFIG: &str = "get_watchtower_config";
+    pub const REGISTER_WATCHTOWER: &str = "register_watchtower";
+    pub const UNREGISTER_WATCHTOWER: &str = "unregister_watchtower";
+    pub const GET_WATCHTOWER: &str = "get_watchtower";
+    pub const FLAG_TRANSFER: &str = "flag_transfer";
+    pub const GET_TRANSFER_FLAGS: &str = "get_transfer_flags";
+    pub const IS_TRANSFER_HELD: &str = "is_transfer_held";
+    pub const CLEAR_TRANSFER_FLAGS: &str = "clear_transfer_flags";
+
+    pub const SEND_MESSAGE: &str = "send_message";
+    pub const RECEIVE_MESSAGE: &str = "receive_message";
+    pub const REGISTER_MESSAGE_HANDLER: &str = "register_message_handler";
+    pub const UNREGISTER_MESSAGE_HANDLER: &str = "unregister_message_handler";
+    pub const IS_MESSAGE_HANDLER: &str = "is_message_handler";
+    pub const IS_MESSAGE_RECEIVED: &str = "is_message_received";
+    pub const GET_CURRENT_MESSAGE_NONCE: &str = "get_current_message_nonce";
+
+    pub const SET_FEE_ESCALATION_POLICY: &str = "set_fee_escalation_policy";
+    pub const GET_FEE_ESCALATION_POLICY: &str = "get_fee_escalation_policy";
+    pub const FUND_FEE_ESCALATION_BUDGET: &str = "fund_fee_escalation_budget";
+    pub const WITHDRAW_FEE_ESCALATION_BUDGET: &str = "withdraw_fee_escalation_budget";
+    pub const GET_FEE_ESCALATION_BUDGET: &str = "get_fee_escalation_budget";
+    pub const GET_FEE_ESCALATION: &str = "get_fee_escalation";
+    pub const ESCALATE_TRANSFER_FEE: &str = "escalate_transfer_fee";
+}
+
+/// Format of the arguments of a contract method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgsFormat {
+    Json,
+    Borsh,
+}
+
+/// Arguments of a contract method, serialized the way the contract expects them.
+pub trait ContractMethod: Serialize + BorshSerialize {
+    const NAME: &'static str;
+    const ARGS_FORMAT: ArgsFormat = ArgsFormat::Json;
+
+    fn to_args(&self) -> std::io::Result<Vec<u8>> {
+        match Self::ARGS_FORMAT {
+            ArgsFormat::Json => serde_json::to_vec(self).map_err(std::io::Error::other),
+            ArgsFormat::Borsh => borsh::to_vec(self),
+        }
+    }
+}
+
+/// View method with the JSON response returned by the contract.
+pub trait ViewMethod: ContractMethod {
+    type Response: DeserializeOwned;
+}
+
+impl ContractMethod for FinTransferArgs {
+    const NAME: &'static str = methods::FIN_TRANSFER;
+    const ARGS_FORMAT: ArgsFormat = ArgsFormat::Borsh;
+}
+
+impl ContractMethod for ClaimFeeArgs {
+    const NAME: &'static str = methods::CLAIM_FEE;
+    const ARGS_FORMAT: ArgsFormat = ArgsFormat::Borsh;
+}
+
+impl ContractMethod for DeployTokenArgs {
+    const NAME: &'static str = methods::DEPLOY_TOKEN;
+    const ARGS_FORMAT: ArgsFormat = ArgsFormat::Borsh;
+}
+
+impl ContractMethod for BindTokenArgs {
+    const NAME: &'static str = methods::BIND_TOKEN;
+    const ARGS_FORMAT: ArgsFormat = ArgsFormat::Borsh;
+}
+
+impl ContractMethod for GovernanceActionArgs {
+    const NAME: &'static str = methods::EXECUTE_GOVERNANCE_ACTION;
+    const ARGS_FORMAT: ArgsFormat = ArgsFormat::Borsh;
+}
+
+impl ContractMethod for ReceiveMessageArgs {
+    const NAME: &'static str = methods::RECEIVE_MESSAGE;
+    const ARGS_FORMAT: ArgsFormat = ArgsFormat::Borsh;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct SignTransferArgs {
+    pub transfer_id: TransferId,
+    pub fee_recipient: Option<AccountId>,
+    pub fee: Option<Fee>,
+}
+
+impl ContractMethod for SignTransferArgs {
+    const NAME: &'static str = methods::SIGN_TRANSFER;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct UpdateTransferFeeArgs {
+    pub transfer_id: TransferId,
+    pub fee: UpdateFee,
+}
+
+impl ContractMethod for UpdateTransferFeeArgs {
+    const NAME: &'static str = methods::UPDATE_TRANSFER_FEE;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct SubmitTransferToUtxoChainConnectorArgs {
+    pub transfer_id: TransferId,
+    pub msg: String,
+    pub fee_recipient: Option<AccountId>,
+    pub fee: Option<Fee>,
+}
+
+impl ContractMethod for SubmitTransferToUtxoChainConnectorArgs {
+    const NAME: &'static str = methods::SUBMIT_TRANSFER_TO_UTXO_CHAIN_CONNECTOR;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct RbfIncreaseGasFeeArgs {
+    pub chain_kind: ChainKind,
+    pub original_btc_pending_verify_id: String,
+    pub output: Vec<TxOut>,
+}
+
+impl ContractMethod for RbfIncreaseGasFeeArgs {
+    const NAME: &'static str = methods::RBF_INCREASE_GAS_FEE;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct ReportFeeRecipientArgs {
+    pub transfer_id: TransferId,
+    pub fee_recipient: AccountId,
+}
+
+impl ContractMethod for ReportFeeRecipientArgs {
+    const NAME: &'static str = methods::REPORT_FEE_RECIPIENT;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct CancelTransferArgs {
+    pub transfer_id: TransferId,
+    pub signature: Base64VecU8,
+}
+
+impl ContractMethod for CancelTransferArgs {
+    const NAME: &'static str = methods::CANCEL_TRANSFER;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct CancelTransferAsOwnerArgs {
+    pub transfer_id: TransferId,
+}
+
+impl ContractMethod for CancelTransferAsOwnerArgs {
+    const NAME: &'static str = methods::CANCEL_TRANSFER_AS_OWNER;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct ExpireTransferArgs {
+    pub transfer_id: TransferId,
+}
+
+impl ContractMethod for ExpireTransferArgs {
+    const NAME: &'static str = methods::EXPIRE_TRANSFER;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct ClaimUnclaimedDepositArgs {
+    pub transfer_id: TransferId,
+    pub recipient: AccountId,
+    pub signature: Base64VecU8,
+}
+
+impl ContractMethod for ClaimUnclaimedDepositArgs {
+    const NAME: &'static str = methods::CLAIM_UNCLAIMED_DEPOSIT;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct TransferOwnershipArgs {
+    pub transfer_id: TransferId,
+    pub new_owner: AccountId,
+}
+
+impl ContractMethod for TransferOwnershipArgs {
+    const NAME: &'static str = methods::TRANSFER_OWNERSHIP;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct SetTransferLabelsArgs {
+    pub transfer_id: TransferId,
+    pub labels: Vec<String>,
+}
+
+impl ContractMethod for SetTransferLabelsArgs {
+    const NAME: &'static str = methods::SET_TRANSFER_LABELS;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct LogMetadataArgs {
+    pub token_id: AccountId,
+}
+
+impl ContractMethod for LogMetadataArgs {
+    const NAME: &'static str = methods::LOG_METADATA;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct ClaimRewardsArgs {
+    pub epochs: Vec<u64>,
+}
+
+impl ContractMethod for ClaimRewardsArgs {
+    const NAME: &'static str = methods::CLAIM_REWARDS;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct SetFeeConversionArgs {
+    pub max_slippage_bps: Option<u16>,
+}
+
+impl ContractMethod for SetFeeConversionArgs {
+    const NAME: &'static str = methods::SET_FEE_CONVERSION;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct IndexPendingTransfersArgs {
+    pub transfer_ids: Vec<TransferId>,
+}
+
+impl ContractMethod for IndexPendingTransfersArgs {
+    const NAME: &'static str = methods::INDEX_PENDING_TRANSFERS;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct PruneTransferTimestampsArgs {
+    pub transfer_ids: Vec<TransferId>,
+}
+
+impl ContractMethod for PruneTransferTimestampsArgs {
+    const NAME: &'static str = methods::PRUNE_TRANSFER_TIMESTAMPS;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetTransferMessageArgs {
+    pub transfer_id: TransferId,
+}
+
+impl ContractMethod for GetTransferMessageArgs {
+    const NAME: &'static str = methods::GET_TRANSFER_MESSAGE;
+}
+
+impl ViewMethod for GetTransferMessageArgs {
+    type Response = TransferMessage;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct IsTransferFinalisedArgs {
+    pub transfer_id: TransferId,
+}
+
+impl ContractMethod for IsTransferFinalisedArgs {
+    const NAME: &'static str = methods::IS_TRANSFER_FINALISED;
+}
+
+impl ViewMethod for IsTransferFinalisedArgs {
+    type Response = bool;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct IsTransferClaimedArgs {
+    pub transfer_id: TransferId,
+}
+
+impl ContractMethod for IsTransferClaimedArgs {
+    const NAME: &'static str = methods::IS_TRANSFER_CLAIMED;
+}
+
+impl ViewMethod for IsTransferClaimedArgs {
+    type Response = bool;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct IsUnifiedTransferFinalisedArgs {
+    pub transfer_id: UnifiedTransferId,
+}
+
+impl ContractMethod for IsUnifiedTransferFinalisedArgs {
+    const NAME: &'static str = methods::IS_UNIFIED_TRANSFER_FINALISED;
+}
+
+impl ViewMethod for IsUnifiedTransferFinalisedArgs {
+    type Response = bool;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetFastTransferStatusArgs {
+    pub fast_transfer_id: FastTransferId,
+}
+
+impl ContractMethod for GetFastTransferStatusArgs {
+    const NAME: &'static str = methods::GET_FAST_TRANSFER_STATUS;
+}
+
+impl ViewMethod for GetFastTransferStatusArgs {
+    type Response = Option<FastTransferStatus>;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct IsFastTransferFinalisedArgs {
+    pub fast_transfer_id: FastTransferId,
+}
+
+impl ContractMethod for IsFastTransferFinalisedArgs {
+    const NAME: &'static str = methods::IS_FAST_TRANSFER_FINALISED;
+}
+
+impl ViewMethod for IsFastTransferFinalisedArgs {
+    type Response = bool;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetUnreportedFeeTransferArgs {
+    pub transfer_id: TransferId,
+}
+
+impl ContractMethod for GetUnreportedFeeTransferArgs {
+    const NAME: &'static str = methods::GET_UNREPORTED_FEE_TRANSFER;
+}
+
+impl ViewMethod for GetUnreportedFeeTransferArgs {
+    type Response = Option<TransferMessage>;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetTransferLabelsArgs {
+    pub transfer_id: TransferId,
+}
+
+impl ContractMethod for GetTransferLabelsArgs {
+    const NAME: &'static str = methods::GET_TRANSFER_LABELS;
+}
+
+impl ViewMethod for GetTransferLabelsArgs {
+    type Response = Vec<String>;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetTransferOriginSendersArgs {
+    pub transfer_id: UnifiedTransferId,
+}
+
+impl ContractMethod for GetTransferOriginSendersArgs {
+    const NAME: &'static str = methods::GET_TRANSFER_ORIGIN_SENDERS;
+}
+
+impl ViewMethod for GetTransferOriginSendersArgs {
+    type Response = Option<Vec<OmniAddress>>;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct EstimateCompletionTimeArgs {
+    pub origin_chain: ChainKind,
+    pub destination_chain: ChainKind,
+}
+
+impl ContractMethod for EstimateCompletionTimeArgs {
+    const NAME: &'static str = methods::ESTIMATE_COMPLETION_TIME;
+}
+
+impl ViewMethod for EstimateCompletionTimeArgs {
+    type Response = Option<u64>;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetTokenIdArgs {
+    pub address: OmniAddress,
+}
+
+impl ContractMethod for GetTokenIdArgs {
+    const NAME: &'static str = methods::GET_TOKEN_ID;
+}
+
+impl ViewMethod for GetTokenIdArgs {
+    type Response = AccountId;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetTokenAddressArgs {
+    pub chain_kind: ChainKind,
+    pub token: AccountId,
+}
+
+impl ContractMethod for GetTokenAddressArgs {
+    const NAME: &'static str = methods::GET_TOKEN_ADDRESS;
+}
+
+impl ViewMethod for GetTokenAddressArgs {
+    type Response = Option<OmniAddress>;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetBridgedTokenArgs {
+    pub address: OmniAddress,
+    pub chain: ChainKind,
+}
+
+impl ContractMethod for GetBridgedTokenArgs {
+    const NAME: &'static str = methods::GET_BRIDGED_TOKEN;
+}
+
+impl ViewMethod for GetBridgedTokenArgs {
+    type Response = Option<OmniAddress>;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetNativeTokenIdArgs {
+    pub chain: ChainKind,
+}
+
+impl ContractMethod for GetNativeTokenIdArgs {
+    const NAME: &'static str = methods::GET_NATIVE_TOKEN_ID;
+}
+
+impl ViewMethod for GetNativeTokenIdArgs {
+    type Response = AccountId;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetCurrentDestinationNonceArgs {
+    pub chain_kind: ChainKind,
+}
+
+impl ContractMethod for GetCurrentDestinationNonceArgs {
+    const NAME: &'static str = methods::GET_CURRENT_DESTINATION_NONCE;
+}
+
+impl ViewMethod for GetCurrentDestinationNonceArgs {
+    type Response = Nonce;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetMpcAccountArgs {}
+
+impl ContractMethod for GetMpcAccountArgs {
+    const NAME: &'static str = methods::GET_MPC_ACCOUNT;
+}
+
+impl ViewMethod for GetMpcAccountArgs {
+    type Response = AccountId;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetEventSchemasArgs {}
+
+impl ContractMethod for GetEventSchemasArgs {
+    const NAME: &'static str = methods::GET_EVENT_SCHEMAS;
+}
+
+impl ViewMethod for GetEventSchemasArgs {
+    type Response = Vec<EventSchema>;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetSignatureSchemeArgs {
+    pub chain_kind: ChainKind,
+}
+
+impl ContractMethod for GetSignatureSchemeArgs {
+    const NAME: &'static str = methods::GET_SIGNATURE_SCHEME;
+}
+
+impl ViewMethod for GetSignatureSchemeArgs {
+    type Response = SignatureScheme;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetDerivedNearAccountIdArgs {
+    pub address: OmniAddress,
+}
+
+impl ContractMethod for GetDerivedNearAccountIdArgs {
+    const NAME: &'static str = methods::GET_DERIVED_NEAR_ACCOUNT_ID;
+}
+
+impl ViewMethod for GetDerivedNearAccountIdArgs {
+    type Response = Option<AccountId>;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetUtxoChainConnectorArgs {
+    pub chain_kind: ChainKind,
+}
+
+impl ContractMethod for GetUtxoChainConnectorArgs {
+    const NAME: &'static str = methods::GET_UTXO_CHAIN_CONNECTOR;
+}
+
+impl ViewMethod for GetUtxoChainConnectorArgs {
+    type Response = AccountId;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetUtxoChainTokenArgs {
+    pub chain_kind: ChainKind,
+}
+
+impl ContractMethod for GetUtxoChainTokenArgs {
+    const NAME: &'static str = methods::GET_UTXO_CHAIN_TOKEN;
+}
+
+impl ViewMethod for GetUtxoChainTokenArgs {
+    type Response = AccountId;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetUtxoChainConnectorsArgs {}
+
+impl ContractMethod for GetUtxoChainConnectorsArgs {
+    const NAME: &'static str = methods::GET_UTXO_CHAIN_CONNECTORS;
+}
+
+impl ViewMethod for GetUtxoChainConnectorsArgs {
+    type Response = Vec<(ChainKind, UTXOChainConfig)>;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetConnectorInterfaceVersionArgs {
+    pub chain_kind: ChainKind,
+}
+
+impl ContractMethod for GetConnectorInterfaceVersionArgs {
+    const NAME: &'static str = methods::GET_CONNECTOR_INTERFACE_VERSION;
+}
+
+impl ViewMethod for GetConnectorInterfaceVersionArgs {
+    type Response = u32;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetOutpointReservationArgs {
+    pub chain_kind: ChainKind,
+    pub outpoint: OutPoint,
+}
+
+impl ContractMethod for GetOutpointReservationArgs {
+    const NAME: &'static str = methods::GET_OUTPOINT_RESERVATION;
+}
+
+impl ViewMethod for GetOutpointReservationArgs {
+    type Response = Vec<TransferId>;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct IsLegacyOutpointArgs {
+    pub chain_kind: ChainKind,
+    pub outpoint: OutPoint,
+}
+
+impl ContractMethod for IsLegacyOutpointArgs {
+    const NAME: &'static str = methods::IS_LEGACY_OUTPOINT;
+}
+
+impl ViewMethod for IsLegacyOutpointArgs {
+    type Response = bool;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetPendingTransfersCountArgs {
+    pub chain_kind: ChainKind,
+}
+
+impl ContractMethod for GetPendingTransfersCountArgs {
+    const NAME: &'static str = methods::GET_PENDING_TRANSFERS_COUNT;
+}
+
+impl ViewMethod for GetPendingTransfersCountArgs {
+    type Response = u64;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetTransferRestoreCountArgs {
+    pub transfer_id: TransferId,
+}
+
+impl ContractMethod for GetTransferRestoreCountArgs {
+    const NAME: &'static str = methods::GET_TRANSFER_RESTORE_COUNT;
+}
+
+impl ViewMethod for GetTransferRestoreCountArgs {
+    type Response = u32;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetProtocolFeesArgs {
+    pub token_id: AccountId,
+}
+
+impl ContractMethod for GetProtocolFeesArgs {
+    const NAME: &'static str = methods::GET_PROTOCOL_FEES;
+}
+
+impl ViewMethod for GetProtocolFeesArgs {
+    type Response = U128;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetMaxTransferAmountArgs {
+    pub token_id: AccountId,
+}
+
+impl ContractMethod for GetMaxTransferAmountArgs {
+    const NAME: &'static str = methods::GET_MAX_TRANSFER_AMOUNT;
+}
+
+impl ViewMethod for GetMaxTransferAmountArgs {
+    type Response = Option<U128>;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GetClaimableRewardsArgs {
+    pub account_id: AccountId,
+    pub epochs: Vec<u64>,
+}
+
+impl ContractMethod for GetClaimableRewardsArgs {
+    const NAME: &'static str = methods::GET_CLAIMABLE_REWARDS;
+}
+
+impl ViewMethod for GetClaimableRewardsArgs {
+    type Response = U128;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct RequiredBalanceForAccountArgs {}
+
+impl ContractMethod for RequiredBalanceForAccountArgs {
+    const NAME: &'static str = methods::REQUIRED_BALANCE_FOR_ACCOUNT;
+}
+
+impl ViewMethod for RequiredBalanceForAccountArgs {
+    type Response = NearToken;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct RequiredBalanceForInitTransferArgs {
+    pub msg: Option<String>,
+}
+
+impl ContractMethod for RequiredBalanceForInitTransferArgs {
+    const NAME: &'static str = methods::REQUIRED_BALANCE_FOR_INIT_TRANSFER;
+}
+
+impl ViewMethod for RequiredBalanceForInitTransferArgs {
+    type Response = NearToken;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct RequiredBalanceForInitTransferMessageArgs {
+    pub transfer_message: TransferMessage,
+}
+
+impl ContractMethod for RequiredBalanceForInitTransferMessageArgs {
+    const NAME: &'static str = methods::REQUIRED_BALANCE_FOR_INIT_TRANSFER_MESSAGE;
+}
+
+impl ViewMethod for RequiredBalanceForInitTransferMessageArgs {
+    type Response = NearToken;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct RequiredBalanceForFinTransferArgs {}
+
+impl ContractMethod for RequiredBalanceForFinTransferArgs {
+    const NAME: &'static str = methods::REQUIRED_BALANCE_FOR_FIN_TRANSFER;
+}
+
+impl ViewMethod for RequiredBalanceForFinTransferArgs {
+    type Response = NearToken;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct RequiredBalanceForFastTransferArgs {}
+
+impl ContractMethod for RequiredBalanceForFastTransferArgs {
+    const NAME: &'static str = methods::REQUIRED_BALANCE_FOR_FAST_TRANSFER;
+}
+
+impl ViewMethod for RequiredBalanceForFastTransferArgs {
+    type Response = NearToken;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct RequiredBalanceForBindTokenArgs {}
+
+impl ContractMethod for RequiredBalanceForBindTokenArgs {
+    const NAME: &'static str = methods::REQUIRED_BALANCE_FOR_BIND_TOKEN;
+}
+
+impl ViewMethod for RequiredBalanceForBindTokenArgs {
+    type Response = NearToken;
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone)]
+pub struct RequiredBalanceForDeployTokenArgs {}
+
+impl ContractMethod for RequiredBalanceForDeployTokenArgs {
+    const NAME: &'static str = methods::REQUIRED_BALANCE_FOR_DEPLOY_TOKEN;
+}
+
+impl ViewMethod for RequiredBalanceForDeployTokenArgs {
+    type Response = NearToken;
+}
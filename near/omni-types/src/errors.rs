@@ -0,0 +1,66 @@
+use core::fmt;
+
+/// Validation error of the bridge. The contract panics with the `ERR_` code of the error,
+/// which is listed in the error catalog of the contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BridgeError {
+    InvalidWithdrawMsg,
+    InvalidWithdrawMsgType,
+    IncorrectTargetAddress,
+    ShieldedAddressNotSupported,
+    ShieldedOutputNotSupported,
+    InvalidUtxoChainMsg,
+    MissingMaxGasFee,
+    InvalidMaxGasFee,
+    MissingMaxFeeRate,
+    InvalidMaxFeeRate,
+    MissingMaxFeePerKb,
+    InvalidMaxFeePerKb,
+    FeeRateAboveMaxFee,
+    TvlCapExceeded,
+    ZeroOutputValue,
+    InvalidScriptPubkey,
+    ScriptPubkeyTooLarge,
+    InvalidDestinationChain,
+    NotUtxoChainToken,
+    ExtraMsgRequired,
+    InvalidTransaction,
+    TxidNotSupported,
+    InvalidAddress,
+}
+
+impl BridgeError {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::InvalidWithdrawMsg => "ERR_INVALID_WITHDRAW_MSG",
+            Self::InvalidWithdrawMsgType => "ERR_INVALID_WITHDRAW_MSG_TYPE",
+            Self::IncorrectTargetAddress => "ERR_INCORRECT_TARGET_ADDRESS",
+            Self::ShieldedAddressNotSupported => "ERR_SHIELDED_ADDRESS_NOT_SUPPORTED",
+            Self::ShieldedOutputNotSupported => "ERR_SHIELDED_OUTPUT_NOT_SUPPORTED",
+            Self::InvalidUtxoChainMsg => "ERR_INVALID_UTXO_CHAIN_MSG",
+            Self::MissingMaxGasFee => "ERR_MISSING_MAX_GAS_FEE",
+            Self::InvalidMaxGasFee => "ERR_INVALID_MAX_GAS_FEE",
+            Self::MissingMaxFeeRate => "ERR_MISSING_MAX_FEE_RATE",
+            Self::InvalidMaxFeeRate => "ERR_INVALID_MAX_FEE_RATE",
+            Self::MissingMaxFeePerKb => "ERR_MISSING_MAX_FEE_PER_KB",
+            Self::InvalidMaxFeePerKb => "ERR_INVALID_MAX_FEE_PER_KB",
+            Self::FeeRateAboveMaxFee => "ERR_UTXO_FEE_RATE_ABOVE_MAX_FEE",
+            Self::TvlCapExceeded => "ERR_TVL_CAP_EXCEEDED",
+            Self::ZeroOutputValue => "ERR_ZERO_OUTPUT_VALUE",
+            Self::InvalidScriptPubkey => "ERR_INVALID_SCRIPT_PUBKEY",
+            Self::ScriptPubkeyTooLarge => "ERR_SCRIPT_PUBKEY_TOO_LARGE",
+            Self::InvalidDestinationChain => "ERR_INVALID_DESTINATION_CHAIN",
+            Self::NotUtxoChainToken => "ERR_NOT_UTXO_CHAIN_TOKEN",
+            Self::ExtraMsgRequired => "ERR_EXTRA_MSG_REQUIRED",
+            Self::InvalidTransaction => "ERR_INVALID_TRANSACTION",
+            Self::TxidNotSupported => "ERR_TXID_NOT_SUPPORTED",
+            Self::InvalidAddress => "ERR_INVALID_ADDRESS",
+        }
+    }
+}
+
+impl fmt::Display for BridgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
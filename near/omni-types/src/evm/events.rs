@@ -2,7 +2,8 @@ use alloy::{primitives::Log, rlp::Decodable, sol, sol_types::SolEvent};
 
 use crate::{
     prover_result::{
-        DeployTokenMessage, FinTransferMessage, InitTransferMessage, LogMetadataMessage,
+        DeployTokenMessage, FinTransferMessage, GovernanceActionMessage, IncomingMessage,
+        InitTransferMessage, LogMetadataMessage, UnresolvedRecipientTransferMessage,
     },
     stringify, ChainKind, Fee, OmniAddress, H160,
 };
@@ -43,6 +44,18 @@ sol! {
         string symbol,
         uint8 decimals
     );
+
+    event GovernanceAction(
+        uint64 indexed nonce,
+        bytes action
+    );
+
+    event Message(
+        uint64 indexed originNonce,
+        address sender,
+        string recipient,
+        bytes32 payloadHash
+    );
 }
 
 #[allow(clippy::needless_pass_by_value)]
@@ -108,6 +121,29 @@ impl TryFromLog<Log<InitTransfer>> for InitTransferMessage {
     }
 }
 
+impl TryFromLog<Log<InitTransfer>> for UnresolvedRecipientTransferMessage {
+    type Error = String;
+
+    fn try_from_log(chain_kind: ChainKind, event: Log<InitTransfer>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            emitter_address: OmniAddress::new_from_evm_address(
+                chain_kind,
+                H160(event.address.into()),
+            )?,
+            origin_nonce: event.data.originNonce,
+            token: OmniAddress::new_from_evm_address(chain_kind, H160(event.tokenAddress.into()))?,
+            amount: near_sdk::json_types::U128(event.data.amount),
+            raw_recipient: event.data.recipient,
+            fee: Fee {
+                fee: near_sdk::json_types::U128(event.data.fee),
+                native_fee: near_sdk::json_types::U128(event.data.nativeTokenFee),
+            },
+            sender: OmniAddress::new_from_evm_address(chain_kind, H160(event.data.sender.into()))?,
+            msg: event.data.message,
+        })
+    }
+}
+
 impl TryFromLog<Log<DeployToken>> for DeployTokenMessage {
     type Error = String;
 
@@ -149,6 +185,41 @@ impl TryFromLog<Log<LogMetadata>> for LogMetadataMessage {
     }
 }
 
+impl TryFromLog<Log<GovernanceAction>> for GovernanceActionMessage {
+    type Error = String;
+
+    fn try_from_log(
+        chain_kind: ChainKind,
+        event: Log<GovernanceAction>,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            nonce: event.data.nonce,
+            action: event.data.action.to_vec(),
+            emitter_address: OmniAddress::new_from_evm_address(
+                chain_kind,
+                H160(event.address.into()),
+            )?,
+        })
+    }
+}
+
+impl TryFromLog<Log<Message>> for IncomingMessage {
+    type Error = String;
+
+    fn try_from_log(chain_kind: ChainKind, event: Log<Message>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            origin_nonce: event.data.originNonce,
+            sender: OmniAddress::new_from_evm_address(chain_kind, H160(event.data.sender.into()))?,
+            recipient: event.data.recipient.parse().map_err(stringify)?,
+            payload_hash: near_sdk::CryptoHash::from(event.data.payloadHash.0).into(),
+            emitter_address: OmniAddress::new_from_evm_address(
+                chain_kind,
+                H160(event.address.into()),
+            )?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloy::primitives::IntoLogData;
@@ -2,22 +2,29 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use core::fmt;
 use core::str::FromStr;
 use hex::FromHex;
-use near_sdk::json_types::U128;
+use near_sdk::json_types::{Base58CryptoHash, U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{near, AccountId};
-use num_enum::IntoPrimitive;
 use schemars::JsonSchema;
 use serde::de::Visitor;
 use sol_address::SolAddress;
 
 pub mod btc;
+pub mod canonical_json;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod errors;
 pub mod evm;
 pub mod locker_args;
 pub mod mpc_types;
 pub mod near_events;
 pub mod prover_args;
 pub mod prover_result;
+pub mod safe_amount;
 pub mod sol_address;
+#[cfg(feature = "test-vectors")]
+pub mod test_vectors;
+pub mod units;
 pub mod utils;
 
 #[cfg(test)]
@@ -126,21 +133,18 @@ impl Serialize for H160 {
     }
 }
 
-#[near(serializers = [borsh, json])]
-#[derive(
-    Debug,
-    Eq,
-    Clone,
-    Copy,
-    PartialEq,
-    PartialOrd,
-    Ord,
-    strum_macros::AsRefStr,
-    Default,
-    IntoPrimitive,
-    Hash,
-)]
-#[repr(u8)]
+/// Chain supported by the bridge.
+///
+/// The borsh encoding of a chain is its stable numeric id, see [`ChainKind::id`]. New chains must
+/// get a new id instead of being inserted in between, so stored state and signed payloads keep
+/// their meaning. Ids that are unknown to this version are decoded as [`ChainKind::Unknown`]
+/// and encoded back unchanged, so no state migration is needed when a chain is added.
+///
+/// Chains are compared by their id, so an `Unknown` holding the id of a known chain is the same
+/// chain. Decoding always yields the known variant.
+#[near(serializers = [json])]
+#[serde(from = "ChainKindJson")]
+#[derive(Debug, Clone, Copy, strum_macros::AsRefStr, Default)]
 pub enum ChainKind {
     #[default]
     #[serde(alias = "eth")]
@@ -161,24 +165,226 @@ pub enum ChainKind {
     Zcash,
     #[serde(alias = "pol")]
     Pol,
+    #[serde(alias = "ltc")]
+    Ltc,
+    #[serde(alias = "doge")]
+    Doge,
+    Unknown(u8),
+}
+
+// Mirror of `ChainKind` used to decode the JSON, so an `Unknown` holding a known id is
+// normalized like in the borsh decoding.
+#[near(serializers = [json])]
+enum ChainKindJson {
+    #[serde(alias = "eth")]
+    Eth,
+    #[serde(alias = "near")]
+    Near,
+    #[serde(alias = "sol")]
+    Sol,
+    #[serde(alias = "arb")]
+    Arb,
+    #[serde(alias = "base")]
+    Base,
+    #[serde(alias = "bnb")]
+    Bnb,
+    #[serde(alias = "btc")]
+    Btc,
+    #[serde(alias = "zcash")]
+    Zcash,
+    #[serde(alias = "pol")]
+    Pol,
+    #[serde(alias = "ltc")]
+    Ltc,
+    #[serde(alias = "doge")]
+    Doge,
+    Unknown(u8),
+}
+
+impl From<ChainKindJson> for ChainKind {
+    fn from(chain_kind: ChainKindJson) -> Self {
+        match chain_kind {
+            ChainKindJson::Eth => Self::Eth,
+            ChainKindJson::Near => Self::Near,
+            ChainKindJson::Sol => Self::Sol,
+            ChainKindJson::Arb => Self::Arb,
+            ChainKindJson::Base => Self::Base,
+            ChainKindJson::Bnb => Self::Bnb,
+            ChainKindJson::Btc => Self::Btc,
+            ChainKindJson::Zcash => Self::Zcash,
+            ChainKindJson::Pol => Self::Pol,
+            ChainKindJson::Ltc => Self::Ltc,
+            ChainKindJson::Doge => Self::Doge,
+            ChainKindJson::Unknown(id) => Self::from_id(id),
+        }
+    }
+}
+
+impl PartialEq for ChainKind {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl Eq for ChainKind {}
+
+impl PartialOrd for ChainKind {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ChainKind {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.id().cmp(&other.id())
+    }
+}
+
+impl core::hash::Hash for ChainKind {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+    }
 }
 
 impl ChainKind {
-    pub const fn is_evm_chain(&self) -> bool {
+    pub const fn id(&self) -> u8 {
         match self {
+            Self::Eth => 0,
+            Self::Near => 1,
+            Self::Sol => 2,
+            Self::Arb => 3,
+            Self::Base => 4,
+            Self::Bnb => 5,
+            Self::Btc => 6,
+            Self::Zcash => 7,
+            Self::Pol => 8,
+            Self::Ltc => 9,
+            Self::Doge => 10,
+            Self::Unknown(id) => *id,
+        }
+    }
+
+    pub const fn from_id(id: u8) -> Self {
+        match id {
+            0 => Self::Eth,
+            1 => Self::Near,
+            2 => Self::Sol,
+            3 => Self::Arb,
+            4 => Self::Base,
+            5 => Self::Bnb,
+            6 => Self::Btc,
+            7 => Self::Zcash,
+            8 => Self::Pol,
+            9 => Self::Ltc,
+            10 => Self::Doge,
+            _ => Self::Unknown(id),
+        }
+    }
+
+    /// Returns the known variant when an `Unknown` holds the id of a known chain.
+    pub const fn normalized(&self) -> Self {
+        Self::from_id(self.id())
+    }
+
+    pub const fn is_evm_chain(&self) -> bool {
+        match self.normalized() {
             Self::Eth | Self::Arb | Self::Base | Self::Bnb | Self::Pol => true,
-            Self::Btc | Self::Zcash | Self::Near | Self::Sol => false,
+            Self::Btc
+            | Self::Zcash
+            | Self::Ltc
+            | Self::Doge
+            | Self::Near
+            | Self::Sol
+            | Self::Unknown(_) => false,
         }
     }
 
     pub const fn is_utxo_chain(&self) -> bool {
-        match self {
-            Self::Btc | Self::Zcash => true,
-            Self::Eth | Self::Arb | Self::Base | Self::Bnb | Self::Pol | Self::Near | Self::Sol => {
-                false
-            }
+        match self.normalized() {
+            Self::Btc | Self::Zcash | Self::Ltc | Self::Doge => true,
+            Self::Eth
+            | Self::Arb
+            | Self::Base
+            | Self::Bnb
+            | Self::Pol
+            | Self::Near
+            | Self::Sol
+            | Self::Unknown(_) => false,
         }
     }
+
+    /// Traits the contract assumes for the chain when the DAO hasn't configured them.
+    pub const fn default_traits(&self) -> ChainTraits {
+        let address_kind = match self.normalized() {
+            Self::Near => AddressKind::Near,
+            Self::Eth | Self::Arb | Self::Base | Self::Bnb | Self::Pol => AddressKind::Evm,
+            Self::Sol => AddressKind::Solana,
+            Self::Btc | Self::Zcash | Self::Ltc | Self::Doge => AddressKind::Utxo,
+            Self::Unknown(_) => AddressKind::Unsupported,
+        };
+        let is_utxo = self.is_utxo_chain();
+        ChainTraits {
+            is_utxo,
+            supports_memo: !is_utxo,
+            fee_model: if is_utxo {
+                FeeModel::TokenOnly
+            } else {
+                FeeModel::TokenAndNative
+            },
+            address_kind,
+        }
+    }
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeModel {
+    /// The fee can be paid both in the transferred token and in the native token of the origin
+    /// chain.
+    TokenAndNative,
+    /// The fee is only paid in the transferred token.
+    TokenOnly,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    Near,
+    Evm,
+    Solana,
+    Utxo,
+    Unsupported,
+}
+
+/// Capabilities of a chain consulted by the chain-agnostic paths of the contract, so a new chain
+/// is mostly onboarded by configuring its traits.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainTraits {
+    /// Transfers to the chain are withdrawals through a UTXO connector.
+    pub is_utxo: bool,
+    /// The `msg` of a transfer is delivered to the recipient on the chain.
+    pub supports_memo: bool,
+    pub fee_model: FeeModel,
+    pub address_kind: AddressKind,
+}
+
+impl BorshSerialize for ChainKind {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.id(), writer)
+    }
+}
+
+impl BorshDeserialize for ChainKind {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(Self::from_id(u8::deserialize_reader(reader)?))
+    }
+}
+
+impl From<ChainKind> for u8 {
+    fn from(chain_kind: ChainKind) -> Self {
+        chain_kind.id()
+    }
 }
 
 impl FromStr for ChainKind {
@@ -198,17 +404,9 @@ impl From<&OmniAddress> for ChainKind {
 impl TryFrom<u8> for ChainKind {
     type Error = String;
     fn try_from(input: u8) -> Result<Self, String> {
-        match input {
-            0 => Ok(Self::Eth),
-            1 => Ok(Self::Near),
-            2 => Ok(Self::Sol),
-            3 => Ok(Self::Arb),
-            4 => Ok(Self::Base),
-            5 => Ok(Self::Bnb),
-            6 => Ok(Self::Btc),
-            7 => Ok(Self::Zcash),
-            8 => Ok(Self::Pol),
-            _ => Err(format!("{input:?} invalid chain kind")),
+        match Self::from_id(input) {
+            Self::Unknown(_) => Err(format!("{input:?} invalid chain kind")),
+            chain_kind => Ok(chain_kind),
         }
     }
 }
@@ -231,12 +429,14 @@ pub enum OmniAddress {
     Pol(EvmAddress),
     Btc(UTXOChainAddress),
     Zcash(UTXOChainAddress),
+    Ltc(UTXOChainAddress),
+    Doge(UTXOChainAddress),
 }
 
 impl OmniAddress {
     #[allow(clippy::missing_panics_doc)]
     pub fn new_zero(chain_kind: ChainKind) -> Result<Self, String> {
-        match chain_kind {
+        match chain_kind.normalized() {
             ChainKind::Eth => Ok(Self::Eth(H160::ZERO)),
             ChainKind::Near => Ok(Self::Near(ZERO_ACCOUNT_ID.parse().map_err(stringify)?)),
             ChainKind::Sol => Ok(Self::Sol(SolAddress::ZERO)),
@@ -246,6 +446,9 @@ impl OmniAddress {
             ChainKind::Pol => Ok(Self::Pol(H160::ZERO)),
             ChainKind::Btc => Ok(Self::Btc(String::new())),
             ChainKind::Zcash => Ok(Self::Zcash(String::new())),
+            ChainKind::Ltc => Ok(Self::Ltc(String::new())),
+            ChainKind::Doge => Ok(Self::Doge(String::new())),
+            ChainKind::Unknown(_) => Err(format!("{chain_kind:?} is not supported")),
         }
     }
 
@@ -253,7 +456,7 @@ impl OmniAddress {
         chain_kind: ChainKind,
         address: EvmAddress,
     ) -> Result<Self, String> {
-        match chain_kind {
+        match chain_kind.normalized() {
             ChainKind::Eth => Ok(Self::Eth(address)),
             ChainKind::Arb => Ok(Self::Arb(address)),
             ChainKind::Base => Ok(Self::Base(address)),
@@ -264,7 +467,7 @@ impl OmniAddress {
     }
 
     pub fn new_from_slice(chain_kind: ChainKind, address: &[u8]) -> Result<Self, String> {
-        match chain_kind {
+        match chain_kind.normalized() {
             ChainKind::Sol => Ok(Self::Sol(Self::to_sol_address(address)?)),
             ChainKind::Eth | ChainKind::Arb | ChainKind::Base | ChainKind::Bnb | ChainKind::Pol => {
                 Self::new_from_evm_address(chain_kind, Self::to_evm_address(address)?)
@@ -278,6 +481,15 @@ impl OmniAddress {
                 String::from_utf8(address.to_vec())
                     .map_err(|e| format!("Invalid ZCash address: {e}"))?,
             )),
+            ChainKind::Ltc => Ok(Self::Ltc(
+                String::from_utf8(address.to_vec())
+                    .map_err(|e| format!("Invalid LTC address: {e}"))?,
+            )),
+            ChainKind::Doge => Ok(Self::Doge(
+                String::from_utf8(address.to_vec())
+                    .map_err(|e| format!("Invalid DOGE address: {e}"))?,
+            )),
+            ChainKind::Unknown(_) => Err(format!("{chain_kind:?} is not supported")),
         }
     }
 
@@ -292,6 +504,8 @@ impl OmniAddress {
             Self::Pol(_) => ChainKind::Pol,
             Self::Btc(_) => ChainKind::Btc,
             Self::Zcash(_) => ChainKind::Zcash,
+            Self::Ltc(_) => ChainKind::Ltc,
+            Self::Doge(_) => ChainKind::Doge,
         }
     }
 
@@ -306,6 +520,8 @@ impl OmniAddress {
             Self::Pol(address) => ("pol", address.to_string()),
             Self::Btc(address) => ("btc", address.to_string()),
             Self::Zcash(address) => ("zcash", address.to_string()),
+            Self::Ltc(address) => ("ltc", address.to_string()),
+            Self::Doge(address) => ("doge", address.to_string()),
         };
 
         if skip_zero_address && self.is_zero() {
@@ -324,7 +540,10 @@ impl OmniAddress {
             | Self::Pol(address) => address.is_zero(),
             Self::Near(address) => *address == ZERO_ACCOUNT_ID,
             Self::Sol(address) => address.is_zero(),
-            Self::Btc(address) | Self::Zcash(address) => address.is_empty(),
+            Self::Btc(address)
+            | Self::Zcash(address)
+            | Self::Ltc(address)
+            | Self::Doge(address) => address.is_empty(),
         }
     }
 
@@ -360,12 +579,33 @@ impl OmniAddress {
         match self {
             Self::Btc(btc_address) => Some(btc_address.clone()),
             Self::Zcash(zcash_address) => Some(zcash_address.clone()),
+            Self::Ltc(ltc_address) => Some(ltc_address.clone()),
+            Self::Doge(doge_address) => Some(doge_address.clone()),
             _ => None,
         }
     }
 
     pub fn is_utxo_chain(&self) -> bool {
-        matches!(self, Self::Btc(_) | Self::Zcash(_))
+        matches!(
+            self,
+            Self::Btc(_) | Self::Zcash(_) | Self::Ltc(_) | Self::Doge(_)
+        )
+    }
+
+    /// Returns the NEAR implicit account that is controlled by the same key as this address.
+    ///
+    /// EVM addresses map to ETH-implicit accounts (`0x` followed by 40 hex characters) and
+    /// Solana addresses map to NEAR-implicit accounts (hex-encoded ed25519 public key).
+    pub fn get_implicit_near_account_id(&self) -> Option<AccountId> {
+        match self {
+            Self::Eth(address)
+            | Self::Arb(address)
+            | Self::Base(address)
+            | Self::Bnb(address)
+            | Self::Pol(address) => format!("0x{}", hex::encode(address.0)).parse().ok(),
+            Self::Sol(address) => hex::encode(address.0).parse().ok(),
+            Self::Near(_) | Self::Btc(_) | Self::Zcash(_) | Self::Ltc(_) | Self::Doge(_) => None,
+        }
     }
 
     fn to_evm_address(address: &[u8]) -> Result<EvmAddress, String> {
@@ -410,6 +650,8 @@ impl FromStr for OmniAddress {
             "pol" => Ok(Self::Pol(recipient.parse().map_err(stringify)?)),
             "btc" => Ok(Self::Btc(recipient.to_string())),
             "zcash" => Ok(Self::Zcash(recipient.to_string())),
+            "ltc" => Ok(Self::Ltc(recipient.to_string())),
+            "doge" => Ok(Self::Doge(recipient.to_string())),
             _ => Err(format!("Chain {chain} is not supported")),
         }
     }
@@ -476,6 +718,7 @@ pub enum BridgeOnTransferMsg {
     FastFinTransfer(FastFinTransferMsg),
     UtxoFinTransfer(UtxoFinTransferMsg),
     SwapMigratedToken,
+    FundFeeSponsor { recipient: OmniAddress },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -484,6 +727,58 @@ pub struct InitTransferMsg {
     pub fee: U128,
     pub native_token_fee: U128,
     pub msg: Option<String>,
+    // Set when the transfer is initiated on behalf of another account, e.g. by a router contract
+    #[serde(default)]
+    pub beneficiary: Option<TransferBeneficiary>,
+    // Set when the tokens are passed to a contract on the EVM destination chain
+    #[serde(default)]
+    pub destination_call: Option<EvmDestinationCall>,
+    // Set when only the given relayer may submit the transfer until its exclusivity window ends
+    #[serde(default)]
+    pub preferred_relayer: Option<AccountId>,
+    // Set when the transfer can be expired and refunded if it is not signed by this timestamp
+    #[serde(default)]
+    pub expires_at: Option<U64>,
+}
+
+/// Account on whose behalf a transfer was initiated. The beneficiary can cancel the transfer
+/// before it is signed with a signature of its key, and receives the refunded tokens.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferBeneficiary {
+    pub account_id: AccountId,
+    pub public_key: near_sdk::PublicKey,
+}
+
+/// Call of a contract on the EVM destination chain made with the transferred tokens once the
+/// transfer is finalised by `finTransferWithCall`. Only the keccak256 hash of the calldata is
+/// signed, the relayer submits the calldata itself, and `gas_limit` is covered by the native
+/// fee of the transfer. The tokens are sent to the recipient even if the call fails.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvmDestinationCall {
+    pub target: H160,
+    pub calldata_hash: Base58CryptoHash,
+    pub gas_limit: U64,
+}
+
+/// Payload signed by the beneficiary to authorize the cancellation of a transfer.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone)]
+pub struct TransferCancellationPayload {
+    pub prefix: PayloadType,
+    pub locker: AccountId,
+    pub transfer_id: TransferId,
+}
+
+/// Payload signed with the key of the origin address to claim a deposit whose recipient couldn't be parsed.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone)]
+pub struct UnclaimedDepositClaimPayload {
+    pub prefix: PayloadType,
+    pub locker: AccountId,
+    pub transfer_id: TransferId,
+    pub recipient: AccountId,
 }
 
 #[derive(Serialize, Deserialize, BorshSerialize, BorshDeserialize, Debug, Clone)]
@@ -504,6 +799,9 @@ pub struct UtxoFinTransferMsg {
     pub recipient: OmniAddress,
     pub relayer_fee: U128,
     pub msg: String,
+    // Addresses of the deposit inputs on the origin UTXO chain, as reported by the connector
+    #[serde(default)]
+    pub sender_addresses: Vec<UTXOChainAddress>,
 }
 
 impl UtxoFinTransferMsg {
@@ -526,6 +824,52 @@ impl Fee {
     pub const fn is_zero(&self) -> bool {
         self.fee.0 == 0 && self.native_fee.0 == 0
     }
+
+    /// Returns the part of `amount` that is left after the token fee is deducted.
+    pub const fn net_amount(&self, amount: U128) -> Result<U128, FeeExceedsAmount> {
+        match amount.0.checked_sub(self.fee.0) {
+            Some(net_amount) => Ok(U128(net_amount)),
+            None => Err(FeeExceedsAmount),
+        }
+    }
+}
+
+/// The token fee of a transfer is greater than its amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeExceedsAmount;
+
+impl FeeExceedsAmount {
+    pub const fn as_str(&self) -> &'static str {
+        "ERR_FEE_EXCEEDS_AMOUNT"
+    }
+}
+
+impl fmt::Display for FeeExceedsAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitStatus {
+    Forwarded,
+    Failed,
+}
+
+/// Result of the submission of a transfer to the connector of a UTXO chain, so the contracts
+/// calling the locker can branch on it.
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmitOutcome {
+    pub status: SubmitStatus,
+    /// Amount accepted by the connector.
+    pub forwarded_amount: U128,
+    /// Fee of the transfer paid to the relayer, or held until the connector reports it.
+    pub fee_paid: Fee,
+    /// The failed transfer is pending again and can be resubmitted. Otherwise it was moved
+    /// to the dead letters after too many restores.
+    pub restored: bool,
 }
 
 #[near(serializers = [borsh, json])]
@@ -537,6 +881,14 @@ pub struct TransferId {
     pub origin_nonce: Nonce,
 }
 
+impl TransferId {
+    /// Identifier binding the notices of the transfer to its claim on the destination chain.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn trace_id(&self) -> [u8; 32] {
+        utils::keccak256(&borsh::to_vec(self).unwrap())
+    }
+}
+
 #[near(serializers=[borsh, json])]
 #[derive(Debug, Clone)]
 pub struct TransferMessage {
@@ -549,6 +901,9 @@ pub struct TransferMessage {
     pub msg: String,
     pub destination_nonce: Nonce,
     pub origin_transfer_id: Option<UnifiedTransferId>,
+    // Timestamp in nanoseconds after which anyone can expire the unsigned transfer
+    #[serde(default)]
+    pub expires_at: Option<U64>,
 }
 
 impl TransferMessage {
@@ -570,6 +925,15 @@ impl TransferMessage {
     pub fn calculate_storage_account_id(&self) -> AccountId {
         TransferMessageStorageAccount::from(self.clone()).id()
     }
+
+    /// Returns the storage account of the message with the canonical form of its `msg`.
+    pub fn calculate_canonical_storage_account_id(&self) -> AccountId {
+        TransferMessageStorageAccount {
+            msg: canonical_json::canonical_msg(&self.msg),
+            ..self.clone().into()
+        }
+        .id()
+    }
 }
 
 // Used to calculate virtual account ID that can be used to deposit storage required for the message
@@ -612,6 +976,11 @@ pub enum PayloadType {
     TransferMessage,
     Metadata,
     ClaimNativeFee,
+    TransferCancellation,
+    UnclaimedDepositClaim,
+    IncomingTransferNotice,
+    TransferMessageWithCall,
+    Message,
 }
 
 #[near(serializers=[borsh, json])]
@@ -626,6 +995,59 @@ pub struct TransferMessagePayload {
     pub fee_recipient: Option<AccountId>,
 }
 
+/// Payload signed instead of `TransferMessagePayload` for the transfers with
+/// an `EvmDestinationCall`, so the payloads of the other transfers keep their encoding.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone)]
+pub struct TransferMessageWithCallPayload {
+    pub prefix: PayloadType,
+    pub destination_nonce: Nonce,
+    pub transfer_id: TransferId,
+    pub token_address: OmniAddress,
+    pub amount: U128,
+    pub recipient: OmniAddress,
+    pub fee_recipient: Option<AccountId>,
+    pub destination_call: EvmDestinationCall,
+}
+
+/// Gas of the delivery of a message on the destination chain, paid to the relayer there.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageGasParams {
+    pub gas_limit: U64,
+    /// Fee in the native token of the destination chain.
+    pub native_fee: U128,
+}
+
+/// Message sent from NEAR without moving tokens. Only the keccak256 hash of the payload is
+/// signed, the payload itself is delivered to the destination app by its relayer, through
+/// `receiveMessage` on the EVM chains.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone)]
+pub struct MessagePayload {
+    pub prefix: PayloadType,
+    pub nonce: Nonce,
+    pub sender: AccountId,
+    pub destination_chain: ChainKind,
+    pub payload_hash: Base58CryptoHash,
+    pub gas_params: MessageGasParams,
+}
+
+/// Non-authoritative notice of a transfer that will be claimable on the destination chain,
+/// so integrators can prepare for it. It can't be used to claim the transfer, which requires
+/// the signed `TransferMessagePayload`; `trace_id` is the hash of the transfer id of both.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone)]
+pub struct IncomingTransferNoticePayload {
+    pub prefix: PayloadType,
+    pub trace_id: [u8; 32],
+    pub destination_nonce: Nonce,
+    pub transfer_id: TransferId,
+    pub token_address: OmniAddress,
+    pub amount: U128,
+    pub recipient: OmniAddress,
+}
+
 #[near(serializers = [borsh, json])]
 #[derive(Debug, Clone)]
 pub struct MetadataPayload {
@@ -791,6 +1213,15 @@ impl FastTransfer {
     pub fn id(&self) -> FastTransferId {
         FastTransferId(utils::sha256(&borsh::to_vec(self).unwrap()))
     }
+
+    /// Returns the id of the transfer with the canonical form of its message.
+    pub fn canonical_id(&self) -> FastTransferId {
+        Self {
+            msg: canonical_json::canonical_msg(&self.msg),
+            ..self.clone()
+        }
+        .id()
+    }
 }
 
 impl FastTransfer {
@@ -25,6 +25,20 @@ pub struct ClaimFeeArgs {
     pub prover_args: Vec<u8>,
 }
 
+#[near(serializers = [borsh, json])]
+#[derive(Clone)]
+pub struct GovernanceActionArgs {
+    pub chain_kind: ChainKind,
+    pub prover_args: Vec<u8>,
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Clone)]
+pub struct ReceiveMessageArgs {
+    pub chain_kind: ChainKind,
+    pub prover_args: Vec<u8>,
+}
+
 #[near(serializers = [borsh, json])]
 #[derive(Clone)]
 pub struct BindTokenArgs {
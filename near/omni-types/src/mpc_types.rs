@@ -1,5 +1,7 @@
 use near_sdk::near;
 
+use crate::utils::keccak256;
+
 #[near(serializers = [json])]
 #[derive(Clone, Debug)]
 pub struct AffinePoint {
@@ -40,3 +42,38 @@ impl SignatureResponse {
         bytes
     }
 }
+
+/// Signature scheme expected by the verifier of the destination chain.
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SignatureScheme {
+    // Signature of the keccak256 hash of the payload
+    #[default]
+    Secp256k1Ecdsa,
+    Ed25519,
+    Secp256k1Schnorr,
+}
+
+/// Signature requested from the signer, independent of the interface of the signer.
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureRequest {
+    pub scheme: SignatureScheme,
+    pub payload: Vec<u8>,
+    pub path: String,
+}
+
+impl SignatureRequest {
+    /// Builds the request for the message, hashed as required by the scheme.
+    pub fn new(scheme: SignatureScheme, message: &[u8], path: String) -> Self {
+        let payload = match scheme {
+            SignatureScheme::Secp256k1Ecdsa => keccak256(message).to_vec(),
+            SignatureScheme::Ed25519 | SignatureScheme::Secp256k1Schnorr => message.to_vec(),
+        };
+        Self {
+            scheme,
+            payload,
+            path,
+        }
+    }
+}
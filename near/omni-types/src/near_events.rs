@@ -1,11 +1,13 @@
-use near_sdk::json_types::U128;
+use near_sdk::json_types::{Base58CryptoHash, U128};
 use near_sdk::serde_json::json;
-use near_sdk::{near, AccountId};
+use near_sdk::{near, AccountId, NearToken};
 
+use crate::btc::{OutPoint, TxOut};
 use crate::mpc_types::SignatureResponse;
 use crate::{
-    BasicMetadata, FastTransfer, MetadataPayload, OmniAddress, TransferId, TransferMessage,
-    TransferMessagePayload, UtxoFinTransferMsg,
+    BasicMetadata, ChainKind, FastTransfer, Fee, IncomingTransferNoticePayload, MessagePayload,
+    MetadataPayload, Nonce, OmniAddress, TransferId, TransferMessage, TransferMessagePayload,
+    TransferMessageWithCallPayload, UnifiedTransferId, UtxoFinTransferMsg,
 };
 
 #[near(serializers=[json])]
@@ -55,10 +57,271 @@ pub enum OmniBridgeEvent {
         utxo_transfer_message: UtxoFinTransferMsg,
         new_transfer_id: Option<TransferId>,
     },
+    DeadLetterTransferEvent {
+        transfer_message: TransferMessage,
+        restore_count: u32,
+    },
+    LowAvailableBalanceEvent {
+        available_balance: U128,
+        safety_margin: U128,
+    },
+    GovernanceActionEvent {
+        nonce: Nonce,
+        emitter_address: OmniAddress,
+    },
+    TransferLabelsEvent {
+        transfer_id: TransferId,
+        labels: Vec<String>,
+    },
+    CancelTransferEvent {
+        transfer_message: TransferMessage,
+        beneficiary: AccountId,
+    },
+    UnclaimedDepositEvent {
+        transfer_id: TransferId,
+        token_id: AccountId,
+        amount: U128,
+        sender: OmniAddress,
+        raw_recipient: String,
+    },
+    ClaimUnclaimedDepositEvent {
+        transfer_id: TransferId,
+        recipient: AccountId,
+    },
+    TransferOwnershipEvent {
+        transfer_id: TransferId,
+        old_owner: AccountId,
+        new_owner: AccountId,
+    },
+    ForceFinalizeTransferEvent {
+        transfer_message: TransferMessage,
+        justification_hash: Base58CryptoHash,
+        proposer: AccountId,
+        guardian: AccountId,
+    },
+    LegacyAddressSweepEvent {
+        chain_kind: ChainKind,
+        legacy_path: String,
+        outpoints: Vec<OutPoint>,
+        fee_rate: u64,
+        destination_script: Option<String>,
+    },
+    ConnectorCircuitBreakerEvent {
+        chain_kind: ChainKind,
+        connector: AccountId,
+        submissions: u32,
+        failures: u32,
+    },
+    ConnectorFailoverEvent {
+        chain_kind: ChainKind,
+        old_connector: AccountId,
+        new_connector: AccountId,
+        is_automatic: bool,
+    },
+    IncomingTransferNoticeEvent {
+        signature: SignatureResponse,
+        notice_payload: IncomingTransferNoticePayload,
+    },
+    EventSummaryEvent {
+        event_name: String,
+        block_height: u64,
+        count: u64,
+    },
+    UtxoRefundEvent {
+        transfer_id: UnifiedTransferId,
+        refund_address: OmniAddress,
+        new_transfer_id: TransferId,
+    },
+    TransferFlaggedEvent {
+        transfer_id: TransferId,
+        watchtower: AccountId,
+        reason_hash: Base58CryptoHash,
+        flags: u32,
+    },
+    TransferFlagsClearedEvent {
+        transfer_id: TransferId,
+        guardian: AccountId,
+        is_frivolous: bool,
+        slashed: NearToken,
+    },
+    ExpireTransferEvent {
+        transfer_message: TransferMessage,
+        refund_recipient: AccountId,
+    },
+    UtxoTransferSubmittedEvent {
+        transfer_id: TransferId,
+        chain_kind: ChainKind,
+        connector: AccountId,
+        amount: U128,
+        fee: Fee,
+    },
+    UtxoTransferForwardedEvent {
+        transfer_id: TransferId,
+        chain_kind: ChainKind,
+        amount: U128,
+        fee: Fee,
+    },
+    TransferRestoredEvent {
+        transfer_id: TransferId,
+        chain_kind: ChainKind,
+        amount: U128,
+        restore_count: u32,
+    },
+    SignTransferWithCallEvent {
+        signature: SignatureResponse,
+        message_payload: TransferMessageWithCallPayload,
+    },
+    SendMessageEvent {
+        signature: SignatureResponse,
+        message_payload: MessagePayload,
+    },
+    ReceiveMessageEvent {
+        origin_chain: ChainKind,
+        origin_nonce: Nonce,
+        sender: OmniAddress,
+        recipient: AccountId,
+        payload_hash: Base58CryptoHash,
+    },
+    FeeEscalatedEvent {
+        transfer_message: TransferMessage,
+        step: u32,
+        native_fee_added: U128,
+    },
+    UtxoConsolidatedEvent {
+        chain_kind: ChainKind,
+        inputs: Vec<OutPoint>,
+        output: TxOut,
+        fee: U128,
+    },
+    UtxoWithdrawalTxidEvent {
+        transfer_id: TransferId,
+        chain_kind: ChainKind,
+        txid: String,
+    },
+}
+
+/// Name and version of the payload of an event type. The version is bumped on every change of
+/// the payload shape: the minor version for added fields, the major version for breaking changes.
+#[near(serializers=[json])]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EventSchema {
+    pub name: String,
+    pub version: String,
 }
 
+pub const EVENT_SCHEMA_VERSIONS: &[(&str, &str)] = &[
+    ("InitTransferEvent", "1.0.0"),
+    ("SignTransferEvent", "1.0.0"),
+    ("FinTransferEvent", "1.0.0"),
+    ("FailedFinTransferEvent", "1.0.0"),
+    ("UpdateFeeEvent", "1.0.0"),
+    ("LogMetadataEvent", "1.0.0"),
+    ("ClaimFeeEvent", "1.0.0"),
+    ("DeployTokenEvent", "1.0.0"),
+    ("BindTokenEvent", "1.0.0"),
+    ("FastTransferEvent", "1.0.0"),
+    ("UtxoTransferEvent", "1.0.0"),
+    ("DeadLetterTransferEvent", "1.0.0"),
+    ("LowAvailableBalanceEvent", "1.0.0"),
+    ("GovernanceActionEvent", "1.0.0"),
+    ("TransferLabelsEvent", "1.0.0"),
+    ("CancelTransferEvent", "1.0.0"),
+    ("UnclaimedDepositEvent", "1.0.0"),
+    ("ClaimUnclaimedDepositEvent", "1.0.0"),
+    ("TransferOwnershipEvent", "1.0.0"),
+    ("ForceFinalizeTransferEvent", "1.0.0"),
+    ("LegacyAddressSweepEvent", "1.1.0"),
+    ("ConnectorCircuitBreakerEvent", "1.0.0"),
+    ("ConnectorFailoverEvent", "1.0.0"),
+    ("IncomingTransferNoticeEvent", "1.0.0"),
+    ("EventSummaryEvent", "1.0.0"),
+    ("UtxoRefundEvent", "1.0.0"),
+    ("TransferFlaggedEvent", "1.0.0"),
+    ("TransferFlagsClearedEvent", "1.0.0"),
+    ("ExpireTransferEvent", "1.0.0"),
+    ("UtxoTransferSubmittedEvent", "1.0.0"),
+    ("UtxoTransferForwardedEvent", "1.0.0"),
+    ("TransferRestoredEvent", "1.0.0"),
+    ("SignTransferWithCallEvent", "1.0.0"),
+    ("SendMessageEvent", "1.0.0"),
+    ("ReceiveMessageEvent", "1.0.0"),
+    ("FeeEscalatedEvent", "1.0.0"),
+    ("UtxoConsolidatedEvent", "1.0.0"),
+    ("UtxoWithdrawalTxidEvent", "1.0.0"),
+];
+
 impl OmniBridgeEvent {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::InitTransferEvent { .. } => "InitTransferEvent",
+            Self::SignTransferEvent { .. } => "SignTransferEvent",
+            Self::FinTransferEvent { .. } => "FinTransferEvent",
+            Self::FailedFinTransferEvent { .. } => "FailedFinTransferEvent",
+            Self::UpdateFeeEvent { .. } => "UpdateFeeEvent",
+            Self::LogMetadataEvent { .. } => "LogMetadataEvent",
+            Self::ClaimFeeEvent { .. } => "ClaimFeeEvent",
+            Self::DeployTokenEvent { .. } => "DeployTokenEvent",
+            Self::BindTokenEvent { .. } => "BindTokenEvent",
+            Self::FastTransferEvent { .. } => "FastTransferEvent",
+            Self::UtxoTransferEvent { .. } => "UtxoTransferEvent",
+            Self::DeadLetterTransferEvent { .. } => "DeadLetterTransferEvent",
+            Self::LowAvailableBalanceEvent { .. } => "LowAvailableBalanceEvent",
+            Self::GovernanceActionEvent { .. } => "GovernanceActionEvent",
+            Self::TransferLabelsEvent { .. } => "TransferLabelsEvent",
+            Self::CancelTransferEvent { .. } => "CancelTransferEvent",
+            Self::UnclaimedDepositEvent { .. } => "UnclaimedDepositEvent",
+            Self::ClaimUnclaimedDepositEvent { .. } => "ClaimUnclaimedDepositEvent",
+            Self::TransferOwnershipEvent { .. } => "TransferOwnershipEvent",
+            Self::ForceFinalizeTransferEvent { .. } => "ForceFinalizeTransferEvent",
+            Self::LegacyAddressSweepEvent { .. } => "LegacyAddressSweepEvent",
+            Self::ConnectorCircuitBreakerEvent { .. } => "ConnectorCircuitBreakerEvent",
+            Self::ConnectorFailoverEvent { .. } => "ConnectorFailoverEvent",
+            Self::IncomingTransferNoticeEvent { .. } => "IncomingTransferNoticeEvent",
+            Self::EventSummaryEvent { .. } => "EventSummaryEvent",
+            Self::UtxoRefundEvent { .. } => "UtxoRefundEvent",
+            Self::TransferFlaggedEvent { .. } => "TransferFlaggedEvent",
+            Self::TransferFlagsClearedEvent { .. } => "TransferFlagsClearedEvent",
+            Self::ExpireTransferEvent { .. } => "ExpireTransferEvent",
+            Self::UtxoTransferSubmittedEvent { .. } => "UtxoTransferSubmittedEvent",
+            Self::UtxoTransferForwardedEvent { .. } => "UtxoTransferForwardedEvent",
+            Self::TransferRestoredEvent { .. } => "TransferRestoredEvent",
+            Self::SignTransferWithCallEvent { .. } => "SignTransferWithCallEvent",
+            Self::SendMessageEvent { .. } => "SendMessageEvent",
+            Self::ReceiveMessageEvent { .. } => "ReceiveMessageEvent",
+            Self::FeeEscalatedEvent { .. } => "FeeEscalatedEvent",
+            Self::UtxoConsolidatedEvent { .. } => "UtxoConsolidatedEvent",
+            Self::UtxoWithdrawalTxidEvent { .. } => "UtxoWithdrawalTxidEvent",
+        }
+    }
+
+    pub fn version(&self) -> &'static str {
+        let name = self.name();
+        EVENT_SCHEMA_VERSIONS
+            .iter()
+            .find(|(schema_name, _)| *schema_name == name)
+            .map_or("1.0.0", |(_, version)| version)
+    }
+
+    pub fn schemas() -> Vec<EventSchema> {
+        EVENT_SCHEMA_VERSIONS
+            .iter()
+            .map(|(name, version)| EventSchema {
+                name: (*name).to_string(),
+                version: (*version).to_string(),
+            })
+            .collect()
+    }
+
+    // The version is added to the payload of the event, so the parsers of the previous
+    // unversioned payloads keep working.
     pub fn to_log_string(&self) -> String {
-        json!(self).to_string()
+        let mut event = json!(self);
+        if let Some(payload) = event
+            .get_mut(self.name())
+            .and_then(|payload| payload.as_object_mut())
+        {
+            payload.insert("version".to_string(), json!(self.version()));
+        }
+        event.to_string()
     }
 }
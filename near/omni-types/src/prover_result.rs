@@ -1,4 +1,4 @@
-use near_sdk::json_types::U128;
+use near_sdk::json_types::{Base58CryptoHash, U128};
 use near_sdk::{near, AccountId};
 use num_enum::IntoPrimitive;
 
@@ -46,6 +46,60 @@ pub struct LogMetadataMessage {
     pub emitter_address: OmniAddress,
 }
 
+/// Init transfer whose recipient couldn't be parsed. The locker holds the tokens until
+/// the sender proves the control of its origin address.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone)]
+pub struct UnresolvedRecipientTransferMessage {
+    pub origin_nonce: Nonce,
+    pub token: OmniAddress,
+    pub amount: U128,
+    pub raw_recipient: String,
+    pub fee: Fee,
+    pub sender: OmniAddress,
+    pub msg: String,
+    pub emitter_address: OmniAddress,
+}
+
+impl UnresolvedRecipientTransferMessage {
+    /// Returns the init transfer when the recipient can be parsed. Otherwise the message is
+    /// given back, so the locker holds the tokens for the sender.
+    pub fn try_resolve(self) -> Result<InitTransferMessage, Self> {
+        let Ok(recipient) = self.raw_recipient.parse() else {
+            return Err(self);
+        };
+        Ok(InitTransferMessage {
+            origin_nonce: self.origin_nonce,
+            token: self.token,
+            amount: self.amount,
+            recipient,
+            fee: self.fee,
+            sender: self.sender,
+            msg: self.msg,
+            emitter_address: self.emitter_address,
+        })
+    }
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone)]
+pub struct GovernanceActionMessage {
+    pub nonce: Nonce,
+    pub action: Vec<u8>,
+    pub emitter_address: OmniAddress,
+}
+
+/// Message sent to a NEAR app from another chain, see `MessagePayload` for the other direction.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone)]
+pub struct IncomingMessage {
+    pub origin_nonce: Nonce,
+    pub sender: OmniAddress,
+    pub recipient: AccountId,
+    pub payload_hash: Base58CryptoHash,
+    pub emitter_address: OmniAddress,
+}
+
 #[near(serializers=[borsh, json])]
 #[derive(Debug, Clone)]
 pub enum ProverResult {
@@ -53,6 +107,9 @@ pub enum ProverResult {
     FinTransfer(FinTransferMessage),
     DeployToken(DeployTokenMessage),
     LogMetadata(LogMetadataMessage),
+    GovernanceAction(GovernanceActionMessage),
+    UnresolvedRecipientTransfer(UnresolvedRecipientTransferMessage),
+    Message(IncomingMessage),
 }
 
 #[near(serializers=[borsh, json])]
@@ -63,4 +120,6 @@ pub enum ProofKind {
     FinTransfer,
     DeployToken,
     LogMetadata,
+    GovernanceAction,
+    Message,
 }
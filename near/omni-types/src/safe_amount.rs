@@ -0,0 +1,94 @@
+use core::fmt;
+
+use ethereum_types::U256;
+use near_sdk::json_types::U128;
+use near_sdk::near;
+
+/// Token amount whose arithmetic fails instead of wrapping or saturating.
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SafeAmount(pub U128);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmountError {
+    Overflow,
+    Underflow,
+}
+
+impl AmountError {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Overflow => "ERR_AMOUNT_OVERFLOW",
+            Self::Underflow => "ERR_AMOUNT_UNDERFLOW",
+        }
+    }
+}
+
+impl fmt::Display for AmountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl SafeAmount {
+    pub const ZERO: Self = Self(U128(0));
+
+    pub const fn new(amount: u128) -> Self {
+        Self(U128(amount))
+    }
+
+    pub const fn get(self) -> u128 {
+        self.0 .0
+    }
+
+    pub const fn checked_add(self, other: Self) -> Result<Self, AmountError> {
+        match self.get().checked_add(other.get()) {
+            Some(amount) => Ok(Self::new(amount)),
+            None => Err(AmountError::Overflow),
+        }
+    }
+
+    pub const fn checked_sub(self, other: Self) -> Result<Self, AmountError> {
+        match self.get().checked_sub(other.get()) {
+            Some(amount) => Ok(Self::new(amount)),
+            None => Err(AmountError::Underflow),
+        }
+    }
+
+    pub const fn checked_mul(self, factor: u128) -> Result<Self, AmountError> {
+        match self.get().checked_mul(factor) {
+            Some(amount) => Ok(Self::new(amount)),
+            None => Err(AmountError::Overflow),
+        }
+    }
+
+    /// Computes `self * numerator / denominator`, rounded down, with a 256-bit intermediate
+    /// product. Fails if the result doesn't fit, or if `denominator` is zero.
+    pub fn checked_mul_div(self, numerator: u128, denominator: u128) -> Result<Self, AmountError> {
+        if denominator == 0 {
+            return Err(AmountError::Overflow);
+        }
+        let amount = U256::from(self.get()) * U256::from(numerator) / U256::from(denominator);
+        u128::try_from(amount)
+            .map(Self::new)
+            .map_err(|_| AmountError::Overflow)
+    }
+}
+
+impl From<U128> for SafeAmount {
+    fn from(amount: U128) -> Self {
+        Self(amount)
+    }
+}
+
+impl From<u128> for SafeAmount {
+    fn from(amount: u128) -> Self {
+        Self::new(amount)
+    }
+}
+
+impl From<SafeAmount> for U128 {
+    fn from(amount: SafeAmount) -> Self {
+        amount.0
+    }
+}
@@ -0,0 +1,68 @@
+//! Canonical `Withdraw` messages of the UTXO connectors, shared by the tests of the locker and
+//! the test suites of the connectors so the encodings of the two sides can't drift apart.
+//! Changing a vector is a breaking change of the messages of the connector of its chain.
+
+use crate::ChainKind;
+
+pub struct WithdrawMsgVector {
+    pub chain_kind: ChainKind,
+    /// `msg` of the `ft_transfer_call` to the connector, in the encoding of `serde_json`.
+    pub msg: &'static str,
+    /// Hex of the sha256 of `msg`.
+    pub msg_hash: &'static str,
+    pub target_address: &'static str,
+    /// Outpoints in the `txid:vout` form.
+    pub input: &'static [&'static str],
+    /// Value and script pubkey of the outputs.
+    pub output: &'static [(u64, &'static str)],
+    /// Fee bound in the unit of the fee model of the chain.
+    pub max_fee: u128,
+}
+
+pub const WITHDRAW_MSG_VECTORS: &[WithdrawMsgVector] = &[
+    WithdrawMsgVector {
+        chain_kind: ChainKind::Btc,
+        msg: r#"{"Withdraw":{"target_btc_address":"bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq","input":["abababababababababababababababababababababababababababababababab:0"],"output":[{"value":90000,"script_pubkey":"0014e8df018c7e326cc253faac7e46cdc51e68542c42"},{"value":9000,"script_pubkey":"00141111111111111111111111111111111111111111"}],"max_gas_fee":"1000"}}"#,
+        msg_hash: "55e34a94b5b5e8be77e1e34c3380cf7e3af865e3845a8b433fdbc7eb728cb7d9",
+        target_address: "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+        input: &["abababababababababababababababababababababababababababababababab:0"],
+        output: &[
+            (90000, "0014e8df018c7e326cc253faac7e46cdc51e68542c42"),
+            (9000, "00141111111111111111111111111111111111111111"),
+        ],
+        max_fee: 1000,
+    },
+    WithdrawMsgVector {
+        chain_kind: ChainKind::Zcash,
+        msg: r#"{"Withdraw":{"target_btc_address":"t1Hsc1LR8yKnbbe3twRp88p6vFfC5t7DLbs","input":["abababababababababababababababababababababababababababababababab:1","0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f:3"],"output":[{"value":50000,"script_pubkey":"76a9145e6e3b5dbd5b0e1c6d7a4f3c2b1a0918273645aa88ac"}],"max_gas_fee":"10000"}}"#,
+        msg_hash: "3ecb12a045a5a6516ef041211ee96f0527669cc25f4fba528a29a69c367ece14",
+        target_address: "t1Hsc1LR8yKnbbe3twRp88p6vFfC5t7DLbs",
+        input: &[
+            "abababababababababababababababababababababababababababababababab:1",
+            "0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f:3",
+        ],
+        output: &[(50000, "76a9145e6e3b5dbd5b0e1c6d7a4f3c2b1a0918273645aa88ac")],
+        max_fee: 10000,
+    },
+    WithdrawMsgVector {
+        chain_kind: ChainKind::Ltc,
+        msg: r#"{"Withdraw":{"target_ltc_address":"ltc1qg82tjmz2fd6x7mhhvtwd3hrmhzqgrnxsz0h4mq","input":["0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f:0"],"output":[{"value":1000,"script_pubkey":"00142222222222222222222222222222222222222222"}],"max_fee_rate":"20"}}"#,
+        msg_hash: "e0d152b8dd2d6eacd575ed14c2ac4b6dfa911d0f330bc6767eb5b68cd6370a22",
+        target_address: "ltc1qg82tjmz2fd6x7mhhvtwd3hrmhzqgrnxsz0h4mq",
+        input: &["0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f:0"],
+        output: &[(1000, "00142222222222222222222222222222222222222222")],
+        max_fee: 20,
+    },
+    WithdrawMsgVector {
+        chain_kind: ChainKind::Doge,
+        msg: r#"{"Withdraw":{"target_doge_address":"DH5yaieqoZN36fDVciNyRueRGvGLR3mr7L","input":["0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f:2"],"output":[{"value":100000000,"script_pubkey":"76a91412343b5dbd5b0e1c6d7a4f3c2b1a0918273645aa88ac"}],"max_fee_per_kb":"1000000"}}"#,
+        msg_hash: "745a7c04394f123316304b90bf78c86cf2f3e33777ef007605efcde8dec58bba",
+        target_address: "DH5yaieqoZN36fDVciNyRueRGvGLR3mr7L",
+        input: &["0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f:2"],
+        output: &[(
+            100000000,
+            "76a91412343b5dbd5b0e1c6d7a4f3c2b1a0918273645aa88ac",
+        )],
+        max_fee: 1000000,
+    },
+];
@@ -1,9 +1,20 @@
 use near_sdk::borsh;
 use near_sdk::json_types::U128;
 use near_sdk::serde_json;
+use near_sdk::AccountId;
 
+use crate::canonical_json::{canonical_msg, canonicalize_json};
+use crate::mpc_types::{SignatureRequest, SignatureScheme};
+use crate::near_events::OmniBridgeEvent;
+use crate::safe_amount::{AmountError, SafeAmount};
+use crate::units::{
+    format_amount, parse_amount, scale_amount, units_per_token, UnitsError, KOINU_PER_DOGE,
+    NEAR_DECIMALS, SATS_PER_BTC, UTXO_DECIMALS, YOCTO_PER_NEAR,
+};
 use crate::{
-    stringify, ChainKind, Fee, OmniAddress, PayloadType, TransferId, TransferMessage, H160,
+    stringify, utils::keccak256, AddressKind, ChainKind, ChainTraits, FastTransfer, Fee,
+    FeeExceedsAmount, FeeModel, OmniAddress, PayloadType, TransferId, TransferIdKind,
+    TransferMessage, UnifiedTransferId, H160,
 };
 use std::str::FromStr;
 
@@ -27,6 +38,10 @@ fn test_payload_prefix() {
     assert_eq!(hex::encode(res), "01");
     let res = borsh::to_vec(&PayloadType::ClaimNativeFee).unwrap();
     assert_eq!(hex::encode(res), "02");
+    let res = borsh::to_vec(&PayloadType::TransferCancellation).unwrap();
+    assert_eq!(hex::encode(res), "03");
+    let res = borsh::to_vec(&PayloadType::UnclaimedDepositClaim).unwrap();
+    assert_eq!(hex::encode(res), "04");
 }
 
 #[test]
@@ -190,6 +205,40 @@ fn test_omni_address_from_evm_address() {
     }
 }
 
+#[test]
+fn test_implicit_near_account_id() {
+    let evm_address =
+        H160::from_str("0x5A08FeED678C056650b3eb4a5cb1b9BB6F0fE265").expect("Valid address");
+    let expected: AccountId = "0x5a08feed678c056650b3eb4a5cb1b9bb6f0fe265"
+        .parse()
+        .unwrap();
+    for address in [
+        OmniAddress::Eth(evm_address.clone()),
+        OmniAddress::Base(evm_address.clone()),
+    ] {
+        assert_eq!(
+            address.get_implicit_near_account_id(),
+            Some(expected.clone())
+        );
+    }
+
+    let sol_address = OmniAddress::Sol("11111111111111111111111111111111".parse().unwrap());
+    assert_eq!(
+        sol_address.get_implicit_near_account_id(),
+        Some("0".repeat(64).parse().unwrap())
+    );
+
+    assert_eq!(
+        OmniAddress::Btc("bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh".to_string())
+            .get_implicit_near_account_id(),
+        None
+    );
+    assert_eq!(
+        OmniAddress::Near("alice.near".parse().unwrap()).get_implicit_near_account_id(),
+        None
+    );
+}
+
 #[test]
 fn test_omni_address_from_str() {
     let evm_addr = "0x5a08feed678c056650b3eb4a5cb1b9bb6f0fe265";
@@ -346,6 +395,7 @@ fn test_transfer_message_getters() {
                 sender: OmniAddress::Eth(evm_addr.clone()),
                 msg: String::new(),
                 origin_transfer_id: None,
+                expires_at: None,
             },
             ChainKind::Eth,
             TransferId {
@@ -365,6 +415,7 @@ fn test_transfer_message_getters() {
                 sender: OmniAddress::Near("alice.near".parse().unwrap()),
                 msg: String::new(),
                 origin_transfer_id: None,
+                expires_at: None,
             },
             ChainKind::Near,
             TransferId {
@@ -384,6 +435,7 @@ fn test_transfer_message_getters() {
                 sender: OmniAddress::Sol("11111111111111111111111111111111".parse().unwrap()),
                 msg: String::new(),
                 origin_transfer_id: None,
+                expires_at: None,
             },
             ChainKind::Sol,
             TransferId {
@@ -469,3 +521,748 @@ fn test_chain_kind_from_str() {
     let chain: ChainKind = "Base".parse().unwrap();
     assert_eq!(chain, ChainKind::Base);
 }
+
+#[test]
+fn test_chain_kind_borsh_ids_are_stable() {
+    let chains = [
+        (ChainKind::Eth, 0),
+        (ChainKind::Near, 1),
+        (ChainKind::Sol, 2),
+        (ChainKind::Arb, 3),
+        (ChainKind::Base, 4),
+        (ChainKind::Bnb, 5),
+        (ChainKind::Btc, 6),
+        (ChainKind::Zcash, 7),
+        (ChainKind::Pol, 8),
+        (ChainKind::Ltc, 9),
+        (ChainKind::Doge, 10),
+    ];
+
+    for (chain_kind, id) in chains {
+        assert_eq!(borsh::to_vec(&chain_kind).unwrap(), vec![id]);
+        assert_eq!(u8::from(chain_kind), id);
+        assert_eq!(ChainKind::try_from(id), Ok(chain_kind));
+    }
+}
+
+#[test]
+fn test_chain_kind_default_traits() {
+    for id in 0..=u8::MAX {
+        let chain_kind = ChainKind::from_id(id);
+        let traits = chain_kind.default_traits();
+        assert_eq!(traits.is_utxo, chain_kind.is_utxo_chain());
+        assert_eq!(
+            traits.address_kind == AddressKind::Evm,
+            chain_kind.is_evm_chain()
+        );
+    }
+
+    assert_eq!(
+        ChainKind::Btc.default_traits(),
+        ChainTraits {
+            is_utxo: true,
+            supports_memo: false,
+            fee_model: FeeModel::TokenOnly,
+            address_kind: AddressKind::Utxo,
+        }
+    );
+    assert_eq!(
+        ChainKind::Sol.default_traits().fee_model,
+        FeeModel::TokenAndNative
+    );
+    assert_eq!(
+        ChainKind::Unknown(42).default_traits().address_kind,
+        AddressKind::Unsupported
+    );
+}
+
+#[test]
+fn test_chain_kind_borsh_round_trip() {
+    for id in 0..=u8::MAX {
+        let chain_kind: ChainKind = borsh::from_slice(&[id]).unwrap();
+        assert_eq!(chain_kind, ChainKind::from_id(id));
+        assert_eq!(chain_kind.id(), id);
+        assert_eq!(borsh::to_vec(&chain_kind).unwrap(), vec![id]);
+
+        if matches!(chain_kind, ChainKind::Unknown(_)) {
+            assert!(ChainKind::try_from(id).is_err());
+        } else {
+            let serialized = serde_json::to_string(&chain_kind).unwrap();
+            assert_eq!(
+                serde_json::from_str::<ChainKind>(&serialized).unwrap(),
+                chain_kind
+            );
+        }
+    }
+}
+
+#[test]
+fn test_unknown_chain_kind_with_known_id() {
+    assert_eq!(ChainKind::Unknown(3), ChainKind::Arb);
+    assert_eq!(ChainKind::Unknown(3).normalized(), ChainKind::Arb);
+    assert!(ChainKind::Unknown(3).is_evm_chain());
+    assert_eq!(ChainKind::Unknown(6).native_decimals(), Some(8));
+    assert_ne!(ChainKind::Unknown(42), ChainKind::Arb);
+
+    let mut chains = std::collections::HashSet::new();
+    chains.insert(ChainKind::Arb);
+    assert!(chains.contains(&ChainKind::Unknown(3)));
+
+    let chain_kind: ChainKind = serde_json::from_str(r#"{"Unknown":3}"#).unwrap();
+    assert!(matches!(chain_kind, ChainKind::Arb));
+    let chain_kind: ChainKind = serde_json::from_str("\"arb\"").unwrap();
+    assert!(matches!(chain_kind, ChainKind::Arb));
+    let chain_kind: ChainKind = serde_json::from_str(r#"{"Unknown":42}"#).unwrap();
+    assert!(matches!(chain_kind, ChainKind::Unknown(42)));
+}
+
+#[test]
+fn test_unknown_chain_kind_in_stored_transfer_id() {
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Unknown(42),
+        origin_nonce: 7,
+    };
+
+    let serialized = borsh::to_vec(&transfer_id).unwrap();
+    assert_eq!(serialized[0], 42);
+    assert_eq!(
+        borsh::from_slice::<TransferId>(&serialized).unwrap(),
+        transfer_id
+    );
+}
+
+#[test]
+fn test_event_log_contains_version() {
+    let event = OmniBridgeEvent::TransferLabelsEvent {
+        transfer_id: TransferId {
+            origin_chain: ChainKind::Near,
+            origin_nonce: 1,
+        },
+        labels: vec!["batch".to_string()],
+    };
+    let log: serde_json::Value = serde_json::from_str(&event.to_log_string()).unwrap();
+    assert_eq!(log["TransferLabelsEvent"]["version"], "1.0.0");
+
+    // The versioned payload can still be parsed as the event
+    let parsed: OmniBridgeEvent = serde_json::from_value(log).unwrap();
+    assert_eq!(parsed.name(), "TransferLabelsEvent");
+
+    assert!(OmniBridgeEvent::schemas()
+        .iter()
+        .any(|schema| schema.name == event.name()));
+}
+
+#[test]
+fn test_signature_request_payload_per_scheme() {
+    let message = b"transfer payload";
+
+    let request = SignatureRequest::new(
+        SignatureScheme::Secp256k1Ecdsa,
+        message,
+        "bridge-1".to_string(),
+    );
+    assert_eq!(request.payload, keccak256(message).to_vec());
+
+    let request = SignatureRequest::new(SignatureScheme::Ed25519, message, "bridge-1".to_string());
+    assert_eq!(request.payload, message.to_vec());
+}
+
+#[test]
+fn test_fee_net_amount() {
+    let fee = Fee {
+        fee: U128(10),
+        native_fee: U128(0),
+    };
+    assert_eq!(fee.net_amount(U128(15)), Ok(U128(5)));
+    assert_eq!(fee.net_amount(U128(10)), Ok(U128(0)));
+    assert_eq!(fee.net_amount(U128(9)), Err(FeeExceedsAmount));
+    assert_eq!(FeeExceedsAmount.to_string(), "ERR_FEE_EXCEEDS_AMOUNT");
+}
+
+#[test]
+fn test_safe_amount_boundaries() {
+    let max = SafeAmount::new(u128::MAX);
+    let one = SafeAmount::new(1);
+
+    assert_eq!(max.checked_add(SafeAmount::ZERO), Ok(max));
+    assert_eq!(max.checked_add(one), Err(AmountError::Overflow));
+    assert_eq!(max.checked_sub(max), Ok(SafeAmount::ZERO));
+    assert_eq!(
+        SafeAmount::ZERO.checked_sub(one),
+        Err(AmountError::Underflow)
+    );
+    assert_eq!(max.checked_mul(1), Ok(max));
+    assert_eq!(max.checked_mul(2), Err(AmountError::Overflow));
+    assert_eq!(
+        SafeAmount::ZERO.checked_mul(u128::MAX),
+        Ok(SafeAmount::ZERO)
+    );
+    assert_eq!(AmountError::Overflow.to_string(), "ERR_AMOUNT_OVERFLOW");
+}
+
+#[test]
+fn test_safe_amount_mul_div() {
+    let max = SafeAmount::new(u128::MAX);
+
+    assert_eq!(
+        max.checked_mul_div(9_999, 10_000),
+        Ok(SafeAmount::new(
+            u128::MAX / 10_000 * 9_999 + u128::MAX % 10_000 * 9_999 / 10_000
+        ))
+    );
+    assert_eq!(max.checked_mul_div(3, 3), Ok(max));
+    assert_eq!(max.checked_mul_div(2, 1), Err(AmountError::Overflow));
+    assert_eq!(max.checked_mul_div(1, 0), Err(AmountError::Overflow));
+    assert_eq!(
+        SafeAmount::new(7).checked_mul_div(1, 2),
+        Ok(SafeAmount::new(3))
+    );
+    // The product of the remainder and the numerator doesn't fit in 128 bits
+    let scale = 10u128.pow(20);
+    assert_eq!(
+        SafeAmount::new(2 * scale - 1).checked_mul_div(scale, scale),
+        Ok(SafeAmount::new(2 * scale - 1))
+    );
+}
+
+#[test]
+fn test_safe_amount_serialization() {
+    let amount = SafeAmount::new(u128::MAX);
+
+    let serialized = serde_json::to_string(&amount).unwrap();
+    assert_eq!(serialized, format!("\"{}\"", u128::MAX));
+    assert_eq!(
+        serde_json::from_str::<SafeAmount>(&serialized).unwrap(),
+        amount
+    );
+    assert_eq!(
+        borsh::to_vec(&amount).unwrap(),
+        borsh::to_vec(&U128(u128::MAX)).unwrap()
+    );
+}
+
+#[test]
+fn test_canonicalize_json() {
+    assert_eq!(
+        canonicalize_json(r#" { "b": [1, {"d": null, "c": "x"}], "a": "\u0041" } "#),
+        Some(r#"{"a":"A","b":[1,{"c":"x","d":null}]}"#.to_string())
+    );
+    assert_eq!(
+        canonicalize_json(r#"{"a":1,"b":2}"#),
+        canonicalize_json(r#"{"b": 2, "a": 1}"#)
+    );
+    // Plain text and JSON scalars are kept as they are
+    assert_eq!(canonicalize_json("memo"), None);
+    assert_eq!(canonicalize_json("42"), None);
+    assert_eq!(canonical_msg("memo"), "memo");
+    assert_eq!(canonical_msg(""), "");
+    // Numbers keep their literal text, even beyond 64 bits
+    assert_eq!(
+        canonicalize_json(r#"{ "amount": 340282366920938463463374607431768211455, "rate": 1.50 }"#),
+        Some(r#"{"amount":340282366920938463463374607431768211455,"rate":1.50}"#.to_string())
+    );
+    assert_eq!(
+        canonicalize_json(r#"{"a":1,"a":2}"#),
+        Some(r#"{"a":2}"#.to_string())
+    );
+    assert_eq!(canonicalize_json(r#"{"a":01}"#), None);
+    assert_eq!(canonicalize_json(r#"{"a":1} x"#), None);
+}
+
+#[test]
+fn test_canonical_storage_account_id() {
+    let mut transfer_message = TransferMessage {
+        origin_nonce: 1,
+        token: OmniAddress::Near("token.near".parse().unwrap()),
+        amount: U128(100),
+        recipient: OmniAddress::Near("recipient.near".parse().unwrap()),
+        fee: Fee::default(),
+        sender: OmniAddress::Near("sender.near".parse().unwrap()),
+        msg: r#"{"b": 2, "a": 1}"#.to_string(),
+        destination_nonce: 1,
+        origin_transfer_id: None,
+        expires_at: None,
+    };
+    let canonical_id = transfer_message.calculate_canonical_storage_account_id();
+    assert_ne!(
+        transfer_message.calculate_storage_account_id(),
+        canonical_id
+    );
+
+    transfer_message.msg = r#"{"a":1,"b":2}"#.to_string();
+    assert_eq!(
+        transfer_message.calculate_storage_account_id(),
+        canonical_id
+    );
+    assert_eq!(
+        transfer_message.calculate_canonical_storage_account_id(),
+        canonical_id
+    );
+}
+
+#[test]
+fn test_fast_transfer_canonical_id() {
+    let fast_transfer = |msg: &str| FastTransfer {
+        transfer_id: UnifiedTransferId {
+            origin_chain: ChainKind::Eth,
+            kind: TransferIdKind::Nonce(1),
+        },
+        token_id: "token.near".parse().unwrap(),
+        amount: U128(100),
+        fee: Fee::default(),
+        recipient: OmniAddress::Near("alice.near".parse().unwrap()),
+        msg: msg.to_string(),
+    };
+
+    let relayer_transfer = fast_transfer(r#"{"receiver_id": "bob.near", "amount": "1"}"#);
+    let finalized_transfer = fast_transfer(r#"{"amount":"1","receiver_id":"bob.near"}"#);
+    assert_ne!(relayer_transfer.id().0, finalized_transfer.id().0);
+    assert_eq!(
+        relayer_transfer.canonical_id().0,
+        finalized_transfer.canonical_id().0
+    );
+    assert_eq!(
+        finalized_transfer.canonical_id().0,
+        finalized_transfer.id().0
+    );
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn test_client_method_args() {
+    use crate::client::{methods, ContractMethod, SignTransferArgs, ViewMethod};
+    use crate::locker_args::ClaimFeeArgs;
+
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: 1,
+    };
+    let args = SignTransferArgs {
+        transfer_id,
+        fee_recipient: Some("relayer.near".parse().unwrap()),
+        fee: None,
+    };
+    assert_eq!(SignTransferArgs::NAME, methods::SIGN_TRANSFER);
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&args.to_args().unwrap()).unwrap(),
+        serde_json::json!({
+            "transfer_id": { "origin_chain": "Near", "origin_nonce": 1 },
+            "fee_recipient": "relayer.near",
+            "fee": null,
+        })
+    );
+
+    let args = ClaimFeeArgs {
+        chain_kind: ChainKind::Eth,
+        prover_args: vec![1, 2, 3],
+    };
+    assert_eq!(args.to_args().unwrap(), borsh::to_vec(&args).unwrap());
+
+    let response: <crate::client::IsTransferFinalisedArgs as ViewMethod>::Response =
+        serde_json::from_str("true").unwrap();
+    assert!(response);
+}
+
+#[test]
+fn test_utxo_address_format() {
+    use crate::btc::{AddressEncoding, ScriptKind, UtxoAddressFormat};
+
+    let p2sh = UtxoAddressFormat {
+        encoding: AddressEncoding::Base58,
+        prefix: "3".to_string(),
+        min_len: 34,
+        max_len: 34,
+        script_kind: ScriptKind::P2sh,
+    };
+    assert!(p2sh.matches("3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy"));
+    assert!(!p2sh.matches("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2"));
+    // `0` is not in the base58 alphabet
+    assert!(!p2sh.matches("3J98t1WpEZ73CNmQviecrnyiWrnqRhWNL0"));
+    assert!(p2sh.is_script_hash());
+
+    let p2wpkh = UtxoAddressFormat {
+        encoding: AddressEncoding::Bech32,
+        prefix: "bc1q".to_string(),
+        min_len: 42,
+        max_len: 42,
+        script_kind: ScriptKind::P2wpkh,
+    };
+    assert!(p2wpkh.matches("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"));
+    assert!(p2wpkh.matches("BC1QAR0SRRR7XFKVY5L643LYDNW9RE59GTZZWF5MDQ"));
+    assert!(!p2wpkh.matches("bc1qAR0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"));
+    // `b` is not in the bech32 charset
+    assert!(!p2wpkh.matches("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdb"));
+    assert!(!p2wpkh.matches("tb1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"));
+    assert!(!p2wpkh.is_script_hash());
+}
+
+#[test]
+fn test_utxo_address_script_pubkey() {
+    use crate::btc::{AddressEncoding, ScriptKind, UtxoAddressFormat};
+    use crate::errors::BridgeError;
+
+    let format = |encoding, script_kind| UtxoAddressFormat {
+        encoding,
+        prefix: String::new(),
+        min_len: 0,
+        max_len: 100,
+        script_kind,
+    };
+    let script_pubkey =
+        |format: UtxoAddressFormat, address: &str| format.script_pubkey(address).map(hex::encode);
+
+    let p2pkh = format(AddressEncoding::Base58, ScriptKind::P2pkh);
+    assert_eq!(
+        script_pubkey(p2pkh.clone(), "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2").unwrap(),
+        "76a91477bff20c60e522dfaa3350c39b030a5d004e839a88ac"
+    );
+    // The version of the Zcash addresses is two bytes long
+    assert_eq!(
+        script_pubkey(p2pkh.clone(), "t1Hsc1LR8yKnbbe3twRp88p6vFfC5t7DLbs").unwrap(),
+        format!("76a914{}88ac", "00".repeat(20))
+    );
+    assert_eq!(
+        script_pubkey(p2pkh, "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN3"),
+        Err(BridgeError::InvalidAddress)
+    );
+    assert_eq!(
+        script_pubkey(
+            format(AddressEncoding::Base58, ScriptKind::P2sh),
+            "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy"
+        )
+        .unwrap(),
+        "a914b472a266d0bd89c13706a4132ccfb16f7c3b9fcb87"
+    );
+
+    let segwit = format(AddressEncoding::Bech32, ScriptKind::P2wpkh);
+    assert_eq!(
+        script_pubkey(segwit.clone(), "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq").unwrap(),
+        "0014e8df018c7e326cc253faac7e46cdc51e68542c42"
+    );
+    assert_eq!(
+        script_pubkey(segwit.clone(), "BC1QAR0SRRR7XFKVY5L643LYDNW9RE59GTZZWF5MDQ").unwrap(),
+        "0014e8df018c7e326cc253faac7e46cdc51e68542c42"
+    );
+    // Taproot addresses use the bech32m checksum
+    assert_eq!(
+        script_pubkey(
+            segwit.clone(),
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0"
+        )
+        .unwrap(),
+        "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+    );
+    assert_eq!(
+        script_pubkey(segwit, "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdp"),
+        Err(BridgeError::InvalidAddress)
+    );
+}
+
+#[test]
+fn test_zcash_transparent_outputs() {
+    use crate::btc::{is_zcash_transparent_address, ZecTxOut};
+
+    let output = |script_pubkey: String| ZecTxOut {
+        value: 1000,
+        script_pubkey,
+    };
+    assert!(output(format!("76a914{}88ac", "11".repeat(20))).is_transparent());
+    assert!(output(format!("a914{}87", "11".repeat(20))).is_transparent());
+    // P2WPKH scripts don't exist on Zcash
+    assert!(!output(format!("0014{}", "11".repeat(20))).is_transparent());
+    assert!(!output(format!("76a914{}88", "11".repeat(20))).is_transparent());
+    assert!(!output("not hex".to_string()).is_transparent());
+
+    assert!(is_zcash_transparent_address(
+        "t1Hsc1LR8yKnbbe3twRp88p6vFfC5t7DLbs"
+    ));
+    assert!(!is_zcash_transparent_address(
+        "zs1z7rejlpsa98s2rrrfkwmaxu53e4ue0ulcrw0h4x5g8jl04tak0d3mm47vdtahatqrlkngh9sly"
+    ));
+    assert!(!is_zcash_transparent_address("u1qpatys4zruk99pg59gcscrt7y6akvl9vrhcfyhm9yxvxz7h87q6n8cgrzzpe9zru68uq39uhmlpp5uefxu0su5uqyqfe5zp3tycn0ecl"));
+}
+
+#[test]
+fn test_script_kind() {
+    use crate::btc::ScriptKind;
+
+    let kind = |script_pubkey: String| ScriptKind::from_hex(&script_pubkey);
+    assert_eq!(
+        kind(format!("76a914{}88ac", "11".repeat(20))),
+        ScriptKind::P2pkh
+    );
+    assert_eq!(kind(format!("a914{}87", "11".repeat(20))), ScriptKind::P2sh);
+    assert_eq!(kind(format!("0014{}", "11".repeat(20))), ScriptKind::P2wpkh);
+    assert_eq!(kind(format!("0020{}", "11".repeat(32))), ScriptKind::P2wsh);
+    assert_eq!(kind(format!("5120{}", "11".repeat(32))), ScriptKind::P2tr);
+    assert_eq!(kind("6a0401020304".to_string()), ScriptKind::NullData);
+    // Witness program of a version without a spending rule
+    assert_eq!(
+        kind(format!("5220{}", "11".repeat(32))),
+        ScriptKind::NonStandard
+    );
+    assert_eq!(
+        kind(format!("0014{}", "11".repeat(19))),
+        ScriptKind::NonStandard
+    );
+    assert_eq!(kind("not hex".to_string()), ScriptKind::NonStandard);
+}
+
+#[test]
+fn test_tx_out_validation() {
+    use crate::btc::{TxOut, MAX_STANDARD_SCRIPT_PUBKEY_SIZE};
+    use crate::errors::BridgeError;
+
+    let script_pubkey = format!("0014{}", "11".repeat(20));
+    assert!(TxOut::new(1000, script_pubkey.clone()).is_ok());
+    assert_eq!(
+        TxOut::new(0, script_pubkey).unwrap_err(),
+        BridgeError::ZeroOutputValue
+    );
+    assert_eq!(
+        TxOut::new(1000, "not hex".to_string()).unwrap_err(),
+        BridgeError::InvalidScriptPubkey
+    );
+    assert_eq!(
+        TxOut::new(1000, String::new()).unwrap_err(),
+        BridgeError::InvalidScriptPubkey
+    );
+    assert_eq!(
+        TxOut::new(1000, "6a".repeat(MAX_STANDARD_SCRIPT_PUBKEY_SIZE + 1)).unwrap_err(),
+        BridgeError::ScriptPubkeyTooLarge
+    );
+}
+
+#[test]
+fn test_outpoint_encoding() {
+    use crate::btc::OutPoint;
+
+    let outpoint_str = "abc94fc5b954136a691594c7044bcfa6c6f127cdb0802ac8b97c0117482f2305:7";
+    let outpoint: OutPoint = outpoint_str.parse().unwrap();
+    assert_eq!(outpoint.vout, 7);
+    assert_eq!(outpoint.to_string(), outpoint_str);
+    assert_eq!(
+        serde_json::to_string(&outpoint).unwrap(),
+        format!("\"{outpoint_str}\"")
+    );
+
+    // The txid and vout are stored without the string length prefix and separator
+    assert_eq!(borsh::to_vec(&outpoint).unwrap().len(), 36);
+    assert_eq!(borsh::to_vec(&outpoint_str.to_string()).unwrap().len(), 70);
+
+    assert!("a1b2c3:0".parse::<OutPoint>().is_err());
+    assert!(
+        "abc94fc5b954136a691594c7044bcfa6c6f127cdb0802ac8b97c0117482f2305"
+            .parse::<OutPoint>()
+            .is_err()
+    );
+    // The withdrawal messages with a malformed outpoint are rejected when they are parsed
+    assert!(serde_json::from_str::<OutPoint>("\"a1b2c3:0\"").is_err());
+    assert!(
+        serde_json::from_str::<Vec<OutPoint>>(&format!("[\"{outpoint_str}\", \"a1b2c3:0\"]"))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_utxo_transaction_txid() {
+    use crate::btc::UtxoTransaction;
+
+    // Coinbase transaction of the Bitcoin genesis block
+    let genesis_tx = hex::decode("01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000").unwrap();
+    let transaction = UtxoTransaction::from_bytes(&genesis_tx).unwrap();
+    assert_eq!(
+        hex::encode(transaction.txid),
+        "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"
+    );
+    assert_eq!(transaction.outputs[0].value, 5_000_000_000);
+
+    // The witnesses of a SegWit transaction are not part of its id
+    let segwit_tx = hex::decode("0200000000010101010101010101010101010101010101010101010101010101010101010101010000000000fdffffff01905f010000000000160014e8df018c7e326cc253faac7e46cdc51e68542c4202483030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030303030302102020202020202020202020202020202020202020202020202020202020202020200000000").unwrap();
+    let transaction = UtxoTransaction::from_bytes(&segwit_tx).unwrap();
+    assert_eq!(
+        hex::encode(transaction.txid),
+        "23b3f9055f2d4d98dd166f8d581179377140d5ffac8d50b5d0f7cc369254a5bd"
+    );
+    assert_eq!(
+        transaction.inputs[0].to_string(),
+        format!("{}:0", "01".repeat(32))
+    );
+    assert_eq!(
+        transaction.outputs[0].script_pubkey,
+        "0014e8df018c7e326cc253faac7e46cdc51e68542c42"
+    );
+
+    assert!(UtxoTransaction::from_bytes(&segwit_tx[..segwit_tx.len() - 1]).is_err());
+    assert!(UtxoTransaction::from_bytes(&[segwit_tx.as_slice(), &[0]].concat()).is_err());
+}
+
+#[test]
+fn test_token_receiver_message_borsh() {
+    use crate::btc::{OutPoint, TokenReceiverMessage, TxOut};
+
+    let outpoint: OutPoint = "abc94fc5b954136a691594c7044bcfa6c6f127cdb0802ac8b97c0117482f2305:1"
+        .parse()
+        .unwrap();
+    let message = TokenReceiverMessage::Withdraw {
+        target_btc_address: "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string(),
+        input: vec![outpoint],
+        output: vec![TxOut {
+            value: 1000,
+            script_pubkey: "0014e8df018c7e326cc253faac7e46cdc51e68542c42".to_string(),
+        }],
+        max_gas_fee: Some(U128(500)),
+    };
+
+    let deserialized: TokenReceiverMessage =
+        borsh::from_slice(&borsh::to_vec(&message).unwrap()).unwrap();
+    let TokenReceiverMessage::Withdraw {
+        input,
+        output,
+        max_gas_fee,
+        ..
+    } = deserialized
+    else {
+        panic!("Unexpected message");
+    };
+    assert_eq!(input, vec![outpoint]);
+    assert_eq!(output[0].value, 1000);
+    assert_eq!(max_gas_fee, Some(U128(500)));
+    // The JSON encoding sent to the connectors is unchanged
+    assert!(serde_json::to_string(&message)
+        .unwrap()
+        .starts_with("{\"Withdraw\":{\"target_btc_address\""));
+}
+
+#[test]
+fn test_transfer_trace_id() {
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Eth,
+        origin_nonce: 7,
+    };
+    assert_eq!(transfer_id.trace_id(), transfer_id.trace_id());
+    assert_eq!(
+        transfer_id.trace_id(),
+        keccak256(&borsh::to_vec(&transfer_id).unwrap())
+    );
+    assert_ne!(
+        transfer_id.trace_id(),
+        TransferId {
+            origin_chain: ChainKind::Arb,
+            origin_nonce: 7,
+        }
+        .trace_id()
+    );
+}
+
+#[test]
+fn test_format_and_parse_amount() {
+    assert_eq!(format_amount(150_000_000, UTXO_DECIMALS), "1.5");
+    assert_eq!(format_amount(u128::from(SATS_PER_BTC), UTXO_DECIMALS), "1");
+    assert_eq!(
+        format_amount(1, NEAR_DECIMALS),
+        "0.000000000000000000000001"
+    );
+    assert_eq!(format_amount(0, UTXO_DECIMALS), "0");
+    assert_eq!(format_amount(42, 0), "42");
+
+    assert_eq!(parse_amount("1.5", UTXO_DECIMALS), Ok(150_000_000));
+    assert_eq!(parse_amount("1", NEAR_DECIMALS), Ok(YOCTO_PER_NEAR));
+    assert_eq!(parse_amount("0.00000001", UTXO_DECIMALS), Ok(1));
+    assert_eq!(
+        parse_amount("0.000000001", UTXO_DECIMALS),
+        Err(UnitsError::TooManyDecimals)
+    );
+    assert_eq!(
+        parse_amount("-1", UTXO_DECIMALS),
+        Err(UnitsError::InvalidAmount)
+    );
+    assert_eq!(
+        parse_amount(".5", UTXO_DECIMALS),
+        Err(UnitsError::InvalidAmount)
+    );
+    assert_eq!(
+        parse_amount(&u128::MAX.to_string(), UTXO_DECIMALS),
+        Err(UnitsError::AmountOverflow)
+    );
+}
+
+#[test]
+fn test_scale_amount() {
+    assert_eq!(
+        scale_amount(1, UTXO_DECIMALS, NEAR_DECIMALS),
+        Ok(10_u128.pow(16))
+    );
+    assert_eq!(scale_amount(YOCTO_PER_NEAR + 1, NEAR_DECIMALS, 0), Ok(1));
+    assert_eq!(
+        scale_amount(u128::MAX, 0, 1),
+        Err(UnitsError::AmountOverflow)
+    );
+    assert_eq!(units_per_token(39), Err(UnitsError::DecimalsOverflow));
+    assert_eq!(
+        ChainKind::Doge.native_decimals().map(units_per_token),
+        Some(Ok(u128::from(KOINU_PER_DOGE)))
+    );
+    assert_eq!(ChainKind::Unknown(42).native_decimals(), None);
+}
+
+#[cfg(feature = "test-vectors")]
+#[test]
+fn test_withdraw_msg_vectors_encoding() {
+    use crate::btc::{
+        DogeTokenReceiverMessage, LtcTokenReceiverMessage, TokenReceiverMessage,
+        ZecTokenReceiverMessage,
+    };
+    use crate::test_vectors::WITHDRAW_MSG_VECTORS;
+    use crate::utils::sha256;
+
+    fn reencode<T: serde::Serialize + serde::de::DeserializeOwned>(msg: &str) -> String {
+        serde_json::to_string(&serde_json::from_str::<T>(msg).unwrap()).unwrap()
+    }
+
+    for vector in WITHDRAW_MSG_VECTORS {
+        assert_eq!(hex::encode(sha256(vector.msg.as_bytes())), vector.msg_hash);
+
+        let encoded = match vector.chain_kind {
+            ChainKind::Btc => reencode::<TokenReceiverMessage>(vector.msg),
+            ChainKind::Zcash => reencode::<ZecTokenReceiverMessage>(vector.msg),
+            ChainKind::Ltc => reencode::<LtcTokenReceiverMessage>(vector.msg),
+            ChainKind::Doge => reencode::<DogeTokenReceiverMessage>(vector.msg),
+            chain_kind => panic!("No message type for {chain_kind:?}"),
+        };
+        assert_eq!(encoded, vector.msg);
+    }
+}
+
+#[test]
+fn test_unresolved_recipient_transfer_try_resolve() {
+    use crate::prover_result::UnresolvedRecipientTransferMessage;
+
+    let sender = OmniAddress::from_str("eth:0x3ee430c1e9bc3f22a1ddb8e5c24e6a743fdb3b43").unwrap();
+    let transfer = UnresolvedRecipientTransferMessage {
+        origin_nonce: 1,
+        token: sender.clone(),
+        amount: U128(100),
+        raw_recipient: "near:alice.near".to_string(),
+        fee: Fee::default(),
+        sender: sender.clone(),
+        msg: String::new(),
+        emitter_address: sender,
+    };
+
+    let init_transfer = transfer.clone().try_resolve().unwrap();
+    assert_eq!(
+        init_transfer.recipient,
+        OmniAddress::Near("alice.near".parse().unwrap())
+    );
+    assert_eq!(init_transfer.amount, U128(100));
+
+    let unresolved = UnresolvedRecipientTransferMessage {
+        raw_recipient: "near:invalid..account".to_string(),
+        ..transfer
+    };
+    assert_eq!(
+        unresolved.try_resolve().unwrap_err().raw_recipient,
+        "near:invalid..account"
+    );
+}
@@ -0,0 +1,108 @@
+use core::fmt;
+
+use crate::ChainKind;
+
+pub const NEAR_DECIMALS: u8 = 24;
+pub const YOCTO_PER_NEAR: u128 = 1_000_000_000_000_000_000_000_000;
+
+pub const EVM_NATIVE_DECIMALS: u8 = 18;
+pub const SOL_DECIMALS: u8 = 9;
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// Decimals of the native tokens of the UTXO chains supported by the bridge.
+pub const UTXO_DECIMALS: u8 = 8;
+pub const SATS_PER_BTC: u64 = 100_000_000;
+pub const ZATOSHIS_PER_ZEC: u64 = 100_000_000;
+pub const LITOSHIS_PER_LTC: u64 = 100_000_000;
+pub const KOINU_PER_DOGE: u64 = 100_000_000;
+
+/// Denominator of the fees and ratios expressed in basis points.
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitsError {
+    DecimalsOverflow,
+    AmountOverflow,
+    InvalidAmount,
+    TooManyDecimals,
+}
+
+impl UnitsError {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::DecimalsOverflow => "ERR_DECIMALS_OVERFLOW",
+            Self::AmountOverflow => "ERR_AMOUNT_OVERFLOW",
+            Self::InvalidAmount => "ERR_INVALID_AMOUNT",
+            Self::TooManyDecimals => "ERR_TOO_MANY_DECIMALS",
+        }
+    }
+}
+
+impl fmt::Display for UnitsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl ChainKind {
+    /// Decimals of the native token of the chain, `None` for the chains unknown to this version.
+    pub const fn native_decimals(&self) -> Option<u8> {
+        match self.normalized() {
+            Self::Near => Some(NEAR_DECIMALS),
+            Self::Eth | Self::Arb | Self::Base | Self::Bnb | Self::Pol => Some(EVM_NATIVE_DECIMALS),
+            Self::Sol => Some(SOL_DECIMALS),
+            Self::Btc | Self::Zcash | Self::Ltc | Self::Doge => Some(UTXO_DECIMALS),
+            Self::Unknown(_) => None,
+        }
+    }
+}
+
+/// Returns `10^decimals`, the number of minimal units in one whole token.
+pub fn units_per_token(decimals: u8) -> Result<u128, UnitsError> {
+    10_u128
+        .checked_pow(u32::from(decimals))
+        .ok_or(UnitsError::DecimalsOverflow)
+}
+
+/// Converts an amount between two precisions of the same token. Scaling down truncates the
+/// units below the target precision.
+pub fn scale_amount(amount: u128, from_decimals: u8, to_decimals: u8) -> Result<u128, UnitsError> {
+    if from_decimals <= to_decimals {
+        amount
+            .checked_mul(units_per_token(to_decimals - from_decimals)?)
+            .ok_or(UnitsError::AmountOverflow)
+    } else {
+        Ok(amount / units_per_token(from_decimals - to_decimals)?)
+    }
+}
+
+/// Formats the amount in minimal units as a decimal number of whole tokens, without trailing
+/// zeros, e.g. `150_000_000` with 8 decimals is `1.5`.
+pub fn format_amount(amount: u128, decimals: u8) -> String {
+    let decimals = usize::from(decimals);
+    let digits = format!("{amount:0>width$}", width = decimals + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
+
+/// Parses a decimal number of whole tokens into minimal units, the inverse of `format_amount`.
+pub fn parse_amount(amount: &str, decimals: u8) -> Result<u128, UnitsError> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    let is_digits = |part: &str| part.bytes().all(|c| c.is_ascii_digit());
+    if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+        return Err(UnitsError::InvalidAmount);
+    }
+    let decimals = usize::from(decimals);
+    if fraction.len() > decimals {
+        return Err(UnitsError::TooManyDecimals);
+    }
+
+    format!("{whole}{fraction:0<decimals$}")
+        .parse()
+        .map_err(|_| UnitsError::AmountOverflow)
+}